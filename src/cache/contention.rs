@@ -0,0 +1,132 @@
+//! Wait and operation timings for `agpm install --concurrency-report`.
+//!
+//! With a high `--max-parallel`, installs can slow down because tasks queue on
+//! the cache's locks rather than on the network or disk. When enabled, the
+//! [`ContentionTracker`] of a [`Cache`](super::Cache) adds up, per source, how
+//! long tasks waited on each kind of lock and how long the work itself took:
+//!
+//! - **cache lock**: waiting for a [`CacheLock`](super::lock::CacheLock) file lock
+//! - **fetch lock**: waiting for another task's or process's fetch of the same repository
+//! - **pending worktree**: waiting for another task to finish creating a worktree
+//! - **network**: cloning and fetching repositories
+//! - **disk**: creating worktrees
+//!
+//! Tracking is off by default, so uninstrumented runs only pay for an atomic load
+//! at each measuring point.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// What a task spent time on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Timing {
+    /// Waiting for a cross-process cache lock
+    CacheLock,
+    /// Waiting for the fetch lock of a repository
+    FetchLock,
+    /// Waiting for a worktree another task is creating
+    PendingWorktree,
+    /// Cloning or fetching a repository
+    Network,
+    /// Creating a worktree
+    Disk,
+}
+
+/// Accumulated timings of one source.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SourceTimings {
+    pub cache_lock: Duration,
+    pub fetch_lock: Duration,
+    pub pending_worktree: Duration,
+    pub network: Duration,
+    pub disk: Duration,
+}
+
+impl SourceTimings {
+    /// Total time spent waiting on locks and pending worktrees.
+    #[must_use]
+    pub fn waiting(&self) -> Duration {
+        self.cache_lock + self.fetch_lock + self.pending_worktree
+    }
+
+    /// The largest contributor to this source's time, as shown in the report.
+    #[must_use]
+    pub fn bottleneck(&self) -> &'static str {
+        let waiting = self.waiting();
+        if waiting > self.network && waiting > self.disk {
+            "lock contention"
+        } else if self.disk > self.network {
+            "disk"
+        } else {
+            "network"
+        }
+    }
+}
+
+/// Per-source timings collected while enabled.
+#[derive(Debug, Default)]
+pub struct ContentionTracker {
+    enabled: AtomicBool,
+    timings: Mutex<BTreeMap<String, SourceTimings>>,
+}
+
+impl ContentionTracker {
+    /// Start collecting timings.
+    pub fn enable(&self) {
+        self.enabled.store(true, Ordering::Relaxed);
+    }
+
+    /// The start of a measured span, or `None` when tracking is disabled.
+    #[must_use]
+    pub fn start(&self) -> Option<Instant> {
+        self.enabled.load(Ordering::Relaxed).then(Instant::now)
+    }
+
+    /// Add the time since `start` to `source`'s `timing`.
+    pub fn record(&self, source: &str, timing: Timing, start: Option<Instant>) {
+        let Some(start) = start else {
+            return;
+        };
+        let elapsed = start.elapsed();
+        let mut timings = self.timings.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        let entry = timings.entry(source.to_string()).or_default();
+        let total = match timing {
+            Timing::CacheLock => &mut entry.cache_lock,
+            Timing::FetchLock => &mut entry.fetch_lock,
+            Timing::PendingWorktree => &mut entry.pending_worktree,
+            Timing::Network => &mut entry.network,
+            Timing::Disk => &mut entry.disk,
+        };
+        *total += elapsed;
+    }
+
+    /// The timings collected so far, by source name.
+    #[must_use]
+    pub fn timings(&self) -> BTreeMap<String, SourceTimings> {
+        self.timings.lock().unwrap_or_else(std::sync::PoisonError::into_inner).clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records_only_when_enabled() {
+        let tracker = ContentionTracker::default();
+        tracker.record("community", Timing::Network, tracker.start());
+        assert!(tracker.timings().is_empty());
+
+        tracker.enable();
+        let start = Instant::now().checked_sub(Duration::from_millis(50));
+        tracker.record("community", Timing::CacheLock, start);
+        tracker.record("community", Timing::Network, Some(Instant::now()));
+
+        let timings = &tracker.timings()["community"];
+        assert!(timings.cache_lock >= Duration::from_millis(50));
+        assert_eq!(timings.waiting(), timings.cache_lock);
+        assert_eq!(timings.bottleneck(), "lock contention");
+    }
+}
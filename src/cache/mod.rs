@@ -217,10 +217,12 @@
 //! See [`crate::manifest`] for manifest handling, [`crate::lockfile`] for
 //! lockfile management, and [`installer::context::read_with_cache_retry`] for cache coherency retry logic.
 
+use self::contention::Timing;
 use crate::core::error::AgpmError;
 use crate::core::file_error::{FileOperation, FileResultExt};
-use crate::git::GitRepo;
 use crate::git::command_builder::GitCommand;
+use crate::git::{CloneStrategy, GitRepo};
+use crate::manifest::SourceMap;
 use crate::utils::fs;
 use crate::utils::security::validate_path_security;
 use anyhow::{Context, Result};
@@ -309,6 +311,10 @@ struct WorktreeRecord {
     version: String,
     path: PathBuf,
     last_used: u64,
+    /// Registered by `agpm cache import-worktree`; the directory is outside
+    /// the cache and never created or removed by AGPM
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    imported: bool,
 }
 
 impl WorktreeRegistry {
@@ -329,6 +335,8 @@ impl WorktreeRegistry {
             .unwrap_or_else(|_| Duration::from_secs(0))
             .as_secs();
 
+        let imported =
+            self.entries.get(&key).is_some_and(|record| record.imported && record.path == path);
         self.entries.insert(
             key,
             WorktreeRecord {
@@ -336,6 +344,7 @@ impl WorktreeRegistry {
                 version,
                 path,
                 last_used: timestamp,
+                imported,
             },
         );
     }
@@ -366,12 +375,75 @@ impl WorktreeRegistry {
     }
 }
 
+/// Disk usage attributed to a single cached source repository.
+///
+/// Sources are identified by the `owner_repo` directory name shared by the
+/// bare repository (`sources/owner_repo.git`) and its worktrees
+/// (`worktrees/owner_repo_<sha>`). Reported by `agpm cache stats`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SourceCacheStats {
+    /// Directory name of the source (`owner_repo`)
+    pub source: String,
+    /// Size of the bare repository in bytes (0 if only worktrees remain)
+    pub bare_repo_size: u64,
+    /// Part of `bare_repo_size` taken by submodule repositories checked out
+    /// for sources with `submodules = true`
+    pub submodule_size: u64,
+    /// Number of worktrees checked out from this source
+    pub worktree_count: usize,
+    /// Combined size of all worktrees in bytes
+    pub worktree_size: u64,
+    /// Most recent worktree use recorded in the registry (seconds since the Unix epoch)
+    pub last_used: Option<u64>,
+}
+
+/// A worktree checked out from a cached source repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedWorktree {
+    /// Path to the worktree directory (`worktrees/owner_repo_<sha8>`)
+    pub path: PathBuf,
+    /// Abbreviated commit SHA the worktree is checked out at
+    pub sha: String,
+    /// Last use recorded in the registry (seconds since the Unix epoch)
+    pub last_used: Option<u64>,
+}
+
+/// A cached bare repository that failed `git fsck`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorruptSource {
+    /// Directory name of the source (`owner_repo`)
+    pub source: String,
+    /// Path to the bare repository
+    pub path: PathBuf,
+    /// Diagnostics reported by Git
+    pub error: String,
+}
+
+/// How a source's repository was obtained during this command.
+///
+/// When a source is used several times, the status records the most network
+/// activity seen, in the order of the variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SourceFetchStatus {
+    /// A local directory, read in place
+    Local,
+    /// The cached repository was used without contacting the remote
+    Cached,
+    /// Fetching failed, so the cached repository was used as it was
+    OfflineSkipped,
+    /// The repository was cloned or fetched from the remote
+    Fetched,
+}
+
 /// File-based locking mechanism for cache operations
 ///
 /// This module provides thread-safe and process-safe locking for cache
 /// operations through OS-level file locks, ensuring data consistency
 /// when multiple AGPM processes access the same cache directory.
+pub mod contention;
 pub mod lock;
+pub use contention::{ContentionTracker, SourceTimings};
 pub use lock::CacheLock;
 
 /// Git repository cache for efficient resource management
@@ -454,6 +526,29 @@ pub struct Cache {
     /// AGPM runs. Tracks last-used timestamps and paths so we can validate
     /// and clean up cached worktrees without recreating them unnecessarily.
     worktree_registry: Arc<Mutex<WorktreeRegistry>>,
+
+    /// Sources (by `owner_repo` cache key) whose worktrees must have their Git
+    /// submodules initialized, failing the checkout otherwise. Populated from
+    /// `submodules = true` in the manifest's `[sources]` section; other
+    /// sources only get a best-effort initialization.
+    submodule_sources: Arc<RwLock<HashSet<String>>>,
+
+    /// Clone strategies of sources (by `owner_repo` cache key) declared with
+    /// `clone_depth` or `resumable = true`. Other sources use a regular clone.
+    clone_strategies: Arc<RwLock<HashMap<String, CloneStrategy>>>,
+
+    /// Replacement URLs by source name, from the manifest's `[replace]`
+    /// section. Repositories of these sources are fetched from the
+    /// replacement instead of the URL passed in by callers.
+    replaced_sources: Arc<RwLock<HashMap<String, String>>>,
+
+    /// How each source (by name) was obtained during this command, reported
+    /// by `agpm install --report`.
+    source_fetches: Arc<RwLock<HashMap<String, SourceFetchStatus>>>,
+
+    /// Lock wait and operation timings by source name, collected for
+    /// `agpm install --concurrency-report` once enabled.
+    contention: Arc<ContentionTracker>,
 }
 
 impl Clone for Cache {
@@ -464,6 +559,11 @@ impl Clone for Cache {
             fetch_locks: Arc::clone(&self.fetch_locks),
             fetched_repos: Arc::clone(&self.fetched_repos),
             worktree_registry: Arc::clone(&self.worktree_registry),
+            submodule_sources: Arc::clone(&self.submodule_sources),
+            clone_strategies: Arc::clone(&self.clone_strategies),
+            replaced_sources: Arc::clone(&self.replaced_sources),
+            source_fetches: Arc::clone(&self.source_fetches),
+            contention: Arc::clone(&self.contention),
         }
     }
 }
@@ -563,6 +663,11 @@ impl Cache {
             fetch_locks: Arc::new(DashMap::new()),
             fetched_repos: Arc::new(RwLock::new(HashSet::new())),
             worktree_registry: Arc::new(Mutex::new(registry)),
+            submodule_sources: Arc::new(RwLock::new(HashSet::new())),
+            clone_strategies: Arc::new(RwLock::new(HashMap::new())),
+            replaced_sources: Arc::new(RwLock::new(HashMap::new())),
+            source_fetches: Arc::new(RwLock::new(HashMap::new())),
+            contention: Arc::new(ContentionTracker::default()),
         })
     }
 
@@ -615,6 +720,11 @@ impl Cache {
             fetch_locks: Arc::new(DashMap::new()),
             fetched_repos: Arc::new(RwLock::new(HashSet::new())),
             worktree_registry: Arc::new(Mutex::new(registry)),
+            submodule_sources: Arc::new(RwLock::new(HashSet::new())),
+            clone_strategies: Arc::new(RwLock::new(HashMap::new())),
+            replaced_sources: Arc::new(RwLock::new(HashMap::new())),
+            source_fetches: Arc::new(RwLock::new(HashMap::new())),
+            contention: Arc::new(ContentionTracker::default()),
         })
     }
 
@@ -688,6 +798,108 @@ impl Cache {
         &self.dir
     }
 
+    /// Require Git submodules in worktrees checked out from this source.
+    ///
+    /// Applies to worktrees created or reused after the call, for every source
+    /// name that shares the repository URL. Without this, submodules are still
+    /// initialized on a best-effort basis but failures are ignored.
+    pub async fn enable_submodules(&self, url: &str) {
+        let (owner, repo) =
+            crate::git::parse_git_url(url).unwrap_or(("direct".to_string(), "repo".to_string()));
+        self.submodule_sources.write().await.insert(format!("{owner}_{repo}"));
+    }
+
+    /// Apply per-source settings from the manifest's `[sources]` section.
+    ///
+    /// Enables submodule initialization (see [`Self::enable_submodules`]) for
+    /// sources declared with `submodules = true`, and records the clone
+    /// strategy of sources with `clone_depth` or `resumable = true`.
+    pub async fn configure_sources(&self, sources: &SourceMap) {
+        for (name, url) in sources {
+            let options = sources.options(name);
+            let url = crate::manifest::expand_url(url).unwrap_or_else(|_| url.clone());
+            if options.submodules {
+                self.enable_submodules(&url).await;
+            }
+            let strategy = options.clone_strategy();
+            if strategy != CloneStrategy::default() {
+                let (owner, repo) = crate::git::parse_git_url(&url)
+                    .unwrap_or(("direct".to_string(), "repo".to_string()));
+                self.clone_strategies.write().await.insert(format!("{owner}_{repo}"), strategy);
+            }
+        }
+    }
+
+    /// The clone strategy configured for the repository at `url`.
+    async fn clone_strategy(&self, url: &str) -> CloneStrategy {
+        let (owner, repo) =
+            crate::git::parse_git_url(url).unwrap_or(("direct".to_string(), "repo".to_string()));
+        self.clone_strategies
+            .read()
+            .await
+            .get(&format!("{owner}_{repo}"))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Fetch the named sources from replacement URLs instead of their own.
+    ///
+    /// Callers keep passing the declared URL, which is what lockfiles record;
+    /// [`Self::get_or_clone_source`] and [`Self::get_or_create_worktree_for_sha`]
+    /// substitute the replacement for every source name in `replacements`.
+    pub async fn replace_sources(&self, replacements: HashMap<String, String>) {
+        *self.replaced_sources.write().await = replacements;
+    }
+
+    /// Record how source `name` was obtained, keeping the most network activity seen.
+    async fn record_fetch(&self, name: &str, status: SourceFetchStatus) {
+        let mut fetches = self.source_fetches.write().await;
+        let recorded = fetches.entry(name.to_string()).or_insert(status);
+        *recorded = (*recorded).max(status);
+    }
+
+    /// How each source used by this cache instance was obtained, by source name.
+    pub async fn source_fetches(&self) -> HashMap<String, SourceFetchStatus> {
+        self.source_fetches.read().await.clone()
+    }
+
+    /// Start timing lock waits, fetches and worktree creation per source.
+    pub fn enable_concurrency_report(&self) {
+        self.contention.enable();
+    }
+
+    /// Timings collected since [`Self::enable_concurrency_report`], by source name.
+    #[must_use]
+    pub fn concurrency_report(&self) -> std::collections::BTreeMap<String, SourceTimings> {
+        self.contention.timings()
+    }
+
+    /// The URL to fetch source `name` from: its replacement, or `url`.
+    async fn effective_url(&self, name: &str, url: &str) -> String {
+        self.replaced_sources.read().await.get(name).map_or_else(|| url.to_string(), Clone::clone)
+    }
+
+    /// Initialize submodules in a worktree if its source has them enabled.
+    async fn init_submodules_if_enabled(
+        &self,
+        source_key: &str,
+        worktree_path: &Path,
+        context: Option<&str>,
+    ) -> Result<()> {
+        if !self.submodule_sources.read().await.contains(source_key) {
+            return Ok(());
+        }
+
+        if let Some(ctx) = context {
+            tracing::debug!(
+                target: "git",
+                "({ctx}) Initializing submodules in {}",
+                worktree_path.display()
+            );
+        }
+        GitRepo::new(worktree_path).update_submodules().await
+    }
+
     /// Get the worktree path for a specific URL and commit SHA.
     ///
     /// This method constructs the expected worktree directory path based on the cache's
@@ -730,6 +942,21 @@ impl Cache {
         Ok(self.dir.join("worktrees").join(format!("{owner}_{repo}_{sha_short}")))
     }
 
+    /// Get the bare repository path for a URL without touching the filesystem.
+    ///
+    /// Uses the same `sources/owner_repo.git` naming as
+    /// [`get_or_clone_source`](Self::get_or_clone_source). The repository may not
+    /// have been cloned yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL cannot be parsed as a Git URL.
+    pub fn get_bare_repo_path(&self, url: &str) -> Result<PathBuf> {
+        let (owner, repo) =
+            crate::git::parse_git_url(url).map_err(|e| anyhow::anyhow!("Invalid Git URL: {e}"))?;
+        Ok(self.dir.join("sources").join(format!("{owner}_{repo}.git")))
+    }
+
     /// Gets or clones a source repository, ensuring it's available in the cache.
     ///
     /// This is the primary method for source repository management. It handles both
@@ -846,13 +1073,42 @@ impl Cache {
         url: &str,
         version: Option<&str>,
     ) -> Result<PathBuf> {
-        self.get_or_clone_source_impl(name, url, version).await
+        let url = self.effective_url(name, url).await;
+        // Sources with a registered provider are not Git repositories
+        if let Some(provider) = crate::source::provider::for_url(&url) {
+            return provider.fetch(name, &url).await;
+        }
+        self.get_or_clone_source_impl(name, &url, version, None).await
+    }
+
+    /// Like [`get_or_clone_source`](Self::get_or_clone_source), but only fetches `refs`.
+    ///
+    /// Resolving pinned versions only needs the tags, branches and commits they
+    /// name, so an existing repository fetches just those instead of every ref.
+    /// New repositories are still cloned in full.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the repository cannot be cloned or the source is
+    /// not accessible.
+    pub async fn get_or_clone_source_for_refs(
+        &self,
+        name: &str,
+        url: &str,
+        refs: &[String],
+    ) -> Result<PathBuf> {
+        let url = self.effective_url(name, url).await;
+        if let Some(provider) = crate::source::provider::for_url(&url) {
+            return provider.fetch(name, &url).await;
+        }
+        self.get_or_clone_source_impl(name, &url, None, Some(refs)).await
     }
 
     /// Clean up a worktree after use (fast version).
     ///
     /// This just removes the worktree directory without calling git.
     /// Git will clean up its internal references when `git worktree prune` is called.
+    /// Checkouts registered with [`Cache::import_worktree`] are left untouched.
     ///
     /// # Parameters
     ///
@@ -864,6 +1120,12 @@ impl Cache {
     /// - Unable to remove worktree directory
     /// - Unable to update worktree registry
     pub async fn cleanup_worktree(&self, worktree_path: &Path) -> Result<()> {
+        // Imported checkouts belong to the user; AGPM never removes them
+        if self.is_imported_worktree(worktree_path).await {
+            tracing::debug!("Keeping imported worktree {}", worktree_path.display());
+            return Ok(());
+        }
+
         // Just remove the directory - don't call git worktree remove
         // This is much faster and git will clean up its references later
         if worktree_path.exists() {
@@ -932,6 +1194,124 @@ impl Cache {
         Ok(())
     }
 
+    /// Key of the worktree of `owner/repo` at `sha` in the worktree cache and registry.
+    fn worktree_cache_key(&self, owner: &str, repo: &str, sha: &str) -> String {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+        let mut hasher = DefaultHasher::new();
+        self.dir.hash(&mut hasher);
+        let cache_dir_hash = format!("{:x}", hasher.finish());
+        format!("{}:{owner}_{repo}:{sha}", &cache_dir_hash[..8])
+    }
+
+    /// The imported checkout registered for `cache_key`, if it is still at `sha`.
+    ///
+    /// A checkout that was deleted or moved to another commit is dropped from
+    /// the registry, so the worktree is created in the cache as usual.
+    async fn imported_worktree(&self, cache_key: &str, sha: &str) -> Option<PathBuf> {
+        let path = {
+            let registry = self.worktree_registry.lock().await;
+            let record = registry.entries.get(cache_key).filter(|record| record.imported)?;
+            record.path.clone()
+        };
+
+        let head = GitRepo::new(&path).get_current_commit().await.ok();
+        if head.as_deref() == Some(sha) {
+            return Some(path);
+        }
+        tracing::warn!(
+            "Imported worktree {} is missing or no longer at commit {}, creating a cached worktree instead",
+            path.display(),
+            &sha[..8]
+        );
+        let _ = self.remove_worktree_record_by_path(&path).await;
+        None
+    }
+
+    /// Whether `path` is a checkout registered with [`Cache::import_worktree`].
+    pub async fn is_imported_worktree(&self, path: &Path) -> bool {
+        let registry = self.worktree_registry.lock().await;
+        registry.entries.values().any(|record| record.imported && record.path == path)
+    }
+
+    /// Register an existing checkout of `url` as the worktree for its commit.
+    ///
+    /// The checkout at `path` must be a non-bare Git working tree outside the
+    /// cache directory. Its HEAD must be `expected_sha` when one is given (a
+    /// full or abbreviated commit). Later installs that need `url` at that
+    /// commit read from `path` instead of cloning and checking out; AGPM never
+    /// modifies or removes the directory.
+    ///
+    /// Returns the registered commit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path is not a Git checkout, lies inside the
+    /// cache, or is not at `expected_sha`.
+    pub async fn import_worktree(
+        &self,
+        url: &str,
+        path: &Path,
+        expected_sha: Option<&str>,
+    ) -> Result<String> {
+        if let Some(expected) = expected_sha
+            && (expected.len() < 7 || !expected.chars().all(|c| c.is_ascii_hexdigit()))
+        {
+            anyhow::bail!("'{expected}' is not a commit SHA (at least 7 hex characters)");
+        }
+        let path = path
+            .canonicalize()
+            .with_context(|| format!("Cannot import {}: directory not found", path.display()))?;
+        if !path.is_dir() {
+            anyhow::bail!("Cannot import {}: not a directory", path.display());
+        }
+        self.ensure_cache_dir().await?;
+        let cache_dir = self.dir.canonicalize().unwrap_or_else(|_| self.dir.clone());
+        if path.starts_with(&cache_dir) {
+            anyhow::bail!(
+                "Cannot import {}: directories inside the AGPM cache are managed by AGPM",
+                path.display()
+            );
+        }
+
+        let repo = GitRepo::new(&path);
+        if !path.join(".git").exists() || repo.is_bare().await.unwrap_or(true) {
+            anyhow::bail!("Cannot import {}: not a Git working tree", path.display());
+        }
+        let head = repo
+            .get_current_commit()
+            .await
+            .with_context(|| format!("Failed to read HEAD of {}", path.display()))?;
+        if let Some(expected) = expected_sha
+            && !head.starts_with(&expected.to_ascii_lowercase())
+        {
+            anyhow::bail!(
+                "{} is checked out at {}, not {expected}",
+                path.display(),
+                &head[..8.min(head.len())]
+            );
+        }
+
+        let (owner, repo_name) =
+            crate::git::parse_git_url(url).map_err(|e| anyhow::anyhow!("Invalid Git URL: {e}"))?;
+        let cache_key = self.worktree_cache_key(&owner, &repo_name, &head);
+        {
+            let mut registry = self.worktree_registry.lock().await;
+            registry.update(
+                cache_key.clone(),
+                url.to_string(),
+                head[..8].to_string(),
+                path.clone(),
+            );
+            if let Some(record) = registry.entries.get_mut(&cache_key) {
+                record.imported = true;
+            }
+            registry.persist(&self.registry_path()).await?;
+        }
+        self.worktree_cache.write().await.remove(&cache_key);
+        Ok(head)
+    }
+
     /// Get or create a worktree for a specific commit SHA.
     ///
     /// **Important**: This function uses lightweight verification to avoid deadlocks.
@@ -990,6 +1370,10 @@ impl Cache {
         sha: &str,
         context: Option<&str>,
     ) -> Result<PathBuf> {
+        if let Some(provider) = crate::source::provider::for_url(url) {
+            return provider.checkout(name, url, sha).await;
+        }
+
         // Validate SHA format
         if sha.len() != 40 || !sha.chars().all(|c| c.is_ascii_hexdigit()) {
             return Err(anyhow::anyhow!(
@@ -997,6 +1381,9 @@ impl Cache {
             ));
         }
 
+        let effective_url = self.effective_url(name, url).await;
+        let url = effective_url.as_str();
+
         // Check if this is a local path
         let is_local_path = crate::utils::is_local_path(url);
         if is_local_path {
@@ -1013,14 +1400,17 @@ impl Cache {
         // Create SHA-based cache key
         // Using first 8 chars of SHA for directory name (like Git does)
         let sha_short = &sha[..8];
-        let cache_dir_hash = {
-            use std::collections::hash_map::DefaultHasher;
-            use std::hash::{Hash, Hasher};
-            let mut hasher = DefaultHasher::new();
-            self.dir.hash(&mut hasher);
-            format!("{:x}", hasher.finish())[..8].to_string()
-        };
-        let cache_key = format!("{cache_dir_hash}:{owner}_{repo}:{sha}");
+        let cache_key = self.worktree_cache_key(&owner, &repo, sha);
+
+        if let Some(imported) = self.imported_worktree(&cache_key, sha).await {
+            self.record_worktree_usage(&cache_key, name, sha_short, &imported).await?;
+            self.record_fetch(name, SourceFetchStatus::Cached).await;
+            tracing::debug!(
+                "Using imported worktree {} for {url} @ {sha_short}",
+                imported.display()
+            );
+            return Ok(imported);
+        }
 
         // Check if we already have a worktree for this SHA
         let mut should_create_worktree = false;
@@ -1034,6 +1424,7 @@ impl Cache {
                             drop(cache_read);
                             self.record_worktree_usage(&cache_key, name, sha_short, &cached_path)
                                 .await?;
+                            self.record_fetch(name, SourceFetchStatus::Cached).await;
 
                             if let Some(ctx) = context {
                                 tracing::debug!(
@@ -1059,7 +1450,9 @@ impl Cache {
                             );
                         }
                         drop(cache_read);
+                        let waited = self.contention.start();
                         tokio::time::sleep(Duration::from_millis(100)).await;
+                        self.contention.record(name, Timing::PendingWorktree, waited);
                     }
                     None => {
                         should_create_worktree = true;
@@ -1074,11 +1467,16 @@ impl Cache {
             let mut cache_write = self.worktree_cache.write().await;
             match cache_write.get(&cache_key) {
                 Some(WorktreeState::Ready(cached_path)) if cached_path.exists() => {
-                    return Ok(cached_path.clone());
+                    let cached_path = cached_path.clone();
+                    drop(cache_write);
+                    self.record_fetch(name, SourceFetchStatus::Cached).await;
+                    return Ok(cached_path);
                 }
                 Some(WorktreeState::Pending) => {
                     drop(cache_write);
+                    let waited = self.contention.start();
                     tokio::time::sleep(Duration::from_millis(50)).await;
+                    self.contention.record(name, Timing::PendingWorktree, waited);
                 }
                 _ => {
                     cache_write.insert(cache_key.clone(), WorktreeState::Pending);
@@ -1090,12 +1488,21 @@ impl Cache {
         // Get bare repository (fetches if needed)
         let bare_repo_dir = self.dir.join("sources").join(format!("{owner}_{repo}.git"));
 
-        if bare_repo_dir.exists() {
-            // Fetch to ensure we have the SHA
-            self.fetch_with_hybrid_lock(&bare_repo_dir, context).await?;
+        if bare_repo_dir.exists() && !GitRepo::is_partial_clone(&bare_repo_dir) {
+            // Fetch the SHA unless the repository already has it
+            let refs = [sha.to_string()];
+            let status =
+                if self.fetch_with_hybrid_lock(name, &bare_repo_dir, context, Some(&refs)).await? {
+                    SourceFetchStatus::Fetched
+                } else {
+                    SourceFetchStatus::Cached
+                };
+            self.record_fetch(name, status).await;
         } else {
             let lock_name = format!("{owner}_{repo}");
+            let waited = self.contention.start();
             let _lock = CacheLock::acquire(&self.dir, &lock_name).await?;
+            self.contention.record(name, Timing::CacheLock, waited);
 
             if let Some(parent) = bare_repo_dir.parent() {
                 tokio::fs::create_dir_all(parent).await.with_file_context(
@@ -1106,16 +1513,20 @@ impl Cache {
                 )?;
             }
 
-            if !bare_repo_dir.exists() {
+            if !bare_repo_dir.exists() || GitRepo::is_partial_clone(&bare_repo_dir) {
                 if let Some(ctx) = context {
                     tracing::debug!("📦 ({ctx}) Cloning repository {url}...");
                 } else {
                     tracing::debug!("📦 Cloning repository {url} to cache...");
                 }
 
-                GitRepo::clone_bare_with_context(url, &bare_repo_dir, context).await?;
+                let strategy = self.clone_strategy(url).await;
+                let cloning = self.contention.start();
+                GitRepo::clone_bare_with_strategy(url, &bare_repo_dir, strategy, context).await?;
+                self.contention.record(name, Timing::Network, cloning);
                 Self::configure_connection_pooling(&bare_repo_dir).await.ok();
             }
+            self.record_fetch(name, SourceFetchStatus::Fetched).await;
         }
 
         let bare_repo = GitRepo::new(&bare_repo_dir);
@@ -1125,10 +1536,20 @@ impl Cache {
 
         // Acquire worktree creation lock
         let worktree_lock_name = format!("worktree-{owner}-{repo}-{sha_short}");
+        let waited = self.contention.start();
         let _worktree_lock = CacheLock::acquire(&self.dir, &worktree_lock_name).await?;
+        self.contention.record(name, Timing::CacheLock, waited);
 
         // Re-check after lock
         if worktree_path.exists() {
+            // Idempotent; covers worktrees created before submodules were enabled
+            if let Err(e) = self
+                .init_submodules_if_enabled(&format!("{owner}_{repo}"), &worktree_path, context)
+                .await
+            {
+                self.worktree_cache.write().await.remove(&cache_key);
+                return Err(e);
+            }
             let mut cache_write = self.worktree_cache.write().await;
             cache_write.insert(cache_key.clone(), WorktreeState::Ready(worktree_path.clone()));
             self.record_worktree_usage(&cache_key, name, sha_short, &worktree_path).await?;
@@ -1155,11 +1576,15 @@ impl Cache {
         // Hold the lock through cache update to prevent git state corruption
         // when multiple worktrees are created concurrently for the same repo
         let bare_repo_lock_name = format!("bare-repo-{owner}_{repo}");
+        let waited = self.contention.start();
         let _bare_repo_lock = CacheLock::acquire(&self.dir, &bare_repo_lock_name).await?;
+        self.contention.record(name, Timing::CacheLock, waited);
 
         // Create worktree using SHA directly
+        let creating = self.contention.start();
         let worktree_result =
             bare_repo.create_worktree_with_context(&worktree_path, Some(sha), context).await;
+        self.contention.record(name, Timing::Disk, creating);
 
         // Keep lock held until cache is updated to ensure git state is fully settled
         match worktree_result {
@@ -1206,6 +1631,14 @@ impl Cache {
                     worktree_path.display(),
                     &sha[..8]
                 );
+
+                if let Err(e) = self
+                    .init_submodules_if_enabled(&format!("{owner}_{repo}"), &worktree_path, context)
+                    .await
+                {
+                    self.worktree_cache.write().await.remove(&cache_key);
+                    return Err(e);
+                }
                 // At this point, worktree is marked Ready. File access retries will handle any
                 // remaining cache coherency issues through read_with_cache_retry functions.
 
@@ -1235,7 +1668,7 @@ impl Cache {
     /// * `name` - The name of the source (used for cache directory naming)
     /// * `url` - The Git repository URL or local path
     /// * `version` - Optional specific version/tag/branch to checkout
-    /// * `force_refresh` - If true, ignore cached version and clone/fetch fresh
+    /// * `refs` - Only fetch these refs into an existing repository, or all if `None`
     ///
     /// # Returns
     ///
@@ -1245,6 +1678,7 @@ impl Cache {
         name: &str,
         url: &str,
         version: Option<&str>,
+        refs: Option<&[String]>,
     ) -> Result<PathBuf> {
         // Check if this is a local path (not a git repository URL)
         let is_local_path = crate::utils::is_local_path(url);
@@ -1270,15 +1704,18 @@ impl Cache {
                 eprintln!("Warning: Version constraints are ignored for local paths");
             }
 
+            self.record_fetch(name, SourceFetchStatus::Local).await;
             return Ok(canonical_path);
         }
 
         self.ensure_cache_dir().await?;
 
         // Acquire lock for this source to prevent concurrent access
+        let waited = self.contention.start();
         let _lock = CacheLock::acquire(&self.dir, name)
             .await
             .with_context(|| format!("Failed to acquire lock for source: {name}"))?;
+        self.contention.record(name, Timing::CacheLock, waited);
 
         // Use the same cache directory structure as worktrees - bare repos with .git suffix
         // This ensures we have ONE repository that's shared by all operations
@@ -1296,11 +1733,29 @@ impl Cache {
             )?;
         }
 
-        if source_dir.exists() {
+        if source_dir.exists() && !GitRepo::is_partial_clone(&source_dir) {
             // Use existing cache - fetch to ensure we have latest refs
             // Skip fetch for local paths as they don't have remotes
             // For Git URLs, always fetch to get the latest refs (especially important for branches)
-            if crate::utils::is_git_url(url) {
+            if crate::utils::is_git_url(url)
+                && let Some(refs) = refs
+            {
+                let status =
+                    match self.fetch_with_hybrid_lock(name, &source_dir, None, Some(refs)).await {
+                        Ok(true) => SourceFetchStatus::Fetched,
+                        Ok(false) => SourceFetchStatus::Cached,
+                        Err(e) => {
+                            tracing::warn!(
+                                target: "agpm::cache",
+                                "Failed to fetch updates for {}: {}",
+                                name,
+                                e
+                            );
+                            SourceFetchStatus::OfflineSkipped
+                        }
+                    };
+                self.record_fetch(name, status).await;
+            } else if crate::utils::is_git_url(url) {
                 // Check if we've already fetched this repo in this command instance
                 let already_fetched = {
                     let fetched = self.fetched_repos.read().await;
@@ -1313,6 +1768,7 @@ impl Cache {
                         "Skipping fetch for {} (already fetched in this command)",
                         name
                     );
+                    self.record_fetch(name, SourceFetchStatus::Cached).await;
                 } else {
                     tracing::debug!(
                         target: "agpm::cache",
@@ -1321,14 +1777,19 @@ impl Cache {
                         url
                     );
                     let repo = crate::git::GitRepo::new(&source_dir);
-                    if let Err(e) = repo.fetch(None).await {
+                    let fetching = self.contention.start();
+                    let fetch_result = repo.fetch(None).await;
+                    self.contention.record(name, Timing::Network, fetching);
+                    if let Err(e) = fetch_result {
                         tracing::warn!(
                             target: "agpm::cache",
                             "Failed to fetch updates for {}: {}",
                             name,
                             e
                         );
+                        self.record_fetch(name, SourceFetchStatus::OfflineSkipped).await;
                     } else {
+                        self.record_fetch(name, SourceFetchStatus::Fetched).await;
                         // Mark this repo as fetched for this command execution
                         let mut fetched = self.fetched_repos.write().await;
                         fetched.insert(source_dir.clone());
@@ -1345,10 +1806,14 @@ impl Cache {
                     "Skipping fetch for local path: {}",
                     url
                 );
+                self.record_fetch(name, SourceFetchStatus::Cached).await;
             }
         } else {
-            // Directory doesn't exist - clone fresh as bare repo
+            // Directory doesn't exist (or holds an interrupted resumable clone) - clone as bare repo
+            let cloning = self.contention.start();
             self.clone_source(url, &source_dir).await?;
+            self.contention.record(name, Timing::Network, cloning);
+            self.record_fetch(name, SourceFetchStatus::Fetched).await;
         }
 
         Ok(source_dir)
@@ -1392,7 +1857,8 @@ impl Cache {
         tracing::debug!("📦 Cloning {} to cache...", url);
 
         // Clone as a bare repository for better concurrency and worktree support
-        GitRepo::clone_bare(url, target)
+        let strategy = self.clone_strategy(url).await;
+        GitRepo::clone_bare_with_strategy(url, target, strategy, None)
             .await
             .with_context(|| format!("Failed to clone repository from {url}"))?;
 
@@ -1823,6 +2289,521 @@ impl Cache {
         Ok(size)
     }
 
+    /// Break down cache disk usage per source repository.
+    ///
+    /// Walks `sources/` and `worktrees/`, attributing each directory to a source
+    /// by its `owner_repo` prefix, and takes last-used timestamps from the
+    /// worktree registry. Results are sorted by total size, largest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a cache directory cannot be read or measured.
+    pub async fn source_stats(&self) -> Result<Vec<SourceCacheStats>> {
+        let mut stats: HashMap<String, SourceCacheStats> = HashMap::new();
+        let mut entry_for = |source: &str| {
+            stats.entry(source.to_string()).or_insert_with(|| SourceCacheStats {
+                source: source.to_string(),
+                ..Default::default()
+            })
+        };
+
+        let sources_dir = self.dir.join("sources");
+        if sources_dir.exists() {
+            let mut entries = async_fs::read_dir(&sources_dir).await.with_file_context(
+                FileOperation::Read,
+                &sources_dir,
+                "reading cached sources",
+                "cache::source_stats",
+            )?;
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                    continue;
+                };
+                if !path.is_dir() {
+                    continue;
+                }
+                let source = name.strip_suffix(".git").unwrap_or(name);
+                let bare_repo_size = fs::get_directory_size(&path).await?;
+                let submodule_size = Self::submodule_dirs_size(&path).await?;
+
+                let source_stats = entry_for(source);
+                source_stats.bare_repo_size = bare_repo_size;
+                source_stats.submodule_size = submodule_size;
+            }
+        }
+
+        let last_used: HashMap<PathBuf, u64> = {
+            let registry = self.worktree_registry.lock().await;
+            registry
+                .entries
+                .values()
+                .map(|record| (record.path.clone(), record.last_used))
+                .collect()
+        };
+
+        let worktrees_dir = self.dir.join("worktrees");
+        if worktrees_dir.exists() {
+            let mut entries = async_fs::read_dir(&worktrees_dir).await.with_file_context(
+                FileOperation::Read,
+                &worktrees_dir,
+                "reading cached worktrees",
+                "cache::source_stats",
+            )?;
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                    continue;
+                };
+                if !path.is_dir() {
+                    continue;
+                }
+                // Worktree directories are named `owner_repo_<sha8>`
+                let source = name.rsplit_once('_').map_or(name, |(source, _sha)| source);
+                let size = fs::get_directory_size(&path).await?;
+                let used = last_used.get(&path).copied();
+
+                let source_stats = entry_for(source);
+                source_stats.worktree_count += 1;
+                source_stats.worktree_size += size;
+                source_stats.last_used = source_stats.last_used.max(used);
+            }
+        }
+
+        let mut stats: Vec<_> = stats.into_values().collect();
+        stats.sort_by(|a, b| {
+            (b.bare_repo_size + b.worktree_size)
+                .cmp(&(a.bare_repo_size + a.worktree_size))
+                .then_with(|| a.source.cmp(&b.source))
+        });
+        Ok(stats)
+    }
+
+    /// List the worktrees checked out from the source at `url`.
+    ///
+    /// Worktrees are matched by their `owner_repo_<sha8>` directory name and
+    /// paired with last-used timestamps from the worktree registry. Results are
+    /// sorted most recently used first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL cannot be parsed or `worktrees/` cannot be read.
+    pub async fn source_worktrees(&self, url: &str) -> Result<Vec<CachedWorktree>> {
+        let (owner, repo) =
+            crate::git::parse_git_url(url).map_err(|e| anyhow::anyhow!("Invalid Git URL: {e}"))?;
+        let prefix = format!("{owner}_{repo}_");
+
+        let worktrees_dir = self.dir.join("worktrees");
+        if !worktrees_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let last_used: HashMap<PathBuf, u64> = {
+            let registry = self.worktree_registry.lock().await;
+            registry
+                .entries
+                .values()
+                .map(|record| (record.path.clone(), record.last_used))
+                .collect()
+        };
+
+        let mut worktrees = Vec::new();
+        let mut entries = async_fs::read_dir(&worktrees_dir).await.with_file_context(
+            FileOperation::Read,
+            &worktrees_dir,
+            "reading cached worktrees",
+            "cache::source_worktrees",
+        )?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            let Some(sha) = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|name| name.strip_prefix(&prefix))
+                .filter(|sha| !sha.contains('_'))
+                .map(str::to_string)
+            else {
+                continue;
+            };
+            if !path.is_dir() {
+                continue;
+            }
+            let last_used = last_used.get(&path).copied();
+            worktrees.push(CachedWorktree {
+                path,
+                sha,
+                last_used,
+            });
+        }
+
+        worktrees.sort_by(|a, b| b.last_used.cmp(&a.last_used).then_with(|| a.path.cmp(&b.path)));
+        Ok(worktrees)
+    }
+
+    /// Measure the submodule repositories stored inside a bare repository.
+    ///
+    /// Git keeps submodule repositories under `modules/` in the directory of
+    /// the worktree that checked them out (`worktrees/<id>/modules`), so they
+    /// are reclaimed when the worktree is pruned.
+    async fn submodule_dirs_size(bare_repo: &Path) -> Result<u64> {
+        let mut size = 0;
+        let modules = bare_repo.join("modules");
+        if modules.is_dir() {
+            size += fs::get_directory_size(&modules).await?;
+        }
+
+        let admin_dir = bare_repo.join("worktrees");
+        if admin_dir.is_dir() {
+            let mut entries = async_fs::read_dir(&admin_dir).await.with_file_context(
+                FileOperation::Read,
+                &admin_dir,
+                "reading worktree metadata",
+                "cache::submodule_dirs_size",
+            )?;
+            while let Some(entry) = entries.next_entry().await? {
+                let modules = entry.path().join("modules");
+                if modules.is_dir() {
+                    size += fs::get_directory_size(&modules).await?;
+                }
+            }
+        }
+        Ok(size)
+    }
+
+    /// Run `git fsck` on every bare repository under `sources/`.
+    ///
+    /// Returns the number of repositories checked and those that failed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `sources/` directory cannot be read.
+    pub async fn verify_sources(&self) -> Result<(usize, Vec<CorruptSource>)> {
+        let sources_dir = self.dir.join("sources");
+        if !sources_dir.exists() {
+            return Ok((0, Vec::new()));
+        }
+
+        let mut checked = 0;
+        let mut corrupt = Vec::new();
+        let mut entries = async_fs::read_dir(&sources_dir).await.with_file_context(
+            FileOperation::Read,
+            &sources_dir,
+            "reading cached sources",
+            "cache::verify_sources",
+        )?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            let Some(source) = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_suffix(".git"))
+                .map(str::to_string)
+            else {
+                continue;
+            };
+            if !path.is_dir() {
+                continue;
+            }
+
+            checked += 1;
+            if let Err(e) = GitRepo::new(&path).fsck().await {
+                corrupt.push(CorruptSource {
+                    source,
+                    path,
+                    error: format!("{e:#}"),
+                });
+            }
+        }
+
+        corrupt.sort_by(|a, b| a.source.cmp(&b.source));
+        Ok((checked, corrupt))
+    }
+
+    /// Replace a cached bare repository with a fresh clone from `url`.
+    ///
+    /// The source's worktrees are removed as well, since they reference
+    /// objects in the old repository; they are recreated on the next install.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the old repository cannot be removed or the clone fails.
+    pub async fn repair_source(&self, source: &str, url: &str) -> Result<()> {
+        let _lock = CacheLock::acquire(&self.dir, source).await?;
+
+        let worktrees_dir = self.dir.join("worktrees");
+        if worktrees_dir.exists() {
+            let prefix = format!("{source}_");
+            let mut entries = async_fs::read_dir(&worktrees_dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                let is_source_worktree = path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .and_then(|n| n.strip_prefix(&prefix))
+                    .is_some_and(|sha| !sha.contains('_'));
+                if is_source_worktree && path.is_dir() {
+                    self.remove_worktree_dir(&path).await?;
+                }
+            }
+        }
+
+        let bare_repo_dir = self.dir.join("sources").join(format!("{source}.git"));
+        if bare_repo_dir.exists() {
+            async_fs::remove_dir_all(&bare_repo_dir).await.with_file_context(
+                FileOperation::Write,
+                &bare_repo_dir,
+                "removing corrupted repository",
+                "cache::repair_source",
+            )?;
+        }
+
+        GitRepo::clone_bare(url, &bare_repo_dir).await?;
+        Self::configure_connection_pooling(&bare_repo_dir).await.ok();
+        Ok(())
+    }
+
+    /// Remove worktrees whose backing commit no longer exists.
+    ///
+    /// A worktree is stale when its bare repository is gone, its `HEAD` cannot
+    /// be read, or the bare repository no longer contains that commit. Stale
+    /// worktrees are deleted and dropped from the worktree registry, then
+    /// `git worktree prune` cleans up the bare repositories' bookkeeping.
+    ///
+    /// # Returns
+    ///
+    /// The paths of the removed worktrees.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a stale worktree cannot be removed.
+    pub async fn prune_stale_worktrees(&self) -> Result<Vec<PathBuf>> {
+        let worktrees_dir = self.dir.join("worktrees");
+        let mut removed = Vec::new();
+        if !worktrees_dir.exists() {
+            return Ok(removed);
+        }
+
+        let mut bare_repos = HashSet::new();
+        let mut entries = async_fs::read_dir(&worktrees_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if !path.is_dir() {
+                continue;
+            }
+
+            // Worktree directories are named `owner_repo_<sha8>`
+            let source = name.rsplit_once('_').map_or(name, |(source, _sha)| source);
+            let bare_repo_dir = self.dir.join("sources").join(format!("{source}.git"));
+
+            let stale = if bare_repo_dir.exists() {
+                bare_repos.insert(bare_repo_dir.clone());
+                match GitRepo::new(&path).get_current_commit().await {
+                    Ok(head) => !GitRepo::new(&bare_repo_dir).has_commit(head.trim()).await,
+                    Err(_) => true,
+                }
+            } else {
+                true
+            };
+
+            if stale {
+                self.remove_worktree_dir(&path).await?;
+                removed.push(path);
+            }
+        }
+
+        for bare_repo_dir in bare_repos {
+            if let Err(e) = GitRepo::new(&bare_repo_dir).prune_worktrees().await {
+                tracing::warn!("Failed to prune worktrees in {}: {e}", bare_repo_dir.display());
+            }
+        }
+
+        removed.sort();
+        Ok(removed)
+    }
+
+    /// Remove worktrees for commits that `locked` does not reference.
+    ///
+    /// `locked` maps each source's `owner_repo` cache directory name to the
+    /// commits the lockfile pins it to. Worktrees (`owner_repo_<sha8>`) of
+    /// those sources whose commit is not among them are deleted and dropped
+    /// from the worktree registry. The cache is shared between projects, so
+    /// other sources are left alone unless `prune_sources` is set; then their
+    /// worktrees and bare repositories are deleted as well. Used by
+    /// `agpm install --prune-cache`.
+    ///
+    /// Each source's worktrees are removed while holding its bare repository
+    /// lock, and a repository while holding its clone lock, so a concurrent
+    /// install never sees a half-deleted entry.
+    ///
+    /// # Returns
+    ///
+    /// The paths of the removed worktrees and repositories.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a worktree or repository cannot be removed.
+    pub async fn prune_unlocked(
+        &self,
+        locked: &HashMap<String, HashSet<String>>,
+        prune_sources: bool,
+    ) -> Result<Vec<PathBuf>> {
+        let mut removed = Vec::new();
+        let mut bare_repos = HashSet::new();
+
+        // Unreferenced worktrees, grouped by source so each source is locked once
+        let mut unreferenced: HashMap<String, Vec<PathBuf>> = HashMap::new();
+        let worktrees_dir = self.dir.join("worktrees");
+        if worktrees_dir.exists() {
+            let mut entries = async_fs::read_dir(&worktrees_dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                    continue;
+                };
+                let Some((source, sha_short)) = name.rsplit_once('_') else {
+                    continue;
+                };
+                if !path.is_dir() {
+                    continue;
+                }
+
+                let referenced = match locked.get(source) {
+                    Some(commits) => commits.iter().any(|sha| sha.starts_with(sha_short)),
+                    // Another project's source
+                    None => !prune_sources,
+                };
+                if !referenced {
+                    unreferenced.entry(source.to_string()).or_default().push(path);
+                }
+            }
+        }
+
+        for (source, paths) in unreferenced {
+            let _lock = CacheLock::acquire(&self.dir, &format!("bare-repo-{source}")).await?;
+            for path in paths {
+                self.remove_worktree_dir(&path).await?;
+                removed.push(path);
+            }
+            bare_repos.insert(self.dir.join("sources").join(format!("{source}.git")));
+        }
+
+        let sources_dir = self.dir.join("sources");
+        if prune_sources && sources_dir.exists() {
+            let mut entries = async_fs::read_dir(&sources_dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                let Some(source) = path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .and_then(|name| name.strip_suffix(".git"))
+                else {
+                    continue;
+                };
+                if path.is_dir() && !locked.contains_key(source) {
+                    let _lock = CacheLock::acquire(&self.dir, source).await?;
+                    async_fs::remove_dir_all(&path).await.with_file_context(
+                        FileOperation::Write,
+                        &path,
+                        "removing unused source repository",
+                        "cache::prune_unlocked",
+                    )?;
+                    bare_repos.remove(&path);
+                    removed.push(path);
+                }
+            }
+        }
+
+        for bare_repo_dir in bare_repos.into_iter().filter(|dir| dir.exists()) {
+            if let Err(e) = GitRepo::new(&bare_repo_dir).prune_worktrees().await {
+                tracing::warn!("Failed to prune worktrees in {}: {e}", bare_repo_dir.display());
+            }
+        }
+
+        removed.sort();
+        Ok(removed)
+    }
+
+    /// Delete a worktree directory and forget it in the worktree registry.
+    async fn remove_worktree_dir(&self, worktree_path: &Path) -> Result<()> {
+        async_fs::remove_dir_all(worktree_path).await.with_file_context(
+            FileOperation::Write,
+            worktree_path,
+            "removing cached worktree",
+            "cache::remove_worktree_dir",
+        )?;
+        self.remove_worktree_record_by_path(worktree_path).await
+    }
+
+    /// Write a Git bundle containing the given commits of a source repository.
+    ///
+    /// The source is cloned or fetched into the cache first, so every locked
+    /// commit is available. Used by `agpm bundle export`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source cannot be fetched or a commit is missing.
+    pub async fn export_source_bundle(
+        &self,
+        name: &str,
+        url: &str,
+        commits: &[String],
+        bundle_path: &Path,
+    ) -> Result<()> {
+        let bare_repo_dir = self.get_or_clone_source(name, url, None).await?;
+        GitRepo::new(&bare_repo_dir).create_bundle(bundle_path, commits).await
+    }
+
+    /// Populate the cached repository for `url` from a bundle.
+    ///
+    /// The bare repository is created (with `url` as its origin) if it does not
+    /// exist yet, then the bundle's commits are fetched into it. The repository is
+    /// marked as fetched, so installs run with this `Cache` instance use the
+    /// imported commits without contacting the remote. Used by `agpm bundle import`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `url` cannot be parsed, or the repository cannot be
+    /// created or does not accept the bundle.
+    pub async fn import_source_bundle(&self, url: &str, bundle_path: &Path) -> Result<PathBuf> {
+        self.ensure_cache_dir().await?;
+
+        let (owner, repo) =
+            crate::git::parse_git_url(url).map_err(|e| anyhow::anyhow!("Invalid Git URL: {e}"))?;
+        let _lock = CacheLock::acquire(&self.dir, &format!("{owner}_{repo}")).await?;
+
+        let bare_repo_dir = self.dir.join("sources").join(format!("{owner}_{repo}.git"));
+        if !bare_repo_dir.exists() {
+            async_fs::create_dir_all(&bare_repo_dir).await.with_file_context(
+                FileOperation::CreateDir,
+                &bare_repo_dir,
+                "creating cached repository",
+                "cache::import_source_bundle",
+            )?;
+            GitCommand::new()
+                .args(["init", "--bare"])
+                .current_dir(&bare_repo_dir)
+                .execute_success()
+                .await?;
+            GitCommand::new()
+                .args(["remote", "add", "origin", url])
+                .current_dir(&bare_repo_dir)
+                .execute_success()
+                .await?;
+            GitCommand::new()
+                .args(["config", "remote.origin.fetch", "+refs/heads/*:refs/remotes/origin/*"])
+                .current_dir(&bare_repo_dir)
+                .execute_success()
+                .await?;
+        }
+
+        GitRepo::new(&bare_repo_dir).fetch_bundle(bundle_path).await?;
+        self.fetched_repos.write().await.insert(bare_repo_dir.clone());
+        Ok(bare_repo_dir)
+    }
+
     /// Returns the path to the cache directory.
     ///
     /// This method provides access to the cache directory path for inspection,
@@ -1981,17 +2962,27 @@ impl Cache {
     ///
     /// # Parameters
     ///
+    /// * `name` - Source name, for the concurrency report
     /// * `bare_repo_path` - Path to the bare repository
     /// * `context` - Optional context string for logging
+    /// * `refs` - Only fetch these tags, branches and commits, or everything if `None`
+    ///
+    /// A selective fetch skips commits the repository already has and does not
+    /// mark the repository as fetched, so a later full fetch still runs. If it
+    /// fails, for example because the remote refuses to serve a commit by SHA,
+    /// this falls back to a full fetch.
     ///
     /// # Returns
     ///
-    /// Returns Ok(()) if the fetch was successful or skipped.
+    /// Returns `Ok(true)` if a fetch ran, or `Ok(false)` if the repository was already
+    /// fetched earlier in this command or already had every requested commit.
     async fn fetch_with_hybrid_lock(
         &self,
+        name: &str,
         bare_repo_path: &Path,
         context: Option<&str>,
-    ) -> Result<()> {
+        refs: Option<&[String]>,
+    ) -> Result<bool> {
         use fs4::fs_std::FileExt;
 
         // Level 1: In-process lock (fast path)
@@ -2000,7 +2991,9 @@ impl Cache {
             .entry(bare_repo_path.to_path_buf())
             .or_insert_with(|| Arc::new(Mutex::new(())))
             .clone();
+        let waited = self.contention.start();
         let _memory_guard = memory_lock.lock().await;
+        self.contention.record(name, Timing::FetchLock, waited);
 
         // Level 2: File-based lock (cross-process)
         let safe_name = bare_repo_path
@@ -2041,7 +3034,9 @@ impl Cache {
                 bare_repo_path.display()
             );
         }
+        let waited = self.contention.start();
         std_file.lock_exclusive()?;
+        self.contention.record(name, Timing::FetchLock, waited);
 
         if let Some(ctx) = context {
             tracing::debug!(
@@ -2081,12 +3076,39 @@ impl Cache {
                 );
             }
             // Release the file lock and return
-            return Ok(());
+            return Ok(false);
         }
 
         // Now safe to fetch
         let repo = GitRepo::new(bare_repo_path);
 
+        if let Some(refs) = refs {
+            // Tags and branches are always fetched, since they can move
+            let mut missing = Vec::with_capacity(refs.len());
+            for reference in refs {
+                let is_sha =
+                    reference.len() == 40 && reference.chars().all(|c| c.is_ascii_hexdigit());
+                if !(is_sha && repo.has_commit(reference).await) {
+                    missing.push(reference.clone());
+                }
+            }
+            if missing.is_empty() {
+                return Ok(false);
+            }
+
+            let fetching = self.contention.start();
+            let result = repo.fetch_refs(None, &missing).await;
+            self.contention.record(name, Timing::Network, fetching);
+            match result {
+                Ok(()) => return Ok(true),
+                Err(e) => tracing::debug!(
+                    target: "agpm::git",
+                    "Selective fetch of {} failed, fetching all refs: {e}",
+                    bare_repo_path.display()
+                ),
+            }
+        }
+
         if let Some(ctx) = context {
             tracing::debug!(
                 target: "agpm::git",
@@ -2096,7 +3118,9 @@ impl Cache {
             );
         }
 
+        let fetching = self.contention.start();
         repo.fetch(None).await?;
+        self.contention.record(name, Timing::Network, fetching);
 
         // Mark this repo as fetched for this command execution
         {
@@ -2115,7 +3139,7 @@ impl Cache {
         }
 
         // File lock automatically released when std_file is dropped
-        Ok(())
+        Ok(true)
     }
 }
 
@@ -2153,6 +3177,192 @@ mod tests {
         assert_eq!(size, 0);
     }
 
+    #[tokio::test]
+    async fn test_source_stats_groups_by_source() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = Cache::with_dir(temp_dir.path().to_path_buf()).unwrap();
+
+        let bare = temp_dir.path().join("sources").join("owner_agents.git");
+        std::fs::create_dir_all(&bare).unwrap();
+        std::fs::write(bare.join("HEAD"), "ref: refs/heads/main").unwrap();
+        let submodule = bare.join("worktrees").join("owner_agents_abc12345").join("modules");
+        std::fs::create_dir_all(&submodule).unwrap();
+        std::fs::write(submodule.join("config"), "module").unwrap();
+
+        let worktrees = temp_dir.path().join("worktrees");
+        for sha in ["abc12345", "def67890"] {
+            let worktree = worktrees.join(format!("owner_agents_{sha}"));
+            std::fs::create_dir_all(&worktree).unwrap();
+            std::fs::write(worktree.join("agent.md"), "# Agent").unwrap();
+        }
+        let orphan = worktrees.join("other_repo_12345678");
+        std::fs::create_dir_all(&orphan).unwrap();
+        std::fs::write(orphan.join("snippet.md"), "# Snippet").unwrap();
+
+        cache
+            .record_worktree_usage(
+                "key",
+                "agents",
+                "abc12345",
+                &worktrees.join("owner_agents_abc12345"),
+            )
+            .await
+            .unwrap();
+
+        let stats = cache.source_stats().await.unwrap();
+        assert_eq!(stats.len(), 2);
+
+        let agents = stats.iter().find(|s| s.source == "owner_agents").unwrap();
+        assert_eq!(agents.bare_repo_size, 26);
+        assert_eq!(agents.submodule_size, 6);
+        assert_eq!(agents.worktree_count, 2);
+        assert_eq!(agents.worktree_size, 14);
+        assert!(agents.last_used.is_some());
+
+        let other = stats.iter().find(|s| s.source == "other_repo").unwrap();
+        assert_eq!(other.bare_repo_size, 0);
+        assert_eq!(other.worktree_count, 1);
+        assert_eq!(other.last_used, None);
+    }
+
+    #[tokio::test]
+    async fn test_source_worktrees_lists_matching_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = Cache::with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let url = "https://github.com/owner/agents.git";
+
+        assert_eq!(
+            cache.get_bare_repo_path(url).unwrap(),
+            temp_dir.path().join("sources").join("owner_agents.git")
+        );
+        assert!(cache.source_worktrees(url).await.unwrap().is_empty());
+
+        let worktrees = temp_dir.path().join("worktrees");
+        for name in
+            ["owner_agents_abc12345", "owner_agents_def67890", "owner_agents_extra_12345678"]
+        {
+            std::fs::create_dir_all(worktrees.join(name)).unwrap();
+        }
+        cache
+            .record_worktree_usage(
+                "key",
+                "agents",
+                "def67890",
+                &worktrees.join("owner_agents_def67890"),
+            )
+            .await
+            .unwrap();
+
+        let listed = cache.source_worktrees(url).await.unwrap();
+        let shas: Vec<_> = listed.iter().map(|w| w.sha.as_str()).collect();
+        assert_eq!(shas, vec!["def67890", "abc12345"]);
+        assert!(listed[0].last_used.is_some());
+        assert_eq!(listed[1].last_used, None);
+        assert_eq!(listed[1].path, cache.get_worktree_path(url, "abc12345deadbeef").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_configure_sources_enables_submodules() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = Cache::with_dir(temp_dir.path().to_path_buf()).unwrap();
+
+        let mut sources = SourceMap::new();
+        sources.insert("plain".to_string(), "https://github.com/org/plain.git".to_string());
+        sources.insert("shared".to_string(), "https://github.com/org/shared.git".to_string());
+        sources.set_options(
+            "shared",
+            crate::manifest::SourceOptions {
+                submodules: true,
+                ..crate::manifest::SourceOptions::default()
+            },
+        );
+        cache.configure_sources(&sources).await;
+
+        let enabled = cache.submodule_sources.read().await;
+        assert!(enabled.contains("org_shared"));
+        assert!(!enabled.contains("org_plain"));
+    }
+
+    #[tokio::test]
+    async fn test_configure_sources_records_clone_strategy() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = Cache::with_dir(temp_dir.path().to_path_buf()).unwrap();
+
+        let mut sources = SourceMap::new();
+        sources.insert("plain".to_string(), "https://github.com/org/plain.git".to_string());
+        sources.insert("large".to_string(), "https://github.com/org/large.git".to_string());
+        sources.set_options(
+            "large",
+            crate::manifest::SourceOptions {
+                clone_depth: Some(50),
+                resumable: true,
+                ..crate::manifest::SourceOptions::default()
+            },
+        );
+        cache.configure_sources(&sources).await;
+
+        assert_eq!(
+            cache.clone_strategy("https://github.com/org/large.git").await,
+            CloneStrategy {
+                depth: Some(50),
+                resumable: true,
+            }
+        );
+        assert_eq!(
+            cache.clone_strategy("https://github.com/org/plain.git").await,
+            CloneStrategy::default()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prune_stale_worktrees_without_bare_repo() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = Cache::with_dir(temp_dir.path().to_path_buf()).unwrap();
+
+        let worktree = temp_dir.path().join("worktrees").join("owner_gone_abc12345");
+        std::fs::create_dir_all(&worktree).unwrap();
+        cache.record_worktree_usage("gone", "gone", "abc12345", &worktree).await.unwrap();
+
+        let removed = cache.prune_stale_worktrees().await.unwrap();
+        assert_eq!(removed, vec![worktree.clone()]);
+        assert!(!worktree.exists());
+        assert!(cache.worktree_registry.lock().await.entries.is_empty());
+
+        let (checked, corrupt) = cache.verify_sources().await.unwrap();
+        assert_eq!(checked, 0);
+        assert!(corrupt.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_prune_unlocked_keeps_locked_commits() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = Cache::with_dir(temp_dir.path().to_path_buf()).unwrap();
+
+        let worktrees = temp_dir.path().join("worktrees");
+        let kept = worktrees.join("org_tools_abc12345");
+        let old_commit = worktrees.join("org_tools_def67890");
+        let other_source = worktrees.join("org_other_abc12345");
+        let unused_repo = temp_dir.path().join("sources").join("org_other.git");
+        let used_repo = temp_dir.path().join("sources").join("org_tools.git");
+        for dir in [&kept, &old_commit, &other_source, &unused_repo, &used_repo] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+
+        let locked = HashMap::from([(
+            "org_tools".to_string(),
+            HashSet::from([format!("abc12345{}", "0".repeat(32))]),
+        )]);
+
+        // Sources of other projects sharing the cache are left alone
+        let removed = cache.prune_unlocked(&locked, false).await.unwrap();
+        assert_eq!(removed, vec![old_commit.clone()]);
+        assert!(kept.exists() && other_source.exists() && unused_repo.exists());
+
+        let removed = cache.prune_unlocked(&locked, true).await.unwrap();
+        assert_eq!(removed, vec![unused_repo.clone(), other_source.clone()]);
+        assert!(kept.exists() && used_repo.exists());
+    }
+
     #[tokio::test]
     async fn test_cache_size_with_content() {
         let temp_dir = TempDir::new().unwrap();
@@ -2499,4 +3709,42 @@ mod tests {
         let size = cache.get_cache_size().await.unwrap();
         assert_eq!(size, 18); // 5 + 10 + 3
     }
+
+    #[tokio::test]
+    async fn test_import_worktree_is_reused() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = Cache::with_dir(temp_dir.path().join("cache")).unwrap();
+        let url = "https://github.com/org/agents.git";
+
+        let checkout = temp_dir.path().join("agents");
+        std::fs::create_dir_all(checkout.join("agents")).unwrap();
+        std::fs::write(checkout.join("agents/reviewer.md"), "# Reviewer\n").unwrap();
+        let git = crate::test_utils::TestGit::new(&checkout);
+        git.init().unwrap();
+        git.config_user().unwrap();
+        git.add_all().unwrap();
+        git.commit("initial").unwrap();
+        let head = git.get_head_sha().unwrap();
+
+        let err = cache.import_worktree(url, &checkout, Some("0000000")).await.unwrap_err();
+        assert!(err.to_string().contains("is checked out at"), "{err}");
+        let inside = temp_dir.path().join("cache/worktrees/manual");
+        std::fs::create_dir_all(&inside).unwrap();
+        assert!(cache.import_worktree(url, &inside, None).await.is_err());
+
+        let imported = cache.import_worktree(url, &checkout, Some(&head[..10])).await.unwrap();
+        assert_eq!(imported, head);
+
+        // A fresh cache instance finds the import in the persisted registry
+        let cache = Cache::with_dir(temp_dir.path().join("cache")).unwrap();
+        let path = cache.get_or_create_worktree_for_sha("agents", url, &head, None).await.unwrap();
+        assert_eq!(path, checkout.canonicalize().unwrap());
+        assert!(cache.worktree_registry.lock().await.entries.values().all(|r| r.imported));
+
+        // `--no-cache` refreshes worktrees through cleanup_worktree; the user's checkout stays
+        cache.cleanup_worktree(&path).await.unwrap();
+        assert!(checkout.join("agents/reviewer.md").exists());
+        let again = cache.get_or_create_worktree_for_sha("agents", url, &head, None).await.unwrap();
+        assert_eq!(again, path);
+    }
 }
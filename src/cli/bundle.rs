@@ -0,0 +1,497 @@
+//! Export and import offline dependency bundles.
+//!
+//! This module provides the `bundle` command, which moves a project's locked
+//! dependencies to a machine without network access. `agpm bundle export`
+//! packages everything `agpm.lock` pins into a single archive, and
+//! `agpm bundle import` restores it into the cache and installs from it.
+//!
+//! # Bundle Format
+//!
+//! A bundle is a zip archive containing:
+//! - `bundle.json` - format version and SHA-256 checksums of every other entry
+//! - `agpm.toml` and `agpm.lock` - the project's manifest and lockfile
+//! - `sources/<n>.bundle` - one Git bundle per source, holding the history
+//!   of exactly the commits pinned in the lockfile; `bundle.json` maps each
+//!   source name to its entry
+//! - `local/<path>` - local (source-less) dependency files inside the project
+//!
+//! Checksums are verified before anything is written on import.
+//!
+//! # Examples
+//!
+//! ```bash
+//! # On a connected machine
+//! agpm bundle export --output deps.zip
+//!
+//! # On the air-gapped machine, in an empty project directory
+//! agpm bundle import deps.zip
+//! ```
+//!
+//! # Limitations
+//!
+//! Sources that are local directories (not Git repositories) and local
+//! dependencies outside the project directory are not bundled; they must
+//! already exist at the same paths on the target machine. Submodules of
+//! sources declared with `submodules = true` are not bundled either.
+
+use anyhow::{Context, Result, anyhow, bail};
+use clap::{Args, Subcommand};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::cache::Cache;
+use crate::lockfile::LockFile;
+use crate::manifest::{Manifest, find_manifest_with_optional};
+
+/// Version of the bundle layout written by `agpm bundle export`.
+const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// Name of the metadata entry inside a bundle.
+const BUNDLE_METADATA: &str = "bundle.json";
+
+/// Command to export and import offline dependency bundles.
+#[derive(Args)]
+pub struct BundleCommand {
+    /// Bundle operation to perform
+    #[command(subcommand)]
+    command: BundleSubcommands,
+}
+
+/// Subcommands for offline bundles.
+#[derive(Subcommand)]
+enum BundleSubcommands {
+    /// Package the locked dependencies into a single archive.
+    ///
+    /// Requires an up-to-date `agpm.lock`. Sources are fetched as needed, so
+    /// this runs on a machine with network access.
+    ///
+    /// # Examples
+    /// ```bash
+    /// agpm bundle export --output deps.zip
+    /// ```
+    Export {
+        /// Path of the archive to write
+        #[arg(short, long, default_value = "agpm-bundle.zip")]
+        output: PathBuf,
+    },
+
+    /// Restore a bundle into the cache and install it without network access.
+    ///
+    /// Verifies the bundle's checksums, writes its `agpm.toml` and `agpm.lock`
+    /// into the project directory, populates the cache from the bundled Git
+    /// data, and installs the locked resources.
+    ///
+    /// # Examples
+    /// ```bash
+    /// agpm bundle import deps.zip
+    /// agpm bundle import deps.zip --force  # Overwrite existing agpm.toml/agpm.lock
+    /// ```
+    Import {
+        /// Path of the archive to import
+        #[arg(value_name = "BUNDLE")]
+        bundle: PathBuf,
+
+        /// Overwrite an existing agpm.toml and agpm.lock
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+/// Metadata stored as `bundle.json` inside a bundle.
+#[derive(Debug, Serialize, Deserialize)]
+struct BundleMetadata {
+    /// Bundle layout version
+    format_version: u32,
+    /// Version of AGPM that wrote the bundle
+    agpm_version: String,
+    /// Bundled sources, keyed by source name
+    sources: BTreeMap<String, BundledSource>,
+    /// Local dependency files: archive entry to project-relative path
+    local_files: BTreeMap<String, String>,
+    /// SHA-256 checksum of every other entry, keyed by entry name
+    checksums: BTreeMap<String, String>,
+}
+
+/// A source repository included in a bundle.
+#[derive(Debug, Serialize, Deserialize)]
+struct BundledSource {
+    /// Repository URL recorded in the lockfile
+    url: String,
+    /// Archive entry holding the Git bundle
+    entry: String,
+}
+
+impl BundleCommand {
+    /// Execute the bundle command.
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest_path` - Optional path to the manifest; for import, the bundle
+    ///   is restored next to it (defaults to the current directory)
+    pub async fn execute_with_manifest_path(self, manifest_path: Option<PathBuf>) -> Result<()> {
+        let cache = Cache::new()?;
+        match self.command {
+            BundleSubcommands::Export {
+                output,
+            } => {
+                let manifest_path = find_manifest_with_optional(manifest_path)?;
+                export_bundle(&cache, &manifest_path, &output).await
+            }
+            BundleSubcommands::Import {
+                bundle,
+                force,
+            } => {
+                let project_dir = match manifest_path {
+                    Some(path) => path.parent().map(Path::to_path_buf).unwrap_or_default(),
+                    None => std::env::current_dir()?,
+                };
+                import_bundle(&cache, &bundle, &project_dir, force).await
+            }
+        }
+    }
+}
+
+/// Write a bundle for the project whose manifest is at `manifest_path`.
+async fn export_bundle(cache: &Cache, manifest_path: &Path, output: &Path) -> Result<()> {
+    let project_dir = manifest_path.parent().unwrap_or_else(|| Path::new("."));
+    let lockfile_path = project_dir.join("agpm.lock");
+    if !lockfile_path.exists() {
+        bail!("No agpm.lock found at {}. Run 'agpm install' first", lockfile_path.display());
+    }
+
+    let manifest = Manifest::load(manifest_path)?;
+    let lockfile = LockFile::load(&lockfile_path)?;
+    if let Some(reason) = lockfile.validate_against_manifest(&manifest, false)? {
+        bail!("agpm.lock is out of date: {reason}\nRun 'agpm install' before exporting a bundle");
+    }
+
+    println!("📦 Exporting bundle from {}...", lockfile_path.display());
+
+    let mut entries: Vec<(String, Vec<u8>)> = vec![
+        ("agpm.toml".to_string(), tokio::fs::read(manifest_path).await?),
+        ("agpm.lock".to_string(), tokio::fs::read(&lockfile_path).await?),
+    ];
+    let mut metadata = BundleMetadata {
+        format_version: BUNDLE_FORMAT_VERSION,
+        agpm_version: env!("CARGO_PKG_VERSION").to_string(),
+        sources: BTreeMap::new(),
+        local_files: BTreeMap::new(),
+        checksums: BTreeMap::new(),
+    };
+
+    // Group the locked commits by source
+    let mut commits: BTreeMap<(String, String), BTreeSet<String>> = BTreeMap::new();
+    let mut unbundled_sources = BTreeSet::new();
+    for entry in lockfile.all_resources() {
+        match (&entry.source, &entry.url, entry.resolved_commit.as_deref()) {
+            (Some(source), Some(url), Some(sha))
+                if !sha.is_empty() && !crate::utils::is_local_path(url) =>
+            {
+                commits.entry((source.clone(), url.clone())).or_default().insert(sha.to_string());
+            }
+            (Some(source), _, _) => {
+                unbundled_sources.insert(source.clone());
+            }
+            (None, _, _) => {
+                let path = Path::new(&entry.path);
+                if path.is_absolute() || !crate::utils::fs::is_safe_path(project_dir, path) {
+                    eprintln!(
+                        "{} Local dependency '{}' is outside the project and is not bundled",
+                        "⚠".yellow(),
+                        entry.path
+                    );
+                    continue;
+                }
+                let name = format!("local/{}", entry.path.replace('\\', "/"));
+                if !metadata.local_files.contains_key(&name) {
+                    let content =
+                        tokio::fs::read(project_dir.join(path)).await.with_context(|| {
+                            format!("Failed to read local dependency {}", entry.path)
+                        })?;
+                    metadata.local_files.insert(name.clone(), entry.path.clone());
+                    entries.push((name, content));
+                }
+            }
+        }
+    }
+
+    for source in &unbundled_sources {
+        eprintln!("{} Source '{source}' is a local directory and is not bundled", "⚠".yellow());
+    }
+    for (source, _) in commits.keys() {
+        if manifest.sources.options(source).submodules {
+            eprintln!(
+                "{} Submodules of source '{source}' are not bundled; resources inside them will \
+                 be missing after import",
+                "⚠".yellow()
+            );
+        }
+    }
+
+    // Bundle files are named by index, since source names come from the manifest and
+    // are not safe to use as file names
+    let temp_dir = tempfile::TempDir::new()?;
+    for (index, ((source, url), shas)) in commits.into_iter().enumerate() {
+        let shas: Vec<String> = shas.into_iter().collect();
+        let bundle_path = temp_dir.path().join(format!("{index}.bundle"));
+        cache
+            .export_source_bundle(&source, &url, &shas, &bundle_path)
+            .await
+            .with_context(|| format!("Failed to bundle source '{source}'"))?;
+
+        let name = format!("sources/{index}.bundle");
+        println!("  {} {source} ({} commit(s))", "✓".green(), shas.len());
+        entries.push((name.clone(), tokio::fs::read(&bundle_path).await?));
+        metadata.sources.insert(
+            source,
+            BundledSource {
+                url,
+                entry: name,
+            },
+        );
+    }
+
+    for (name, content) in &entries {
+        metadata.checksums.insert(name.clone(), checksum(content));
+    }
+    entries.push((BUNDLE_METADATA.to_string(), serde_json::to_vec_pretty(&metadata)?));
+
+    let output = output.to_path_buf();
+    tokio::task::spawn_blocking(move || write_archive(&output, &entries))
+        .await
+        .context("Failed to join bundle writer task")??;
+
+    println!("{}", format!("✅ Bundle written to {}", output.display()).green().bold());
+    Ok(())
+}
+
+/// Restore a bundle into the cache and install it in `project_dir`.
+async fn import_bundle(
+    cache: &Cache,
+    bundle: &Path,
+    project_dir: &Path,
+    force: bool,
+) -> Result<()> {
+    let manifest_path = project_dir.join("agpm.toml");
+    let lockfile_path = project_dir.join("agpm.lock");
+    if !force && (manifest_path.exists() || lockfile_path.exists()) {
+        bail!(
+            "{} already contains agpm.toml or agpm.lock\nUse --force to overwrite them with the bundle's",
+            project_dir.display()
+        );
+    }
+
+    println!("📦 Importing bundle {}...", bundle.display());
+
+    let bundle_path = bundle.to_path_buf();
+    let (metadata, entries) = tokio::task::spawn_blocking(move || read_archive(&bundle_path))
+        .await
+        .context("Failed to join bundle reader task")??;
+    println!("  {} Verified {} checksum(s)", "✓".green(), metadata.checksums.len());
+
+    let entry =
+        |name: &str| entries.get(name).ok_or_else(|| anyhow!("Bundle is missing entry '{name}'"));
+
+    // Populate the cache from the bundled Git data
+    let temp_dir = tempfile::TempDir::new()?;
+    for (index, (source, bundled)) in metadata.sources.iter().enumerate() {
+        let git_bundle = temp_dir.path().join(format!("{index}.bundle"));
+        tokio::fs::write(&git_bundle, entry(&bundled.entry)?).await?;
+        cache
+            .import_source_bundle(&bundled.url, &git_bundle)
+            .await
+            .with_context(|| format!("Failed to import source '{source}'"))?;
+        println!("  {} {source}", "✓".green());
+    }
+
+    // Restore project files
+    tokio::fs::create_dir_all(project_dir).await?;
+    tokio::fs::write(&manifest_path, entry("agpm.toml")?).await?;
+    tokio::fs::write(&lockfile_path, entry("agpm.lock")?).await?;
+    for (name, relative) in &metadata.local_files {
+        let path = Path::new(relative);
+        if path.is_absolute() || !crate::utils::fs::is_safe_path(project_dir, path) {
+            bail!("Bundle entry '{name}' points outside the project: {relative}");
+        }
+        let target = project_dir.join(path);
+        if let Some(parent) = target.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&target, entry(name)?).await?;
+    }
+
+    // Install straight from the lockfile; the cache already has every locked commit
+    let manifest = Manifest::load(&manifest_path)?;
+    let mut lockfile = LockFile::load(&lockfile_path)?;
+    let locked = Arc::new(lockfile.clone());
+    let results = crate::installer::install_resources(
+        crate::installer::ResourceFilter::All,
+        &locked,
+        &manifest,
+        project_dir,
+        cache.clone(),
+        false,
+        None,
+        None,
+        None,
+        false,
+        None,
+    )
+    .await?;
+    lockfile.apply_installation_results(
+        results.checksums,
+        results.context_checksums,
+        results.applied_patches,
+    );
+    crate::installer::finalize_installation(
+        &mut lockfile,
+        &manifest,
+        project_dir,
+        cache,
+        None,
+        false,
+        Some(&lockfile_path),
+    )
+    .await?;
+
+    println!(
+        "{}",
+        format!("✅ Installed {} resource(s) from bundle", results.installed_count).green().bold()
+    );
+    Ok(())
+}
+
+/// Format a SHA-256 checksum in the lockfile's `sha256:<hex>` style.
+fn checksum(content: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    format!("sha256:{}", hex::encode(Sha256::digest(content)))
+}
+
+/// Write the bundle entries into a zip archive.
+fn write_archive(output: &Path, entries: &[(String, Vec<u8>)]) -> Result<()> {
+    let file = std::fs::File::create(output)
+        .with_context(|| format!("Failed to create bundle {}", output.display()))?;
+    let mut writer = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    for (name, content) in entries {
+        writer.start_file(name.as_str(), options)?;
+        writer.write_all(content)?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+/// Read a bundle and verify every entry against the recorded checksums.
+fn read_archive(bundle: &Path) -> Result<(BundleMetadata, BTreeMap<String, Vec<u8>>)> {
+    let file = std::fs::File::open(bundle)
+        .with_context(|| format!("Failed to open bundle {}", bundle.display()))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .with_context(|| format!("{} is not a valid AGPM bundle", bundle.display()))?;
+
+    let metadata: BundleMetadata = {
+        let mut content = String::new();
+        archive
+            .by_name(BUNDLE_METADATA)
+            .with_context(|| format!("{} has no {BUNDLE_METADATA}", bundle.display()))?
+            .read_to_string(&mut content)?;
+        serde_json::from_str(&content).context("Invalid bundle metadata")?
+    };
+    if metadata.format_version > BUNDLE_FORMAT_VERSION {
+        bail!(
+            "Bundle format version {} is newer than supported version {BUNDLE_FORMAT_VERSION} \
+             (written by agpm {})",
+            metadata.format_version,
+            metadata.agpm_version
+        );
+    }
+
+    let mut entries = BTreeMap::new();
+    for (name, expected) in &metadata.checksums {
+        let mut content = Vec::new();
+        archive
+            .by_name(name)
+            .with_context(|| format!("Bundle is missing entry '{name}'"))?
+            .read_to_end(&mut content)?;
+        let actual = checksum(&content);
+        if &actual != expected {
+            bail!("Checksum mismatch for bundle entry '{name}': expected {expected}, got {actual}");
+        }
+        entries.insert(name.clone(), content);
+    }
+
+    Ok((metadata, entries))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn metadata_for(entries: &[(String, Vec<u8>)]) -> BundleMetadata {
+        BundleMetadata {
+            format_version: BUNDLE_FORMAT_VERSION,
+            agpm_version: env!("CARGO_PKG_VERSION").to_string(),
+            sources: BTreeMap::new(),
+            local_files: BTreeMap::new(),
+            checksums: entries
+                .iter()
+                .map(|(name, content)| (name.clone(), checksum(content)))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_archive_round_trip_verifies_checksums() -> Result<()> {
+        let temp = TempDir::new()?;
+        let output = temp.path().join("deps.zip");
+
+        let mut entries = vec![
+            ("agpm.toml".to_string(), b"[sources]\n".to_vec()),
+            ("agpm.lock".to_string(), b"version = 2\n".to_vec()),
+        ];
+        let metadata = metadata_for(&entries);
+        entries.push((BUNDLE_METADATA.to_string(), serde_json::to_vec(&metadata)?));
+        write_archive(&output, &entries)?;
+
+        let (read, contents) = read_archive(&output)?;
+        assert_eq!(read.format_version, BUNDLE_FORMAT_VERSION);
+        assert_eq!(contents["agpm.lock"], b"version = 2\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_archive_rejects_tampered_entry() -> Result<()> {
+        let temp = TempDir::new()?;
+        let output = temp.path().join("deps.zip");
+
+        let metadata = metadata_for(&[("agpm.lock".to_string(), b"version = 2\n".to_vec())]);
+        let entries = vec![
+            ("agpm.lock".to_string(), b"version = 3\n".to_vec()),
+            (BUNDLE_METADATA.to_string(), serde_json::to_vec(&metadata)?),
+        ];
+        write_archive(&output, &entries)?;
+
+        let err = read_archive(&output).unwrap_err();
+        assert!(err.to_string().contains("Checksum mismatch for bundle entry 'agpm.lock'"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_import_refuses_to_overwrite_project() -> Result<()> {
+        let temp = TempDir::new()?;
+        std::fs::write(temp.path().join("agpm.toml"), "[sources]\n")?;
+        let cache = Cache::with_dir(temp.path().join("cache"))?;
+
+        let err = import_bundle(&cache, &temp.path().join("deps.zip"), temp.path(), false)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("--force"));
+        Ok(())
+    }
+}
@@ -7,6 +7,7 @@
 //! # Features
 //!
 //! - **Cache Information**: View cache location, size, and contents
+//! - **Cache Statistics**: Disk usage and worktree counts per source
 //! - **Selective Cleanup**: Remove unused cached repositories
 //! - **Complete Cleanup**: Clear entire cache directory
 //! - **Size Reporting**: Human-readable cache size formatting
@@ -27,6 +28,29 @@
 //! agpm cache  # defaults to info
 //! ```
 //!
+//! Show disk usage per source:
+//! ```bash
+//! agpm cache stats
+//! agpm cache stats --format json
+//! ```
+//!
+//! Pre-fetch everything the project needs before going offline:
+//! ```bash
+//! agpm cache warm
+//! agpm cache warm --manifest-only
+//! ```
+//!
+//! Locate the cache and a source's repositories:
+//! ```bash
+//! agpm cache path
+//! agpm cache which community
+//! ```
+//!
+//! Reuse an existing checkout instead of cloning:
+//! ```bash
+//! agpm cache import-worktree community /workspaces/agpm-community
+//! ```
+//!
 //! Clean unused cache entries:
 //! ```bash
 //! agpm cache clean
@@ -70,9 +94,10 @@ use anyhow::Result;
 use clap::{Args, Subcommand};
 use colored::Colorize;
 
-use crate::cache::Cache;
+use crate::cache::{Cache, SourceFetchStatus};
 use crate::manifest::{Manifest, find_manifest_with_optional};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 /// Command to manage the global Git repository cache.
 ///
@@ -170,6 +195,124 @@ enum CacheSubcommands {
     /// agpm cache         # Defaults to info
     /// ```
     Info,
+
+    /// Show disk usage per cached source.
+    ///
+    /// Attributes cache disk usage to each source repository by its
+    /// `owner_repo` directory name and shows, per source:
+    /// - Size of the bare repository under `sources/`
+    /// - Number and combined size of its worktrees under `worktrees/`
+    /// - When one of its worktrees was last used
+    ///
+    /// Sources are listed largest first.
+    ///
+    /// # Examples
+    /// ```bash
+    /// agpm cache stats                # Table output
+    /// agpm cache stats --format json  # Machine-readable output
+    /// ```
+    Stats {
+        /// Output format (table or json)
+        #[arg(long, default_value = "table", value_parser = ["table", "json"])]
+        format: String,
+    },
+
+    /// Check cached repositories for corruption.
+    ///
+    /// Runs `git fsck` on every bare repository under `sources/` and reports
+    /// those that fail. Worktrees whose backing commit no longer exists are
+    /// removed and `git worktree prune` is run on their repositories.
+    ///
+    /// # Repair
+    /// With `--repair`, each corrupted repository is deleted and re-cloned.
+    /// Its URL is recovered from the sources in the current project's
+    /// `agpm.toml`, `agpm.lock`, and the global config; repositories whose URL
+    /// cannot be found are reported and left in place.
+    ///
+    /// # Examples
+    /// ```bash
+    /// agpm cache verify           # Report corruption
+    /// agpm cache verify --repair  # Re-clone corrupted repositories
+    /// ```
+    Verify {
+        /// Re-clone corrupted repositories from their source URL
+        #[arg(long)]
+        repair: bool,
+    },
+
+    /// Pre-fetch every source of the project into the cache.
+    ///
+    /// Clones or fetches each source in `agpm.toml`, then creates a worktree
+    /// for every commit recorded in `agpm.lock`, so later installs from the
+    /// lockfile find everything in the cache and need no network access.
+    /// Reports what was fetched and the resulting cache size.
+    ///
+    /// # Examples
+    /// ```bash
+    /// agpm cache warm                  # Sources and locked commits
+    /// agpm cache warm --manifest-only  # Sources only
+    /// ```
+    Warm {
+        /// Fetch the sources only, without checking out the locked commits
+        #[arg(long)]
+        manifest_only: bool,
+    },
+
+    /// Print the cache directory.
+    ///
+    /// Honors `AGPM_CACHE_DIR`. Prints only the path, for use in scripts.
+    ///
+    /// # Examples
+    /// ```bash
+    /// agpm cache path
+    /// ls "$(agpm cache path)/sources"
+    /// ```
+    Path,
+
+    /// Show where a source is cached.
+    ///
+    /// Prints the bare repository path and every worktree checked out from
+    /// the source, with the time each worktree was last used. The source is
+    /// looked up by name in the project manifest and then the global config;
+    /// a Git URL can be given instead of a name.
+    ///
+    /// # Examples
+    /// ```bash
+    /// agpm cache which community
+    /// agpm cache which https://github.com/org/agents.git
+    /// ```
+    Which {
+        /// Source name or Git URL
+        source: String,
+    },
+
+    /// Use an existing checkout as the cached worktree for its commit.
+    ///
+    /// Registers a Git working tree that is already on disk, such as a source
+    /// repository checked out in a dev container, as the worktree of the
+    /// source at the checkout's HEAD commit. Installs that need the source at
+    /// that commit then read from the checkout instead of cloning it.
+    ///
+    /// The checkout must be outside the cache directory. AGPM never updates or
+    /// deletes it; if it is removed or moved to another commit, AGPM falls back
+    /// to creating a worktree in the cache.
+    ///
+    /// # Examples
+    /// ```bash
+    /// agpm cache import-worktree community /workspaces/agpm-community
+    /// agpm cache import-worktree https://github.com/org/agents.git ../agents --commit 1a2b3c4d
+    /// ```
+    ImportWorktree {
+        /// Source name or Git URL the checkout belongs to
+        source: String,
+
+        /// Directory of the checkout
+        path: PathBuf,
+
+        /// Fail unless the checkout's HEAD is this commit (full or abbreviated SHA)
+        #[arg(long)]
+        commit: Option<String>,
+    },
 }
 
 impl CacheCommand {
@@ -232,6 +375,10 @@ impl CacheCommand {
     /// Dispatches to the appropriate handler based on the subcommand:
     /// - `Clean { all: true }` → Complete cache cleanup
     /// - `Clean { all: false }` → Smart unused cache cleanup
+    /// - `Warm { manifest_only }` → Pre-fetch sources and locked commits
+    /// - `Path` → Print the cache directory
+    /// - `Which { source }` → Show where a source is cached
+    /// - `ImportWorktree { .. }` → Register an existing checkout as a worktree
     /// - `Info` or `None` → Display cache information
     ///
     /// # Returns
@@ -290,6 +437,27 @@ impl CacheCommand {
                     self.clean_unused(cache, manifest_path).await
                 }
             }
+            Some(CacheSubcommands::Stats {
+                ref format,
+            }) => self.show_stats(cache, format).await,
+            Some(CacheSubcommands::Verify {
+                repair,
+            }) => self.verify(cache, manifest_path, repair).await,
+            Some(CacheSubcommands::Warm {
+                manifest_only,
+            }) => self.warm(cache, manifest_path, manifest_only).await,
+            Some(CacheSubcommands::Path) => {
+                println!("{}", cache.get_cache_location().display());
+                Ok(())
+            }
+            Some(CacheSubcommands::Which {
+                ref source,
+            }) => self.show_which(cache, source, manifest_path).await,
+            Some(CacheSubcommands::ImportWorktree {
+                ref source,
+                ref path,
+                ref commit,
+            }) => self.import_worktree(cache, source, path, commit.as_deref(), manifest_path).await,
             Some(CacheSubcommands::Info) | None => self.show_info(cache).await,
         }
     }
@@ -483,11 +651,457 @@ impl CacheCommand {
         println!("\n{}", "Tip:".yellow());
         println!("  Use 'agpm cache clean' to remove unused cache");
         println!("  Use 'agpm cache clean --all' to clear all cache");
+        println!("  Use 'agpm cache stats' to see disk usage per source");
+
+        Ok(())
+    }
+
+    /// Display disk usage per cached source.
+    ///
+    /// Prints a table (or JSON with `--format json`) with one row per source:
+    /// bare repository size, worktree count, combined worktree size, and the
+    /// last time one of its worktrees was used.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache` - The cache instance to analyze
+    /// * `format` - Output format: "table" or "json"
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the statistics were displayed
+    /// - `Err(anyhow::Error)` if the cache directories cannot be read
+    async fn show_stats(&self, cache: Cache, format: &str) -> Result<()> {
+        let stats = cache.source_stats().await?;
+        let total: u64 = stats.iter().map(|s| s.bare_repo_size + s.worktree_size).sum();
+
+        if format == "json" {
+            let output = serde_json::json!({
+                "location": cache.get_cache_location(),
+                "total_size": total,
+                "sources": stats,
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+            return Ok(());
+        }
+
+        if stats.is_empty() {
+            println!("Cache is empty ({})", cache.get_cache_location().display());
+            return Ok(());
+        }
+
+        let width = stats.iter().map(|s| s.source.len()).max().unwrap_or(0).max(6);
+        println!(
+            "{:<width$}  {:>10}  {:>9}  {:>13}  {}",
+            "Source".bold(),
+            "Bare repo".bold(),
+            "Worktrees".bold(),
+            "Worktree size".bold(),
+            "Last used".bold(),
+        );
+        println!("{}", "─".repeat(width + 56));
+
+        for source in &stats {
+            println!(
+                "{:<width$}  {:>10}  {:>9}  {:>13}  {}",
+                source.source,
+                format_size(source.bare_repo_size),
+                source.worktree_count,
+                format_size(source.worktree_size),
+                format_last_used(source.last_used).bright_black(),
+            );
+        }
 
+        let submodule_total: u64 = stats.iter().map(|s| s.submodule_size).sum();
+        if submodule_total > 0 {
+            println!(
+                "\n{}",
+                format!("Bare repo sizes include {} of submodules", format_size(submodule_total))
+                    .bright_black()
+            );
+        }
+
+        println!("\n{} {} across {} source(s)", "Total:".bold(), format_size(total), stats.len());
+        Ok(())
+    }
+
+    /// Check cached repositories for corruption and optionally repair them.
+    ///
+    /// Stale worktrees are always pruned. Corrupted bare repositories are only
+    /// reported unless `repair` is set, in which case they are re-cloned from
+    /// the URL recovered by [`known_source_urls`].
+    ///
+    /// # Arguments
+    ///
+    /// * `cache` - The cache instance to verify
+    /// * `manifest_path` - Optional path to the manifest used to recover source URLs
+    /// * `repair` - Re-clone corrupted repositories
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if no corruption remains
+    /// - `Err(anyhow::Error)` if corrupted repositories were found and not repaired
+    async fn verify(
+        &self,
+        cache: Cache,
+        manifest_path: Option<PathBuf>,
+        repair: bool,
+    ) -> Result<()> {
+        println!("🔍 Verifying cached repositories...");
+
+        let (checked, corrupt) = cache.verify_sources().await?;
+        let pruned = cache.prune_stale_worktrees().await?;
+        for path in &pruned {
+            println!("  Removed stale worktree {}", path.display());
+        }
+
+        if corrupt.is_empty() {
+            println!(
+                "{}",
+                format!("✅ {checked} cached repositories verified, no corruption found")
+                    .green()
+                    .bold()
+            );
+            return Ok(());
+        }
+
+        let urls = if repair {
+            known_source_urls(manifest_path).await
+        } else {
+            HashMap::new()
+        };
+
+        let mut unrepaired = 0;
+        for source in &corrupt {
+            println!("{} {} is corrupted", "✗".red(), source.source);
+            for line in source.error.lines() {
+                println!("    {}", line.bright_black());
+            }
+
+            if !repair {
+                unrepaired += 1;
+                continue;
+            }
+
+            match urls.get(&source.source) {
+                Some(url) => match cache.repair_source(&source.source, url).await {
+                    Ok(()) => println!("  {} Re-cloned {}", "✓".green(), source.source),
+                    Err(e) => {
+                        unrepaired += 1;
+                        println!("  {} Failed to re-clone {}: {e}", "✗".red(), source.source);
+                    }
+                },
+                None => {
+                    unrepaired += 1;
+                    println!(
+                        "  {} No URL found for {} in agpm.toml, agpm.lock, or the global config",
+                        "⚠".yellow(),
+                        source.source
+                    );
+                }
+            }
+        }
+
+        if unrepaired == 0 {
+            println!("{}", format!("✅ Repaired {} repositories", corrupt.len()).green().bold());
+            return Ok(());
+        }
+
+        if !repair {
+            println!("\nRun 'agpm cache verify --repair' to re-clone corrupted repositories");
+        }
+        Err(anyhow::anyhow!("{unrepaired} cached repositories are corrupted"))
+    }
+
+    /// Fetch every manifest source and check out every locked commit.
+    ///
+    /// Sources are fetched first, then worktrees are created for each
+    /// distinct `(source, resolved_commit)` pair in `agpm.lock`, both with
+    /// bounded parallelism. Failures are reported per source or commit and
+    /// do not stop the remaining work.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache` - The cache instance to populate
+    /// * `manifest_path` - Optional path to the manifest whose sources are warmed
+    /// * `manifest_only` - Skip the lockfile and fetch the sources only
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if every source and commit is now in the cache
+    /// - `Err(anyhow::Error)` if the manifest is missing or anything failed to fetch
+    async fn warm(
+        &self,
+        cache: Cache,
+        manifest_path: Option<PathBuf>,
+        manifest_only: bool,
+    ) -> Result<()> {
+        use futures::stream::{self, StreamExt};
+
+        let manifest_path = find_manifest_with_optional(manifest_path)?;
+        let manifest = Manifest::load(&manifest_path)?;
+        let source_manager =
+            crate::source::SourceManager::from_manifest_with_global(&manifest).await?;
+        let max_concurrency = std::cmp::max(
+            10,
+            std::thread::available_parallelism().map(std::num::NonZero::get).unwrap_or(4) * 2,
+        );
+
+        let mut sources: Vec<(String, String)> = manifest
+            .sources
+            .keys()
+            .filter_map(|name| Some((name.clone(), source_manager.get_source_url(name)?)))
+            .collect();
+        sources.sort();
+
+        println!("🔥 Warming cache for {} source(s)...", sources.len());
+        let mut failed = 0;
+
+        let mut results: Vec<_> = stream::iter(&sources)
+            .map(|(name, url)| {
+                let cache = &cache;
+                async move { (name, cache.get_or_clone_source(name, url, None).await) }
+            })
+            .buffer_unordered(max_concurrency)
+            .collect()
+            .await;
+        let statuses = cache.source_fetches().await;
+        results.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, result) in results {
+            match (result, statuses.get(name.as_str())) {
+                (Err(e), _) => {
+                    failed += 1;
+                    println!("  {} {name}: {e}", "✗".red());
+                }
+                (Ok(_), Some(SourceFetchStatus::OfflineSkipped)) => {
+                    failed += 1;
+                    println!("  {} {name}: fetch failed, cached copy may be stale", "✗".red());
+                }
+                (Ok(_), Some(SourceFetchStatus::Local)) => {
+                    println!("  {} {name} {}", "-".dimmed(), "(local directory)".bright_black());
+                }
+                (Ok(_), Some(SourceFetchStatus::Cached)) => {
+                    println!("  {} {name} {}", "✓".green(), "(cached)".bright_black());
+                }
+                (Ok(_), _) => println!("  {} {name} fetched", "✓".green()),
+            }
+        }
+
+        if !manifest_only {
+            let lockfile_path = manifest_path.with_file_name("agpm.lock");
+            if lockfile_path.exists() {
+                let lockfile = crate::lockfile::LockFile::load(&lockfile_path)?;
+                let mut commits: Vec<(String, String, String)> = lockfile
+                    .all_resources()
+                    .into_iter()
+                    .filter_map(|resource| {
+                        let source = resource.source.clone()?;
+                        let sha = resource.resolved_commit.clone()?;
+                        let url = source_manager
+                            .get_source_url(&source)
+                            .or_else(|| resource.url.clone())?;
+                        Some((source, url, sha))
+                    })
+                    .collect();
+                commits.sort();
+                commits.dedup();
+
+                println!("🔥 Checking out {} locked commit(s)...", commits.len());
+                let mut results: Vec<_> = stream::iter(&commits)
+                    .map(|(source, url, sha)| {
+                        let cache = &cache;
+                        async move {
+                            let result = cache
+                                .get_or_create_worktree_for_sha(source, url, sha, Some("warm"))
+                                .await;
+                            (source, sha, result)
+                        }
+                    })
+                    .buffer_unordered(max_concurrency)
+                    .collect()
+                    .await;
+                results.sort_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)));
+                for (source, sha, result) in results {
+                    let short = &sha[..sha.len().min(8)];
+                    match result {
+                        Ok(_) => println!("  {} {source} @ {short}", "✓".green()),
+                        Err(e) => {
+                            failed += 1;
+                            println!("  {} {source} @ {short}: {e}", "✗".red());
+                        }
+                    }
+                }
+            } else {
+                eprintln!(
+                    "{} No agpm.lock found; run 'agpm install' to lock commits, then warm again",
+                    "⚠".yellow()
+                );
+            }
+        }
+
+        let size = cache.get_cache_size().await?;
+        println!("\n{} {}", "Cache size:".bold(), format_size(size));
+
+        if failed > 0 {
+            return Err(anyhow::anyhow!("{failed} source(s) or commit(s) could not be warmed"));
+        }
+        println!("{}", "✅ Cache is warm".green().bold());
+        Ok(())
+    }
+
+    /// Print the cache locations of a single source.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache` - The cache instance to inspect
+    /// * `source` - Source name or Git URL
+    /// * `manifest_path` - Optional path to the manifest used to look up the source
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the source was found and its locations were printed
+    /// - `Err(anyhow::Error)` if the source is unknown or the cache cannot be read
+    async fn show_which(
+        &self,
+        cache: Cache,
+        source: &str,
+        manifest_path: Option<PathBuf>,
+    ) -> Result<()> {
+        let url = source_url(source, manifest_path).await?;
+        let bare_repo = cache.get_bare_repo_path(&url)?;
+        let worktrees = cache.source_worktrees(&url).await?;
+
+        let display_url = crate::git::strip_auth_from_url(&url).unwrap_or_else(|_| url.clone());
+        println!("{} {source}", "Source:".bold());
+        println!("  URL: {display_url}");
+        let status = if bare_repo.exists() {
+            ""
+        } else {
+            " (not cloned)"
+        };
+        println!("  Bare repository: {}{}", bare_repo.display(), status.bright_black());
+
+        if worktrees.is_empty() {
+            println!("  Worktrees: none");
+            return Ok(());
+        }
+        println!("  Worktrees:");
+        for worktree in &worktrees {
+            println!(
+                "    {}  {}  {}",
+                worktree.sha,
+                worktree.path.display(),
+                format!("last used {}", format_last_used(worktree.last_used)).bright_black()
+            );
+        }
+        Ok(())
+    }
+
+    /// Register an existing checkout as the cached worktree for its commit.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache` - The cache instance to register the checkout in
+    /// * `source` - Source name or Git URL the checkout belongs to
+    /// * `path` - Directory of the checkout
+    /// * `commit` - Commit the checkout must be at, if given
+    /// * `manifest_path` - Optional path to the manifest used to look up the source
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the checkout was registered
+    /// - `Err(anyhow::Error)` if the source is unknown or the checkout is rejected
+    async fn import_worktree(
+        &self,
+        cache: Cache,
+        source: &str,
+        path: &Path,
+        commit: Option<&str>,
+        manifest_path: Option<PathBuf>,
+    ) -> Result<()> {
+        let url = source_url(source, manifest_path).await?;
+        let sha = cache.import_worktree(&url, path, commit).await?;
+
+        println!("{} Imported {} as {source} @ {}", "✓".green(), path.display(), &sha[..8]);
+        eprintln!(
+            "{} AGPM does not manage this directory: it is never updated or removed, and \
+             installs fall back to the cache if it changes commit",
+            "⚠".yellow()
+        );
         Ok(())
     }
 }
 
+/// Find the URL of a source by name.
+///
+/// The project manifest takes precedence over the global config. An argument
+/// that is itself a Git URL is returned unchanged.
+async fn source_url(source: &str, manifest_path: Option<PathBuf>) -> Result<String> {
+    if let Some(url) = find_manifest_with_optional(manifest_path)
+        .ok()
+        .and_then(|path| Manifest::load(&path).ok())
+        .and_then(|manifest| manifest.sources.get(source).cloned())
+    {
+        return Ok(url);
+    }
+
+    if let Ok(config) = crate::config::GlobalConfig::load().await
+        && let Some(url) = config.sources.resolved_url(source)?
+    {
+        return Ok(url);
+    }
+
+    if source.contains("://") || source.starts_with("git@") {
+        return Ok(source.to_string());
+    }
+
+    anyhow::bail!(
+        "Unknown source '{source}'. Add it to [sources] in agpm.toml or the global config, \
+         or pass its Git URL"
+    )
+}
+
+/// Collect source URLs keyed by their cache directory name (`owner_repo`).
+///
+/// URLs come from the lockfile, the manifest, and the global config, with later
+/// sources taking precedence. Missing or unreadable files are skipped, since
+/// repair is best-effort.
+async fn known_source_urls(manifest_path: Option<PathBuf>) -> HashMap<String, String> {
+    let mut urls = Vec::new();
+
+    if let Ok(manifest_path) = find_manifest_with_optional(manifest_path) {
+        let lockfile_path = manifest_path.with_file_name("agpm.lock");
+        if let Ok(lockfile) = crate::lockfile::LockFile::load(&lockfile_path) {
+            urls.extend(lockfile.sources.into_iter().map(|source| source.url));
+        }
+        if let Ok(manifest) = Manifest::load(&manifest_path) {
+            urls.extend(manifest.sources.into_values());
+        }
+    }
+
+    // Global sources last: they may carry the credentials needed to clone
+    if let Ok(config) = crate::config::GlobalConfig::load().await {
+        urls.extend(
+            config.sources.keys().filter_map(|name| config.sources.resolved_url(name).ok()?),
+        );
+    }
+
+    urls.into_iter()
+        .filter_map(|url| {
+            let (owner, repo) = crate::git::parse_git_url(&url).ok()?;
+            Some((format!("{owner}_{repo}"), url))
+        })
+        .collect()
+}
+
+/// Format a registry timestamp (seconds since the Unix epoch) for display.
+fn format_last_used(last_used: Option<u64>) -> String {
+    last_used
+        .and_then(|secs| i64::try_from(secs).ok())
+        .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+        .map_or_else(|| "never".to_string(), |time| time.format("%Y-%m-%d %H:%M UTC").to_string())
+}
+
 /// Format byte size into human-readable string with appropriate units.
 ///
 /// This function converts raw byte values into human-readable format using
@@ -674,7 +1288,8 @@ mod tests {
             sources: std::collections::HashMap::from([(
                 "active".to_string(),
                 "https://github.com/test/active.git".to_string(),
-            )]),
+            )])
+            .into(),
             ..Default::default()
         };
         let manifest_path = work_dir.path().join("agpm.toml");
@@ -774,6 +1389,50 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_cache_stats_command() -> Result<()> {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new()?;
+        let cache = Cache::with_dir(temp_dir.path().to_path_buf())?;
+
+        let worktree = temp_dir.path().join("worktrees").join("owner_repo_abc12345");
+        std::fs::create_dir_all(&worktree)?;
+        std::fs::write(worktree.join("agent.md"), "# Agent")?;
+
+        for format in ["table", "json"] {
+            let cmd = CacheCommand {
+                command: Some(CacheSubcommands::Stats {
+                    format: format.to_string(),
+                }),
+            };
+            cmd.execute_with_cache(cache.clone()).await?;
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cache_verify_empty_cache() -> Result<()> {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new()?;
+        let cache = Cache::with_dir(temp_dir.path().to_path_buf())?;
+
+        let cmd = CacheCommand {
+            command: Some(CacheSubcommands::Verify {
+                repair: false,
+            }),
+        };
+        cmd.execute_with_cache(cache).await?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_last_used() {
+        assert_eq!(format_last_used(None), "never");
+        assert_eq!(format_last_used(Some(0)), "1970-01-01 00:00 UTC");
+    }
+
     #[tokio::test]
     async fn test_cache_execute_without_dir() -> Result<()> {
         // Test CacheCommand::execute which creates its own Cache
@@ -821,7 +1480,8 @@ mod tests {
             sources: std::collections::HashMap::from([
                 ("source1".to_string(), "https://github.com/test/repo1.git".to_string()),
                 ("source2".to_string(), "https://github.com/test/repo2.git".to_string()),
-            ]),
+            ])
+            .into(),
             ..Default::default()
         };
         let manifest_path = work_dir.path().join("agpm.toml");
@@ -945,7 +1605,8 @@ mod tests {
             sources: std::collections::HashMap::from([(
                 "manifest-source".to_string(),
                 "https://github.com/test/repo.git".to_string(),
-            )]),
+            )])
+            .into(),
             ..Default::default()
         };
         let manifest_path = work_dir.path().join("agpm.toml");
@@ -959,11 +1620,15 @@ mod tests {
                     name: "manifest-source".to_string(),
                     url: "https://github.com/test/repo.git".to_string(),
                     fetched_at: chrono::Utc::now().to_string(),
+                    resolved_commit: None,
+                    verified_tags: Vec::new(),
                 },
                 LockedSource {
                     name: "lockfile-only".to_string(),
                     url: "https://github.com/test/other.git".to_string(),
                     fetched_at: chrono::Utc::now().to_string(),
+                    resolved_commit: None,
+                    verified_tags: Vec::new(),
                 },
             ],
             agents: vec![],
@@ -972,6 +1637,7 @@ mod tests {
             mcp_servers: vec![],
             scripts: vec![],
             hooks: vec![],
+            disabled: vec![],
         };
         lockfile.save(&work_dir.path().join("agpm.lock"))?;
 
@@ -1032,6 +1698,28 @@ mod tests {
         assert_eq!(format_size(1099511627776), "1.0 TB");
     }
 
+    #[tokio::test]
+    async fn test_source_url_from_manifest_or_url() -> Result<()> {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new()?;
+        let manifest_path = temp_dir.path().join("agpm.toml");
+        std::fs::write(
+            &manifest_path,
+            "[sources]\ncommunity = \"https://github.com/org/community.git\"\n",
+        )?;
+
+        assert_eq!(
+            source_url("community", Some(manifest_path.clone())).await?,
+            "https://github.com/org/community.git"
+        );
+        assert_eq!(
+            source_url("https://github.com/org/other.git", Some(manifest_path)).await?,
+            "https://github.com/org/other.git"
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_cache_path_display() -> Result<()> {
         use tempfile::TempDir;
@@ -0,0 +1,142 @@
+//! Remove everything AGPM installed into the current project.
+//!
+//! This module provides the `clean` command, which returns a project to the
+//! state before `agpm install` without touching `agpm.toml`. Every file
+//! recorded in `agpm.lock` is deleted, together with directories left empty
+//! by the removal, and the AGPM-managed hook and MCP server entries are
+//! removed from the shared configuration files. User-authored files and
+//! entries are never touched: only paths and entries the lockfile records
+//! are removed. Recorded files that are already gone are reported.
+//!
+//! With `--lockfile`, `agpm.lock` and `agpm.private.lock` are deleted as well,
+//! so the next install resolves every dependency from scratch.
+//!
+//! This is unrelated to `agpm cache clean`, which prunes the global Git cache.
+//!
+//! # Examples
+//!
+//! ```bash
+//! # Remove installed resources, keep agpm.lock
+//! agpm clean
+//!
+//! # Start completely fresh
+//! agpm clean --lockfile
+//! ```
+
+use anyhow::{Context, Result};
+use clap::Args;
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+use crate::core::ResourceType;
+use crate::installer::{cleanup_removed_artifacts, cleanup_removed_merged_entries};
+use crate::lockfile::{LockFile, PrivateLockFile};
+use crate::manifest::{Manifest, find_manifest_with_optional};
+
+/// Command to remove installed resources from the current project.
+#[derive(Args)]
+pub struct CleanCommand {
+    /// Also delete agpm.lock and agpm.private.lock
+    #[arg(long)]
+    lockfile: bool,
+}
+
+impl CleanCommand {
+    /// Execute the clean command with an optional manifest path.
+    pub async fn execute_with_manifest_path(self, manifest_path: Option<PathBuf>) -> Result<()> {
+        let manifest_path = find_manifest_with_optional(manifest_path)
+            .context("No agpm.toml found. Please create one to define your dependencies.")?;
+        let manifest = Manifest::load(&manifest_path)?;
+        let project_dir =
+            manifest_path.parent().context("Manifest path has no parent directory")?;
+
+        clean_project(&manifest, project_dir, self.lockfile).await
+    }
+}
+
+/// Remove the resources recorded in `project_dir`'s lockfile, and optionally the lockfile.
+async fn clean_project(
+    manifest: &Manifest,
+    project_dir: &Path,
+    remove_lockfile: bool,
+) -> Result<()> {
+    let lockfile_path = project_dir.join("agpm.lock");
+    if !lockfile_path.exists() {
+        println!("No agpm.lock found, nothing to clean");
+        return Ok(());
+    }
+    let lockfile = LockFile::load(&lockfile_path)?;
+
+    for entry in lockfile.all_resources() {
+        let merged = matches!(entry.resource_type, ResourceType::Hook | ResourceType::McpServer);
+        if merged || entry.install == Some(false) || entry.installed_at.is_empty() {
+            continue;
+        }
+        if !project_dir.join(&entry.installed_at).exists() {
+            eprintln!("{} Recorded file is missing: {}", "⚠".yellow(), entry.installed_at);
+        }
+    }
+
+    // Diffing against an empty lockfile drops every recorded resource
+    let empty = LockFile::new();
+    let removed = cleanup_removed_artifacts(&lockfile, &empty, project_dir).await?;
+    let (hooks, servers) =
+        cleanup_removed_merged_entries(&lockfile, &empty, manifest, project_dir)?;
+
+    println!("{} Removed {} installed file(s)", "✓".green(), removed.len());
+    if hooks + servers > 0 {
+        println!(
+            "{} Removed {hooks} hook(s) and {servers} MCP server(s) from configuration",
+            "✓".green()
+        );
+    }
+
+    if remove_lockfile {
+        std::fs::remove_file(&lockfile_path)
+            .with_context(|| format!("Failed to remove {}", lockfile_path.display()))?;
+        // Saving an empty private lockfile deletes it
+        PrivateLockFile::new().save(project_dir)?;
+        println!("{} Removed agpm.lock", "✓".green());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lockfile::LockedResourceBuilder;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_clean_removes_only_recorded_files() {
+        let temp = TempDir::new().unwrap();
+        let project = temp.path();
+        let agents = project.join(".claude/agents");
+        std::fs::create_dir_all(&agents).unwrap();
+        std::fs::write(agents.join("reviewer.md"), "installed").unwrap();
+        std::fs::write(agents.join("mine.md"), "user file").unwrap();
+
+        let mut lockfile = LockFile::new();
+        for name in ["reviewer", "missing"] {
+            lockfile.agents.push(
+                LockedResourceBuilder::new(
+                    name.to_string(),
+                    format!("agents/{name}.md"),
+                    String::new(),
+                    format!(".claude/agents/{name}.md"),
+                    ResourceType::Agent,
+                )
+                .build(),
+            );
+        }
+        lockfile.save(&project.join("agpm.lock")).unwrap();
+
+        clean_project(&Manifest::new(), project, false).await.unwrap();
+        assert!(!agents.join("reviewer.md").exists());
+        assert!(agents.join("mine.md").exists());
+        assert!(project.join("agpm.lock").exists());
+
+        clean_project(&Manifest::new(), project, true).await.unwrap();
+        assert!(!project.join("agpm.lock").exists());
+    }
+}
@@ -183,6 +183,31 @@ impl CommandContext {
         &self,
         can_regenerate: bool,
         operation_name: &str,
+    ) -> Result<Option<crate::lockfile::LockFile>> {
+        self.load_lockfile_checked(can_regenerate, operation_name, false)
+    }
+
+    /// Load an existing lockfile like [`Self::load_lockfile_with_regeneration`],
+    /// saving a lockfile in an older format back in the current format.
+    ///
+    /// For commands that write the lockfile anyway, such as `agpm install`, so
+    /// the format migration only runs once.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lockfile is invalid and can't be regenerated.
+    pub fn load_lockfile_persisting_migration(
+        &self,
+        operation_name: &str,
+    ) -> Result<Option<crate::lockfile::LockFile>> {
+        self.load_lockfile_checked(true, operation_name, true)
+    }
+
+    fn load_lockfile_checked(
+        &self,
+        can_regenerate: bool,
+        operation_name: &str,
+        persist_migration: bool,
     ) -> Result<Option<crate::lockfile::LockFile>> {
         // If lockfile doesn't exist, that's not an error
         if !self.lockfile_path.exists() {
@@ -190,7 +215,12 @@ impl CommandContext {
         }
 
         // Try to load the lockfile
-        match crate::lockfile::LockFile::load(&self.lockfile_path) {
+        let loaded = if persist_migration {
+            crate::lockfile::LockFile::load_and_persist_migration(&self.lockfile_path)
+        } else {
+            crate::lockfile::LockFile::load(&self.lockfile_path)
+        };
+        match loaded {
             Ok(lockfile) => Ok(Some(lockfile)),
             Err(e) => {
                 // Analyze the error to see if it's recoverable
@@ -436,6 +466,23 @@ pub async fn handle_legacy_ccpm_migration(from_dir: Option<PathBuf>) -> Result<O
     }
 }
 
+/// The lockfile a command reads and writes for `--lockfile` and `--profile`.
+///
+/// An explicit `lockfile` (resolved against `project_dir`) wins, then the
+/// profile's own `agpm.<profile>.lock`, then `agpm.lock`.
+#[must_use]
+pub fn selected_lockfile_path(
+    project_dir: &Path,
+    lockfile: Option<&Path>,
+    profile: Option<&str>,
+) -> PathBuf {
+    match (lockfile, profile) {
+        (Some(path), _) => project_dir.join(path),
+        (None, Some(profile)) => project_dir.join(format!("agpm.{profile}.lock")),
+        (None, None) => project_dir.join("agpm.lock"),
+    }
+}
+
 /// Check for legacy CCPM files and return a migration message if found.
 ///
 /// This function searches for ccpm.toml and ccpm.lock files in the current
@@ -907,6 +954,7 @@ fetched_at = "2024-01-01T00:00:00Z"
             commands: vec![],
             scripts: vec![],
             hooks: vec![],
+            disabled: vec![],
             mcp_servers: vec![],
         };
 
@@ -914,7 +962,7 @@ fetched_at = "2024-01-01T00:00:00Z"
 
         assert!(context.lockfile_path.exists());
         let saved_content = std::fs::read_to_string(&context.lockfile_path).unwrap();
-        assert!(saved_content.contains("version = 1"));
+        assert!(saved_content.contains("version = 2"));
     }
 
     #[test]
@@ -0,0 +1,420 @@
+//! Show how a resource's content differs between versions.
+//!
+//! This module provides the `diff` command for auditing changes before
+//! accepting them. With a spec of the form `source:path@from..to` it checks
+//! out both refs of the source in cache worktrees and prints a unified diff of
+//! the file between them, which shows what a dependency update would change.
+//!
+//! Without arguments it looks for local modifications instead: every
+//! installed file whose checksum no longer matches `agpm.lock` is diffed
+//! against the file at its locked commit (or its local path, for resources
+//! without a source). Resources that are rendered from templates or patched
+//! also show those changes, since the locked source is the unrendered file.
+//! Hooks and MCP servers are not compared.
+//!
+//! # Examples
+//!
+//! ```bash
+//! # What changed upstream between two releases
+//! agpm diff community:agents/reviewer.md@v1.0.0..v1.1.0
+//!
+//! # Which installed files were edited locally
+//! agpm diff
+//! ```
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+use crate::cache::Cache;
+use crate::config::GlobalConfig;
+use crate::core::ResourceType;
+use crate::git::GitRepo;
+use crate::lockfile::{LockFile, LockedResource};
+use crate::manifest::{Manifest, find_manifest_with_optional};
+
+/// Unchanged lines shown before and after each change.
+const CONTEXT_LINES: usize = 3;
+
+/// Command to compare a resource's content between versions.
+#[derive(Args)]
+pub struct DiffCommand {
+    /// Resource and refs to compare, as `source:path@from..to`
+    ///
+    /// Refs may be tags, branches or commits. Without a spec, installed files
+    /// are compared against their locked versions.
+    #[arg(value_name = "SPEC")]
+    spec: Option<String>,
+}
+
+/// A parsed `source:path@from..to` spec.
+#[derive(Debug, PartialEq, Eq)]
+struct RangeSpec {
+    source: String,
+    path: String,
+    from: String,
+    to: String,
+}
+
+impl DiffCommand {
+    /// Execute the diff command with an optional manifest path.
+    pub async fn execute_with_manifest_path(self, manifest_path: Option<PathBuf>) -> Result<()> {
+        let manifest_path = find_manifest_with_optional(manifest_path)
+            .context("No agpm.toml found. Please create one to define your dependencies.")?;
+        let manifest = Manifest::load(&manifest_path)?;
+        let project_dir =
+            manifest_path.parent().context("Manifest path has no parent directory")?.to_path_buf();
+
+        let cache = Cache::new()?;
+        cache.configure_sources(&manifest.sources).await;
+        cache.replace_sources(manifest.replacement_urls()?).await;
+
+        match self.spec {
+            Some(spec) => diff_versions(&parse_range_spec(&spec)?, &manifest, &cache).await,
+            None => diff_installed(&project_dir, &cache).await,
+        }
+    }
+}
+
+/// Parse `source:path@from..to`.
+fn parse_range_spec(spec: &str) -> Result<RangeSpec> {
+    let invalid = || {
+        anyhow::anyhow!(
+            "Invalid diff spec '{spec}'. Expected source:path@from..to \
+             (e.g. community:agents/reviewer.md@v1.0.0..v1.1.0)"
+        )
+    };
+    let (source, rest) = spec.split_once(':').ok_or_else(invalid)?;
+    let (path, range) = rest.rsplit_once('@').ok_or_else(invalid)?;
+    let (from, to) = range.split_once("..").ok_or_else(invalid)?;
+    if [source, path, from, to].iter().any(|part| part.is_empty()) {
+        return Err(invalid());
+    }
+    Ok(RangeSpec {
+        source: source.to_string(),
+        path: path.to_string(),
+        from: from.to_string(),
+        to: to.to_string(),
+    })
+}
+
+/// Print the diff of one file between two refs of a source.
+async fn diff_versions(spec: &RangeSpec, manifest: &Manifest, cache: &Cache) -> Result<()> {
+    let sources =
+        GlobalConfig::load().await.unwrap_or_default().merge_sources(&manifest.sources)?;
+    let url = sources.get(&spec.source).with_context(|| {
+        format!(
+            "Unknown source '{}'. Add it to [sources] in agpm.toml or the global config",
+            spec.source
+        )
+    })?;
+
+    let bare_repo = cache.get_or_clone_source(&spec.source, url, None).await?;
+    let repo = GitRepo::new(&bare_repo);
+    let mut contents = [None, None];
+    for (content, reference) in contents.iter_mut().zip([&spec.from, &spec.to]) {
+        let sha = repo
+            .resolve_to_sha(Some(reference))
+            .await
+            .with_context(|| format!("Cannot resolve '{reference}' in source '{}'", spec.source))?;
+        let worktree =
+            cache.get_or_create_worktree_for_sha(&spec.source, url, &sha, Some(&spec.path)).await?;
+        *content = read_if_exists(&worktree.join(&spec.path))?;
+    }
+
+    let [old, new] = contents;
+    if old.is_none() && new.is_none() {
+        bail!(
+            "'{}' does not exist at '{}' or '{}' in source '{}'",
+            spec.path,
+            spec.from,
+            spec.to,
+            spec.source
+        );
+    }
+
+    let diff = unified_diff(
+        &file_label("a", &spec.path, &spec.from, old.is_some()),
+        &file_label("b", &spec.path, &spec.to, new.is_some()),
+        old.as_deref().unwrap_or_default(),
+        new.as_deref().unwrap_or_default(),
+    );
+    if diff.is_empty() {
+        println!("No differences in {} between {} and {}", spec.path, spec.from, spec.to);
+    } else {
+        print_diff(&diff);
+    }
+    Ok(())
+}
+
+/// Label for one side of a version diff, `/dev/null` if the file is absent.
+fn file_label(side: &str, path: &str, reference: &str, exists: bool) -> String {
+    if exists {
+        format!("{side}/{path} ({reference})")
+    } else {
+        "/dev/null".to_string()
+    }
+}
+
+/// Print the diff of every locally modified installed file.
+async fn diff_installed(project_dir: &Path, cache: &Cache) -> Result<()> {
+    let lockfile_path = project_dir.join("agpm.lock");
+    if !lockfile_path.exists() {
+        bail!("No agpm.lock found. Run 'agpm install' first to generate it.");
+    }
+    let lockfile = LockFile::load(&lockfile_path)?;
+
+    let mut modified = 0;
+    for entry in lockfile.all_resources() {
+        // Hooks and MCP servers are merged into shared config files
+        if matches!(entry.resource_type, ResourceType::Hook | ResourceType::McpServer) {
+            continue;
+        }
+        let installed = project_dir.join(&entry.installed_at);
+        if entry.checksum.is_empty() || !installed.is_file() {
+            continue;
+        }
+        if LockFile::compute_checksum_cached(&installed)? == entry.checksum {
+            continue;
+        }
+
+        let locked = read_if_exists(&locked_source_file(entry, project_dir, cache).await?)?;
+        let current = std::fs::read_to_string(&installed)
+            .with_context(|| format!("Failed to read {}", installed.display()))?;
+        let locked_label = match entry.resolved_commit.as_deref() {
+            Some(commit) if !commit.is_empty() => {
+                format!("a/{} ({})", entry.installed_at, &commit[..commit.len().min(7)])
+            }
+            _ => format!("a/{} (locked)", entry.installed_at),
+        };
+        let diff = unified_diff(
+            &locked_label,
+            &format!("b/{}", entry.installed_at),
+            locked.as_deref().unwrap_or_default(),
+            &current,
+        );
+        if !diff.is_empty() {
+            print_diff(&diff);
+        }
+        modified += 1;
+    }
+
+    if modified == 0 {
+        println!("{} No installed resources differ from agpm.lock", "✓".green());
+    } else {
+        eprintln!("{} {modified} installed resource(s) differ from agpm.lock", "⚠".yellow());
+    }
+    Ok(())
+}
+
+/// The file a locked resource was installed from.
+async fn locked_source_file(
+    entry: &LockedResource,
+    project_dir: &Path,
+    cache: &Cache,
+) -> Result<PathBuf> {
+    let commit = entry.resolved_commit.as_deref().filter(|commit| !commit.is_empty());
+    match (&entry.source, &entry.url, commit) {
+        (Some(source), Some(url), Some(commit)) => Ok(cache
+            .get_or_create_worktree_for_sha(source, url, commit, Some(&entry.name))
+            .await?
+            .join(&entry.path)),
+        _ => Ok(project_dir.join(&entry.path)),
+    }
+}
+
+/// Read a file, returning `None` if it does not exist.
+fn read_if_exists(path: &Path) -> Result<Option<String>> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+/// Print a unified diff with removed lines in red and added lines in green.
+fn print_diff(diff: &str) {
+    for line in diff.lines() {
+        if line.starts_with("---") || line.starts_with("+++") {
+            println!("{}", line.bold());
+        } else if line.starts_with("@@") {
+            println!("{}", line.cyan());
+        } else if line.starts_with('-') {
+            println!("{}", line.red());
+        } else if line.starts_with('+') {
+            println!("{}", line.green());
+        } else {
+            println!("{line}");
+        }
+    }
+}
+
+/// One line of a line-based diff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum DiffOp {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Line-level edit script from `old` to `new`, from their longest common subsequence.
+pub(super) fn diff_lines(old: &[&str], new: &[&str]) -> Vec<DiffOp> {
+    // Common prefix and suffix need no table
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    // lcs[i][j] is the LCS length of old_mid[i..] and new_mid[j..]
+    let width = new_mid.len() + 1;
+    let mut lcs = vec![0u32; (old_mid.len() + 1) * width];
+    for i in (0..old_mid.len()).rev() {
+        for j in (0..new_mid.len()).rev() {
+            lcs[i * width + j] = if old_mid[i] == new_mid[j] {
+                lcs[(i + 1) * width + j + 1] + 1
+            } else {
+                lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut ops = vec![DiffOp::Equal; prefix];
+    let (mut i, mut j) = (0, 0);
+    while i < old_mid.len() || j < new_mid.len() {
+        if i < old_mid.len() && j < new_mid.len() && old_mid[i] == new_mid[j] {
+            ops.push(DiffOp::Equal);
+            i += 1;
+            j += 1;
+        } else if i < old_mid.len()
+            && (j == new_mid.len() || lcs[(i + 1) * width + j] >= lcs[i * width + j + 1])
+        {
+            ops.push(DiffOp::Delete);
+            i += 1;
+        } else {
+            ops.push(DiffOp::Insert);
+            j += 1;
+        }
+    }
+    ops.extend(std::iter::repeat_n(DiffOp::Equal, suffix));
+    ops
+}
+
+/// Render a unified diff of `old` and `new`, or an empty string if they are equal.
+fn unified_diff(old_label: &str, new_label: &str, old: &str, new: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = diff_lines(&old_lines, &new_lines);
+    let changes: Vec<usize> = (0..ops.len()).filter(|&k| ops[k] != DiffOp::Equal).collect();
+    let Some(&first_change) = changes.first() else {
+        return String::new();
+    };
+
+    // Line positions in old and new before each op
+    let mut positions = Vec::with_capacity(ops.len() + 1);
+    let (mut old_pos, mut new_pos) = (0, 0);
+    for op in &ops {
+        positions.push((old_pos, new_pos));
+        match op {
+            DiffOp::Equal => {
+                old_pos += 1;
+                new_pos += 1;
+            }
+            DiffOp::Delete => old_pos += 1,
+            DiffOp::Insert => new_pos += 1,
+        }
+    }
+    positions.push((old_pos, new_pos));
+
+    // Group changes whose context would overlap into one hunk
+    let mut hunks = Vec::new();
+    let mut start = first_change.saturating_sub(CONTEXT_LINES);
+    let mut end = first_change + 1;
+    for &change in &changes[1..] {
+        if change > end + 2 * CONTEXT_LINES {
+            hunks.push((start, (end + CONTEXT_LINES).min(ops.len())));
+            start = change - CONTEXT_LINES;
+        }
+        end = change + 1;
+    }
+    hunks.push((start, (end + CONTEXT_LINES).min(ops.len())));
+
+    let mut out = format!("--- {old_label}\n+++ {new_label}\n");
+    for (start, end) in hunks {
+        let (old_start, new_start) = positions[start];
+        let (old_end, new_end) = positions[end];
+        let range = |pos: usize, count: usize| {
+            if count == 0 {
+                format!("{pos},0")
+            } else {
+                format!("{},{count}", pos + 1)
+            }
+        };
+        out.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            range(old_start, old_end - old_start),
+            range(new_start, new_end - new_start)
+        ));
+        for (k, op) in ops.iter().enumerate().take(end).skip(start) {
+            let (old_pos, new_pos) = positions[k];
+            let line = match op {
+                DiffOp::Equal => format!(" {}", old_lines[old_pos]),
+                DiffOp::Delete => format!("-{}", old_lines[old_pos]),
+                DiffOp::Insert => format!("+{}", new_lines[new_pos]),
+            };
+            out.push_str(&line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_range_spec() {
+        assert_eq!(
+            parse_range_spec("community:agents/reviewer.md@v1.0.0..v1.1.0").unwrap(),
+            RangeSpec {
+                source: "community".to_string(),
+                path: "agents/reviewer.md".to_string(),
+                from: "v1.0.0".to_string(),
+                to: "v1.1.0".to_string(),
+            }
+        );
+        for invalid in [
+            "agents/reviewer.md@v1.0.0..v1.1.0",
+            "community:agents/reviewer.md",
+            "community:agents/reviewer.md@v1.0.0",
+            "community:agents/reviewer.md@..v1.1.0",
+        ] {
+            assert!(parse_range_spec(invalid).is_err(), "{invalid} should be rejected");
+        }
+    }
+
+    #[test]
+    fn test_unified_diff_hunks() {
+        assert_eq!(unified_diff("a", "b", "same\n", "same\n"), "");
+
+        let old: String = (1..=20).map(|n| format!("line {n}\n")).collect();
+        let new = old.replace("line 2\n", "line two\n").replace("line 18\n", "");
+        let diff = unified_diff("a/agent.md", "b/agent.md", &old, &new);
+        assert_eq!(
+            diff,
+            "--- a/agent.md\n+++ b/agent.md\n\
+             @@ -1,5 +1,5 @@\n line 1\n-line 2\n+line two\n line 3\n line 4\n line 5\n\
+             @@ -15,6 +15,5 @@\n line 15\n line 16\n line 17\n-line 18\n line 19\n line 20\n"
+        );
+
+        // A new file is all additions
+        let diff = unified_diff("/dev/null", "b/new.md", "", "one\ntwo\n");
+        assert!(diff.contains("@@ -0,0 +1,2 @@\n+one\n+two\n"), "{diff}");
+    }
+}
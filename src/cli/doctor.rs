@@ -0,0 +1,373 @@
+//! Diagnose the environment AGPM runs in.
+//!
+//! This module provides the `doctor` command, which checks the things AGPM
+//! depends on before a failed install has to point them out: the Git binary
+//! and its version, the cache directory, the global configuration file, and
+//! whether each configured source can be reached.
+//!
+//! Every check reports pass, warning, or failure together with a suggested
+//! fix. The command exits with a non-zero status if any check fails; warnings
+//! alone do not affect the exit status.
+//!
+//! # Examples
+//!
+//! ```bash
+//! # Run all checks
+//! agpm doctor
+//!
+//! # Skip the network checks
+//! agpm doctor --offline
+//! ```
+
+use anyhow::{Result, bail};
+use clap::Args;
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::config::GlobalConfig;
+use crate::git::command_builder::{GitCommand, git_settings};
+use crate::manifest::{Manifest, find_manifest_with_optional};
+use crate::utils::is_local_path;
+
+/// Oldest Git release AGPM supports.
+///
+/// Git 2.17 added `git worktree remove`, which AGPM uses to clean up the
+/// worktrees it checks out resources from.
+const MIN_GIT_VERSION: (u32, u32) = (2, 17);
+
+/// How long to wait for a source to answer `git ls-remote`.
+const SOURCE_CHECK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Command to diagnose the AGPM environment.
+#[derive(Args)]
+pub struct DoctorCommand {
+    /// Skip checks that need network access
+    #[arg(long)]
+    offline: bool,
+}
+
+/// Outcome of a single check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// Result of a single check, with a suggested fix for warnings and failures.
+#[derive(Debug)]
+struct CheckResult {
+    name: String,
+    status: CheckStatus,
+    detail: String,
+    fix: Option<String>,
+}
+
+impl CheckResult {
+    fn pass(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Pass,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn warn(name: impl Into<String>, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Warn,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn fail(name: impl Into<String>, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn print(&self) {
+        let marker = match self.status {
+            CheckStatus::Pass => "✓".green(),
+            CheckStatus::Warn => "⚠".yellow(),
+            CheckStatus::Fail => "✗".red(),
+        };
+        println!("{marker} {}: {}", self.name.bold(), self.detail);
+        if let Some(fix) = &self.fix {
+            println!("    {} {fix}", "→".dimmed());
+        }
+    }
+}
+
+impl DoctorCommand {
+    /// Run all checks and print a checklist.
+    ///
+    /// `config_path` is the `--config` override, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any check failed.
+    pub async fn execute(
+        self,
+        manifest_path: Option<PathBuf>,
+        config_path: Option<PathBuf>,
+    ) -> Result<()> {
+        let mut results = vec![check_git().await];
+        results.extend(check_cache_dir());
+        let (config_result, global) = check_config(config_path).await;
+        results.push(config_result);
+
+        if self.offline {
+            results.push(CheckResult::pass("Sources", "skipped (--offline)"));
+        } else {
+            let sources = configured_sources(manifest_path, global.as_ref());
+            if sources.is_empty() {
+                results.push(CheckResult::pass("Sources", "none configured"));
+            }
+            for (name, url) in &sources {
+                results.push(check_source(name, url).await);
+            }
+        }
+
+        println!("{}", "AGPM environment check".bold());
+        println!();
+        for result in &results {
+            result.print();
+        }
+
+        let failures = results.iter().filter(|r| r.status == CheckStatus::Fail).count();
+        let warnings = results.iter().filter(|r| r.status == CheckStatus::Warn).count();
+        println!();
+        if failures > 0 {
+            bail!("{failures} check(s) failed, {warnings} warning(s)");
+        }
+        if warnings > 0 {
+            println!("{} All checks passed with {warnings} warning(s)", "✓".green());
+        } else {
+            println!("{} All checks passed", "✓".green());
+        }
+        Ok(())
+    }
+}
+
+/// Check that Git can be run and is recent enough.
+async fn check_git() -> CheckResult {
+    let program = git_settings().program().to_string();
+    let output = match GitCommand::new().args(["--version"]).execute_stdout().await {
+        Ok(output) => output,
+        Err(e) => {
+            return CheckResult::fail(
+                "Git",
+                format!("could not run `{program}`: {e}"),
+                "Install Git and make sure it is on PATH, or set git_binary in the global \
+                 config (or AGPM_GIT_BINARY)",
+            );
+        }
+    };
+
+    match parse_git_version(&output) {
+        Some(version) if version < MIN_GIT_VERSION => CheckResult::warn(
+            "Git",
+            format!("{} ({program})", output.trim()),
+            format!(
+                "Upgrade to Git {}.{} or newer; older releases lack worktree features AGPM uses",
+                MIN_GIT_VERSION.0, MIN_GIT_VERSION.1
+            ),
+        ),
+        Some(_) => CheckResult::pass("Git", format!("{} ({program})", output.trim())),
+        None => CheckResult::warn(
+            "Git",
+            format!("unrecognized version output: {}", output.trim()),
+            format!(
+                "Make sure `{program}` is Git {}.{} or newer",
+                MIN_GIT_VERSION.0, MIN_GIT_VERSION.1
+            ),
+        ),
+    }
+}
+
+/// Parse the major and minor version from `git --version` output.
+///
+/// Handles vendor suffixes such as `git version 2.39.3 (Apple Git-145)` and
+/// `git version 2.42.0.windows.2`.
+fn parse_git_version(output: &str) -> Option<(u32, u32)> {
+    let version = output.trim().strip_prefix("git version ")?.split_whitespace().next()?;
+    let mut parts = version.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
+
+/// Check the cache directory, reporting an `AGPM_CACHE_DIR` override first.
+fn check_cache_dir() -> Vec<CheckResult> {
+    let mut results = Vec::new();
+    if let Ok(dir) = std::env::var("AGPM_CACHE_DIR") {
+        results.push(CheckResult::pass("AGPM_CACHE_DIR", format!("set to {dir}")));
+    }
+
+    let result = match crate::config::get_cache_dir() {
+        Ok(dir) => check_dir_writable(&dir),
+        Err(e) => CheckResult::fail(
+            "Cache directory",
+            format!("{e:#}"),
+            "Set AGPM_CACHE_DIR to a writable directory",
+        ),
+    };
+    results.push(result);
+    results
+}
+
+/// Check that `dir` exists and a file can be created in it.
+fn check_dir_writable(dir: &Path) -> CheckResult {
+    if !dir.is_dir() {
+        return CheckResult::fail(
+            "Cache directory",
+            format!("{} does not exist", dir.display()),
+            "Create the directory or set AGPM_CACHE_DIR to a writable directory",
+        );
+    }
+    match tempfile::NamedTempFile::new_in(dir) {
+        Ok(_) => CheckResult::pass("Cache directory", format!("{} (writable)", dir.display())),
+        Err(e) => CheckResult::fail(
+            "Cache directory",
+            format!("{} is not writable: {e}", dir.display()),
+            "Fix the directory permissions or set AGPM_CACHE_DIR to a writable directory",
+        ),
+    }
+}
+
+/// Check that the global config file, if present, can be parsed.
+///
+/// Returns the loaded config so its sources can be checked as well.
+async fn check_config(config_path: Option<PathBuf>) -> (CheckResult, Option<GlobalConfig>) {
+    let path = match config_path.map_or_else(GlobalConfig::default_path, Ok) {
+        Ok(path) => path,
+        Err(e) => {
+            return (
+                CheckResult::fail(
+                    "Config file",
+                    format!("{e:#}"),
+                    "Pass --config with an explicit path",
+                ),
+                None,
+            );
+        }
+    };
+
+    if !path.exists() {
+        return (
+            CheckResult::pass("Config file", format!("{} not found (optional)", path.display())),
+            Some(GlobalConfig::default()),
+        );
+    }
+
+    match GlobalConfig::load_from(&path).await {
+        Ok(config) => (CheckResult::pass("Config file", path.display().to_string()), Some(config)),
+        Err(e) => (
+            CheckResult::fail(
+                "Config file",
+                format!("{} could not be loaded: {e:#}", path.display()),
+                "Fix the TOML syntax, or run `agpm config init` to start from a fresh file",
+            ),
+            None,
+        ),
+    }
+}
+
+/// Collect the sources from the global config and the project manifest.
+///
+/// Project sources override global sources with the same name. A missing or
+/// unreadable manifest contributes no sources.
+fn configured_sources(
+    manifest_path: Option<PathBuf>,
+    global: Option<&GlobalConfig>,
+) -> BTreeMap<String, String> {
+    let mut sources: BTreeMap<String, String> =
+        global.map(|g| g.sources.clone().into_iter().collect()).unwrap_or_default();
+
+    if let Some(manifest) =
+        find_manifest_with_optional(manifest_path).ok().and_then(|path| Manifest::load(&path).ok())
+    {
+        for (name, url) in &manifest.sources {
+            sources.insert(name.clone(), url.clone());
+        }
+    }
+    sources
+}
+
+/// Check that a source can be reached.
+async fn check_source(name: &str, url: &str) -> CheckResult {
+    let label = format!("Source '{name}'");
+    let display_url = crate::git::strip_auth_from_url(url).unwrap_or_else(|_| url.to_string());
+
+    if is_local_path(url) || url.starts_with("file://") {
+        let path = url.strip_prefix("file://").unwrap_or(url);
+        return if Path::new(path).exists() {
+            CheckResult::pass(label, display_url)
+        } else {
+            CheckResult::fail(
+                label,
+                format!("{display_url} does not exist"),
+                "Fix the path in [sources] or create the repository",
+            )
+        };
+    }
+
+    match GitCommand::ls_remote(url)
+        .with_timeout(Some(SOURCE_CHECK_TIMEOUT))
+        .execute_success()
+        .await
+    {
+        Ok(()) => CheckResult::pass(label, format!("{display_url} (reachable)")),
+        Err(e) => CheckResult::fail(
+            label,
+            format!("{display_url} is not reachable: {e}"),
+            "Check the URL, network and credentials (global config sources can hold tokens); \
+             use --offline to skip this check",
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_git_version() {
+        assert_eq!(parse_git_version("git version 2.43.0\n"), Some((2, 43)));
+        assert_eq!(parse_git_version("git version 2.39.3 (Apple Git-145)"), Some((2, 39)));
+        assert_eq!(parse_git_version("git version 2.42.0.windows.2"), Some((2, 42)));
+        assert_eq!(parse_git_version("not git"), None);
+        assert!(parse_git_version("git version 2.5.0").unwrap() < MIN_GIT_VERSION);
+    }
+
+    #[test]
+    fn test_check_dir_writable() {
+        let temp = TempDir::new().unwrap();
+        assert_eq!(check_dir_writable(temp.path()).status, CheckStatus::Pass);
+        assert_eq!(check_dir_writable(&temp.path().join("missing")).status, CheckStatus::Fail);
+    }
+
+    #[tokio::test]
+    async fn test_check_config_reports_parse_errors() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("config.toml");
+
+        let (result, config) = check_config(Some(path.clone())).await;
+        assert_eq!(result.status, CheckStatus::Pass);
+        assert!(config.is_some());
+
+        std::fs::write(&path, "sources = [unclosed").unwrap();
+        let (result, config) = check_config(Some(path)).await;
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(config.is_none());
+    }
+}
@@ -0,0 +1,245 @@
+//! Export an audit report of every locked resource.
+//!
+//! This module provides the `export` command, which reads `agpm.lock` and
+//! emits a flat listing of every resource with its origin, resolved commit
+//! and checksum, for security and compliance reviews. It is a report, not an
+//! input for installation.
+//!
+//! Rows are sorted by resource type, name and install path, so exports of the
+//! same lockfile are byte-identical and diff cleanly between audits. Local
+//! resources are included with empty `source`, `url` and `resolved_commit`
+//! fields. Resources without a checksum are reported on stderr.
+//!
+//! # Examples
+//!
+//! ```bash
+//! # CSV report on stdout
+//! agpm export
+//!
+//! # JSON report written to a file
+//! agpm export --format json --output agpm-audit.json
+//! ```
+
+use anyhow::{Context, Result, bail};
+use clap::{Args, ValueEnum};
+use colored::Colorize;
+use serde::Serialize;
+use std::path::PathBuf;
+
+use crate::lockfile::{LockFile, LockedResource};
+use crate::manifest::find_manifest_with_optional;
+
+/// Output format of the export report.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// Comma-separated values with a header row
+    #[default]
+    Csv,
+    /// A JSON array of objects
+    Json,
+}
+
+/// Command to export an audit report of the lockfile.
+#[derive(Args)]
+pub struct ExportCommand {
+    /// Report format
+    #[arg(long, value_enum, default_value = "csv")]
+    format: ExportFormat,
+
+    /// Write the report to this file instead of stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+/// Column names of the report, in output order.
+const COLUMNS: [&str; 9] = [
+    "resource_type",
+    "name",
+    "source",
+    "url",
+    "path",
+    "version",
+    "resolved_commit",
+    "checksum",
+    "installed_at",
+];
+
+/// One row of the report. Missing values are empty strings so CSV and JSON
+/// exports have the same shape.
+#[derive(Debug, Serialize)]
+struct ExportRow {
+    resource_type: String,
+    name: String,
+    source: String,
+    url: String,
+    path: String,
+    version: String,
+    resolved_commit: String,
+    checksum: String,
+    installed_at: String,
+}
+
+impl ExportRow {
+    fn from_resource(resource: &LockedResource) -> Self {
+        Self {
+            resource_type: resource.resource_type.to_string(),
+            name: resource.name.clone(),
+            source: resource.source.clone().unwrap_or_default(),
+            url: resource.url.clone().unwrap_or_default(),
+            path: resource.path.clone(),
+            version: resource.version.clone().unwrap_or_default(),
+            resolved_commit: resource.resolved_commit.clone().unwrap_or_default(),
+            checksum: resource.checksum.clone(),
+            installed_at: resource.installed_at.clone(),
+        }
+    }
+
+    fn fields(&self) -> [&str; 9] {
+        [
+            &self.resource_type,
+            &self.name,
+            &self.source,
+            &self.url,
+            &self.path,
+            &self.version,
+            &self.resolved_commit,
+            &self.checksum,
+            &self.installed_at,
+        ]
+    }
+}
+
+impl ExportCommand {
+    /// Execute the export command with an optional manifest path.
+    pub async fn execute_with_manifest_path(self, manifest_path: Option<PathBuf>) -> Result<()> {
+        let manifest_path = find_manifest_with_optional(manifest_path)
+            .context("No agpm.toml found. Please create one to define your dependencies.")?;
+        let project_dir =
+            manifest_path.parent().context("Manifest path has no parent directory")?.to_path_buf();
+        let lockfile_path = project_dir.join("agpm.lock");
+        if !lockfile_path.exists() {
+            bail!("No agpm.lock found. Run 'agpm install' first to generate it.");
+        }
+
+        let lockfile = LockFile::load(&lockfile_path)?;
+        let rows = export_rows(&lockfile);
+        for row in rows.iter().filter(|row| row.checksum.is_empty()) {
+            eprintln!(
+                "{} {} '{}' has no checksum in agpm.lock",
+                "⚠".yellow(),
+                row.resource_type,
+                row.name
+            );
+        }
+
+        let report = match self.format {
+            ExportFormat::Csv => render_csv(&rows),
+            ExportFormat::Json => serde_json::to_string_pretty(&rows)? + "\n",
+        };
+
+        match self.output {
+            Some(path) => {
+                std::fs::write(&path, report)
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+                eprintln!(
+                    "{} Exported {} resource(s) to {}",
+                    "✓".green(),
+                    rows.len(),
+                    path.display()
+                );
+            }
+            None => print!("{report}"),
+        }
+        Ok(())
+    }
+}
+
+/// Collect one row per locked resource in a stable order.
+fn export_rows(lockfile: &LockFile) -> Vec<ExportRow> {
+    let mut rows: Vec<ExportRow> =
+        lockfile.all_resources().into_iter().map(ExportRow::from_resource).collect();
+    rows.sort_by(|a, b| {
+        (&a.resource_type, &a.name, &a.installed_at, &a.path).cmp(&(
+            &b.resource_type,
+            &b.name,
+            &b.installed_at,
+            &b.path,
+        ))
+    });
+    rows
+}
+
+/// Render rows as CSV with a header row, quoting fields where needed.
+fn render_csv(rows: &[ExportRow]) -> String {
+    let mut out = COLUMNS.join(",");
+    out.push('\n');
+    for row in rows {
+        let fields: Vec<String> = row.fields().iter().map(|field| csv_field(field)).collect();
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Quote a CSV field if it contains a delimiter, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ResourceType;
+    use crate::lockfile::LockedResourceBuilder;
+
+    fn resource(name: &str, resource_type: ResourceType, checksum: &str) -> LockedResource {
+        LockedResourceBuilder::new(
+            name.to_string(),
+            format!("{}/{name}.md", resource_type.to_plural()),
+            checksum.to_string(),
+            format!(".claude/{}/{name}.md", resource_type.to_plural()),
+            resource_type,
+        )
+        .build()
+    }
+
+    #[test]
+    fn test_export_rows_are_sorted_and_include_local_resources() {
+        let mut lockfile = LockFile::new();
+        let mut remote = resource("reviewer", ResourceType::Agent, "sha256:abc");
+        remote.source = Some("community".to_string());
+        remote.url = Some("https://github.com/org/agents.git".to_string());
+        remote.version = Some("v1.0.0".to_string());
+        remote.resolved_commit = Some("0123456789abcdef".to_string());
+        lockfile.agents.push(remote);
+        lockfile.agents.push(resource("helper", ResourceType::Agent, "sha256:def"));
+        lockfile.commands.push(resource("deploy", ResourceType::Command, ""));
+
+        let rows = export_rows(&lockfile);
+        let names: Vec<_> = rows.iter().map(|row| row.name.as_str()).collect();
+        assert_eq!(names, ["helper", "reviewer", "deploy"]);
+        assert_eq!(rows[0].source, "");
+        assert_eq!(rows[0].resolved_commit, "");
+        assert_eq!(rows[1].resolved_commit, "0123456789abcdef");
+
+        let csv = render_csv(&rows);
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some(COLUMNS.join(",").as_str()));
+        assert_eq!(
+            lines.next(),
+            Some("agent,helper,,,agents/helper.md,,,sha256:def,.claude/agents/helper.md")
+        );
+        assert_eq!(csv, render_csv(&export_rows(&lockfile)));
+    }
+
+    #[test]
+    fn test_csv_field_quoting() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
@@ -0,0 +1,178 @@
+//! Normalize the formatting of `agpm.toml`.
+//!
+//! This module provides the `fmt` command, which rewrites the manifest in a
+//! canonical form so that hand edits and tool edits produce the same file:
+//!
+//! - Dependencies are sorted by name within each resource section.
+//! - A detailed dependency that only sets `path` becomes the simple string
+//!   form (`helper = { path = "../helper.md" }` → `helper = "../helper.md"`).
+//! - Inline tables are spaced uniformly (`{ key = "value", other = 1 }`).
+//!
+//! The file is edited with `toml_edit`, so comments attached to entries move
+//! with them and everything outside the dependency sections is left as is.
+//! With `--check`, nothing is written and the command fails if the file is
+//! not already formatted, which is useful in CI.
+//!
+//! # Examples
+//!
+//! ```bash
+//! # Format agpm.toml in place
+//! agpm fmt
+//!
+//! # Fail if agpm.toml is not formatted
+//! agpm fmt --check
+//! ```
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use colored::Colorize;
+use std::path::PathBuf;
+use toml_edit::{DocumentMut, Item, Value};
+
+use crate::core::ResourceType;
+use crate::core::file_error::{FileOperation, FileResultExt};
+use crate::manifest::find_manifest_with_optional;
+
+/// Command to normalize the formatting of `agpm.toml`.
+#[derive(Args)]
+pub struct FmtCommand {
+    /// Do not write; exit with an error if agpm.toml is not formatted
+    #[arg(long)]
+    check: bool,
+}
+
+impl FmtCommand {
+    /// Execute the fmt command with an optional manifest path.
+    pub async fn execute_with_manifest_path(self, manifest_path: Option<PathBuf>) -> Result<()> {
+        let manifest_path = find_manifest_with_optional(manifest_path)
+            .context("No agpm.toml found. Please create one to define your dependencies.")?;
+        let content = std::fs::read_to_string(&manifest_path).with_file_context(
+            FileOperation::Read,
+            &manifest_path,
+            "reading manifest file",
+            "fmt",
+        )?;
+        let formatted = format_manifest(&content)
+            .with_context(|| format!("Failed to parse {}", manifest_path.display()))?;
+
+        if formatted == content {
+            println!("{} agpm.toml is already formatted", "✓".green());
+            return Ok(());
+        }
+        if self.check {
+            bail!("agpm.toml is not formatted. Run 'agpm fmt' to format it.");
+        }
+
+        std::fs::write(&manifest_path, formatted).with_file_context(
+            FileOperation::Write,
+            &manifest_path,
+            "writing manifest file",
+            "fmt",
+        )?;
+        println!("{} Formatted agpm.toml", "✓".green());
+        Ok(())
+    }
+}
+
+/// Return `content` with every dependency section in canonical form.
+fn format_manifest(content: &str) -> Result<String> {
+    let mut doc: DocumentMut = content.parse()?;
+
+    for resource_type in ResourceType::all() {
+        let Some(section) = doc.get_mut(resource_type.to_plural()).and_then(Item::as_table_mut)
+        else {
+            continue;
+        };
+        section.sort_values();
+
+        for (_, item) in section.iter_mut() {
+            let Some(Value::InlineTable(table)) = item.as_value_mut() else {
+                continue;
+            };
+            let path_only = match table.get("path") {
+                Some(Value::String(path)) if table.len() == 1 => Some(path.value().clone()),
+                _ => None,
+            };
+            match path_only {
+                Some(path) => {
+                    // Keep the decor so a trailing comment stays on the line
+                    let decor = table.decor().clone();
+                    let mut simple = Value::from(path);
+                    *simple.decor_mut() = decor;
+                    *item = Item::Value(simple);
+                }
+                None => table.fmt(),
+            }
+        }
+    }
+
+    Ok(doc.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_format_sorts_and_normalizes_dependencies() {
+        let content = r#"[sources]
+zeta = "https://github.com/org/zeta.git"
+alpha = "https://github.com/org/alpha.git"
+
+[agents]
+# Reviews pull requests
+reviewer = {source="alpha",path="agents/reviewer.md",   version="v1.0.0"}
+helper = { path = "../local/helper.md" } # local copy
+
+[snippets]
+utils = "../snippets/utils.md"
+"#;
+        let formatted = format_manifest(content).unwrap();
+
+        assert_eq!(
+            formatted,
+            r#"[sources]
+zeta = "https://github.com/org/zeta.git"
+alpha = "https://github.com/org/alpha.git"
+
+[agents]
+helper = "../local/helper.md" # local copy
+# Reviews pull requests
+reviewer = { source = "alpha", path = "agents/reviewer.md", version = "v1.0.0" }
+
+[snippets]
+utils = "../snippets/utils.md"
+"#
+        );
+        assert_eq!(format_manifest(&formatted).unwrap(), formatted);
+    }
+
+    #[tokio::test]
+    async fn test_check_fails_without_writing() {
+        let temp = TempDir::new().unwrap();
+        let manifest_path = temp.path().join("agpm.toml");
+        let unformatted = "[agents]\nb = \"b.md\"\na = \"a.md\"\n";
+        std::fs::write(&manifest_path, unformatted).unwrap();
+
+        let check = FmtCommand {
+            check: true,
+        };
+        assert!(check.execute_with_manifest_path(Some(manifest_path.clone())).await.is_err());
+        assert_eq!(std::fs::read_to_string(&manifest_path).unwrap(), unformatted);
+
+        let fmt = FmtCommand {
+            check: false,
+        };
+        fmt.execute_with_manifest_path(Some(manifest_path.clone())).await.unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&manifest_path).unwrap(),
+            "[agents]\na = \"a.md\"\nb = \"b.md\"\n"
+        );
+
+        let check = FmtCommand {
+            check: true,
+        };
+        assert!(check.execute_with_manifest_path(Some(manifest_path)).await.is_ok());
+    }
+}
@@ -0,0 +1,210 @@
+//! Export the resolved dependency graph for visualization.
+//!
+//! This module provides the `graph` command, which reads `agpm.lock` and
+//! emits its dependency graph in Graphviz DOT format. The graph is built from
+//! the same adjacency data as `agpm tree`: one node per locked resource,
+//! labeled `type/name@version` and colored by resource type, and one edge from
+//! each resource to every entry of its `dependencies`.
+//!
+//! Resolution rejects dependency cycles, but a hand-edited or merged lockfile
+//! may still contain one. Resources and edges on a cycle are drawn in red.
+//!
+//! # Examples
+//!
+//! ```bash
+//! # Render the graph as a PNG
+//! agpm graph --dot | dot -Tpng -o deps.png
+//!
+//! # Write the DOT file directly
+//! agpm graph --dot-out deps.dot
+//! ```
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use colored::Colorize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use crate::cli::tree::{TreeBuilder, TreeNode};
+use crate::core::ResourceType;
+use crate::lockfile::LockFile;
+use crate::manifest::find_manifest_with_optional;
+
+/// Command to export the dependency graph of the lockfile.
+#[derive(Args, Debug)]
+pub struct GraphCommand {
+    /// Print the graph in Graphviz DOT format
+    #[arg(long, required_unless_present = "dot_out")]
+    dot: bool,
+
+    /// Write the DOT graph to this file instead of stdout
+    #[arg(long, value_name = "FILE")]
+    dot_out: Option<PathBuf>,
+}
+
+impl GraphCommand {
+    /// Execute the graph command with an optional manifest path.
+    pub async fn execute_with_manifest_path(self, manifest_path: Option<PathBuf>) -> Result<()> {
+        let manifest_path = find_manifest_with_optional(manifest_path)
+            .context("No agpm.toml found. Please create one to define your dependencies.")?;
+        let project_dir =
+            manifest_path.parent().context("Manifest path has no parent directory")?.to_path_buf();
+        let lockfile_path = project_dir.join("agpm.lock");
+        if !lockfile_path.exists() {
+            bail!("No agpm.lock found. Run 'agpm install' first to generate it.");
+        }
+
+        let lockfile = LockFile::load(&lockfile_path)?;
+        let project_name =
+            project_dir.file_name().and_then(|n| n.to_str()).unwrap_or("project").to_string();
+        let nodes = TreeBuilder::new(&lockfile, project_name.clone()).build_graph()?;
+        let dot = render_dot(&project_name, &nodes);
+
+        match self.dot_out {
+            Some(path) => {
+                std::fs::write(&path, dot)
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+                eprintln!(
+                    "{} Wrote graph of {} resource(s) to {}",
+                    "✓".green(),
+                    nodes.len(),
+                    path.display()
+                );
+            }
+            None => print!("{dot}"),
+        }
+        Ok(())
+    }
+}
+
+/// Render `nodes` as a DOT digraph named after the project.
+fn render_dot(project_name: &str, nodes: &HashMap<String, TreeNode>) -> String {
+    // Sort nodes and edges so exports of the same lockfile are identical
+    let nodes: BTreeMap<&String, &TreeNode> = nodes.iter().collect();
+    let edges: BTreeSet<(&str, &str)> = nodes
+        .iter()
+        .flat_map(|(id, node)| {
+            node.dependencies
+                .iter()
+                .filter(|dep| nodes.contains_key(dep))
+                .map(move |dep| (id.as_str(), dep.as_str()))
+        })
+        .collect();
+    let cyclic = cyclic_edges(&edges);
+    let cyclic_nodes: BTreeSet<&str> = cyclic.iter().flat_map(|(from, to)| [*from, *to]).collect();
+
+    let mut out = String::new();
+    let _ = writeln!(out, "digraph {} {{", quote(project_name));
+    out.push_str("    rankdir=LR;\n");
+    out.push_str("    node [shape=box, style=\"rounded,filled\", fontname=\"Helvetica\"];\n");
+    for (id, node) in &nodes {
+        let label = match &node.version {
+            Some(version) => format!("{}/{}@{version}", node.resource_type, node.name),
+            None => format!("{}/{}", node.resource_type, node.name),
+        };
+        let cycle = if cyclic_nodes.contains(id.as_str()) {
+            ", color=\"red\", penwidth=2"
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            out,
+            "    {} [label={}, fillcolor=\"{}\"{cycle}];",
+            quote(id),
+            quote(&label),
+            type_color(node.resource_type)
+        );
+    }
+    for (from, to) in &edges {
+        let cycle = if cyclic.contains(&(*from, *to)) {
+            " [color=\"red\", penwidth=2]"
+        } else {
+            ""
+        };
+        let _ = writeln!(out, "    {} -> {}{cycle};", quote(from), quote(to));
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// The edges that lie on a cycle: those whose target can reach their source.
+fn cyclic_edges<'a>(edges: &BTreeSet<(&'a str, &'a str)>) -> BTreeSet<(&'a str, &'a str)> {
+    let mut adjacency: HashMap<&str, Vec<&str>> = HashMap::new();
+    for &(from, to) in edges {
+        adjacency.entry(from).or_default().push(to);
+    }
+
+    let reaches = |start: &'a str, target: &str| {
+        let mut stack = vec![start];
+        let mut seen = BTreeSet::new();
+        while let Some(node) = stack.pop() {
+            if node == target {
+                return true;
+            }
+            if seen.insert(node) {
+                stack.extend(adjacency.get(node).into_iter().flatten().copied());
+            }
+        }
+        false
+    };
+    edges.iter().filter(|&&(from, to)| reaches(to, from)).copied().collect()
+}
+
+/// Fill color of a resource type's nodes.
+const fn type_color(resource_type: ResourceType) -> &'static str {
+    match resource_type {
+        ResourceType::Agent => "#cfe2ff",
+        ResourceType::Snippet => "#d1e7dd",
+        ResourceType::Command => "#fff3cd",
+        ResourceType::Script => "#e2d9f3",
+        ResourceType::Hook => "#ffe5d0",
+        ResourceType::McpServer => "#d2f4f4",
+    }
+}
+
+/// Quote a DOT identifier.
+fn quote(id: &str) -> String {
+    format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lockfile::{LockedResource, LockedResourceBuilder};
+
+    fn resource(name: &str, resource_type: ResourceType, dependencies: &[&str]) -> LockedResource {
+        let mut resource = LockedResourceBuilder::new(
+            name.to_string(),
+            format!("{}/{name}.md", resource_type.to_plural()),
+            String::new(),
+            format!(".claude/{}/{name}.md", resource_type.to_plural()),
+            resource_type,
+        )
+        .build();
+        resource.version = Some("v1.0.0".to_string());
+        resource.dependencies = dependencies.iter().map(ToString::to_string).collect();
+        resource
+    }
+
+    #[test]
+    fn test_render_dot_highlights_cycles() -> Result<()> {
+        let mut lockfile = LockFile::new();
+        lockfile.agents.push(resource("reviewer", ResourceType::Agent, &["snippet:style"]));
+        lockfile.snippets.push(resource("style", ResourceType::Snippet, &["snippet:rules"]));
+        lockfile.snippets.push(resource("rules", ResourceType::Snippet, &["snippet:style"]));
+
+        let nodes = TreeBuilder::new(&lockfile, "demo".to_string()).build_graph()?;
+        let dot = render_dot("demo", &nodes);
+
+        assert!(dot.starts_with("digraph \"demo\" {\n"));
+        assert!(dot.contains(
+            "\"reviewer@v1.0.0\" [label=\"agent/reviewer@v1.0.0\", fillcolor=\"#cfe2ff\"];"
+        ));
+        assert!(dot.contains("\"reviewer@v1.0.0\" -> \"style@v1.0.0\";"));
+        assert!(dot.contains("\"style@v1.0.0\" -> \"rules@v1.0.0\" [color=\"red\", penwidth=2];"));
+        assert!(dot.contains("\"rules@v1.0.0\" -> \"style@v1.0.0\" [color=\"red\", penwidth=2];"));
+        assert!(dot.ends_with("}\n"));
+        Ok(())
+    }
+}
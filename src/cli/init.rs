@@ -21,6 +21,17 @@
 //! agpm init --force
 //! ```
 //!
+//! Start from a short commented manifest instead of the full default:
+//! ```bash
+//! agpm init --template minimal
+//! ```
+//!
+//! Start from the manifest of an existing project, then install it:
+//! ```bash
+//! agpm init --from https://github.com/org/starter.git
+//! agpm init --from ../other-project
+//! ```
+//!
 //! # Manifest Structure
 //!
 //! The generated manifest contains empty sections for all resource types:
@@ -46,19 +57,53 @@
 //! - Returns error if manifest already exists and `--force` is not used
 //! - Returns error if unable to create the target directory
 //! - Returns error if unable to write the manifest file (permissions, disk space, etc.)
+//! - With `--from`, returns error if the repository cannot be cloned or has no `agpm.toml`
 //!
 //! # Safety
 //!
 //! This command is safe to run and will not overwrite existing files unless `--force` is specified.
 
-use anyhow::{Result, anyhow};
-use clap::Args;
+use anyhow::{Context, Result, anyhow};
+use clap::{Args, ValueEnum};
 use colored::Colorize;
 use std::fs;
-use std::path::PathBuf;
-use toml_edit::{DocumentMut, Item, Table};
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, Item, Table, Value};
 
+use crate::core::ResourceType;
+use crate::git::GitRepo;
 use crate::manifest::tool_config::ToolsConfig;
+use crate::utils::{compute_relative_path, is_local_path, normalize_path};
+
+/// Starter manifest written by `agpm init --template`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum InitTemplate {
+    /// Sources, agents and snippets with commented examples
+    Minimal,
+    /// Every section and the default tool configurations (the default)
+    Full,
+}
+
+/// Short starter manifest for `agpm init --template minimal`.
+const MINIMAL_MANIFEST: &str = r#"# AGPM Manifest
+# This file defines your Claude Code resource dependencies
+
+# Git repositories that resources are installed from
+[sources]
+# community = "https://github.com/aig787/agpm-community.git"
+
+# Agents, each from a source at a version (tag, range, branch or commit)
+[agents]
+# reviewer = { source = "community", path = "agents/code-reviewer.md", version = "v1.0.0" }
+#
+# A pattern dependency installs every file matching a glob
+# rust-agents = { source = "community", path = "agents/rust/*.md", version = "^1.0" }
+
+# Snippets, shared content that agents and commands can include
+[snippets]
+# utils = { source = "community", path = "snippets/utils.md", version = "v1.0.0" }
+# local = "./snippets/local.md"
+"#;
 
 /// Builds the default manifest template programmatically from the actual default configurations.
 ///
@@ -222,6 +267,7 @@ fn build_default_manifest() -> String {
             "agents",
             "# Add your agent dependencies here\n\
              # Example: my-agent = { source = \"official\", path = \"agents/my-agent.md\", version = \"v1.0.0\" }\n\
+             # Pattern: rust-agents = { source = \"official\", path = \"agents/rust/*.md\", version = \"^1.0\" }\n\
              # For OpenCode: my-agent = { source = \"official\", path = \"agents/my-agent.md\", version = \"v1.0.0\", tool = \"opencode\" }\n",
         ),
         (
@@ -286,6 +332,9 @@ fn build_default_manifest() -> String {
 ///     path: None,
 ///     force: false,
 ///     defaults: false,
+///     from: None,
+///     template: None,
+///     no_install: false,
 /// };
 ///
 /// // Initialize in specific directory with force overwrite
@@ -293,6 +342,9 @@ fn build_default_manifest() -> String {
 ///     path: Some(PathBuf::from("./my-project")),
 ///     force: true,
 ///     defaults: false,
+///     from: None,
+///     template: None,
+///     no_install: false,
 /// };
 ///
 /// // Merge defaults into existing manifest
@@ -300,6 +352,9 @@ fn build_default_manifest() -> String {
 ///     path: None,
 ///     force: false,
 ///     defaults: true,
+///     from: None,
+///     template: None,
+///     no_install: false,
 /// };
 /// ```
 #[derive(Args)]
@@ -328,6 +383,24 @@ pub struct InitCommand {
     /// without overwriting customizations.
     #[arg(long)]
     defaults: bool,
+
+    /// Start from the manifest of an existing project
+    ///
+    /// Takes a Git repository URL or a local path (a directory containing
+    /// `agpm.toml`, or the file itself). The manifest is copied into the new
+    /// project and installed. Relative local paths are rewritten to point at
+    /// the same files from the new project; for a Git repository they cannot
+    /// be, and are reported instead.
+    #[arg(long, value_name = "URL|PATH", conflicts_with_all = ["defaults", "template"])]
+    from: Option<String>,
+
+    /// Starter manifest to write (default: full)
+    #[arg(long, value_enum, conflicts_with = "defaults")]
+    template: Option<InitTemplate>,
+
+    /// With --from, don't run `agpm install` after copying the manifest
+    #[arg(long, requires = "from")]
+    no_install: bool,
 }
 
 impl InitCommand {
@@ -460,8 +533,16 @@ impl InitCommand {
             fs::create_dir_all(&target_dir)?;
         }
 
-        // Write the default template (built programmatically from ToolsConfig::default())
-        fs::write(&manifest_path, build_default_manifest())?;
+        if let Some(from) = &self.from {
+            return Self::execute_from(from, &target_dir, &manifest_path, self.no_install).await;
+        }
+
+        // Write the chosen template (the full one is built from ToolsConfig::default())
+        let content = match self.template.unwrap_or(InitTemplate::Full) {
+            InitTemplate::Minimal => MINIMAL_MANIFEST.to_string(),
+            InitTemplate::Full => build_default_manifest(),
+        };
+        fs::write(&manifest_path, content)?;
 
         // Add .agpm/backups/ to .gitignore
         Self::update_gitignore(&target_dir)?;
@@ -479,6 +560,145 @@ impl InitCommand {
         Ok(())
     }
 
+    /// Execute init with --from to copy and install an existing manifest.
+    ///
+    /// A local `from` is read in place; anything else is cloned into a
+    /// temporary directory. Relative local paths in the copied manifest are
+    /// rewritten (local origin) or reported (Git origin) by
+    /// [`Self::rewrite_local_paths`].
+    async fn execute_from(
+        from: &str,
+        target_dir: &Path,
+        manifest_path: &Path,
+        no_install: bool,
+    ) -> Result<()> {
+        let local = Path::new(from);
+        let (content, warnings) = if local.exists() {
+            let starter = if local.is_dir() {
+                local.join("agpm.toml")
+            } else {
+                local.to_path_buf()
+            };
+            let content = fs::read_to_string(&starter)
+                .with_context(|| format!("No agpm.toml found at {}", starter.display()))?;
+            let origin_dir = starter
+                .parent()
+                .map_or_else(|| PathBuf::from("."), Path::to_path_buf)
+                .canonicalize()?;
+            Self::rewrite_local_paths(&content, Some((&origin_dir, &target_dir.canonicalize()?)))?
+        } else {
+            let temp_dir = tempfile::TempDir::new()?;
+            let checkout = temp_dir.path().join("starter");
+            println!("Fetching {from}...");
+            GitRepo::clone(from, &checkout)
+                .await
+                .with_context(|| format!("Failed to fetch starter repository {from}"))?;
+            let content = fs::read_to_string(checkout.join("agpm.toml"))
+                .with_context(|| format!("Repository {from} has no agpm.toml at its root"))?;
+            Self::rewrite_local_paths(&content, None)?
+        };
+
+        fs::write(manifest_path, content)?;
+        Self::update_gitignore(target_dir)?;
+
+        println!(
+            "{} Initialized agpm.toml at {} from {from}",
+            "✓".green(),
+            manifest_path.display()
+        );
+        for warning in &warnings {
+            eprintln!("{} {warning}", "⚠".yellow());
+        }
+
+        if no_install {
+            println!("\n  Run {} to install its dependencies", "agpm install".bright_white());
+            return Ok(());
+        }
+        println!();
+        crate::cli::install::InstallCommand::new()
+            .execute_from_path(Some(manifest_path))
+            .await
+            .context("Initialized agpm.toml, but installing its dependencies failed")
+    }
+
+    /// Rewrite the relative local paths of a copied manifest.
+    ///
+    /// Covers local `[sources]` and dependencies without a `source`. With
+    /// `dirs` set to `(origin_dir, target_dir)`, each relative path is made to
+    /// point at the same file from `target_dir`. Without it (a manifest from a
+    /// Git repository, whose files are not kept), paths are left as they are
+    /// and a warning is returned for each.
+    ///
+    /// # Returns
+    ///
+    /// The rewritten manifest and the warnings to show.
+    fn rewrite_local_paths(
+        content: &str,
+        dirs: Option<(&Path, &Path)>,
+    ) -> Result<(String, Vec<String>)> {
+        let mut doc = content
+            .parse::<DocumentMut>()
+            .map_err(|e| anyhow!("Failed to parse starter manifest: {e}"))?;
+        let mut warnings = Vec::new();
+
+        let mut rewrite = |value: &mut Value, what: String| {
+            let Some(path) = value.as_str() else {
+                return;
+            };
+            match dirs {
+                Some((origin_dir, target_dir)) if Path::new(path).is_relative() => {
+                    let rewritten =
+                        compute_relative_path(target_dir, &normalize_path(&origin_dir.join(path)));
+                    let rewritten = if rewritten.starts_with("../") {
+                        rewritten
+                    } else {
+                        format!("./{rewritten}")
+                    };
+                    let decor = value.decor().clone();
+                    *value = Value::from(rewritten);
+                    *value.decor_mut() = decor;
+                }
+                Some(_) => {}
+                None => warnings.push(format!(
+                    "{what} uses the local path '{path}', which is not part of this project; \
+                     copy the file here or point it at a source"
+                )),
+            }
+        };
+
+        if let Some(sources) = doc.get_mut("sources").and_then(Item::as_table_like_mut) {
+            for (name, item) in sources.iter_mut() {
+                if let Some(value) = item.as_value_mut()
+                    && value.as_str().is_some_and(is_local_path)
+                {
+                    rewrite(value, format!("Source '{name}'"));
+                }
+            }
+        }
+
+        for resource_type in ResourceType::all() {
+            let section = resource_type.to_plural();
+            let Some(table) = doc.get_mut(section).and_then(Item::as_table_like_mut) else {
+                continue;
+            };
+            for (name, item) in table.iter_mut() {
+                let what = format!("{resource_type} '{name}'");
+                if let Some(value) = item.as_value_mut()
+                    && value.is_str()
+                {
+                    rewrite(value, what);
+                } else if let Some(dep) = item.as_table_like_mut()
+                    && !dep.contains_key("source")
+                    && let Some(value) = dep.get_mut("path").and_then(Item::as_value_mut)
+                {
+                    rewrite(value, what);
+                }
+            }
+        }
+
+        Ok((doc.to_string(), warnings))
+    }
+
     /// Execute init with --defaults flag to merge default configurations.
     ///
     /// This method loads an existing manifest, parses the default template, merges
@@ -606,6 +826,9 @@ mod tests {
             path: Some(temp_dir.path().to_path_buf()),
             force: false,
             defaults: false,
+            from: None,
+            template: None,
+            no_install: false,
         };
 
         cmd.execute().await?;
@@ -629,6 +852,9 @@ mod tests {
             path: Some(new_dir.clone()),
             force: false,
             defaults: false,
+            from: None,
+            template: None,
+            no_install: false,
         };
 
         cmd.execute().await?;
@@ -648,6 +874,9 @@ mod tests {
             path: Some(temp_dir.path().to_path_buf()),
             force: false,
             defaults: false,
+            from: None,
+            template: None,
+            no_install: false,
         };
 
         let result = cmd.execute().await;
@@ -666,6 +895,9 @@ mod tests {
             path: Some(temp_dir.path().to_path_buf()),
             force: true,
             defaults: false,
+            from: None,
+            template: None,
+            no_install: false,
         };
 
         cmd.execute().await?;
@@ -685,6 +917,9 @@ mod tests {
             path: Some(temp_dir.path().to_path_buf()),
             force: false,
             defaults: false,
+            from: None,
+            template: None,
+            no_install: false,
         };
 
         cmd.execute().await?;
@@ -699,6 +934,9 @@ mod tests {
             path: Some(temp_dir.path().to_path_buf()),
             force: false,
             defaults: false,
+            from: None,
+            template: None,
+            no_install: false,
         };
 
         cmd.execute().await?;
@@ -734,6 +972,9 @@ mod tests {
             path: Some(nested_path.clone()),
             force: false,
             defaults: false,
+            from: None,
+            template: None,
+            no_install: false,
         };
 
         cmd.execute().await?;
@@ -756,6 +997,9 @@ mod tests {
             path: Some(temp_dir.path().to_path_buf()),
             force: false,
             defaults: false,
+            from: None,
+            template: None,
+            no_install: false,
         };
         let result = cmd.execute().await;
         assert!(result.is_err());
@@ -769,6 +1013,9 @@ mod tests {
             path: Some(temp_dir.path().to_path_buf()),
             force: true,
             defaults: false,
+            from: None,
+            template: None,
+            no_install: false,
         };
         cmd.execute().await?;
 
@@ -786,6 +1033,9 @@ mod tests {
             path: Some(temp_dir.path().to_path_buf()),
             force: false,
             defaults: false,
+            from: None,
+            template: None,
+            no_install: false,
         };
 
         cmd.execute().await?;
@@ -812,6 +1062,9 @@ mod tests {
             path: Some(temp_dir.path().to_path_buf()),
             force: false,
             defaults: false,
+            from: None,
+            template: None,
+            no_install: false,
         };
 
         cmd.execute().await?;
@@ -839,6 +1092,9 @@ mod tests {
             path: Some(temp_dir.path().to_path_buf()),
             force: false,
             defaults: false,
+            from: None,
+            template: None,
+            no_install: false,
         };
 
         cmd.execute().await?;
@@ -863,6 +1119,9 @@ mod tests {
             path: Some(temp_dir.path().to_path_buf()),
             force: false,
             defaults: false,
+            from: None,
+            template: None,
+            no_install: false,
         };
 
         cmd.execute().await?;
@@ -902,6 +1161,9 @@ my-agent = { source = "community", path = "agents/my-agent.md", version = "v1.0.
             path: Some(temp_dir.path().to_path_buf()),
             force: false,
             defaults: true,
+            from: None,
+            template: None,
+            no_install: false,
         };
 
         cmd.execute().await?;
@@ -936,6 +1198,9 @@ community = "https://github.com/example/repo.git"
             path: Some(temp_dir.path().to_path_buf()),
             force: false,
             defaults: true,
+            from: None,
+            template: None,
+            no_install: false,
         };
 
         cmd.execute().await?;
@@ -978,6 +1243,9 @@ my-agent = { source = "community", path = "agents/my-agent.md" }
             path: Some(temp_dir.path().to_path_buf()),
             force: false,
             defaults: true,
+            from: None,
+            template: None,
+            no_install: false,
         };
 
         cmd.execute().await?;
@@ -1002,6 +1270,9 @@ my-agent = { source = "community", path = "agents/my-agent.md" }
             path: Some(temp_dir.path().to_path_buf()),
             force: false,
             defaults: true,
+            from: None,
+            template: None,
+            no_install: false,
         };
 
         let result = cmd.execute().await;
@@ -1025,6 +1296,9 @@ my-agent = { source = "community", path = "agents/my-agent.md" }
             path: Some(temp_dir.path().to_path_buf()),
             force: false,
             defaults: true,
+            from: None,
+            template: None,
+            no_install: false,
         };
 
         cmd.execute().await?;
@@ -1040,9 +1314,87 @@ my-agent = { source = "community", path = "agents/my-agent.md" }
             path: Some(temp_dir.path().to_path_buf()),
             force: false,
             defaults: true,
+            from: None,
+            template: None,
+            no_install: false,
         };
 
         cmd2.execute().await?;
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_init_minimal_template() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let cmd = InitCommand {
+            path: Some(temp_dir.path().to_path_buf()),
+            force: false,
+            defaults: false,
+            from: None,
+            template: Some(InitTemplate::Minimal),
+            no_install: false,
+        };
+
+        cmd.execute().await?;
+
+        let manifest_path = temp_dir.path().join("agpm.toml");
+        let content = fs::read_to_string(&manifest_path).unwrap();
+        assert!(content.contains("[sources]"));
+        assert!(content.contains("path = \"agents/rust/*.md\""));
+        assert!(!content.contains("[tools"));
+        crate::manifest::Manifest::load(&manifest_path)?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_init_from_local_project() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let starter = temp_dir.path().join("starter");
+        fs::create_dir_all(&starter).unwrap();
+        fs::write(
+            starter.join("agpm.toml"),
+            r#"[sources]
+shared = "../repos/shared"
+community = "https://github.com/example/community.git"
+
+[agents]
+helper = "./agents/helper.md" # kept comment
+reviewer = { source = "community", path = "agents/reviewer.md", version = "v1.0.0" }
+
+[snippets]
+utils = { path = "snippets/utils.md" }
+"#,
+        )
+        .unwrap();
+
+        let project = temp_dir.path().join("project");
+        let cmd = InitCommand {
+            path: Some(project.clone()),
+            force: false,
+            defaults: false,
+            from: Some(starter.display().to_string()),
+            template: None,
+            no_install: true,
+        };
+        cmd.execute().await?;
+
+        let content = fs::read_to_string(project.join("agpm.toml")).unwrap();
+        assert!(content.contains(r#"shared = "../repos/shared""#), "{content}");
+        assert!(content.contains(r#"helper = "../starter/agents/helper.md" # kept comment"#));
+        assert!(content.contains(r#"path = "agents/reviewer.md""#));
+        assert!(content.contains(r#"path = "../starter/snippets/utils.md""#));
+        Ok(())
+    }
+
+    #[test]
+    fn test_rewrite_local_paths_from_git_warns() -> Result<()> {
+        let (content, warnings) = InitCommand::rewrite_local_paths(
+            "[agents]\nhelper = \"./agents/helper.md\"\nremote = { source = \"x\", path = \"a.md\" }\n",
+            None,
+        )?;
+        assert!(content.contains(r#"helper = "./agents/helper.md""#));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("agent 'helper'"), "{warnings:?}");
+        Ok(())
+    }
 }
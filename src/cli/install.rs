@@ -24,7 +24,7 @@
 //! agpm install
 //! ```
 //!
-//! Force reinstall all dependencies:
+//! Repair duplicate lockfile entries left by a bad merge:
 //! ```bash
 //! agpm install --force
 //! ```
@@ -39,6 +39,17 @@
 //! agpm install --frozen
 //! ```
 //!
+//! Install exactly what agpm.lock records, without re-resolving:
+//! ```bash
+//! agpm install --locked
+//! ```
+//!
+//! Split resolution and installation across CI jobs:
+//! ```bash
+//! agpm install --resolve-only   # resolve and write agpm.lock only
+//! agpm install --no-resolve     # install agpm.lock as is
+//! ```
+//!
 //! Disable cache and clone fresh:
 //! ```bash
 //! agpm install --no-cache
@@ -89,15 +100,19 @@
 //! - **Atomic file operations**: Safe, corruption-resistant file installation
 //! - **Multi-phase progress**: Real-time progress updates with phase transitions
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Args;
+use colored::Colorize;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 use crate::cache::Cache;
+use crate::cli::install_plan::InstallPlan;
+use crate::cli::install_report::InstallReport;
 use crate::core::{OperationContext, ResourceIterator};
 use crate::lockfile::LockFile;
-use crate::manifest::{ResourceDependency, find_manifest_with_optional};
-use crate::resolver::DependencyResolver;
+use crate::manifest::{Manifest, ResourceDependency, find_manifest_with_optional};
+use crate::resolver::{DEFAULT_MAX_DEPTH, DependencyResolver};
 
 /// Command to install Claude Code resources from manifest dependencies.
 ///
@@ -118,31 +133,70 @@ use crate::resolver::DependencyResolver;
 ///
 /// ```rust,no_run
 /// use agpm_cli::cli::install::InstallCommand;
+/// use agpm_cli::resolver::DEFAULT_MAX_DEPTH;
 ///
 /// // Standard installation
 /// let cmd = InstallCommand {
 ///     no_lock: false,
 ///     frozen: false,
+///     locked: false,
+///     commit_replace: false,
 ///     no_cache: false,
 ///     max_parallel: None,
+///     io_parallel: None,
 ///     quiet: false,
 ///     no_progress: false,
 ///     verbose: false,
 ///     no_transitive: false,
+///     max_depth: DEFAULT_MAX_DEPTH,
 ///     dry_run: false,
+///     tools: vec![],
+///     auto_detect_tools: false,
+///     tool_commands: Default::default(),
+///     no_post_install: false,
+///     report: None,
+///     prune_cache: false,
+///     concurrency_report: false,
+///     prune_sources: false,
+///     force: false,
+///     verify_signatures: false,
+///     profile: None,
+///     lockfile: None,
+///     resolve_only: false,
+///     no_resolve: false,
+///     target_dir: None,
 /// };
 ///
 /// // CI/Production installation (frozen lockfile)
 /// let cmd = InstallCommand {
 ///     no_lock: false,
 ///     frozen: true,
+///     locked: false,
+///     commit_replace: false,
 ///     no_cache: false,
 ///     max_parallel: Some(2),
+///     io_parallel: None,
 ///     quiet: false,
 ///     no_progress: false,
 ///     verbose: false,
 ///     no_transitive: false,
+///     max_depth: DEFAULT_MAX_DEPTH,
 ///     dry_run: false,
+///     tools: vec![],
+///     auto_detect_tools: false,
+///     tool_commands: Default::default(),
+///     no_post_install: false,
+///     report: None,
+///     prune_cache: false,
+///     concurrency_report: false,
+///     prune_sources: false,
+///     force: false,
+///     verify_signatures: false,
+///     profile: None,
+///     lockfile: None,
+///     resolve_only: false,
+///     no_resolve: false,
+///     target_dir: None,
 /// };
 /// ```
 #[derive(Args)]
@@ -155,14 +209,35 @@ pub struct InstallCommand {
     #[arg(long)]
     pub no_lock: bool,
 
-    /// Verify checksums from existing lockfile
+    /// Require the existing lockfile to be up to date
     ///
-    /// Uses the existing lockfile as-is without updating dependencies.
-    /// This mode ensures reproducible installations and is recommended
-    /// for CI/CD pipelines and production deployments.
+    /// Resolves dependencies normally and fails if the result differs from
+    /// `agpm.lock` in any way (new or removed dependencies, changed versions,
+    /// commits, or paths), reporting the specific staleness reason. The
+    /// lockfile is never written. Unlike an offline install, sources are still
+    /// fetched. Recommended for CI/CD pipelines and production deployments.
     #[arg(long)]
     pub frozen: bool,
 
+    /// Install exactly the commits recorded in the lockfile
+    ///
+    /// Checks that `agpm.lock` is up to date with the manifest and fails,
+    /// naming the staleness reason, if the manifest would change it. Versions
+    /// are not re-resolved: only the locked commits are fetched and installed,
+    /// and the lockfile is never written. Unlike `--frozen`, a tag that moved
+    /// upstream does not fail the install.
+    #[arg(long, conflicts_with = "frozen")]
+    pub locked: bool,
+
+    /// Record `[replace]` URLs in the lockfile
+    ///
+    /// By default, sources replaced in the manifest's `[replace]` section are
+    /// fetched from the replacement but `agpm.lock` keeps their declared URLs,
+    /// so a temporary fork never leaks into the committed lockfile. With this
+    /// flag the replacement URLs are written instead.
+    #[arg(long, conflicts_with_all = ["frozen", "locked"])]
+    pub commit_replace: bool,
+
     /// Don't use cache, clone fresh repositories
     ///
     /// Disables the local Git repository cache and clones repositories
@@ -192,6 +267,16 @@ pub struct InstallCommand {
     #[arg(long, value_name = "NUM")]
     pub max_parallel: Option<usize>,
 
+    /// Maximum number of resources written to disk at once (default: CPU cores)
+    ///
+    /// Bounds file writes separately from `--max-parallel`, which governs
+    /// fetching, resolution and worktree creation. Lower it on slow shared or
+    /// network filesystems, where many simultaneous writes can overwhelm the
+    /// mount. Defaults to `io_parallel` in the global config, or one write per
+    /// CPU core.
+    #[arg(long, value_name = "NUM")]
+    pub io_parallel: Option<usize>,
+
     /// Suppress non-essential output
     ///
     /// When enabled, only errors and essential information will be printed.
@@ -213,12 +298,21 @@ pub struct InstallCommand {
     ///
     /// When enabled, only direct dependencies from the manifest will be installed.
     /// Transitive dependencies declared within resource files (via YAML frontmatter
-    /// or JSON fields) will be ignored. This can be useful for faster installations
-    /// when you know transitive dependencies are already satisfied or for debugging
-    /// dependency issues.
+    /// or JSON fields) are recorded in the lockfile entries of the resources that
+    /// declare them and listed after the install, but not fetched or installed.
+    /// This can be useful for faster installations when you know transitive
+    /// dependencies are already satisfied or for debugging dependency issues.
     #[arg(long)]
     pub no_transitive: bool,
 
+    /// Maximum depth of transitive dependency chains
+    ///
+    /// Resolution fails, naming the chain, when a dependency is declared more
+    /// than this many levels below a manifest dependency. Guards against
+    /// accidentally deep or ever-growing dependency graphs.
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_MAX_DEPTH)]
+    pub max_depth: usize,
+
     /// Preview installation without making changes
     ///
     /// Shows what would be installed, including new dependencies and lockfile changes,
@@ -229,6 +323,9 @@ pub struct InstallCommand {
     /// - Resolves all dependencies normally
     /// - Shows what resources would be installed
     /// - Shows lockfile changes (new entries, version updates)
+    /// - Shows which files would be created, overwritten or deleted, and which
+    ///   hook and MCP server entries would change in their configuration files
+    /// - Warns about overwriting or deleting locally modified files
     /// - Does NOT write the lockfile
     /// - Does NOT install any resources
     /// - Does NOT update .gitignore
@@ -238,6 +335,130 @@ pub struct InstallCommand {
     /// - 1: Changes would be made (useful for CI checks)
     #[arg(long)]
     pub dry_run: bool,
+
+    /// Only install dependencies for these tools (comma-separated)
+    ///
+    /// Skips dependencies of all other tools for this run, overriding tool
+    /// auto-detection. Tools disabled in the manifest stay disabled, and the
+    /// `agpm` tool, which hosts shared snippets, is always installed. The
+    /// lockfile still records every tool's dependencies.
+    ///
+    /// # Examples
+    ///
+    /// - `--tools claude-code`: Skip opencode and custom tools
+    /// - `--tools claude-code,opencode`: Install for both tools
+    #[arg(long, value_delimiter = ',', value_name = "TOOLS")]
+    pub tools: Vec<String>,
+
+    /// Disable tools that are not detected on this machine (from the global config)
+    ///
+    /// Populated from `auto_detect_tools` in the global config via execute_with_config.
+    #[arg(skip)]
+    pub auto_detect_tools: bool,
+
+    /// Detection command overrides per tool (from the global config)
+    #[arg(skip)]
+    pub tool_commands: BTreeMap<String, String>,
+
+    /// Skip dependencies' post-install commands (from the global `--no-post-install` flag)
+    #[arg(skip)]
+    pub no_post_install: bool,
+
+    /// Write a JSON summary of the install to FILE
+    ///
+    /// The report lists every resource with its status (installed, updated,
+    /// up-to-date or skipped) and bytes written, how each source was obtained
+    /// (fetched, cached, offline-skipped or local), the time taken and any
+    /// warnings. It is written even when the install fails.
+    #[arg(long, value_name = "FILE", conflicts_with = "dry_run")]
+    pub report: Option<PathBuf>,
+
+    /// After a successful install, remove cached worktrees for commits not in agpm.lock
+    ///
+    /// Keeps the cache warm for the current lockfile while bounding its growth,
+    /// for example on CI runners with limited disk. Only worktrees of the
+    /// lockfile's sources are removed; the cache is shared between projects, so
+    /// other sources are kept unless `--prune-sources` is given.
+    #[arg(long, conflicts_with = "dry_run")]
+    pub prune_cache: bool,
+
+    /// Print how long each source spent waiting on cache locks, fetching and creating worktrees
+    ///
+    /// Shows, per source, the time tasks waited on cache file locks, on
+    /// another task's fetch of the same repository and on worktrees another
+    /// task was still creating, next to the time spent on the network and
+    /// disk. Useful for tuning `--max-parallel`.
+    #[arg(long)]
+    pub concurrency_report: bool,
+
+    /// With --prune-cache, also remove cached repositories and worktrees of sources not in agpm.lock
+    #[arg(long, requires = "prune_cache")]
+    pub prune_sources: bool,
+
+    /// Install despite duplicate entries in agpm.lock, e.g. after a bad merge
+    ///
+    /// Duplicate entries (same name, source, tool and variant) are reported as
+    /// lockfile corruption. With `--force`, the dependencies are resolved again
+    /// from agpm.toml, the duplicates are listed as dropped, and the lockfile is
+    /// written with one entry each (unless `--dry-run` or `--no-lock`).
+    #[arg(long, conflicts_with_all = ["frozen", "locked", "no_resolve"])]
+    pub force: bool,
+
+    /// Require the tags dependencies resolve to to be GPG- or SSH-signed
+    ///
+    /// Runs `git verify-tag` on every tag a version resolves to, for every
+    /// source, and fails resolution if a tag is unsigned or its signature does
+    /// not verify. Branch and commit dependencies are not checked. Individual
+    /// sources can require this with `verify_signatures = true`. Verified tags
+    /// and their signers are recorded in agpm.lock.
+    #[arg(long)]
+    pub verify_signatures: bool,
+
+    /// Install only the dependencies of a profile defined in `[profiles]`
+    ///
+    /// The profile is resolved into its own lockfile, `agpm.<PROFILE>.lock`
+    /// unless `--lockfile` is given. Files installed by other profiles are
+    /// removed, so switching profiles never leaves resources of the previous
+    /// one behind.
+    #[arg(long, value_name = "PROFILE")]
+    pub profile: Option<String>,
+
+    /// Read and write this lockfile instead of agpm.lock
+    ///
+    /// Relative paths are resolved against the project directory.
+    /// `--frozen` and `--locked` check this lockfile.
+    #[arg(long, value_name = "FILE")]
+    pub lockfile: Option<PathBuf>,
+
+    /// Resolve dependencies and write the lockfile without installing anything
+    ///
+    /// Fetches sources and resolves versions as usual, then writes the
+    /// lockfile and stops: no files are copied, no hooks or MCP servers are
+    /// merged and `.gitignore` is left alone. Checksums of resources whose
+    /// commit is unchanged are kept; the others are filled in by the next
+    /// install. With `--frozen`, only checks that the lockfile is up to date
+    /// and writes nothing.
+    #[arg(long, conflicts_with_all = ["locked", "dry_run", "no_lock", "no_resolve", "prune_cache"])]
+    pub resolve_only: bool,
+
+    /// Install exactly what the lockfile records, without resolving
+    ///
+    /// Skips resolution entirely: sources are fetched only for the locked
+    /// commits and the lockfile is not checked against agpm.toml, which is
+    /// reported as a warning if it is out of date. Dependencies missing from
+    /// the lockfile are not installed. Fails if the lockfile does not exist.
+    #[arg(long, conflicts_with_all = ["frozen", "locked", "no_transitive", "commit_replace"])]
+    pub no_resolve: bool,
+
+    /// Install into this directory instead of the project directory
+    ///
+    /// Installed files, `.claude/settings.local.json`, `.mcp.json` and
+    /// `.gitignore` are written under this directory, and install paths must
+    /// stay inside it. agpm.toml and the lockfile are still read from the
+    /// project directory, and local dependencies are resolved against it.
+    /// Relative paths are resolved against the current directory.
+    #[arg(long, value_name = "DIR", conflicts_with = "resolve_only")]
+    pub target_dir: Option<PathBuf>,
 }
 
 impl Default for InstallCommand {
@@ -269,13 +490,32 @@ impl InstallCommand {
         Self {
             no_lock: false,
             frozen: false,
+            locked: false,
+            commit_replace: false,
             no_cache: false,
             max_parallel: None,
+            io_parallel: None,
             quiet: false,
             no_progress: false,
             verbose: false,
             no_transitive: false,
+            max_depth: DEFAULT_MAX_DEPTH,
             dry_run: false,
+            tools: Vec::new(),
+            auto_detect_tools: false,
+            tool_commands: BTreeMap::new(),
+            no_post_install: false,
+            report: None,
+            prune_cache: false,
+            concurrency_report: false,
+            prune_sources: false,
+            force: false,
+            verify_signatures: false,
+            profile: None,
+            lockfile: None,
+            resolve_only: false,
+            no_resolve: false,
+            target_dir: None,
         }
     }
 
@@ -298,13 +538,32 @@ impl InstallCommand {
         Self {
             no_lock: false,
             frozen: false,
+            locked: false,
+            commit_replace: false,
             no_cache: false,
             max_parallel: None,
+            io_parallel: None,
             quiet: true,
             no_progress: true,
             verbose: false,
             no_transitive: false,
+            max_depth: DEFAULT_MAX_DEPTH,
             dry_run: false,
+            tools: Vec::new(),
+            auto_detect_tools: false,
+            tool_commands: BTreeMap::new(),
+            no_post_install: false,
+            report: None,
+            prune_cache: false,
+            concurrency_report: false,
+            prune_sources: false,
+            force: false,
+            verify_signatures: false,
+            profile: None,
+            lockfile: None,
+            resolve_only: false,
+            no_resolve: false,
+            target_dir: None,
         }
     }
 
@@ -394,9 +653,63 @@ impl InstallCommand {
         self.execute_from_path(Some(&manifest_path)).await
     }
 
+    /// The manifest to install from: the enabled tools restricted to `--tools`,
+    /// or to the detected tools when `auto_detect_tools` is set in the global
+    /// config.
+    ///
+    /// Only installation uses it. Resolution keeps every enabled tool, so the
+    /// lockfile does not depend on the tools present on this machine, and files
+    /// already installed for the other tools are left in place.
+    fn select_tools(&self, manifest: &Manifest, project_dir: &Path) -> Result<Manifest> {
+        let mut selected = manifest.clone();
+        if !self.tools.is_empty() {
+            selected.tools_config_mut().restrict_to(&self.tools)?;
+        } else if self.auto_detect_tools {
+            let disabled =
+                selected.tools_config_mut().disable_undetected(project_dir, &self.tool_commands);
+            if !disabled.is_empty() && !self.quiet {
+                println!("Skipping dependencies for undetected tools: {}", disabled.join(", "));
+            }
+        }
+        Ok(selected)
+    }
+
     pub async fn execute_from_path(&self, path: Option<&Path>) -> Result<()> {
+        // Initialize cache (always needed now, even with --no-cache)
+        let cache = Cache::new()?;
+        let mut report = InstallReport::default();
+        if self.concurrency_report {
+            cache.enable_concurrency_report();
+        }
+        let start = std::time::Instant::now();
+        let result = self.install(path, &cache, &mut report).await;
+
+        if self.concurrency_report {
+            print_concurrency_report(&cache);
+        }
+
+        if let Some(report_path) = &self.report {
+            report.finish(start.elapsed(), &result, &cache).await;
+            if let Err(e) = report.save(report_path) {
+                // A failed install's own error matters more than the report's
+                if result.is_err() {
+                    tracing::warn!("Failed to write install report: {e:#}");
+                } else {
+                    return Err(e);
+                }
+            }
+        }
+        result
+    }
+
+    /// Run the install, recording what it does in `report`.
+    async fn install(
+        &self,
+        path: Option<&Path>,
+        cache: &Cache,
+        report: &mut InstallReport,
+    ) -> Result<()> {
         use crate::installer::{ResourceFilter, install_resources};
-        use crate::manifest::Manifest;
         use crate::utils::progress::{InstallationPhase, MultiPhaseProgress};
         use std::sync::Arc;
 
@@ -410,7 +723,12 @@ impl InstallCommand {
             return Err(anyhow::anyhow!("No agpm.toml found at {}", manifest_path.display()));
         }
 
-        let (manifest, _patch_conflicts) = Manifest::load_with_private(&manifest_path)?;
+        let (mut manifest, _patch_conflicts) = Manifest::load_with_private(&manifest_path)?;
+        if let Some(profile) = &self.profile {
+            manifest.select_profile(profile)?;
+        }
+        let install_manifest =
+            self.select_tools(&manifest, manifest_path.parent().unwrap_or_else(|| Path::new(".")))?;
 
         // Note: Private patches silently override project patches when they conflict.
         // This allows users to customize their local configuration without modifying
@@ -418,13 +736,46 @@ impl InstallCommand {
 
         // Create command context for using enhanced lockfile loading
         let project_dir = manifest_path.parent().unwrap_or_else(|| Path::new("."));
-        let command_context =
+        let mut command_context =
             crate::cli::common::CommandContext::new(manifest.clone(), project_dir.to_path_buf())?;
+        command_context.lockfile_path = self.lockfile_path(project_dir);
 
-        // In --frozen mode, check for corruption and security issues only
-        let lockfile_path = project_dir.join("agpm.lock");
+        // In --frozen mode, check for corruption and security issues before resolving
+        let lockfile_path = command_context.lockfile_path.clone();
+        let lockfile_name = lockfile_path.file_name().map_or_else(
+            || lockfile_path.display().to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+
+        if self.frozen && !lockfile_path.exists() {
+            return Err(anyhow::anyhow!(
+                "No lockfile found at {} in --frozen mode.\n\n\
+                 --frozen installs exactly what {lockfile_name} records and never creates it.\n\
+                 Hint: Run 'agpm install' without --frozen and commit {lockfile_name}.",
+                lockfile_path.display()
+            ));
+        }
+
+        if self.locked {
+            if !lockfile_path.exists() {
+                return Err(anyhow::anyhow!(
+                    "No lockfile found at {} in --locked mode.\n\n\
+                     Hint: Run 'agpm install' without --locked and commit {lockfile_name}.",
+                    lockfile_path.display()
+                ));
+            }
+            let lockfile = LockFile::load(&lockfile_path)
+                .context("Cannot proceed in --locked mode due to invalid lockfile")?;
+            if let Some(reason) = lockfile.validate_against_manifest(&manifest, true)? {
+                return Err(anyhow::anyhow!(
+                    "Lockfile is out of date with agpm.toml in --locked mode:\n\n\
+                     {reason}\n\n\
+                     Hint: Run 'agpm install' without --locked to update {lockfile_name}, then commit it."
+                ));
+            }
+        }
 
-        if self.frozen && lockfile_path.exists() {
+        if self.frozen {
             // In frozen mode, we should NOT regenerate - fail hard if lockfile is invalid
             match LockFile::load(&lockfile_path) {
                 Ok(lockfile) => {
@@ -449,6 +800,25 @@ impl InstallCommand {
                 }
             }
         }
+        if self.no_resolve {
+            if !lockfile_path.exists() {
+                return Err(anyhow::anyhow!(
+                    "No lockfile found at {} with --no-resolve.\n\n\
+                     Hint: Run 'agpm install --resolve-only' to create {lockfile_name} first.",
+                    lockfile_path.display()
+                ));
+            }
+            let lockfile = LockFile::load(&lockfile_path)
+                .context("Cannot proceed with --no-resolve due to invalid lockfile")?;
+            if let Some(reason) = lockfile.validate_against_manifest(&manifest, true)? {
+                let warning =
+                    format!("{lockfile_name} is out of date with agpm.toml, installing it as is");
+                if !self.quiet {
+                    eprintln!("{} {warning}:\n{reason}", "⚠".yellow());
+                }
+                report.warnings.push(warning);
+            }
+        }
         let total_deps = manifest.all_dependencies().len();
 
         // Initialize multi-phase progress for all progress tracking
@@ -456,17 +826,21 @@ impl InstallCommand {
 
         // Show initial status
 
-        let actual_project_dir =
+        let manifest_dir =
             manifest_path.parent().ok_or_else(|| anyhow::anyhow!("Invalid manifest path"))?;
+        // With --target-dir, everything is installed relative to that directory instead
+        let target_dir = self.target_dir.as_deref().map(std::path::absolute).transpose()?;
+        let actual_project_dir = target_dir.as_deref().unwrap_or(manifest_dir);
 
-        // Check for existing lockfile
-        let lockfile_path = actual_project_dir.join("agpm.lock");
-
-        // Use enhanced lockfile loading with automatic regeneration for non-frozen mode
-        let existing_lockfile = if !self.frozen {
-            command_context.load_lockfile_with_regeneration(true, "install")?
+        // Use enhanced lockfile loading with automatic regeneration for non-frozen mode.
+        // An older lockfile format is saved back upgraded.
+        let existing_lockfile = if !self.frozen && !self.locked && !self.no_resolve && !self.dry_run
+        {
+            command_context.load_lockfile_persisting_migration("install")?
         } else {
-            // In frozen mode, use the original loading logic (already validated above)
+            // In frozen, locked, no-resolve and dry-run mode, never write the lockfile
+            // before installing (not even a format migration); frozen, locked and
+            // no-resolve lockfiles were already loaded above
             if lockfile_path.exists() {
                 Some(LockFile::load(&lockfile_path)?)
             } else {
@@ -474,8 +848,19 @@ impl InstallCommand {
             }
         };
 
-        // Initialize cache (always needed now, even with --no-cache)
-        let cache = Cache::new()?;
+        // Duplicate entries mean the lockfile was corrupted, e.g. by a bad merge
+        if !self.force
+            && let Some(existing) = &existing_lockfile
+            && let Some(reason) = existing.detect_duplicate_entries()?
+        {
+            return Err(anyhow::anyhow!(
+                "Lockfile corruption detected in {}:\n\n\
+                 {reason}\n\n\
+                 Hint: Run 'agpm install --force' to keep the entries matching agpm.toml \
+                 and drop the duplicates.",
+                lockfile_path.display()
+            ));
+        }
 
         // Calculate max concurrency (used for both resolution and installation)
         let max_concurrency = self.max_parallel.unwrap_or_else(|| {
@@ -495,12 +880,17 @@ impl InstallCommand {
             Some(operation_context.clone()),
         )
         .await?;
+        resolver.set_max_depth(self.max_depth);
+        if self.verify_signatures {
+            resolver.require_tag_signatures();
+        }
 
-        // Pre-sync sources phase (if not frozen and we have remote deps)
+        // Pre-sync sources phase (if we have remote deps)
         let has_remote_deps =
             manifest.all_dependencies().iter().any(|(_, dep)| dep.get_source().is_some());
 
-        if !self.frozen && has_remote_deps {
+        // --locked and --no-resolve fetch only the locked commits, during installation
+        if has_remote_deps && !self.locked && !self.no_resolve {
             // Start syncing sources phase
             if !self.quiet && !self.no_progress {
                 multi_phase.start_phase(InstallationPhase::SyncingSources, None);
@@ -528,8 +918,35 @@ impl InstallCommand {
         }
 
         let mut lockfile = if let Some(existing) = existing_lockfile {
-            if self.frozen {
-                // Use existing lockfile as-is
+            if self.locked {
+                if !self.quiet {
+                    println!("✓ Using locked lockfile ({total_deps} dependencies)");
+                }
+                existing
+            } else if self.no_resolve {
+                if !self.quiet {
+                    println!(
+                        "✓ Installing {lockfile_name} without resolving ({} resources)",
+                        ResourceIterator::count_total_resources(&existing)
+                    );
+                }
+                existing
+            } else if self.frozen {
+                // Resolve normally, then require the result to match the lockfile exactly
+                let progress = if !self.quiet && !self.no_progress {
+                    Some(multi_phase.clone())
+                } else {
+                    None
+                };
+                let resolved = resolver.update(&existing, None, progress).await?;
+                if let Some(reason) = existing.diff_against_resolved(&resolved) {
+                    return Err(anyhow::anyhow!(
+                        "Lockfile is out of date in --frozen mode:\n\n\
+                         {reason}\n\n\
+                         Hint: Run 'agpm install' without --frozen to update {lockfile_name}, then commit it."
+                    ));
+                }
+
                 if !self.quiet {
                     println!("✓ Using frozen lockfile ({total_deps} dependencies)");
                 }
@@ -541,7 +958,13 @@ impl InstallCommand {
                 } else {
                     None
                 };
-                resolver.update(&existing, None, progress).await?
+                if self.no_transitive {
+                    let mut resolved = resolver.resolve_with_options(false, progress).await?;
+                    resolved.carry_over_fetched_at(&existing);
+                    resolved
+                } else {
+                    resolver.update(&existing, None, progress).await?
+                }
             }
         } else {
             // Fresh resolution with windowed progress tracking
@@ -553,11 +976,40 @@ impl InstallCommand {
             resolver.resolve_with_options(!self.no_transitive, progress).await?
         };
 
+        if self.commit_replace {
+            lockfile.commit_source_replacements(&manifest.replacement_urls()?);
+        }
+
+        // Resolution normally yields one entry per dependency; make sure
+        // nothing carried over from the corrupted lockfile survives --force
+        if self.force {
+            let resolved = lockfile.clone();
+            lockfile.remove_duplicate_entries(&resolved);
+        }
+
         // Check for tag movement if we have both old and new lockfiles (skip in frozen mode)
-        let old_lockfile = if !self.frozen && lockfile_path.exists() {
+        let old_lockfile = if !self.frozen && !self.locked && lockfile_path.exists() {
             // Load the old lockfile for comparison
-            if let Ok(old) = LockFile::load(&lockfile_path) {
-                detect_tag_movement(&old, &lockfile, self.quiet);
+            if let Ok(mut old) = LockFile::load(&lockfile_path) {
+                if self.force {
+                    for dropped in old.remove_duplicate_entries(&lockfile) {
+                        let warning = format!(
+                            "Dropped duplicate lockfile entry for {} '{}' at {}",
+                            dropped.resource_type,
+                            dropped.display_name(),
+                            dropped.resolved_commit.as_deref().unwrap_or("local")
+                        );
+                        if !self.quiet {
+                            eprintln!("{} {warning}", "⚠".yellow());
+                        }
+                        report.warnings.push(warning);
+                    }
+                }
+                report.warnings.extend(detect_tag_movement(&old, &lockfile, self.quiet));
+                // Orphans are only removed by a full install
+                if !self.dry_run && !self.resolve_only && !self.no_resolve {
+                    report.warnings.extend(report_orphans(&old, &manifest, self.quiet));
+                }
                 Some(old)
             } else {
                 None
@@ -566,13 +1018,55 @@ impl InstallCommand {
             None
         };
 
+        // With --resolve-only, stop once the lockfile is resolved
+        if self.resolve_only {
+            // --frozen already checked that the lockfile is up to date
+            if !self.frozen {
+                if let Some(old) = &old_lockfile {
+                    lockfile.carry_over_checksums(old);
+                }
+                lockfile.save(&lockfile_path).with_context(|| {
+                    format!("Failed to save lockfile to {}", lockfile_path.display())
+                })?;
+                if !self.quiet {
+                    println!(
+                        "✓ Resolved {} resources into {lockfile_name}",
+                        ResourceIterator::count_total_resources(&lockfile)
+                    );
+                }
+            }
+            return Ok(());
+        }
+
         // Handle dry-run mode: show what would be installed without making changes
         if self.dry_run {
-            return crate::cli::common::display_dry_run_results(
+            let lockfile_result = crate::cli::common::display_dry_run_results(
                 &lockfile,
                 old_lockfile.as_ref(),
                 self.quiet,
             );
+            let plan = InstallPlan::build(
+                &lockfile,
+                old_lockfile.as_ref(),
+                &manifest,
+                actual_project_dir,
+                cache.cache_dir(),
+            );
+            if !self.quiet {
+                plan.display();
+            }
+            if lockfile_result.is_ok() && plan.has_changes() {
+                return Err(anyhow::anyhow!("Dry-run detected changes (exit 1)"));
+            }
+            return lockfile_result;
+        }
+
+        // Resources of deselected tools are not installed; keep the checksums
+        // they were last installed with
+        if (!self.tools.is_empty() || self.auto_detect_tools)
+            && let Some(old) = &old_lockfile
+        {
+            lockfile.carry_over_checksums(old);
         }
 
         let total_resources = ResourceIterator::count_total_resources(&lockfile);
@@ -602,11 +1096,12 @@ impl InstallCommand {
             match install_resources(
                 ResourceFilter::All,
                 &lockfile_for_install,
-                &manifest,
+                &install_manifest,
                 actual_project_dir,
                 cache.clone(),
                 self.no_cache,
                 Some(max_concurrency),
+                self.io_parallel,
                 Some(multi_phase.clone()),
                 self.verbose,
                 old_lockfile.as_ref(), // Pass old lockfile for early-exit optimization
@@ -620,6 +1115,26 @@ impl InstallCommand {
                         results.context_checksums,
                         results.applied_patches,
                     );
+                    report.record_resources(
+                        &lockfile,
+                        old_lockfile.as_ref(),
+                        &results.written,
+                        actual_project_dir,
+                    );
+
+                    // Run post-install commands before the lockfile is saved, so a
+                    // failed command is retried by the next install
+                    if !self.no_post_install
+                        && let Err(e) = crate::installer::run_post_install_commands(
+                            &lockfile,
+                            old_lockfile.as_ref(),
+                            &install_manifest,
+                            actual_project_dir,
+                        )
+                        .await
+                    {
+                        installation_error = Some(e);
+                    }
 
                     results.installed_count
                 }
@@ -641,15 +1156,27 @@ impl InstallCommand {
             // Call shared finalization function
             let (hook_count_result, server_count_result) = crate::installer::finalize_installation(
                 &mut lockfile,
-                &manifest,
+                &install_manifest,
                 actual_project_dir,
-                &cache,
+                cache,
                 old_lockfile.as_ref(),
                 self.quiet,
-                self.no_lock,
+                // never rewrite the lockfile with --no-lock, --frozen or --locked
+                (!self.no_lock && !self.frozen && !self.locked).then_some(lockfile_path.as_path()),
             )
             .await?;
 
+            if !manifest.profiles.is_empty() {
+                self.prune_other_profiles(
+                    &manifest,
+                    manifest_dir,
+                    actual_project_dir,
+                    &lockfile_path,
+                    &lockfile,
+                )
+                .await?;
+            }
+
             hook_count = hook_count_result;
             server_count = server_count_result;
 
@@ -664,6 +1191,22 @@ impl InstallCommand {
             return Err(error);
         }
 
+        if !self.quiet && total_resources > 0 {
+            let summary = &report.summary;
+            println!(
+                "{} installed, {} up-to-date, {} updated.",
+                summary.installed, summary.up_to_date, summary.updated
+            );
+        }
+
+        if self.prune_cache {
+            self.prune_unlocked_cache(cache, &lockfile).await?;
+        }
+
+        if self.no_transitive && !self.quiet {
+            report_skipped_transitive(&lockfile);
+        }
+
         // Only show "no dependencies" message if nothing was installed AND no progress shown
         if self.no_progress
             && !self.quiet
@@ -679,6 +1222,188 @@ impl InstallCommand {
 
         Ok(())
     }
+
+    /// The lockfile this install reads and writes.
+    fn lockfile_path(&self, project_dir: &Path) -> PathBuf {
+        crate::cli::common::selected_lockfile_path(
+            project_dir,
+            self.lockfile.as_deref(),
+            self.profile.as_deref(),
+        )
+    }
+
+    /// Remove the files and merged entries installed from the lockfiles of
+    /// other profiles (and of the default `agpm.lock`) that `lockfile` does
+    /// not install too.
+    ///
+    /// The other lockfiles are looked up in `manifest_dir`; their files are
+    /// removed from `project_dir`, the directory being installed into.
+    async fn prune_other_profiles(
+        &self,
+        manifest: &Manifest,
+        manifest_dir: &Path,
+        project_dir: &Path,
+        lockfile_path: &Path,
+        lockfile: &LockFile,
+    ) -> Result<()> {
+        let other_lockfiles = std::iter::once(manifest_dir.join("agpm.lock"))
+            .chain(
+                manifest
+                    .profiles
+                    .keys()
+                    .map(|profile| manifest_dir.join(format!("agpm.{profile}.lock"))),
+            )
+            .filter(|path| path != lockfile_path && path.exists());
+
+        for path in other_lockfiles {
+            let Ok(other) = LockFile::load(&path) else {
+                continue;
+            };
+            let removed =
+                crate::installer::cleanup_removed_artifacts(&other, lockfile, project_dir).await?;
+            crate::installer::cleanup_removed_merged_entries(
+                &other,
+                lockfile,
+                manifest,
+                project_dir,
+            )?;
+            if !self.quiet && !removed.is_empty() {
+                println!(
+                    "✓ Removed {} artifact(s) installed from {}",
+                    removed.len(),
+                    path.display()
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove cached worktrees (and with `--prune-sources`, repositories) that
+    /// the installed lockfile does not reference.
+    async fn prune_unlocked_cache(&self, cache: &Cache, lockfile: &LockFile) -> Result<()> {
+        let source_dirs: HashMap<&str, String> = lockfile
+            .sources
+            .iter()
+            .filter_map(|source| {
+                let (owner, repo) = crate::git::parse_git_url(&source.url).ok()?;
+                Some((source.name.as_str(), format!("{owner}_{repo}")))
+            })
+            .collect();
+
+        let mut locked: HashMap<String, HashSet<String>> =
+            source_dirs.values().map(|dir| (dir.clone(), HashSet::new())).collect();
+        for entry in lockfile.all_resources() {
+            if let (Some(source), Some(commit)) = (&entry.source, &entry.resolved_commit)
+                && let Some(dir) = source_dirs.get(source.as_str())
+            {
+                locked.entry(dir.clone()).or_default().insert(commit.clone());
+            }
+        }
+
+        let removed = cache.prune_unlocked(&locked, self.prune_sources).await?;
+        if !self.quiet && !removed.is_empty() {
+            println!(
+                "Pruned {} unused cache {}",
+                removed.len(),
+                if removed.len() == 1 {
+                    "entry"
+                } else {
+                    "entries"
+                }
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Prints the per-source timings collected for `--concurrency-report`.
+fn print_concurrency_report(cache: &Cache) {
+    let timings = cache.concurrency_report();
+    println!("\nConcurrency report:");
+    if timings.is_empty() {
+        println!("  No cache operations were recorded");
+        return;
+    }
+
+    let ms = |duration: std::time::Duration| format!("{}ms", duration.as_millis());
+    for (source, timing) in &timings {
+        println!(
+            "  {}: waited {} (cache lock {}, fetch lock {}, pending worktree {}), network {}, disk {} → {}",
+            source.cyan(),
+            ms(timing.waiting()),
+            ms(timing.cache_lock),
+            ms(timing.fetch_lock),
+            ms(timing.pending_worktree),
+            ms(timing.network),
+            ms(timing.disk),
+            timing.bottleneck()
+        );
+    }
+}
+
+/// Lists the dependencies that installed resources declare but that
+/// `--no-transitive` left out of the lockfile.
+fn report_skipped_transitive(lockfile: &LockFile) {
+    let skipped = lockfile.missing_dependencies();
+    if skipped.is_empty() {
+        return;
+    }
+
+    println!(
+        "Skipped {} transitive {} (--no-transitive):",
+        skipped.len(),
+        if skipped.len() == 1 {
+            "dependency"
+        } else {
+            "dependencies"
+        }
+    );
+    for (dependent, dependency) in skipped {
+        println!(
+            "  - {} ({}) required by {}",
+            dependency.path,
+            dependency.resource_type,
+            dependent.display_name()
+        );
+    }
+}
+
+/// Reports lockfile entries that are no longer required by the manifest.
+///
+/// Installation re-resolves from the manifest, so orphaned entries are dropped
+/// from the new lockfile and their installed files and merged hook/MCP entries
+/// are removed during finalization. This only tells the user what is going away.
+///
+/// Returns one warning per orphan for the install report.
+fn report_orphans(old_lockfile: &LockFile, manifest: &Manifest, quiet: bool) -> Vec<String> {
+    let orphans = old_lockfile.find_orphans(manifest);
+    if orphans.is_empty() {
+        return Vec::new();
+    }
+
+    if !quiet {
+        eprintln!(
+            "Removing {} orphaned lockfile {} no longer required by agpm.toml:",
+            orphans.len(),
+            if orphans.len() == 1 {
+                "entry"
+            } else {
+                "entries"
+            }
+        );
+    }
+    let mut warnings = Vec::with_capacity(orphans.len());
+    for (resource_type, entry) in orphans {
+        if !quiet {
+            eprintln!("  - {} ({resource_type}) at {}", entry.display_name(), entry.installed_at);
+        }
+        warnings.push(format!(
+            "Removing orphaned {resource_type} '{}' at {}",
+            entry.display_name(),
+            entry.installed_at
+        ));
+    }
+    warnings
 }
 
 /// Detects if any tags have moved between the old and new lockfiles.
@@ -687,7 +1412,13 @@ impl InstallCommand {
 /// commit than before, this is potentially problematic and worth warning about.
 ///
 /// Branches are expected to move, so we don't warn about those.
-fn detect_tag_movement(old_lockfile: &LockFile, new_lockfile: &LockFile, quiet: bool) {
+///
+/// Returns the warnings for the install report.
+fn detect_tag_movement(
+    old_lockfile: &LockFile,
+    new_lockfile: &LockFile,
+    quiet: bool,
+) -> Vec<String> {
     use crate::core::ResourceType;
 
     // Helper function to check if a version looks like a tag (not a branch or SHA)
@@ -722,6 +1453,7 @@ fn detect_tag_movement(old_lockfile: &LockFile, new_lockfile: &LockFile, quiet:
         new_resources: &[crate::lockfile::LockedResource],
         resource_type: ResourceType,
         quiet: bool,
+        warnings: &mut Vec<String>,
     ) {
         for new_resource in new_resources {
             // Skip if no version or resolved commit
@@ -744,35 +1476,39 @@ fn detect_tag_movement(old_lockfile: &LockFile, new_lockfile: &LockFile, quiet:
                     (&old_resource.version, &old_resource.resolved_commit)
             {
                 // Check if the same tag now points to a different commit
-                if old_version == new_version && old_commit != new_commit && !quiet {
-                    eprintln!(
-                        "⚠️  Warning: Tag '{}' for {} '{}' has moved from {} to {}",
+                if old_version == new_version && old_commit != new_commit {
+                    let warning = format!(
+                        "Tag '{}' for {} '{}' has moved from {} to {}",
                         new_version,
                         resource_type,
                         new_resource.display_name(),
                         &old_commit[..8.min(old_commit.len())],
                         &new_commit[..8.min(new_commit.len())]
                     );
-                    eprintln!(
-                        "   Tags should be immutable. This may indicate the upstream repository force-pushed the tag."
-                    );
+                    if !quiet {
+                        eprintln!("⚠️  Warning: {warning}");
+                        eprintln!(
+                            "   Tags should be immutable. This may indicate the upstream repository force-pushed the tag."
+                        );
+                    }
+                    warnings.push(warning);
                 }
             }
         }
     }
 
     // Check all resource types
-    check_resources(&old_lockfile.agents, &new_lockfile.agents, ResourceType::Agent, quiet);
-    check_resources(&old_lockfile.snippets, &new_lockfile.snippets, ResourceType::Snippet, quiet);
-    check_resources(&old_lockfile.commands, &new_lockfile.commands, ResourceType::Command, quiet);
-    check_resources(&old_lockfile.scripts, &new_lockfile.scripts, ResourceType::Script, quiet);
-    check_resources(&old_lockfile.hooks, &new_lockfile.hooks, ResourceType::Hook, quiet);
-    check_resources(
-        &old_lockfile.mcp_servers,
-        &new_lockfile.mcp_servers,
-        ResourceType::McpServer,
-        quiet,
-    );
+    let mut warnings = Vec::new();
+    for resource_type in ResourceType::all() {
+        check_resources(
+            old_lockfile.get_resources(resource_type),
+            new_lockfile.get_resources(resource_type),
+            *resource_type,
+            quiet,
+            &mut warnings,
+        );
+    }
+    warnings
 }
 
 #[cfg(test)]
@@ -796,6 +1532,25 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_install_report_failure_keeps_install_error() -> Result<()> {
+        let temp = TempDir::new()?;
+        let manifest_path = temp.path().join("agpm.toml");
+
+        // The report path is a directory, so writing the report fails too
+        let mut cmd = InstallCommand::new();
+        cmd.report = Some(temp.path().to_path_buf());
+        let err = cmd.execute_from_path(Some(&manifest_path)).await.unwrap_err();
+        assert!(err.to_string().contains("agpm.toml"), "{err:#}");
+        assert!(!err.to_string().contains("File operation failed"), "{err:#}");
+
+        // With a successful install the report error is still returned
+        Manifest::new().save(&manifest_path)?;
+        let err = cmd.execute_from_path(Some(&manifest_path)).await.unwrap_err();
+        assert!(err.to_string().contains("File operation failed"), "{err:#}");
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_install_with_empty_manifest() -> Result<()> {
         let temp = TempDir::new()?;
@@ -832,13 +1587,32 @@ mod tests {
         let cmd = InstallCommand {
             no_lock: true,
             frozen: false,
+            locked: false,
+            commit_replace: false,
             no_cache: false,
             max_parallel: None,
+            io_parallel: None,
             quiet: false,
             no_progress: false,
             verbose: false,
             no_transitive: false,
+            max_depth: DEFAULT_MAX_DEPTH,
             dry_run: false,
+            tools: vec![],
+            auto_detect_tools: false,
+            tool_commands: BTreeMap::new(),
+            no_post_install: false,
+            report: None,
+            prune_cache: false,
+            concurrency_report: false,
+            prune_sources: false,
+            force: false,
+            verify_signatures: false,
+            profile: None,
+            lockfile: None,
+            resolve_only: false,
+            no_resolve: false,
+            target_dir: None,
         };
 
         cmd.execute_from_path(Some(&manifest_path)).await?;
@@ -876,6 +1650,17 @@ This is a test agent.",
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             })),
         );
         manifest.save(&manifest_path)?;
@@ -886,6 +1671,118 @@ This is a test agent.",
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_install_resolve_only_then_no_resolve() -> Result<()> {
+        let temp = TempDir::new()?;
+        let manifest_path = temp.path().join("agpm.toml");
+        let lockfile_path = temp.path().join("agpm.lock");
+        let installed = temp.path().join(".claude/agents/local-agent.md");
+        fs::write(temp.path().join("local-agent.md"), "# Local Agent\n")?;
+        fs::write(&manifest_path, "[agents]\nlocal-agent = \"local-agent.md\"\n")?;
+
+        let cmd = InstallCommand {
+            no_resolve: true,
+            ..InstallCommand::new_quiet()
+        };
+        let err = cmd.execute_from_path(Some(&manifest_path)).await.unwrap_err();
+        assert!(err.to_string().contains("--resolve-only"), "{err}");
+
+        let cmd = InstallCommand {
+            resolve_only: true,
+            ..InstallCommand::new_quiet()
+        };
+        cmd.execute_from_path(Some(&manifest_path)).await?;
+        assert_eq!(LockFile::load(&lockfile_path)?.agents.len(), 1);
+        assert!(!installed.exists());
+
+        // A manifest change is not picked up without resolving
+        fs::write(temp.path().join("other.md"), "# Other\n")?;
+        fs::write(
+            &manifest_path,
+            "[agents]\nlocal-agent = \"local-agent.md\"\nother = \"other.md\"\n",
+        )?;
+        let cmd = InstallCommand {
+            no_resolve: true,
+            ..InstallCommand::new_quiet()
+        };
+        cmd.execute_from_path(Some(&manifest_path)).await?;
+        assert!(installed.exists());
+        assert!(!temp.path().join(".claude/agents/other.md").exists());
+        assert_eq!(LockFile::load(&lockfile_path)?.agents.len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_install_persists_lockfile_migration() -> Result<()> {
+        let temp = TempDir::new()?;
+        let manifest_path = temp.path().join("agpm.toml");
+        let lockfile_path = temp.path().join("agpm.lock");
+        fs::write(temp.path().join("reviewer.md"), "# Reviewer\n")?;
+        fs::write(&manifest_path, "[agents]\nreviewer = \"reviewer.md\"\n")?;
+        fs::write(
+            &lockfile_path,
+            r#"version = 1
+
+[[agents]]
+name = "reviewer"
+path = "reviewer.md"
+checksum = "sha256:abc"
+installed_at = ".claude/agents/reviewer.md"
+"#,
+        )?;
+
+        InstallCommand::new_quiet().execute_from_path(Some(&manifest_path)).await?;
+
+        let saved = fs::read_to_string(&lockfile_path)?;
+        assert!(saved.contains("version = 2"), "{saved}");
+        assert!(!saved.contains("version = 1"), "{saved}");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_install_profiles_use_separate_lockfiles() -> Result<()> {
+        let temp = TempDir::new()?;
+        let manifest_path = temp.path().join("agpm.toml");
+        fs::write(temp.path().join("reviewer.md"), "# Reviewer\n")?;
+        fs::write(temp.path().join("debugger.md"), "# Debugger\n")?;
+        fs::write(
+            &manifest_path,
+            r#"
+[profiles]
+prod = ["reviewer"]
+dev = ["reviewer", "debugger"]
+
+[agents]
+reviewer = "reviewer.md"
+debugger = "debugger.md"
+"#,
+        )?;
+        let agents = temp.path().join(".claude/agents");
+
+        let mut cmd = InstallCommand::new_quiet();
+        cmd.profile = Some("dev".to_string());
+        cmd.execute_from_path(Some(&manifest_path)).await?;
+        assert!(temp.path().join("agpm.dev.lock").exists());
+        assert!(!temp.path().join("agpm.lock").exists());
+        assert!(agents.join("debugger.md").exists());
+
+        // Switching profiles removes the files only the other profile installs
+        cmd.profile = Some("prod".to_string());
+        cmd.execute_from_path(Some(&manifest_path)).await?;
+        let prod = LockFile::load(&temp.path().join("agpm.prod.lock"))?;
+        assert_eq!(prod.agents.len(), 1);
+        assert!(agents.join("reviewer.md").exists());
+        assert!(!agents.join("debugger.md").exists());
+
+        // --frozen checks the profile's lockfile
+        cmd.frozen = true;
+        cmd.execute_from_path(Some(&manifest_path)).await?;
+        cmd.lockfile = Some(PathBuf::from("missing.lock"));
+        let err = cmd.execute_from_path(Some(&manifest_path)).await.unwrap_err();
+        assert!(err.to_string().contains("missing.lock"), "{err}");
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_install_with_invalid_manifest_syntax() -> Result<(), anyhow::Error> {
         let temp = TempDir::new()?;
@@ -942,6 +1839,17 @@ Body",
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             })),
         );
         manifest.save(&manifest_path)?;
@@ -967,24 +1875,46 @@ Body",
                 applied_patches: std::collections::BTreeMap::new(),
                 install: None,
                 variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+                strip_metadata: false,
+                inline: None,
             }],
             snippets: vec![],
             mcp_servers: vec![],
             scripts: vec![],
             hooks: vec![],
+            disabled: vec![],
         }
         .save(&lockfile_path)?;
 
         let cmd = InstallCommand {
             no_lock: false,
             frozen: true,
+            locked: false,
+            commit_replace: false,
             no_cache: false,
             max_parallel: None,
+            io_parallel: None,
             quiet: false,
             no_progress: false,
             verbose: false,
             no_transitive: false,
+            max_depth: DEFAULT_MAX_DEPTH,
             dry_run: false,
+            tools: vec![],
+            auto_detect_tools: false,
+            tool_commands: BTreeMap::new(),
+            no_post_install: false,
+            report: None,
+            prune_cache: false,
+            concurrency_report: false,
+            prune_sources: false,
+            force: false,
+            verify_signatures: false,
+            profile: None,
+            lockfile: None,
+            resolve_only: false,
+            no_resolve: false,
+            target_dir: None,
         };
 
         cmd.execute_from_path(Some(&manifest_path)).await?;
@@ -992,6 +1922,40 @@ Body",
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_install_locked_rejects_stale_lockfile() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let manifest_path = temp.path().join("agpm.toml");
+        let lockfile_path = temp.path().join("agpm.lock");
+        fs::write(temp.path().join("test-agent.md"), "# Test Agent")?;
+
+        let mut manifest = Manifest::new();
+        manifest
+            .agents
+            .insert("test-agent".into(), ResourceDependency::Simple("test-agent.md".to_string()));
+        manifest.save(&manifest_path)?;
+
+        let cmd = InstallCommand {
+            locked: true,
+            ..InstallCommand::new_quiet()
+        };
+        let err = cmd.execute_from_path(Some(&manifest_path)).await.unwrap_err();
+        assert!(err.to_string().contains("No lockfile found"), "{err}");
+
+        LockFile::new().save(&lockfile_path)?;
+        let before = fs::read_to_string(&lockfile_path)?;
+        let cmd = InstallCommand {
+            locked: true,
+            ..InstallCommand::new_quiet()
+        };
+        let err = cmd.execute_from_path(Some(&manifest_path)).await.unwrap_err();
+        assert!(err.to_string().contains("agpm update"), "{err}");
+        assert!(err.to_string().contains("test-agent"), "{err}");
+        assert_eq!(fs::read_to_string(&lockfile_path)?, before);
+        assert!(!temp.path().join(".claude/agents/test-agent.md").exists());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_install_errors_when_local_file_missing() -> Result<(), anyhow::Error> {
         let temp = TempDir::new()?;
@@ -1016,6 +1980,17 @@ Body",
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             })),
         );
         manifest.save(&manifest_path)?;
@@ -1063,6 +2038,17 @@ Body",
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             })),
         );
         manifest.save(&manifest_path)?;
@@ -1107,6 +2093,17 @@ Body",
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             })),
         );
         manifest.save(&manifest_path)?;
@@ -1149,6 +2146,17 @@ Body",
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             })),
         );
         manifest.save(&manifest_path)?;
@@ -1156,13 +2164,32 @@ Body",
         let cmd = InstallCommand {
             no_lock: false,
             frozen: false,
+            locked: false,
+            commit_replace: false,
             no_cache: false,
             max_parallel: None,
+            io_parallel: None,
             quiet: true, // Suppress output in test
             no_progress: true,
             verbose: false,
             no_transitive: false,
+            max_depth: DEFAULT_MAX_DEPTH,
             dry_run: true,
+            tools: vec![],
+            auto_detect_tools: false,
+            tool_commands: BTreeMap::new(),
+            no_post_install: false,
+            report: None,
+            prune_cache: false,
+            concurrency_report: false,
+            prune_sources: false,
+            force: false,
+            verify_signatures: false,
+            profile: None,
+            lockfile: None,
+            resolve_only: false,
+            no_resolve: false,
+            target_dir: None,
         };
 
         // In dry-run mode, this should return an error indicating changes would be made
@@ -1210,6 +2237,17 @@ Body",
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             })),
         );
         manifest.add_mcp_server(
@@ -1230,6 +2268,17 @@ Body",
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             })),
         );
         manifest.save(&manifest_path)?;
@@ -0,0 +1,358 @@
+//! File-level plan of an install, shown by `agpm install --dry-run`.
+//!
+//! After resolution the new lockfile already holds every resource's
+//! `installed_at` path, so the plan compares it with the previous lockfile and
+//! the project directory without writing anything:
+//!
+//! - files that would be created, overwritten or deleted
+//! - hook and MCP server entries that would be added to, updated in or removed
+//!   from their merge targets (`.claude/settings.local.json`, `.mcp.json`, ...)
+//!
+//! Overwriting or deleting a file whose content no longer matches the checksum
+//! in `agpm.lock`, or that AGPM did not install, is flagged as a warning, since
+//! the install would discard local changes.
+
+use colored::Colorize;
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::core::ResourceType;
+use crate::lockfile::{LockFile, LockedResource};
+use crate::manifest::Manifest;
+
+/// What the install would do to one path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileAction {
+    /// Write a file that does not exist yet
+    Create,
+    /// Replace an existing file
+    Overwrite,
+    /// Remove a file no longer in the lockfile
+    Delete,
+}
+
+/// One planned file operation.
+#[derive(Debug, Clone)]
+pub struct PlannedFile {
+    pub action: FileAction,
+    /// Path relative to the project directory
+    pub path: String,
+    /// Display name of the resource
+    pub name: String,
+    /// Whether the operation discards content AGPM did not write
+    pub discards_local_changes: bool,
+}
+
+/// What the install would do to one merged hook or MCP server entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeAction {
+    Add,
+    Update,
+    Remove,
+}
+
+/// One planned change to a merge target.
+#[derive(Debug, Clone)]
+pub struct PlannedMerge {
+    pub action: MergeAction,
+    pub resource_type: ResourceType,
+    pub name: String,
+    /// Configuration file the entry is merged into, relative to the project
+    pub target: String,
+}
+
+/// Every file and merge-target change an install would make.
+#[derive(Debug, Default)]
+pub struct InstallPlan {
+    pub files: Vec<PlannedFile>,
+    pub merges: Vec<PlannedMerge>,
+}
+
+impl InstallPlan {
+    /// Plan the install of `lockfile` over `old_lockfile` in `project_dir`.
+    ///
+    /// `cache_dir` is consulted for the project files templated resources read
+    /// when they were last rendered.
+    #[must_use]
+    pub fn build(
+        lockfile: &LockFile,
+        old_lockfile: Option<&LockFile>,
+        manifest: &Manifest,
+        project_dir: &Path,
+        cache_dir: &Path,
+    ) -> Self {
+        let mut plan = Self::default();
+        let manifest_dir = manifest.manifest_dir.as_deref().unwrap_or(project_dir);
+        let changed = |old: &LockedResource, new: &LockedResource| {
+            inputs_changed(old, new) || context_changed(old, new, cache_dir, manifest_dir)
+        };
+        let empty = LockFile::new();
+        let old_lockfile = old_lockfile.unwrap_or(&empty);
+
+        let new_paths: HashSet<&str> = lockfile
+            .all_resources()
+            .into_iter()
+            .filter(|entry| installs_file(entry))
+            .map(|entry| entry.installed_at.as_str())
+            .collect();
+
+        for entry in lockfile.all_resources() {
+            let old = old_lockfile
+                .find_resource_by_id(&entry.id())
+                .or_else(|| old_lockfile.find_resource_by_id(&entry.id().ignoring_install_path()));
+            if is_merged(entry.resource_type) {
+                let action = match old {
+                    None => MergeAction::Add,
+                    Some(old) if changed(old, entry) => MergeAction::Update,
+                    Some(_) => continue,
+                };
+                plan.push_merge(action, entry, manifest);
+                continue;
+            }
+            if !installs_file(entry) {
+                continue;
+            }
+
+            let full_path = project_dir.join(&entry.installed_at);
+            if !full_path.exists() {
+                plan.push_file(FileAction::Create, entry, false);
+                continue;
+            }
+
+            // The file AGPM last wrote at this path, if any
+            let previous = old_lockfile
+                .all_resources()
+                .into_iter()
+                .find(|old| installs_file(old) && old.installed_at == entry.installed_at);
+            let modified = previous.is_none_or(|previous| is_modified(project_dir, previous));
+            let unchanged = old
+                .is_some_and(|old| old.installed_at == entry.installed_at && !changed(old, entry));
+            if modified || !unchanged {
+                plan.push_file(FileAction::Overwrite, entry, modified);
+            }
+        }
+
+        for old in old_lockfile.all_resources() {
+            if is_merged(old.resource_type) {
+                if lockfile.find_resource_by_id(&old.id().ignoring_install_path()).is_none() {
+                    plan.push_merge(MergeAction::Remove, old, manifest);
+                }
+            } else if installs_file(old)
+                && !new_paths.contains(old.installed_at.as_str())
+                && project_dir.join(&old.installed_at).exists()
+            {
+                plan.push_file(FileAction::Delete, old, is_modified(project_dir, old));
+            }
+        }
+
+        plan
+    }
+
+    /// Whether the install would change any file or merge target.
+    #[must_use]
+    pub fn has_changes(&self) -> bool {
+        !self.files.is_empty() || !self.merges.is_empty()
+    }
+
+    fn push_file(&mut self, action: FileAction, entry: &LockedResource, discards: bool) {
+        self.files.push(PlannedFile {
+            action,
+            path: entry.installed_at.clone(),
+            name: entry.display_name().to_string(),
+            discards_local_changes: discards,
+        });
+    }
+
+    fn push_merge(&mut self, action: MergeAction, entry: &LockedResource, manifest: &Manifest) {
+        let tool = entry.tool.as_deref().unwrap_or_else(|| entry.resource_type.default_tool());
+        let target = manifest
+            .get_merge_target(tool, entry.resource_type)
+            .map_or_else(|| format!("{tool} configuration"), |path| path.display().to_string());
+        self.merges.push(PlannedMerge {
+            action,
+            resource_type: entry.resource_type,
+            name: entry.display_name().to_string(),
+            target,
+        });
+    }
+
+    /// Print the plan.
+    pub fn display(&self) {
+        if !self.has_changes() {
+            println!("{}", "No files would be changed".dimmed());
+            return;
+        }
+
+        if !self.files.is_empty() {
+            println!("{}", "Files:".bold());
+            for file in &self.files {
+                let (marker, verb) = match file.action {
+                    FileAction::Create => ("+".green(), "create"),
+                    FileAction::Overwrite => ("~".yellow(), "overwrite"),
+                    FileAction::Delete => ("-".red(), "delete"),
+                };
+                println!("  {marker} {} ({verb}, {})", file.path, file.name.cyan());
+            }
+            println!();
+        }
+
+        if !self.merges.is_empty() {
+            println!("{}", "Configuration changes:".bold());
+            for merge in &self.merges {
+                let (marker, verb) = match merge.action {
+                    MergeAction::Add => ("+".green(), "add"),
+                    MergeAction::Update => ("~".yellow(), "update"),
+                    MergeAction::Remove => ("-".red(), "remove"),
+                };
+                println!(
+                    "  {marker} {verb} {} {} in {}",
+                    merge.resource_type,
+                    merge.name.cyan(),
+                    merge.target
+                );
+            }
+            println!();
+        }
+
+        for file in self.files.iter().filter(|file| file.discards_local_changes) {
+            let verb = if file.action == FileAction::Delete {
+                "delete"
+            } else {
+                "overwrite"
+            };
+            eprintln!(
+                "{} Would {verb} {}, which has local modifications not recorded in agpm.lock",
+                "⚠".yellow(),
+                file.path
+            );
+        }
+    }
+}
+
+/// Hooks and MCP servers are merged into configuration files rather than installed.
+fn is_merged(resource_type: ResourceType) -> bool {
+    matches!(resource_type, ResourceType::Hook | ResourceType::McpServer)
+}
+
+/// Whether `entry` is installed as its own file.
+fn installs_file(entry: &LockedResource) -> bool {
+    !is_merged(entry.resource_type) && entry.install != Some(false)
+}
+
+/// Whether anything recorded in the lockfile that determines the installed content changed.
+fn inputs_changed(old: &LockedResource, new: &LockedResource) -> bool {
+    old.resolved_commit != new.resolved_commit
+        || old.variant_inputs != new.variant_inputs
+        || old.applied_patches != new.applied_patches
+        || old.strip_metadata != new.strip_metadata
+        || new.resolved_commit.as_deref().is_none_or(str::is_empty)
+}
+
+/// Whether the template context of `old` changed.
+///
+/// Resolution does not render templates, so a resolved entry has no context
+/// checksum yet. Like install, the project files read when `old` was rendered
+/// are then checked against its checksum instead.
+fn context_changed(
+    old: &LockedResource,
+    new: &LockedResource,
+    cache_dir: &Path,
+    manifest_dir: &Path,
+) -> bool {
+    match (&old.context_checksum, &new.context_checksum) {
+        (Some(checksum), None) => {
+            !crate::installer::read_files_unchanged(cache_dir, manifest_dir, checksum)
+        }
+        (old_checksum, new_checksum) => old_checksum != new_checksum,
+    }
+}
+
+/// Whether the file of `entry` differs from the checksum recorded for it.
+fn is_modified(project_dir: &Path, entry: &LockedResource) -> bool {
+    LockFile::compute_checksum(&project_dir.join(&entry.installed_at))
+        .is_ok_and(|checksum| checksum != entry.checksum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lockfile::LockedResourceBuilder;
+    use tempfile::TempDir;
+
+    fn agent(name: &str, content: &str) -> LockedResource {
+        let mut entry = LockedResourceBuilder::new(
+            name.to_string(),
+            format!("agents/{name}.md"),
+            format!("sha256:{}", hex::encode(<sha2::Sha256 as sha2::Digest>::digest(content))),
+            format!(".claude/agents/{name}.md"),
+            ResourceType::Agent,
+        )
+        .build();
+        entry.resolved_commit = Some("a".repeat(40));
+        entry
+    }
+
+    #[test]
+    fn test_plan_classifies_files() {
+        let temp = TempDir::new().unwrap();
+        let agents = temp.path().join(".claude/agents");
+        std::fs::create_dir_all(&agents).unwrap();
+        std::fs::write(agents.join("same.md"), "same").unwrap();
+        std::fs::write(agents.join("edited.md"), "edited locally").unwrap();
+        std::fs::write(agents.join("gone.md"), "gone").unwrap();
+
+        let mut old = LockFile::new();
+        for (name, content) in [("same", "same"), ("edited", "edited"), ("gone", "gone")] {
+            old.agents.push(agent(name, content));
+        }
+        let mut new = LockFile::new();
+        for (name, content) in [("same", "same"), ("edited", "edited"), ("fresh", "fresh")] {
+            new.agents.push(agent(name, content));
+        }
+
+        let plan = InstallPlan::build(&new, Some(&old), &Manifest::new(), temp.path(), temp.path());
+        let action =
+            |name: &str| plan.files.iter().find(|file| file.name == name).map(|file| file.action);
+        assert_eq!(action("same"), None);
+        assert_eq!(action("fresh"), Some(FileAction::Create));
+        assert_eq!(action("edited"), Some(FileAction::Overwrite));
+        assert_eq!(action("gone"), Some(FileAction::Delete));
+        assert!(
+            plan.files.iter().find(|file| file.name == "edited").unwrap().discards_local_changes
+        );
+        assert!(
+            !plan.files.iter().find(|file| file.name == "gone").unwrap().discards_local_changes
+        );
+        assert!(plan.has_changes());
+    }
+
+    #[test]
+    fn test_plan_compares_context_checksums() {
+        let temp = TempDir::new().unwrap();
+        let agents = temp.path().join(".claude/agents");
+        std::fs::create_dir_all(&agents).unwrap();
+
+        let mut old = LockFile::new();
+        let mut new = LockFile::new();
+        for (name, old_context, new_context) in [
+            ("same", Some("sha256:a"), Some("sha256:a")),
+            ("changed", Some("sha256:a"), Some("sha256:b")),
+            // Not rendered yet and nothing recorded about its inputs
+            ("unknown", Some("sha256:a"), None),
+        ] {
+            std::fs::write(agents.join(format!("{name}.md")), name).unwrap();
+            let mut entry = agent(name, name);
+            entry.context_checksum = old_context.map(String::from);
+            old.agents.push(entry.clone());
+            entry.context_checksum = new_context.map(String::from);
+            new.agents.push(entry);
+        }
+
+        let plan = InstallPlan::build(&new, Some(&old), &Manifest::new(), temp.path(), temp.path());
+        let action =
+            |name: &str| plan.files.iter().find(|file| file.name == name).map(|file| file.action);
+        assert_eq!(action("same"), None);
+        assert_eq!(action("changed"), Some(FileAction::Overwrite));
+        assert_eq!(action("unknown"), Some(FileAction::Overwrite));
+    }
+}
@@ -0,0 +1,255 @@
+//! Machine-readable summary of an install, written by `agpm install --report`.
+//!
+//! The report is a single JSON file for CI pipelines to archive and inspect
+//! instead of scraping stdout. It is written after every install, including
+//! failed ones, in which case `success` is `false`, `error` holds the message
+//! and only the steps that completed are filled in:
+//!
+//! ```json
+//! {
+//!   "success": true,
+//!   "error": null,
+//!   "duration_ms": 1834,
+//!   "summary": { "installed": 1, "updated": 0, "up_to_date": 4, "skipped": 0, "bytes_written": 2048 },
+//!   "resources": [
+//!     { "type": "agent", "name": "reviewer", "source": "community", "version": "v1.2.0",
+//!       "resolved_commit": "…", "installed_at": ".claude/agents/reviewer.md",
+//!       "status": "installed", "bytes_written": 2048 }
+//!   ],
+//!   "sources": [{ "name": "community", "status": "fetched" }],
+//!   "warnings": []
+//! }
+//! ```
+//!
+//! Resource `status` is `installed` (not in the previous lockfile), `updated`
+//! (its file changed), `up-to-date` (its file already matched) or `skipped`
+//! (`install = false`). Source `status` is `fetched`, `cached` (the cached
+//! repository was used without contacting the remote), `offline-skipped`
+//! (fetching failed and the cached repository was used) or `local`.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::cache::{Cache, SourceFetchStatus};
+use crate::core::file_error::{FileOperation, FileResultExt};
+use crate::lockfile::{LockFile, ResourceId};
+
+/// Summary of one `agpm install` run.
+#[derive(Debug, Default, Serialize)]
+pub struct InstallReport {
+    /// Whether the install completed without error
+    pub success: bool,
+    /// The error that stopped the install, if any
+    pub error: Option<String>,
+    /// Wall-clock time of the install in milliseconds
+    pub duration_ms: u64,
+    /// Resource counts by status and total bytes written
+    pub summary: ReportSummary,
+    /// Every resource in the resulting lockfile
+    pub resources: Vec<ReportedResource>,
+    /// How each source used by the install was obtained
+    pub sources: Vec<ReportedSource>,
+    /// Warnings printed during the install
+    pub warnings: Vec<String>,
+}
+
+/// Resource counts by status.
+#[derive(Debug, Default, Serialize)]
+pub struct ReportSummary {
+    pub installed: usize,
+    pub updated: usize,
+    pub up_to_date: usize,
+    pub skipped: usize,
+    pub bytes_written: u64,
+}
+
+/// What the install did with one resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ResourceStatus {
+    /// Written for the first time
+    Installed,
+    /// Written because its content changed
+    Updated,
+    /// Left alone because its file already matched
+    UpToDate,
+    /// Not written because of `install = false`
+    Skipped,
+}
+
+/// One resource in the report.
+#[derive(Debug, Serialize)]
+pub struct ReportedResource {
+    #[serde(rename = "type")]
+    pub resource_type: String,
+    pub name: String,
+    pub source: Option<String>,
+    pub version: Option<String>,
+    pub resolved_commit: Option<String>,
+    pub installed_at: String,
+    pub status: ResourceStatus,
+    pub bytes_written: u64,
+}
+
+/// How one source was obtained.
+#[derive(Debug, Serialize)]
+pub struct ReportedSource {
+    pub name: String,
+    pub status: SourceFetchStatus,
+}
+
+impl InstallReport {
+    /// Record the status of every resource in `lockfile`.
+    ///
+    /// `written` lists the resources whose files this install wrote; they are
+    /// `updated` if `old_lockfile` already had them and `installed` otherwise.
+    pub fn record_resources(
+        &mut self,
+        lockfile: &LockFile,
+        old_lockfile: Option<&LockFile>,
+        written: &[ResourceId],
+        project_dir: &Path,
+    ) {
+        let written: HashSet<_> = written.iter().collect();
+        let previously_locked: HashSet<_> = old_lockfile
+            .map(|old| {
+                old.all_resources()
+                    .into_iter()
+                    .map(|entry| entry.id().ignoring_install_path())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        self.summary = ReportSummary::default();
+        self.resources.clear();
+        for entry in lockfile.all_resources() {
+            let id = entry.id();
+            let status = if entry.install == Some(false) {
+                ResourceStatus::Skipped
+            } else if !written.contains(&id) {
+                ResourceStatus::UpToDate
+            } else if previously_locked.contains(&id.ignoring_install_path()) {
+                ResourceStatus::Updated
+            } else {
+                ResourceStatus::Installed
+            };
+            let bytes_written = match status {
+                ResourceStatus::Installed | ResourceStatus::Updated => {
+                    std::fs::metadata(project_dir.join(&entry.installed_at))
+                        .map(|metadata| metadata.len())
+                        .unwrap_or(0)
+                }
+                ResourceStatus::UpToDate | ResourceStatus::Skipped => 0,
+            };
+
+            match status {
+                ResourceStatus::Installed => self.summary.installed += 1,
+                ResourceStatus::Updated => self.summary.updated += 1,
+                ResourceStatus::UpToDate => self.summary.up_to_date += 1,
+                ResourceStatus::Skipped => self.summary.skipped += 1,
+            }
+            self.summary.bytes_written += bytes_written;
+
+            self.resources.push(ReportedResource {
+                resource_type: entry.resource_type.to_string(),
+                name: entry.display_name().to_string(),
+                source: entry.source.clone(),
+                version: entry.version.clone(),
+                resolved_commit: entry.resolved_commit.clone(),
+                installed_at: entry.installed_at.clone(),
+                status,
+                bytes_written,
+            });
+        }
+    }
+
+    /// Record the outcome, duration and source fetch statuses of the install.
+    pub async fn finish(&mut self, elapsed: Duration, result: &Result<()>, cache: &Cache) {
+        self.success = result.is_ok();
+        self.error = result.as_ref().err().map(|e| format!("{e:#}"));
+        self.duration_ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
+
+        let mut sources: Vec<_> = cache
+            .source_fetches()
+            .await
+            .into_iter()
+            .map(|(name, status)| ReportedSource {
+                name,
+                status,
+            })
+            .collect();
+        sources.sort_by(|a, b| a.name.cmp(&b.name));
+        self.sources = sources;
+    }
+
+    /// Write the report to `path` as pretty-printed JSON.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json + "\n").with_file_context(
+            FileOperation::Write,
+            path,
+            "writing install report",
+            "install",
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ResourceType;
+    use crate::lockfile::LockedResourceBuilder;
+    use tempfile::TempDir;
+
+    fn agent(name: &str) -> crate::lockfile::LockedResource {
+        LockedResourceBuilder::new(
+            name.to_string(),
+            format!("agents/{name}.md"),
+            String::new(),
+            format!(".claude/agents/{name}.md"),
+            ResourceType::Agent,
+        )
+        .build()
+    }
+
+    #[test]
+    fn test_record_resources_distinguishes_statuses() {
+        let temp = TempDir::new().unwrap();
+        let agents = temp.path().join(".claude/agents");
+        std::fs::create_dir_all(&agents).unwrap();
+        std::fs::write(agents.join("fresh.md"), "12345").unwrap();
+        std::fs::write(agents.join("changed.md"), "123").unwrap();
+
+        let mut old = LockFile::new();
+        old.agents.push(agent("changed"));
+        old.agents.push(agent("same"));
+
+        let mut new = LockFile::new();
+        for name in ["fresh", "changed", "same", "content-only"] {
+            new.agents.push(agent(name));
+        }
+        new.agents[3].install = Some(false);
+        let written = vec![new.agents[0].id(), new.agents[1].id()];
+
+        let mut report = InstallReport::default();
+        report.record_resources(&new, Some(&old), &written, temp.path());
+
+        let status = |name: &str| {
+            report.resources.iter().find(|r| r.name == name).map(|r| r.status).unwrap()
+        };
+        assert_eq!(status("fresh"), ResourceStatus::Installed);
+        assert_eq!(status("changed"), ResourceStatus::Updated);
+        assert_eq!(status("same"), ResourceStatus::UpToDate);
+        assert_eq!(status("content-only"), ResourceStatus::Skipped);
+        assert_eq!(report.summary.bytes_written, 8);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["resources"][0]["type"], "agent");
+        assert_eq!(json["summary"]["up_to_date"], 1);
+        assert!(json["resources"].as_array().unwrap().iter().any(|r| r["status"] == "up-to-date"));
+    }
+}
@@ -16,5 +16,36 @@ pub fn lockentry_to_listitem(
         resolved_commit: entry.resolved_commit.clone(),
         tool: Some(entry.tool.clone().unwrap_or_else(|| "claude-code".to_string())),
         applied_patches: entry.applied_patches.clone(),
+        installed: entry.install != Some(false),
+        disabled: false,
+        variant_inputs: Some(entry.variant_inputs.json().clone()),
+    }
+}
+
+/// Convert a disabled dependency recorded in the lockfile to a `ListItem`,
+/// filling in what the manifest still declares for it
+pub fn disabled_to_listitem(
+    disabled: &crate::lockfile::DisabledResource,
+    dep: Option<&crate::manifest::ResourceDependency>,
+    manifest: &crate::manifest::Manifest,
+) -> ListItem {
+    ListItem {
+        name: disabled.name.clone(),
+        source: dep.and_then(|dep| dep.get_source()).map(str::to_string),
+        version: dep.and_then(|dep| dep.get_version()).map(str::to_string),
+        path: dep.map(|dep| dep.get_path().to_string()),
+        resource_type: disabled.resource_type.to_string(),
+        installed_at: None,
+        checksum: None,
+        resolved_commit: None,
+        tool: Some(
+            dep.and_then(|dep| dep.get_tool())
+                .map(str::to_string)
+                .unwrap_or_else(|| manifest.get_default_tool(disabled.resource_type)),
+        ),
+        applied_patches: std::collections::BTreeMap::new(),
+        installed: false,
+        disabled: true,
+        variant_inputs: None,
     }
 }
@@ -1,6 +1,12 @@
+use anyhow::Result;
+use std::path::Path;
+
+use crate::lockfile::LockFile;
 use crate::manifest::ResourceDependency;
 
-use super::formatters::ListItem;
+use crate::cli::outdated::OutdatedInfo;
+
+use super::formatters::{ListItem, ModifiedListItem, OutdatedListItem};
 
 /// Determine if a resource type should be shown based on filters
 pub fn should_show_resource_type(
@@ -105,3 +111,75 @@ pub fn sort_items(items: &mut [ListItem], sort_field: Option<&String>) {
         }
     }
 }
+
+/// Keep the items that have a newer compatible version available.
+///
+/// `outdated` is the result of the update check, or `None` when it was skipped
+/// (`--offline`). Without it, every item from a Git source is kept with an
+/// unknown latest version. Local resources are never outdated.
+pub fn outdated_items(
+    items: Vec<ListItem>,
+    outdated: Option<&[OutdatedInfo]>,
+) -> Vec<OutdatedListItem> {
+    items
+        .into_iter()
+        .filter(|item| item.source.is_some())
+        .filter_map(|item| match outdated {
+            None => Some(OutdatedListItem {
+                item,
+                latest: None,
+                latest_available: None,
+            }),
+            Some(outdated) => {
+                let info = outdated.iter().find(|info| {
+                    info.has_update
+                        && info.name == item.name
+                        && item.source.as_deref() == Some(info.source.as_str())
+                })?;
+                Some(OutdatedListItem {
+                    latest: Some(info.latest.clone()),
+                    latest_available: Some(info.latest_available.clone()),
+                    item,
+                })
+            }
+        })
+        .collect()
+}
+
+/// Keep the items whose installed file no longer matches its lockfile checksum.
+///
+/// Files are read relative to `project_dir`. Hooks and MCP servers are merged
+/// into shared configuration files and are skipped, as are resources that are
+/// not installed to disk. A missing file is reported as modified too.
+///
+/// # Errors
+///
+/// Returns an error if an installed file exists but cannot be read.
+pub fn modified_items(items: Vec<ListItem>, project_dir: &Path) -> Result<Vec<ModifiedListItem>> {
+    let mut modified = Vec::new();
+    for item in items {
+        if !item.installed || matches!(item.resource_type.as_str(), "hook" | "mcp-server") {
+            continue;
+        }
+        let (Some(installed_at), Some(checksum)) = (&item.installed_at, &item.checksum) else {
+            continue;
+        };
+        if installed_at.is_empty() {
+            continue;
+        }
+
+        let path = project_dir.join(installed_at);
+        if !path.is_file() {
+            modified.push(ModifiedListItem {
+                item,
+                missing: true,
+            });
+        } else if LockFile::compute_checksum_cached(&path)? != *checksum {
+            modified.push(ModifiedListItem {
+                item,
+                missing: false,
+            });
+        }
+    }
+    Ok(modified)
+}
@@ -58,13 +58,45 @@ pub struct ListItem {
     pub tool: Option<String>,
     /// Patches that were applied to this resource
     pub applied_patches: std::collections::BTreeMap<String, toml::Value>,
+    /// Whether the resource is written to disk (`false` for `install = false`)
+    pub installed: bool,
+    /// Whether the dependency is disabled with `enabled = false`
+    pub disabled: bool,
+    /// Template variables that distinguish variants of the same resource
+    pub variant_inputs: Option<serde_json::Value>,
+}
+
+/// An installed resource listed by `agpm list --outdated-only`.
+///
+/// The latest versions are `None` when they could not be determined because
+/// the update check was skipped with `--offline`.
+#[derive(Debug, Clone)]
+pub struct OutdatedListItem {
+    /// The installed resource
+    pub item: ListItem,
+    /// The newest version satisfying the manifest's constraint
+    pub latest: Option<String>,
+    /// The newest version available, which may be outside the constraint
+    pub latest_available: Option<String>,
+}
+
+/// An installed resource listed by `agpm list --modified`.
+///
+/// Local resources are expected to be edited in place; edits to a resource
+/// from a source are overwritten the next time it is installed.
+#[derive(Debug, Clone)]
+pub struct ModifiedListItem {
+    /// The installed resource
+    pub item: ListItem,
+    /// Whether the installed file no longer exists
+    pub missing: bool,
 }
 
 /// Output items in the specified format
 pub fn output_items(items: &[ListItem], config: &OutputConfig) -> Result<()> {
     if items.is_empty() {
         if config.format == "json" {
-            println!("{{}}");
+            println!("[]");
         } else {
             println!("No installed resources found.");
         }
@@ -128,37 +160,35 @@ pub async fn output_items_detailed(
 
 /// Output in JSON format
 fn output_json(items: &[ListItem]) -> Result<()> {
-    let json_items: Vec<serde_json::Value> = items
+    println!("{}", serde_json::to_string_pretty(&list_json(items))?);
+    Ok(())
+}
+
+/// Build the JSON array emitted by `agpm list --format json`
+///
+/// Every object has the same keys, with `null` for values that do not apply,
+/// so tooling can rely on the schema regardless of how a resource was declared.
+pub fn list_json(items: &[ListItem]) -> serde_json::Value {
+    items
         .iter()
         .map(|item| {
-            let mut obj = serde_json::json!({
-                "name": item.name,
+            serde_json::json!({
                 "type": item.resource_type,
-                "tool": item.tool
-            });
-
-            if let Some(ref source) = item.source {
-                obj["source"] = serde_json::Value::String(source.clone());
-            }
-            if let Some(ref version) = item.version {
-                obj["version"] = serde_json::Value::String(version.clone());
-            }
-            if let Some(ref path) = item.path {
-                obj["path"] = serde_json::Value::String(path.clone());
-            }
-            if let Some(ref installed_at) = item.installed_at {
-                obj["installed_at"] = serde_json::Value::String(installed_at.clone());
-            }
-            if let Some(ref checksum) = item.checksum {
-                obj["checksum"] = serde_json::Value::String(checksum.clone());
-            }
-
-            obj
+                "name": item.name,
+                "source": item.source,
+                "version": item.version,
+                "path": item.path,
+                "resolved_commit": item.resolved_commit,
+                "installed_at": item.installed_at,
+                "checksum": item.checksum,
+                "tool": item.tool,
+                "installed": item.installed,
+                "disabled": item.disabled,
+                "variant_inputs": item.variant_inputs,
+                "patches": item.applied_patches.keys().collect::<Vec<_>>(),
+            })
         })
-        .collect();
-
-    println!("{}", serde_json::to_string_pretty(&json_items)?);
-    Ok(())
+        .collect()
 }
 
 /// Output in YAML format
@@ -266,6 +296,319 @@ fn output_table(items: &[ListItem], config: &OutputConfig) {
     println!("{}: {} resources", "Total".green().bold(), items.len());
 }
 
+/// Count items per resource type, in [`ResourceType::all`] order, skipping empty types.
+///
+/// Keys are the plural section names used in the manifest and lockfile (`agents`,
+/// `mcp-servers`, ...).
+///
+/// [`ResourceType::all`]: crate::core::ResourceType::all
+pub fn type_totals(items: &[ListItem]) -> Vec<(&'static str, usize)> {
+    crate::core::ResourceType::all()
+        .iter()
+        .map(|resource_type| {
+            let type_str = resource_type.to_string();
+            let count = items.iter().filter(|i| i.resource_type == type_str).count();
+            (resource_type.to_plural(), count)
+        })
+        .filter(|(_, count)| *count > 0)
+        .collect()
+}
+
+/// Print the `Total: N resources (agents: 2, snippets: 1)` footer of the tree view
+fn print_totals_footer(items: &[ListItem]) {
+    let totals = type_totals(items);
+    if totals.is_empty() {
+        println!("{}: {} resources", "Total".green().bold(), items.len());
+        return;
+    }
+    let per_type = totals
+        .iter()
+        .map(|(plural, count)| format!("{plural}: {count}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    println!("{}: {} resources ({per_type})", "Total".green().bold(), items.len());
+}
+
+/// Group items by resource type (in [`ResourceType::all`] order), then by source.
+///
+/// Local resources (no source) are grouped under `local`.
+///
+/// [`ResourceType::all`]: crate::core::ResourceType::all
+fn group_items(items: &[ListItem]) -> Vec<(&'static str, BTreeMap<&str, Vec<&ListItem>>)> {
+    crate::core::ResourceType::all()
+        .iter()
+        .filter_map(|resource_type| {
+            let type_str = resource_type.to_string();
+            let mut by_source: BTreeMap<&str, Vec<&ListItem>> = BTreeMap::new();
+            for item in items.iter().filter(|i| i.resource_type == type_str) {
+                by_source.entry(item.source.as_deref().unwrap_or("local")).or_default().push(item);
+            }
+            (!by_source.is_empty()).then_some((resource_type.to_plural(), by_source))
+        })
+        .collect()
+}
+
+/// Output items grouped by resource type and source (`agpm list --tree`)
+pub fn output_tree(items: &[ListItem], config: &OutputConfig) -> Result<()> {
+    if config.format == "json" {
+        println!("{}", serde_json::to_string_pretty(&tree_json(items))?);
+        return Ok(());
+    }
+
+    if items.is_empty() {
+        println!("No installed resources found.");
+        return Ok(());
+    }
+
+    println!("{}", config.title.bold());
+    println!();
+
+    for (plural, by_source) in group_items(items) {
+        let count: usize = by_source.values().map(Vec::len).sum();
+        println!("{} ({count})", plural.cyan().bold());
+
+        let source_count = by_source.len();
+        for (source_index, (source, entries)) in by_source.iter().enumerate() {
+            let last_source = source_index + 1 == source_count;
+            let (branch, indent) = if last_source {
+                ("└──", "    ")
+            } else {
+                ("├──", "│   ")
+            };
+            println!("{branch} {} ({})", source.bright_black(), entries.len());
+
+            for (entry_index, item) in entries.iter().enumerate() {
+                let leaf = if entry_index + 1 == entries.len() {
+                    "└──"
+                } else {
+                    "├──"
+                };
+                println!("{indent}{leaf} {}", format_tree_entry(item));
+            }
+        }
+        println!();
+    }
+
+    print_totals_footer(items);
+    Ok(())
+}
+
+/// Format a single `--tree` leaf: name, resolved version, and install location
+fn format_tree_entry(item: &ListItem) -> String {
+    let version = if item.source.is_none() {
+        "local".to_string()
+    } else {
+        let version = item.version.as_deref().unwrap_or("latest");
+        match item.resolved_commit.as_deref().filter(|c| !c.is_empty()) {
+            Some(commit) => format!("{version}@{}", &commit[..7.min(commit.len())]),
+            None => version.to_string(),
+        }
+    };
+
+    let mut line = format!("{} {}", item.name.bright_white(), version.yellow());
+    if let Some(ref installed_at) = item.installed_at {
+        line.push_str(&format!(" → {}", installed_at.bright_black()));
+    }
+    line
+}
+
+/// Output resources with updates available (`agpm list --outdated-only`)
+pub fn output_outdated(items: &[OutdatedListItem], config: &OutputConfig) -> Result<()> {
+    if config.format == "json" {
+        println!("{}", serde_json::to_string_pretty(&outdated_json(items))?);
+        return Ok(());
+    }
+
+    if items.is_empty() {
+        println!("All installed resources are up to date.");
+        return Ok(());
+    }
+
+    println!("{}", config.title.bold());
+    println!();
+    println!(
+        "{:<32} {:<12} {:<15} {:<15} {:<15}",
+        "Name".cyan().bold(),
+        "Type".cyan().bold(),
+        "Current".cyan().bold(),
+        "Latest".cyan().bold(),
+        "Source".cyan().bold()
+    );
+    println!("{}", "-".repeat(92).bright_black());
+
+    for outdated in items {
+        let item = &outdated.item;
+        let latest = outdated.latest.as_deref().unwrap_or("unknown");
+        println!(
+            "{:<32} {:<12} {:<15} {:<15} {:<15}",
+            item.name.bright_white(),
+            item.resource_type,
+            item.version.as_deref().unwrap_or("latest").yellow(),
+            if outdated.latest.is_some() {
+                latest.green()
+            } else {
+                latest.bright_black()
+            },
+            item.source.as_deref().unwrap_or("local").bright_black()
+        );
+    }
+
+    if items.iter().any(|outdated| outdated.latest.is_none()) {
+        println!();
+        println!("{}", "Latest versions are unknown because --offline was given.".bright_black());
+    }
+    Ok(())
+}
+
+/// Build the JSON structure emitted by `agpm list --outdated-only --format json`
+pub fn outdated_json(items: &[OutdatedListItem]) -> serde_json::Value {
+    let resources: Vec<serde_json::Value> = items
+        .iter()
+        .map(|outdated| {
+            let item = &outdated.item;
+            serde_json::json!({
+                "name": item.name,
+                "type": item.resource_type,
+                "source": item.source,
+                "tool": item.tool,
+                "current": item.version,
+                "latest": outdated.latest.as_deref().unwrap_or("unknown"),
+                "latest_available": outdated.latest_available.as_deref().unwrap_or("unknown"),
+                "installed_at": item.installed_at,
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "outdated": resources,
+        "total": items.len(),
+    })
+}
+
+/// Output installed files that no longer match agpm.lock (`agpm list --modified`)
+pub fn output_modified(items: &[ModifiedListItem], config: &OutputConfig) -> Result<()> {
+    if config.format == "json" {
+        println!("{}", serde_json::to_string_pretty(&modified_json(items))?);
+        return Ok(());
+    }
+
+    if items.is_empty() {
+        println!("All installed files match agpm.lock.");
+        return Ok(());
+    }
+
+    println!("{}", config.title.bold());
+    println!();
+    println!(
+        "{:<32} {:<12} {:<15} {:<10} {}",
+        "Name".cyan().bold(),
+        "Type".cyan().bold(),
+        "Source".cyan().bold(),
+        "Status".cyan().bold(),
+        "Installed At".cyan().bold()
+    );
+    println!("{}", "-".repeat(92).bright_black());
+
+    for modified in items {
+        let item = &modified.item;
+        println!(
+            "{:<32} {:<12} {:<15} {:<10} {}",
+            item.name.bright_white(),
+            item.resource_type,
+            item.source.as_deref().unwrap_or("local").bright_black(),
+            if modified.missing {
+                "missing".red()
+            } else {
+                "modified".yellow()
+            },
+            item.installed_at.as_deref().unwrap_or("").bright_black()
+        );
+    }
+
+    let remote = items.iter().filter(|modified| modified.item.source.is_some()).count();
+    if remote > 0 {
+        println!();
+        eprintln!(
+            "{} {remote} resource(s) from a source will be overwritten by the next 'agpm install'",
+            "⚠".yellow()
+        );
+    }
+    Ok(())
+}
+
+/// Build the JSON structure emitted by `agpm list --modified --format json`
+pub fn modified_json(items: &[ModifiedListItem]) -> serde_json::Value {
+    let resources: Vec<serde_json::Value> = items
+        .iter()
+        .map(|modified| {
+            let item = &modified.item;
+            serde_json::json!({
+                "name": item.name,
+                "type": item.resource_type,
+                "source": item.source,
+                "tool": item.tool,
+                "installed_at": item.installed_at,
+                "status": if modified.missing { "missing" } else { "modified" },
+                "local": item.source.is_none(),
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "modified": resources,
+        "total": items.len(),
+    })
+}
+
+/// Build the grouped JSON structure emitted by `agpm list --tree --format json`
+pub fn tree_json(items: &[ListItem]) -> serde_json::Value {
+    let types: Vec<serde_json::Value> = group_items(items)
+        .into_iter()
+        .map(|(plural, by_source)| {
+            let count: usize = by_source.values().map(Vec::len).sum();
+            let sources: Vec<serde_json::Value> = by_source
+                .iter()
+                .map(|(source, entries)| {
+                    let resources: Vec<serde_json::Value> = entries
+                        .iter()
+                        .map(|item| {
+                            serde_json::json!({
+                                "name": item.name,
+                                "version": item.version,
+                                "resolved_commit": item.resolved_commit,
+                                "installed_at": item.installed_at,
+                                "tool": item.tool,
+                                "local": item.source.is_none(),
+                            })
+                        })
+                        .collect();
+                    serde_json::json!({
+                        "source": source,
+                        "count": entries.len(),
+                        "resources": resources,
+                    })
+                })
+                .collect();
+            serde_json::json!({
+                "type": plural,
+                "count": count,
+                "sources": sources,
+            })
+        })
+        .collect();
+
+    let totals: serde_json::Map<String, serde_json::Value> = type_totals(items)
+        .into_iter()
+        .map(|(plural, count)| (plural.to_string(), serde_json::Value::from(count)))
+        .collect();
+
+    serde_json::json!({
+        "types": types,
+        "totals": totals,
+        "total": items.len(),
+    })
+}
+
 /// Print a single item in detailed mode with patch comparison
 async fn print_item_detailed(item: &ListItem, lockfile: &LockFile, cache: Option<&Cache>) {
     let source = item.source.as_deref().unwrap_or("local");
@@ -357,7 +700,9 @@ fn print_item(item: &ListItem, format: &str, files: bool, detailed: bool) {
     if format == "table" && !files && !detailed {
         // Table format with columns
         // Build the name field with proper padding before adding colors
-        let name_with_indicator = if !item.applied_patches.is_empty() {
+        let name_with_indicator = if item.disabled {
+            format!("{} (disabled)", item.name)
+        } else if !item.applied_patches.is_empty() {
             format!("{} (patched)", item.name)
         } else {
             item.name.clone()
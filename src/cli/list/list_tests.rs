@@ -20,6 +20,11 @@ fn create_default_command() -> ListCommand {
         files: false,
         verbose: false,
         sort: None,
+        tree: false,
+        outdated_only: false,
+        offline: false,
+        show_disabled: false,
+        modified: false,
     }
 }
 
@@ -53,6 +58,17 @@ fn create_test_manifest() -> crate::manifest::Manifest {
             install: None,
 
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         })),
     );
 
@@ -80,6 +96,17 @@ fn create_test_manifest() -> crate::manifest::Manifest {
             install: None,
 
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         })),
     );
 
@@ -99,12 +126,16 @@ fn create_test_lockfile() -> crate::lockfile::LockFile {
         name: "official".to_string(),
         url: "https://github.com/example/official.git".to_string(),
         fetched_at: "2024-01-01T00:00:00Z".to_string(),
+        resolved_commit: None,
+        verified_tags: Vec::new(),
     });
 
     lockfile.sources.push(LockedSource {
         name: "community".to_string(),
         url: "https://github.com/example/community.git".to_string(),
         fetched_at: "2024-01-01T00:00:00Z".to_string(),
+        resolved_commit: None,
+        verified_tags: Vec::new(),
     });
 
     // Add agents
@@ -126,6 +157,8 @@ fn create_test_lockfile() -> crate::lockfile::LockFile {
         applied_patches: std::collections::BTreeMap::new(),
         install: None,
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+        strip_metadata: false,
+        inline: None,
     });
 
     lockfile.agents.push(LockedResource {
@@ -146,6 +179,8 @@ fn create_test_lockfile() -> crate::lockfile::LockFile {
         applied_patches: std::collections::BTreeMap::new(),
         install: None,
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+        strip_metadata: false,
+        inline: None,
     });
 
     // Add snippets
@@ -167,6 +202,8 @@ fn create_test_lockfile() -> crate::lockfile::LockFile {
         applied_patches: std::collections::BTreeMap::new(),
         install: None,
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+        strip_metadata: false,
+        inline: None,
     });
 
     lockfile
@@ -510,6 +547,17 @@ fn test_matches_filters_source() -> Result<()> {
         install: None,
 
         template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+        env: None,
+        transport: None,
+        url: None,
+        as_of: None,
+        post_install: None,
+        variants: None,
+        strip_metadata: None,
+        inline: None,
+        expect_commit: None,
+        enabled: None,
+        git: None,
     }));
 
     let dep_with_different_source = ResourceDependency::Detailed(Box::new(DetailedDependency {
@@ -528,6 +576,17 @@ fn test_matches_filters_source() -> Result<()> {
         install: None,
 
         template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+        env: None,
+        transport: None,
+        url: None,
+        as_of: None,
+        post_install: None,
+        variants: None,
+        strip_metadata: None,
+        inline: None,
+        expect_commit: None,
+        enabled: None,
+        git: None,
     }));
 
     let dep_without_source = ResourceDependency::Simple("local/file.md".to_string());
@@ -576,6 +635,8 @@ fn test_matches_lockfile_filters_source() -> Result<()> {
         applied_patches: std::collections::BTreeMap::new(),
         install: None,
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+        strip_metadata: false,
+        inline: None,
     };
 
     let entry_with_different_source = LockedResource {
@@ -596,6 +657,8 @@ fn test_matches_lockfile_filters_source() -> Result<()> {
         applied_patches: std::collections::BTreeMap::new(),
         install: None,
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+        strip_metadata: false,
+        inline: None,
     };
 
     let entry_without_source = LockedResource {
@@ -616,6 +679,8 @@ fn test_matches_lockfile_filters_source() -> Result<()> {
         applied_patches: std::collections::BTreeMap::new(),
         install: None,
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+        strip_metadata: false,
+        inline: None,
     };
 
     assert!(cmd.matches_lockfile_filters("test", &entry_with_source, "agent"));
@@ -649,6 +714,8 @@ fn test_matches_lockfile_filters_search() -> Result<()> {
         applied_patches: std::collections::BTreeMap::new(),
         install: None,
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+        strip_metadata: false,
+        inline: None,
     };
 
     assert!(cmd.matches_lockfile_filters("code-reviewer", &entry, "agent"));
@@ -676,6 +743,9 @@ fn test_sort_items() -> Result<()> {
             resolved_commit: None,
             tool: Some("claude-code".to_string()),
             applied_patches: std::collections::BTreeMap::new(),
+            installed: true,
+            disabled: false,
+            variant_inputs: None,
         },
         ListItem {
             name: "alpha".to_string(),
@@ -688,6 +758,9 @@ fn test_sort_items() -> Result<()> {
             resolved_commit: None,
             tool: Some("claude-code".to_string()),
             applied_patches: std::collections::BTreeMap::new(),
+            installed: true,
+            disabled: false,
+            variant_inputs: None,
         },
     ];
 
@@ -716,6 +789,9 @@ fn test_sort_items_by_version() -> Result<()> {
             resolved_commit: None,
             tool: Some("claude-code".to_string()),
             applied_patches: std::collections::BTreeMap::new(),
+            installed: true,
+            disabled: false,
+            variant_inputs: None,
         },
         ListItem {
             name: "test2".to_string(),
@@ -728,6 +804,9 @@ fn test_sort_items_by_version() -> Result<()> {
             resolved_commit: None,
             tool: Some("claude-code".to_string()),
             applied_patches: std::collections::BTreeMap::new(),
+            installed: true,
+            disabled: false,
+            variant_inputs: None,
         },
     ];
 
@@ -756,6 +835,9 @@ fn test_sort_items_by_source() -> Result<()> {
             resolved_commit: None,
             tool: Some("claude-code".to_string()),
             applied_patches: std::collections::BTreeMap::new(),
+            installed: true,
+            disabled: false,
+            variant_inputs: None,
         },
         ListItem {
             name: "test2".to_string(),
@@ -768,6 +850,9 @@ fn test_sort_items_by_source() -> Result<()> {
             resolved_commit: None,
             tool: Some("claude-code".to_string()),
             applied_patches: std::collections::BTreeMap::new(),
+            installed: true,
+            disabled: false,
+            variant_inputs: None,
         },
         ListItem {
             name: "test3".to_string(),
@@ -780,6 +865,9 @@ fn test_sort_items_by_source() -> Result<()> {
             resolved_commit: None,
             tool: Some("claude-code".to_string()),
             applied_patches: std::collections::BTreeMap::new(),
+            installed: true,
+            disabled: false,
+            variant_inputs: None,
         },
     ];
 
@@ -809,6 +897,9 @@ fn test_sort_items_by_type() -> Result<()> {
             resolved_commit: None,
             tool: Some("agpm".to_string()),
             applied_patches: std::collections::BTreeMap::new(),
+            installed: true,
+            disabled: false,
+            variant_inputs: None,
         },
         ListItem {
             name: "test2".to_string(),
@@ -821,6 +912,9 @@ fn test_sort_items_by_type() -> Result<()> {
             resolved_commit: None,
             tool: Some("claude-code".to_string()),
             applied_patches: std::collections::BTreeMap::new(),
+            installed: true,
+            disabled: false,
+            variant_inputs: None,
         },
     ];
 
@@ -852,6 +946,8 @@ fn test_lockentry_to_listitem() -> Result<()> {
         applied_patches: std::collections::BTreeMap::new(),
         install: None,
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+        strip_metadata: false,
+        inline: None,
     };
 
     let list_item = converters::lockentry_to_listitem(&lock_entry, "agent");
@@ -1157,3 +1253,263 @@ async fn test_list_empty_lockfile_json_output() -> Result<()> {
     result?;
     Ok(())
 }
+
+#[test]
+fn test_validate_arguments_tree() -> Result<()> {
+    let cmd = ListCommand {
+        tree: true,
+        format: "json".to_string(),
+        ..create_default_command()
+    };
+    assert!(cmd.validate_arguments().is_ok());
+
+    let cmd = ListCommand {
+        tree: true,
+        format: "yaml".to_string(),
+        ..create_default_command()
+    };
+    assert!(cmd.validate_arguments().is_err());
+
+    let cmd = ListCommand {
+        tree: true,
+        manifest: true,
+        ..create_default_command()
+    };
+    assert!(cmd.validate_arguments().is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_list_tree_from_lockfile() -> Result<()> {
+    let temp = TempDir::new().unwrap();
+    let manifest_path = temp.path().join("agpm.toml");
+
+    create_test_manifest().save(&manifest_path)?;
+    create_test_lockfile().save(&temp.path().join("agpm.lock"))?;
+
+    for format in ["table", "json"] {
+        let cmd = ListCommand {
+            tree: true,
+            format: format.to_string(),
+            ..create_default_command()
+        };
+        cmd.execute_from_path(manifest_path.clone()).await?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_list_json_has_stable_schema() -> Result<()> {
+    let mut lockfile = create_test_lockfile();
+    lockfile.agents[0].install = Some(false);
+    lockfile.agents[0].variant_inputs = crate::resolver::lockfile_builder::VariantInputs::new(
+        serde_json::json!({"project": {"language": "rust"}}),
+    );
+    let items: Vec<ListItem> =
+        lockfile.agents.iter().map(|e| converters::lockentry_to_listitem(e, "agent")).collect();
+
+    let json = formatters::list_json(&items);
+    let entries = json.as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+
+    let keys =
+        |entry: &serde_json::Value| entry.as_object().unwrap().keys().cloned().collect::<Vec<_>>();
+    assert_eq!(keys(&entries[0]), keys(&entries[1]));
+
+    assert_eq!(entries[0]["type"], "agent");
+    assert_eq!(entries[0]["installed"], false);
+    assert_eq!(entries[0]["resolved_commit"], "abc123def456");
+    assert_eq!(entries[0]["variant_inputs"]["project"]["language"], "rust");
+    assert_eq!(entries[1]["installed"], true);
+    assert!(entries[1]["source"].is_null());
+    assert!(entries[1]["resolved_commit"].is_null());
+    Ok(())
+}
+
+#[test]
+fn test_tree_json_grouping() -> Result<()> {
+    let lockfile = create_test_lockfile();
+    let items: Vec<ListItem> = lockfile
+        .agents
+        .iter()
+        .map(|e| converters::lockentry_to_listitem(e, "agent"))
+        .chain(lockfile.snippets.iter().map(|e| converters::lockentry_to_listitem(e, "snippet")))
+        .collect();
+
+    let json = formatters::tree_json(&items);
+
+    assert_eq!(json["total"], 3);
+    assert_eq!(json["totals"]["agents"], 2);
+    assert_eq!(json["totals"]["snippets"], 1);
+
+    let types = json["types"].as_array().unwrap();
+    assert_eq!(types[0]["type"], "agents");
+    assert_eq!(types[0]["count"], 2);
+
+    // Sources are sorted, with local resources grouped under "local"
+    let agent_sources = types[0]["sources"].as_array().unwrap();
+    assert_eq!(agent_sources[0]["source"], "local");
+    assert_eq!(agent_sources[0]["resources"][0]["local"], true);
+    assert_eq!(agent_sources[1]["source"], "official");
+    assert_eq!(agent_sources[1]["resources"][0]["version"], "v1.0.0");
+    assert_eq!(agent_sources[1]["resources"][0]["installed_at"], "agents/code-reviewer.md");
+
+    assert_eq!(types[1]["type"], "snippets");
+    Ok(())
+}
+
+#[test]
+fn test_validate_arguments_outdated_only() -> Result<()> {
+    let cmd = ListCommand {
+        outdated_only: true,
+        format: "json".to_string(),
+        ..create_default_command()
+    };
+    assert!(cmd.validate_arguments().is_ok());
+
+    for cmd in [
+        ListCommand {
+            outdated_only: true,
+            format: "yaml".to_string(),
+            ..create_default_command()
+        },
+        ListCommand {
+            outdated_only: true,
+            tree: true,
+            ..create_default_command()
+        },
+        ListCommand {
+            outdated_only: true,
+            manifest: true,
+            ..create_default_command()
+        },
+    ] {
+        assert!(cmd.validate_arguments().is_err());
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_list_outdated_only_offline() -> Result<()> {
+    let temp = TempDir::new().unwrap();
+    let manifest_path = temp.path().join("agpm.toml");
+
+    create_test_manifest().save(&manifest_path)?;
+    create_test_lockfile().save(&temp.path().join("agpm.lock"))?;
+
+    for format in ["table", "json"] {
+        let cmd = ListCommand {
+            outdated_only: true,
+            offline: true,
+            format: format.to_string(),
+            ..create_default_command()
+        };
+        cmd.execute_from_path(manifest_path.clone()).await?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_outdated_items() -> Result<()> {
+    let lockfile = create_test_lockfile();
+    let items: Vec<ListItem> = lockfile
+        .agents
+        .iter()
+        .map(|e| converters::lockentry_to_listitem(e, "agent"))
+        .chain(lockfile.snippets.iter().map(|e| converters::lockentry_to_listitem(e, "snippet")))
+        .collect();
+
+    // Offline: every resource from a source is kept with an unknown latest version
+    let offline = filters::outdated_items(items.clone(), None);
+    assert_eq!(offline.len(), 2);
+    assert!(offline.iter().all(|o| o.latest.is_none()));
+    let json = formatters::outdated_json(&offline);
+    assert_eq!(json["outdated"][0]["latest"], "unknown");
+
+    // Online: only resources with a compatible update are kept
+    let info = |name: &str, source: &str, has_update: bool| crate::cli::outdated::OutdatedInfo {
+        name: name.to_string(),
+        resource_type: "agent".to_string(),
+        source: source.to_string(),
+        tool: "claude-code".to_string(),
+        current: "v1.0.0".to_string(),
+        latest: "v1.1.0".to_string(),
+        latest_available: "v2.0.0".to_string(),
+        constraint: "^1.0.0".to_string(),
+        has_update,
+        has_major_update: true,
+    };
+    let outdated = vec![info("code-reviewer", "official", true), info("utils", "community", false)];
+    let online = filters::outdated_items(items, Some(&outdated));
+    assert_eq!(online.len(), 1);
+    assert_eq!(online[0].item.name, "code-reviewer");
+    assert_eq!(online[0].latest.as_deref(), Some("v1.1.0"));
+    Ok(())
+}
+
+#[test]
+fn test_validate_arguments_modified() -> Result<()> {
+    let cmd = ListCommand {
+        modified: true,
+        format: "json".to_string(),
+        ..create_default_command()
+    };
+    assert!(cmd.validate_arguments().is_ok());
+
+    for cmd in [
+        ListCommand {
+            modified: true,
+            format: "yaml".to_string(),
+            ..create_default_command()
+        },
+        ListCommand {
+            modified: true,
+            manifest: true,
+            ..create_default_command()
+        },
+        ListCommand {
+            modified: true,
+            outdated_only: true,
+            ..create_default_command()
+        },
+    ] {
+        assert!(cmd.validate_arguments().is_err());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_modified_items() -> Result<()> {
+    let temp = TempDir::new().unwrap();
+    let lockfile = create_test_lockfile();
+    let mut items: Vec<ListItem> = lockfile
+        .agents
+        .iter()
+        .map(|e| converters::lockentry_to_listitem(e, "agent"))
+        .chain(lockfile.snippets.iter().map(|e| converters::lockentry_to_listitem(e, "snippet")))
+        .collect();
+
+    // Write every file, then make the checksum of the first one match
+    for item in &items {
+        let path = temp.path().join(item.installed_at.as_deref().unwrap());
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        std::fs::write(&path, "edited locally")?;
+    }
+    let unchanged = temp.path().join(items[0].installed_at.as_deref().unwrap());
+    items[0].checksum = Some(crate::lockfile::LockFile::compute_checksum(&unchanged)?);
+    std::fs::remove_file(temp.path().join(items[1].installed_at.as_deref().unwrap()))?;
+
+    let modified = filters::modified_items(items.clone(), temp.path())?;
+    assert_eq!(modified.len(), items.len() - 1);
+    assert!(modified.iter().all(|m| m.item.name != items[0].name));
+    let missing = modified.iter().find(|m| m.item.name == items[1].name).unwrap();
+    assert!(missing.missing);
+
+    let json = formatters::modified_json(&modified);
+    assert_eq!(json["total"], modified.len());
+    assert_eq!(json["modified"][0]["status"], "missing");
+    assert_eq!(json["modified"][0]["local"], true);
+    assert_eq!(json["modified"][1]["status"], "modified");
+    assert_eq!(json["modified"][1]["local"], false);
+    Ok(())
+}
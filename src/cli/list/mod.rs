@@ -35,9 +35,19 @@
 //! agpm list --format json
 //! ```
 //!
-//! Show dependency tree:
+//! Group by resource type and source, with totals:
 //! ```bash
-//! agpm list --format tree
+//! agpm list --tree
+//! ```
+//!
+//! Show only resources with a newer compatible version available:
+//! ```bash
+//! agpm list --outdated-only
+//! ```
+//!
+//! Show installed files that were edited since they were installed:
+//! ```bash
+//! agpm list --modified
 //! ```
 //!
 //! List specific dependencies:
@@ -63,13 +73,15 @@
 //! }
 //! ```
 //!
-//! ## Tree Format
+//! ## Tree View (`--tree`)
 //! ```text
-//! Sources:
-//! ├── official (https://github.com/org/official.git)
-//! │   └── agents/code-reviewer.md@v1.0.0
-//! └── community (https://github.com/org/community.git)
-//!     └── snippets/utils.md@v2.1.0
+//! agents (2)
+//! ├── local (1)
+//! │   └── helper local → .claude/agents/helper.md
+//! └── official (1)
+//!     └── code-reviewer v1.0.0@abc1234 → .claude/agents/code-reviewer.md
+//!
+//! Total: 2 resources (agents: 2)
 //! ```
 //!
 //! # Data Sources
@@ -90,6 +102,7 @@ use clap::Args;
 use std::path::PathBuf;
 
 use crate::cache::Cache;
+use crate::cli::outdated::OutdatedCommand;
 use crate::manifest::{Manifest, find_manifest_with_optional};
 
 mod converters;
@@ -99,7 +112,7 @@ mod formatters;
 #[cfg(test)]
 mod list_tests;
 
-pub use formatters::{ListItem, OutputConfig};
+pub use formatters::{ListItem, ModifiedListItem, OutdatedListItem, OutputConfig};
 
 /// Command to list installed Claude Code resources.
 ///
@@ -126,6 +139,9 @@ pub use formatters::{ListItem, OutputConfig};
 ///     files: false,
 ///     verbose: false,
 ///     sort: None,
+///     tree: false,
+///     outdated_only: false,
+///     offline: false,
 /// };
 ///
 /// // List only agents with detailed information
@@ -141,6 +157,9 @@ pub use formatters::{ListItem, OutputConfig};
 ///     files: true,
 ///     verbose: false,
 ///     sort: Some("name".to_string()),
+///     tree: false,
+///     outdated_only: false,
+///     offline: false,
 /// };
 /// ```
 #[derive(Args)]
@@ -237,6 +256,48 @@ pub struct ListCommand {
     /// - `type`: Sort by resource type (agents first, then snippets)
     #[arg(long, value_name = "FIELD")]
     sort: Option<String>,
+
+    /// Group output by resource type and source
+    ///
+    /// Shows installed resources from `agpm.lock` as a tree: resource types
+    /// with counts, then sources (or `local`) underneath, with each entry's
+    /// resolved version and install location. With `--format json`, emits the
+    /// same grouping as a structured object with per-type totals.
+    #[arg(long)]
+    tree: bool,
+
+    /// Show only resources with a newer compatible version available
+    ///
+    /// Compares installed resources from `agpm.lock` against their sources,
+    /// like `agpm outdated`, and lists just the ones with an update within
+    /// their version constraint. Sources are fetched first unless `--offline`
+    /// is given.
+    #[arg(long)]
+    outdated_only: bool,
+
+    /// Don't access the network with `--outdated-only`
+    ///
+    /// Every resource from a Git source is listed with its latest version
+    /// reported as "unknown".
+    #[arg(long, requires = "outdated_only")]
+    offline: bool,
+
+    /// Also list dependencies disabled with `enabled = false`
+    ///
+    /// Disabled dependencies stay in `agpm.toml` but are not installed. They
+    /// are shown with a "(disabled)" marker, and with `"disabled": true` in
+    /// JSON output.
+    #[arg(long, conflicts_with_all = ["tree", "outdated_only"])]
+    show_disabled: bool,
+
+    /// Show only installed files that no longer match agpm.lock
+    ///
+    /// Recomputes the checksum of each installed file and lists those that
+    /// differ from the one recorded in `agpm.lock`, or that are missing. Edits
+    /// to local resources are expected; edits to resources from a source are
+    /// overwritten by the next `agpm install`. Nothing is modified.
+    #[arg(long, conflicts_with_all = ["tree", "outdated_only", "show_disabled"])]
+    modified: bool,
 }
 
 impl ListCommand {
@@ -291,6 +352,7 @@ impl ListCommand {
     ///     files: false,
     ///     verbose: false,
     ///     sort: Some("name".to_string()),
+    ///     tree: false,
     /// };
     /// // cmd.execute_with_manifest_path(None).await?;
     /// # Ok::<(), anyhow::Error>(())
@@ -344,6 +406,48 @@ impl ListCommand {
             }
         }
 
+        if self.tree {
+            if self.manifest {
+                return Err(anyhow::anyhow!(
+                    "--tree reads installed resources from agpm.lock and cannot be used with --manifest"
+                ));
+            }
+            if !matches!(self.format.as_str(), "table" | "json") {
+                return Err(anyhow::anyhow!(
+                    "--tree supports the table and json formats, not '{}'",
+                    self.format
+                ));
+            }
+        }
+
+        if self.outdated_only {
+            if self.manifest || self.tree {
+                return Err(anyhow::anyhow!(
+                    "--outdated-only reads installed resources from agpm.lock and cannot be used with --manifest or --tree"
+                ));
+            }
+            if !matches!(self.format.as_str(), "table" | "json") {
+                return Err(anyhow::anyhow!(
+                    "--outdated-only supports the table and json formats, not '{}'",
+                    self.format
+                ));
+            }
+        }
+
+        if self.modified {
+            if self.manifest || self.tree || self.outdated_only {
+                return Err(anyhow::anyhow!(
+                    "--modified reads installed resources from agpm.lock and cannot be used with --manifest, --tree or --outdated-only"
+                ));
+            }
+            if !matches!(self.format.as_str(), "table" | "json") {
+                return Err(anyhow::anyhow!(
+                    "--modified supports the table and json formats, not '{}'",
+                    self.format
+                ));
+            }
+        }
+
         // Validate type filter
         if let Some(ref t) = self.r#type {
             match t.as_str() {
@@ -395,6 +499,9 @@ impl ListCommand {
             // Get dependencies for this resource type from the manifest
             if let Some(deps) = manifest.get_dependencies(*resource_type) {
                 for (name, dep) in deps {
+                    if !dep.is_enabled() && !self.show_disabled {
+                        continue;
+                    }
                     if self.matches_filters(name, Some(dep), &type_str) {
                         items.push(ListItem {
                             name: name.clone(),
@@ -411,6 +518,9 @@ impl ListCommand {
                                     .unwrap_or_else(|| manifest.get_default_tool(*resource_type)),
                             ),
                             applied_patches: std::collections::BTreeMap::new(),
+                            installed: dep.get_install().unwrap_or(true),
+                            disabled: !dep.is_enabled(),
+                            variant_inputs: None,
                         });
                     }
                 }
@@ -420,6 +530,9 @@ impl ListCommand {
         // Handle MCP servers (now using standard ResourceDependency)
         if self.should_show_resource_type(crate::core::ResourceType::McpServer) {
             for (name, mcp_dep) in &manifest.mcp_servers {
+                if !mcp_dep.is_enabled() && !self.show_disabled {
+                    continue;
+                }
                 // MCP servers now use standard ResourceDependency
                 if self.matches_filters(name, Some(mcp_dep), "mcp-server") {
                     items.push(ListItem {
@@ -435,6 +548,9 @@ impl ListCommand {
                             || manifest.get_default_tool(crate::core::ResourceType::McpServer),
                         )),
                         applied_patches: std::collections::BTreeMap::new(),
+                        installed: mcp_dep.get_install().unwrap_or(true),
+                        disabled: !mcp_dep.is_enabled(),
+                        variant_inputs: None,
                     });
                 }
             }
@@ -512,13 +628,72 @@ impl ListCommand {
             }
         }
 
+        if self.show_disabled {
+            for disabled in &lockfile.disabled {
+                if !self.should_show_resource_type(disabled.resource_type) {
+                    continue;
+                }
+                let type_str = disabled.resource_type.to_string();
+                let dep = command_context
+                    .manifest
+                    .get_dependencies(disabled.resource_type)
+                    .and_then(|deps| deps.get(&disabled.name));
+                if self.matches_filters(&disabled.name, dep, &type_str) {
+                    items.push(converters::disabled_to_listitem(
+                        disabled,
+                        dep,
+                        &command_context.manifest,
+                    ));
+                }
+            }
+        }
+
         // Sort items
         self.sort_items(&mut items);
 
         // Handle special flags
+        if self.outdated_only {
+            let outdated = if self.offline {
+                None
+            } else {
+                let check = OutdatedCommand {
+                    no_progress: true,
+                    ..OutdatedCommand::default()
+                };
+                Some(check.find_outdated(&manifest_path).await?.0)
+            };
+            return formatters::output_outdated(
+                &filters::outdated_items(items, outdated.as_deref()),
+                &OutputConfig {
+                    title: "Outdated resources from agpm.lock:".to_string(),
+                    format: self.format.clone(),
+                    ..OutputConfig::default()
+                },
+            );
+        }
+
+        if self.modified {
+            return formatters::output_modified(
+                &filters::modified_items(items, project_dir)?,
+                &OutputConfig {
+                    title: "Installed files modified since agpm.lock was written:".to_string(),
+                    format: self.format.clone(),
+                    ..OutputConfig::default()
+                },
+            );
+        }
 
         // Output results
-        if self.detailed {
+        if self.tree {
+            formatters::output_tree(
+                &items,
+                &OutputConfig {
+                    title: "Installed resources from agpm.lock:".to_string(),
+                    format: self.format.clone(),
+                    ..OutputConfig::default()
+                },
+            )?;
+        } else if self.detailed {
             formatters::output_items_detailed(
                 &items,
                 "Installed resources from agpm.lock:",
@@ -0,0 +1,212 @@
+//! Maintain the lockfile without re-resolving dependencies.
+//!
+//! This module provides the `lock` command for lockfile-only maintenance.
+//! `agpm lock --refresh-checksums` recomputes checksums from the installed
+//! files, which is useful after intentionally editing a local resource: the
+//! lockfile is brought up to date without the network access and version
+//! re-resolution of a full `agpm update`.
+//!
+//! Only the `checksum` field of local (source-less) resources is changed;
+//! versions and commits are left untouched. A changed file from a source
+//! repository is reported as a warning and its checksum is kept, since it
+//! may have been tampered with; `agpm install` restores the locked content.
+//!
+//! `agpm lock --sort` rewrites the lockfile in canonical order: resources by
+//! source, name, tool and variant inputs, and sources by name. Every command
+//! that writes the lockfile already uses this order, so this is only needed
+//! after merges or hand edits.
+//!
+//! # Examples
+//!
+//! ```bash
+//! # Accept edits to local resources
+//! agpm lock --refresh-checksums
+//!
+//! # Restore canonical ordering after a merge
+//! agpm lock --sort
+//! ```
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+use crate::lockfile::LockFile;
+use crate::manifest::find_manifest_with_optional;
+
+/// Command to maintain the lockfile without re-resolving dependencies.
+#[derive(Args)]
+#[command(arg_required_else_help = true)]
+pub struct LockCommand {
+    /// Recompute checksums of local resources from the installed files
+    #[arg(long)]
+    refresh_checksums: bool,
+
+    /// Rewrite the lockfile in canonical order
+    #[arg(long)]
+    sort: bool,
+}
+
+impl LockCommand {
+    /// Execute the lock command with an optional manifest path.
+    pub async fn execute_with_manifest_path(self, manifest_path: Option<PathBuf>) -> Result<()> {
+        let manifest_path = find_manifest_with_optional(manifest_path)
+            .context("No agpm.toml found. Please create one to define your dependencies.")?;
+        let project_dir =
+            manifest_path.parent().context("Manifest path has no parent directory")?.to_path_buf();
+        let lockfile_path = project_dir.join("agpm.lock");
+        if !lockfile_path.exists() {
+            bail!("No agpm.lock found. Run 'agpm install' first to generate it.");
+        }
+
+        if self.refresh_checksums {
+            refresh_checksums(&project_dir, &lockfile_path)?;
+        }
+        if self.sort {
+            sort_lockfile(&lockfile_path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Update stale checksums of local resources and report everything else.
+fn refresh_checksums(project_dir: &Path, lockfile_path: &Path) -> Result<()> {
+    let mut lockfile = LockFile::load(lockfile_path)?;
+    let refresh = lockfile.refresh_checksums(project_dir)?;
+
+    for path in &refresh.remote_mismatches {
+        println!(
+            "{} {path} differs from the locked checksum; it comes from a source repository, so \
+             the checksum was kept (run 'agpm install' to restore it)",
+            "⚠".yellow()
+        );
+    }
+    for path in &refresh.missing {
+        println!("{} {path} is not installed; skipped", "⚠".yellow());
+    }
+
+    if refresh.updated.is_empty() {
+        println!("{} All local checksums are up to date", "✓".green());
+        return Ok(());
+    }
+
+    lockfile.save(lockfile_path)?;
+    for path in &refresh.updated {
+        println!("  {} {path}", "updated".green());
+    }
+    println!("{} Refreshed {} checksum(s) in agpm.lock", "✓".green(), refresh.updated.len());
+    Ok(())
+}
+
+/// Re-save the lockfile, which writes it in canonical order.
+fn sort_lockfile(lockfile_path: &Path) -> Result<()> {
+    let before = std::fs::read_to_string(lockfile_path)
+        .with_context(|| format!("Failed to read {}", lockfile_path.display()))?;
+    LockFile::load(lockfile_path)?.save(lockfile_path)?;
+    let after = std::fs::read_to_string(lockfile_path)
+        .with_context(|| format!("Failed to read {}", lockfile_path.display()))?;
+
+    if before == after {
+        println!("{} agpm.lock is already in canonical order", "✓".green());
+    } else {
+        println!("{} Sorted agpm.lock", "✓".green());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ResourceType;
+    use crate::lockfile::LockedResourceBuilder;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_refresh_checksums_rewrites_lockfile() -> Result<()> {
+        let temp = TempDir::new()?;
+        std::fs::write(temp.path().join("agpm.toml"), "[agents]\n")?;
+        std::fs::create_dir_all(temp.path().join(".claude/agents"))?;
+        let installed = temp.path().join(".claude/agents/helper.md");
+        std::fs::write(&installed, "edited")?;
+
+        let mut lockfile = LockFile::new();
+        let mut helper = LockedResourceBuilder::new(
+            "helper".to_string(),
+            "agents/helper.md".to_string(),
+            "sha256:old".to_string(),
+            ".claude/agents/helper.md".to_string(),
+            ResourceType::Agent,
+        )
+        .build();
+        helper.version = Some("local".to_string());
+        lockfile.agents.push(helper);
+        lockfile.save(&temp.path().join("agpm.lock"))?;
+
+        let cmd = LockCommand {
+            refresh_checksums: true,
+            sort: false,
+        };
+        cmd.execute_with_manifest_path(Some(temp.path().join("agpm.toml"))).await?;
+
+        let reloaded = LockFile::load(&temp.path().join("agpm.lock"))?;
+        assert_eq!(reloaded.agents[0].checksum, LockFile::compute_checksum(&installed)?);
+        assert_eq!(reloaded.agents[0].version.as_deref(), Some("local"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sort_canonicalizes_hand_edited_lockfile() -> Result<()> {
+        let temp = TempDir::new()?;
+        std::fs::write(temp.path().join("agpm.toml"), "[agents]\n")?;
+        let lockfile_path = temp.path().join("agpm.lock");
+
+        let resource = |name: &str, source: Option<&str>| {
+            let mut resource = LockedResourceBuilder::new(
+                name.to_string(),
+                format!("agents/{name}.md"),
+                String::new(),
+                format!(".claude/agents/{name}.md"),
+                ResourceType::Agent,
+            )
+            .build();
+            resource.source = source.map(ToString::to_string);
+            resource
+        };
+        let mut lockfile = LockFile::new();
+        lockfile.add_source(
+            "zeta".to_string(),
+            "https://example.com/z.git".to_string(),
+            String::new(),
+        );
+        lockfile.add_source(
+            "alpha".to_string(),
+            "https://example.com/a.git".to_string(),
+            String::new(),
+        );
+        lockfile.agents.push(resource("a-remote", Some("zeta")));
+        lockfile.agents.push(resource("b-remote", Some("alpha")));
+        lockfile.agents.push(resource("c-local", None));
+        lockfile.save(&lockfile_path)?;
+        let canonical = std::fs::read_to_string(&lockfile_path)?;
+
+        // Simulate a merge that left the arrays out of order
+        let mut toml: toml::Value = toml::from_str(&canonical)?;
+        for key in ["sources", "agents"] {
+            toml[key].as_array_mut().unwrap().reverse();
+        }
+        std::fs::write(&lockfile_path, toml::to_string(&toml)?)?;
+
+        let cmd = LockCommand {
+            refresh_checksums: false,
+            sort: true,
+        };
+        cmd.execute_with_manifest_path(Some(temp.path().join("agpm.toml"))).await?;
+
+        assert_eq!(std::fs::read_to_string(&lockfile_path)?, canonical);
+        let reloaded = LockFile::load(&lockfile_path)?;
+        let paths: Vec<_> = reloaded.agents.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(paths, ["agents/c-local.md", "agents/b-remote.md", "agents/a-remote.md"]);
+        assert_eq!(reloaded.sources[0].name, "alpha");
+        Ok(())
+    }
+}
@@ -1,20 +1,68 @@
-//! Migration command for renaming legacy CCPM files to AGPM.
+//! Migration command for upgrading legacy CCPM projects to AGPM.
 //!
 //! This module provides functionality to migrate from the legacy CCPM (Claude Code Package Manager)
 //! naming to the new AGPM naming. It detects and renames ccpm.toml and ccpm.lock files to their
 //! agpm equivalents, then automatically runs installation to move artifacts to their correct locations.
+//!
+//! It also converts a legacy `[target]` section, which the manifest parser no
+//! longer reads, into the equivalent `[tools]` configuration:
+//!
+//! ```toml
+//! # Before
+//! [target]
+//! agents = ".claude/custom-agents"
+//! snippets = "shared/snippets"
+//! gitignore = false
+//!
+//! # After
+//! gitignore = false
+//!
+//! [tools.claude-code]
+//! path = ".claude"
+//!
+//! [tools.claude-code.resources.agents]
+//! path = "custom-agents"
+//! flatten = true
+//!
+//! [tools.opencode]
+//! path = ".opencode"
+//!
+//! [tools.agpm]
+//! path = ".agpm"
+//!
+//! [tools.agpm.resources.snippets]
+//! path = "../shared/snippets"
+//! flatten = false
+//! ```
+//!
+//! The manifest is edited with `toml_edit`, so comments and formatting outside
+//! the `[target]` section are preserved.
 
-use anyhow::{Context, Result, bail};
+use anyhow::{Context, Result, anyhow, bail};
 use clap::Parser;
 use colored::Colorize;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+use toml_edit::{DocumentMut, Item, Table};
 
 use crate::cli::install::InstallCommand;
+use crate::manifest::ToolsConfig;
+
+/// Legacy `[target]` directory keys and the tool whose resource path replaces each.
+///
+/// Snippets are installed by the `agpm` tool by default, so their directory
+/// moves there; the other directory-based types belong to `claude-code`.
+const LEGACY_TARGET_DIRS: &[(&str, &str)] = &[
+    ("agents", "claude-code"),
+    ("snippets", "agpm"),
+    ("commands", "claude-code"),
+    ("scripts", "claude-code"),
+];
 
 /// Migrate from legacy CCPM naming to AGPM.
 ///
 /// This command detects ccpm.toml and ccpm.lock files in the current directory,
-/// renames them to agpm.toml and agpm.lock respectively, and automatically runs
+/// renames them to agpm.toml and agpm.lock respectively, converts a legacy
+/// `[target]` section into `[tools]` configuration, and automatically runs
 /// installation to move artifacts from .claude/ccpm/ to their correct locations.
 ///
 /// # Examples
@@ -41,7 +89,7 @@ pub struct MigrateCommand {
     #[arg(short, long)]
     path: Option<PathBuf>,
 
-    /// Show what would be renamed without actually renaming files.
+    /// Show what would be changed without renaming or writing any files.
     ///
     /// This is useful for previewing the migration before committing to it.
     #[arg(long)]
@@ -90,6 +138,58 @@ impl MigrateCommand {
         let dir = self.path.as_deref().unwrap_or_else(|| Path::new("."));
         let dir = dir.canonicalize().context("Failed to resolve directory path")?;
 
+        let renamed = self.migrate_files(&dir)?;
+        let converted = self.migrate_target_section(&dir)?;
+
+        if !renamed && !converted {
+            return Ok(());
+        }
+
+        if self.dry_run {
+            println!(
+                "\n{} (use without --dry-run to perform migration)",
+                "Dry run complete".yellow()
+            );
+            return Ok(());
+        }
+
+        println!("\n🎉 {}", "Migration completed successfully!".green().bold());
+
+        // Run installation to move artifacts to correct locations
+        if !self.skip_install {
+            println!("\n📦 {}", "Running installation to update artifact locations...".cyan());
+
+            let install_cmd = InstallCommand::new();
+            let manifest_path = dir.join("agpm.toml");
+            match install_cmd.execute_from_path(Some(&manifest_path)).await {
+                Ok(()) => {
+                    println!("✅ {}", "Artifacts moved to correct locations".green());
+                }
+                Err(e) => {
+                    eprintln!("\n⚠️  {}", "Warning: Installation failed".yellow());
+                    eprintln!("   {}", format!("Error: {}", e).yellow());
+                    eprintln!("   {}", "You may need to run 'agpm install' manually".yellow());
+                }
+            }
+        } else {
+            println!(
+                "\n💡 Next step: Run {} to move artifacts to correct locations",
+                "agpm install".cyan()
+            );
+        }
+
+        println!(
+            "\n💡 Remember to:\n  • Review the changes\n  • Run {} to verify\n  • Commit the changes to version control",
+            "agpm validate".cyan()
+        );
+
+        Ok(())
+    }
+
+    /// Rename `ccpm.toml` and `ccpm.lock` to their AGPM names.
+    ///
+    /// Returns whether there was anything to rename.
+    fn migrate_files(&self, dir: &Path) -> Result<bool> {
         println!("🔍 Checking for legacy CCPM files in: {}", dir.display());
 
         let ccpm_toml = dir.join("ccpm.toml");
@@ -105,7 +205,7 @@ impl MigrateCommand {
         // Check if there are any CCPM files to migrate
         if !ccpm_toml_exists && !ccpm_lock_exists {
             println!("✅ {}", "No legacy CCPM files found.".green());
-            return Ok(());
+            return Ok(false);
         }
 
         // Check for conflicts
@@ -134,11 +234,7 @@ impl MigrateCommand {
         }
 
         if self.dry_run {
-            println!(
-                "\n{} (use without --dry-run to perform migration)",
-                "Dry run complete".yellow()
-            );
-            return Ok(());
+            return Ok(true);
         }
 
         // Perform the migration
@@ -154,38 +250,236 @@ impl MigrateCommand {
             println!("✅ {}", "Renamed ccpm.lock → agpm.lock".green());
         }
 
-        println!("\n🎉 {}", "File migration completed successfully!".green().bold());
+        Ok(true)
+    }
 
-        // Run installation to move artifacts to correct locations
-        if !self.skip_install {
-            println!("\n📦 {}", "Running installation to update artifact locations...".cyan());
+    /// Convert a legacy `[target]` section in the manifest to `[tools]`.
+    ///
+    /// Prints a diff of the manifest change. Returns whether the manifest
+    /// had a `[target]` section.
+    fn migrate_target_section(&self, dir: &Path) -> Result<bool> {
+        // In a dry run the manifest has not been renamed yet
+        let manifest_path =
+            [dir.join("agpm.toml"), dir.join("ccpm.toml")].into_iter().find(|path| path.exists());
+        let Some(manifest_path) = manifest_path else {
+            return Ok(false);
+        };
 
-            let install_cmd = InstallCommand::new();
-            let manifest_path = dir.join("agpm.toml");
-            match install_cmd.execute_from_path(Some(&manifest_path)).await {
-                Ok(()) => {
-                    println!("✅ {}", "Artifacts moved to correct locations".green());
-                }
-                Err(e) => {
-                    eprintln!("\n⚠️  {}", "Warning: Installation failed".yellow());
-                    eprintln!("   {}", format!("Error: {}", e).yellow());
-                    eprintln!("   {}", "You may need to run 'agpm install' manually".yellow());
-                }
+        let content = std::fs::read_to_string(&manifest_path)
+            .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+        let mut doc: DocumentMut = content
+            .parse()
+            .with_context(|| format!("Failed to parse {}", manifest_path.display()))?;
+
+        let Some(notes) = convert_target_section(&mut doc)? else {
+            println!("✅ {}", "No legacy [target] section found.".green());
+            return Ok(false);
+        };
+        let migrated = doc.to_string();
+
+        println!("\n📦 Converting [target] to [tools] in {}:", manifest_path.display());
+        for line in line_diff(&content, &migrated) {
+            match line.chars().next() {
+                Some('-') => println!("  {}", line.red()),
+                Some('+') => println!("  {}", line.green()),
+                _ => println!("  {line}"),
             }
-        } else {
-            println!(
-                "\n💡 Next step: Run {} to move artifacts to correct locations",
-                "agpm install".cyan()
+        }
+        for note in &notes {
+            println!("⚠️  {}", note.yellow());
+        }
+
+        if !self.dry_run {
+            std::fs::write(&manifest_path, migrated)
+                .with_context(|| format!("Failed to write {}", manifest_path.display()))?;
+            println!("✅ {}", "Converted [target] → [tools]".green());
+        }
+
+        Ok(true)
+    }
+}
+
+/// Replace a legacy `[target]` section with the equivalent `[tools]` settings.
+///
+/// Directory overrides become `[tools.<tool>.resources.<type>]` entries whose
+/// path is relative to the tool's base directory; overrides equal to the
+/// current defaults are dropped. `gitignore` moves to the top level. Settings
+/// already present in `[tools]` are kept, since they took precedence anyway.
+///
+/// Returns `None` if there is no `[target]` section, otherwise notes about
+/// settings that could not be carried over.
+fn convert_target_section(doc: &mut DocumentMut) -> Result<Option<Vec<String>>> {
+    let Some(target) = doc.remove("target") else {
+        return Ok(None);
+    };
+    let target =
+        target.into_table().map_err(|_| anyhow!("[target] in the manifest is not a table"))?;
+
+    let defaults = ToolsConfig::default();
+    let mut notes = Vec::new();
+
+    for (key, item) in target.iter() {
+        if key == "gitignore" {
+            if doc.contains_key("gitignore") {
+                notes.push(
+                    "target.gitignore dropped; the top-level gitignore setting is kept".to_string(),
+                );
+            } else {
+                doc.insert("gitignore", item.clone());
+            }
+            continue;
+        }
+
+        let Some(&(_, tool)) = LEGACY_TARGET_DIRS.iter().find(|(name, _)| *name == key) else {
+            let reason = if matches!(key, "hooks" | "mcp-servers") {
+                "hooks and MCP servers are now merged into tool configuration files"
+            } else {
+                "it is not a recognized setting"
+            };
+            notes.push(format!("target.{key} dropped: {reason}"));
+            continue;
+        };
+        let Some(legacy_path) = item.as_str() else {
+            notes.push(format!("target.{key} dropped: expected a path string"));
+            continue;
+        };
+
+        // Resource paths are relative to the tool's base directory, which the
+        // manifest may already override
+        let tool_defaults = &defaults.types[tool];
+        let tool_dir = doc
+            .get("tools")
+            .and_then(|tools| tools.get(tool))
+            .and_then(|config| config.get("path"))
+            .and_then(Item::as_str)
+            .map_or_else(|| tool_defaults.path.to_string_lossy().into_owned(), str::to_string);
+        let Some(path) = tool_relative_path(&tool_dir, legacy_path) else {
+            notes.push(format!(
+                "target.{key} dropped: '{legacy_path}' must be a path inside the project"
+            ));
+            continue;
+        };
+        let default_resource = &tool_defaults.resources[key];
+        if default_resource.path.as_deref() == Some(path.as_str()) {
+            continue;
+        }
+
+        let tool_table = tools_table(doc)?
+            .entry(tool)
+            .or_insert_with(|| {
+                let mut table = Table::new();
+                table.insert("path", toml_edit::value(tool_dir.as_str()));
+                Item::Table(table)
+            })
+            .as_table_like_mut()
+            .ok_or_else(|| anyhow!("[tools.{tool}] in the manifest is not a table"))?;
+        let resources = tool_table
+            .entry("resources")
+            .or_insert_with(implicit_table)
+            .as_table_like_mut()
+            .ok_or_else(|| anyhow!("tools.{tool}.resources in the manifest is not a table"))?;
+        if resources.contains_key(key) {
+            notes.push(format!(
+                "target.{key} dropped: tools.{tool}.resources.{key} is already configured"
+            ));
+            continue;
+        }
+
+        let mut resource = Table::new();
+        resource.insert("path", toml_edit::value(path));
+        if let Some(flatten) = default_resource.flatten {
+            resource.insert("flatten", toml_edit::value(flatten));
+        }
+        resources.insert(key, Item::Table(resource));
+    }
+
+    Ok(Some(notes))
+}
+
+/// Get the `[tools]` table, creating it if needed.
+///
+/// A new `[tools]` section declares every built-in tool, because tools not
+/// listed in `[tools]` are not available at all.
+fn tools_table(doc: &mut DocumentMut) -> Result<&mut Table> {
+    if !doc.contains_key("tools") {
+        let mut tools = Table::new();
+        tools.set_implicit(true);
+        let defaults = ToolsConfig::default();
+        for tool in ["claude-code", "opencode", "agpm"] {
+            let mut table = Table::new();
+            table.insert(
+                "path",
+                toml_edit::value(defaults.types[tool].path.to_string_lossy().as_ref()),
             );
+            tools.insert(tool, Item::Table(table));
         }
+        doc.insert("tools", Item::Table(tools));
+    }
+    doc["tools"].as_table_mut().ok_or_else(|| anyhow!("[tools] in the manifest is not a table"))
+}
 
-        println!(
-            "\n💡 Remember to:\n  • Review the changes\n  • Run {} to verify\n  • Commit the changes to version control",
-            "agpm validate".cyan()
-        );
+/// Create a table that is only written out through its subtables.
+fn implicit_table() -> Item {
+    let mut table = Table::new();
+    table.set_implicit(true);
+    Item::Table(table)
+}
 
-        Ok(())
+/// Express a project-relative legacy path relative to a tool's base directory.
+///
+/// Returns `None` for absolute paths and paths that leave the project.
+fn tool_relative_path(tool_dir: &str, legacy_path: &str) -> Option<String> {
+    let mut parts = Vec::new();
+    for component in Path::new(legacy_path).components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_str()?),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                parts.pop()?;
+            }
+            Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    if parts.is_empty() {
+        return None;
+    }
+
+    let tool_parts: Vec<&str> =
+        tool_dir.split('/').filter(|part| !part.is_empty() && *part != ".").collect();
+    if parts.len() > tool_parts.len() && parts.starts_with(&tool_parts) {
+        return Some(parts[tool_parts.len()..].join("/"));
+    }
+    let up = std::iter::repeat_n("..", tool_parts.len());
+    Some(up.chain(parts).collect::<Vec<_>>().join("/"))
+}
+
+/// Compute a line diff, returning changed lines prefixed with `-` or `+`.
+///
+/// Unchanged lines are omitted.
+fn line_diff(old: &str, new: &str) -> Vec<String> {
+    use super::diff::{DiffOp, diff_lines};
+
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let (mut i, mut j) = (0, 0);
+    let mut diff = Vec::new();
+    for op in diff_lines(&old, &new) {
+        match op {
+            DiffOp::Equal => {
+                i += 1;
+                j += 1;
+            }
+            DiffOp::Delete => {
+                diff.push(format!("- {}", old[i]));
+                i += 1;
+            }
+            DiffOp::Insert => {
+                diff.push(format!("+ {}", new[j]));
+                j += 1;
+            }
+        }
     }
+    diff
 }
 
 #[cfg(test)]
@@ -367,4 +661,121 @@ mod tests {
         assert!(temp_dir.path().join("agpm.toml").exists());
         Ok(())
     }
+
+    #[test]
+    fn test_tool_relative_path() {
+        assert_eq!(
+            tool_relative_path(".claude", ".claude/custom-agents"),
+            Some("custom-agents".into())
+        );
+        assert_eq!(tool_relative_path(".claude", "./.claude/a/b/"), Some("a/b".into()));
+        assert_eq!(tool_relative_path(".claude", "custom/agents"), Some("../custom/agents".into()));
+        assert_eq!(tool_relative_path("./.agpm", ".agpm/snippets"), Some("snippets".into()));
+        assert_eq!(tool_relative_path(".claude", "/abs/agents"), None);
+        assert_eq!(tool_relative_path(".claude", "../outside"), None);
+    }
+
+    #[test]
+    fn test_convert_target_section() -> Result<()> {
+        let mut doc: DocumentMut = r#"# Team manifest
+[sources]
+community = "https://github.com/aig787/agpm-community.git" # shared agents
+
+[target]
+agents = "custom/agents"
+snippets = ".agpm/snippets"
+commands = ".claude/team-commands"
+hooks = ".claude/hooks"
+gitignore = false
+"#
+        .parse()?;
+
+        let notes = convert_target_section(&mut doc)?.unwrap();
+        assert_eq!(notes.len(), 1, "{notes:?}");
+        assert!(notes[0].contains("target.hooks dropped"));
+
+        let migrated = doc.to_string();
+        assert!(migrated.contains("# shared agents"));
+        assert!(!migrated.contains("[target]"));
+
+        let manifest: crate::manifest::Manifest = toml::from_str(&migrated)?;
+        assert!(!manifest.gitignore);
+        assert_eq!(
+            manifest.get_artifact_resource_path("claude-code", crate::core::ResourceType::Agent),
+            Some(PathBuf::from(".claude/../custom/agents"))
+        );
+        assert_eq!(
+            manifest.get_artifact_resource_path("claude-code", crate::core::ResourceType::Command),
+            Some(PathBuf::from(".claude/team-commands"))
+        );
+        // Defaults are left alone, and the other built-in tools stay available
+        assert_eq!(
+            manifest.get_artifact_resource_path("agpm", crate::core::ResourceType::Snippet),
+            Some(PathBuf::from(".agpm/snippets"))
+        );
+        assert!(manifest.get_tool_config("opencode").is_some());
+        let agents = &manifest.get_tool_config("claude-code").unwrap().resources["agents"];
+        assert_eq!(agents.flatten, Some(true));
+
+        // Already migrated: nothing to do
+        assert!(convert_target_section(&mut doc)?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_target_section_keeps_existing_tools() -> Result<()> {
+        let mut doc: DocumentMut = r#"
+[target]
+agents = "ai/bots"
+scripts = "bin"
+
+[tools.claude-code]
+path = "ai"
+
+[tools.claude-code.resources.scripts]
+path = "tools"
+"#
+        .parse()?;
+
+        let notes = convert_target_section(&mut doc)?.unwrap();
+        assert_eq!(notes.len(), 1, "{notes:?}");
+        assert!(notes[0].contains("tools.claude-code.resources.scripts is already configured"));
+
+        let tools = doc["tools"]["claude-code"].as_table().unwrap();
+        assert_eq!(tools["path"].as_str(), Some("ai"));
+        assert_eq!(tools["resources"]["agents"]["path"].as_str(), Some("bots"));
+        assert_eq!(tools["resources"]["scripts"]["path"].as_str(), Some("tools"));
+        assert!(doc["tools"].get("opencode").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_line_diff() {
+        let diff = line_diff("a\nb\nc\n", "a\nc\nd\n");
+        assert_eq!(diff, vec!["- b".to_string(), "+ d".to_string()]);
+        assert!(line_diff("same\n", "same\n").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_migrate_target_section_writes_manifest() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let agpm_toml = temp_dir.path().join("agpm.toml");
+        fs::write(&agpm_toml, "[target]\nagents = \".claude/team\"\n")?;
+
+        let cmd = MigrateCommand::new(Some(temp_dir.path().to_path_buf()), true, true);
+        cmd.execute().await?;
+        assert!(fs::read_to_string(&agpm_toml)?.contains("[target]"));
+
+        let cmd = MigrateCommand::new(Some(temp_dir.path().to_path_buf()), false, true);
+        cmd.execute().await?;
+        let migrated = fs::read_to_string(&agpm_toml)?;
+        assert!(!migrated.contains("[target]"));
+        assert!(migrated.contains("[tools.claude-code.resources.agents]"));
+
+        // Running again is a no-op
+        let cmd = MigrateCommand::new(Some(temp_dir.path().to_path_buf()), false, true);
+        cmd.execute().await?;
+        assert_eq!(fs::read_to_string(&agpm_toml)?, migrated);
+        Ok(())
+    }
 }
@@ -25,6 +25,7 @@
 //! ## Information and Inspection
 //! - `list` - List installed resources from the lockfile
 //! - `tree` - Display dependency tree for installed resources
+//! - `graph` - Export the dependency graph in Graphviz DOT format
 //! - `outdated` - Check for available updates to dependencies
 //! - `validate` - Validate project configuration and dependencies
 //!
@@ -101,6 +102,7 @@
 //! - `--quiet` - Suppress all output except errors
 //! - `--no-progress` - Disable progress bars and spinners
 //! - `--config` - Path to custom config file
+//! - `--log-format` - Log output format (`text` or `json`)
 //!
 //! # Example
 //!
@@ -116,16 +118,28 @@
 //! ```
 
 mod add;
+mod bundle;
 mod cache;
+mod clean;
 pub mod common;
 mod config;
+mod diff;
+mod doctor;
+mod export;
+mod fmt;
+mod graph;
 mod init;
 pub mod install;
+mod install_plan;
+mod install_report;
 mod list;
+mod lock;
 mod migrate;
 mod outdated;
+mod pattern;
 mod remove;
 mod tree;
+mod uninstall;
 mod update;
 /// Self-update functionality for upgrading AGPM to newer versions.
 ///
@@ -204,6 +218,45 @@ pub struct CliConfig {
     /// - Alternative configuration layouts
     /// - Shared configuration in team environments
     pub config_path: Option<String>,
+
+    /// Format of the tracing log output written to stderr.
+    ///
+    /// [`LogFormat::Json`] emits one JSON object per log event for CI systems
+    /// that parse logs. It is independent of the `--format` option some
+    /// commands offer for their results.
+    pub log_format: LogFormat,
+
+    /// Whether to skip dependencies' `post_install` commands.
+    ///
+    /// Passed to the install and update commands as a safety switch, for
+    /// example when installing from an untrusted manifest.
+    pub no_post_install: bool,
+
+    /// Timeout for network Git operations, overriding `network_timeout` from
+    /// the global config.
+    pub timeout: Option<std::time::Duration>,
+
+    /// Whether to hash every file in full instead of reusing checksums of
+    /// files whose size and modification time are unchanged.
+    pub no_mtime_cache: bool,
+
+    /// Whether a failing command reports its error as a JSON object on
+    /// stderr instead of colored text.
+    pub json_errors: bool,
+}
+
+/// Format of the tracing log output.
+///
+/// Selected with the global `--log-format` option or the `AGPM_LOG_FORMAT`
+/// environment variable.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable log lines.
+    #[default]
+    Text,
+    /// One JSON object per line with the level, target, message, fields,
+    /// and the enclosing spans.
+    Json,
 }
 
 impl CliConfig {
@@ -213,6 +266,11 @@ impl CliConfig {
     /// - No log level override (`log_level: None`)
     /// - Progress indicators enabled (`no_progress: false`)
     /// - Default config file location (`config_path: None`)
+    /// - Human-readable logs (`log_format: LogFormat::Text`)
+    /// - Post-install commands enabled (`no_post_install: false`)
+    /// - Network timeout from the global config (`timeout: None`)
+    /// - Checksum cache enabled (`no_mtime_cache: false`)
+    /// - Colored error output (`json_errors: false`)
     ///
     /// # Examples
     ///
@@ -250,6 +308,7 @@ impl CliConfig {
 /// - **Verbosity control**: `--verbose` and `--quiet` for output level
 /// - **Configuration**: `--config` for custom config file paths
 /// - **UI control**: `--no-progress` for automation-friendly output
+/// - **Logging**: `--log-format json` for structured logs
 ///
 /// # Examples
 ///
@@ -410,6 +469,81 @@ pub struct Cli {
     /// ```
     #[arg(long, global = true)]
     no_progress: bool,
+
+    /// Format of log output: human-readable text or JSON lines.
+    ///
+    /// JSON logs carry the level, target, message, fields, and span context
+    /// of each event, for CI systems that parse logs. This only affects
+    /// logging; use a command's own `--format` option for machine-readable
+    /// results. Progress bars are disabled in JSON mode so they do not
+    /// interleave with log lines.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// agpm --log-format json --verbose install 2> agpm-log.jsonl
+    /// AGPM_LOG_FORMAT=json RUST_LOG=info agpm update
+    /// ```
+    #[arg(long, global = true, value_enum, env = "AGPM_LOG_FORMAT", default_value = "text")]
+    log_format: LogFormat,
+
+    /// Never run dependencies' `post_install` commands.
+    ///
+    /// Post-install commands run arbitrary code from the project root after a
+    /// dependency is installed. Use this flag to install without running any
+    /// of them, for example when reviewing an unfamiliar manifest.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// agpm --no-post-install install
+    /// ```
+    #[arg(long, global = true)]
+    no_post_install: bool,
+
+    /// Timeout for each network Git operation (e.g. `60s`, `5m`).
+    ///
+    /// A clone, fetch or ls-remote that runs longer is killed and reported
+    /// with the source it was talking to. Operations on local repositories
+    /// are not limited. Overrides `network_timeout` in the global config.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// agpm --timeout 60s install
+    /// AGPM_TIMEOUT=2m agpm update
+    /// ```
+    #[arg(long, global = true, value_name = "DURATION", env = "AGPM_TIMEOUT", value_parser = crate::utils::parse_duration)]
+    timeout: Option<std::time::Duration>,
+
+    /// Hash every installed file in full instead of trusting cached checksums.
+    ///
+    /// AGPM remembers the checksum of each file it hashes together with its
+    /// size and modification time, and skips re-reading files where both are
+    /// unchanged. This flag disables that cache for the run.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// agpm --no-mtime-cache lock --refresh-checksums
+    /// ```
+    #[arg(long, global = true)]
+    no_mtime_cache: bool,
+
+    /// Report a failing command's error as a JSON object on stderr.
+    ///
+    /// The object has `error`, `details` and `suggestion` keys. Version
+    /// conflicts also list every conflicting dependency under `conflicts`,
+    /// with the version each party requires and the chain of dependencies
+    /// that introduced it.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// agpm --json-errors install 2> error.json
+    /// ```
+    #[arg(long, global = true)]
+    json_errors: bool,
 }
 
 /// Available subcommands for the AGPM CLI.
@@ -426,15 +560,25 @@ pub struct Cli {
 /// - [`Remove`](Commands::Remove): Remove sources and dependencies
 /// - [`Install`](Commands::Install): Install dependencies from manifest
 /// - [`Update`](Commands::Update): Update dependencies within constraints
+/// - [`Lock`](Commands::Lock): Maintain the lockfile without re-resolving
+/// - [`Clean`](Commands::Clean): Remove installed resources from the project
+/// - [`Uninstall`](Commands::Uninstall): Uninstall one dependency, keeping it in the manifest
+/// - [`Fmt`](Commands::Fmt): Normalize the formatting of the manifest
 ///
 /// ## Information & Validation
 /// - [`List`](Commands::List): Display installed resources
 /// - [`Tree`](Commands::Tree): Display dependency tree
+/// - [`Graph`](Commands::Graph): Export the dependency graph in Graphviz DOT format
 /// - [`Validate`](Commands::Validate): Verify project configuration
+/// - [`Export`](Commands::Export): Export an audit report of locked resources
+/// - [`Diff`](Commands::Diff): Compare a resource between versions
+/// - [`Pattern`](Commands::Pattern): Preview the files a pattern dependency matches
 ///
 /// ## System Management
 /// - [`Cache`](Commands::Cache): Manage Git repository cache
+/// - [`Bundle`](Commands::Bundle): Export and import offline dependency bundles
 /// - [`Config`](Commands::Config): Manage global configuration
+/// - [`Doctor`](Commands::Doctor): Diagnose the AGPM environment
 /// - [`Upgrade`](Commands::Upgrade): Self-update AGPM to newer versions
 /// - [`Migrate`](Commands::Migrate): Migrate from legacy CCPM naming to AGPM
 ///
@@ -513,6 +657,39 @@ enum Commands {
     /// See [`update::UpdateCommand`] for detailed options and behavior.
     Update(update::UpdateCommand),
 
+    /// Maintain the lockfile without re-resolving dependencies.
+    ///
+    /// Refreshes the checksums of local resources from their installed
+    /// files, leaving versions and commits untouched.
+    ///
+    /// See [`lock::LockCommand`] for detailed options and behavior.
+    Lock(lock::LockCommand),
+
+    /// Remove everything AGPM installed into the project.
+    ///
+    /// Deletes the files recorded in `agpm.lock` and the managed hook and MCP
+    /// server entries, leaving `agpm.toml` and user files untouched.
+    ///
+    /// See [`clean::CleanCommand`] for detailed options and behavior.
+    Clean(clean::CleanCommand),
+
+    /// Uninstall one dependency while keeping it in `agpm.toml`.
+    ///
+    /// Deletes the dependency's installed files and managed configuration
+    /// entries and drops it from `agpm.lock`, so the next `agpm install`
+    /// reinstalls it. Refuses if other installed resources depend on it.
+    ///
+    /// See [`uninstall::UninstallCommand`] for detailed options and behavior.
+    Uninstall(uninstall::UninstallCommand),
+
+    /// Normalize the formatting of `agpm.toml`.
+    ///
+    /// Sorts dependencies by name and rewrites them in their simplest form,
+    /// preserving comments. `--check` reports unformatted manifests in CI.
+    ///
+    /// See [`fmt::FmtCommand`] for detailed options and behavior.
+    Fmt(fmt::FmtCommand),
+
     /// Check for available updates to installed dependencies.
     ///
     /// Compares installed versions from the lockfile against available versions
@@ -549,6 +726,14 @@ enum Commands {
     /// See [`tree::TreeCommand`] for detailed options and behavior.
     Tree(tree::TreeCommand),
 
+    /// Export the dependency graph of agpm.lock in Graphviz DOT format.
+    ///
+    /// Emits one node per locked resource, colored by resource type, and an
+    /// edge to each of its dependencies, for rendering with `dot -Tpng`.
+    ///
+    /// See [`graph::GraphCommand`] for detailed options and behavior.
+    Graph(graph::GraphCommand),
+
     /// Validate AGPM project configuration and dependencies.
     ///
     /// Performs comprehensive validation of the project manifest, dependencies,
@@ -567,6 +752,41 @@ enum Commands {
     /// See [`cache::CacheCommand`] for detailed options and behavior.
     Cache(cache::CacheCommand),
 
+    /// Export or import an offline dependency bundle.
+    ///
+    /// Packages the commits and files pinned in `agpm.lock` into a single
+    /// archive, and installs from such an archive on a machine without
+    /// network access.
+    ///
+    /// See [`bundle::BundleCommand`] for detailed options and behavior.
+    Bundle(bundle::BundleCommand),
+
+    /// Export an audit report of every locked resource.
+    ///
+    /// Writes a sorted CSV or JSON listing of each resource's type, name,
+    /// origin, resolved commit and checksum from `agpm.lock`, for security
+    /// and compliance reviews.
+    ///
+    /// See [`export::ExportCommand`] for detailed options and behavior.
+    Export(export::ExportCommand),
+
+    /// Compare a resource's content between versions.
+    ///
+    /// Prints a unified diff of a source file between two refs, or of each
+    /// locally modified installed file against its locked version.
+    ///
+    /// See [`diff::DiffCommand`] for detailed options and behavior.
+    Diff(diff::DiffCommand),
+
+    /// Preview the files a pattern dependency would install.
+    ///
+    /// Resolves a glob dependency spec against its source and lists every
+    /// matched file with its resource name and install path, without
+    /// installing anything or modifying the manifest.
+    ///
+    /// See [`pattern::PatternCommand`] for detailed options and behavior.
+    Pattern(pattern::PatternCommand),
+
     /// Manage global AGPM configuration.
     ///
     /// Provides operations for managing the global configuration file
@@ -576,6 +796,15 @@ enum Commands {
     /// See [`config::ConfigCommand`] for detailed options and behavior.
     Config(config::ConfigCommand),
 
+    /// Diagnose the AGPM environment.
+    ///
+    /// Checks the Git installation, cache directory, global configuration,
+    /// and reachability of configured sources, and suggests fixes for any
+    /// problems found.
+    ///
+    /// See [`doctor::DoctorCommand`] for detailed options and behavior.
+    Doctor(doctor::DoctorCommand),
+
     /// Migrate from legacy CCPM naming to AGPM.
     ///
     /// Detects and renames ccpm.toml and ccpm.lock files to agpm.toml
@@ -629,7 +858,8 @@ impl Cli {
     /// - **Verbose mode**: Sets log level to "debug" for detailed output
     /// - **Quiet mode**: Disables logging for automation-friendly output
     /// - **Default mode**: Uses "info" level for normal operation
-    /// - **Progress control**: Honors `--no-progress` flag for animations
+    /// - **Progress control**: Honors `--no-progress` flag for animations, and
+    ///   disables progress when logging JSON so bars don't interleave with it
     /// - **Config path**: Uses custom config file if specified
     ///
     /// # Validation
@@ -663,8 +893,13 @@ impl Cli {
 
         CliConfig {
             log_level,
-            no_progress: self.no_progress,
+            no_progress: self.no_progress || self.log_format == LogFormat::Json,
             config_path: self.config.clone(),
+            log_format: self.log_format,
+            no_post_install: self.no_post_install,
+            timeout: self.timeout,
+            no_mtime_cache: self.no_mtime_cache,
+            json_errors: self.json_errors,
         }
     }
 
@@ -720,21 +955,53 @@ impl Cli {
             Self::check_for_updates_if_needed().await;
         }
 
+        // Apply git_binary/git_args and network_timeout from the global config;
+        // environment variables and --timeout win
+        let global_config = crate::config::GlobalConfig::load_with_optional(
+            config.config_path.as_ref().map(PathBuf::from),
+        )
+        .await
+        .unwrap_or_default();
+        let mut git_settings = global_config.git_settings();
+        if config.timeout.is_some() {
+            git_settings.network_timeout = config.timeout;
+        }
+        crate::git::command_builder::configure_git(git_settings);
+
+        if global_config.dedup {
+            crate::installer::dedup::enable();
+        }
+
+        if !config.no_mtime_cache
+            && let Ok(cache_dir) = crate::config::get_cache_dir()
+        {
+            crate::lockfile::checksum_cache::enable(&cache_dir);
+        }
+
         // Pass configuration directly to commands that need it
-        match self.command {
+        let result = match self.command {
             Commands::Init(cmd) => cmd.execute_with_manifest_path(self.manifest_path).await,
-            Commands::Add(cmd) => cmd.execute_with_manifest_path(self.manifest_path).await,
+            Commands::Add(mut cmd) => {
+                cmd.no_post_install = config.no_post_install;
+                cmd.execute_with_manifest_path(self.manifest_path).await
+            }
             Commands::Remove(cmd) => cmd.execute_with_manifest_path(self.manifest_path).await,
             Commands::Install(mut cmd) => {
                 // Pass no_progress and verbose flags to install command
                 cmd.no_progress = cmd.no_progress || config.no_progress;
                 cmd.verbose = config.log_level == Some("debug".to_string());
+                cmd.auto_detect_tools = global_config.auto_detect_tools;
+                cmd.tool_commands = global_config.tool_commands;
+                cmd.io_parallel = cmd.io_parallel.or(global_config.io_parallel);
+                cmd.no_post_install = config.no_post_install;
                 cmd.execute_with_manifest_path(self.manifest_path).await
             }
             Commands::Update(mut cmd) => {
                 // Pass no_progress and verbose flags to update command
                 cmd.no_progress = cmd.no_progress || config.no_progress;
                 cmd.verbose = config.log_level == Some("debug".to_string());
+                cmd.no_post_install = config.no_post_install;
+                cmd.io_parallel = cmd.io_parallel.or(global_config.io_parallel);
                 cmd.execute_with_manifest_path(self.manifest_path).await
             }
             Commands::Outdated(mut cmd) => {
@@ -744,16 +1011,35 @@ impl Cli {
             }
             Commands::Upgrade(cmd) => upgrade::execute(cmd).await,
             Commands::List(cmd) => cmd.execute_with_manifest_path(self.manifest_path).await,
+            Commands::Lock(cmd) => cmd.execute_with_manifest_path(self.manifest_path).await,
+            Commands::Clean(cmd) => cmd.execute_with_manifest_path(self.manifest_path).await,
+            Commands::Uninstall(cmd) => cmd.execute_with_manifest_path(self.manifest_path).await,
+            Commands::Fmt(cmd) => cmd.execute_with_manifest_path(self.manifest_path).await,
             Commands::Tree(cmd) => cmd.execute_with_manifest_path(self.manifest_path).await,
+            Commands::Graph(cmd) => cmd.execute_with_manifest_path(self.manifest_path).await,
             Commands::Validate(cmd) => cmd.execute_with_manifest_path(self.manifest_path).await,
             Commands::Cache(cmd) => cmd.execute_with_manifest_path(self.manifest_path).await,
+            Commands::Bundle(cmd) => cmd.execute_with_manifest_path(self.manifest_path).await,
+            Commands::Export(cmd) => cmd.execute_with_manifest_path(self.manifest_path).await,
+            Commands::Diff(cmd) => cmd.execute_with_manifest_path(self.manifest_path).await,
+            Commands::Pattern(cmd) => cmd.execute_with_manifest_path(self.manifest_path).await,
             Commands::Config(cmd) => {
                 // Pass config_path to config command if provided
                 let config_path = config.config_path.as_ref().map(PathBuf::from);
                 cmd.execute(config_path).await
             }
+            Commands::Doctor(cmd) => {
+                let config_path = config.config_path.as_ref().map(PathBuf::from);
+                cmd.execute(self.manifest_path, config_path).await
+            }
             Commands::Migrate(cmd) => cmd.execute().await,
+        };
+
+        // The checksum cache only speeds up later runs, so failing to save it is not fatal
+        if let Err(e) = crate::lockfile::checksum_cache::persist() {
+            tracing::debug!("Failed to save checksum cache: {e:#}");
         }
+        result
     }
 
     /// Check for AGPM updates automatically based on configuration.
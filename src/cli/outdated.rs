@@ -151,7 +151,7 @@ use anyhow::{Context, Result};
 use clap::Args;
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tracing::{debug, info};
 
@@ -632,8 +632,37 @@ impl OutdatedCommand {
     pub async fn execute_from_path(self, manifest_path: PathBuf) -> Result<()> {
         info!("Checking for outdated dependencies");
 
+        let (outdated_deps, total) = self.find_outdated(&manifest_path).await?;
+
+        // 7. Calculate summary
+        let summary = self.calculate_summary(&outdated_deps, total);
+
+        // 8. Display results
+        self.display_results(&outdated_deps, &summary)?;
+
+        // 9. Exit with appropriate code
+        if self.check && outdated_deps.iter().any(|d| d.has_update || d.has_major_update) {
+            std::process::exit(1);
+        }
+
+        Ok(())
+    }
+
+    /// Find the dependencies that have a compatible or major update available.
+    ///
+    /// Returns the outdated dependencies together with the total number of
+    /// resources in the lockfile. Also used by `agpm list --outdated-only`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest or lockfile cannot be loaded, or if
+    /// syncing sources or resolving updates fails.
+    pub(crate) async fn find_outdated(
+        &self,
+        manifest_path: &Path,
+    ) -> Result<(Vec<OutdatedInfo>, usize)> {
         // 1. Load manifest and lockfile
-        let manifest = Manifest::load(&manifest_path)
+        let manifest = Manifest::load(manifest_path)
             .with_context(|| format!("Failed to load manifest from {manifest_path:?}"))?;
 
         let project_dir =
@@ -692,6 +721,9 @@ impl OutdatedCommand {
                 .map(|(name, dep)| (name.to_string(), dep.clone()))
                 .collect();
 
+            // The newest tags are needed even for dependencies pinned to exact versions
+            resolver.set_fetch_all_refs(true);
+
             // TODO: Thread progress parameter through outdated command
             resolver.pre_sync_sources(&deps, None).await.context("Failed to sync sources")?;
 
@@ -753,18 +785,7 @@ impl OutdatedCommand {
             }
         }
 
-        // 7. Calculate summary
-        let summary = self.calculate_summary(&outdated_deps, lockfile.all_resources().len());
-
-        // 8. Display results
-        self.display_results(&outdated_deps, &summary)?;
-
-        // 9. Exit with appropriate code
-        if self.check && outdated_deps.iter().any(|d| d.has_update || d.has_major_update) {
-            std::process::exit(1);
-        }
-
-        Ok(())
+        Ok((outdated_deps, lockfile.all_resources().len()))
     }
 
     /// Analyze a single dependency update by comparing old and new lockfile entries.
@@ -862,7 +883,13 @@ impl OutdatedCommand {
             return Ok(None);
         }
 
-        let available_versions = resolver.get_available_versions(&bare_repo_path).await?;
+        let mut available_versions = resolver.get_available_versions(&bare_repo_path).await?;
+
+        // Sources with tag_prefix/tag_pattern only version through their selected tags
+        if let Some(filter) = manifest.sources.options(source_name).tag_filter()? {
+            available_versions =
+                filter.apply(available_versions).into_iter().map(|(_, part)| part).collect();
+        }
 
         // Filter to semantic versions
         let mut semver_versions: Vec<semver::Version> = available_versions
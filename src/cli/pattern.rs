@@ -0,0 +1,302 @@
+//! Preview pattern dependencies before adding them to the manifest.
+//!
+//! This module provides the `pattern` command. `agpm pattern expand` takes a
+//! dependency spec whose path is a glob, checks the pattern like the manifest
+//! does, resolves it against the source at the requested version (fetching
+//! the source into the cache if needed) and lists every matched file with the
+//! name AGPM gives it and the path it would be installed to. Nothing is
+//! installed and the manifest is not modified.
+//!
+//! # Examples
+//!
+//! ```bash
+//! # Preview a remote pattern at a version
+//! agpm pattern expand "community:agents/**/*.md@v1.0.0"
+//!
+//! # Preview a local pattern as snippets for OpenCode
+//! agpm pattern expand "./shared/*.md" --type snippet --tool opencode
+//! ```
+
+use anyhow::{Context, Result, bail};
+use clap::{Args, Subcommand};
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+use crate::cache::Cache;
+use crate::config::GlobalConfig;
+use crate::core::ResourceType;
+use crate::manifest::{Manifest, ResourceDependency, find_manifest_with_optional};
+use crate::pattern::{PatternResolver, extract_resource_name, validate_pattern_safety};
+use crate::resolver::path_resolver;
+
+/// Number of matches above which a pattern is reported as unexpectedly broad.
+const LARGE_MATCH_COUNT: usize = 50;
+
+/// Command to work with pattern dependencies.
+#[derive(Args)]
+pub struct PatternCommand {
+    /// Pattern operation to perform
+    #[command(subcommand)]
+    command: PatternSubcommands,
+}
+
+/// Subcommands for the pattern command.
+#[derive(Subcommand)]
+enum PatternSubcommands {
+    /// List the files a pattern dependency would install
+    ///
+    /// The spec uses the same form as `agpm add dep`: `source:pattern@version`
+    /// for a Git source, or a local path pattern.
+    Expand {
+        /// Dependency spec whose path is a glob pattern
+        spec: String,
+
+        /// Resource type of the matched files (inferred from the path if omitted)
+        #[arg(long = "type", value_name = "TYPE")]
+        resource_type: Option<ResourceType>,
+
+        /// Tool to compute install paths for (defaults to the resource type's default tool)
+        #[arg(long)]
+        tool: Option<String>,
+    },
+}
+
+/// A file matched by a pattern, as it would be installed.
+#[derive(Debug, PartialEq, Eq)]
+struct ExpandedFile {
+    /// Name AGPM gives the matched resource
+    name: String,
+    /// Path of the file within the source (or relative to the pattern base)
+    path: String,
+    /// Where the file would be installed, relative to the project
+    installed_at: String,
+}
+
+impl PatternCommand {
+    /// Execute the pattern command with an optional manifest path.
+    pub async fn execute_with_manifest_path(self, manifest_path: Option<PathBuf>) -> Result<()> {
+        let manifest_path = find_manifest_with_optional(manifest_path)
+            .context("No agpm.toml found. Please create one to define your dependencies.")?;
+        let manifest = Manifest::load(&manifest_path)?;
+
+        match self.command {
+            PatternSubcommands::Expand {
+                spec,
+                resource_type,
+                tool,
+            } => expand(&manifest, &spec, resource_type, tool).await,
+        }
+    }
+}
+
+/// Resolve a pattern spec and print the files it matches.
+async fn expand(
+    manifest: &Manifest,
+    spec: &str,
+    resource_type: Option<ResourceType>,
+    tool: Option<String>,
+) -> Result<()> {
+    let resource_type = match resource_type {
+        Some(resource_type) => resource_type,
+        None => super::add::infer_resource_type(spec)?,
+    };
+    let (_, dep) = super::add::parse_dependency_spec(spec, &None, Some(manifest))?;
+    let pattern = dep.get_path();
+    if !dep.is_pattern() {
+        bail!("'{pattern}' is not a glob pattern. Use *, ? or [...] to match several files");
+    }
+    validate_pattern_safety(pattern)?;
+
+    let tool = tool.unwrap_or_else(|| manifest.get_default_tool(resource_type));
+    let (files, location) = match dep.get_source() {
+        Some(source) => {
+            let (worktree, commit) = checkout_source(manifest, source, dep.get_version()).await?;
+            let version = dep.get_version().unwrap_or("latest");
+            let files = expand_in_worktree(manifest, &dep, resource_type, &tool, &worktree)?;
+            (files, format!("{source}@{version} ({})", &commit[..commit.len().min(8)]))
+        }
+        None => (expand_local(manifest, &dep, resource_type, &tool)?, "local files".to_string()),
+    };
+
+    if files.is_empty() {
+        println!("Pattern '{pattern}' matches no files in {location}");
+        return Ok(());
+    }
+
+    println!("{}", format!("Pattern '{pattern}' in {location} matches:").bold());
+    println!();
+    println!(
+        "{:<32} {:<40} {}",
+        "Name".cyan().bold(),
+        "Path".cyan().bold(),
+        "Installed At".cyan().bold()
+    );
+    println!("{}", "-".repeat(100).bright_black());
+    for file in &files {
+        println!(
+            "{:<32} {:<40} {}",
+            file.name.bright_white(),
+            file.path,
+            file.installed_at.bright_black()
+        );
+    }
+    println!();
+    println!("{} file(s) as {} for {tool}", files.len(), resource_type.to_plural());
+
+    if files.len() > LARGE_MATCH_COUNT {
+        eprintln!(
+            "{} Pattern matches {} files; check that it is not broader than intended",
+            "⚠".yellow(),
+            files.len()
+        );
+    }
+    Ok(())
+}
+
+/// Fetch a source and check out `version` (the newest for `None`), returning
+/// the worktree and commit.
+async fn checkout_source(
+    manifest: &Manifest,
+    source: &str,
+    version: Option<&str>,
+) -> Result<(PathBuf, String)> {
+    let sources =
+        GlobalConfig::load().await.unwrap_or_default().merge_sources(&manifest.sources)?;
+    let url = sources.get(source).with_context(|| {
+        format!("Unknown source '{source}'. Add it to [sources] in agpm.toml or the global config")
+    })?;
+
+    let cache = Cache::new()?;
+    cache.configure_sources(&manifest.sources).await;
+    cache.replace_sources(manifest.replacement_urls()?).await;
+
+    let provider = crate::source::provider::provider_for(&cache, url);
+    provider
+        .fetch(source, url)
+        .await
+        .with_context(|| format!("Failed to access source repository: {source}"))?;
+    let commit =
+        provider.resolve_version_to_commit(source, url, version).await.with_context(|| {
+            format!(
+                "Failed to resolve version '{}' for source {source}",
+                version.unwrap_or("latest")
+            )
+        })?;
+    let worktree = provider
+        .checkout(source, url, &commit)
+        .await
+        .with_context(|| format!("Failed to create worktree for {source}@{commit}"))?;
+    Ok((worktree, commit))
+}
+
+/// Match a Git pattern dependency in a checked-out source, as `agpm install` does.
+fn expand_in_worktree(
+    manifest: &Manifest,
+    dep: &ResourceDependency,
+    resource_type: ResourceType,
+    tool: &str,
+    worktree: &Path,
+) -> Result<Vec<ExpandedFile>> {
+    PatternResolver::new()
+        .resolve(dep.get_path(), worktree)?
+        .into_iter()
+        .map(|matched| {
+            Ok(ExpandedFile {
+                name: extract_resource_name(&matched),
+                installed_at: path_resolver::resolve_git_pattern_install_path(
+                    manifest,
+                    dep,
+                    tool,
+                    resource_type,
+                    worktree,
+                    &matched,
+                )?,
+                path: crate::utils::normalize_path_for_storage(
+                    matched.to_string_lossy().to_string(),
+                ),
+            })
+        })
+        .collect()
+}
+
+/// Match a local pattern dependency, as `agpm install` does.
+fn expand_local(
+    manifest: &Manifest,
+    dep: &ResourceDependency,
+    resource_type: ResourceType,
+    tool: &str,
+) -> Result<Vec<ExpandedFile>> {
+    let (base_path, pattern) = path_resolver::parse_pattern_base_path(dep.get_path());
+    PatternResolver::new()
+        .resolve(&pattern, &base_path)?
+        .into_iter()
+        .map(|matched| {
+            let filename = path_resolver::extract_pattern_filename(&base_path, &matched);
+            Ok(ExpandedFile {
+                name: extract_resource_name(&matched),
+                installed_at: path_resolver::resolve_install_path(
+                    manifest,
+                    dep,
+                    tool,
+                    resource_type,
+                    &filename,
+                )?,
+                path: path_resolver::construct_full_relative_path(&base_path, &matched),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_expand_in_worktree() {
+        // Agents are flattened by default, so nested matches install side by side
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path();
+        std::fs::create_dir_all(repo.join("agents/review")).unwrap();
+        std::fs::write(repo.join("agents/helper.md"), "helper").unwrap();
+        std::fs::write(repo.join("agents/review/strict.md"), "strict").unwrap();
+        std::fs::write(repo.join("agents/notes.txt"), "not matched").unwrap();
+
+        let manifest = Manifest::new();
+        let (_, dep) = super::super::add::parse_dependency_spec(
+            "community:agents/**/*.md@v1.0.0",
+            &None,
+            Some(&manifest),
+        )
+        .unwrap();
+
+        let mut files =
+            expand_in_worktree(&manifest, &dep, ResourceType::Agent, "claude-code", repo).unwrap();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        assert_eq!(
+            files,
+            vec![
+                ExpandedFile {
+                    name: "helper".to_string(),
+                    path: "agents/helper.md".to_string(),
+                    installed_at: ".claude/agents/helper.md".to_string(),
+                },
+                ExpandedFile {
+                    name: "strict".to_string(),
+                    path: "agents/review/strict.md".to_string(),
+                    installed_at: ".claude/agents/strict.md".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_expand_rejects_unsafe_and_plain_paths() {
+        let manifest = Manifest::new();
+        let err = expand(&manifest, "community:agents/../../*.md@v1", None, None).await;
+        assert!(err.unwrap_err().to_string().contains("path traversal"));
+
+        let err = expand(&manifest, "community:agents/helper.md@v1", None, None).await;
+        assert!(err.unwrap_err().to_string().contains("not a glob pattern"));
+    }
+}
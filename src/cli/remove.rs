@@ -36,7 +36,7 @@ use clap::{Args, Subcommand};
 use colored::Colorize;
 
 use crate::core::ResourceType;
-use crate::lockfile::LockFile;
+use crate::lockfile::{LockFile, LockedResource};
 use crate::manifest::{Manifest, ResourceDependency, find_manifest_with_optional};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -137,6 +137,23 @@ const fn get_dependencies_for_type_mut(
     }
 }
 
+/// Helper function to resolve a locked resource's install path inside the project.
+///
+/// Returns `None` (with a warning) when a hand-edited lockfile points outside the
+/// project directory, so removal never deletes files it did not install.
+fn installed_path_in_project(
+    project_root: &std::path::Path,
+    entry: &LockedResource,
+) -> Option<PathBuf> {
+    match crate::installer::resolve_install_destination(project_root, entry) {
+        Ok(path) => Some(path),
+        Err(e) => {
+            tracing::warn!("Not removing '{}' ({}): {e}", entry.name, entry.installed_at);
+            None
+        }
+    }
+}
+
 /// Helper function to get the installed path for a resource from lockfile
 fn get_installed_path_from_lockfile(
     lockfile: &LockFile,
@@ -150,32 +167,32 @@ fn get_installed_path_from_lockfile(
             .agents
             .iter()
             .find(|a| a.lookup_name() == name)
-            .map(|a| project_root.join(&a.installed_at)),
+            .and_then(|a| installed_path_in_project(project_root, a)),
         ResourceType::Snippet => lockfile
             .snippets
             .iter()
             .find(|s| s.lookup_name() == name)
-            .map(|s| project_root.join(&s.installed_at)),
+            .and_then(|s| installed_path_in_project(project_root, s)),
         ResourceType::Command => lockfile
             .commands
             .iter()
             .find(|c| c.lookup_name() == name)
-            .map(|c| project_root.join(&c.installed_at)),
+            .and_then(|c| installed_path_in_project(project_root, c)),
         ResourceType::McpServer => lockfile
             .mcp_servers
             .iter()
             .find(|m| m.lookup_name() == name)
-            .map(|m| project_root.join(&m.installed_at)),
+            .and_then(|m| installed_path_in_project(project_root, m)),
         ResourceType::Script => lockfile
             .scripts
             .iter()
             .find(|s| s.lookup_name() == name)
-            .map(|s| project_root.join(&s.installed_at)),
+            .and_then(|s| installed_path_in_project(project_root, s)),
         ResourceType::Hook => lockfile
             .hooks
             .iter()
             .find(|h| h.lookup_name() == name)
-            .map(|h| project_root.join(&h.installed_at)),
+            .and_then(|h| installed_path_in_project(project_root, h)),
     }
 }
 
@@ -315,36 +332,35 @@ async fn remove_source_with_manifest_path(
         };
 
         // Find and remove installed files from this source
-        let agents_to_remove: Vec<String> = lockfile
+        let agents_to_remove: Vec<PathBuf> = lockfile
             .agents
             .iter()
             .filter(|a| a.source.as_deref() == Some(name))
-            .map(|a| a.installed_at.clone())
+            .filter_map(|a| installed_path_in_project(project_root, a))
             .collect();
 
-        let snippets_to_remove: Vec<String> = lockfile
+        let snippets_to_remove: Vec<PathBuf> = lockfile
             .snippets
             .iter()
             .filter(|s| s.source.as_deref() == Some(name))
-            .map(|s| s.installed_at.clone())
+            .filter_map(|s| installed_path_in_project(project_root, s))
             .collect();
 
-        let commands_to_remove: Vec<String> = lockfile
+        let commands_to_remove: Vec<PathBuf> = lockfile
             .commands
             .iter()
             .filter(|c| c.source.as_deref() == Some(name))
-            .map(|c| c.installed_at.clone())
+            .filter_map(|c| installed_path_in_project(project_root, c))
             .collect();
 
         // Delete all installed files from this source
-        for path_str in agents_to_remove
+        for path in agents_to_remove
             .iter()
             .chain(snippets_to_remove.iter())
             .chain(commands_to_remove.iter())
         {
-            let path = project_root.join(path_str);
             if path.exists() {
-                tokio::fs::remove_file(&path).await.with_context(|| {
+                tokio::fs::remove_file(path).await.with_context(|| {
                     format!("Failed to remove installed file: {}", path.display())
                 })?;
             }
@@ -920,6 +936,8 @@ test-agent = "../test/agent.md"
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
+            inline: None,
         });
         lockfile.save(&lockfile_path).unwrap();
         // Remove an agent (should update lockfile)
@@ -1038,6 +1056,8 @@ test-snippet = { source = "test-source", path = "snippets/test.md", version = "v
             name: "test-source".to_string(),
             url: "https://github.com/test/repo.git".to_string(),
             fetched_at: "2024-01-01T00:00:00Z".to_string(),
+            resolved_commit: None,
+            verified_tags: Vec::new(),
         });
 
         // Add agent with installed path (relative to project directory)
@@ -1059,6 +1079,8 @@ test-snippet = { source = "test-source", path = "snippets/test.md", version = "v
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
+            inline: None,
         });
 
         // Add snippet with installed path (relative to project directory)
@@ -1080,6 +1102,8 @@ test-snippet = { source = "test-source", path = "snippets/test.md", version = "v
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
+            inline: None,
         });
 
         lockfile.save(&lockfile_path).unwrap();
@@ -1129,6 +1153,57 @@ test-snippet = { source = "test-source", path = "snippets/test.md", version = "v
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_remove_never_deletes_outside_project() -> Result<()> {
+        use crate::lockfile::LockedResourceBuilder;
+
+        let temp = TempDir::new().unwrap();
+        let project_dir = temp.path().join("project");
+        fs::create_dir_all(&project_dir).unwrap();
+        let manifest_path = project_dir.join("agpm.toml");
+        let outside_file = temp.path().join("outside.md");
+        fs::write(&outside_file, "# Not ours").unwrap();
+
+        let manifest = r#"
+[sources]
+test-source = "https://github.com/test/repo.git"
+
+[agents]
+escaping = { source = "test-source", path = "agents/a.md", version = "v1.0.0" }
+
+[snippets]
+escaping = { source = "test-source", path = "snippets/s.md", version = "v1.0.0" }
+"#;
+        fs::write(&manifest_path, manifest).unwrap();
+
+        // A hand-edited lockfile whose install paths point outside the project
+        let mut lockfile = LockFile::new();
+        for resource_type in [ResourceType::Agent, ResourceType::Snippet] {
+            let entry = LockedResourceBuilder::new(
+                "escaping".to_string(),
+                "agents/a.md".to_string(),
+                "sha256:test".to_string(),
+                "../outside.md".to_string(),
+                resource_type,
+            )
+            .source(Some("test-source".to_string()))
+            .build();
+            match resource_type {
+                ResourceType::Agent => lockfile.agents.push(entry),
+                _ => lockfile.snippets.push(entry),
+            }
+        }
+        lockfile.save(&project_dir.join("agpm.lock")).unwrap();
+
+        remove_dependency_with_manifest_path("escaping", "snippet", Some(manifest_path.clone()))
+            .await?;
+        assert!(outside_file.exists(), "dependency removal must stay inside the project");
+
+        remove_source_with_manifest_path("test-source", true, Some(manifest_path)).await?;
+        assert!(outside_file.exists(), "source removal must stay inside the project");
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_remove_script_and_hook_from_lockfile() -> Result<()> {
         use crate::lockfile::{LockFile, LockedResource};
@@ -1172,6 +1247,8 @@ test-hook = "../test/hook.json"
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
+            inline: None,
         });
         lockfile.hooks.push(LockedResource {
             name: "test-hook".to_string(),
@@ -1191,6 +1268,8 @@ test-hook = "../test/hook.json"
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
+            inline: None,
         });
         lockfile.save(&lockfile_path).unwrap();
         // Remove script
@@ -1242,6 +1321,8 @@ test-snippet = "../local/snippet.md"
             name: "test-source".to_string(),
             url: "https://github.com/test/repo.git".to_string(),
             fetched_at: chrono::Utc::now().to_rfc3339(),
+            resolved_commit: None,
+            verified_tags: Vec::new(),
         });
         lockfile.agents.push(LockedResource {
             name: "test-agent".to_string(),
@@ -1261,6 +1342,8 @@ test-snippet = "../local/snippet.md"
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
+            inline: None,
         });
         lockfile.snippets.push(LockedResource {
             name: "test-snippet".to_string(),
@@ -1280,6 +1363,8 @@ test-snippet = "../local/snippet.md"
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
+            inline: None,
         });
         lockfile.save(&lockfile_path).unwrap();
         // Remove a snippet
@@ -1456,6 +1541,8 @@ test-script = "../test/script.sh"
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
+            inline: None,
         });
         lockfile.save(&lockfile_path).unwrap();
 
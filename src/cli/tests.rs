@@ -171,6 +171,26 @@ mod cli_tests {
         let cli = Cli::try_parse_from(["agpm", "--config", "/custom/path", "list"])?;
         let config = cli.build_config();
         assert_eq!(config.config_path, Some("/custom/path".to_string()));
+
+        // Test JSON log format disables progress
+        let cli = Cli::try_parse_from(["agpm", "--log-format", "json", "list"])?;
+        let config = cli.build_config();
+        assert_eq!(config.log_format, crate::cli::LogFormat::Json);
+        assert!(config.no_progress);
+        assert!(Cli::try_parse_from(["agpm", "--log-format", "xml", "list"]).is_err());
+
+        // Test no-post-install flag
+        let cli = Cli::try_parse_from(["agpm", "install", "--no-post-install"])?;
+        assert!(cli.build_config().no_post_install);
+
+        // Test network timeout
+        let cli = Cli::try_parse_from(["agpm", "--timeout", "60s", "install"])?;
+        assert_eq!(cli.build_config().timeout, Some(std::time::Duration::from_secs(60)));
+        assert!(Cli::try_parse_from(["agpm", "--timeout", "soon", "install"]).is_err());
+
+        // Test checksum cache opt-out
+        let cli = Cli::try_parse_from(["agpm", "--no-mtime-cache", "install"])?;
+        assert!(cli.build_config().no_mtime_cache);
         Ok(())
     }
 
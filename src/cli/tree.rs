@@ -630,16 +630,16 @@ impl TreeCommand {
 
 /// A node in the dependency tree
 #[derive(Debug, Clone)]
-struct TreeNode {
-    name: String,
-    resource_type: ResourceType,
-    version: Option<String>,
-    source: Option<String>,
-    tool: Option<String>,
-    dependencies: Vec<String>, // IDs of dependency nodes
-    has_patches: bool,         // True if resource has applied patches
-    installed_at: String,      // Installation path for detailed output
-    applied_patches: std::collections::BTreeMap<String, toml::Value>, // Patch field -> value mapping
+pub(crate) struct TreeNode {
+    pub(crate) name: String,
+    pub(crate) resource_type: ResourceType,
+    pub(crate) version: Option<String>,
+    pub(crate) source: Option<String>,
+    pub(crate) tool: Option<String>,
+    pub(crate) dependencies: Vec<String>, // IDs of dependency nodes
+    pub(crate) has_patches: bool,         // True if resource has applied patches
+    pub(crate) installed_at: String,      // Installation path for detailed output
+    pub(crate) applied_patches: std::collections::BTreeMap<String, toml::Value>, // Patch field -> value mapping
 }
 
 /// The complete dependency tree structure
@@ -681,13 +681,13 @@ impl DependencyTree {
 }
 
 /// Builds the dependency tree from the lockfile
-struct TreeBuilder<'a> {
+pub(crate) struct TreeBuilder<'a> {
     lockfile: &'a LockFile,
     project_name: String,
 }
 
 impl<'a> TreeBuilder<'a> {
-    const fn new(lockfile: &'a LockFile, project_name: String) -> Self {
+    pub(crate) const fn new(lockfile: &'a LockFile, project_name: String) -> Self {
         Self {
             lockfile,
             project_name,
@@ -701,11 +701,11 @@ impl<'a> TreeBuilder<'a> {
         // If a specific package is requested, find it
         if let Some(ref package_name) = cmd.package {
             let found = self.find_package(package_name)?;
-            let node = self.build_node(found, cmd)?;
+            let node = self.build_node(found)?;
             let node_id = self.node_id(&node);
 
             nodes.insert(node_id, node.clone());
-            self.build_dependencies(&node, &mut nodes, cmd)?;
+            self.build_dependencies(&node, &mut nodes)?;
             roots.push(node);
         } else {
             // First, build all nodes and their dependencies
@@ -715,11 +715,11 @@ impl<'a> TreeBuilder<'a> {
                 }
 
                 for resource in self.lockfile.get_resources(resource_type) {
-                    let node = self.build_node(resource, cmd)?;
+                    let node = self.build_node(resource)?;
                     let node_id = self.node_id(&node);
 
                     nodes.insert(node_id.clone(), node.clone());
-                    self.build_dependencies(&node, &mut nodes, cmd)?;
+                    self.build_dependencies(&node, &mut nodes)?;
                 }
             }
 
@@ -784,6 +784,19 @@ impl<'a> TreeBuilder<'a> {
         })
     }
 
+    /// Every resource in the lockfile keyed by node ID, with edges to the
+    /// node IDs of its dependencies.
+    pub(crate) fn build_graph(&self) -> Result<HashMap<String, TreeNode>> {
+        let mut nodes = HashMap::new();
+        for resource_type in ResourceType::all() {
+            for resource in self.lockfile.get_resources(resource_type) {
+                let node = self.build_node(resource)?;
+                nodes.insert(self.node_id(&node), node);
+            }
+        }
+        Ok(nodes)
+    }
+
     fn find_package(&self, name: &str) -> Result<&LockedResource> {
         for resource_type in ResourceType::all() {
             for resource in self.lockfile.get_resources(resource_type) {
@@ -796,7 +809,7 @@ impl<'a> TreeBuilder<'a> {
         Err(anyhow::anyhow!("Package '{name}' not found in lockfile"))
     }
 
-    fn build_node(&self, resource: &LockedResource, _cmd: &TreeCommand) -> Result<TreeNode> {
+    fn build_node(&self, resource: &LockedResource) -> Result<TreeNode> {
         // Extract display name from unique lockfile name
         // Unique name format: "source:name@version" or "name@version"
         // We want just "name" for display
@@ -871,7 +884,6 @@ impl<'a> TreeBuilder<'a> {
         &self,
         node: &TreeNode,
         nodes: &mut HashMap<String, TreeNode>,
-        cmd: &TreeCommand,
     ) -> Result<()> {
         // Dependencies are already in tree node ID format (type/name)
         for dep_node_id in &node.dependencies {
@@ -883,11 +895,11 @@ impl<'a> TreeBuilder<'a> {
             if let Some(dep_resource) =
                 self.find_resource_by_id(dep_node_id, node.source.as_deref())
             {
-                let dep_node = self.build_node(dep_resource, cmd)?;
+                let dep_node = self.build_node(dep_resource)?;
                 let actual_dep_node_id = self.node_id(&dep_node);
 
                 nodes.insert(actual_dep_node_id.clone(), dep_node.clone());
-                self.build_dependencies(&dep_node, nodes, cmd)?;
+                self.build_dependencies(&dep_node, nodes)?;
             }
         }
 
@@ -0,0 +1,211 @@
+//! Uninstall a single dependency while keeping it in the manifest.
+//!
+//! This module provides the `uninstall` command. Unlike `agpm remove`, which
+//! deletes a dependency from `agpm.toml`, it only undoes the installation:
+//! the dependency's installed files and its AGPM-managed hook or MCP server
+//! entries are removed, and its entries are dropped from `agpm.lock`. The
+//! manifest is left untouched, so the next `agpm install` reinstalls it from
+//! scratch. This is useful for forcing a clean reinstall of one resource.
+//!
+//! Uninstalling a resource that another installed resource depends on would
+//! break the dependent, so it is refused unless `--force` is given.
+//!
+//! # Examples
+//!
+//! ```bash
+//! # Uninstall an agent, then reinstall it
+//! agpm uninstall code-reviewer
+//! agpm install
+//!
+//! # Disambiguate a name used by several resource types
+//! agpm uninstall utils --type snippet
+//!
+//! # Uninstall even though other resources depend on it
+//! agpm uninstall utils --force
+//! ```
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use colored::Colorize;
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::core::ResourceType;
+use crate::installer::{cleanup_removed_artifacts, cleanup_removed_merged_entries};
+use crate::lockfile::{LockFile, LockedResource};
+use crate::manifest::{Manifest, find_manifest_with_optional};
+
+/// Command to uninstall one dependency without removing it from the manifest.
+#[derive(Args)]
+pub struct UninstallCommand {
+    /// Name of the dependency, as in agpm.toml or agpm.lock
+    name: String,
+
+    /// Resource type, when the name is used by several types
+    #[arg(long = "type", value_name = "TYPE")]
+    resource_type: Option<ResourceType>,
+
+    /// Uninstall even if other installed resources depend on it
+    #[arg(long)]
+    force: bool,
+}
+
+impl UninstallCommand {
+    /// Execute the uninstall command with an optional manifest path.
+    pub async fn execute_with_manifest_path(self, manifest_path: Option<PathBuf>) -> Result<()> {
+        let manifest_path = find_manifest_with_optional(manifest_path)
+            .context("No agpm.toml found. Please create one to define your dependencies.")?;
+        let manifest = Manifest::load(&manifest_path)?;
+        let project_dir =
+            manifest_path.parent().context("Manifest path has no parent directory")?;
+
+        uninstall(&manifest, project_dir, &self.name, self.resource_type, self.force).await
+    }
+}
+
+/// Remove the installed files, merged entries and lockfile entries of `name`.
+async fn uninstall(
+    manifest: &Manifest,
+    project_dir: &Path,
+    name: &str,
+    resource_type: Option<ResourceType>,
+    force: bool,
+) -> Result<()> {
+    let lockfile_path = project_dir.join("agpm.lock");
+    if !lockfile_path.exists() {
+        bail!("No agpm.lock found, nothing is installed");
+    }
+    let lockfile = LockFile::load(&lockfile_path)?;
+
+    let matches = |entry: &LockedResource| entry.lookup_name() == name || entry.name == name;
+    let found: Vec<(ResourceType, &LockedResource)> = ResourceType::all()
+        .iter()
+        .filter(|candidate| resource_type.is_none_or(|wanted| wanted == **candidate))
+        .flat_map(|candidate| {
+            lockfile
+                .get_resources(candidate)
+                .iter()
+                .filter(|entry| matches(entry))
+                .map(|entry| (*candidate, entry))
+        })
+        .collect();
+
+    let types: BTreeSet<String> =
+        found.iter().map(|(found_type, _)| found_type.to_string()).collect();
+    if found.is_empty() {
+        bail!("'{name}' is not installed (not found in agpm.lock)");
+    }
+    if types.len() > 1 {
+        bail!(
+            "'{name}' matches several resource types ({}). Use --type to choose one",
+            types.into_iter().collect::<Vec<_>>().join(", ")
+        );
+    }
+
+    if !force {
+        let mut dependents: Vec<String> = found
+            .iter()
+            .flat_map(|(found_type, entry)| lockfile.find_dependents(*found_type, entry))
+            .filter(|(_, dependent)| {
+                !found.iter().any(|(_, entry)| std::ptr::eq(*entry, *dependent))
+            })
+            .map(|(dependent_type, dependent)| {
+                format!("{dependent_type} '{}'", dependent.display_name())
+            })
+            .collect();
+        dependents.sort();
+        dependents.dedup();
+        if !dependents.is_empty() {
+            bail!(
+                "'{name}' is required by {}. Use --force to uninstall it anyway",
+                dependents.join(", ")
+            );
+        }
+    }
+
+    let removed_ids: HashSet<_> = found.iter().map(|(_, entry)| entry.id()).collect();
+    let mut updated = lockfile.clone();
+    for resource_type in ResourceType::all() {
+        updated.get_resources_mut(resource_type).retain(|entry| !removed_ids.contains(&entry.id()));
+    }
+    let used_sources: HashSet<String> =
+        updated.all_resources().into_iter().filter_map(|entry| entry.source.clone()).collect();
+    updated.sources.retain(|source| used_sources.contains(&source.name));
+
+    let removed = cleanup_removed_artifacts(&lockfile, &updated, project_dir).await?;
+    let (hooks, servers) =
+        cleanup_removed_merged_entries(&lockfile, &updated, manifest, project_dir)?;
+    updated.save(&lockfile_path)?;
+
+    println!(
+        "{} Uninstalled {} '{name}', removed {} installed file(s)",
+        "✓".green(),
+        types.into_iter().next().unwrap_or_default(),
+        removed.len()
+    );
+    if hooks + servers > 0 {
+        println!(
+            "{} Removed {hooks} hook(s) and {servers} MCP server(s) from configuration",
+            "✓".green()
+        );
+    }
+    if manifest.get_resources(&found[0].0).contains_key(name) {
+        println!("Run 'agpm install' to reinstall it");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lockfile::LockedResourceBuilder;
+    use tempfile::TempDir;
+
+    fn locked(name: &str, resource_type: ResourceType, dependencies: &[&str]) -> LockedResource {
+        let mut entry = LockedResourceBuilder::new(
+            name.to_string(),
+            format!("{name}.md"),
+            String::new(),
+            format!(".claude/{name}.md"),
+            resource_type,
+        )
+        .build();
+        entry.dependencies = dependencies.iter().map(|dep| (*dep).to_string()).collect();
+        entry
+    }
+
+    #[tokio::test]
+    async fn test_uninstall_refuses_dependency_of_installed_resource() {
+        let temp = TempDir::new().unwrap();
+        let project = temp.path();
+        std::fs::create_dir_all(project.join(".claude/agents")).unwrap();
+        std::fs::create_dir_all(project.join(".claude/snippets")).unwrap();
+        std::fs::write(project.join(".claude/agents/helper.md"), "agent").unwrap();
+        std::fs::write(project.join(".claude/snippets/utils.md"), "snippet").unwrap();
+
+        let mut lockfile = LockFile::new();
+        lockfile.agents.push(locked(
+            "agents/helper",
+            ResourceType::Agent,
+            &["snippet:snippets/utils"],
+        ));
+        lockfile.snippets.push(locked("snippets/utils", ResourceType::Snippet, &[]));
+        lockfile.save(&project.join("agpm.lock")).unwrap();
+
+        let manifest = Manifest::new();
+        let err = uninstall(&manifest, project, "snippets/utils", None, false).await.unwrap_err();
+        assert!(err.to_string().contains("required by agent"), "{err}");
+        assert!(project.join(".claude/snippets/utils.md").exists());
+
+        uninstall(&manifest, project, "snippets/utils", None, true).await.unwrap();
+        assert!(!project.join(".claude/snippets/utils.md").exists());
+        assert!(project.join(".claude/agents/helper.md").exists());
+        let saved = LockFile::load(&project.join("agpm.lock")).unwrap();
+        assert!(saved.snippets.is_empty());
+        assert_eq!(saved.agents.len(), 1);
+
+        uninstall(&manifest, project, "agents/helper", None, false).await.unwrap();
+        assert!(!project.join(".claude/agents/helper.md").exists());
+        assert!(uninstall(&manifest, project, "agents/helper", None, false).await.is_err());
+    }
+}
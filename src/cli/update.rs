@@ -27,6 +27,16 @@
 //! agpm update my-agent utils-snippet
 //! ```
 //!
+//! Update one dependency without moving anything else:
+//! ```bash
+//! agpm update --conservative my-agent
+//! ```
+//!
+//! Choose which available updates to apply:
+//! ```bash
+//! agpm update --interactive
+//! ```
+//!
 //! Preview updates without applying:
 //! ```bash
 //! agpm update --dry-run
@@ -42,6 +52,11 @@
 //! agpm update --force
 //! ```
 //!
+//! Summarize the version changes for a pull request:
+//! ```bash
+//! agpm update --changelog --changelog-file CHANGELOG-deps.md
+//! ```
+//!
 //! Update with custom parallelism:
 //! ```bash
 //! agpm update --max-parallel 4
@@ -76,13 +91,15 @@
 use anyhow::{Context, Result};
 use clap::Args;
 use colored::Colorize;
+use std::collections::HashMap;
+use std::io::IsTerminal;
 use std::path::PathBuf;
 
 use crate::cache::Cache;
 use crate::core::{OperationContext, ResourceIterator};
 use crate::lockfile::LockFile;
 use crate::manifest::{Manifest, ResourceDependency, find_manifest_with_optional};
-use crate::resolver::DependencyResolver;
+use crate::resolver::{DEFAULT_MAX_DEPTH, DependencyResolver};
 
 /// Command-line arguments for the update command.
 ///
@@ -160,10 +177,36 @@ pub struct UpdateCommand {
     #[arg(value_name = "DEPENDENCY")]
     pub dependencies: Vec<String>,
 
+    /// Keep every dependency that was not named at its locked version.
+    ///
+    /// Re-resolves only the named dependencies and the resources they depend
+    /// on, like `cargo update -p`. Transitive dependencies they share with
+    /// other resources stay pinned to their locked commits.
+    #[arg(long, requires = "dependencies")]
+    pub conservative: bool,
+
+    /// Choose which available updates to apply.
+    ///
+    /// Resolves every manifest dependency to its newest allowed version, lists
+    /// the ones that would change and lets you toggle which to apply. The
+    /// selected dependencies are then updated as with `--conservative`; all
+    /// others stay at their locked versions. Requires a terminal.
+    #[arg(long, conflicts_with_all = ["dependencies", "dry_run", "check", "quiet"])]
+    pub interactive: bool,
+
+    /// Maximum depth of transitive dependency chains.
+    ///
+    /// Resolution fails, naming the chain, when a dependency is declared more
+    /// than this many levels below a manifest dependency.
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_MAX_DEPTH)]
+    pub max_depth: usize,
+
     /// Preview updates without applying changes.
     ///
-    /// Shows a detailed list of what would be updated, including version
-    /// changes and affected files, but doesn't modify anything.
+    /// Resolves dependencies and prints a changelog grouped by source
+    /// (`name: old_version@short_sha → new_version@short_sha`) without
+    /// modifying `agpm.lock` or any installed files. Local and unchanged
+    /// dependencies are only listed with `--verbose`.
     ///
     /// Exit codes:
     /// - 0: No updates available
@@ -213,9 +256,62 @@ pub struct UpdateCommand {
     #[arg(long, value_name = "NUMBER")]
     pub max_parallel: Option<usize>,
 
+    /// Maximum number of resources written to disk at once.
+    ///
+    /// Bounds file writes separately from `--max-parallel`, for slow shared
+    /// or network filesystems.
+    ///
+    /// Default: `io_parallel` from the global config, or CPU cores
+    #[arg(long, value_name = "NUMBER")]
+    pub io_parallel: Option<usize>,
+
+    /// Print a changelog of the applied version changes.
+    ///
+    /// Lists each updated remote dependency as `name: old → new`, grouped by
+    /// source, followed by the tags released in between when the source has
+    /// version tags. Suitable for pasting into a pull request.
+    #[arg(long, conflicts_with_all = ["dry_run", "check"])]
+    pub changelog: bool,
+
+    /// Write the changelog to a file, e.g. for CI to attach to release notes.
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["dry_run", "check"])]
+    pub changelog_file: Option<PathBuf>,
+
+    /// Record `[replace]` URLs in the lockfile.
+    ///
+    /// Sources replaced in the manifest's `[replace]` section are always
+    /// fetched from the replacement; by default `agpm.lock` still records
+    /// their declared URLs.
+    #[arg(long)]
+    pub commit_replace: bool,
+
     /// Disable progress bars (for programmatic use, not exposed as CLI arg)
     #[arg(skip)]
     pub no_progress: bool,
+
+    /// Skip dependencies' post-install commands (from the global `--no-post-install` flag)
+    #[arg(skip)]
+    pub no_post_install: bool,
+
+    /// Require the tags dependencies resolve to to be GPG- or SSH-signed
+    ///
+    /// Fails the update if a tag a version resolves to is unsigned or its
+    /// signature does not verify. Branch and commit dependencies are not checked.
+    #[arg(long)]
+    pub verify_signatures: bool,
+
+    /// Update only the dependencies of a profile defined in `[profiles]`
+    ///
+    /// Reads and writes the profile's lockfile, `agpm.<PROFILE>.lock`,
+    /// unless `--lockfile` is given.
+    #[arg(long, value_name = "PROFILE")]
+    pub profile: Option<String>,
+
+    /// Read and write this lockfile instead of agpm.lock
+    ///
+    /// Relative paths are resolved against the project directory.
+    #[arg(long, value_name = "FILE")]
+    pub lockfile: Option<PathBuf>,
 }
 
 impl UpdateCommand {
@@ -274,6 +370,10 @@ impl UpdateCommand {
     ///     backup: true,
     ///     verbose: true,
     ///     quiet: false,
+    ///     no_post_install: false,
+    ///     verify_signatures: false,
+    ///     profile: None,
+    ///     lockfile: None,
     /// };
     /// // cmd.execute_with_manifest_path(None).await?;
     /// # Ok::<(), anyhow::Error>(())
@@ -301,11 +401,18 @@ impl UpdateCommand {
             return Err(anyhow::anyhow!("Manifest file {} not found", manifest_path.display()));
         }
 
+        if self.interactive && !std::io::stdin().is_terminal() {
+            anyhow::bail!(
+                "'agpm update --interactive' needs a terminal.\n\n\
+                 Name the dependencies to update instead, e.g. 'agpm update --conservative my-agent'."
+            );
+        }
+
         let project_dir = manifest_path.parent().unwrap();
         let multi_phase = Arc::new(MultiPhaseProgress::new(!self.quiet && !self.no_progress));
 
         // Load manifest with private config merged
-        let (manifest, _conflicts) =
+        let (mut manifest, _conflicts) =
             Manifest::load_with_private(&manifest_path).with_context(|| {
                 format!(
                     "Failed to parse manifest file: {}\n\n\
@@ -314,10 +421,30 @@ impl UpdateCommand {
                 )
             })?;
 
+        if let Some(profile) = &self.profile {
+            manifest.select_profile(profile)?;
+        }
+
         // Load existing lockfile or perform fresh install if missing
-        let lockfile_path = project_dir.join("agpm.lock");
+        let lockfile_path = crate::cli::common::selected_lockfile_path(
+            project_dir,
+            self.lockfile.as_deref(),
+            self.profile.as_deref(),
+        );
         let existing_lockfile = if lockfile_path.exists() {
-            LockFile::load(&lockfile_path)?
+            if self.dry_run || self.check {
+                // Previews must not even persist a lockfile format migration
+                LockFile::load(&lockfile_path)?
+            } else {
+                LockFile::load_and_persist_migration(&lockfile_path)?
+            }
+        } else if self.dry_run || self.check {
+            // Nothing to compare against, and a preview must not install anything
+            if !self.quiet && !self.no_progress {
+                println!("⚠️  No lockfile found");
+                println!("ℹ️  'agpm update' would perform a fresh install (no changes made)");
+            }
+            return Ok(());
         } else {
             if !self.quiet && !self.no_progress {
                 println!("⚠️  No lockfile found");
@@ -325,17 +452,19 @@ impl UpdateCommand {
             }
 
             // Use the install command to do the actual installation
-            let install_cmd = if self.quiet {
+            let mut install_cmd = if self.quiet {
                 crate::cli::install::InstallCommand::new_quiet()
             } else {
                 crate::cli::install::InstallCommand::new()
             };
+            install_cmd.profile.clone_from(&self.profile);
+            install_cmd.lockfile.clone_from(&self.lockfile);
 
             return install_cmd.execute_from_path(Some(&manifest_path)).await;
         };
 
-        // Create backup if requested
-        if self.backup {
+        // Create backup if requested (a preview never touches the lockfile)
+        if self.backup && !self.dry_run && !self.check {
             let backup_path = crate::utils::generate_backup_path(&lockfile_path, "agpm")?;
 
             // Ensure backup directory exists
@@ -379,6 +508,10 @@ impl UpdateCommand {
         // Create operation context for warning deduplication
         let operation_context = Arc::new(OperationContext::new());
         resolver.set_operation_context(operation_context);
+        resolver.set_max_depth(self.max_depth);
+        if self.verify_signatures {
+            resolver.require_tag_signatures();
+        }
 
         // Get all dependencies for pre-syncing (only if we have remote deps)
         if has_remote_deps {
@@ -404,8 +537,47 @@ impl UpdateCommand {
         } else {
             None
         };
-        let mut new_lockfile =
-            resolver.update(&existing_lockfile, deps_to_update.clone(), progress).await?;
+        let mut new_lockfile = if self.interactive {
+            // Resolve everything to find the available updates, then apply only the chosen ones
+            let candidate = resolver.update(&existing_lockfile, None, progress.clone()).await?;
+            if !self.no_progress {
+                multi_phase.clear();
+            }
+
+            let direct = manifest.all_dependencies();
+            let jumps: Vec<VersionJump> = version_jumps(&existing_lockfile, &candidate, &[])
+                .into_iter()
+                .filter(|jump| direct.iter().any(|(name, _)| *name == jump.name))
+                .collect();
+            if jumps.is_empty() {
+                crate::cli::common::display_no_changes(
+                    crate::cli::common::OperationMode::Update,
+                    self.no_progress,
+                );
+                return Ok(());
+            }
+
+            let Some(selected) = select_updates(&jumps).await? else {
+                println!("{}", "Update cancelled - no changes made".yellow());
+                return Ok(());
+            };
+            if selected.is_empty() {
+                println!("{}", "No updates selected - no changes made".yellow());
+                return Ok(());
+            }
+            resolver.update_conservative(&existing_lockfile, &selected, progress).await?
+        } else if self.conservative {
+            resolver.update_conservative(&existing_lockfile, &self.dependencies, progress).await?
+        } else {
+            resolver.update(&existing_lockfile, deps_to_update.clone(), progress).await?
+        };
+        if self.commit_replace {
+            new_lockfile.commit_source_replacements(&manifest.replacement_urls()?);
+        }
+
+        if self.dry_run {
+            return self.report_dry_run(&existing_lockfile, &new_lockfile, &multi_phase);
+        }
 
         // Compare lockfiles to see what changed
         let mut updates = Vec::new();
@@ -457,22 +629,14 @@ impl UpdateCommand {
                 }
             }
 
-            if self.dry_run || self.check {
-                if self.check {
-                    // Check mode: minimal output
-                    if !self.quiet && !self.no_progress {
-                        println!(); // Add spacing
-                        println!("{}", "Check mode - no changes made".yellow());
-                    }
-                } else {
-                    // Dry-run mode: rich output (but we already showed update info above)
-                    if !self.quiet && !self.no_progress {
-                        println!(); // Add spacing
-                        println!("{} {}", "Would update".green(), "(dry run)".yellow());
-                    }
+            if self.check {
+                // Check mode: minimal output
+                if !self.quiet && !self.no_progress {
+                    println!(); // Add spacing
+                    println!("{}", "Check mode - no changes made".yellow());
                 }
                 // Return with error to indicate updates are available (exit code 1 for CI)
-                return Err(anyhow::anyhow!("Dry-run detected updates available (exit 1)"));
+                return Err(anyhow::anyhow!("Check detected updates available (exit 1)"));
             }
 
             // Install all updated resources first, before saving lockfile
@@ -491,6 +655,7 @@ impl UpdateCommand {
                 cache.clone(), // Clone cache since we need it later for finalize_installation
                 false,         // don't force refresh for updates
                 self.max_parallel, // use provided or default concurrency
+                self.io_parallel,
                 if self.quiet || self.no_progress {
                     None
                 } else {
@@ -508,6 +673,17 @@ impl UpdateCommand {
                 results.applied_patches,
             );
 
+            // Run post-install commands before the lockfile is saved
+            if !self.no_post_install {
+                crate::installer::run_post_install_commands(
+                    &new_lockfile,
+                    Some(&existing_lockfile),
+                    &manifest,
+                    project_dir,
+                )
+                .await?;
+            }
+
             // Complete installation phase
             if results.installed_count > 0 && !self.quiet && !self.no_progress {
                 multi_phase.complete_phase(Some(&format!(
@@ -529,7 +705,7 @@ impl UpdateCommand {
                 &cache,
                 Some(&existing_lockfile), // Pass old lockfile for artifact cleanup
                 self.quiet,
-                false, // no_lock - always save lockfile in update command
+                Some(&lockfile_path), // always save lockfile in update command
             )
             .await?;
 
@@ -557,8 +733,468 @@ impl UpdateCommand {
             }
         }
 
+        if self.changelog || self.changelog_file.is_some() {
+            self.write_changelog(&existing_lockfile, &new_lockfile, &resolver).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Print and/or write the `--changelog` summary of the applied version changes.
+    async fn write_changelog(
+        &self,
+        old: &LockFile,
+        new: &LockFile,
+        resolver: &DependencyResolver,
+    ) -> Result<()> {
+        let mut jumps = version_jumps(old, new, &self.dependencies);
+
+        let mut tags_by_source: HashMap<String, Option<Vec<(String, String)>>> = HashMap::new();
+        for jump in &mut jumps {
+            if !tags_by_source.contains_key(&jump.source) {
+                // Tags only enrich the changelog; the update itself has already been applied
+                let tags = resolver.source_tags(&jump.source).await.ok().flatten();
+                tags_by_source.insert(jump.source.clone(), tags);
+            }
+            if let Some(Some(tags)) = tags_by_source.get(&jump.source) {
+                jump.between = tags_between(tags, &jump.old, &jump.new);
+            }
+        }
+
+        let lines = format_version_jumps(&jumps);
+        if self.changelog && !self.quiet {
+            println!();
+            println!("{}", "Changelog:".bold());
+            for line in &lines {
+                println!("{line}");
+            }
+        }
+
+        if let Some(path) = &self.changelog_file {
+            let mut content = lines.join("\n");
+            content.push('\n');
+            tokio::fs::write(path, content)
+                .await
+                .with_context(|| format!("Failed to write changelog to {}", path.display()))?;
+            if !self.quiet && !self.no_progress {
+                println!("ℹ️  Wrote changelog to {}", path.display());
+            }
+        }
         Ok(())
     }
+
+    /// Print the `--dry-run` changelog and return without touching any files.
+    ///
+    /// Returns an error (exit code 1) when at least one resource would change,
+    /// so the preview can gate CI jobs.
+    fn report_dry_run(
+        &self,
+        existing_lockfile: &LockFile,
+        new_lockfile: &LockFile,
+        multi_phase: &crate::utils::progress::MultiPhaseProgress,
+    ) -> Result<()> {
+        let changes =
+            plan_changes(existing_lockfile, new_lockfile, &self.dependencies, self.verbose);
+        let pending = changes.iter().filter(|c| c.status != ChangeStatus::Unchanged).count();
+
+        if !self.quiet && !self.no_progress {
+            multi_phase.clear();
+        }
+
+        if changes.is_empty() {
+            crate::cli::common::display_no_changes(
+                crate::cli::common::OperationMode::Update,
+                self.quiet || self.no_progress,
+            );
+            return Ok(());
+        }
+
+        if !self.quiet {
+            if pending > 0 {
+                println!("✓ Found {pending} update(s)");
+            } else {
+                println!("✓ All dependencies are up to date");
+            }
+            println!(); // Add spacing
+            for line in format_changelog(&changes) {
+                println!("{line}");
+            }
+            println!(); // Add spacing
+            println!("{} {}", "Would update".green(), "(dry run - no changes made)".yellow());
+        }
+
+        if pending > 0 {
+            // Return with error to indicate updates are available (exit code 1 for CI)
+            return Err(anyhow::anyhow!("Dry-run detected updates available (exit 1)"));
+        }
+
+        Ok(())
+    }
+}
+
+/// How a resource would change if the update were applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChangeStatus {
+    /// Resolved to a different version, commit, or patch set.
+    Updated,
+    /// Present only in the newly resolved lockfile.
+    Added,
+    /// Present only in the existing lockfile.
+    Removed,
+    /// Resolved to the same commit (only reported with `--verbose`).
+    Unchanged,
+}
+
+/// One line of the `--dry-run` changelog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PlannedChange {
+    name: String,
+    source: Option<String>,
+    old: Option<String>,
+    new: Option<String>,
+    status: ChangeStatus,
+}
+
+/// Format a locked resource as `version@short_sha`, or `local` for local dependencies.
+fn format_locked_ref(entry: &crate::lockfile::LockedResource) -> String {
+    if entry.source.is_none() {
+        return "local".to_string();
+    }
+
+    let version = entry.version.as_deref().unwrap_or("latest");
+    match entry.resolved_commit.as_deref().filter(|sha| !sha.is_empty()) {
+        Some(sha) => format!("{version}@{}", &sha[..8.min(sha.len())]),
+        None => version.to_string(),
+    }
+}
+
+/// Diff two lockfiles into the changes `agpm update` would apply.
+///
+/// Resources are matched by display name and source, like the regular update
+/// path. When `names` is non-empty only those resources are reported. Local
+/// dependencies and unchanged resources are omitted unless `verbose` is set.
+fn plan_changes(
+    old: &LockFile,
+    new: &LockFile,
+    names: &[String],
+    verbose: bool,
+) -> Vec<PlannedChange> {
+    let selected = |name: &str| names.is_empty() || names.iter().any(|n| n == name);
+    let mut changes = Vec::new();
+
+    ResourceIterator::for_each_resource(new, |_, new_entry| {
+        let name = new_entry.display_name();
+        if !selected(name) || (new_entry.source.is_none() && !verbose) {
+            return;
+        }
+
+        let old_entry = ResourceIterator::find_resource_by_name_and_source(
+            old,
+            name,
+            new_entry.source.as_deref(),
+        )
+        .map(|(_, entry)| entry);
+
+        let status = match old_entry {
+            None => ChangeStatus::Added,
+            Some(old_entry)
+                if old_entry.resolved_commit != new_entry.resolved_commit
+                    || old_entry.version != new_entry.version
+                    || old_entry.applied_patches != new_entry.applied_patches =>
+            {
+                ChangeStatus::Updated
+            }
+            Some(_) => ChangeStatus::Unchanged,
+        };
+        if status == ChangeStatus::Unchanged && !verbose {
+            return;
+        }
+
+        changes.push(PlannedChange {
+            name: name.to_string(),
+            source: new_entry.source.clone(),
+            old: old_entry.map(format_locked_ref),
+            new: Some(format_locked_ref(new_entry)),
+            status,
+        });
+    });
+
+    ResourceIterator::for_each_resource(old, |_, old_entry| {
+        let name = old_entry.display_name();
+        if !selected(name) || (old_entry.source.is_none() && !verbose) {
+            return;
+        }
+
+        let still_locked = ResourceIterator::find_resource_by_name_and_source(
+            new,
+            name,
+            old_entry.source.as_deref(),
+        )
+        .is_some();
+        if !still_locked {
+            changes.push(PlannedChange {
+                name: name.to_string(),
+                source: old_entry.source.clone(),
+                old: Some(format_locked_ref(old_entry)),
+                new: None,
+                status: ChangeStatus::Removed,
+            });
+        }
+    });
+
+    changes
+}
+
+/// Render planned changes as changelog lines grouped by source.
+///
+/// Local dependencies are grouped under `local`.
+fn format_changelog(changes: &[PlannedChange]) -> Vec<String> {
+    let mut by_source: std::collections::BTreeMap<&str, Vec<&PlannedChange>> =
+        std::collections::BTreeMap::new();
+    for change in changes {
+        by_source.entry(change.source.as_deref().unwrap_or("local")).or_default().push(change);
+    }
+
+    let mut lines = Vec::new();
+    for (source, mut entries) in by_source {
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        lines.push(format!("{source}:"));
+        for change in entries {
+            let old = change.old.as_deref().unwrap_or("(none)");
+            let new = change.new.as_deref().unwrap_or("(removed)");
+            let line = match change.status {
+                ChangeStatus::Unchanged => format!("  {}: {old} (unchanged)", change.name),
+                _ => format!("  {}: {old} → {new}", change.name),
+            };
+            lines.push(line);
+        }
+    }
+    lines
+}
+
+/// A version change of one remote dependency, for `--changelog`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct VersionJump {
+    name: String,
+    source: String,
+    old: String,
+    new: String,
+    /// Tags released between `old` and `new`, oldest first.
+    between: Vec<String>,
+}
+
+/// Collect the version changes of remote dependencies between two lockfiles.
+///
+/// Versions are shown as-is; when only the commit moved (e.g. a branch), both
+/// sides are shown as `version@short_sha`. When `names` is non-empty only those
+/// resources are reported.
+fn version_jumps(old: &LockFile, new: &LockFile, names: &[String]) -> Vec<VersionJump> {
+    let mut jumps = Vec::new();
+
+    ResourceIterator::for_each_resource(new, |_, new_entry| {
+        let Some(source) = new_entry.source.as_deref() else {
+            return;
+        };
+        let name = new_entry.display_name();
+        if !names.is_empty() && !names.iter().any(|n| n == name) {
+            return;
+        }
+        let Some((_, old_entry)) =
+            ResourceIterator::find_resource_by_name_and_source(old, name, Some(source))
+        else {
+            return;
+        };
+        if old_entry.resolved_commit == new_entry.resolved_commit
+            && old_entry.version == new_entry.version
+        {
+            return;
+        }
+
+        let (old_ref, new_ref) = if old_entry.version == new_entry.version {
+            (format_locked_ref(old_entry), format_locked_ref(new_entry))
+        } else {
+            let version = |entry: &crate::lockfile::LockedResource| {
+                entry.version.clone().unwrap_or_else(|| "latest".to_string())
+            };
+            (version(old_entry), version(new_entry))
+        };
+        jumps.push(VersionJump {
+            name: name.to_string(),
+            source: source.to_string(),
+            old: old_ref,
+            new: new_ref,
+            between: Vec::new(),
+        });
+    });
+
+    jumps
+}
+
+/// What a line typed at the `--interactive` prompt asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SelectionInput {
+    /// Apply the current selection.
+    Apply,
+    /// Stop without changing anything.
+    Cancel,
+    /// The selection changed; show it again.
+    Changed,
+}
+
+/// Apply one line typed at the `--interactive` prompt to `selected`.
+///
+/// Numbers (1-based, separated by spaces or commas) toggle entries, `a`
+/// selects all, `n` selects none, `q` cancels and an empty line applies.
+fn parse_selection_input(selected: &mut [bool], input: &str) -> Result<SelectionInput, String> {
+    match input.trim() {
+        "" => return Ok(SelectionInput::Apply),
+        "q" => return Ok(SelectionInput::Cancel),
+        "a" => {
+            selected.fill(true);
+            return Ok(SelectionInput::Changed);
+        }
+        "n" => {
+            selected.fill(false);
+            return Ok(SelectionInput::Changed);
+        }
+        _ => {}
+    }
+
+    let mut toggles = Vec::new();
+    for token in input.split(|c: char| c == ',' || c.is_whitespace()).filter(|t| !t.is_empty()) {
+        match token.parse::<usize>() {
+            Ok(n) if (1..=selected.len()).contains(&n) => toggles.push(n - 1),
+            _ => {
+                return Err(format!("'{token}' is not a number between 1 and {}", selected.len()));
+            }
+        }
+    }
+    for index in toggles {
+        selected[index] = !selected[index];
+    }
+    Ok(SelectionInput::Changed)
+}
+
+/// Let the user pick which of `jumps` to apply, starting with all of them.
+///
+/// Returns the names of the selected dependencies, or `None` if cancelled.
+async fn select_updates(jumps: &[VersionJump]) -> Result<Option<Vec<String>>> {
+    use std::io::Write;
+    use tokio::io::{AsyncBufReadExt, BufReader};
+
+    let mut selected = vec![true; jumps.len()];
+    let mut reader = BufReader::new(tokio::io::stdin());
+    println!("{}", "Available updates:".bold());
+    loop {
+        for (index, (jump, on)) in jumps.iter().zip(&selected).enumerate() {
+            let mark = if *on {
+                "[x]".green()
+            } else {
+                "[ ]".dimmed()
+            };
+            println!(
+                "  {mark} {:>2}. {} ({}): {} → {}",
+                index + 1,
+                jump.name.cyan(),
+                jump.source,
+                jump.old.yellow(),
+                jump.new.green()
+            );
+        }
+        print!("Toggle numbers, 'a' for all, 'n' for none, Enter to apply, 'q' to cancel: ");
+        std::io::stdout().flush()?;
+
+        let mut line = String::new();
+        if reader.read_line(&mut line).await? == 0 {
+            // End of input
+            return Ok(None);
+        }
+        match parse_selection_input(&mut selected, &line) {
+            Ok(SelectionInput::Apply) => break,
+            Ok(SelectionInput::Cancel) => return Ok(None),
+            Ok(SelectionInput::Changed) => {}
+            Err(e) => println!("{} {e}", "⚠".yellow()),
+        }
+        println!();
+    }
+
+    let mut names: Vec<String> = jumps
+        .iter()
+        .zip(selected)
+        .filter(|(_, on)| *on)
+        .map(|(jump, _)| jump.name.clone())
+        .collect();
+    names.sort();
+    names.dedup();
+    Ok(Some(names))
+}
+
+/// List the tags released strictly between two versions, oldest first.
+///
+/// `tags` pairs each tag with its version part (see
+/// [`DependencyResolver::source_tags`]). Only tags with the same version prefix
+/// as `old` and `new` are considered. Returns nothing if either side is not a
+/// semantic version, e.g. a branch.
+fn tags_between(tags: &[(String, String)], old: &str, new: &str) -> Vec<String> {
+    let parse = |part: &str| {
+        let (prefix, version) = crate::version::split_prefix_and_version(part);
+        semver::Version::parse(version.trim_start_matches('v')).ok().map(|v| (prefix, v))
+    };
+    let version_of = |reference: &str| {
+        let part =
+            tags.iter().find(|(tag, _)| tag == reference).map_or(reference, |(_, p)| p.as_str());
+        parse(part)
+    };
+
+    let (Some((prefix, old)), Some((new_prefix, new))) = (version_of(old), version_of(new)) else {
+        return Vec::new();
+    };
+    if prefix != new_prefix {
+        return Vec::new();
+    }
+    let (low, high) = if old <= new {
+        (old, new)
+    } else {
+        (new, old)
+    };
+
+    let mut between: Vec<(semver::Version, &str)> = tags
+        .iter()
+        .filter_map(|(tag, part)| {
+            let (tag_prefix, version) = parse(part)?;
+            (tag_prefix == prefix && version > low && version < high)
+                .then_some((version, tag.as_str()))
+        })
+        .collect();
+    between.sort();
+    between.into_iter().map(|(_, tag)| tag.to_string()).collect()
+}
+
+/// Render version changes as changelog lines grouped by source.
+fn format_version_jumps(jumps: &[VersionJump]) -> Vec<String> {
+    if jumps.is_empty() {
+        return vec!["No dependency versions changed.".to_string()];
+    }
+
+    let mut by_source: std::collections::BTreeMap<&str, Vec<&VersionJump>> =
+        std::collections::BTreeMap::new();
+    for jump in jumps {
+        by_source.entry(jump.source.as_str()).or_default().push(jump);
+    }
+
+    let mut lines = Vec::new();
+    for (source, mut entries) in by_source {
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        lines.push(format!("{source}:"));
+        for jump in entries {
+            let mut line = format!("  {}: {} → {}", jump.name, jump.old, jump.new);
+            if !jump.between.is_empty() {
+                line.push_str(&format!(" (between: {})", jump.between.join(", ")));
+            }
+            lines.push(line);
+        }
+    }
+    lines
 }
 
 #[cfg(test)]
@@ -575,6 +1211,9 @@ mod tests {
     fn create_update_command() -> UpdateCommand {
         UpdateCommand {
             dependencies: vec![],
+            conservative: false,
+            interactive: false,
+            max_depth: DEFAULT_MAX_DEPTH,
             dry_run: false,
             check: false,
             backup: false,
@@ -582,6 +1221,14 @@ mod tests {
             quiet: true,       // Quiet by default for tests
             no_progress: true, // No progress bars in tests
             max_parallel: None,
+            io_parallel: None,
+            changelog: false,
+            changelog_file: None,
+            commit_replace: false,
+            no_post_install: false,
+            verify_signatures: false,
+            profile: None,
+            lockfile: None,
         }
     }
 
@@ -610,11 +1257,23 @@ mod tests {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             })),
         );
 
         Manifest {
-            sources,
+            min_agpm_version: None,
+            sources: sources.into(),
             tools: None,
             agents,
             snippets: HashMap::new(),
@@ -625,10 +1284,16 @@ mod tests {
             patches: crate::manifest::patches::ManifestPatches::default(),
             project_patches: crate::manifest::patches::ManifestPatches::default(),
             private_patches: crate::manifest::patches::ManifestPatches::default(),
+            version_overrides: crate::manifest::VersionOverrides::default(),
+            replacements: std::collections::BTreeMap::new(),
             manifest_dir: None,
+            git_sources: std::collections::BTreeSet::new(),
             default_tools: HashMap::new(),
+            profiles: std::collections::BTreeMap::new(),
             project: None,
             gitignore: true,
+            strip_metadata: false,
+            metadata_keys: Vec::new(),
         }
     }
 
@@ -641,6 +1306,8 @@ mod tests {
                 name: "test-source".to_string(),
                 url: "file:///tmp/test-repo".to_string(),
                 fetched_at: "2023-01-01T00:00:00Z".to_string(),
+                resolved_commit: None,
+                verified_tags: Vec::new(),
             }],
             agents: vec![LockedResource {
                 name: "test-agent".to_string(),
@@ -660,11 +1327,14 @@ mod tests {
                 applied_patches: std::collections::BTreeMap::new(),
                 install: None,
                 variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+                strip_metadata: false,
+                inline: None,
             }],
             snippets: vec![],
             mcp_servers: vec![],
             scripts: vec![],
             hooks: vec![],
+            disabled: vec![],
         }
     }
 
@@ -749,6 +1419,42 @@ mod tests {
         assert!(lockfile_path.exists());
     }
 
+    #[tokio::test]
+    async fn test_update_profile_uses_profile_lockfile() -> Result<()> {
+        let temp = TempDir::new()?;
+        let manifest_path = temp.path().join("agpm.toml");
+        fs::write(temp.path().join("reviewer.md"), "# Reviewer\n")?;
+        fs::write(temp.path().join("debugger.md"), "# Debugger\n")?;
+        fs::write(
+            &manifest_path,
+            r#"
+[profiles]
+prod = ["reviewer"]
+
+[agents]
+reviewer = "reviewer.md"
+debugger = "debugger.md"
+"#,
+        )?;
+
+        // Without a lockfile, update installs the profile into its own lockfile
+        let mut cmd = create_update_command();
+        cmd.profile = Some("prod".to_string());
+        cmd.execute_from_path(manifest_path.clone()).await?;
+        let prod_lock = temp.path().join("agpm.prod.lock");
+        assert!(prod_lock.exists());
+        assert!(!temp.path().join("agpm.lock").exists());
+
+        // With one, it updates that lockfile and leaves agpm.lock alone
+        let mut cmd = create_update_command();
+        cmd.profile = Some("prod".to_string());
+        cmd.execute_from_path(manifest_path).await?;
+        let lockfile = LockFile::load(&prod_lock)?;
+        assert_eq!(lockfile.agents.len(), 1);
+        assert!(!temp.path().join("agpm.lock").exists());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_execute_with_backup_flag() {
         let temp = TempDir::new().unwrap();
@@ -1085,10 +1791,191 @@ mod tests {
         assert!(lockfile_path.exists());
     }
 
+    fn locked(name: &str, source: Option<&str>, version: &str, sha: &str) -> LockedResource {
+        let mut entry = create_test_lockfile().agents.remove(0);
+        entry.name = name.to_string();
+        entry.source = source.map(str::to_string);
+        entry.version = Some(version.to_string());
+        entry.resolved_commit = (!sha.is_empty()).then(|| sha.to_string());
+        entry
+    }
+
+    #[test]
+    fn test_plan_changes_reports_moved_resources() {
+        let mut old = create_test_lockfile();
+        old.agents = vec![
+            locked("reviewer", Some("community"), "v1.0.0", "aaaaaaaa11111111"),
+            locked("stable", Some("community"), "v2.0.0", "cccccccc33333333"),
+            locked("helper", None, "", ""),
+        ];
+        old.snippets = vec![locked("utils", Some("team"), "v0.1.0", "dddddddd44444444")];
+
+        let mut new = old.clone();
+        new.agents[0] = locked("reviewer", Some("community"), "v1.1.0", "bbbbbbbb22222222");
+        new.snippets[0].resolved_commit = Some("eeeeeeee55555555".to_string());
+
+        let changes = plan_changes(&old, &new, &[], false);
+        assert_eq!(changes.len(), 2);
+        assert!(changes.iter().all(|c| c.status == ChangeStatus::Updated));
+
+        assert_eq!(
+            format_changelog(&changes),
+            vec![
+                "community:".to_string(),
+                "  reviewer: v1.0.0@aaaaaaaa → v1.1.0@bbbbbbbb".to_string(),
+                "team:".to_string(),
+                "  utils: v0.1.0@dddddddd → v0.1.0@eeeeeeee".to_string(),
+            ]
+        );
+
+        // Only named dependencies are reported
+        let changes = plan_changes(&old, &new, &["utils".to_string()], false);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].name, "utils");
+    }
+
+    #[test]
+    fn test_plan_changes_verbose_includes_local_and_unchanged() {
+        let mut old = create_test_lockfile();
+        old.agents = vec![
+            locked("stable", Some("community"), "v2.0.0", "cccccccc33333333"),
+            locked("helper", None, "", ""),
+        ];
+        let new = old.clone();
+
+        assert!(plan_changes(&old, &new, &[], false).is_empty());
+
+        let changes = plan_changes(&old, &new, &[], true);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(
+            format_changelog(&changes),
+            vec![
+                "community:".to_string(),
+                "  stable: v2.0.0@cccccccc (unchanged)".to_string(),
+                "local:".to_string(),
+                "  helper: local (unchanged)".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_changes_added_and_removed() {
+        let mut old = create_test_lockfile();
+        old.agents = vec![locked("legacy", Some("community"), "v1.0.0", "aaaaaaaa11111111")];
+        let mut new = create_test_lockfile();
+        new.agents = vec![locked("fresh", Some("community"), "v1.0.0", "bbbbbbbb22222222")];
+
+        let changes = plan_changes(&old, &new, &[], false);
+        assert_eq!(
+            format_changelog(&changes),
+            vec![
+                "community:".to_string(),
+                "  fresh: (none) → v1.0.0@bbbbbbbb".to_string(),
+                "  legacy: v1.0.0@aaaaaaaa → (removed)".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_version_jumps_changelog() {
+        let mut old = create_test_lockfile();
+        old.agents = vec![
+            locked("reviewer", Some("community"), "v1.2.0", "aaaaaaaa11111111"),
+            locked("nightly", Some("community"), "main", "cccccccc33333333"),
+            locked("stable", Some("community"), "v2.0.0", "dddddddd44444444"),
+            locked("helper", None, "", ""),
+        ];
+        let mut new = old.clone();
+        new.agents[0] = locked("reviewer", Some("community"), "v1.4.0", "bbbbbbbb22222222");
+        new.agents[1].resolved_commit = Some("eeeeeeee55555555".to_string());
+
+        let mut jumps = version_jumps(&old, &new, &[]);
+        assert_eq!(jumps.len(), 2);
+
+        let tags: Vec<(String, String)> =
+            ["v1.1.0", "v1.3.1", "v1.2.0", "v1.3.0", "v1.4.0", "v2.0.0"]
+                .iter()
+                .map(|tag| (tag.to_string(), tag.to_string()))
+                .collect();
+        for jump in &mut jumps {
+            jump.between = tags_between(&tags, &jump.old, &jump.new);
+        }
+
+        assert_eq!(
+            format_version_jumps(&jumps),
+            vec![
+                "community:".to_string(),
+                "  nightly: main@cccccccc → main@eeeeeeee".to_string(),
+                "  reviewer: v1.2.0 → v1.4.0 (between: v1.3.0, v1.3.1)".to_string(),
+            ]
+        );
+        assert_eq!(format_version_jumps(&[]), vec!["No dependency versions changed.".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_selection_input() {
+        let mut selected = vec![true, true, true];
+
+        assert_eq!(parse_selection_input(&mut selected, "1, 3\n"), Ok(SelectionInput::Changed));
+        assert_eq!(selected, [false, true, false]);
+        assert_eq!(parse_selection_input(&mut selected, "3"), Ok(SelectionInput::Changed));
+        assert_eq!(selected, [false, true, true]);
+        assert_eq!(parse_selection_input(&mut selected, "n"), Ok(SelectionInput::Changed));
+        assert_eq!(selected, [false, false, false]);
+        assert_eq!(parse_selection_input(&mut selected, "a"), Ok(SelectionInput::Changed));
+        assert_eq!(selected, [true, true, true]);
+
+        // Invalid input leaves the selection alone
+        assert!(parse_selection_input(&mut selected, "1 4").is_err());
+        assert!(parse_selection_input(&mut selected, "x").is_err());
+        assert_eq!(selected, [true, true, true]);
+
+        assert_eq!(parse_selection_input(&mut selected, "\n"), Ok(SelectionInput::Apply));
+        assert_eq!(parse_selection_input(&mut selected, "q"), Ok(SelectionInput::Cancel));
+    }
+
+    #[test]
+    fn test_tags_between_respects_prefixes() {
+        let tags: Vec<(String, String)> =
+            ["agents-v1.0.0", "agents-v1.1.0", "snippets-v1.1.0", "agents-v2.0.0"]
+                .iter()
+                .map(|tag| (tag.to_string(), tag.to_string()))
+                .collect();
+        assert_eq!(tags_between(&tags, "agents-v1.0.0", "agents-v2.0.0"), vec!["agents-v1.1.0"]);
+        assert!(tags_between(&tags, "main", "agents-v2.0.0").is_empty());
+
+        // With a tag filter the version part is compared, the full tag is reported
+        let filtered = vec![
+            ("release/1.0.0".to_string(), "1.0.0".to_string()),
+            ("release/1.5.0".to_string(), "1.5.0".to_string()),
+            ("release/2.0.0".to_string(), "2.0.0".to_string()),
+        ];
+        assert_eq!(
+            tags_between(&filtered, "release/1.0.0", "release/2.0.0"),
+            vec!["release/1.5.0"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_without_lockfile_does_not_install() {
+        let temp = TempDir::new().unwrap();
+        let manifest_path = temp.path().join("agpm.toml");
+        std::fs::write(&manifest_path, "[sources]\n\n[agents]\n").unwrap();
+
+        let mut cmd = create_update_command();
+        cmd.dry_run = true;
+
+        cmd.execute_from_path(manifest_path).await.unwrap();
+        assert!(!temp.path().join("agpm.lock").exists());
+    }
+
     #[test]
     fn test_update_command_defaults() {
         let cmd = UpdateCommand {
             dependencies: vec![],
+            conservative: false,
+            interactive: false,
+            max_depth: DEFAULT_MAX_DEPTH,
             dry_run: false,
             check: false,
             backup: false,
@@ -1096,6 +1983,14 @@ mod tests {
             quiet: false,
             no_progress: false,
             max_parallel: None,
+            io_parallel: None,
+            changelog: false,
+            changelog_file: None,
+            commit_replace: false,
+            no_post_install: false,
+            verify_signatures: false,
+            profile: None,
+            lockfile: None,
         };
 
         assert!(cmd.dependencies.is_empty());
@@ -1110,6 +2005,9 @@ mod tests {
     fn test_update_command_with_all_flags() {
         let cmd = UpdateCommand {
             dependencies: vec!["dep1".to_string(), "dep2".to_string()],
+            conservative: false,
+            interactive: false,
+            max_depth: DEFAULT_MAX_DEPTH,
             dry_run: true,
             check: true,
             backup: true,
@@ -1117,6 +2015,14 @@ mod tests {
             quiet: true,
             no_progress: true,
             max_parallel: Some(4),
+            io_parallel: None,
+            changelog: true,
+            changelog_file: None,
+            commit_replace: false,
+            no_post_install: false,
+            verify_signatures: false,
+            profile: None,
+            lockfile: None,
         };
 
         assert_eq!(cmd.dependencies.len(), 2);
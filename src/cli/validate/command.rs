@@ -33,6 +33,11 @@ use clap::Args;
 ///     quiet: false,
 ///     strict: false,
 ///     render: false,
+///     mcp: false,
+///     prune: false,
+///     explain: false,
+///     network: false,
+///     offline: false,
 /// };
 ///
 /// // Comprehensive CI validation
@@ -47,6 +52,11 @@ use clap::Args;
 ///     quiet: true,
 ///     strict: true,
 ///     render: false,
+///     mcp: false,
+///     prune: false,
+///     explain: false,
+///     network: false,
+///     offline: false,
 /// };
 /// ```
 #[derive(Args)]
@@ -70,10 +80,20 @@ pub struct ValidateCommand {
     ///
     /// Compares the manifest dependencies with those recorded in the
     /// lockfile to identify inconsistencies. Warns if dependencies are
-    /// missing from the lockfile or if extra entries exist.
+    /// missing from the lockfile and fails on orphaned entries: entries
+    /// whose manifest dependency was removed and that no remaining
+    /// resource depends on.
     #[arg(long, alias = "lockfile")]
     pub check_lock: bool,
 
+    /// Remove orphaned lockfile entries and their installed files
+    ///
+    /// Implies `--check-lock`. Orphaned entries are dropped from `agpm.lock`,
+    /// their installed files are deleted, and their hook and MCP server
+    /// entries are removed from the merged configuration files.
+    #[arg(long)]
+    pub prune: bool,
+
     /// Check if all sources are accessible
     ///
     /// Tests network connectivity to all source repositories defined
@@ -141,6 +161,41 @@ pub struct ValidateCommand {
     /// - Testing template rendering without modifying the filesystem
     #[arg(long)]
     pub render: bool,
+
+    /// Validate MCP server configurations without installing
+    ///
+    /// Reads each MCP server JSON file from its source and checks that it has
+    /// the fields its transport requires: `command` for stdio servers and `url`
+    /// for `http`/`sse` servers. Unknown transports are rejected. Uses the
+    /// lockfile when present; otherwise only local MCP dependencies are checked.
+    #[arg(long)]
+    pub mcp: bool,
+
+    /// Explain how each dependency's tool and install location are chosen
+    ///
+    /// Prints, per dependency, the effective tool and whether it was set on
+    /// the dependency, taken from `[default-tools]`, or the built-in default
+    /// for the resource type. Also shows whether the tool is enabled and the
+    /// directory the resource installs into, or the configuration file that
+    /// hooks and MCP servers are merged into.
+    #[arg(long)]
+    pub explain: bool,
+
+    /// With `--resolve`, check versions against the live remotes instead of cloning
+    ///
+    /// Lists each source's branches and tags with `git ls-remote` and reports,
+    /// per dependency, whether its branch, tag, commit or version constraint
+    /// can currently be satisfied upstream. Nothing is cloned, cached or
+    /// installed, which makes this a fast pre-flight check for CI that catches
+    /// tags deleted upstream.
+    #[arg(long, requires = "resolve")]
+    pub network: bool,
+
+    /// Don't contact sources
+    ///
+    /// Skips `--network` and `--sources` checks, reporting them as not checked.
+    #[arg(long)]
+    pub offline: bool,
 }
 
 /// Output format options for validation results.
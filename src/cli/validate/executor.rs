@@ -30,10 +30,13 @@ impl ValidateCommand {
     ///
     /// Validations are performed in this order to provide early feedback:
     /// 1. Manifest structure and syntax
-    /// 2. Dependency resolution (if `--resolve`)
+    /// 2. Dependency resolution (if `--resolve`, against the remotes with `--network`)
     /// 3. Source accessibility (if `--sources`)
     /// 4. Local path validation (if `--paths`)
     /// 5. Lockfile consistency (if `--check-lock`)
+    /// 6. Template rendering (if `--render`)
+    /// 7. MCP server configurations (if `--mcp`)
+    /// 8. Tool resolution explanation (if `--explain`)
     ///
     /// # Returns
     ///
@@ -60,6 +63,11 @@ impl ValidateCommand {
     ///     quiet: false,
     ///     strict: false,
     ///     render: false,
+    ///     mcp: false,
+    ///     prune: false,
+    ///     explain: false,
+    ///     network: false,
+    ///     offline: false,
     /// };
     /// // cmd.execute().await?;
     /// ```
@@ -98,6 +106,11 @@ impl ValidateCommand {
     ///     json: false,
     ///     paths: false,
     ///     fix: false,
+    ///     mcp: false,
+    ///     prune: false,
+    ///     explain: false,
+    ///     network: false,
+    ///     offline: false,
     /// };
     ///
     /// cmd.execute_with_manifest_path(Some(PathBuf::from("./agpm.toml"))).await?;
@@ -188,8 +201,19 @@ impl ValidateCommand {
         )
         .await?;
 
-        // Check if dependencies can be resolved
-        if self.resolve {
+        // Check if dependencies can be resolved, against the live remotes with --network
+        if self.resolve && self.network {
+            let mut ctx = validators::ValidationContext::new(
+                &manifest,
+                &self.format,
+                self.verbose,
+                self.quiet,
+                &mut validation_results,
+                &mut warnings,
+                &mut errors,
+            );
+            validators::validate_remote_versions(&mut ctx, self.offline).await?;
+        } else if self.resolve {
             validators::validate_dependencies(
                 &manifest,
                 &self.format,
@@ -203,7 +227,11 @@ impl ValidateCommand {
         }
 
         // Check if sources are accessible
-        if self.sources {
+        if self.sources && self.offline {
+            if !self.quiet && matches!(self.format, OutputFormat::Text) {
+                println!("- Sources not checked (--offline)");
+            }
+        } else if self.sources {
             validators::validate_sources(
                 &manifest,
                 &self.format,
@@ -230,8 +258,8 @@ impl ValidateCommand {
             validators::validate_paths(&mut ctx, &manifest_path).await?;
         }
 
-        // Check lockfile consistency
-        if self.check_lock {
+        // Check lockfile consistency (--prune implies --check-lock)
+        if self.check_lock || self.prune {
             let project_dir = manifest_path.parent().unwrap();
             let mut ctx = validators::ValidationContext::new(
                 &manifest,
@@ -242,7 +270,7 @@ impl ValidateCommand {
                 &mut warnings,
                 &mut errors,
             );
-            validators::validate_lockfile(&mut ctx, project_dir).await?;
+            validators::validate_lockfile(&mut ctx, project_dir, self.prune).await?;
         }
 
         // Validate template rendering if requested
@@ -260,6 +288,35 @@ impl ValidateCommand {
             validators::validate_templates(&mut ctx, project_dir).await?;
         }
 
+        // Validate MCP server configurations if requested
+        if self.mcp {
+            let project_dir = manifest_path.parent().unwrap();
+            let mut ctx = validators::ValidationContext::new(
+                &manifest,
+                &self.format,
+                self.verbose,
+                self.quiet,
+                &mut validation_results,
+                &mut warnings,
+                &mut errors,
+            );
+            validators::validate_mcp_servers(&mut ctx, project_dir).await?;
+        }
+
+        // Explain tool resolution if requested
+        if self.explain {
+            let mut ctx = validators::ValidationContext::new(
+                &manifest,
+                &self.format,
+                self.verbose,
+                self.quiet,
+                &mut validation_results,
+                &mut warnings,
+                &mut errors,
+            );
+            validators::explain_tools(&mut ctx, &manifest_path).await?;
+        }
+
         // Handle strict mode - treat warnings as errors
         if self.strict && !warnings.is_empty() {
             let error_msg = "Strict mode: Warnings treated as errors";
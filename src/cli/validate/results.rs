@@ -16,6 +16,7 @@ use serde::Serialize;
 /// - `sources_accessible`: Whether all source repositories are accessible
 /// - `local_paths_exist`: Whether all local file dependencies exist
 /// - `lockfile_consistent`: Whether the lockfile matches the manifest
+/// - `orphaned_entries`: Lockfile entries no longer required by the manifest
 /// - `errors`: List of error messages that caused validation to fail
 /// - `warnings`: List of warning messages (non-fatal issues)
 ///
@@ -49,14 +50,60 @@ pub struct ValidationResults {
     pub lockfile_consistent: bool,
     /// Whether all templates rendered successfully (when --render is used)
     pub templates_valid: bool,
+    /// Whether all MCP server configurations are well-formed (when --mcp is used)
+    pub mcp_servers_valid: bool,
     /// Number of templates successfully rendered
     pub templates_rendered: usize,
     /// Total number of templates found
     pub templates_total: usize,
+    /// Lockfile entries no longer required by the manifest (when --check-lock is used)
+    pub orphaned_entries: Vec<String>,
     /// List of error messages that caused validation failure
     pub errors: Vec<String>,
     /// List of warning messages (non-fatal issues)
     pub warnings: Vec<String>,
+    /// How each dependency's tool and install location were chosen (when --explain is used)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tool_resolution: Vec<ToolExplanation>,
+    /// Whether each remote dependency's version exists upstream (when --network is used)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub remote_versions: Vec<RemoteVersionCheck>,
+}
+
+/// How one dependency's tool and install location were chosen, for `--explain`.
+#[derive(Debug, Serialize)]
+pub struct ToolExplanation {
+    /// Resource type of the dependency (`agent`, `snippet`, ...)
+    pub resource_type: String,
+    /// Dependency name from the manifest
+    pub name: String,
+    /// The tool the dependency is installed for
+    pub tool: String,
+    /// Where the tool came from: `explicit`, `default-tools` or `built-in`
+    pub origin: String,
+    /// Whether the tool is enabled in `[tools]`
+    pub enabled: bool,
+    /// Directory the resource is installed into, for file-based resources
+    pub install_path: Option<String>,
+    /// Configuration file the resource is merged into, for hooks and MCP servers
+    pub merge_target: Option<String>,
+}
+
+/// Whether one dependency's version can be satisfied by its live remote, for `--network`.
+#[derive(Debug, Serialize)]
+pub struct RemoteVersionCheck {
+    /// Resource type of the dependency (`agent`, `snippet`, ...)
+    pub resource_type: String,
+    /// Dependency name from the manifest
+    pub name: String,
+    /// Source the dependency is fetched from
+    pub source: String,
+    /// The version, branch, tag, commit or constraint requested, `HEAD` if none
+    pub version: String,
+    /// `ok`, `missing`, `unreachable` or `not-checked`
+    pub status: String,
+    /// The matching ref when `ok`, otherwise why the version was not satisfied or checked
+    pub detail: String,
 }
 
 impl Default for ValidationResults {
@@ -69,10 +116,14 @@ impl Default for ValidationResults {
             local_paths_exist: false,
             lockfile_consistent: false,
             templates_valid: false,
+            mcp_servers_valid: false,
             templates_rendered: 0,
             templates_total: 0,
+            orphaned_entries: Vec::new(),
             errors: Vec::new(),
             warnings: Vec::new(),
+            tool_resolution: Vec::new(),
+            remote_versions: Vec::new(),
         }
     }
 }
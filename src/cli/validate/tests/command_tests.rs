@@ -29,6 +29,11 @@ async fn test_validate_custom_file_path() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -52,6 +57,11 @@ async fn test_execute_with_no_manifest_json_format() {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute().await;
@@ -75,6 +85,11 @@ async fn test_execute_with_no_manifest_text_format() {
         quiet: false, // Not quiet - should print error message
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute().await;
@@ -98,6 +113,11 @@ async fn test_execute_with_no_manifest_quiet_mode() {
         quiet: true, // Quiet mode - should not print
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute().await;
@@ -121,6 +141,11 @@ async fn test_execute_from_path_nonexistent_file_json() {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(nonexistent_path).await;
@@ -144,6 +169,11 @@ async fn test_execute_from_path_nonexistent_file_text() {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(nonexistent_path).await;
@@ -164,6 +194,11 @@ async fn test_validate_command_defaults() {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
     assert_eq!(cmd.file, None);
     assert!(!cmd.resolve);
@@ -193,6 +228,11 @@ async fn test_execute_without_manifest_file() {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute().await;
@@ -218,6 +258,11 @@ async fn test_execute_with_specified_file() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute().await;
@@ -241,6 +286,11 @@ async fn test_execute_with_nonexistent_specified_file() {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute().await;
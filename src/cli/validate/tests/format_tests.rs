@@ -27,6 +27,11 @@ async fn test_validate_json_format() -> Result<()> {
         quiet: true,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -54,6 +59,11 @@ async fn test_validate_strict_mode() -> Result<()> {
         quiet: true,
         strict: true, // Strict mode treats warnings as errors
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -83,6 +93,11 @@ async fn test_validate_verbose_mode() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -116,6 +131,17 @@ async fn test_validate_json_error_format() -> Result<()> {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             },
         )),
         true,
@@ -133,6 +159,11 @@ async fn test_validate_json_error_format() -> Result<()> {
         quiet: true,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -159,6 +190,11 @@ async fn test_validate_verbose_output() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -187,6 +223,11 @@ async fn test_validate_strict_mode_with_warnings() -> Result<()> {
         quiet: false,
         strict: true, // Strict mode
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -214,6 +255,11 @@ async fn test_validate_quiet_mode() -> Result<()> {
         quiet: true, // Enable quiet
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -248,6 +294,17 @@ async fn test_validate_json_output_success() -> Result<()> {
             install: None,
 
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         })),
     );
     manifest.save(&manifest_path)?;
@@ -263,6 +320,11 @@ async fn test_validate_json_output_success() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -289,6 +351,11 @@ async fn test_validate_manifest_toml_syntax_error_json() -> Result<()> {
         quiet: true,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -324,6 +391,17 @@ async fn test_validate_resolve_with_error_json_output() -> Result<()> {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             },
         )),
         true,
@@ -341,6 +419,11 @@ async fn test_validate_resolve_with_error_json_output() -> Result<()> {
         quiet: true,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -380,6 +463,11 @@ async fn test_validate_sources_accessibility_error_json() -> Result<()> {
         quiet: true,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -414,6 +502,17 @@ async fn test_validate_check_paths_missing_snippets_json() -> Result<()> {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             },
         )),
     );
@@ -430,6 +529,11 @@ async fn test_validate_check_paths_missing_snippets_json() -> Result<()> {
         quiet: true,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -462,6 +566,11 @@ async fn test_validate_lockfile_syntax_error_json() -> Result<()> {
         quiet: true,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -490,6 +599,11 @@ async fn test_validate_strict_mode_with_json_output() -> Result<()> {
         quiet: true,
         strict: true, // Strict mode with JSON output
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -518,6 +632,11 @@ async fn test_validate_strict_mode_text_output() -> Result<()> {
         quiet: false, // Not quiet - should print error message
         strict: true,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -557,6 +676,11 @@ async fn test_validate_verbose_mode_with_summary() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -593,6 +717,11 @@ async fn test_json_output_format() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -619,6 +748,11 @@ async fn test_validation_with_verbose_mode() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -645,6 +779,11 @@ async fn test_validation_with_quiet_mode() -> Result<()> {
         quiet: true,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -672,6 +811,11 @@ async fn test_validation_with_strict_mode_and_warnings() -> Result<()> {
         quiet: false,
         strict: true, // Strict mode will fail on warnings
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -698,6 +842,11 @@ async fn test_validation_json_output_with_errors() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -721,6 +870,11 @@ async fn test_validation_with_manifest_not_found_json() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -744,6 +898,11 @@ async fn test_validation_with_manifest_not_found_text() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -777,6 +936,11 @@ async fn test_validation_with_verbose_and_text_format() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -20,6 +20,11 @@ async fn test_validate_no_manifest() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -48,6 +53,11 @@ async fn test_validate_valid_manifest() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -81,6 +91,17 @@ async fn test_validate_invalid_manifest() -> Result<()> {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             },
         )),
         true,
@@ -98,6 +119,11 @@ async fn test_validate_invalid_manifest() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -124,6 +150,11 @@ async fn test_validate_manifest_toml_syntax_error() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -158,6 +189,17 @@ async fn test_validate_manifest_structure_error() -> Result<()> {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             },
         )),
         true,
@@ -175,6 +217,11 @@ async fn test_validate_manifest_structure_error() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -212,6 +259,11 @@ another-agent = { source = "test", path = "agent.md", version = "v2.0.0" }
         quiet: true,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     // Version conflicts are automatically resolved during installation
@@ -249,6 +301,17 @@ async fn test_validate_with_outdated_version_warnings() -> Result<()> {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             },
         )),
         true,
@@ -266,6 +329,11 @@ async fn test_validate_with_outdated_version_warnings() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -293,6 +361,11 @@ async fn test_validate_final_success_with_warnings() -> Result<()> {
         quiet: false,
         strict: false, // Not strict - warnings don't cause failure
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -329,6 +402,11 @@ async fn test_validate_all_checks_enabled() -> Result<()> {
         quiet: false,
         strict: true,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -359,6 +437,11 @@ async fn test_validate_with_specific_file_path() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute().await;
@@ -410,6 +493,17 @@ async fn test_validation_with_outdated_version_warning() -> Result<()> {
             install: None,
 
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         })),
     );
     manifest.save(&manifest_path)?;
@@ -425,6 +519,11 @@ async fn test_validation_with_outdated_version_warning() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
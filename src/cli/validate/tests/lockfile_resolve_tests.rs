@@ -32,6 +32,17 @@ async fn test_validate_with_resolve() -> Result<()> {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             },
         )),
         true,
@@ -49,6 +60,11 @@ async fn test_validate_with_resolve() -> Result<()> {
         quiet: true, // Make quiet to avoid output
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -82,6 +98,11 @@ async fn test_validate_check_lock_consistent() -> Result<()> {
         quiet: true,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -119,6 +140,8 @@ async fn test_validate_check_lock_with_extra_entries() -> Result<()> {
         applied_patches: std::collections::BTreeMap::new(),
         install: None,
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+        strip_metadata: false,
+        inline: None,
     });
     lockfile.save(&temp.path().join("agpm.lock")).unwrap();
 
@@ -133,6 +156,11 @@ async fn test_validate_check_lock_with_extra_entries() -> Result<()> {
         quiet: true,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -167,6 +195,11 @@ async fn test_validate_check_lock() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path.clone()).await;
@@ -195,11 +228,14 @@ async fn test_validate_check_lock() -> Result<()> {
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
+            inline: None,
         }],
         snippets: vec![],
         mcp_servers: vec![],
         scripts: vec![],
         hooks: vec![],
+        disabled: vec![],
     };
     lockfile.save(&temp.path().join("agpm.lock")).unwrap();
 
@@ -214,6 +250,11 @@ async fn test_validate_check_lock() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -248,6 +289,17 @@ async fn test_validate_resolve_dependency_not_found_error() -> Result<()> {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             },
         )),
         true,
@@ -271,6 +323,17 @@ async fn test_validate_resolve_dependency_not_found_error() -> Result<()> {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             },
         )),
         false,
@@ -288,6 +351,11 @@ async fn test_validate_resolve_dependency_not_found_error() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -316,6 +384,11 @@ async fn test_validate_lockfile_missing_warning() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -359,6 +432,11 @@ async fn test_validate_lockfile_missing_dependencies() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -397,6 +475,8 @@ async fn test_validate_lockfile_extra_entries_error() -> Result<()> {
         applied_patches: std::collections::BTreeMap::new(),
         install: None,
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+        strip_metadata: false,
+        inline: None,
     });
     lockfile.save(&lockfile_path).unwrap();
 
@@ -411,6 +491,11 @@ async fn test_validate_lockfile_extra_entries_error() -> Result<()> {
         quiet: true,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -441,6 +526,11 @@ async fn test_validation_with_lockfile_consistency_check_no_lockfile() -> Result
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -481,6 +571,8 @@ async fn test_validation_with_inconsistent_lockfile() -> Result<()> {
         applied_patches: std::collections::BTreeMap::new(),
         install: None,
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+        strip_metadata: false,
+        inline: None,
     });
     lockfile.save(&lockfile_path).unwrap();
 
@@ -495,6 +587,11 @@ async fn test_validation_with_inconsistent_lockfile() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -502,6 +599,74 @@ async fn test_validation_with_inconsistent_lockfile() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_validation_prunes_orphaned_entries() -> Result<()> {
+    let temp = TempDir::new().unwrap();
+    let manifest_path = temp.path().join("agpm.toml");
+    let lockfile_path = temp.path().join("agpm.lock");
+
+    let mut manifest = Manifest::new();
+    manifest.agents.insert("kept".to_string(), ResourceDependency::Simple("kept.md".to_string()));
+    manifest.save(&manifest_path).unwrap();
+
+    // The "removed" agent is still locked and installed, but no longer in the manifest
+    let agents_dir = temp.path().join(".claude").join("agents");
+    std::fs::create_dir_all(&agents_dir).unwrap();
+    std::fs::write(agents_dir.join("kept.md"), "# Kept").unwrap();
+    std::fs::write(agents_dir.join("removed.md"), "# Removed").unwrap();
+
+    let mut lockfile = crate::lockfile::LockFile::new();
+    for name in ["kept", "removed"] {
+        lockfile.agents.push(crate::lockfile::LockedResource {
+            name: name.to_string(),
+            source: None,
+            url: None,
+            path: format!("{name}.md"),
+            version: None,
+            resolved_commit: None,
+            checksum: "sha256:dummy".to_string(),
+            installed_at: format!(".claude/agents/{name}.md"),
+            dependencies: vec![],
+            resource_type: crate::core::ResourceType::Agent,
+            tool: Some("claude-code".to_string()),
+            manifest_alias: Some(name.to_string()),
+            context_checksum: None,
+            applied_patches: std::collections::BTreeMap::new(),
+            install: None,
+            variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
+            inline: None,
+        });
+    }
+    lockfile.save(&lockfile_path).unwrap();
+
+    let cmd = ValidateCommand {
+        file: None,
+        resolve: false,
+        check_lock: false,
+        sources: false,
+        paths: false,
+        format: OutputFormat::Text,
+        verbose: false,
+        quiet: true,
+        strict: false,
+        render: false,
+        mcp: false,
+        prune: true,
+        explain: false,
+        network: false,
+        offline: false,
+    };
+    cmd.execute_from_path(manifest_path).await?;
+
+    let pruned = crate::lockfile::LockFile::load(&lockfile_path)?;
+    assert_eq!(pruned.agents.len(), 1);
+    assert_eq!(pruned.agents[0].display_name(), "kept");
+    assert!(agents_dir.join("kept.md").exists());
+    assert!(!agents_dir.join("removed.md").exists());
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_validation_with_invalid_lockfile_syntax() -> Result<()> {
     let temp = TempDir::new().unwrap();
@@ -525,6 +690,11 @@ async fn test_validation_with_invalid_lockfile_syntax() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -571,6 +741,8 @@ async fn test_validation_with_missing_lockfile_dependencies() -> Result<()> {
         applied_patches: std::collections::BTreeMap::new(),
         install: None,
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+        strip_metadata: false,
+        inline: None,
     });
     lockfile.save(&lockfile_path).unwrap();
 
@@ -585,6 +757,11 @@ async fn test_validation_with_missing_lockfile_dependencies() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -0,0 +1,80 @@
+//! Tests for `agpm validate --mcp`
+
+use super::super::{OutputFormat, ValidateCommand};
+use crate::manifest::{Manifest, ResourceDependency};
+use anyhow::Result;
+use tempfile::TempDir;
+
+fn mcp_command() -> ValidateCommand {
+    ValidateCommand {
+        file: None,
+        resolve: false,
+        check_lock: false,
+        sources: false,
+        paths: false,
+        format: OutputFormat::Text,
+        verbose: false,
+        quiet: true,
+        strict: false,
+        render: false,
+        mcp: true,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
+    }
+}
+
+fn write_project(temp: &TempDir, server_json: &str) -> Result<std::path::PathBuf> {
+    std::fs::create_dir_all(temp.path().join("mcp"))?;
+    std::fs::write(temp.path().join("mcp/server.json"), server_json)?;
+
+    let mut manifest = Manifest::new();
+    manifest
+        .mcp_servers
+        .insert("server".to_string(), ResourceDependency::Simple("./mcp/server.json".to_string()));
+    let manifest_path = temp.path().join("agpm.toml");
+    manifest.save(&manifest_path)?;
+    Ok(manifest_path)
+}
+
+#[tokio::test]
+async fn test_validate_mcp_valid_config() -> Result<()> {
+    let temp = TempDir::new()?;
+    let manifest_path = write_project(&temp, r#"{"command": "npx", "args": ["server"]}"#)?;
+
+    mcp_command().execute_from_path(manifest_path).await
+}
+
+#[tokio::test]
+async fn test_validate_mcp_missing_command() -> Result<()> {
+    let temp = TempDir::new()?;
+    let manifest_path = write_project(&temp, r#"{"args": ["server"]}"#)?;
+
+    let result = mcp_command().execute_from_path(manifest_path).await;
+    assert!(result.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_validate_mcp_unknown_transport() -> Result<()> {
+    let temp = TempDir::new()?;
+    let manifest_path =
+        write_project(&temp, r#"{"type": "websocket", "url": "wss://example.com"}"#)?;
+
+    let mut cmd = mcp_command();
+    cmd.format = OutputFormat::Json;
+    let result = cmd.execute_from_path(manifest_path).await;
+    assert!(result.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_validate_without_mcp_flag_skips_mcp_checks() -> Result<()> {
+    let temp = TempDir::new()?;
+    let manifest_path = write_project(&temp, r#"{"args": ["server"]}"#)?;
+
+    let mut cmd = mcp_command();
+    cmd.mcp = false;
+    cmd.execute_from_path(manifest_path).await
+}
@@ -5,10 +5,12 @@
 //! - `format_tests`: Output formatting (JSON, text, verbose, quiet, strict)
 //! - `integration_tests`: End-to-end scenarios and complex interactions
 //! - `lockfile_resolve_tests`: Lockfile validation and dependency resolution
+//! - `mcp_tests`: MCP server configuration validation
 //! - `path_source_tests`: Path validation, source checking, and file references
 
 mod command_tests;
 mod format_tests;
 mod integration_tests;
 mod lockfile_resolve_tests;
+mod mcp_tests;
 mod path_source_tests;
@@ -37,6 +37,17 @@ async fn test_validate_check_paths_local() -> Result<()> {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             },
         )),
         true,
@@ -54,6 +65,11 @@ async fn test_validate_check_paths_local() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -87,6 +103,11 @@ async fn test_validate_paths_check() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path.clone()).await;
@@ -107,6 +128,11 @@ async fn test_validate_paths_check() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -144,6 +170,11 @@ async fn test_validate_check_sources() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     // This will check if the local source is accessible
@@ -180,6 +211,17 @@ async fn test_validate_check_paths() -> Result<()> {
             install: None,
 
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         })),
     );
     manifest.save(&manifest_path).unwrap();
@@ -198,6 +240,11 @@ async fn test_validate_check_paths() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -235,6 +282,11 @@ async fn test_validate_sources_accessibility_error() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -271,6 +323,17 @@ async fn test_validate_check_paths_snippets_and_commands() -> Result<()> {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             },
         )),
     );
@@ -295,6 +358,17 @@ async fn test_validate_check_paths_snippets_and_commands() -> Result<()> {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             },
         )),
     );
@@ -318,6 +392,11 @@ async fn test_validate_check_paths_snippets_and_commands() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -346,6 +425,11 @@ async fn test_validate_sources_check_with_invalid_url() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -376,6 +460,11 @@ async fn test_validation_with_local_paths_check() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -409,6 +498,11 @@ async fn test_validation_with_existing_local_paths() -> Result<()> {
         quiet: false,
         strict: false,
         render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -468,6 +562,8 @@ See [helper](.agpm/snippets/helper.md) for details.
         applied_patches: std::collections::BTreeMap::new(),
         install: None,
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+        strip_metadata: false,
+        inline: None,
     });
     lockfile.save(&lockfile_path).unwrap();
 
@@ -482,6 +578,11 @@ See [helper](.agpm/snippets/helper.md) for details.
         quiet: false,
         strict: false,
         render: true,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -537,6 +638,8 @@ Also check `.claude/nonexistent.md`.
         applied_patches: std::collections::BTreeMap::new(),
         install: None,
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+        strip_metadata: false,
+        inline: None,
     });
     lockfile.save(&lockfile_path).unwrap();
 
@@ -551,6 +654,11 @@ Also check `.claude/nonexistent.md`.
         quiet: false,
         strict: false,
         render: true,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -608,6 +716,8 @@ Visit http://example.com for more info.
         applied_patches: std::collections::BTreeMap::new(),
         install: None,
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+        strip_metadata: false,
+        inline: None,
     });
     lockfile.save(&lockfile_path).unwrap();
 
@@ -622,6 +732,11 @@ Visit http://example.com for more info.
         quiet: false,
         strict: false,
         render: true,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -681,6 +796,8 @@ Inline code `example.md` should also be ignored.
         applied_patches: std::collections::BTreeMap::new(),
         install: None,
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+        strip_metadata: false,
+        inline: None,
     });
     lockfile.save(&lockfile_path).unwrap();
 
@@ -695,6 +812,11 @@ Inline code `example.md` should also be ignored.
         quiet: false,
         strict: false,
         render: true,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -753,6 +875,8 @@ async fn test_file_reference_validation_multiple_resources() -> Result<()> {
         applied_patches: std::collections::BTreeMap::new(),
         install: None,
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+        strip_metadata: false,
+        inline: None,
     });
     lockfile.commands.push(LockedResource {
         name: "cmd1".to_string(),
@@ -771,6 +895,8 @@ async fn test_file_reference_validation_multiple_resources() -> Result<()> {
         applied_patches: std::collections::BTreeMap::new(),
         install: None,
         variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+        strip_metadata: false,
+        inline: None,
     });
     lockfile.save(&lockfile_path).unwrap();
 
@@ -785,6 +911,11 @@ async fn test_file_reference_validation_multiple_resources() -> Result<()> {
         quiet: false,
         strict: false,
         render: true,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: false,
+        offline: false,
     };
 
     let result = cmd.execute_from_path(manifest_path).await;
@@ -793,3 +924,57 @@ async fn test_file_reference_validation_multiple_resources() -> Result<()> {
     assert!(err_msg.contains("File reference validation failed"));
     Ok(())
 }
+
+#[tokio::test]
+async fn test_validate_resolve_network_checks_remote_versions() -> Result<()> {
+    let temp = TempDir::new().unwrap();
+    let manifest_path = temp.path().join("agpm.toml");
+
+    let source_dir = temp.path().join("test-source");
+    std::fs::create_dir_all(source_dir.join("agents")).unwrap();
+    std::fs::write(source_dir.join("agents/reviewer.md"), "# Reviewer").unwrap();
+    let git = TestGit::new(&source_dir);
+    git.init()?;
+    git.config_user()?;
+    git.add_all()?;
+    git.commit("Initial commit")?;
+    git.tag("v1.0.0")?;
+
+    let source_url = format!("file://{}", normalize_path_for_storage(&source_dir));
+    let write_manifest = |version: &str| {
+        std::fs::write(
+            &manifest_path,
+            format!(
+                "[sources]\ntest = \"{source_url}\"\n\n[agents]\n\
+                 reviewer = {{ source = \"test\", path = \"agents/reviewer.md\", version = \"{version}\" }}\n"
+            ),
+        )
+    };
+    let cmd = |offline: bool| ValidateCommand {
+        file: None,
+        resolve: true,
+        check_lock: false,
+        sources: false,
+        paths: false,
+        format: OutputFormat::Json,
+        verbose: false,
+        quiet: true,
+        strict: false,
+        render: false,
+        mcp: false,
+        prune: false,
+        explain: false,
+        network: true,
+        offline,
+    };
+
+    write_manifest("^1.0.0")?;
+    cmd(false).execute_from_path(manifest_path.clone()).await?;
+
+    // A tag deleted upstream fails the check, unless the remote is not contacted
+    write_manifest("v0.9.0")?;
+    let err = cmd(false).execute_from_path(manifest_path.clone()).await.unwrap_err();
+    assert!(err.to_string().contains("cannot be satisfied"), "{err}");
+    cmd(true).execute_from_path(manifest_path.clone()).await?;
+    Ok(())
+}
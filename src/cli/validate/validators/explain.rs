@@ -0,0 +1,168 @@
+//! Explanation of how each dependency's tool and install location are chosen.
+
+use anyhow::Result;
+use colored::Colorize;
+use std::path::Path;
+
+use crate::cli::validate::results::ToolExplanation;
+use crate::core::ResourceType;
+use crate::manifest::Manifest;
+
+use super::{OutputFormat, ValidationContext};
+
+// Where a dependency's effective tool came from
+const EXPLICIT: &str = "explicit";
+const DEFAULT_TOOLS: &str = "default-tools";
+const BUILT_IN: &str = "built-in";
+
+/// Explain, per dependency, the tool it installs for and where it lands.
+///
+/// The tool is either set on the dependency (`explicit`), taken from the
+/// manifest's `[default-tools]` section, or the built-in default for the
+/// resource type. File-based resources report the directory from the tool's
+/// configuration (plus the dependency's `target`), merged resources report
+/// the configuration file they are merged into.
+///
+/// # Arguments
+///
+/// * `ctx` - Validation context containing all necessary parameters
+/// * `manifest_path` - Path to the manifest file, read again to tell explicit
+///   tools from defaults that loading has already applied
+pub async fn explain_tools(ctx: &mut ValidationContext<'_>, manifest_path: &Path) -> Result<()> {
+    let raw: toml::Table = tokio::fs::read_to_string(manifest_path)
+        .await
+        .ok()
+        .and_then(|content| toml::from_str(&content).ok())
+        .unwrap_or_default();
+
+    let explanations = explain_manifest(ctx.manifest, &raw);
+
+    if matches!(ctx.format, OutputFormat::Text) && !ctx.quiet {
+        println!("\nTool resolution:");
+        if explanations.is_empty() {
+            println!("  No dependencies defined");
+        }
+        for explanation in &explanations {
+            let destination = match (&explanation.install_path, &explanation.merge_target) {
+                (Some(path), _) => path.clone(),
+                (None, Some(target)) => format!("merged into {target}"),
+                (None, None) => "not supported by this tool".red().to_string(),
+            };
+            let enabled = if explanation.enabled {
+                String::new()
+            } else {
+                format!(", {}", "disabled".yellow())
+            };
+            println!(
+                "  {} {}: {} ({}{enabled}) → {destination}",
+                explanation.resource_type,
+                explanation.name.cyan(),
+                explanation.tool,
+                explanation.origin,
+            );
+        }
+    }
+
+    ctx.validation_results.tool_resolution = explanations;
+    Ok(())
+}
+
+/// Build the explanations for every dependency in `manifest`, sorted by type and name.
+///
+/// `raw` is the manifest as written, used to detect an explicit `tool` key.
+fn explain_manifest(manifest: &Manifest, raw: &toml::Table) -> Vec<ToolExplanation> {
+    let mut explanations = Vec::new();
+
+    for resource_type in ResourceType::all() {
+        let Some(deps) = manifest.get_dependencies(*resource_type) else {
+            continue;
+        };
+        let section = resource_type.to_plural();
+        let mut names: Vec<_> = deps.keys().collect();
+        names.sort();
+
+        for name in names {
+            let dep = &deps[name];
+            let written_tool = raw
+                .get(section)
+                .and_then(|deps| deps.get(name.as_str()))
+                .and_then(|dep| dep.get("tool"))
+                .is_some();
+            let (tool, origin) = match dep.get_tool() {
+                Some(tool) if written_tool => (tool.to_string(), EXPLICIT),
+                _ if manifest.default_tools.contains_key(section) => {
+                    (manifest.get_default_tool(*resource_type), DEFAULT_TOOLS)
+                }
+                _ => (resource_type.default_tool().to_string(), BUILT_IN),
+            };
+
+            let enabled = manifest.get_tool_config(&tool).is_some_and(|config| config.enabled);
+            let install_path = manifest.get_artifact_resource_path(&tool, *resource_type).map(
+                |dir| match manifest.get_dependency_target(dep, &tool, *resource_type) {
+                    Some(target) => dir.join(target),
+                    None => dir,
+                },
+            );
+            let merge_target = manifest.get_merge_target(&tool, *resource_type);
+
+            explanations.push(ToolExplanation {
+                resource_type: resource_type.to_string(),
+                name: name.clone(),
+                tool,
+                origin: origin.to_string(),
+                enabled,
+                install_path: install_path.map(|path| path.display().to_string()),
+                merge_target: merge_target.map(|path| path.display().to_string()),
+            });
+        }
+    }
+
+    explanations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explain_reports_tool_origin_and_destination() {
+        let content = r#"
+[default-tools]
+snippets = "claude-code"
+
+[agents]
+reviewer = { path = "../agents/reviewer.md" }
+helper = { path = "../agents/helper.md", tool = "opencode" }
+
+[snippets]
+utils = "../snippets/utils.md"
+
+[hooks]
+pre-commit = "../hooks/pre-commit.json"
+"#;
+        let temp = tempfile::TempDir::new().unwrap();
+        let manifest_path = temp.path().join("agpm.toml");
+        std::fs::write(&manifest_path, content).unwrap();
+        let manifest = Manifest::load(&manifest_path).unwrap();
+        let raw: toml::Table = toml::from_str(content).unwrap();
+
+        let explanations = explain_manifest(&manifest, &raw);
+        let find = |name: &str| explanations.iter().find(|e| e.name == name).unwrap();
+
+        let helper = find("helper");
+        assert_eq!((helper.tool.as_str(), helper.origin.as_str()), ("opencode", EXPLICIT));
+        assert_eq!(helper.install_path.as_deref(), Some(".opencode/agent"));
+
+        let reviewer = find("reviewer");
+        assert_eq!((reviewer.tool.as_str(), reviewer.origin.as_str()), ("claude-code", BUILT_IN));
+        assert_eq!(reviewer.install_path.as_deref(), Some(".claude/agents"));
+
+        let utils = find("utils");
+        assert_eq!((utils.tool.as_str(), utils.origin.as_str()), ("claude-code", DEFAULT_TOOLS));
+
+        let hook = find("pre-commit");
+        assert_eq!(hook.install_path, None);
+        assert_eq!(hook.merge_target.as_deref(), Some(".claude/settings.local.json"));
+        assert!(explanations.iter().all(|e| e.enabled));
+    }
+}
@@ -12,17 +12,24 @@ use super::{OutputFormat, ValidationContext};
 ///
 /// This function compares the manifest dependencies with those recorded in the
 /// lockfile to identify inconsistencies. It warns if dependencies are missing
-/// from the lockfile or if extra entries exist.
+/// from the lockfile and fails on orphaned entries (see
+/// [`LockFile::find_orphans`](crate::lockfile::LockFile::find_orphans)).
 ///
 /// # Arguments
 ///
 /// * `ctx` - Validation context containing all necessary parameters
 /// * `project_dir` - Path to the project directory (fallback if not in manifest)
+/// * `prune` - Remove orphaned entries, their installed files, and their merged
+///   hook/MCP server entries instead of failing
 ///
 /// # Returns
 ///
 /// Returns `Ok(())` if the lockfile is consistent or missing, or `Err` if inconsistent.
-pub async fn validate_lockfile(ctx: &mut ValidationContext<'_>, project_dir: &Path) -> Result<()> {
+pub async fn validate_lockfile(
+    ctx: &mut ValidationContext<'_>,
+    project_dir: &Path,
+    prune: bool,
+) -> Result<()> {
     let lockfile_path = project_dir.join("agpm.lock");
 
     if !lockfile_path.exists() {
@@ -42,7 +49,6 @@ pub async fn validate_lockfile(ctx: &mut ValidationContext<'_>, project_dir: &Pa
         Ok(lockfile) => {
             // Check that all manifest dependencies are in lockfile
             let mut missing = Vec::new();
-            let mut extra = Vec::new();
 
             // Check for missing dependencies using unified interface
             for resource_type in &[ResourceType::Agent, ResourceType::Snippet] {
@@ -64,32 +70,53 @@ pub async fn validate_lockfile(ctx: &mut ValidationContext<'_>, project_dir: &Pa
                 }
             }
 
-            // Check for extra dependencies in lockfile
-            for resource_type in &[ResourceType::Agent, ResourceType::Snippet] {
-                let manifest_resources = ctx.manifest.get_resources(resource_type);
-                let lockfile_resources = lockfile.get_resources(resource_type);
-                let type_name = match resource_type {
-                    ResourceType::Agent => "agent",
-                    ResourceType::Snippet => "snippet",
-                    _ => unreachable!(),
-                };
+            // Entries whose manifest dependency was removed and that nothing else requires
+            let orphans: Vec<String> = lockfile
+                .find_orphans(ctx.manifest)
+                .into_iter()
+                .map(|(resource_type, entry)| format!("{} ({resource_type})", entry.display_name()))
+                .collect();
+            ctx.validation_results.orphaned_entries = orphans.clone();
+
+            if !orphans.is_empty() && prune {
+                let pruned = lockfile.without_orphans(ctx.manifest);
+                let removed_files =
+                    crate::installer::cleanup_removed_artifacts(&lockfile, &pruned, project_dir)
+                        .await?;
+                crate::installer::cleanup_removed_merged_entries(
+                    &lockfile,
+                    &pruned,
+                    ctx.manifest,
+                    project_dir,
+                )?;
+                pruned.save(&lockfile_path)?;
 
-                for entry in lockfile_resources {
-                    let manifest_key = entry.manifest_alias.as_ref().unwrap_or(&entry.name);
-                    if !manifest_resources.contains_key(manifest_key) {
-                        extra.push((entry.name.clone(), type_name));
+                ctx.print(&format!(
+                    "{} Pruned {} orphaned lockfile {}:",
+                    "✓".green(),
+                    orphans.len(),
+                    if orphans.len() == 1 {
+                        "entry"
+                    } else {
+                        "entries"
                     }
+                ));
+                for orphan in &orphans {
+                    ctx.print(&format!("  - {orphan}"));
                 }
-            }
-
-            if missing.is_empty() && extra.is_empty() {
-                ctx.validation_results.lockfile_consistent = true;
-                ctx.print("✓ Lockfile consistent");
-            } else if !extra.is_empty() {
+                for path in &removed_files {
+                    ctx.print_verbose(&format!("  Removed {path}"));
+                }
+            } else if !orphans.is_empty() {
                 let error_msg = format!(
-                    "Lockfile inconsistent with manifest: found {}",
-                    // Safe: !extra.is_empty() is checked above, guaranteeing first() returns Some
-                    extra.first().unwrap().0
+                    "Lockfile inconsistent with manifest: found {} orphaned {}: {}",
+                    orphans.len(),
+                    if orphans.len() == 1 {
+                        "entry"
+                    } else {
+                        "entries"
+                    },
+                    orphans.join(", ")
                 );
                 ctx.errors.push(error_msg.clone());
 
@@ -98,11 +125,18 @@ pub async fn validate_lockfile(ctx: &mut ValidationContext<'_>, project_dir: &Pa
                     ctx.validation_results.errors = ctx.errors.clone();
                     ctx.validation_results.warnings = ctx.warnings.to_owned();
                     println!("{}", serde_json::to_string_pretty(&ctx.validation_results)?);
-                    return Err(anyhow::anyhow!("Lockfile inconsistent"));
                 } else {
                     ctx.print(&format!("{} {}", "✗".red(), error_msg));
+                    ctx.print(
+                        "\nRun 'agpm validate --prune' or 'agpm install' to remove orphaned entries",
+                    );
                 }
                 return Err(anyhow::anyhow!("Lockfile inconsistent"));
+            }
+
+            if missing.is_empty() {
+                ctx.validation_results.lockfile_consistent = true;
+                ctx.print("✓ Lockfile consistent");
             } else {
                 ctx.validation_results.lockfile_consistent = false;
                 ctx.print(&format!(
@@ -106,6 +106,13 @@ pub async fn validate_manifest(
         }
     }
 
+    for warning in manifest.project_warnings() {
+        if !quiet && matches!(format, OutputFormat::Text) {
+            println!("⚠ Warning: {warning}");
+        }
+        warnings.push(warning);
+    }
+
     if verbose && !quiet && matches!(format, OutputFormat::Text) {
         println!("\nChecking manifest syntax");
         println!("✓ Manifest Summary:");
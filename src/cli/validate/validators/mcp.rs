@@ -0,0 +1,166 @@
+//! MCP server configuration validation.
+
+use anyhow::{Result, anyhow};
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::cache::Cache;
+use crate::lockfile::{LockFile, LockedResource};
+use crate::manifest::patches::apply_patches_to_content_with_origin;
+use crate::mcp::validation::parse_mcp_server_config;
+
+use super::{OutputFormat, ValidationContext};
+
+/// Validates MCP server configurations without installing them.
+///
+/// Each MCP server JSON file is read from its source (Git worktree or local path),
+/// patched, given the manifest's `transport`/`url` overrides, and checked for the fields its transport requires (`command` for stdio,
+/// `url` for http/sse). When a lockfile exists every locked MCP server is checked;
+/// otherwise only local MCP dependencies from the manifest can be read, and remote
+/// ones are reported as a warning.
+///
+/// # Arguments
+///
+/// * `ctx` - Validation context containing all necessary parameters
+/// * `project_dir` - Path to the project directory
+///
+/// # Returns
+///
+/// Returns `Ok(())` if all MCP server configurations are valid, or `Err` otherwise.
+pub async fn validate_mcp_servers(
+    ctx: &mut ValidationContext<'_>,
+    project_dir: &Path,
+) -> Result<()> {
+    ctx.print_verbose("\n🔍 Validating MCP server configurations...");
+
+    let mut failures = Vec::new();
+    let mut checked = 0;
+
+    let lockfile_path = project_dir.join("agpm.lock");
+    if lockfile_path.exists() {
+        let lockfile = LockFile::load(&lockfile_path)?;
+        let cache = Cache::new()?;
+
+        for entry in &lockfile.mcp_servers {
+            checked += 1;
+            let result = match locked_source_path(entry, project_dir, &cache).await {
+                Ok(source_path) => validate_mcp_file(ctx, entry.lookup_name(), &source_path).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                failures.push(e.to_string());
+            }
+        }
+    } else {
+        let mut remote = 0;
+        for (name, dep) in &ctx.manifest.mcp_servers {
+            if dep.get_source().is_some() || dep.is_pattern() {
+                remote += 1;
+                continue;
+            }
+
+            checked += 1;
+            let source_path = resolve_local_path(project_dir, dep.get_path());
+            if let Err(e) = validate_mcp_file(ctx, name, &source_path).await {
+                failures.push(e.to_string());
+            }
+        }
+
+        if remote > 0 {
+            let warning = format!(
+                "Skipped {remote} remote MCP server(s): lockfile required (run 'agpm install' first)"
+            );
+            ctx.print(&format!("⚠ {warning}"));
+            ctx.warnings.push(warning);
+        }
+    }
+
+    ctx.validation_results.mcp_servers_valid = failures.is_empty();
+
+    if failures.is_empty() {
+        ctx.print(&format!("✓ {checked} MCP server configuration(s) valid"));
+        return Ok(());
+    }
+
+    let error_msg = format!("MCP server validation failed for {} server(s)", failures.len());
+
+    if matches!(ctx.format, OutputFormat::Json) {
+        ctx.errors.extend(failures);
+        ctx.errors.push(error_msg.clone());
+        ctx.validation_results.valid = false;
+        ctx.validation_results.errors = ctx.errors.clone();
+        ctx.validation_results.warnings = ctx.warnings.to_owned();
+        println!("{}", serde_json::to_string_pretty(&ctx.validation_results)?);
+    } else {
+        ctx.print(&format!("{} {}", "✗".red(), error_msg));
+        for failure in &failures {
+            ctx.print(&format!("  {failure}"));
+        }
+        ctx.errors.extend(failures);
+        ctx.errors.push(error_msg.clone());
+    }
+
+    Err(anyhow!("{error_msg}"))
+}
+
+/// Read, patch, and validate a single MCP server file.
+async fn validate_mcp_file(
+    ctx: &ValidationContext<'_>,
+    name: &str,
+    source_path: &Path,
+) -> Result<()> {
+    let content = tokio::fs::read_to_string(source_path).await.map_err(|e| {
+        anyhow!("Invalid MCP server '{name}': failed to read {}: {e}", source_path.display())
+    })?;
+
+    let project_patches = ctx.manifest.project_patches.get("mcp-servers", name);
+    let private_patches = ctx.manifest.private_patches.get("mcp-servers", name);
+    let content = if project_patches.is_some() || private_patches.is_some() {
+        apply_patches_to_content_with_origin(
+            &content,
+            &source_path.display().to_string(),
+            project_patches.unwrap_or(&BTreeMap::new()),
+            private_patches.unwrap_or(&BTreeMap::new()),
+        )?
+        .0
+    } else {
+        content
+    };
+
+    parse_mcp_server_config(&content, source_path, name, ctx.manifest.mcp_servers.get(name))?;
+    ctx.print_verbose(&format!("  ✓ {name}"));
+    Ok(())
+}
+
+/// Locate the source file for a locked MCP server, checking out a worktree if needed.
+async fn locked_source_path(
+    entry: &LockedResource,
+    project_dir: &Path,
+    cache: &Cache,
+) -> Result<PathBuf> {
+    let Some(source_name) = &entry.source else {
+        return Ok(resolve_local_path(project_dir, &entry.path));
+    };
+
+    let url = entry.url.as_ref().ok_or_else(|| anyhow!("MCP server {} has no URL", entry.name))?;
+    match entry.resolved_commit.as_deref().filter(|sha| !sha.is_empty()) {
+        // Local directory source - use URL as path directly
+        None => Ok(PathBuf::from(url).join(&entry.path)),
+        Some(sha) => {
+            let worktree = cache
+                .get_or_create_worktree_for_sha(source_name, url, sha, Some(&entry.name))
+                .await?;
+            Ok(worktree.join(&entry.path))
+        }
+    }
+}
+
+fn resolve_local_path(project_dir: &Path, path: &str) -> PathBuf {
+    let candidate = Path::new(path);
+    if candidate.is_absolute() {
+        candidate.to_path_buf()
+    } else {
+        project_dir.join(candidate)
+    }
+}
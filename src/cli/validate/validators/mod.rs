@@ -63,16 +63,22 @@ impl<'a> ValidationContext<'a> {
 }
 
 pub mod dependencies;
+pub mod explain;
 pub mod lockfile;
 pub mod manifest;
+pub mod mcp;
 pub mod paths;
+pub mod remote;
 pub mod sources;
 pub mod templates;
 
 // Re-export validation functions for convenience
 pub use dependencies::validate_dependencies;
+pub use explain::explain_tools;
 pub use lockfile::validate_lockfile;
 pub use manifest::validate_manifest;
+pub use mcp::validate_mcp_servers;
 pub use paths::validate_paths;
+pub use remote::validate_remote_versions;
 pub use sources::validate_sources;
 pub use templates::validate_templates;
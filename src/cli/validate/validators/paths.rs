@@ -26,7 +26,7 @@ pub async fn validate_paths(ctx: &mut ValidationContext<'_>, manifest_path: &Pat
 
     // Check local dependencies (those without source field)
     for (_name, dep) in ctx.manifest.agents.iter().chain(ctx.manifest.snippets.iter()) {
-        if dep.get_source().is_none() {
+        if dep.get_source().is_none() && dep.get_inline().is_none() {
             // This is a local dependency
             let path = dep.get_path();
             let full_path = if path.starts_with("./") || path.starts_with("../") {
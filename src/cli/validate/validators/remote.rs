@@ -0,0 +1,222 @@
+//! Live checks of dependency versions against their remotes.
+
+use anyhow::{Result, anyhow};
+use colored::Colorize;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::cli::validate::results::RemoteVersionCheck;
+use crate::git::{GitRepo, RemoteRefs};
+use crate::manifest::ResourceDependency;
+use crate::resolver::version_resolver::{find_best_matching_tag, is_version_constraint};
+use crate::source::SourceManager;
+
+use super::{OutputFormat, ValidationContext};
+
+const OK: &str = "ok";
+const MISSING: &str = "missing";
+const UNREACHABLE: &str = "unreachable";
+const NOT_CHECKED: &str = "not-checked";
+
+/// Checks that every remote dependency's version can be satisfied upstream.
+///
+/// Lists the branches and tags of each source used by the manifest with
+/// `git ls-remote`, without cloning or touching the cache, and checks per
+/// dependency that its branch or tag still exists, or that some tag satisfies
+/// its version constraint. Commits that no branch or tag points to, and
+/// `as_of` dates, cannot be confirmed this way and are reported as not
+/// checked. With `offline`, no source is contacted and every dependency is
+/// reported as not checked.
+///
+/// # Arguments
+///
+/// * `ctx` - Validation context containing all necessary parameters
+/// * `offline` - Skip contacting sources
+///
+/// # Returns
+///
+/// Returns `Ok(())` unless a source is unreachable or a version is missing.
+pub async fn validate_remote_versions(
+    ctx: &mut ValidationContext<'_>,
+    offline: bool,
+) -> Result<()> {
+    ctx.print_verbose("\n🌐 Checking dependency versions against their remotes...");
+
+    let deps: Vec<_> = ctx
+        .manifest
+        .all_dependencies_with_types()
+        .into_iter()
+        .filter_map(|(name, dep, resource_type)| {
+            let source = dep.get_source()?.to_string();
+            Some((resource_type, name.to_string(), source, dep.into_owned()))
+        })
+        .collect();
+
+    let mut remotes: BTreeMap<String, Result<RemoteRefs, String>> = BTreeMap::new();
+    if !offline {
+        let manager = SourceManager::from_manifest_with_global(ctx.manifest).await?;
+        let sources: BTreeSet<&String> = deps.iter().map(|(_, _, source, _)| source).collect();
+        let listings = futures::future::join_all(sources.into_iter().map(|source| {
+            let url = manager.get_source_url(source);
+            async move {
+                let refs = match url {
+                    Some(url) => {
+                        GitRepo::list_remote_refs(&url).await.map_err(|e| format!("{e:#}"))
+                    }
+                    None => Err(format!("source '{source}' is not defined")),
+                };
+                (source.clone(), refs)
+            }
+        }))
+        .await;
+        remotes.extend(listings);
+    }
+
+    let checks: Vec<RemoteVersionCheck> = deps
+        .iter()
+        .map(|(resource_type, name, source, dep)| {
+            let (status, detail) = match remotes.get(source) {
+                None => (NOT_CHECKED, "offline".to_string()),
+                Some(Err(e)) => (UNREACHABLE, e.clone()),
+                Some(Ok(refs)) => check_version(dep, refs),
+            };
+            RemoteVersionCheck {
+                resource_type: resource_type.to_string(),
+                name: name.clone(),
+                source: source.clone(),
+                version: dep.get_version().unwrap_or("HEAD").to_string(),
+                status: status.to_string(),
+                detail,
+            }
+        })
+        .collect();
+
+    if matches!(ctx.format, OutputFormat::Text) && !ctx.quiet {
+        println!("\nRemote versions:");
+        if checks.is_empty() {
+            println!("  No remote dependencies defined");
+        }
+        for check in &checks {
+            let marker = match check.status.as_str() {
+                OK => "✓".green(),
+                NOT_CHECKED => "-".dimmed(),
+                _ => "✗".red(),
+            };
+            let detail = match check.status.as_str() {
+                OK => format!("→ {}", check.detail),
+                NOT_CHECKED => format!("not checked ({})", check.detail),
+                _ => check.detail.clone(),
+            };
+            println!(
+                "  {marker} {} {} ({} @ {}): {detail}",
+                check.resource_type,
+                check.name.cyan(),
+                check.source,
+                check.version
+            );
+        }
+    }
+
+    let failures: Vec<String> = checks
+        .iter()
+        .filter(|check| check.status == MISSING || check.status == UNREACHABLE)
+        .map(|check| {
+            format!(
+                "{} '{}' ({} @ {}): {}",
+                check.resource_type, check.name, check.source, check.version, check.detail
+            )
+        })
+        .collect();
+    ctx.validation_results.remote_versions = checks;
+
+    if failures.is_empty() {
+        ctx.validation_results.dependencies_resolvable = true;
+        if !offline {
+            ctx.print("✓ Dependency versions available upstream");
+        }
+        return Ok(());
+    }
+
+    let error_msg =
+        format!("{} dependency version(s) cannot be satisfied by their remotes", failures.len());
+    ctx.errors.extend(failures);
+    ctx.errors.push(error_msg.clone());
+    if matches!(ctx.format, OutputFormat::Json) {
+        ctx.validation_results.valid = false;
+        ctx.validation_results.errors = ctx.errors.clone();
+        ctx.validation_results.warnings = ctx.warnings.to_owned();
+        println!("{}", serde_json::to_string_pretty(&ctx.validation_results)?);
+    } else {
+        ctx.print(&format!("{} {}", "✗".red(), error_msg));
+    }
+    Err(anyhow!("{error_msg}"))
+}
+
+/// Check one dependency's version against the refs its remote advertises.
+///
+/// Returns the status and either the matching ref or the reason.
+fn check_version(dep: &ResourceDependency, refs: &RemoteRefs) -> (&'static str, String) {
+    if dep.get_as_of().is_some() {
+        return (NOT_CHECKED, "as_of needs the commit history".to_string());
+    }
+
+    let version = match dep.get_version() {
+        None | Some("HEAD") => {
+            return if refs.branches.is_empty() {
+                (MISSING, "remote has no branches".to_string())
+            } else {
+                (OK, "default branch".to_string())
+            };
+        }
+        Some(version) => version,
+    };
+
+    if is_version_constraint(version) {
+        return match find_best_matching_tag(version, refs.tags.clone()) {
+            Ok(tag) => (OK, format!("tag {tag}")),
+            Err(_) => (MISSING, format!("no tag satisfies '{version}'")),
+        };
+    }
+    if refs.tags.iter().any(|tag| tag == version) {
+        return (OK, format!("tag {version}"));
+    }
+    if refs.branches.iter().any(|branch| branch == version) {
+        return (OK, format!("branch {version}"));
+    }
+    if version.len() >= 7 && version.len() <= 40 && version.chars().all(|c| c.is_ascii_hexdigit()) {
+        return if refs.has_commit(version) {
+            (OK, format!("commit {version}"))
+        } else {
+            (NOT_CHECKED, "commit is not the tip of a branch or tag".to_string())
+        };
+    }
+    (MISSING, format!("no branch or tag named '{version}'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dep(version: &str) -> ResourceDependency {
+        toml::from_str(&format!(
+            "source = \"community\"\npath = \"agents/a.md\"\nversion = \"{version}\""
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_check_version() {
+        let refs = RemoteRefs::parse(
+            "1111111111111111111111111111111111111111\trefs/heads/main\n\
+             2222222222222222222222222222222222222222\trefs/tags/v1.2.0\n\
+             3333333333333333333333333333333333333333\trefs/tags/v1.2.0^{}\n",
+        );
+
+        assert_eq!(check_version(&dep("main"), &refs), (OK, "branch main".to_string()));
+        assert_eq!(check_version(&dep("v1.2.0"), &refs), (OK, "tag v1.2.0".to_string()));
+        assert_eq!(check_version(&dep("^1.0.0"), &refs), (OK, "tag v1.2.0".to_string()));
+        assert_eq!(check_version(&dep("3333333"), &refs).0, OK);
+        assert_eq!(check_version(&dep("v1.1.0"), &refs).0, MISSING);
+        assert_eq!(check_version(&dep("^2.0.0"), &refs).0, MISSING);
+        assert_eq!(check_version(&dep("abcdef1"), &refs).0, NOT_CHECKED);
+    }
+}
@@ -81,7 +81,9 @@ pub async fn validate_templates(ctx: &mut ValidationContext<'_>, project_dir: &P
     macro_rules! validate_resource_template {
         ($name:expr, $entry:expr, $resource_type:expr) => {{
             // Read the resource content
-            let content = if $entry.source.is_some() && $entry.resolved_commit.is_some() {
+            let content = if let Some(inline) = &$entry.inline {
+                inline.clone()
+            } else if $entry.source.is_some() && $entry.resolved_commit.is_some() {
                 // Git resource - read from worktree
                 // Safe: checked is_some() above in the if condition
                 let source_name = $entry.source.as_ref().unwrap();
@@ -197,7 +199,7 @@ pub async fn validate_templates(ctx: &mut ValidationContext<'_>, project_dir: &P
                 resource_name: $entry.name.clone(),
                 resource_type: $resource_type,
                 dependency_chain: vec![], // Could be enhanced to include parent info
-                source_path: Some($entry.path.clone().into()),
+                source_path: (!$entry.path.is_empty()).then(|| $entry.path.clone().into()),
                 depth: 0,
             };
 
@@ -277,7 +279,9 @@ pub async fn validate_templates(ctx: &mut ValidationContext<'_>, project_dir: &P
     macro_rules! validate_file_references_in_resource {
         ($name:expr, $entry:expr) => {{
             // Read the resource content
-            let content = if $entry.source.is_some() && $entry.resolved_commit.is_some() {
+            let content = if let Some(inline) = &$entry.inline {
+                inline.clone()
+            } else if $entry.source.is_some() && $entry.resolved_commit.is_some() {
                 // Git resource - read from worktree
                 // Safe: checked is_some() above in the if condition
                 let source_name = $entry.source.as_ref().unwrap();
@@ -122,11 +122,12 @@
 //! # }
 //! ```
 
+use super::GlobalSourceMap;
 use crate::core::file_error::LARGE_FILE_SIZE;
 use crate::upgrade::config::UpgradeConfig;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 use tokio::fs;
 
@@ -202,8 +203,10 @@ pub struct GlobalConfig {
     /// - URLs with credentials are **never** logged in plain text
     /// - The `sources` field is skipped during serialization if empty
     /// - Authentication details should use tokens rather than passwords when possible
-    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
-    pub sources: HashMap<String, String>,
+    /// - `{ url = "...", token = "${GITHUB_TOKEN}" }` reads the token from the
+    ///   environment when sources are merged, so it is never stored in the file
+    #[serde(default, skip_serializing_if = "GlobalSourceMap::is_empty")]
+    pub sources: GlobalSourceMap,
 
     /// Upgrade configuration settings.
     ///
@@ -230,6 +233,131 @@ pub struct GlobalConfig {
         skip_serializing_if = "is_default_max_content_file_size"
     )]
     pub max_content_file_size: u64,
+
+    /// Path to the `git` executable.
+    ///
+    /// Default: `git` from `PATH`. The `AGPM_GIT_BINARY` environment variable
+    /// takes precedence over this setting.
+    ///
+    /// # Configuration
+    ///
+    /// Set in `~/.agpm/config.toml`:
+    /// ```toml
+    /// git_binary = "/opt/git/bin/git"
+    /// ```
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_binary: Option<String>,
+
+    /// Extra arguments passed to every Git invocation, before the subcommand.
+    ///
+    /// Useful for settings that must apply to every clone and fetch, such as a
+    /// custom CA bundle. The `AGPM_GIT_ARGS` environment variable (split on
+    /// whitespace) takes precedence over this setting.
+    ///
+    /// # Configuration
+    ///
+    /// Set in `~/.agpm/config.toml`:
+    /// ```toml
+    /// git_args = ["-c", "http.sslCAInfo=/etc/ca.pem"]
+    /// ```
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub git_args: Vec<String>,
+
+    /// HTTP(S) proxy for cloning and fetching sources.
+    ///
+    /// Passed to Git as `-c http.proxy=...` for HTTP(S) sources only; SSH and
+    /// `file://` sources are unaffected. When unset, the `https_proxy` and
+    /// `http_proxy` environment variables are used. Keep proxy credentials in
+    /// the environment rather than in this file.
+    ///
+    /// # Configuration
+    ///
+    /// Set in `~/.agpm/config.toml`:
+    /// ```toml
+    /// proxy = "http://proxy.corp:8080"
+    /// no_proxy = "localhost,.internal.corp"
+    /// ```
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+
+    /// Comma-separated hosts (or `.domain` suffixes) reached without the proxy.
+    ///
+    /// Falls back to the `no_proxy` environment variable when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub no_proxy: Option<String>,
+
+    /// Timeout in seconds for network Git operations (clone, fetch, ls-remote).
+    ///
+    /// A Git process that runs longer is killed and the operation fails with
+    /// an error naming the source. Operations on local repositories are not
+    /// limited. The global `--timeout` option takes precedence. Default: 300
+    /// seconds per Git command.
+    ///
+    /// # Configuration
+    ///
+    /// Set in `~/.agpm/config.toml`:
+    /// ```toml
+    /// network_timeout = 60
+    /// ```
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network_timeout: Option<u64>,
+
+    /// Skip dependencies for tools that are not in use on this machine.
+    ///
+    /// When enabled, `agpm install` skips a tool's dependencies unless its
+    /// directory (e.g. `.claude`, `.opencode`) exists in the project or its
+    /// command (`claude`, `opencode`) is on `PATH`. The lockfile still records
+    /// them. `agpm install --tools` overrides detection.
+    ///
+    /// # Configuration
+    ///
+    /// Set in `~/.agpm/config.toml`:
+    /// ```toml
+    /// auto_detect_tools = true
+    ///
+    /// [tool_commands]
+    /// my-tool = "my-tool-cli"  # command that detects a custom tool
+    /// ```
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub auto_detect_tools: bool,
+
+    /// Command looked up on `PATH` to detect each tool when
+    /// `auto_detect_tools` is enabled, overriding the built-in command.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tool_commands: BTreeMap<String, String>,
+
+    /// Hardlink installed files whose content is identical.
+    ///
+    /// When enabled, a file with the same content as one already installed in
+    /// the same run is hardlinked to it instead of written again. Each path is
+    /// still recorded separately in the lockfile. Falls back to copying on
+    /// Windows and across filesystems. Editing a linked file in place changes
+    /// every path it is linked to.
+    ///
+    /// # Configuration
+    ///
+    /// Set in `~/.agpm/config.toml`:
+    /// ```toml
+    /// dedup = true
+    /// ```
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dedup: bool,
+
+    /// Maximum number of resources written to disk at once during install.
+    ///
+    /// Default: one per CPU core. Independent of `--max-parallel`, which
+    /// bounds Git operations. Lower it on slow shared or network filesystems.
+    /// The `--io-parallel` option of `agpm install` and `agpm update` takes
+    /// precedence.
+    ///
+    /// # Configuration
+    ///
+    /// Set in `~/.agpm/config.toml`:
+    /// ```toml
+    /// io_parallel = 2
+    /// ```
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub io_parallel: Option<usize>,
 }
 
 fn is_default_max_content_file_size(size: &u64) -> bool {
@@ -417,6 +545,20 @@ impl GlobalConfig {
     /// - The file cannot be written
     /// - Serialization to TOML fails
     pub async fn save_to(&self, path: &Path) -> Result<()> {
+        let content = toml::to_string_pretty(self).context("Failed to serialize global config")?;
+        Self::write_file(path, &content).await
+    }
+
+    /// Write `content` to the configuration file at `path`, readable only by its owner.
+    ///
+    /// Used by [`save_to`](Self::save_to) and by `agpm config set`, which edits
+    /// the file in place to keep its comments.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or file cannot be written or its
+    /// permissions set.
+    pub async fn write_file(path: &Path, content: &str) -> Result<()> {
         // Ensure parent directory exists
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent).await.with_context(|| {
@@ -424,8 +566,6 @@ impl GlobalConfig {
             })?;
         }
 
-        let content = toml::to_string_pretty(self).context("Failed to serialize global config")?;
-
         fs::write(path, content)
             .await
             .with_context(|| format!("Failed to write global config to {}", path.display()))?;
@@ -506,6 +646,10 @@ impl GlobalConfig {
     /// 2. Add/override with local sources from project manifest
     /// 3. Local sources win in case of name conflicts
     ///
+    /// Global sources with a `token` setting have it expanded from the
+    /// environment and inserted into their URL. Sources overridden by the
+    /// project are not expanded.
+    ///
     /// # Parameters
     ///
     /// - `local_sources`: Sources from project manifest (`agpm.toml`)
@@ -514,6 +658,11 @@ impl GlobalConfig {
     ///
     /// Combined source map with local sources taking precedence.
     ///
+    /// # Errors
+    ///
+    /// Returns an error naming the source and variable if a global source's
+    /// token references an unset environment variable.
+    ///
     /// # Examples
     ///
     /// ```rust,no_run
@@ -532,7 +681,7 @@ impl GlobalConfig {
     ///     "https://github.com/public/repo.git".to_string()
     /// );
     ///
-    /// let merged = global.merge_sources(&local);
+    /// let merged = global.merge_sources(&local).unwrap();
     /// assert_eq!(merged.len(), 2);
     /// assert!(merged.contains_key("private"));
     /// assert!(merged.contains_key("public"));
@@ -542,19 +691,23 @@ impl GlobalConfig {
     ///
     /// The merged result may contain authentication credentials from global sources.
     /// Handle with care and never log or expose in version control.
-    #[must_use]
     pub fn merge_sources(
         &self,
         local_sources: &HashMap<String, String>,
-    ) -> HashMap<String, String> {
-        let mut merged = self.sources.clone();
+    ) -> Result<HashMap<String, String>> {
+        let mut merged = HashMap::new();
+        for name in self.sources.keys().filter(|name| !local_sources.contains_key(*name)) {
+            if let Some(url) = self.sources.resolved_url(name)? {
+                merged.insert(name.clone(), url);
+            }
+        }
 
         // Local sources override global ones
         for (name, url) in local_sources {
             merged.insert(name.clone(), url.clone());
         }
 
-        merged
+        Ok(merged)
     }
 
     /// Add or update a source in the global configuration.
@@ -593,6 +746,7 @@ impl GlobalConfig {
     ///
     /// URLs containing credentials should use tokens rather than passwords when possible.
     pub fn add_source(&mut self, name: String, url: String) {
+        self.sources.set_token(&name, None);
         self.sources.insert(name, url);
     }
 
@@ -725,9 +879,25 @@ impl GlobalConfig {
         );
 
         Self {
-            sources,
+            sources: sources.into(),
             upgrade: UpgradeConfig::default(),
             max_content_file_size: default_max_content_file_size(),
+            ..Self::default()
+        }
+    }
+
+    /// Git executable, extra arguments and proxy, with environment fallbacks applied.
+    ///
+    /// See [`GitSettings`](crate::git::command_builder::GitSettings) for the
+    /// precedence rules.
+    #[must_use]
+    pub fn git_settings(&self) -> crate::git::command_builder::GitSettings {
+        use crate::git::command_builder::{GitSettings, ProxySettings};
+
+        GitSettings {
+            proxy: ProxySettings::resolve(self.proxy.as_deref(), self.no_proxy.as_deref()),
+            network_timeout: self.network_timeout.map(std::time::Duration::from_secs),
+            ..GitSettings::resolve(self.git_binary.as_deref(), &self.git_args)
         }
     }
 }
@@ -1039,7 +1209,7 @@ mod tests {
         local.insert("shared".to_string(), "https://override.com/repo.git".to_string());
         local.insert("public".to_string(), "https://public.com/repo.git".to_string());
 
-        let merged = global.merge_sources(&local);
+        let merged = global.merge_sources(&local).unwrap();
 
         // Global source preserved
         assert_eq!(merged.get("private"), Some(&"https://token@private.com/repo.git".to_string()));
@@ -171,7 +171,7 @@
 //! );
 //!
 //! // Merge with global sources (may include auth tokens)
-//! let merged = global.merge_sources(&local_sources);
+//! let merged = global.merge_sources(&local_sources)?;
 //!
 //! // Use merged sources for git operations
 //! for (name, url) in &merged {
@@ -183,9 +183,11 @@
 //! ```
 
 mod global;
+mod global_sources;
 mod parser;
 
 pub use global::{GlobalConfig, GlobalConfigManager};
+pub use global_sources::GlobalSourceMap;
 pub use parser::parse_config;
 
 use crate::core::file_error::{FileOperation, FileResultExt};
@@ -114,6 +114,7 @@ use thiserror::Error;
 /// ## Git Operations
 /// - [`GitNotFound`] - Git executable not available
 /// - [`GitCommandError`] - Git command execution failed
+/// - [`GitTimeout`] - Network Git operation exceeded its timeout
 /// - [`GitAuthenticationFailed`] - Git authentication problems
 /// - [`GitCloneFailed`] - Repository cloning failed
 /// - [`GitCheckoutFailed`] - Git checkout operation failed
@@ -145,6 +146,7 @@ use thiserror::Error;
 /// ## Dependency Resolution
 /// - [`CircularDependency`] - Dependency cycle detected
 /// - [`DependencyResolutionFailed`] - Cannot resolve dependencies
+/// - [`ResolutionConflict`] - Dependencies require incompatible versions of a resource
 /// - [`DependencyNotMet`] - Version constraint not satisfied
 /// - [`InvalidDependency`] - Malformed dependency specification
 /// - [`InvalidVersionConstraint`] - Invalid version format
@@ -210,6 +212,7 @@ use thiserror::Error;
 ///
 /// [`GitNotFound`]: AgpmError::GitNotFound
 /// [`GitCommandError`]: AgpmError::GitCommandError
+/// [`GitTimeout`]: AgpmError::GitTimeout
 /// [`GitAuthenticationFailed`]: AgpmError::GitAuthenticationFailed
 /// [`GitCloneFailed`]: AgpmError::GitCloneFailed
 /// [`GitCheckoutFailed`]: AgpmError::GitCheckoutFailed
@@ -233,6 +236,7 @@ use thiserror::Error;
 /// [`AlreadyInstalled`]: AgpmError::AlreadyInstalled
 /// [`CircularDependency`]: AgpmError::CircularDependency
 /// [`DependencyResolutionFailed`]: AgpmError::DependencyResolutionFailed
+/// [`ResolutionConflict`]: AgpmError::ResolutionConflict
 /// [`DependencyNotMet`]: AgpmError::DependencyNotMet
 /// [`InvalidDependency`]: AgpmError::InvalidDependency
 /// [`InvalidVersionConstraint`]: AgpmError::InvalidVersionConstraint
@@ -262,6 +266,25 @@ pub enum AgpmError {
         stderr: String,
     },
 
+    /// Git operation timed out and was cancelled
+    ///
+    /// This error occurs when a network Git command (clone, fetch, ls-remote)
+    /// runs longer than the configured timeout. The Git process is killed.
+    ///
+    /// # Fields
+    /// - `operation`: The git operation that timed out (e.g., "clone", "fetch")
+    /// - `target`: The repository URL or directory the operation ran against
+    /// - `seconds`: The timeout that was exceeded
+    #[error("Git {operation} of {target} timed out after {seconds}s")]
+    GitTimeout {
+        /// The git operation that timed out (e.g., "clone", "fetch")
+        operation: String,
+        /// The repository URL or directory the operation ran against
+        target: String,
+        /// The timeout that was exceeded, in seconds
+        seconds: u64,
+    },
+
     /// Git executable not found in PATH
     ///
     /// This error occurs when AGPM cannot locate the `git` command in the system PATH.
@@ -466,6 +489,21 @@ pub enum AgpmError {
         reason: String,
     },
 
+    /// Dependencies require incompatible versions of the same resource
+    ///
+    /// Raised by the resolver's conflict detection when a dependency is
+    /// required at versions that resolve to different commits and automatic
+    /// resolution could not reconcile them. Each conflict names the
+    /// dependency and every party requiring it, with the chain of
+    /// dependencies that introduced it.
+    #[error("{summary}:\n\n{}", format_conflicts(.conflicts))]
+    ResolutionConflict {
+        /// What failed, such as "Version conflicts detected"
+        summary: String,
+        /// The conflicting dependencies
+        conflicts: Vec<DependencyConflict>,
+    },
+
     /// Network error
     #[error("Network error: {operation}")]
     NetworkError {
@@ -578,6 +616,88 @@ pub enum AgpmError {
     },
 }
 
+/// A dependency required at incompatible versions.
+///
+/// Reported by [`AgpmError::ResolutionConflict`], and serialized as is in the
+/// `--json-errors` output.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct DependencyConflict {
+    /// Type of the conflicting dependency
+    pub resource_type: crate::core::ResourceType,
+    /// Name of the dependency; its path in the source for transitive dependencies
+    pub dependency: String,
+    /// Source repository the dependency comes from
+    pub source: Option<String>,
+    /// The requirements that resolve to different commits
+    pub parties: Vec<ConflictParty>,
+}
+
+/// One requirement in a [`DependencyConflict`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct ConflictParty {
+    /// Version, branch or commit the dependency is required at
+    pub version: String,
+    /// Commit the requirement resolved to
+    pub resolved_commit: String,
+    /// Dependencies from the manifest down to the one that requires this
+    /// version, outermost first; empty when the manifest requires it directly
+    pub chain: Vec<String>,
+}
+
+impl DependencyConflict {
+    /// Suggest an `[override]` entry pinning the dependency to one version.
+    #[must_use]
+    pub fn override_suggestion(&self) -> String {
+        let mut versions: Vec<&str> =
+            self.parties.iter().map(|party| party.version.as_str()).collect();
+        versions.sort_unstable();
+        versions.dedup();
+        let mut suggestion = format!(
+            "Pin '{}' to one version with an [override] entry in agpm.toml:\n\n[override.{}]\n\"{}\" = \"{}\"",
+            self.dependency,
+            self.resource_type.to_plural(),
+            self.dependency,
+            versions.first().copied().unwrap_or_default()
+        );
+        if versions.len() > 1 {
+            let others: Vec<String> =
+                versions[1..].iter().map(|version| format!("\"{version}\"")).collect();
+            suggestion.push_str(&format!("  # or {}", others.join(", ")));
+        }
+        if self.parties.iter().any(|party| party.chain.is_empty()) {
+            suggestion.push_str(
+                "\n\nOverrides only apply to transitive dependencies; a version required \
+                 directly by agpm.toml must be changed there",
+            );
+        }
+        suggestion
+    }
+}
+
+impl fmt::Display for DependencyConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} '{}'", self.resource_type, self.dependency)?;
+        if let Some(source) = &self.source {
+            write!(f, " from '{source}'")?;
+        }
+        writeln!(f, " is required at {} different versions:", self.parties.len())?;
+        for party in &self.parties {
+            let commit = &party.resolved_commit[..8.min(party.resolved_commit.len())];
+            let required_by = if party.chain.is_empty() {
+                "agpm.toml".to_string()
+            } else {
+                party.chain.join(" → ")
+            };
+            writeln!(f, "  - {} ({commit}) required by {required_by}", party.version)?;
+        }
+        Ok(())
+    }
+}
+
+fn format_conflicts(conflicts: &[DependencyConflict]) -> String {
+    conflicts.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n")
+}
+
 /// Error context wrapper that provides user-friendly error information
 ///
 /// `ErrorContext` wraps a [`AgpmError`] and adds optional user-friendly messages,
@@ -745,6 +865,30 @@ impl ErrorContext {
     }
 }
 
+impl ErrorContext {
+    /// Render the error as a JSON object for `--json-errors`.
+    ///
+    /// The object has `error`, `details` and `suggestion` keys, plus
+    /// `conflicts` listing every conflicting dependency and the parties that
+    /// require it for a [`AgpmError::ResolutionConflict`].
+    #[must_use]
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut value = serde_json::json!({
+            "error": self.error.to_string(),
+            "details": self.details,
+            "suggestion": self.suggestion,
+        });
+        if let AgpmError::ResolutionConflict {
+            conflicts,
+            ..
+        } = &self.error
+        {
+            value["conflicts"] = serde_json::json!(conflicts);
+        }
+        value
+    }
+}
+
 impl fmt::Display for ErrorContext {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.error)?;
@@ -1034,4 +1178,39 @@ mod tests {
         assert!(suggestion.contains("https://example.com/repo.git"));
         assert!(ctx.details.is_some());
     }
+
+    #[test]
+    fn test_create_error_context_resolution_conflict() {
+        let party = |version: &str, commit: &str, chain: &[&str]| ConflictParty {
+            version: version.to_string(),
+            resolved_commit: commit.to_string(),
+            chain: chain.iter().map(ToString::to_string).collect(),
+        };
+        let ctx = create_error_context(&AgpmError::ResolutionConflict {
+            summary: "Version conflicts detected".to_string(),
+            conflicts: vec![DependencyConflict {
+                resource_type: crate::core::ResourceType::Snippet,
+                dependency: "snippets/helper".to_string(),
+                source: Some("community".to_string()),
+                parties: vec![
+                    party("v1.0.0", "aaaaaaaaaaaa", &["agent:agents/a"]),
+                    party("v2.0.0", "bbbbbbbbbbbb", &["agent:agents/b", "agent:agents/c"]),
+                ],
+            }],
+        });
+
+        let message = ctx.error.to_string();
+        assert!(message.starts_with("Version conflicts detected:"));
+        assert!(message.contains("snippet 'snippets/helper' from 'community'"));
+        assert!(message.contains("v1.0.0 (aaaaaaaa) required by agent:agents/a"));
+        assert!(message.contains("required by agent:agents/b → agent:agents/c"));
+
+        let suggestion = ctx.suggestion.as_deref().unwrap();
+        assert!(suggestion.contains("[override.snippets]\n\"snippets/helper\" = \"v1.0.0\""));
+        assert!(suggestion.contains("# or \"v2.0.0\""));
+
+        let json = ctx.to_json();
+        assert_eq!(json["conflicts"][0]["dependency"], "snippets/helper");
+        assert_eq!(json["conflicts"][0]["parties"][1]["chain"][1], "agent:agents/c");
+    }
 }
@@ -232,6 +232,23 @@ pub fn create_error_context(error: &AgpmError) -> ErrorContext {
             .with_suggestion(suggestion)
             .with_details(format!("Git {} operation failed: {}", operation, stderr))
         }
+        AgpmError::GitTimeout {
+            operation,
+            target,
+            seconds,
+        } => ErrorContext::new(AgpmError::GitTimeout {
+            operation: operation.clone(),
+            target: target.clone(),
+            seconds: *seconds,
+        })
+        .with_suggestion(
+            "Check that the host is reachable, or raise the limit with --timeout or \
+             network_timeout in ~/.agpm/config.toml",
+        )
+        .with_details(
+            "The Git process was stopped; an unresponsive host or a credential prompt \
+             waiting for input are the usual causes",
+        ),
         AgpmError::GitCloneFailed {
             url,
             reason,
@@ -287,6 +304,20 @@ pub fn create_error_context(error: &AgpmError) -> ErrorContext {
         })
         .with_suggestion("Check file permissions and try running with appropriate privileges")
         .with_details(format!("Permission denied for '{}' on path: {}", operation, path)),
+        AgpmError::ResolutionConflict {
+            summary,
+            conflicts,
+        } => {
+            let suggestion = conflicts.first().map_or_else(
+                || "Manually specify compatible versions in agpm.toml".to_string(),
+                DependencyConflict::override_suggestion,
+            );
+            ErrorContext::new(AgpmError::ResolutionConflict {
+                summary: summary.clone(),
+                conflicts: conflicts.clone(),
+            })
+            .with_suggestion(suggestion)
+        }
         // Default fallback for unhandled error types
         _ => ErrorContext::new(AgpmError::Other {
             message: error.to_string(),
@@ -488,7 +488,7 @@ This is a test agent for reading.
 
         // Test loading non-existent lockfile (should create new)
         let lockfile = LockfileOps::load_lockfile_with_context(&lockfile_path).unwrap();
-        assert_eq!(lockfile.version, 1);
+        assert_eq!(lockfile.version, 2);
         assert!(lockfile.sources.is_empty());
 
         // Create a valid lockfile
@@ -503,9 +503,9 @@ fetched_at = "2024-01-01T00:00:00Z"
 "#;
         fs::write(&lockfile_path, lockfile_content).unwrap();
 
-        // Test loading existing lockfile
+        // Test loading existing lockfile (migrated from version 1)
         let loaded_lockfile = LockfileOps::load_lockfile_with_context(&lockfile_path).unwrap();
-        assert_eq!(loaded_lockfile.version, 1);
+        assert_eq!(loaded_lockfile.version, 2);
         assert!(!loaded_lockfile.sources.is_empty());
 
         // Test error case - invalid lockfile format
@@ -534,7 +534,7 @@ fetched_at = "2024-01-01T00:00:00Z"
         // Verify the saved content
         let content = fs::read_to_string(&lockfile_path).unwrap();
         assert!(content.contains("Auto-generated lockfile"));
-        assert!(content.contains("version = 1"));
+        assert!(content.contains("version = 2"));
 
         // Verify it can be loaded back
         let loaded_lockfile = LockfileOps::load_lockfile_with_context(&lockfile_path).unwrap();
@@ -225,7 +225,9 @@ pub mod operation_context;
 mod resource;
 pub mod resource_iterator;
 
-pub use error::{AgpmError, ErrorContext, IntoAnyhowWithContext};
+pub use error::{
+    AgpmError, ConflictParty, DependencyConflict, ErrorContext, IntoAnyhowWithContext,
+};
 pub use error_builders::{
     ErrorContextExt, file_error_context, git_error_context, manifest_error_context,
 };
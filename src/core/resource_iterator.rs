@@ -397,6 +397,8 @@ mod tests {
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
+            inline: None,
         });
 
         lockfile.snippets.push(LockedResource {
@@ -416,6 +418,8 @@ mod tests {
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
+            inline: None,
         });
 
         lockfile
@@ -446,6 +450,8 @@ mod tests {
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
+            inline: None,
         });
 
         lockfile.agents.push(LockedResource {
@@ -465,6 +471,8 @@ mod tests {
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
+            inline: None,
         });
 
         // Add commands from source1
@@ -485,6 +493,8 @@ mod tests {
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
+            inline: None,
         });
 
         // Add scripts
@@ -505,6 +515,8 @@ mod tests {
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
+            inline: None,
         });
 
         // Add hooks
@@ -525,6 +537,8 @@ mod tests {
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
+            inline: None,
         });
 
         // Add MCP servers
@@ -545,6 +559,8 @@ mod tests {
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
+            inline: None,
         });
 
         // Add resource without source
@@ -565,6 +581,8 @@ mod tests {
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
+            inline: None,
         });
 
         lockfile
@@ -611,6 +629,8 @@ mod tests {
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
+            inline: None,
         });
 
         // Verify the agent was added
@@ -1066,6 +1086,8 @@ mod tests {
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
+            inline: None,
         });
 
         let groups = ResourceIterator::group_by_source(&lockfile);
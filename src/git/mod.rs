@@ -427,7 +427,11 @@ impl GitRepo {
         }
 
         // Execute will handle error context properly
-        cmd.execute().await?;
+        let existed = target_path.exists();
+        if let Err(e) = cmd.execute().await {
+            remove_partial_clone(target_path, existed).await;
+            return Err(e);
+        }
 
         Ok(Self::new(target_path))
     }
@@ -504,11 +508,59 @@ impl GitRepo {
         }
 
         // Now fetch with the potentially updated URL
-        GitCommand::fetch().current_dir(&self.path).execute_success().await?;
+        self.fetch_command(auth_url).await.execute_success().await?;
 
         Ok(())
     }
 
+    /// Fetch only the given refs instead of every branch and tag.
+    ///
+    /// Commit SHAs are fetched directly. Any other name is fetched as both a
+    /// tag and a branch pattern, so a name that is only one of the two, or that
+    /// does not exist at all, does not fail the fetch. Remotes that refuse to
+    /// serve unadvertised commits make SHA fetches fail, in which case callers
+    /// should fall back to [`fetch`](Self::fetch).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the fetch fails.
+    pub async fn fetch_refs(&self, auth_url: Option<&str>, refs: &[String]) -> Result<()> {
+        if let Some(url) = auth_url {
+            GitCommand::set_remote_url(url).current_dir(&self.path).execute_success().await?;
+        }
+
+        let cmd = GitCommand::fetch_refspecs(&fetch_refspecs(refs)).current_dir(&self.path);
+        self.route_fetch(cmd, auth_url).await.execute_success().await?;
+        Ok(())
+    }
+
+    /// Build a fetch command, routed through the configured proxy if needed.
+    ///
+    /// The remote URL is only looked up when a proxy or network timeout is
+    /// configured, since it decides whether the proxy applies (see
+    /// [`ProxySettings`](command_builder::ProxySettings)) and whether the
+    /// remote is local and therefore exempt from the timeout.
+    async fn fetch_command(&self, url: Option<&str>) -> GitCommand {
+        self.route_fetch(GitCommand::fetch().current_dir(&self.path), url).await
+    }
+
+    /// Apply the proxy and network timeout of `url`'s remote to a fetch command.
+    async fn route_fetch(&self, cmd: GitCommand, url: Option<&str>) -> GitCommand {
+        let settings = command_builder::git_settings();
+        if !settings.proxy.is_enabled() && settings.network_timeout.is_none() {
+            return cmd;
+        }
+
+        let url = match url {
+            Some(url) => Some(url.to_string()),
+            None => self.get_remote_url().await.ok(),
+        };
+        match url {
+            Some(url) => cmd.for_remote(&url),
+            None => cmd,
+        }
+    }
+
     /// Checks out a specific Git reference (branch, tag, or commit hash).
     ///
     /// This operation switches the repository's working directory to match the
@@ -1067,10 +1119,26 @@ impl GitRepo {
             .context("Failed to verify remote repository")
     }
 
+    /// Lists the branches and tags a repository advertises, without cloning it.
+    ///
+    /// Runs `git ls-remote --heads --tags` against `url`, so it contacts the
+    /// remote even when the repository is cached locally.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the repository cannot be reached or read.
+    pub async fn list_remote_refs(url: &str) -> Result<RemoteRefs> {
+        let output = GitCommand::ls_remote_refs(url)
+            .execute_stdout()
+            .await
+            .context("Failed to list remote references")?;
+        Ok(RemoteRefs::parse(&output))
+    }
+
     /// Fetch updates for a bare repository with logging context.
     async fn ensure_bare_repo_has_refs_with_context(&self, context: Option<&str>) -> Result<()> {
         // Try to fetch to ensure we have refs
-        let mut fetch_cmd = GitCommand::fetch().current_dir(&self.path);
+        let mut fetch_cmd = self.fetch_command(None).await;
 
         if let Some(ctx) = context {
             fetch_cmd = fetch_cmd.with_context(ctx);
@@ -1150,16 +1218,46 @@ impl GitRepo {
         url: &str,
         target: impl AsRef<Path>,
         context: Option<&str>,
+    ) -> Result<Self> {
+        Self::clone_bare_with_strategy(url, target, CloneStrategy::default(), context).await
+    }
+
+    /// Clone a repository as a bare repository, as configured by `strategy`.
+    ///
+    /// With the default strategy this is a regular `git clone --bare`, and a
+    /// failed clone is removed. A `depth` makes the clone shallow. A resumable
+    /// clone is fetched in stages and left in place when interrupted, so that
+    /// calling this again with the same `target` resumes it (see
+    /// [`CloneStrategy::resumable`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the clone or one of its fetches fails.
+    pub async fn clone_bare_with_strategy(
+        url: &str,
+        target: impl AsRef<Path>,
+        strategy: CloneStrategy,
+        context: Option<&str>,
     ) -> Result<Self> {
         let target_path = target.as_ref();
+        if strategy.resumable {
+            return Self::clone_bare_resumable(url, target_path, strategy.depth, context).await;
+        }
 
         let mut cmd = GitCommand::clone_bare(url, target_path);
+        if let Some(depth) = strategy.depth {
+            cmd = cmd.arg(format!("--depth={depth}"));
+        }
 
         if let Some(ctx) = context {
             cmd = cmd.with_context(ctx);
         }
 
-        cmd.execute_success().await?;
+        let existed = target_path.exists();
+        if let Err(e) = cmd.execute_success().await {
+            remove_partial_clone(target_path, existed).await;
+            return Err(e);
+        }
 
         let repo = Self::new(target_path);
 
@@ -1178,6 +1276,97 @@ impl GitRepo {
         Ok(repo)
     }
 
+    /// Whether `path` holds a resumable clone that has not completed yet.
+    #[must_use]
+    pub fn is_partial_clone(path: &Path) -> bool {
+        path.join(PARTIAL_CLONE_MARKER).exists()
+    }
+
+    /// Clone into `target` in stages, resuming a partial clone left there.
+    ///
+    /// The repository is created with `git init --bare` and filled by
+    /// fetches, each of which keeps what it downloaded if a later one fails.
+    /// Without a `depth`, history is deepened [`RESUMABLE_FETCH_STEP`]
+    /// commits at a time until it is complete. A marker file identifies the
+    /// directory as partial until the last fetch succeeds.
+    async fn clone_bare_resumable(
+        url: &str,
+        target: &Path,
+        depth: Option<u32>,
+        context: Option<&str>,
+    ) -> Result<Self> {
+        let repo = Self::new(target);
+        let with_context = |cmd: GitCommand| match context {
+            Some(ctx) => cmd.with_context(ctx),
+            None => cmd,
+        };
+
+        if Self::is_partial_clone(target) {
+            tracing::debug!("Resuming partial clone of {url} in {}", target.display());
+        } else {
+            with_context(GitCommand::init().arg("--bare").arg(target.display().to_string()))
+                .execute_success()
+                .await?;
+            tokio::fs::write(target.join(PARTIAL_CLONE_MARKER), url).await.with_context(|| {
+                format!("Failed to mark {} as a partial clone", target.display())
+            })?;
+            with_context(GitCommand::new().args(["remote", "add", "origin", url]))
+                .current_dir(target)
+                .execute_success()
+                .await?;
+        }
+
+        let has_refs = GitCommand::new()
+            .args(["for-each-ref", "--count=1", "--format=%(refname)"])
+            .current_dir(target)
+            .execute_stdout()
+            .await
+            .is_ok_and(|refs| !refs.trim().is_empty());
+
+        if !has_refs || depth.is_some() {
+            let first_stage = depth.unwrap_or(RESUMABLE_FETCH_STEP);
+            with_context(repo.fetch_command(Some(url)).await.arg(format!("--depth={first_stage}")))
+                .execute_success()
+                .await
+                .context("Clone interrupted; run the command again to resume it")?;
+        }
+        if depth.is_none() {
+            while repo.is_shallow().await? {
+                with_context(
+                    repo.fetch_command(Some(url))
+                        .await
+                        .arg(format!("--deepen={RESUMABLE_FETCH_STEP}")),
+                )
+                .execute_success()
+                .await
+                .context("Clone interrupted; run the command again to resume it")?;
+            }
+        }
+
+        // Worktrees without an explicit reference check out origin/HEAD
+        with_context(GitCommand::new().args(["remote", "set-head", "origin", "--auto"]))
+            .current_dir(target)
+            .for_remote(url)
+            .execute_success()
+            .await
+            .ok();
+
+        tokio::fs::remove_file(target.join(PARTIAL_CLONE_MARKER))
+            .await
+            .with_context(|| format!("Failed to mark {} as a complete clone", target.display()))?;
+        Ok(repo)
+    }
+
+    /// Whether the repository's history is truncated by a shallow fetch.
+    async fn is_shallow(&self) -> Result<bool> {
+        let output = GitCommand::new()
+            .args(["rev-parse", "--is-shallow-repository"])
+            .current_dir(&self.path)
+            .execute_stdout()
+            .await?;
+        Ok(output.trim() == "true")
+    }
+
     /// Create a new worktree from this repository.
     ///
     /// Worktrees allow multiple working directories to be checked out from
@@ -1597,6 +1786,233 @@ impl GitRepo {
         Ok(())
     }
 
+    /// Check the integrity of the repository with `git fsck`.
+    ///
+    /// Used by `agpm cache verify` to find cached repositories left corrupted
+    /// by an interrupted clone or a disk failure.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error containing Git's diagnostics if any object is missing
+    /// or corrupt.
+    pub async fn fsck(&self) -> Result<()> {
+        GitCommand::fsck()
+            .current_dir(&self.path)
+            .execute_success()
+            .await
+            .with_context(|| format!("Repository {} failed integrity check", self.path.display()))
+    }
+
+    /// Initialize and check out the submodules of this working tree.
+    ///
+    /// Runs `git submodule update --init --recursive`, fetching each submodule
+    /// at the commit recorded by the superproject. Does nothing when the
+    /// repository has no `.gitmodules` file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a submodule cannot be cloned or checked out.
+    pub async fn update_submodules(&self) -> Result<()> {
+        if !self.path.join(".gitmodules").exists() {
+            return Ok(());
+        }
+
+        GitCommand::submodule_update()
+            .current_dir(&self.path)
+            .execute_success()
+            .await
+            .with_context(|| format!("Failed to initialize submodules in {}", self.path.display()))
+    }
+
+    /// Replace Git LFS pointer files in this working tree with their content.
+    ///
+    /// Runs `git lfs pull --include <path>`, which downloads the objects from
+    /// the repository's LFS server. Used when a resource turns out to be an
+    /// LFS pointer (see [`is_lfs_pointer`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if Git LFS is not installed or the download fails.
+    pub async fn lfs_pull(&self, path: &str) -> Result<()> {
+        if GitCommand::lfs_version().execute_success().await.is_err() {
+            return Err(anyhow::anyhow!(
+                "'{path}' is stored with Git LFS, but git-lfs is not installed.\n\
+                 Install Git LFS (https://git-lfs.com) and run 'git lfs install', then retry."
+            ));
+        }
+
+        GitCommand::lfs_pull(path)
+            .current_dir(&self.path)
+            .execute_success()
+            .await
+            .with_context(|| format!("Failed to download Git LFS content for '{path}'"))
+    }
+
+    /// Full SHAs of the commits whose names start with the hex `prefix`.
+    ///
+    /// Trees and blobs sharing the prefix are ignored. Returns an empty list
+    /// when nothing matches.
+    async fn commits_with_prefix(&self, prefix: &str) -> Result<Vec<String>> {
+        let Ok(objects) =
+            GitCommand::disambiguate(prefix).current_dir(&self.path).execute_stdout().await
+        else {
+            return Ok(Vec::new());
+        };
+
+        let mut commits = Vec::new();
+        for object in objects.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let object_type = GitCommand::object_type(object)
+                .current_dir(&self.path)
+                .execute_stdout()
+                .await
+                .with_context(|| format!("Failed to inspect object {object}"))?;
+            if object_type == "commit" {
+                commits.push(object.to_string());
+            }
+        }
+        Ok(commits)
+    }
+
+    /// Find the last commit on `reference` committed on or before `date`.
+    ///
+    /// `date` is a `YYYY-MM-DD` date, which includes the whole day, or any
+    /// timestamp Git understands, such as RFC 3339. The reference is resolved
+    /// like [`resolve_to_sha`](Self::resolve_to_sha), so branch names use the
+    /// fetched remote-tracking branch.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reference cannot be resolved or has no commit
+    /// that old.
+    pub async fn commit_before(&self, reference: &str, date: &str) -> Result<String> {
+        let tip = self.resolve_to_sha(Some(reference)).await?;
+        let before = if chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok() {
+            format!("{date} 23:59:59")
+        } else {
+            date.to_string()
+        };
+
+        let sha = GitCommand::rev_list_before(&tip, &before)
+            .current_dir(&self.path)
+            .execute_stdout()
+            .await
+            .with_context(|| format!("Failed to find the commit on '{reference}' as of {date}"))?;
+        if sha.is_empty() {
+            return Err(anyhow::anyhow!("'{reference}' has no commit on or before {date}"));
+        }
+        Ok(sha)
+    }
+
+    /// Check whether a commit exists in this repository.
+    pub async fn has_commit(&self, sha: &str) -> bool {
+        GitCommand::commit_exists(sha).current_dir(&self.path).execute_success().await.is_ok()
+    }
+
+    /// Check whether `name` is a tag in this repository.
+    pub async fn is_tag(&self, name: &str) -> bool {
+        GitCommand::verify_ref(&format!("refs/tags/{name}"))
+            .current_dir(&self.path)
+            .execute_success()
+            .await
+            .is_ok()
+    }
+
+    /// Verify the GPG or SSH signature of the annotated tag `tag`.
+    ///
+    /// SSH signatures are checked against `allowed_signers` when given, and
+    /// otherwise against Git's `gpg.ssh.allowedSignersFile` setting. Returns the
+    /// signer Git reports, if it names one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tag is lightweight or unsigned, its signature
+    /// does not verify, or Git is not set up to verify signatures of its kind.
+    pub async fn verify_tag(
+        &self,
+        tag: &str,
+        allowed_signers: Option<&Path>,
+    ) -> Result<Option<String>> {
+        if let Some(file) = allowed_signers
+            && !file.exists()
+        {
+            return Err(anyhow::anyhow!(
+                "Allowed signers file {} does not exist, so tag '{tag}' cannot be verified",
+                file.display()
+            ));
+        }
+
+        match GitCommand::verify_tag(tag, allowed_signers).current_dir(&self.path).execute().await {
+            Ok(output) => Ok(parse_tag_signer(&output.stderr)),
+            Err(e) => match e.downcast_ref::<AgpmError>() {
+                Some(AgpmError::GitCommandError {
+                    stderr,
+                    ..
+                }) => Err(tag_signature_error(tag, stderr)),
+                _ => Err(e.context(format!("Failed to verify signature of tag '{tag}'"))),
+            },
+        }
+    }
+
+    /// Write a Git bundle containing the history of the given commits.
+    ///
+    /// Each commit is pinned under a temporary `refs/agpm-bundle/<sha>` ref so
+    /// that it can be named in the bundle; the refs are removed afterwards.
+    /// [`fetch_bundle`](Self::fetch_bundle) restores the same refs on import.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a commit does not exist or the bundle cannot be written.
+    pub async fn create_bundle(&self, bundle_path: &Path, commits: &[String]) -> Result<()> {
+        let refs: Vec<String> =
+            commits.iter().map(|sha| format!("refs/agpm-bundle/{sha}")).collect();
+        for (sha, ref_name) in commits.iter().zip(&refs) {
+            GitCommand::new()
+                .args(["update-ref", ref_name.as_str(), sha.as_str()])
+                .current_dir(&self.path)
+                .execute_success()
+                .await
+                .with_context(|| {
+                    format!("Commit {sha} is not available in {}", self.path.display())
+                })?;
+        }
+
+        let result = GitCommand::new()
+            .args(["bundle", "create"])
+            .arg(bundle_path.display().to_string())
+            .args(refs.iter().map(String::as_str))
+            .current_dir(&self.path)
+            .execute_success()
+            .await
+            .with_context(|| format!("Failed to create bundle {}", bundle_path.display()));
+
+        for ref_name in &refs {
+            GitCommand::new()
+                .args(["update-ref", "-d", ref_name.as_str()])
+                .current_dir(&self.path)
+                .execute_success()
+                .await
+                .ok();
+        }
+
+        result
+    }
+
+    /// Fetch the commits of a bundle written by [`create_bundle`](Self::create_bundle).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bundle is invalid or does not apply to this repository.
+    pub async fn fetch_bundle(&self, bundle_path: &Path) -> Result<()> {
+        GitCommand::new()
+            .arg("fetch")
+            .arg(bundle_path.display().to_string())
+            .arg("+refs/agpm-bundle/*:refs/agpm-bundle/*")
+            .current_dir(&self.path)
+            .execute_success()
+            .await
+            .with_context(|| format!("Failed to fetch from bundle {}", bundle_path.display()))
+    }
+
     /// Check if this repository is a bare repository.
     ///
     /// Bare repositories don't have a working directory and are optimized
@@ -1680,7 +2096,9 @@ impl GitRepo {
     ///
     /// 1. If `ref_spec` is None or "HEAD", resolves to current HEAD commit
     /// 2. If already a full SHA (40 hex chars), returns it unchanged
-    /// 3. Otherwise uses `git rev-parse` to resolve:
+    /// 3. If an abbreviated SHA (7-39 hex chars) matches commits, expands it to
+    ///    the one matching commit, or fails listing the candidates
+    /// 4. Otherwise uses `git rev-parse` to resolve:
     ///    - Tags (e.g., "v1.0.0")
     ///    - Branches (e.g., "main", "origin/main")
     ///    - Short SHAs (e.g., "abc123")
@@ -1712,6 +2130,7 @@ impl GitRepo {
     ///
     /// Returns an error if:
     /// - The reference doesn't exist in the repository
+    /// - An abbreviated SHA matches more than one commit
     /// - The repository is invalid or corrupted
     /// - Git command execution fails
     pub async fn resolve_to_sha(&self, ref_spec: Option<&str>) -> Result<String> {
@@ -1722,6 +2141,32 @@ impl GitRepo {
             return Ok(reference.to_string());
         }
 
+        // Abbreviated SHA: expand it to the full commit, as copied from GitHub
+        if reference.len() >= 7 && reference.chars().all(|c| c.is_ascii_hexdigit()) {
+            let mut commits = self.commits_with_prefix(reference).await?;
+            match commits.len() {
+                0 => {} // Not a commit here; may still be a branch or tag name
+                1 => return Ok(commits.remove(0)),
+                _ => {
+                    let mut candidates = String::new();
+                    for sha in &commits {
+                        let subject = GitCommand::new()
+                            .args(["show", "-s", "--format=%s", sha])
+                            .current_dir(&self.path)
+                            .execute_stdout()
+                            .await
+                            .unwrap_or_default();
+                        candidates.push_str(&format!("\n  {sha} {subject}"));
+                    }
+                    return Err(anyhow::anyhow!(
+                        "Short SHA '{reference}' is ambiguous; it matches {} commits:{candidates}\n\n\
+                         Use more characters of the commit SHA.",
+                        commits.len()
+                    ));
+                }
+            }
+        }
+
         // For branch names, try to resolve origin/branch first to get the latest from remote
         // This ensures we get the most recent commit after a fetch
         let ref_to_resolve = if !reference.contains('/') && reference != "HEAD" {
@@ -1812,8 +2257,115 @@ impl GitRepo {
     }
 }
 
+/// Branches and tags advertised by a remote, as listed by [`GitRepo::list_remote_refs`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RemoteRefs {
+    /// Branch names, without `refs/heads/`
+    pub branches: Vec<String>,
+    /// Tag names, without `refs/tags/`
+    pub tags: Vec<String>,
+    /// Commits the branches and tags point to, including peeled annotated tags
+    pub commits: Vec<String>,
+}
+
+impl RemoteRefs {
+    /// Parse `git ls-remote` output (`<sha>\t<ref>` per line).
+    #[must_use]
+    pub fn parse(output: &str) -> Self {
+        let mut refs = Self::default();
+        for line in output.lines() {
+            let Some((sha, name)) = line.split_once('\t') else {
+                continue;
+            };
+            refs.commits.push(sha.to_string());
+            if let Some(branch) = name.strip_prefix("refs/heads/") {
+                refs.branches.push(branch.to_string());
+            } else if let Some(tag) = name.strip_prefix("refs/tags/")
+                && !tag.ends_with("^{}")
+            {
+                refs.tags.push(tag.to_string());
+            }
+        }
+        refs.commits.sort();
+        refs.commits.dedup();
+        refs
+    }
+
+    /// Whether `rev` is an advertised commit, or a prefix of one.
+    #[must_use]
+    pub fn has_commit(&self, rev: &str) -> bool {
+        let rev = rev.to_ascii_lowercase();
+        self.commits.iter().any(|commit| commit.starts_with(&rev))
+    }
+}
+
 // Module-level helper functions for Git environment management and URL processing
 
+/// The refspecs [`GitRepo::fetch_refs`] uses to fetch `refs`.
+///
+/// Tags and branches are fetched with a trailing `*` so that a name matching
+/// neither is skipped instead of aborting the whole fetch.
+pub(crate) fn fetch_refspecs(refs: &[String]) -> Vec<String> {
+    refs.iter()
+        .flat_map(|name| {
+            if name.len() == 40 && name.chars().all(|c| c.is_ascii_hexdigit()) {
+                vec![name.clone()]
+            } else {
+                vec![
+                    format!("+refs/tags/{name}*:refs/tags/{name}*"),
+                    format!("+refs/heads/{name}*:refs/remotes/origin/{name}*"),
+                ]
+            }
+        })
+        .collect()
+}
+
+/// The signer named in `git verify-tag` output.
+///
+/// GPG reports `Good signature from "Alice <alice@example.com>"` and SSH
+/// reports `Good "git" signature for alice@example.com with ED25519 key ...`.
+fn parse_tag_signer(output: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        if let Some((_, rest)) = line.split_once("Good signature from \"") {
+            return rest.split_once('"').map(|(signer, _)| signer.to_string());
+        }
+        let (_, rest) = line.split_once("\" signature for ")?;
+        Some(rest.split_once(" with ").map_or(rest, |(signer, _)| signer).to_string())
+    })
+}
+
+/// Explain why `git verify-tag` rejected `tag`, with a hint for setup problems.
+fn tag_signature_error(tag: &str, stderr: &str) -> anyhow::Error {
+    let details = stderr.trim();
+    let message = if details.contains("no signature found")
+        || details.contains("cannot verify a non-tag object")
+    {
+        format!("Tag '{tag}' is not signed")
+    } else if details.contains("allowedSignersFile") {
+        format!(
+            "Tag '{tag}' has an SSH signature, but Git has no allowed signers file to check it against.\n\
+             Set `allowed_signers` for the source in agpm.toml, or run \
+             `git config --global gpg.ssh.allowedSignersFile <file>`"
+        )
+    } else if details.contains("cannot run gpg") || details.contains("cannot run ssh-keygen") {
+        format!(
+            "Git could not run the program that verifies the signature of tag '{tag}'.\n\
+             Install GnuPG (or OpenSSH for SSH signatures), or point `gpg.program` at it\n\
+             Details: {details}"
+        )
+    } else if details.contains("No principal matched") {
+        format!("Tag '{tag}' is signed with a key that is not in the allowed signers file")
+    } else if details.contains("No public key") {
+        format!(
+            "Tag '{tag}' is signed with a key that is not in your GPG keyring.\n\
+             Import the signer's public key with `gpg --import` and try again"
+        )
+    } else {
+        format!("Signature of tag '{tag}' did not verify: {details}")
+    };
+    anyhow::anyhow!(message)
+}
+
 /// Checks if Git is installed and accessible on the system.
 ///
 /// This function verifies that the system's `git` command is available in the PATH
@@ -1827,8 +2379,9 @@ impl GitRepo {
 ///
 /// # Implementation Details
 ///
-/// The function uses [`get_git_command()`] to determine the appropriate Git command
-/// for the current platform, then executes `git --version` to verify functionality.
+/// The function runs the configured Git executable (see
+/// [`GitSettings`](command_builder::GitSettings)), which defaults to
+/// [`get_git_command()`], and executes `git --version` to verify functionality.
 ///
 /// # Platform Differences
 ///
@@ -1874,7 +2427,9 @@ impl GitRepo {
 #[must_use]
 pub fn is_git_installed() -> bool {
     // For synchronous checking, we still use std::process::Command directly
-    std::process::Command::new(crate::utils::platform::get_git_command())
+    let settings = command_builder::git_settings();
+    std::process::Command::new(settings.program())
+        .args(&settings.args)
         .arg("--version")
         .output()
         .map(|output| output.status.success())
@@ -2183,6 +2738,42 @@ pub fn ensure_valid_git_repo(path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// File kept in a bare repository while a resumable clone is incomplete.
+const PARTIAL_CLONE_MARKER: &str = "agpm-partial-clone";
+
+/// Commits fetched per stage when a resumable clone fetches the full history.
+pub const RESUMABLE_FETCH_STEP: u32 = 1000;
+
+/// How a source repository is cloned into the cache.
+///
+/// Set per source with `clone_depth` and `resumable` in the manifest's
+/// `[sources]` section; the default is a regular bare clone.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CloneStrategy {
+    /// Only fetch this many commits of history, making a shallow clone.
+    pub depth: Option<u32>,
+    /// Clone with `git init` and staged fetches instead of `git clone`.
+    ///
+    /// An interrupted clone keeps the objects of every completed fetch and is
+    /// resumed with `git fetch` by the next clone into the same directory,
+    /// rather than being deleted and started over.
+    pub resumable: bool,
+}
+
+/// Remove what a failed or timed-out clone left at `target`.
+///
+/// A clone killed part-way leaves a repository without all of its objects,
+/// which later runs would mistake for a valid cache entry. Directories that
+/// existed before the clone started are left alone.
+async fn remove_partial_clone(target: &Path, existed: bool) {
+    if !existed
+        && target.exists()
+        && let Err(e) = tokio::fs::remove_dir_all(target).await
+    {
+        tracing::warn!("Failed to remove partial clone at {}: {e}", target.display());
+    }
+}
+
 /// Parses a Git URL into owner and repository name components.
 ///
 /// This function extracts the repository owner (user/organization) and repository
@@ -2209,6 +2800,12 @@ pub fn ensure_valid_git_repo(path: &Path) -> Result<()> {
 /// ## SSH URLs
 /// - `git@github.com:rust-lang/cargo.git` → `("rust-lang", "cargo")`
 /// - `git@gitlab.com:group/project.git` → `("group", "project")`
+/// - `ssh://git@host:2222/team/repo.git` → `("team", "repo")`
+/// - `git@10.0.0.5:repo.git` → `("10.0.0.5", "repo")`
+///
+/// ## Git Protocol URLs
+/// - `git://gitserver/path/repo.git` → `("path", "repo")`
+/// - `git://gitserver/repo.git` → `("gitserver", "repo")`
 ///
 /// ## Local URLs
 /// - `file:///path/to/repo.git` → `("local", "repo")`
@@ -2320,7 +2917,24 @@ pub fn parse_git_url(url: &str) -> Result<(String, String)> {
         return Ok(("local".to_string(), repo_name.to_string()));
     }
 
-    // Handle SSH URLs like git@github.com:user/repo.git
+    // Handle git:// and ssh:// URLs, which may point at any host and port,
+    // e.g. git://gitserver/repo.git or ssh://git@10.0.0.5:2222/team/repo.git.
+    // Repositories directly under the host use the host name as owner.
+    if let Some(rest) = url.strip_prefix("git://").or_else(|| url.strip_prefix("ssh://")) {
+        let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+        let host = authority.rsplit('@').next().unwrap_or(authority);
+        let host = host.split(':').next().unwrap_or(host);
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        if let Some((repo, parents)) = segments.split_last() {
+            let repo = repo.trim_end_matches(".git");
+            let owner = parents.last().copied().unwrap_or(host);
+            if !repo.is_empty() && !owner.is_empty() {
+                return Ok((owner.to_string(), repo.to_string()));
+            }
+        }
+    }
+
+    // Handle SSH URLs like git@github.com:user/repo.git or git@10.0.0.5:repo.git
     if url.contains('@')
         && url.contains(':')
         && !url.starts_with("ssh://")
@@ -2331,6 +2945,10 @@ pub fn parse_git_url(url: &str) -> Result<(String, String)> {
         if let Some(slash_pos) = path.find('/') {
             return Ok((path[..slash_pos].to_string(), path[slash_pos + 1..].to_string()));
         }
+        let host = &url[url.find('@').map_or(0, |at| at + 1)..colon_pos];
+        if !path.is_empty() && !host.is_empty() && !host.contains('/') {
+            return Ok((host.to_string(), path.to_string()));
+        }
     }
 
     // Handle HTTPS URLs
@@ -2481,3 +3099,19 @@ pub fn strip_auth_from_url(url: &str) -> Result<String> {
     // Return URL as-is if no auth found or not HTTP(S)
     Ok(url.to_string())
 }
+
+/// Header that starts every Git LFS pointer file.
+const LFS_POINTER_HEADER: &[u8] = b"version https://git-lfs.github.com/spec/v1";
+
+/// Check whether file content is a Git LFS pointer rather than the real file.
+///
+/// A pointer is a small text file that starts with the LFS spec line and
+/// records the object's `oid` and `size`; the content itself lives on the LFS
+/// server until `git lfs pull` replaces it. Works on raw bytes, so binary
+/// content that is not valid UTF-8 is simply not a pointer.
+#[must_use]
+pub fn is_lfs_pointer(content: &[u8]) -> bool {
+    content.len() < 1024
+        && content.starts_with(LFS_POINTER_HEADER)
+        && content.split(|&b| b == b'\n').any(|line| line.starts_with(b"oid sha256:"))
+}
@@ -187,6 +187,28 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_parse_git_url_git_protocol_and_ssh_ports() -> Result<()> {
+        let test_cases = vec![
+            ("git://gitserver/repo.git", ("gitserver", "repo")),
+            ("git://host/path/repo.git", ("path", "repo")),
+            ("git://host:9418/team/repo", ("team", "repo")),
+            ("ssh://git@host:2222/repo.git", ("host", "repo")),
+            ("ssh://git@10.0.0.5:2222/team/agents.git", ("team", "agents")),
+            ("git@10.0.0.5:repo.git", ("10.0.0.5", "repo")),
+        ];
+
+        for (url, (expected_owner, expected_repo)) in test_cases {
+            let (owner, repo) = parse_git_url(url)?;
+            assert_eq!(owner, expected_owner, "Owner mismatch for {url}");
+            assert_eq!(repo, expected_repo, "Repo mismatch for {url}");
+        }
+
+        assert!(parse_git_url("git://gitserver").is_err());
+        assert!(parse_git_url("ssh://git@host:2222/").is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_parse_git_url_special_cases() -> Result<()> {
         // Test URLs with ports
@@ -1139,6 +1161,60 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_resumable_clone_resumes_partial_directory() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let source_path = temp_dir.path().join("source");
+        let bare_path = temp_dir.path().join("bare.git");
+
+        std::fs::create_dir(&source_path)?;
+        let git = TestGit::new(&source_path);
+        git.init()?;
+        git.config_user()?;
+        for i in 0..3 {
+            std::fs::write(source_path.join("README.md"), format!("# Test {i}"))?;
+            git.add_all()?;
+            git.commit(&format!("Commit {i}"))?;
+        }
+        git.tag("v1.0.0")?;
+        let file_url = format!("file://{}", source_path.display());
+        let strategy = CloneStrategy {
+            depth: None,
+            resumable: true,
+        };
+
+        // Simulate a clone that was interrupted after its first (shallow) fetch
+        GitCommand::init()
+            .arg("--bare")
+            .arg(bare_path.display().to_string())
+            .execute_success()
+            .await?;
+        std::fs::write(bare_path.join(PARTIAL_CLONE_MARKER), &file_url)?;
+        GitCommand::new()
+            .args(["remote", "add", "origin", &file_url])
+            .current_dir(&bare_path)
+            .execute_success()
+            .await?;
+        GitRepo::new(&bare_path)
+            .fetch_command(None)
+            .await
+            .arg("--depth=1")
+            .execute_success()
+            .await?;
+        assert!(GitRepo::is_partial_clone(&bare_path));
+        assert!(GitRepo::new(&bare_path).is_shallow().await?);
+
+        let repo = GitRepo::clone_bare_with_strategy(&file_url, &bare_path, strategy, None).await?;
+        assert!(!GitRepo::is_partial_clone(&bare_path));
+        assert!(!repo.is_shallow().await?);
+        assert!(repo.is_bare().await?);
+
+        let worktree =
+            repo.create_worktree(temp_dir.path().join("worktree"), Some("v1.0.0")).await?;
+        assert_eq!(std::fs::read_to_string(worktree.path().join("README.md"))?, "# Test 2");
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_clone_bare_with_context() -> Result<()> {
         let temp_dir = TempDir::new()?;
@@ -1499,6 +1575,37 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_resolve_short_sha_expands_to_full_commit() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let repo_path = temp_dir.path();
+        let git = TestGit::new(repo_path);
+        git.init()?;
+        git.config_user()?;
+
+        std::fs::write(repo_path.join("file1.txt"), "content1")?;
+        git.add_all()?;
+        git.commit("First commit")?;
+        let first_sha = git.rev_parse_head()?;
+        std::fs::write(repo_path.join("file2.txt"), "content2")?;
+        git.add_all()?;
+        git.commit("Second commit")?;
+
+        let repo = GitRepo::new(repo_path);
+        for len in [7, 12, 39] {
+            assert_eq!(repo.resolve_to_sha(Some(&first_sha[..len])).await?, first_sha);
+        }
+
+        // A hex string matching no commit is not silently accepted
+        let missing = if first_sha.starts_with("0000000") {
+            "fffffff"
+        } else {
+            "0000000"
+        };
+        assert!(repo.resolve_to_sha(Some(missing)).await.is_err());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_file_url_clone_error_reporting() -> Result<()> {
         let temp_dir = TempDir::new()?;
@@ -1525,4 +1632,159 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_is_lfs_pointer() {
+        let pointer = "version https://git-lfs.github.com/spec/v1\n\
+                       oid sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393\n\
+                       size 12345\n";
+        assert!(is_lfs_pointer(pointer.as_bytes()));
+
+        assert!(!is_lfs_pointer(b"# Agent\n\nversion https://git-lfs.github.com/spec/v1\n"));
+        assert!(!is_lfs_pointer(b"version https://git-lfs.github.com/spec/v1\n"));
+        assert!(!is_lfs_pointer(b""));
+        assert!(!is_lfs_pointer(&[0xff, 0xfe, 0x00, 0x01]));
+    }
+
+    #[tokio::test]
+    async fn test_commit_before_date() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let repo_path = temp_dir.path();
+        let git = TestGit::new(repo_path);
+        git.init()?;
+        git.config_user()?;
+
+        let mut shas = Vec::new();
+        for date in ["2024-05-01T12:00:00Z", "2024-06-01T09:00:00Z", "2024-07-01T12:00:00Z"] {
+            let status = std::process::Command::new("git")
+                .args(["commit", "--allow-empty", "-m", date])
+                .current_dir(repo_path)
+                .env("GIT_AUTHOR_DATE", date)
+                .env("GIT_COMMITTER_DATE", date)
+                .status()?;
+            assert!(status.success());
+            shas.push(git.get_head_sha()?);
+        }
+
+        let repo = GitRepo::new(repo_path);
+        // A plain date includes the whole day
+        assert_eq!(repo.commit_before("HEAD", "2024-06-01").await?, shas[1]);
+        assert_eq!(repo.commit_before("HEAD", "2024-05-31T23:00:00Z").await?, shas[0]);
+        assert!(repo.commit_before("HEAD", "2024-01-01").await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_verify_tag_signatures() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let repo_path = temp_dir.path().join("repo");
+        std::fs::create_dir_all(&repo_path)?;
+        let git = TestGit::new(&repo_path);
+        git.init()?;
+        git.config_user()?;
+        std::fs::write(repo_path.join("README.md"), "# Agents\n")?;
+        git.add_all()?;
+        git.commit("initial")?;
+        git.tag("lightweight")?;
+
+        let key = temp_dir.path().join("signing-key");
+        let keygen = std::process::Command::new("ssh-keygen")
+            .args(["-q", "-t", "ed25519", "-N", "", "-f"])
+            .arg(&key)
+            .status();
+        if !keygen.is_ok_and(|status| status.success()) {
+            // SSH signing needs ssh-keygen
+            return Ok(());
+        }
+        let status = std::process::Command::new("git")
+            .args(["-c", "gpg.format=ssh", "-c"])
+            .arg(format!("user.signingkey={}", key.with_extension("pub").display()))
+            .args(["tag", "-s", "v1.0.0", "-m", "v1.0.0"])
+            .current_dir(&repo_path)
+            .status()?;
+        assert!(status.success());
+        let public_key = std::fs::read_to_string(key.with_extension("pub"))?;
+        let signers = temp_dir.path().join("allowed_signers");
+        std::fs::write(&signers, format!("release@example.com {public_key}"))?;
+
+        let repo = GitRepo::new(&repo_path);
+        assert!(repo.is_tag("v1.0.0").await);
+        assert!(!repo.is_tag("main").await);
+        assert_eq!(
+            repo.verify_tag("v1.0.0", Some(&signers)).await?.as_deref(),
+            Some("release@example.com")
+        );
+
+        let unsigned = repo.verify_tag("lightweight", Some(&signers)).await.unwrap_err();
+        assert_eq!(unsigned.to_string(), "Tag 'lightweight' is not signed");
+        let missing = temp_dir.path().join("missing");
+        assert!(repo.verify_tag("v1.0.0", Some(&missing)).await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fetch_refs_only_fetches_requested_refs() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let source_path = temp_dir.path().join("source");
+        let bare_path = temp_dir.path().join("bare.git");
+        std::fs::create_dir(&source_path)?;
+        std::fs::create_dir(&bare_path)?;
+
+        let source = TestGit::new(&source_path);
+        source.init()?;
+        source.config_user()?;
+        std::fs::write(source_path.join("file.txt"), "one")?;
+        source.add_all()?;
+        source.commit("First")?;
+        source.tag("v1.0.0")?;
+        std::fs::write(source_path.join("file.txt"), "two")?;
+        source.add_all()?;
+        source.commit("Second")?;
+        source.tag("v2.0.0")?;
+
+        let bare = TestGit::new(&bare_path);
+        bare.init_bare()?;
+        bare.remote_add("origin", source_path.to_str().unwrap())?;
+
+        let repo = GitRepo::new(&bare_path);
+        repo.fetch_refs(None, &["v1.0.0".to_string(), "missing".to_string()]).await?;
+        assert!(repo.is_tag("v1.0.0").await);
+        assert!(!repo.is_tag("v2.0.0").await);
+
+        let sha = "a".repeat(40);
+        assert_eq!(
+            fetch_refspecs(&["main".to_string(), sha.clone()]),
+            vec![
+                "+refs/tags/main*:refs/tags/main*".to_string(),
+                "+refs/heads/main*:refs/remotes/origin/main*".to_string(),
+                sha,
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_tag_signer() {
+        assert_eq!(
+            parse_tag_signer(
+                "gpg: Signature made Mon Jan 1\ngpg: Good signature from \"Alice <alice@example.com>\" [ultimate]\n"
+            )
+            .as_deref(),
+            Some("Alice <alice@example.com>")
+        );
+        assert_eq!(
+            parse_tag_signer(
+                "Good \"git\" signature for bob@example.com with ED25519 key SHA256:abc\n"
+            )
+            .as_deref(),
+            Some("bob@example.com")
+        );
+        assert_eq!(parse_tag_signer("Good \"git\" signature with ED25519 key SHA256:abc"), None);
+
+        let error = tag_signature_error(
+            "v1.0.0",
+            "error: gpg.ssh.allowedSignersFile needs to be configured and exist for ssh signature verification",
+        );
+        assert!(error.to_string().contains("allowed_signers"));
+    }
 }
@@ -8,9 +8,12 @@
 use crate::core::file_error::{FileOperation, FileResultExt};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
+/// Priority assigned to hooks that don't declare one.
+pub const DEFAULT_HOOK_PRIORITY: i32 = 0;
+
 /// Hook event types supported by Claude Code
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum HookEvent {
@@ -65,6 +68,20 @@ pub struct HookConfig {
     /// Description of what this hook does
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Execution priority relative to other hooks on the same event.
+    ///
+    /// Lower values run first. Hooks without a priority use [`DEFAULT_HOOK_PRIORITY`],
+    /// and ties are broken by hook name so the merged output is deterministic.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<i32>,
+}
+
+impl HookConfig {
+    /// Effective priority used when ordering merged hooks.
+    #[must_use]
+    pub fn effective_priority(&self) -> i32 {
+        self.priority.unwrap_or(DEFAULT_HOOK_PRIORITY)
+    }
 }
 
 /// A single hook command within a matcher group
@@ -90,6 +107,9 @@ pub struct AgpmHookMetadata {
     pub managed: bool,
     /// Name of the dependency that installed this hook
     pub dependency_name: String,
+    /// Other dependencies that declared an identical hook collapsed into this entry
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub merged_from: Vec<String>,
     /// Source repository name where this hook originated
     pub source: String,
     /// Version constraint or resolved version of the hook dependency
@@ -188,92 +208,252 @@ pub fn load_hook_configs(hooks_dir: &Path) -> Result<HashMap<String, HookConfig>
     Ok(configs)
 }
 
+/// A hook command after deduplication, along with every dependency that declared it.
+#[derive(Debug, Clone)]
+struct MergedHookEntry {
+    /// Event name the hook is registered under
+    event: String,
+    /// Matcher pattern, if the hook is tool-triggered
+    matcher: Option<String>,
+    /// Type of hook (usually "command")
+    hook_type: String,
+    /// Command to execute
+    command: String,
+    /// Timeout in milliseconds
+    timeout: Option<u32>,
+    /// Dependencies that declared this hook, in priority order
+    contributors: Vec<String>,
+}
+
+impl MergedHookEntry {
+    /// Whether `config` declares the same hook for `event` (same matcher, type, command and timeout).
+    fn is_same_hook(&self, event: &str, config: &HookConfig) -> bool {
+        self.event == event
+            && self.matcher == config.matcher
+            && self.hook_type == config.hook_type
+            && self.command == config.command
+            && self.timeout == config.timeout
+    }
+}
+
+/// Source and version recorded in `_agpm` metadata, keyed by dependency name.
+type HookSourceInfo = HashMap<String, (String, String)>;
+
+/// Flatten hook configs into per-event entries, collapsing structurally identical hooks.
+///
+/// Hooks are visited in ascending priority order (ties broken by name). When several
+/// dependencies declare the same event, matcher, command and timeout, the first one wins and the
+/// others are recorded as additional contributors so the entry survives until the last
+/// of them is removed.
+fn collect_hook_entries(hook_configs: HashMap<String, HookConfig>) -> Vec<MergedHookEntry> {
+    let mut ordered: Vec<(String, HookConfig)> = hook_configs.into_iter().collect();
+    ordered.sort_by(|(a_name, a), (b_name, b)| {
+        a.effective_priority().cmp(&b.effective_priority()).then_with(|| a_name.cmp(b_name))
+    });
+
+    let mut entries: Vec<MergedHookEntry> = Vec::new();
+    for (name, config) in ordered {
+        for event in &config.events {
+            let event_name = event_to_string(event);
+
+            if let Some(existing) =
+                entries.iter_mut().find(|entry| entry.is_same_hook(&event_name, &config))
+            {
+                if !existing.contributors.contains(&name) {
+                    existing.contributors.push(name.clone());
+                }
+                continue;
+            }
+
+            entries.push(MergedHookEntry {
+                event: event_name,
+                matcher: config.matcher.clone(),
+                hook_type: config.hook_type.clone(),
+                command: config.command.clone(),
+                timeout: config.timeout,
+                contributors: vec![name.clone()],
+            });
+        }
+    }
+
+    entries
+}
+
+/// Render hook entries into Claude Code's event -> matcher group structure.
+///
+/// Entries sharing an event and matcher are placed in the same group, and groups
+/// appear in the order their first entry was seen. When `source_info` is provided,
+/// each hook is tagged with `_agpm` metadata so later installs can tell AGPM-managed
+/// hooks apart from user-authored ones.
+fn render_hook_entries(
+    entries: &[MergedHookEntry],
+    source_info: Option<(&HookSourceInfo, &str)>,
+) -> Result<serde_json::Map<String, serde_json::Value>> {
+    use serde_json::{Map, Value, json};
+
+    let mut events_map: Map<String, Value> = Map::new();
+
+    for entry in entries {
+        let mut hook_obj = Map::new();
+        hook_obj.insert("type".to_string(), json!(entry.hook_type));
+        hook_obj.insert("command".to_string(), json!(entry.command));
+        if let Some(timeout) = entry.timeout {
+            hook_obj.insert("timeout".to_string(), json!(timeout));
+        }
+        if let Some((source_info, installed_at)) = source_info {
+            let primary = &entry.contributors[0];
+            let (source, version) = source_info
+                .get(primary)
+                .ok_or_else(|| anyhow::anyhow!("Missing source info for hook: {primary}"))?;
+            let metadata = AgpmHookMetadata {
+                managed: true,
+                dependency_name: primary.clone(),
+                merged_from: entry.contributors[1..].to_vec(),
+                source: source.clone(),
+                version: version.clone(),
+                installed_at: installed_at.to_string(),
+            };
+            hook_obj.insert("_agpm".to_string(), serde_json::to_value(metadata)?);
+        }
+        let hook_obj = Value::Object(hook_obj);
+
+        let event_array = events_map.entry(entry.event.clone()).or_insert_with(|| json!([]));
+        let event_vec =
+            event_array.as_array_mut().expect("event entries are always created as arrays");
+
+        let existing_group = event_vec
+            .iter_mut()
+            .find(|group| group.get("matcher").and_then(Value::as_str) == entry.matcher.as_deref());
+
+        match existing_group.and_then(|group| group.get_mut("hooks")).and_then(Value::as_array_mut)
+        {
+            Some(hooks_array) => hooks_array.push(hook_obj),
+            None => match &entry.matcher {
+                Some(matcher) => event_vec.push(json!({
+                    "matcher": matcher,
+                    "hooks": [hook_obj]
+                })),
+                None => event_vec.push(json!({
+                    "hooks": [hook_obj]
+                })),
+            },
+        }
+    }
+
+    Ok(events_map)
+}
+
 /// Convert AGPM hook configs to Claude Code format
 ///
 /// Transforms hooks from the AGPM format to the format expected by Claude Code.
 /// Groups hooks by event type and handles optional matchers correctly.
+///
+/// Hooks are processed in ascending priority order (ties broken by name), so
+/// hooks within a matcher group - and the matcher groups themselves - appear in
+/// a stable order that doesn't depend on `HashMap` iteration. Structurally
+/// identical hooks (same event, matcher, command and timeout) are written only once.
 fn convert_to_claude_format(
     hook_configs: HashMap<String, HookConfig>,
 ) -> Result<serde_json::Value> {
-    use serde_json::{Map, Value, json};
+    let entries = collect_hook_entries(hook_configs);
+    Ok(serde_json::Value::Object(render_hook_entries(&entries, None)?))
+}
 
-    let mut events_map: Map<String, Value> = Map::new();
+/// Whether a hook entry in settings carries AGPM management metadata.
+fn is_managed_hook(hook: &serde_json::Value) -> bool {
+    hook.get("_agpm")
+        .and_then(|meta| meta.get("managed"))
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false)
+}
 
-    for (_name, config) in hook_configs {
-        for event in &config.events {
-            let event_name = event_to_string(event);
+/// Remove AGPM-managed hooks from a matcher group.
+///
+/// Returns `None` when the group only contained managed hooks. Groups without
+/// managed hooks are returned unchanged.
+fn strip_managed_hooks(group: &serde_json::Value) -> Option<serde_json::Value> {
+    let Some(hooks) = group.get("hooks").and_then(serde_json::Value::as_array) else {
+        return Some(group.clone());
+    };
+
+    if !hooks.iter().any(is_managed_hook) {
+        return Some(group.clone());
+    }
+
+    let user_hooks: Vec<serde_json::Value> =
+        hooks.iter().filter(|hook| !is_managed_hook(hook)).cloned().collect();
+    if user_hooks.is_empty() {
+        return None;
+    }
 
-            // Create the hook object in Claude format
-            let mut hook_obj = serde_json::Map::new();
-            hook_obj.insert("type".to_string(), json!(config.hook_type));
-            hook_obj.insert("command".to_string(), json!(config.command));
-            if let Some(timeout) = config.timeout {
-                hook_obj.insert("timeout".to_string(), json!(timeout));
+    let mut group = group.clone();
+    group["hooks"] = serde_json::Value::Array(user_hooks);
+    Some(group)
+}
+
+/// Combine freshly rendered AGPM hooks with the hooks already present in settings.
+///
+/// Every existing hook carrying `_agpm` metadata is replaced by `managed`; everything
+/// else is user-authored and preserved exactly as written (it is never deduplicated
+/// against AGPM hooks). User matcher groups keep their position and AGPM groups are
+/// appended after them.
+fn merge_with_user_hooks(
+    existing: Option<&serde_json::Value>,
+    managed: serde_json::Map<String, serde_json::Value>,
+) -> serde_json::Value {
+    use serde_json::{Map, Value};
+
+    let mut result: Map<String, Value> = Map::new();
+
+    if let Some(existing) = existing.and_then(Value::as_object) {
+        for (event_name, groups) in existing {
+            let Some(groups) = groups.as_array() else {
+                result.insert(event_name.clone(), groups.clone());
+                continue;
+            };
+
+            let user_groups: Vec<Value> = groups.iter().filter_map(strip_managed_hooks).collect();
+            if !user_groups.is_empty() {
+                result.insert(event_name.clone(), Value::Array(user_groups));
             }
-            let hook_obj = Value::Object(hook_obj);
-
-            // Get or create the event array
-            let event_array = events_map.entry(event_name).or_insert_with(|| json!([]));
-            let event_vec = event_array.as_array_mut().unwrap();
-
-            if let Some(ref matcher) = config.matcher {
-                // Tool-triggered event with matcher
-                // Find existing matcher group or create new one
-                let mut found_group = false;
-                for group in event_vec.iter_mut() {
-                    if let Some(group_matcher) = group.get("matcher").and_then(|m| m.as_str())
-                        && group_matcher == matcher
-                    {
-                        // Add to existing matcher group
-                        if let Some(hooks_array) =
-                            group.get_mut("hooks").and_then(|h| h.as_array_mut())
-                        {
-                            hooks_array.push(hook_obj.clone());
-                            found_group = true;
-                            break;
-                        }
-                    }
-                }
+        }
+    }
 
-                if !found_group {
-                    // Create new matcher group
-                    event_vec.push(json!({
-                        "matcher": matcher,
-                        "hooks": [hook_obj]
-                    }));
-                }
-            } else {
-                // Session event without matcher - add to first group or create new one
-                if let Some(first_group) = event_vec.first_mut() {
-                    // Add to existing group if it has no matcher
-                    if first_group.as_object().unwrap().contains_key("matcher") {
-                        // Create new group for session events
-                        event_vec.push(json!({
-                            "hooks": [hook_obj]
-                        }));
-                    } else if let Some(hooks_array) =
-                        first_group.get_mut("hooks").and_then(|h| h.as_array_mut())
-                    {
-                        // Check for duplicates before adding
-                        let hook_exists = hooks_array.iter().any(|existing_hook| {
-                            existing_hook.get("command") == hook_obj.get("command")
-                                && existing_hook.get("type") == hook_obj.get("type")
-                        });
-                        if !hook_exists {
-                            hooks_array.push(hook_obj);
-                        }
-                    }
-                } else {
-                    // Create first group for session events
-                    event_vec.push(json!({
-                        "hooks": [hook_obj]
-                    }));
+    for (event_name, groups) in managed {
+        let Value::Array(groups) = groups else {
+            continue;
+        };
+        let slot = result.entry(event_name).or_insert_with(|| Value::Array(Vec::new()));
+        if let Some(event_groups) = slot.as_array_mut() {
+            event_groups.extend(groups);
+        }
+    }
+
+    Value::Object(result)
+}
+
+/// Strip `installed_at` timestamps from AGPM metadata so re-installs of unchanged
+/// hooks don't register as changes.
+fn without_install_timestamps(hooks: &serde_json::Value) -> serde_json::Value {
+    let mut hooks = hooks.clone();
+    if let Some(events) = hooks.as_object_mut() {
+        for groups in events.values_mut().filter_map(serde_json::Value::as_array_mut) {
+            for commands in groups
+                .iter_mut()
+                .filter_map(|group| group.get_mut("hooks"))
+                .filter_map(serde_json::Value::as_array_mut)
+            {
+                for meta in commands
+                    .iter_mut()
+                    .filter_map(|hook| hook.get_mut("_agpm"))
+                    .filter_map(serde_json::Value::as_object_mut)
+                {
+                    meta.remove("installed_at");
                 }
             }
         }
     }
-
-    Ok(Value::Object(events_map))
+    hooks
 }
 
 /// Convert event enum to string
@@ -296,14 +476,19 @@ fn event_to_string(event: &HookEvent) -> String {
 ///
 /// This function:
 /// 1. Reads hook JSON files directly from source locations (no file copying)
-/// 2. Converts them to Claude Code format
-/// 3. Updates .claude/settings.local.json with proper event-based structure
+/// 2. Converts them to Claude Code format, collapsing structurally identical hooks
+/// 3. Updates .claude/settings.local.json with proper event-based structure,
+///    replacing previously managed hooks and preserving user-authored ones
 /// 4. Can be called from both `add` and `install` commands
 ///
+/// Local hook files are read relative to `manifest_dir`; the settings file is
+/// written under `project_root`.
+///
 /// Returns the count of hooks that were actually changed
 pub async fn install_hooks(
     lockfile: &crate::lockfile::LockFile,
     project_root: &Path,
+    manifest_dir: &Path,
     cache: &crate::cache::Cache,
 ) -> Result<usize> {
     if lockfile.hooks.is_empty() {
@@ -318,6 +503,7 @@ pub async fn install_hooks(
 
     // Load hook configurations directly from source files
     let mut hook_configs = HashMap::new();
+    let mut source_info: HookSourceInfo = HashMap::new();
 
     for entry in &lockfile.hooks {
         // Get the source file path
@@ -345,12 +531,12 @@ pub async fn install_hooks(
                 worktree.join(&entry.path)
             }
         } else {
-            // Local file - resolve relative to project root
+            // Local file - resolve relative to the manifest directory
             let candidate = Path::new(&entry.path);
             if candidate.is_absolute() {
                 candidate.to_path_buf()
             } else {
-                project_root.join(candidate)
+                manifest_dir.join(candidate)
             }
         };
 
@@ -366,48 +552,144 @@ pub async fn install_hooks(
             .with_context(|| format!("Failed to parse hook config: {}", source_path.display()))?;
 
         hook_configs.insert(entry.name.clone(), config);
+        source_info.insert(
+            entry.name.clone(),
+            (
+                entry.source.clone().unwrap_or_else(|| "local".to_string()),
+                entry.version.clone().unwrap_or_default(),
+            ),
+        );
     }
 
     // Load existing settings
     let mut settings = crate::mcp::ClaudeSettings::load_or_default(&settings_path)?;
 
-    // Convert hooks to Claude Code format
-    let claude_hooks = convert_to_claude_format(hook_configs)?;
+    // Convert hooks to Claude Code format, collapsing identical hooks and tagging
+    // each one with the dependencies that declared it
+    let entries = collect_hook_entries(hook_configs);
+    let installed_at = chrono::Utc::now().to_rfc3339();
+    let managed_hooks = render_hook_entries(&entries, Some((&source_info, &installed_at)))?;
 
-    // Compare with existing hooks to detect changes
+    // Replace previously managed hooks while preserving user-authored ones
+    let merged_hooks = merge_with_user_hooks(settings.hooks.as_ref(), managed_hooks);
+
+    // Compare with existing hooks to detect changes (ignoring install timestamps)
     let hooks_changed = match &settings.hooks {
-        Some(existing_hooks) => existing_hooks != &claude_hooks,
-        None => claude_hooks.as_object().is_none_or(|obj| !obj.is_empty()),
+        Some(existing_hooks) => {
+            without_install_timestamps(existing_hooks) != without_install_timestamps(&merged_hooks)
+        }
+        None => merged_hooks.as_object().is_none_or(|obj| !obj.is_empty()),
     };
 
     if hooks_changed {
-        // Count actual configured hooks (after deduplication) before moving
-        let configured_count = claude_hooks.as_object().map_or(0, |events| {
-            events
-                .values()
-                .filter_map(|event_groups| event_groups.as_array())
-                .map(|groups| {
-                    groups
-                        .iter()
-                        .filter_map(|group| group.get("hooks")?.as_array())
-                        .map(std::vec::Vec::len)
-                        .sum::<usize>()
-                })
-                .sum::<usize>()
-        });
-
-        // Update settings with hooks (replaces existing hooks completely)
-        settings.hooks = Some(claude_hooks);
+        settings.hooks = Some(merged_hooks);
 
         // Save updated settings
         settings.save(&settings_path)?;
 
-        Ok(configured_count)
+        // Count configured AGPM hooks (after deduplication)
+        Ok(entries.len())
     } else {
         Ok(0)
     }
 }
 
+/// Remove hooks contributed by dependencies that are no longer installed.
+///
+/// Only hooks carrying `_agpm` metadata are touched. A deduplicated hook shared by
+/// several dependencies is kept until every contributor has been removed; if its
+/// primary dependency goes away, the next contributor takes over. User-authored
+/// hooks are preserved exactly, and `.claude/settings.local.json` is rewritten
+/// atomically only when something was removed.
+///
+/// Returns the number of hook entries removed.
+pub fn remove_managed_hooks(
+    project_root: &Path,
+    dependency_names: &HashSet<String>,
+) -> Result<usize> {
+    use serde_json::Value;
+
+    let settings_path = project_root.join(".claude").join("settings.local.json");
+    if dependency_names.is_empty() || !settings_path.exists() {
+        return Ok(0);
+    }
+
+    let mut settings = crate::mcp::ClaudeSettings::load_or_default(&settings_path)?;
+    let Some(hooks) = settings.hooks.as_mut().and_then(Value::as_object_mut) else {
+        return Ok(0);
+    };
+
+    let mut removed = 0;
+    hooks.retain(|_event, groups| {
+        let Some(groups) = groups.as_array_mut() else {
+            return true;
+        };
+
+        let groups_before = groups.len();
+        groups.retain_mut(|group| {
+            let Some(commands) = group.get_mut("hooks").and_then(Value::as_array_mut) else {
+                return true;
+            };
+
+            let commands_before = commands.len();
+            commands.retain_mut(|hook| release_hook(hook, dependency_names));
+            let dropped = commands_before - commands.len();
+            removed += dropped;
+
+            // Only drop groups that we emptied ourselves
+            dropped == 0 || !commands.is_empty()
+        });
+
+        groups.len() == groups_before || !groups.is_empty()
+    });
+
+    if removed == 0 {
+        return Ok(0);
+    }
+
+    if hooks.is_empty() {
+        settings.hooks = None;
+    }
+    settings.save(&settings_path)?;
+
+    Ok(removed)
+}
+
+/// Remove `dependency_names` from a managed hook's contributors.
+///
+/// Returns `false` when no contributor remains and the hook should be dropped.
+/// User-authored hooks always return `true`.
+fn release_hook(hook: &mut serde_json::Value, dependency_names: &HashSet<String>) -> bool {
+    if !is_managed_hook(hook) {
+        return true;
+    }
+    let Some(meta) = hook.get_mut("_agpm") else {
+        return true;
+    };
+    let Ok(mut metadata) = serde_json::from_value::<AgpmHookMetadata>(meta.clone()) else {
+        return true;
+    };
+
+    let total = 1 + metadata.merged_from.len();
+    let mut remaining: Vec<String> = std::iter::once(metadata.dependency_name.clone())
+        .chain(metadata.merged_from.iter().cloned())
+        .filter(|name| !dependency_names.contains(name))
+        .collect();
+
+    if remaining.is_empty() {
+        return false;
+    }
+    if remaining.len() < total {
+        metadata.dependency_name = remaining.remove(0);
+        metadata.merged_from = remaining;
+        if let Ok(value) = serde_json::to_value(&metadata) {
+            *meta = value;
+        }
+    }
+
+    true
+}
+
 /// Validate a hook configuration for correctness and safety.
 ///
 /// Performs comprehensive validation of a hook configuration including:
@@ -447,6 +729,7 @@ pub async fn install_hooks(
 ///     command: "echo 'validation'".to_string(),
 ///     timeout: Some(5000),
 ///     description: None,
+///     priority: None,
 /// };
 ///
 /// let hook_file = Path::new(".claude/hooks/test.json");
@@ -533,6 +816,7 @@ mod tests {
             command: ".claude/scripts/security-check.sh".to_string(),
             timeout: Some(5000),
             description: Some("Security validation".to_string()),
+            priority: None,
         };
 
         let json = serde_json::to_string_pretty(&config).unwrap();
@@ -554,6 +838,7 @@ mod tests {
             command: "echo 'test'".to_string(),
             timeout: None,
             description: None,
+            priority: None,
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -569,6 +854,7 @@ mod tests {
             source: "community".to_string(),
             version: "v1.0.0".to_string(),
             installed_at: "2024-01-01T00:00:00Z".to_string(),
+            merged_from: Vec::new(),
         };
 
         let command = HookCommand {
@@ -625,6 +911,7 @@ mod tests {
             command: "test1.sh".to_string(),
             timeout: None,
             description: None,
+            priority: None,
         };
 
         let config2 = HookConfig {
@@ -634,6 +921,7 @@ mod tests {
             command: "test2.sh".to_string(),
             timeout: Some(1000),
             description: Some("Test hook 2".to_string()),
+            priority: None,
         };
 
         fs::write(hooks_dir.join("test-hook1.json"), serde_json::to_string(&config1).unwrap())
@@ -692,6 +980,7 @@ mod tests {
             command: "test.sh".to_string(),
             timeout: None,
             description: None,
+            priority: None,
         };
 
         let result = validate_hook_config(&config, temp.path());
@@ -710,6 +999,7 @@ mod tests {
             command: "test.sh".to_string(),
             timeout: None,
             description: None,
+            priority: None,
         };
 
         let result = validate_hook_config(&config, temp.path());
@@ -728,6 +1018,7 @@ mod tests {
             command: "test.sh".to_string(),
             timeout: None,
             description: None,
+            priority: None,
         };
 
         let result = validate_hook_config(&config, temp.path());
@@ -755,6 +1046,7 @@ mod tests {
             command: ".claude/scripts/test.sh".to_string(),
             timeout: None,
             description: None,
+            priority: None,
         };
 
         // Hooks are merged into settings.local.json, but for validation purposes
@@ -778,6 +1070,7 @@ mod tests {
             command: ".claude/scripts/nonexistent.sh".to_string(),
             timeout: None,
             description: None,
+            priority: None,
         };
 
         // Pass the hook file path
@@ -799,6 +1092,7 @@ mod tests {
             command: "/usr/bin/echo".to_string(), // Absolute path not in .claude
             timeout: None,
             description: None,
+            priority: None,
         };
 
         let result = validate_hook_config(&config, temp.path());
@@ -820,6 +1114,7 @@ mod tests {
                 command: "echo 'session started'".to_string(),
                 timeout: Some(1000),
                 description: Some("Session start hook".to_string()),
+                priority: None,
             },
         );
 
@@ -854,6 +1149,7 @@ mod tests {
                 command: "echo 'before tool use'".to_string(),
                 timeout: None,
                 description: None,
+                priority: None,
             },
         );
 
@@ -888,6 +1184,7 @@ mod tests {
                 command: "echo 'tool event'".to_string(),
                 timeout: Some(5000),
                 description: None,
+                priority: None,
             },
         );
 
@@ -923,6 +1220,7 @@ mod tests {
                 command: "agpm update".to_string(),
                 timeout: None,
                 description: None,
+                priority: None,
             },
         );
         hook_configs.insert(
@@ -934,10 +1232,11 @@ mod tests {
                 command: "agpm update".to_string(), // Same command
                 timeout: None,
                 description: None,
+                priority: None,
             },
         );
 
-        let result = convert_to_claude_format(hook_configs).unwrap();
+        let result = convert_to_claude_format(hook_configs.clone()).unwrap();
         let session_start = result.get("SessionStart").unwrap().as_array().unwrap();
 
         // Should have only one group
@@ -947,6 +1246,13 @@ mod tests {
         let hooks = session_start[0].get("hooks").unwrap().as_array().unwrap();
         assert_eq!(hooks.len(), 1);
         assert_eq!(hooks[0].get("command").unwrap().as_str().unwrap(), "agpm update");
+
+        // A different timeout makes them different hooks
+        hook_configs.get_mut("hook2").unwrap().timeout = Some(5000);
+        let result = convert_to_claude_format(hook_configs).unwrap();
+        let session_start = result.get("SessionStart").unwrap().as_array().unwrap();
+        let hooks = session_start[0].get("hooks").unwrap().as_array().unwrap();
+        assert_eq!(hooks.len(), 2);
     }
 
     #[test]
@@ -963,6 +1269,7 @@ mod tests {
                 command: "echo 'bash tool'".to_string(),
                 timeout: None,
                 description: None,
+                priority: None,
             },
         );
         hook_configs.insert(
@@ -974,6 +1281,7 @@ mod tests {
                 command: "echo 'write tool'".to_string(),
                 timeout: None,
                 description: None,
+                priority: None,
             },
         );
 
@@ -1011,6 +1319,7 @@ mod tests {
                 command: "echo 'first'".to_string(),
                 timeout: None,
                 description: None,
+                priority: None,
             },
         );
         hook_configs.insert(
@@ -1022,6 +1331,7 @@ mod tests {
                 command: "echo 'second'".to_string(),
                 timeout: None,
                 description: None,
+                priority: None,
             },
         );
 
@@ -1059,6 +1369,7 @@ mod tests {
                 command: "echo 'future event'".to_string(),
                 timeout: None,
                 description: None,
+                priority: None,
             },
         );
 
@@ -1093,4 +1404,295 @@ mod tests {
             panic!("Expected Other variant");
         }
     }
+
+    #[test]
+    fn test_convert_to_claude_format_priority_ordering() {
+        // Lower priority runs first; ties are broken by name
+        let make = |command: &str, priority: Option<i32>| HookConfig {
+            events: vec![HookEvent::UserPromptSubmit],
+            matcher: None,
+            hook_type: "command".to_string(),
+            command: command.to_string(),
+            timeout: None,
+            description: None,
+            priority,
+        };
+
+        let mut hook_configs = HashMap::new();
+        hook_configs.insert("validate".to_string(), make("validate.sh", Some(10)));
+        hook_configs.insert("setup".to_string(), make("setup.sh", Some(-5)));
+        hook_configs.insert("b-default".to_string(), make("b.sh", None));
+        hook_configs.insert("a-default".to_string(), make("a.sh", None));
+
+        let result = convert_to_claude_format(hook_configs).unwrap();
+        let groups = result.get("UserPromptSubmit").unwrap().as_array().unwrap();
+        assert_eq!(groups.len(), 1);
+
+        let commands: Vec<&str> = groups[0]
+            .get("hooks")
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|h| h.get("command").unwrap().as_str().unwrap())
+            .collect();
+        assert_eq!(commands, vec!["setup.sh", "a.sh", "b.sh", "validate.sh"]);
+    }
+
+    #[test]
+    fn test_convert_to_claude_format_deterministic() {
+        // Repeated conversions must produce identical output regardless of map order
+        let matchers = ["Bash", "Write"];
+        let build = || {
+            let mut hook_configs = HashMap::new();
+            for i in 0..10 {
+                hook_configs.insert(
+                    format!("hook-{i}"),
+                    HookConfig {
+                        events: vec![HookEvent::PreToolUse],
+                        matcher: Some(matchers[i % 2].to_string()),
+                        hook_type: "command".to_string(),
+                        command: format!("hook-{i}.sh"),
+                        timeout: None,
+                        description: None,
+                        priority: Some(10 - i as i32),
+                    },
+                );
+            }
+            convert_to_claude_format(hook_configs).unwrap()
+        };
+
+        let first = build();
+        for _ in 0..5 {
+            assert_eq!(build(), first);
+        }
+    }
+
+    fn command_hook(events: Vec<HookEvent>, matcher: Option<&str>, command: &str) -> HookConfig {
+        HookConfig {
+            events,
+            matcher: matcher.map(str::to_string),
+            hook_type: "command".to_string(),
+            command: command.to_string(),
+            timeout: None,
+            description: None,
+            priority: None,
+        }
+    }
+
+    fn source_info_for(names: &[&str]) -> HookSourceInfo {
+        names
+            .iter()
+            .map(|name| (name.to_string(), ("community".to_string(), "v1.0.0".to_string())))
+            .collect()
+    }
+
+    #[test]
+    fn test_collect_hook_entries_exact_duplicates() {
+        let mut hook_configs = HashMap::new();
+        hook_configs.insert(
+            "lint-a".to_string(),
+            command_hook(vec![HookEvent::PreToolUse], Some("Bash"), "lint.sh"),
+        );
+        hook_configs.insert(
+            "lint-b".to_string(),
+            command_hook(vec![HookEvent::PreToolUse], Some("Bash"), "lint.sh"),
+        );
+
+        let entries = collect_hook_entries(hook_configs);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].contributors, vec!["lint-a", "lint-b"]);
+
+        let info = source_info_for(&["lint-a", "lint-b"]);
+        let rendered =
+            render_hook_entries(&entries, Some((&info, "2024-01-01T00:00:00Z"))).unwrap();
+        let hooks = rendered["PreToolUse"][0]["hooks"].as_array().unwrap();
+        assert_eq!(hooks.len(), 1);
+        assert_eq!(hooks[0]["_agpm"]["dependency_name"], "lint-a");
+        assert_eq!(hooks[0]["_agpm"]["merged_from"], serde_json::json!(["lint-b"]));
+    }
+
+    #[test]
+    fn test_collect_hook_entries_different_matchers_preserved() {
+        let mut hook_configs = HashMap::new();
+        hook_configs.insert(
+            "bash-lint".to_string(),
+            command_hook(vec![HookEvent::PreToolUse], Some("Bash"), "lint.sh"),
+        );
+        hook_configs.insert(
+            "write-lint".to_string(),
+            command_hook(vec![HookEvent::PreToolUse], Some("Write"), "lint.sh"),
+        );
+        hook_configs.insert(
+            "any-lint".to_string(),
+            command_hook(vec![HookEvent::PreToolUse], None, "lint.sh"),
+        );
+
+        let entries = collect_hook_entries(hook_configs);
+        assert_eq!(entries.len(), 3);
+        assert!(entries.iter().all(|entry| entry.contributors.len() == 1));
+
+        let result = convert_to_claude_format(HashMap::from([
+            (
+                "bash-lint".to_string(),
+                command_hook(vec![HookEvent::PreToolUse], Some("Bash"), "lint.sh"),
+            ),
+            (
+                "write-lint".to_string(),
+                command_hook(vec![HookEvent::PreToolUse], Some("Write"), "lint.sh"),
+            ),
+        ]))
+        .unwrap();
+        assert_eq!(result["PreToolUse"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_merge_with_user_hooks_preserves_user_entries() {
+        // A user hook identical to an AGPM hook must not be deduplicated or removed
+        let existing = serde_json::json!({
+            "PreToolUse": [{
+                "matcher": "Bash",
+                "hooks": [
+                    { "type": "command", "command": "lint.sh" },
+                    {
+                        "type": "command",
+                        "command": "stale.sh",
+                        "_agpm": {
+                            "managed": true,
+                            "dependency_name": "removed-hook",
+                            "source": "community",
+                            "version": "v0.9.0",
+                            "installed_at": "2023-01-01T00:00:00Z"
+                        }
+                    }
+                ]
+            }],
+            "Stop": [{
+                "hooks": [{ "type": "command", "command": "notify.sh" }]
+            }]
+        });
+
+        let mut hook_configs = HashMap::new();
+        hook_configs.insert(
+            "lint".to_string(),
+            command_hook(vec![HookEvent::PreToolUse], Some("Bash"), "lint.sh"),
+        );
+        let entries = collect_hook_entries(hook_configs);
+        let info = source_info_for(&["lint"]);
+        let managed = render_hook_entries(&entries, Some((&info, "2024-01-01T00:00:00Z"))).unwrap();
+
+        let merged = merge_with_user_hooks(Some(&existing), managed);
+
+        // User Stop hook is untouched
+        assert_eq!(merged["Stop"], existing["Stop"]);
+
+        // User group keeps its user hook, stale managed hook is dropped,
+        // and the AGPM hook lives in its own group afterwards
+        let pre_tool = merged["PreToolUse"].as_array().unwrap();
+        assert_eq!(pre_tool.len(), 2);
+        assert_eq!(
+            pre_tool[0]["hooks"],
+            serde_json::json!([{ "type": "command", "command": "lint.sh" }])
+        );
+        let managed_hooks = pre_tool[1]["hooks"].as_array().unwrap();
+        assert_eq!(managed_hooks.len(), 1);
+        assert_eq!(managed_hooks[0]["command"], "lint.sh");
+        assert_eq!(managed_hooks[0]["_agpm"]["dependency_name"], "lint");
+    }
+
+    #[test]
+    fn test_without_install_timestamps_ignores_reinstall() {
+        let mut hook_configs = HashMap::new();
+        hook_configs.insert(
+            "lint".to_string(),
+            command_hook(vec![HookEvent::PreToolUse], Some("Bash"), "lint.sh"),
+        );
+        let entries = collect_hook_entries(hook_configs);
+        let info = source_info_for(&["lint"]);
+
+        let first = serde_json::Value::Object(
+            render_hook_entries(&entries, Some((&info, "2024-01-01T00:00:00Z"))).unwrap(),
+        );
+        let second = serde_json::Value::Object(
+            render_hook_entries(&entries, Some((&info, "2025-06-01T00:00:00Z"))).unwrap(),
+        );
+
+        assert_ne!(first, second);
+        assert_eq!(without_install_timestamps(&first), without_install_timestamps(&second));
+    }
+
+    #[test]
+    fn test_remove_managed_hooks() {
+        let temp = tempdir().unwrap();
+        // Settings backups are placed relative to the project root (located via agpm.toml)
+        fs::write(temp.path().join("agpm.toml"), "[sources]\n").unwrap();
+        let claude_dir = temp.path().join(".claude");
+        fs::create_dir_all(&claude_dir).unwrap();
+        let settings_path = claude_dir.join("settings.local.json");
+
+        let managed = |name: &str, merged_from: &[&str]| {
+            serde_json::json!({
+                "managed": true,
+                "dependency_name": name,
+                "merged_from": merged_from,
+                "source": "community",
+                "version": "v1.0.0",
+                "installed_at": "2024-01-01T00:00:00Z"
+            })
+        };
+        let settings = serde_json::json!({
+            "hooks": {
+                "PreToolUse": [
+                    {
+                        "matcher": "Bash",
+                        "hooks": [
+                            { "type": "command", "command": "user.sh" },
+                            { "type": "command", "command": "shared.sh", "_agpm": managed("a", &["b"]) }
+                        ]
+                    },
+                    {
+                        "matcher": "Write",
+                        "hooks": [
+                            { "type": "command", "command": "gone.sh", "_agpm": managed("c", &[]) }
+                        ]
+                    }
+                ],
+                "Stop": [
+                    { "hooks": [] }
+                ]
+            },
+            "permissions": { "allow": ["Bash"] }
+        });
+        fs::write(&settings_path, serde_json::to_string_pretty(&settings).unwrap()).unwrap();
+
+        let dropped: HashSet<String> = ["a".to_string(), "c".to_string()].into_iter().collect();
+        let removed = remove_managed_hooks(temp.path(), &dropped).unwrap();
+        assert_eq!(removed, 1);
+
+        let updated: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&settings_path).unwrap()).unwrap();
+
+        // Unrelated settings and user-authored entries are preserved exactly
+        assert_eq!(updated["permissions"], settings["permissions"]);
+        assert_eq!(updated["hooks"]["Stop"], settings["hooks"]["Stop"]);
+
+        // The Write group only contained the dropped hook and is removed
+        let pre_tool = updated["hooks"]["PreToolUse"].as_array().unwrap();
+        assert_eq!(pre_tool.len(), 1);
+
+        // The shared hook survives with its remaining contributor promoted
+        let hooks = pre_tool[0]["hooks"].as_array().unwrap();
+        assert_eq!(hooks.len(), 2);
+        assert_eq!(hooks[0]["command"], "user.sh");
+        assert_eq!(hooks[1]["_agpm"]["dependency_name"], "b");
+        assert!(hooks[1]["_agpm"].get("merged_from").is_none());
+    }
+
+    #[test]
+    fn test_remove_managed_hooks_no_settings() {
+        let temp = tempdir().unwrap();
+        let dropped: HashSet<String> = ["a".to_string()].into_iter().collect();
+        assert_eq!(remove_managed_hooks(temp.path(), &dropped).unwrap(), 0);
+        assert!(!temp.path().join(".claude").join("settings.local.json").exists());
+    }
 }
@@ -1,7 +1,10 @@
 //! Cleanup utilities for removing obsolete artifacts.
 
-use crate::lockfile::LockFile;
+use crate::core::ResourceType;
+use crate::lockfile::{LockFile, LockedResource};
+use crate::manifest::Manifest;
 use anyhow::{Context, Result};
+use std::collections::{HashMap, HashSet};
 
 /// Removes artifacts that are no longer needed based on lockfile comparison.
 ///
@@ -198,8 +201,6 @@ pub async fn cleanup_removed_artifacts(
     new_lockfile: &LockFile,
     project_dir: &std::path::Path,
 ) -> Result<Vec<String>> {
-    use std::collections::HashSet;
-
     let mut removed = Vec::new();
 
     // Collect installed paths from new lockfile (only resources that should have files on disk)
@@ -213,9 +214,28 @@ pub async fn cleanup_removed_artifacts(
 
     // Check each old resource
     for old_resource in old_lockfile.all_resources() {
+        // Hooks and MCP servers share merge-target files with user configuration;
+        // their entries are cleaned up by `cleanup_removed_merged_entries` instead
+        if is_merged_resource(old_resource.resource_type) {
+            continue;
+        }
+
         // If the old path doesn't exist in new lockfile, it needs to be removed
         if !new_paths.contains(&old_resource.installed_at) {
-            let full_path = project_dir.join(&old_resource.installed_at);
+            // The old lockfile may be hand-edited; never delete outside the project
+            // (or the project root itself, which an empty path would resolve to)
+            let full_path =
+                match super::resource::resolve_install_destination(project_dir, old_resource) {
+                    Ok(path) => path,
+                    Err(e) => {
+                        tracing::warn!(
+                            "Skipping cleanup of '{}' ({}): {e}",
+                            old_resource.name,
+                            old_resource.installed_at
+                        );
+                        continue;
+                    }
+                };
 
             tracing::debug!(
                 "Cleanup: old path not in new lockfile - name={}, path={}, install={:?}, exists={}",
@@ -225,23 +245,135 @@ pub async fn cleanup_removed_artifacts(
                 full_path.exists()
             );
 
-            // Only remove if the file actually exists
-            if full_path.exists() {
+            // Only remove if the file (or directory, for directory installs) actually exists
+            if full_path.is_dir() {
+                tokio::fs::remove_dir_all(&full_path).await.with_context(|| {
+                    format!("Failed to remove old artifact directory: {}", full_path.display())
+                })?;
+            } else if full_path.exists() {
                 tokio::fs::remove_file(&full_path).await.with_context(|| {
                     format!("Failed to remove old artifact: {}", full_path.display())
                 })?;
+            } else {
+                continue;
+            }
 
-                removed.push(old_resource.installed_at.clone());
+            removed.push(old_resource.installed_at.clone());
 
-                // Try to clean up empty parent directories
-                cleanup_empty_dirs(&full_path).await?;
-            }
+            // Try to clean up empty parent directories
+            cleanup_empty_dirs(project_dir, &full_path).await?;
         }
     }
 
     Ok(removed)
 }
 
+/// Finds resources whose install location changed between two lockfiles.
+///
+/// Resources are matched by [`ResourceId`](crate::lockfile::ResourceId)
+/// ignoring the install path, preferring an entry at the same path, so a
+/// dependency whose `target` or `filename` changed is paired with its new
+/// entry. Returns `(old, new)` pairs; the old locations are removed by
+/// [`cleanup_removed_artifacts`] unless another resource now installs there.
+/// Content-only (`install = false`) and merged resources are not included.
+#[must_use]
+pub fn find_moved_artifacts<'a>(
+    old_lockfile: &'a LockFile,
+    new_lockfile: &'a LockFile,
+) -> Vec<(&'a LockedResource, &'a LockedResource)> {
+    old_lockfile
+        .all_resources()
+        .into_iter()
+        .filter(|old| !is_merged_resource(old.resource_type) && old.install != Some(false))
+        .filter_map(|old| {
+            let new = new_lockfile
+                .find_resource_by_id(&old.id())
+                .or_else(|| new_lockfile.find_resource_by_id(&old.id().ignoring_install_path()))?;
+            (new.install != Some(false) && new.installed_at != old.installed_at)
+                .then_some((old, new))
+        })
+        .collect()
+}
+
+/// Removes hook and MCP server entries whose dependencies were dropped from the lockfile.
+///
+/// Hooks and MCP servers are merged into shared configuration files
+/// (`.claude/settings.local.json`, `.mcp.json`, `.opencode/opencode.json`) rather than
+/// installed as standalone files, so [`cleanup_removed_artifacts`] leaves them alone.
+/// This function diffs the old and new lockfiles and removes only the AGPM-managed
+/// entries that belonged to dependencies no longer present, preserving any
+/// user-authored entries in the same files.
+///
+/// MCP servers are diffed per tool so that a server moving from one tool to another
+/// is removed from the old tool's configuration.
+///
+/// # Returns
+///
+/// `(hooks_removed, servers_removed)` - the number of entries removed from each kind
+/// of merge target.
+///
+/// # Errors
+///
+/// Returns an error if a configuration file cannot be read, parsed, or written.
+pub fn cleanup_removed_merged_entries(
+    old_lockfile: &LockFile,
+    new_lockfile: &LockFile,
+    manifest: &Manifest,
+    project_dir: &std::path::Path,
+) -> Result<(usize, usize)> {
+    // Hooks are tracked by canonical dependency name in `_agpm` metadata
+    let current_hooks: HashSet<&str> =
+        new_lockfile.hooks.iter().map(|entry| entry.name.as_str()).collect();
+    let dropped_hooks: HashSet<String> = old_lockfile
+        .hooks
+        .iter()
+        .filter(|entry| !current_hooks.contains(entry.name.as_str()))
+        .map(|entry| entry.name.clone())
+        .collect();
+    let hooks_removed = crate::hooks::remove_managed_hooks(project_dir, &dropped_hooks)?;
+
+    // MCP servers are keyed by lookup name within each tool's configuration
+    let server_key = |entry: &crate::lockfile::LockedResource| {
+        (
+            entry.tool.clone().unwrap_or_else(|| "claude-code".to_string()),
+            entry.lookup_name().to_string(),
+        )
+    };
+    let current_servers: HashSet<(String, String)> =
+        new_lockfile.mcp_servers.iter().map(server_key).collect();
+    let mut dropped_servers: HashMap<String, HashSet<String>> = HashMap::new();
+    for (tool, name) in old_lockfile.mcp_servers.iter().map(server_key) {
+        if !current_servers.contains(&(tool.clone(), name.clone())) {
+            dropped_servers.entry(tool).or_default().insert(name);
+        }
+    }
+
+    let mut servers_removed = 0;
+    for (tool, names) in dropped_servers {
+        use crate::mcp::handlers::McpHandler;
+
+        let (Some(handler), Some(tool_config)) =
+            (crate::mcp::handlers::get_mcp_handler(&tool), manifest.get_tool_config(&tool))
+        else {
+            tracing::debug!("Skipping MCP cleanup for tool '{}' without a handler", tool);
+            continue;
+        };
+
+        let artifact_base = project_dir.join(&tool_config.path);
+        servers_removed += handler
+            .remove_mcp_servers(project_dir, &artifact_base, &names)
+            .with_context(|| format!("Failed to remove stale MCP servers for tool '{tool}'"))?;
+    }
+
+    Ok((hooks_removed, servers_removed))
+}
+
+/// Whether a resource type is merged into a shared configuration file instead of
+/// being installed as its own file.
+fn is_merged_resource(resource_type: ResourceType) -> bool {
+    matches!(resource_type, ResourceType::Hook | ResourceType::McpServer)
+}
+
 /// Recursively removes empty parent directories up to the project root.
 ///
 /// This helper function performs bottom-up directory cleanup after file removal.
@@ -268,12 +400,14 @@ pub async fn cleanup_removed_artifacts(
 ///
 /// The function enforces strict boundaries to prevent accidental data loss:
 /// - **`.claude/` boundary**: Never removes the `.claude` directory itself
-/// - **Project root**: Stops if parent directory is None
+/// - **Project root**: Never walks up to or past `project_dir`, so directories
+///   outside the project are left alone even when they are empty
 /// - **Non-empty guard**: Only removes truly empty directories
 /// - **Error tolerance**: ENOENT (directory not found) is not considered an error
 ///
 /// # Arguments
 ///
+/// * `project_dir` - The project root; cleanup stops before reaching it
 /// * `file_path` - The path to the removed file whose parent directories should be cleaned.
 ///   Typically this is the full path to a resource file that was just deleted.
 ///
@@ -304,7 +438,7 @@ pub async fn cleanup_removed_artifacts(
 /// // If this was the last file in specialized/, the directory will be removed
 /// // If specialized/ was the last item in rust/, that will be removed too
 /// // Cleanup stops at .claude/agents/ or when it finds a non-empty directory
-/// cleanup_empty_dirs(file_path).await?;
+/// cleanup_empty_dirs(Path::new("."), file_path).await?;
 /// # Ok(())
 /// # }
 /// ```
@@ -364,7 +498,7 @@ pub async fn cleanup_removed_artifacts(
 ///             removed.push(old_resource.installed_at.clone());
 ///
 ///             // Automatic directory cleanup after file removal
-///             cleanup_empty_dirs(&full_path).await?;
+///             cleanup_empty_dirs(project_dir, &full_path).await?;
 ///         }
 ///     }
 /// }
@@ -450,12 +584,15 @@ pub async fn cleanup_removed_artifacts(
 ///
 /// - **v0.3.18**: Introduced alongside [`cleanup_removed_artifacts`]
 /// - Complements relative path preservation by cleaning up old directory structures
-async fn cleanup_empty_dirs(file_path: &std::path::Path) -> Result<()> {
+async fn cleanup_empty_dirs(
+    project_dir: &std::path::Path,
+    file_path: &std::path::Path,
+) -> Result<()> {
     let mut current = file_path.parent();
 
     while let Some(dir) = current {
-        // Stop if we've reached .claude or the project root
-        if dir.ends_with(".claude") || dir.parent().is_none() {
+        // Stop at .claude, the project root, or anything outside the project
+        if dir.ends_with(".claude") || !dir.starts_with(project_dir) || dir == project_dir {
             break;
         }
 
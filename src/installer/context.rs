@@ -41,8 +41,9 @@ use anyhow::Result;
 use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, Semaphore};
 
+use super::dedup::ContentDedup;
 use crate::cache::Cache;
 use crate::lockfile::LockFile;
 use crate::manifest::Manifest;
@@ -75,6 +76,10 @@ pub struct InstallContext<'a> {
     pub private_patches: Option<&'a crate::manifest::ManifestPatches>,
     pub gitignore_lock: Option<&'a Arc<Mutex<()>>>,
     pub max_content_file_size: Option<u64>,
+    /// Registry for hardlinking identical files, when deduplication is enabled
+    pub dedup: Option<&'a Arc<ContentDedup>>,
+    /// Bounds how many resources write to disk at once, separately from `--max-parallel`
+    pub io_limit: Option<&'a Arc<Semaphore>>,
     /// Shared template context builder for all resources
     pub template_context_builder: Arc<crate::templating::TemplateContextBuilder>,
 }
@@ -97,6 +102,8 @@ pub struct InstallContextBuilder<'a> {
     private_patches: Option<&'a crate::manifest::ManifestPatches>,
     gitignore_lock: Option<&'a Arc<Mutex<()>>>,
     max_content_file_size: Option<u64>,
+    dedup: Option<&'a Arc<ContentDedup>>,
+    io_limit: Option<&'a Arc<Semaphore>>,
 }
 
 impl<'a> InstallContextBuilder<'a> {
@@ -114,6 +121,8 @@ impl<'a> InstallContextBuilder<'a> {
             private_patches: None,
             gitignore_lock: None,
             max_content_file_size: None,
+            dedup: None,
+            io_limit: None,
         }
     }
 
@@ -165,6 +174,18 @@ impl<'a> InstallContextBuilder<'a> {
         self
     }
 
+    /// Set the registry used to hardlink files with identical content.
+    pub fn dedup(mut self, dedup: Option<&'a Arc<ContentDedup>>) -> Self {
+        self.dedup = dedup;
+        self
+    }
+
+    /// Set the semaphore bounding concurrent file writes.
+    pub fn io_limit(mut self, io_limit: Option<&'a Arc<Semaphore>>) -> Self {
+        self.io_limit = io_limit;
+        self
+    }
+
     /// Set commonly used options in a single call.
     ///
     /// This method groups frequently used options to reduce the number of
@@ -218,7 +239,9 @@ impl<'a> InstallContextBuilder<'a> {
             lockfile_for_builder,
             project_config,
             Arc::new(self.cache.clone()),
-            self.project_dir.to_path_buf(),
+            self.manifest
+                .and_then(|m| m.manifest_dir.clone())
+                .unwrap_or_else(|| self.project_dir.to_path_buf()),
         ));
 
         InstallContext {
@@ -233,6 +256,8 @@ impl<'a> InstallContextBuilder<'a> {
             private_patches: self.private_patches,
             gitignore_lock: self.gitignore_lock,
             max_content_file_size: self.max_content_file_size,
+            dedup: self.dedup,
+            io_limit: self.io_limit,
             template_context_builder,
         }
     }
@@ -244,6 +269,14 @@ impl<'a> InstallContext<'a> {
         InstallContextBuilder::new(project_dir, cache)
     }
 
+    /// Directory that local dependency paths are resolved against.
+    ///
+    /// This is the manifest's directory, which differs from `project_dir`
+    /// when installing into another directory with `install --target-dir`.
+    pub fn manifest_dir(&self) -> &Path {
+        self.manifest.and_then(|m| m.manifest_dir.as_deref()).unwrap_or(self.project_dir)
+    }
+
     /// Create an InstallContext with common options for parallel installation.
     ///
     /// This helper function reduces code duplication by handling the common pattern
@@ -325,6 +358,15 @@ impl<'a> InstallContext<'a> {
 /// Only `NotFound` errors are retried, as these indicate cache coherency issues.
 /// Other errors (permissions, I/O errors) fail immediately by returning Ok to bypass retry.
 pub(crate) async fn read_with_cache_retry(path: &Path) -> Result<String> {
+    let bytes = read_bytes_with_cache_retry(path).await?;
+    String::from_utf8(bytes).map_err(|e| {
+        anyhow::anyhow!("Failed to read resource file: {}: {}", path.display(), e.utf8_error())
+    })
+}
+
+/// Read a file's raw bytes with the same cache coherency retry as
+/// [`read_with_cache_retry`].
+pub(crate) async fn read_bytes_with_cache_retry(path: &Path) -> Result<Vec<u8>> {
     use std::io;
 
     let retry_strategy = tokio_retry::strategy::ExponentialBackoff::from_millis(10)
@@ -337,7 +379,7 @@ pub(crate) async fn read_with_cache_retry(path: &Path) -> Result<String> {
     tokio_retry::Retry::spawn(retry_strategy, || {
         let path = path_buf.clone();
         async move {
-            tokio::fs::read_to_string(&path).await.map_err(|e| {
+            tokio::fs::read(&path).await.map_err(|e| {
                 if e.kind() == io::ErrorKind::NotFound {
                     tracing::debug!(
                         "File not yet visible (likely cache coherency issue): {}",
@@ -0,0 +1,159 @@
+//! Hardlinking of installed files with identical content.
+//!
+//! Multi-tool installs often write the same bytes to several places, for
+//! example a shared snippet installed for both Claude Code and OpenCode. With
+//! `dedup = true` in the global configuration, the CLI calls [`enable`] and
+//! each installation run keeps a [`ContentDedup`] that remembers the first
+//! file written for every content checksum. Later files with the same
+//! checksum are hardlinked to it instead of being written again.
+//!
+//! Every lockfile entry still has its own installed path and checksum, and a
+//! hardlinked file reads back exactly like a copy, so checksum verification is
+//! unaffected. Links are created under a temporary name and renamed into
+//! place, and AGPM's later writes replace files by renaming too, so when AGPM
+//! updates one installed file the other paths keep their content. Editing a
+//! linked file in place, as most editors do, changes every path that shares
+//! it.
+//!
+//! Linking falls back to a regular write on Windows, when the first file is
+//! on a different filesystem, or whenever the link cannot be created.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::resource::compute_file_checksum;
+use crate::utils::fs::atomic_write;
+
+/// Whether deduplication was enabled for this process by [`enable`].
+static DEDUP_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Enable hardlinking of identical installed files for this process.
+pub fn enable() {
+    DEDUP_ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Whether [`enable`] has been called.
+#[must_use]
+pub fn is_enabled() -> bool {
+    DEDUP_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Files installed during one run, keyed by content checksum.
+#[derive(Debug, Default)]
+pub struct ContentDedup {
+    installed: Mutex<HashMap<String, PathBuf>>,
+}
+
+impl ContentDedup {
+    /// Create an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that `path` already holds `content`, so later files can link to it.
+    pub fn remember(&self, path: &Path, content: &str) {
+        let checksum = compute_file_checksum(content);
+        self.installed
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .entry(checksum)
+            .or_insert_with(|| path.to_path_buf());
+    }
+
+    /// Install `content` at `dest`, hardlinking to an identical file when possible.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the fallback write fails.
+    pub fn write(&self, dest: &Path, content: &str) -> Result<()> {
+        let checksum = compute_file_checksum(content);
+        let existing = self
+            .installed
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .get(&checksum)
+            .cloned();
+
+        if let Some(existing) = existing.filter(|existing| existing != dest)
+            && link_identical(&existing, dest, content.as_bytes())
+        {
+            tracing::debug!("Hardlinked {} to {}", dest.display(), existing.display());
+            return Ok(());
+        }
+
+        atomic_write(dest, content.as_bytes())
+            .with_context(|| format!("Failed to install resource to {}", dest.display()))?;
+        self.installed
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .entry(checksum)
+            .or_insert_with(|| dest.to_path_buf());
+        Ok(())
+    }
+}
+
+/// Replace `dest` with a hardlink to `existing` if it still holds `content`.
+///
+/// Returns `false`, leaving `dest` untouched, when linking is not possible.
+fn link_identical(existing: &Path, dest: &Path, content: &[u8]) -> bool {
+    if cfg!(windows) {
+        return false;
+    }
+    // The first file may have been modified since it was recorded
+    if std::fs::read(existing).ok().as_deref() != Some(content) {
+        return false;
+    }
+
+    let temp_path = dest.with_extension("agpm-link");
+    let _ = std::fs::remove_file(&temp_path);
+    if std::fs::hard_link(existing, &temp_path).is_err() {
+        return false;
+    }
+    if std::fs::rename(&temp_path, dest).is_err() {
+        let _ = std::fs::remove_file(&temp_path);
+        return false;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_identical_content_is_hardlinked() {
+        let temp = TempDir::new().unwrap();
+        let first = temp.path().join(".claude/snippets/utils.md");
+        let second = temp.path().join(".opencode/snippet/utils.md");
+        let other = temp.path().join(".claude/snippets/other.md");
+        std::fs::create_dir_all(first.parent().unwrap()).unwrap();
+        std::fs::create_dir_all(second.parent().unwrap()).unwrap();
+
+        let dedup = ContentDedup::new();
+        dedup.write(&first, "# Utils\n").unwrap();
+        dedup.write(&second, "# Utils\n").unwrap();
+        dedup.write(&other, "# Other\n").unwrap();
+
+        assert_eq!(std::fs::read_to_string(&second).unwrap(), "# Utils\n");
+        assert_eq!(std::fs::read_to_string(&other).unwrap(), "# Other\n");
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            let first_meta = std::fs::metadata(&first).unwrap();
+            let second_meta = std::fs::metadata(&second).unwrap();
+            assert_eq!(first_meta.ino(), second_meta.ino());
+            assert_ne!(first_meta.ino(), std::fs::metadata(&other).unwrap().ino());
+        }
+
+        // Rewriting one path replaces it without touching the other
+        dedup.write(&second, "# Utils v2\n").unwrap();
+        assert_eq!(std::fs::read_to_string(&first).unwrap(), "# Utils\n");
+        assert_eq!(std::fs::read_to_string(&second).unwrap(), "# Utils v2\n");
+    }
+}
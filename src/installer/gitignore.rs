@@ -1,6 +1,20 @@
 //! Gitignore management utilities for AGPM resources.
+//!
+//! Installed resources are listed in a managed section of the project
+//! `.gitignore`. Resources that should be committed instead, such as reviewed
+//! agents, are kept out of that section by listing their installed paths in a
+//! `.agpmignore` file at the project root, one glob pattern per line:
+//!
+//! ```text
+//! # Reviewed agents are committed with the project
+//! .claude/agents/reviewed/
+//! .claude/commands/deploy.md
+//! ```
+//!
+//! A pattern matches an installed path itself or any directory containing it.
 
 use anyhow::{Context, Result};
+use glob::{MatchOptions, Pattern};
 use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
@@ -25,6 +39,74 @@ pub(crate) fn sanitize_path_for_error(path: &Path) -> String {
     }
 }
 
+/// Name of the file listing installed paths to keep out of the managed section.
+pub const AGPMIGNORE_FILE: &str = ".agpmignore";
+
+/// Installed paths excluded from the managed `.gitignore` section by `.agpmignore`.
+#[derive(Debug, Default)]
+pub struct CommittedPaths {
+    patterns: Vec<Pattern>,
+}
+
+impl CommittedPaths {
+    /// Read the `.agpmignore` file of `project_dir`, if there is one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or contains an invalid pattern.
+    pub fn load(project_dir: &Path) -> Result<Self> {
+        let path = project_dir.join(AGPMIGNORE_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", sanitize_path_for_error(&path)))?;
+        Self::parse(&content)
+    }
+
+    /// Parse `.agpmignore` content: one pattern per line, `#` starts a comment.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a line is not a valid glob pattern.
+    pub fn parse(content: &str) -> Result<Self> {
+        let patterns = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let pattern = line.trim_start_matches("./").trim_start_matches('/');
+                Pattern::new(pattern.trim_end_matches('/'))
+                    .with_context(|| format!("Invalid pattern '{line}' in {AGPMIGNORE_FILE}"))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            patterns,
+        })
+    }
+
+    /// Whether `path` (relative to the project root) should be committed.
+    #[must_use]
+    pub fn contains(&self, path: &str) -> bool {
+        if self.patterns.is_empty() {
+            return false;
+        }
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..MatchOptions::new()
+        };
+        let path = normalize_path_for_storage(path);
+        let path = path.strip_prefix("./").unwrap_or(&path);
+
+        // Match the path itself and every directory above it
+        let mut candidates = vec![path];
+        candidates.extend(path.match_indices('/').map(|(index, _)| &path[..index]));
+        candidates.iter().any(|candidate| {
+            self.patterns.iter().any(|pattern| pattern.matches_with(candidate, options))
+        })
+    }
+}
+
 /// Add a single path to .gitignore atomically
 ///
 /// This function adds a single path to the AGPM-managed section of `.gitignore`,
@@ -39,7 +121,8 @@ pub(crate) fn sanitize_path_for_error(path: &Path) -> String {
 ///
 /// # Returns
 ///
-/// Returns `Ok(())` if the path was added successfully or was already present.
+/// Returns `Ok(())` if the path was added successfully, was already present,
+/// or is listed in `.agpmignore`.
 pub async fn add_path_to_gitignore(
     project_dir: &Path,
     path: &str,
@@ -48,6 +131,10 @@ pub async fn add_path_to_gitignore(
     // Acquire lock to ensure thread-safe updates
     let _guard = lock.lock().await;
 
+    if CommittedPaths::load(project_dir)?.contains(path) {
+        return Ok(());
+    }
+
     let gitignore_path = project_dir.join(".gitignore");
 
     // Read existing .gitignore content
@@ -224,6 +311,7 @@ pub async fn ensure_gitignore_state(
 /// - **Updates existing file**: Preserves user content, adds/replaces AGPM section
 /// - **No-op when disabled**: Returns early if gitignore management is disabled
 /// - **Always included**: Private config files (`agpm.private.toml`, `agpm.private.lock`)
+/// - **Committed paths**: Paths matching a pattern in `.agpmignore` are left out
 /// - **Resource types**: Includes agents, snippets, commands, and scripts
 /// - **Excludes**: Hooks and MCP servers (configuration only, not installed as files)
 ///
@@ -278,6 +366,8 @@ pub fn update_gitignore(
 
     let gitignore_path = project_dir.join(".gitignore");
 
+    let committed = CommittedPaths::load(project_dir)?;
+
     // Collect all installed file paths relative to project root
     let mut paths_to_ignore = HashSet::new();
 
@@ -288,6 +378,10 @@ pub fn update_gitignore(
             if resource.install == Some(false) {
                 continue;
             }
+            // Skip resources the project commits
+            if committed.contains(&resource.installed_at) {
+                continue;
+            }
             if !resource.installed_at.is_empty() {
                 // Use the explicit installed_at path
                 paths_to_ignore.insert(resource.installed_at.clone());
@@ -57,7 +57,9 @@ use anyhow::Result;
 
 mod cleanup;
 mod context;
+pub mod dedup;
 pub mod gitignore;
+mod post_install;
 mod resource;
 mod selective;
 
@@ -66,11 +68,18 @@ use gitignore::ensure_gitignore_state;
 #[cfg(test)]
 mod tests;
 
-pub use cleanup::cleanup_removed_artifacts;
+pub use cleanup::{
+    cleanup_removed_artifacts, cleanup_removed_merged_entries, find_moved_artifacts,
+};
 pub use context::InstallContext;
-pub use gitignore::{add_path_to_gitignore, cleanup_gitignore, update_gitignore};
+pub use gitignore::{
+    AGPMIGNORE_FILE, CommittedPaths, add_path_to_gitignore, cleanup_gitignore, update_gitignore,
+};
+pub use post_install::run_post_install_commands;
 pub use selective::install_updated_resources;
 
+pub(crate) use resource::{read_files_unchanged, resolve_install_destination};
+
 use resource::{
     apply_resource_patches, compute_file_checksum, read_source_content, render_resource_content,
     should_skip_installation, validate_markdown_content, write_resource_to_disk,
@@ -161,6 +170,7 @@ type InstallResult = Result<
 /// - **checksums**: File checksums for each installed resource (ResourceId -> SHA256)
 /// - **context_checksums**: Template context checksums for each resource (ResourceId -> SHA256 or None)
 /// - **applied_patches**: List of applied patches for each resource (ResourceId -> AppliedPatches)
+/// - **written**: Resources whose files were written by this run
 #[derive(Debug, Clone)]
 pub struct InstallationResults {
     /// Number of resources that were successfully installed
@@ -172,6 +182,8 @@ pub struct InstallationResults {
     /// Applied patch information for each resource
     pub applied_patches:
         Vec<(crate::lockfile::ResourceId, crate::manifest::patches::AppliedPatches)>,
+    /// Resources whose files were written (new or changed content); the rest were up to date
+    pub written: Vec<crate::lockfile::ResourceId>,
 }
 
 impl InstallationResults {
@@ -197,6 +209,7 @@ impl InstallationResults {
             checksums,
             context_checksums,
             applied_patches,
+            written: Vec::new(),
         }
     }
 
@@ -312,13 +325,13 @@ pub async fn install_resource(
     let dest_path = if entry.installed_at.is_empty() {
         context.project_dir.join(resource_dir).join(format!("{}.md", entry.name))
     } else {
-        context.project_dir.join(&entry.installed_at)
+        resolve_install_destination(context.project_dir, entry)?
     };
 
     // Check if file already exists and compute checksum
     let existing_checksum = if dest_path.exists() {
         let path = dest_path.clone();
-        tokio::task::spawn_blocking(move || LockFile::compute_checksum(&path)).await??.into()
+        tokio::task::spawn_blocking(move || LockFile::compute_checksum_cached(&path)).await??.into()
     } else {
         None
     };
@@ -351,6 +364,13 @@ pub async fn install_resource(
     let (final_content, _templating_was_applied, context_checksum) =
         render_resource_content(&patched_content, entry, context).await?;
 
+    // Strip AGPM-managed frontmatter keys after templating, before the checksum
+    let final_content = if entry.strip_metadata && crate::markdown::is_markdown_file(&dest_path) {
+        crate::markdown::strip_agpm_metadata(&final_content)
+    } else {
+        final_content
+    };
+
     // Calculate file checksum of final content
     let file_checksum = compute_file_checksum(&final_content);
 
@@ -569,6 +589,7 @@ pub enum ResourceFilter {
 /// * `cache` - Cache instance for Git repository and worktree management
 /// * `force_refresh` - Whether to force refresh of cached repositories
 /// * `max_concurrency` - Optional limit on concurrent operations (None = unlimited)
+/// * `io_concurrency` - Optional limit on concurrent file writes (None = [`default_io_concurrency`])
 /// * `progress` - Optional multi-phase progress manager ([`MultiPhaseProgress`])
 ///
 /// # Progress Reporting
@@ -591,6 +612,7 @@ pub enum ResourceFilter {
 /// - **Pre-warming phase**: Creates all needed worktrees upfront for maximum parallelism
 /// - **Parallel execution**: Each resource installed in its own async task
 /// - **Concurrency control**: `max_concurrency` limits simultaneous operations
+/// - **Disk I/O control**: `io_concurrency` separately limits simultaneous file writes
 /// - **Thread safety**: Progress updates are atomic and thread-safe
 ///
 /// # Returns
@@ -632,6 +654,7 @@ pub enum ResourceFilter {
 ///     cache,
 ///     false,
 ///     Some(8), // Limit to 8 concurrent operations
+///     Some(4), // ...of which at most 4 write to disk at once
 ///     Some(progress),
 ///     false, // verbose
 ///     None, // old_lockfile
@@ -666,6 +689,7 @@ pub enum ResourceFilter {
 ///     cache,
 ///     false,
 ///     None, // Unlimited concurrency
+///     None, // Default disk I/O concurrency
 ///     None, // No progress output
 ///     false, // verbose
 ///     None, // old_lockfile
@@ -717,12 +741,19 @@ fn collect_install_entries(
         }
     };
 
-    if all_entries.is_empty() {
+    // Tools deselected for this run (`install --tools`, auto-detection) keep their
+    // lockfile entries but are not installed
+    let mut sorted_entries: Vec<_> = all_entries
+        .into_iter()
+        .filter(|(entry, _)| {
+            manifest.is_tool_enabled(entry.tool.as_deref().unwrap_or("claude-code"))
+        })
+        .collect();
+    if sorted_entries.is_empty() {
         return Vec::new();
     }
 
     // Sort entries for deterministic processing order
-    let mut sorted_entries = all_entries;
     sorted_entries.sort_by(|(a, _), (b, _)| {
         a.resource_type.cmp(&b.resource_type).then_with(|| a.name.cmp(&b.name))
     });
@@ -812,6 +843,7 @@ async fn execute_parallel_installation(
     force_refresh: bool,
     verbose: bool,
     max_concurrency: Option<usize>,
+    io_concurrency: usize,
     progress: Option<Arc<MultiPhaseProgress>>,
     old_lockfile: Option<&LockFile>,
 ) -> Vec<InstallResult> {
@@ -824,6 +856,12 @@ async fn execute_parallel_installation(
     // Create gitignore lock for thread-safe gitignore updates
     let gitignore_lock = Arc::new(Mutex::new(()));
 
+    // Shared registry of written content when identical files are hardlinked
+    let content_dedup = dedup::is_enabled().then(|| Arc::new(dedup::ContentDedup::new()));
+
+    // Separate limit for file writes, so slow disks are not hit with every task at once
+    let io_limit = Arc::new(tokio::sync::Semaphore::new(io_concurrency.max(1)));
+
     let total = entries.len();
 
     // Process installations in parallel with active tracking
@@ -835,6 +873,8 @@ async fn execute_parallel_installation(
             let cache = cache.clone();
             let progress = progress.clone();
             let gitignore_lock = Arc::clone(&gitignore_lock);
+            let content_dedup = content_dedup.clone();
+            let io_limit = Arc::clone(&io_limit);
             let entry_type = entry.resource_type;
             async move {
                 // Signal that this resource is starting
@@ -842,7 +882,7 @@ async fn execute_parallel_installation(
                     pm.mark_resource_active(&entry);
                 }
 
-                let install_context = InstallContext::with_common_options(
+                let mut install_context = InstallContext::with_common_options(
                     &project_dir,
                     &cache,
                     Some(manifest),
@@ -852,6 +892,8 @@ async fn execute_parallel_installation(
                     Some(&gitignore_lock),
                     old_lockfile,
                 );
+                install_context.dedup = content_dedup.as_ref();
+                install_context.io_limit = Some(&io_limit);
 
                 let res =
                     install_resource_for_parallel(&entry, &resource_dir, &install_context).await;
@@ -914,10 +956,14 @@ fn process_install_results(
     let mut checksums = Vec::new();
     let mut context_checksums = Vec::new();
     let mut applied_patches_list = Vec::new();
+    let mut written = Vec::new();
 
     for result in results {
         match result {
-            Ok((id, _installed, file_checksum, context_checksum, applied_patches)) => {
+            Ok((id, installed, file_checksum, context_checksum, applied_patches)) => {
+                if installed {
+                    written.push(id.clone());
+                }
                 checksums.push((id.clone(), file_checksum));
                 context_checksums.push((id.clone(), context_checksum));
                 applied_patches_list.push((id, applied_patches));
@@ -994,12 +1040,22 @@ fn process_install_results(
     }
 
     let installed_count = checksums.len();
-    Ok(InstallationResults::new(
+    let mut installation_results = InstallationResults::new(
         installed_count,
         checksums,
         context_checksums,
         applied_patches_list,
-    ))
+    );
+    installation_results.written = written;
+    Ok(installation_results)
+}
+
+/// Default number of resources written to disk concurrently: one per CPU core.
+///
+/// Used when neither `--io-parallel` nor `io_parallel` in the global config is set.
+#[must_use]
+pub fn default_io_concurrency() -> usize {
+    std::thread::available_parallelism().map(std::num::NonZero::get).unwrap_or(4)
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -1011,6 +1067,7 @@ pub async fn install_resources(
     cache: Cache,
     force_refresh: bool,
     max_concurrency: Option<usize>,
+    io_concurrency: Option<usize>,
     progress: Option<Arc<MultiPhaseProgress>>,
     verbose: bool,
     old_lockfile: Option<&LockFile>,
@@ -1053,6 +1110,7 @@ pub async fn install_resources(
         force_refresh,
         verbose,
         max_concurrency,
+        io_concurrency.unwrap_or_else(default_io_concurrency),
         progress.clone(),
         old_lockfile,
     )
@@ -1086,7 +1144,7 @@ pub async fn install_resources(
 /// * `cache` - Cache instance for Git operations
 /// * `old_lockfile` - Optional previous lockfile for artifact cleanup
 /// * `quiet` - Whether to suppress output messages
-/// * `no_lock` - Whether to skip lockfile saving (development mode)
+/// * `lockfile_path` - Where to save the lockfile, or `None` to skip saving (--no-lock)
 ///
 /// # Returns
 ///
@@ -1124,7 +1182,7 @@ pub async fn install_resources(
 ///     &cache,
 ///     None,    // no old lockfile (fresh install)
 ///     false,   // not quiet
-///     false,   // create lockfile
+///     Some(&project_dir.join("agpm.lock")),
 /// ).await?;
 ///
 /// println!("Configured {} hooks and {} servers", hooks, servers);
@@ -1146,17 +1204,19 @@ pub async fn finalize_installation(
     cache: &Cache,
     old_lockfile: Option<&LockFile>,
     quiet: bool,
-    no_lock: bool,
+    lockfile_path: Option<&Path>,
 ) -> Result<(usize, usize)> {
     use anyhow::Context;
 
     let mut hook_count = 0;
     let mut server_count = 0;
 
-    // Handle hooks if present
-    if !lockfile.hooks.is_empty() {
+    // Handle hooks if present (hooks are merged into Claude Code's settings)
+    if !lockfile.hooks.is_empty() && manifest.is_tool_enabled("claude-code") {
         // Configure hooks directly from source files (no copying)
-        let hooks_changed = crate::hooks::install_hooks(lockfile, project_dir, cache).await?;
+        let manifest_dir = manifest.manifest_dir.as_deref().unwrap_or(project_dir);
+        let hooks_changed =
+            crate::hooks::install_hooks(lockfile, project_dir, manifest_dir, cache).await?;
         hook_count = lockfile.hooks.len();
 
         // Always show hooks configuration feedback with changed count
@@ -1197,6 +1257,9 @@ pub async fn finalize_installation(
 
         // Configure MCP servers for each artifact type using appropriate handler
         for (artifact_type, servers) in servers_by_type {
+            if !manifest.is_tool_enabled(&artifact_type) {
+                continue;
+            }
             if let Some(handler) = crate::mcp::handlers::get_mcp_handler(&artifact_type) {
                 // Get artifact base directory - must be properly configured
                 let artifact_base = manifest
@@ -1261,18 +1324,40 @@ pub async fn finalize_installation(
 
     // Clean up removed or moved artifacts if old lockfile provided
     if let Some(old) = old_lockfile {
+        if !quiet {
+            for (from, to) in find_moved_artifacts(old, lockfile) {
+                println!(
+                    "✓ Moved {} from {} to {}",
+                    to.display_name(),
+                    from.installed_at,
+                    to.installed_at
+                );
+            }
+        }
         if let Ok(removed) = cleanup_removed_artifacts(old, lockfile, project_dir).await {
             if !removed.is_empty() && !quiet {
                 println!("✓ Cleaned up {} moved or removed artifact(s)", removed.len());
             }
         }
+
+        // Remove merged hook/MCP entries whose dependencies were dropped
+        let (hooks_removed, servers_removed) =
+            cleanup_removed_merged_entries(old, lockfile, manifest, project_dir)?;
+        if !quiet {
+            if hooks_removed > 0 {
+                println!("✓ Removed {hooks_removed} stale hook(s)");
+            }
+            if servers_removed > 0 {
+                println!("✓ Removed {servers_removed} stale MCP server(s)");
+            }
+        }
     }
 
-    if !no_lock {
+    if let Some(lockfile_path) = lockfile_path {
         // Save lockfile with checksums
-        lockfile.save(&project_dir.join("agpm.lock")).with_context(|| {
-            format!("Failed to save lockfile to {}", project_dir.join("agpm.lock").display())
-        })?;
+        lockfile
+            .save(lockfile_path)
+            .with_context(|| format!("Failed to save lockfile to {}", lockfile_path.display()))?;
 
         // Build and save private lockfile if there are private patches
         use crate::lockfile::PrivateLockFile;
@@ -1293,8 +1378,9 @@ pub async fn finalize_installation(
             }
         }
 
-        // Save private lockfile (automatically deletes if empty)
-        private_lock.save(project_dir).with_context(|| "Failed to save private lockfile")?;
+        // Save private lockfile next to the manifest (automatically deletes if empty)
+        let manifest_dir = manifest.manifest_dir.as_deref().unwrap_or(project_dir);
+        private_lock.save(manifest_dir).with_context(|| "Failed to save private lockfile")?;
     }
 
     // Update .gitignore with lock for safe concurrent access
@@ -0,0 +1,183 @@
+//! Per-dependency post-install commands.
+//!
+//! A dependency declared in the manifest can name a command to run once its
+//! file is installed:
+//!
+//! ```toml
+//! [scripts]
+//! register = { source = "tools", path = "scripts/register.sh", version = "v1.0.0", post_install = "scripts/setup.sh" }
+//! ```
+//!
+//! The command runs through the platform shell (`sh -c` or `cmd /C`) from the
+//! project root, with the installed path appended as its last argument. It runs
+//! when the resource is first installed or its content changes, never for
+//! unchanged resources, and never for transitive dependencies. Each command is
+//! printed before it runs, and a non-zero exit status fails the install.
+//!
+//! Post-install commands execute arbitrary code with the user's permissions.
+//! The script they call is often installed from a source repository, so it is
+//! written by the source author; review it before enabling the command, and use
+//! `--no-post-install` to skip all commands.
+
+use anyhow::{Context, Result, bail};
+use colored::Colorize;
+use std::path::Path;
+
+use crate::core::ResourceType;
+use crate::lockfile::{LockFile, LockedResource};
+use crate::manifest::Manifest;
+
+/// Run the post-install commands of installed or changed resources.
+///
+/// Commands run one at a time in lockfile order. Returns the number of
+/// commands run.
+///
+/// # Errors
+///
+/// Returns an error if a command cannot be started or exits with a non-zero
+/// status; later commands are not run.
+pub async fn run_post_install_commands(
+    lockfile: &LockFile,
+    old_lockfile: Option<&LockFile>,
+    manifest: &Manifest,
+    project_dir: &Path,
+) -> Result<usize> {
+    let mut count = 0;
+    for resource_type in ResourceType::all() {
+        for entry in lockfile.get_resources(resource_type) {
+            let Some(command) = post_install_command(manifest, entry) else {
+                continue;
+            };
+            if !changed_since(entry, old_lockfile) {
+                continue;
+            }
+
+            println!(
+                "{} Running post-install for '{}': {command} {}",
+                "→".cyan(),
+                entry.display_name(),
+                entry.installed_at
+            );
+            run_command(command, &entry.installed_at, project_dir).await.with_context(|| {
+                format!("Post-install command for '{}' failed", entry.display_name())
+            })?;
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// Look up the post-install command of the manifest dependency behind `entry`.
+///
+/// Transitive dependencies have no manifest alias and never run commands.
+fn post_install_command<'a>(manifest: &'a Manifest, entry: &LockedResource) -> Option<&'a str> {
+    if entry.install == Some(false) || entry.installed_at.is_empty() {
+        return None;
+    }
+    let alias = entry.manifest_alias.as_deref()?;
+    manifest.get_resources(&entry.resource_type).get(alias)?.get_post_install()
+}
+
+/// Whether `entry` is new or its installed content differs from the old lockfile.
+fn changed_since(entry: &LockedResource, old_lockfile: Option<&LockFile>) -> bool {
+    let Some(old_lockfile) = old_lockfile else {
+        return true;
+    };
+    let id = entry.id();
+    !old_lockfile
+        .get_resources(&entry.resource_type)
+        .iter()
+        .any(|old| old.id() == id && old.checksum == entry.checksum)
+}
+
+/// Run `command` through the shell with `installed_at` as its last argument.
+async fn run_command(command: &str, installed_at: &str, project_dir: &Path) -> Result<()> {
+    #[cfg(windows)]
+    let status = tokio::process::Command::new("cmd")
+        .arg("/C")
+        .arg(format!("{command} \"{installed_at}\""))
+        .current_dir(project_dir)
+        .status()
+        .await;
+
+    // Pass the path as a positional parameter so it is never re-parsed by the shell
+    #[cfg(not(windows))]
+    let status = tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg(format!("{command} \"$1\""))
+        .arg("agpm-post-install")
+        .arg(installed_at)
+        .current_dir(project_dir)
+        .status()
+        .await;
+
+    let status = status.with_context(|| format!("Failed to run '{command}'"))?;
+    if !status.success() {
+        bail!("'{command}' exited with {status}");
+    }
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::lockfile::LockedResourceBuilder;
+    use tempfile::TempDir;
+
+    fn manifest_with_script(post_install: &str) -> Manifest {
+        toml::from_str(&format!(
+            "[scripts]\nregister = {{ path = \"scripts/register.sh\", post_install = \"{post_install}\" }}\n"
+        ))
+        .unwrap()
+    }
+
+    fn lockfile_with_script(checksum: &str, alias: Option<&str>) -> LockFile {
+        let mut lockfile = LockFile::new();
+        let mut entry = LockedResourceBuilder::new(
+            "scripts/register".to_string(),
+            "scripts/register.sh".to_string(),
+            checksum.to_string(),
+            ".claude/scripts/register.sh".to_string(),
+            ResourceType::Script,
+        )
+        .build();
+        entry.manifest_alias = alias.map(str::to_string);
+        lockfile.scripts.push(entry);
+        lockfile
+    }
+
+    #[tokio::test]
+    async fn test_post_install_runs_for_changed_resources() -> Result<()> {
+        let temp = TempDir::new()?;
+        let manifest = manifest_with_script("echo >> ran.txt");
+        let lockfile = lockfile_with_script("sha256:new", Some("register"));
+
+        let count = run_post_install_commands(&lockfile, None, &manifest, temp.path()).await?;
+        assert_eq!(count, 1);
+        let ran = std::fs::read_to_string(temp.path().join("ran.txt"))?;
+        assert_eq!(ran.trim(), ".claude/scripts/register.sh");
+
+        // Unchanged content does not run the command again
+        let old = lockfile_with_script("sha256:new", Some("register"));
+        assert_eq!(
+            run_post_install_commands(&lockfile, Some(&old), &manifest, temp.path()).await?,
+            0
+        );
+
+        // Transitive dependencies never run commands
+        let transitive = lockfile_with_script("sha256:new", None);
+        assert_eq!(run_post_install_commands(&transitive, None, &manifest, temp.path()).await?, 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_post_install_failure_fails_install() {
+        let temp = TempDir::new().unwrap();
+        let manifest = manifest_with_script("exit 3");
+        let lockfile = lockfile_with_script("sha256:new", Some("register"));
+
+        let err =
+            run_post_install_commands(&lockfile, None, &manifest, temp.path()).await.unwrap_err();
+        assert!(format!("{err:#}").contains("Post-install command for 'register' failed"));
+    }
+}
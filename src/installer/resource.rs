@@ -7,7 +7,10 @@ use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
 
 use crate::core::file_error::{FileOperation, FileResultExt};
-use crate::installer::context::{InstallContext, read_with_cache_retry};
+use crate::git::GitRepo;
+use crate::installer::context::{
+    InstallContext, read_bytes_with_cache_retry, read_with_cache_retry,
+};
 use crate::lockfile::LockedResource;
 use crate::markdown::MarkdownFile;
 use crate::templating::RenderingMetadata;
@@ -19,6 +22,11 @@ use crate::utils::fs::{atomic_write, ensure_dir};
 /// - Git-based sources (using SHA-based worktrees)
 /// - Local directory sources
 /// - Local file paths (relative or absolute)
+/// - Inline content recorded in the lockfile
+///
+/// Files stored with Git LFS are checked out as pointer files; when one is
+/// found in a worktree, its content is downloaded with `git lfs pull` before
+/// it is returned.
 ///
 /// # Arguments
 ///
@@ -32,6 +40,10 @@ pub async fn read_source_content(
     entry: &LockedResource,
     context: &InstallContext<'_>,
 ) -> Result<String> {
+    if let Some(inline) = &entry.inline {
+        return Ok(inline.clone());
+    }
+
     if let Some(source_name) = &entry.source {
         let url = entry
             .url
@@ -67,7 +79,8 @@ pub async fn read_source_content(
                 .get_or_create_worktree_for_sha(source_name, url, sha, Some(&entry.name))
                 .await?;
 
-            if context.force_refresh {
+            // Imported checkouts are the user's own; they are read as-is
+            if context.force_refresh && !context.cache.is_imported_worktree(&cache_dir).await {
                 let _ = context.cache.cleanup_worktree(&cache_dir).await;
                 cache_dir = context
                     .cache
@@ -80,6 +93,28 @@ pub async fn read_source_content(
 
         // Read the content from the source (with cache coherency retry)
         let source_path = cache_dir.join(&entry.path);
+        let content = read_bytes_with_cache_retry(&source_path).await?;
+        if !crate::git::is_lfs_pointer(&content) {
+            return String::from_utf8(content).map_err(|e| {
+                anyhow::anyhow!(
+                    "Failed to read resource file: {}: {}",
+                    source_path.display(),
+                    e.utf8_error()
+                )
+            });
+        }
+
+        // The worktree holds a Git LFS pointer; fetch the real content first
+        if is_local_source {
+            return Err(anyhow::anyhow!(
+                "'{}' in source '{source_name}' is a Git LFS pointer. \
+                 Run 'git lfs pull' in {} to fetch its content.",
+                entry.path,
+                cache_dir.display()
+            ));
+        }
+        tracing::debug!("Fetching Git LFS content for {} ({})", entry.name, entry.path);
+        GitRepo::new(&cache_dir).lfs_pull(&entry.path).await?;
         read_with_cache_retry(&source_path).await
     } else {
         // Local resource - copy directly from project directory or absolute path
@@ -88,7 +123,7 @@ pub async fn read_source_content(
             if candidate.is_absolute() {
                 candidate.to_path_buf()
             } else {
-                context.project_dir.join(candidate)
+                context.manifest_dir().join(candidate)
             }
         };
 
@@ -198,6 +233,8 @@ pub fn apply_resource_patches(
 /// 3. If true: render full file (frontmatter + body)
 /// 4. If false: use rendered frontmatter + original body (via boundary replacement)
 ///
+/// Inline resources skip the flag check and are always rendered in full.
+///
 /// # Arguments
 ///
 /// * `content` - The patched content to render
@@ -212,6 +249,11 @@ pub async fn render_resource_content(
     entry: &LockedResource,
     context: &InstallContext<'_>,
 ) -> Result<(String, bool, Option<String>)> {
+    // Inline content is always a template
+    if entry.inline.is_some() {
+        return render_full_file(content, entry, context, &context.template_context_builder).await;
+    }
+
     // Only process markdown files
     if !entry.path.ends_with(".md") {
         tracing::debug!("Not a markdown file: {}", entry.path);
@@ -230,13 +272,7 @@ pub async fn render_resource_content(
 
     // Step 2: Build template context for frontmatter rendering
     let template_context_builder = &context.template_context_builder;
-    let resource_id = crate::lockfile::ResourceId::new(
-        entry.name.clone(),
-        entry.source.clone(),
-        entry.tool.clone(),
-        entry.resource_type,
-        entry.variant_inputs.hash().to_string(),
-    );
+    let resource_id = entry.id();
 
     // Try to build template context - if it fails, just use original content
     let template_context = match template_context_builder
@@ -259,7 +295,7 @@ pub async fn render_resource_content(
         use crate::templating::TemplateRenderer;
         let mut renderer = match TemplateRenderer::new(
             true,
-            context.project_dir.to_path_buf(),
+            context.manifest_dir().to_path_buf(),
             context.max_content_file_size,
         ) {
             Ok(r) => r,
@@ -350,13 +386,7 @@ async fn render_full_file(
         format!("Failed to compute context digest for resource '{}'", entry.name)
     })?;
 
-    let resource_id = crate::lockfile::ResourceId::new(
-        entry.name.clone(),
-        entry.source.clone(),
-        entry.tool.clone(),
-        entry.resource_type,
-        entry.variant_inputs.hash().to_string(),
-    );
+    let resource_id = entry.id();
 
     // Build template context
     let (template_context, captured_context_checksum) = template_context_builder
@@ -393,13 +423,13 @@ async fn render_full_file(
         resource_name: entry.name.clone(),
         resource_type: entry.resource_type,
         dependency_chain: vec![],
-        source_path: Some(entry.path.clone().into()),
+        source_path: (!entry.path.is_empty()).then(|| entry.path.clone().into()),
         depth: 0,
     };
 
     let mut renderer = TemplateRenderer::new(
         true,
-        context.project_dir.to_path_buf(),
+        context.manifest_dir().to_path_buf(),
         context.max_content_file_size,
     )
     .with_context(|| "Failed to create template renderer")?;
@@ -433,7 +463,178 @@ async fn render_full_file(
         tracing::info!("✅ Template rendered successfully");
     }
 
-    Ok((rendered_content, true, captured_context_checksum))
+    let read_files = renderer.read_files();
+    let context_checksum =
+        include_read_files_in_checksum(captured_context_checksum.clone(), &read_files);
+    if let Some(checksum) = &context_checksum {
+        let inputs = RenderInputs {
+            base: captured_context_checksum,
+            read_files,
+        };
+        record_render_inputs(context.cache.cache_dir(), checksum, &inputs).await;
+    }
+
+    Ok((rendered_content, true, context_checksum))
+}
+
+/// Directory in the cache that records what each context checksum was computed from.
+const RENDER_INPUTS_DIR: &str = "render-inputs";
+
+/// The `read_file` inputs folded into a context checksum.
+///
+/// Stored in the cache under the checksum, so a later install can tell whether
+/// the files a template read have changed without rendering it again.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct RenderInputs {
+    /// Context checksum before the read files were folded in
+    base: Option<String>,
+    /// Files read through `read_file`, with their checksums when rendered
+    read_files: std::collections::BTreeMap<String, String>,
+}
+
+fn render_inputs_path(cache_dir: &Path, context_checksum: &str) -> PathBuf {
+    let digest = context_checksum.strip_prefix("sha256:").unwrap_or(context_checksum);
+    cache_dir.join(RENDER_INPUTS_DIR).join(format!("{digest}.json"))
+}
+
+/// Record the inputs of `context_checksum` in the cache.
+///
+/// Failures are only logged: without a record the resource is re-rendered by
+/// the next install instead of being skipped.
+async fn record_render_inputs(cache_dir: &Path, context_checksum: &str, inputs: &RenderInputs) {
+    let path = render_inputs_path(cache_dir, context_checksum);
+    let result = async {
+        let json = serde_json::to_vec(inputs)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&path, json).await?;
+        anyhow::Ok(())
+    };
+    if let Err(e) = result.await {
+        tracing::debug!("Failed to record render inputs at {}: {e}", path.display());
+    }
+}
+
+/// Whether the project files read when `context_checksum` was computed still match.
+///
+/// The recorded files are hashed again and folded into the recorded base
+/// checksum; the resource is unchanged only if that reproduces
+/// `context_checksum`. A missing record (e.g. after the cache was cleared)
+/// counts as changed.
+pub(crate) fn read_files_unchanged(
+    cache_dir: &Path,
+    manifest_dir: &Path,
+    context_checksum: &str,
+) -> bool {
+    let Some(inputs) = std::fs::read(render_inputs_path(cache_dir, context_checksum))
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<RenderInputs>(&bytes).ok())
+    else {
+        return false;
+    };
+
+    let mut current = std::collections::BTreeMap::new();
+    for path in inputs.read_files.keys() {
+        let Ok(content) = std::fs::read_to_string(manifest_dir.join(path)) else {
+            return false;
+        };
+        current.insert(path.clone(), compute_file_checksum(&content));
+    }
+    include_read_files_in_checksum(inputs.base, &current).as_deref() == Some(context_checksum)
+}
+
+/// Fold the project files a template read via `read_file` into its context checksum.
+///
+/// The checksum is returned unchanged when no files were read, so resources that
+/// do not use `read_file` keep their existing lockfile checksums.
+fn include_read_files_in_checksum(
+    context_checksum: Option<String>,
+    read_files: &std::collections::BTreeMap<String, String>,
+) -> Option<String> {
+    use sha2::{Digest, Sha256};
+
+    if read_files.is_empty() {
+        return context_checksum;
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update(context_checksum.unwrap_or_default().as_bytes());
+    for (path, checksum) in read_files {
+        hasher.update(format!("\n{path}:{checksum}").as_bytes());
+    }
+    Some(format!("sha256:{}", hex::encode(hasher.finalize())))
+}
+
+/// Resolve a resource's `installed_at` path and ensure it stays inside the project.
+///
+/// This is the last line of defense before anything is written: custom `target`
+/// and `filename` values are meant to be relative to the tool's directory, but a
+/// hand-edited lockfile or an unexpected combination of settings could still
+/// produce a path that escapes. The path is rejected if it is absolute, if `..`
+/// components climb above the project root after normalization, if it is empty
+/// or resolves to the project root itself, or if it points into a system
+/// directory (see [`validate_path_security`]).
+///
+/// # Arguments
+///
+/// * `project_dir` - The project root that installs are confined to
+/// * `entry` - The locked resource being installed
+///
+/// # Returns
+///
+/// Returns the absolute destination path inside `project_dir`.
+///
+/// # Errors
+///
+/// Returns [`AgpmError::InvalidDependency`](crate::core::AgpmError::InvalidDependency)
+/// naming the resource if the path escapes the project.
+///
+/// [`validate_path_security`]: crate::utils::security::validate_path_security
+pub fn resolve_install_destination(project_dir: &Path, entry: &LockedResource) -> Result<PathBuf> {
+    use std::path::Component;
+
+    let escapes = |reason: &str| -> anyhow::Error {
+        crate::core::AgpmError::InvalidDependency {
+            name: entry.name.clone(),
+            reason: format!(
+                "install path '{}' {reason}; custom `target` and `filename` values must stay \
+                 inside the project directory",
+                entry.installed_at
+            ),
+        }
+        .into()
+    };
+
+    let mut relative = PathBuf::new();
+    for component in Path::new(&entry.installed_at).components() {
+        match component {
+            Component::Normal(part) => relative.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !relative.pop() {
+                    return Err(escapes("escapes the project directory via '..'"));
+                }
+            }
+            Component::RootDir | Component::Prefix(_) => {
+                return Err(escapes("is absolute"));
+            }
+        }
+    }
+
+    if relative.as_os_str().is_empty() {
+        return Err(escapes("does not name a path inside the project directory"));
+    }
+
+    let dest_path = project_dir.join(&relative);
+    // A project that itself lives under a protected directory (e.g. /root) was
+    // placed there deliberately; the lexical check above already confines it
+    if !crate::utils::security::is_path_blacklisted(project_dir) {
+        crate::utils::security::validate_path_security(&dest_path, false)
+            .map_err(|_| escapes("points into a protected system directory"))?;
+    }
+
+    Ok(dest_path)
 }
 
 /// Compute SHA-256 checksum of file content.
@@ -484,13 +685,28 @@ pub fn should_skip_installation(
     let old_lockfile = context.old_lockfile?;
     let old_entry = old_lockfile.find_resource(&entry.name, &entry.resource_type)?;
 
+    // Templated resources may embed project files via `read_file`, which can
+    // change without the commit changing; skip them only while those files match
+    if let Some(context_checksum) = &old_entry.context_checksum
+        && !read_files_unchanged(
+            context.cache.cache_dir(),
+            context.manifest_dir(),
+            context_checksum,
+        )
+    {
+        return None;
+    }
+
     // Check if all inputs that affect the final content are unchanged
     let resolved_commit_unchanged = old_entry.resolved_commit == entry.resolved_commit;
     let variant_inputs_unchanged = old_entry.variant_inputs == entry.variant_inputs;
     let patches_unchanged = old_entry.applied_patches == entry.applied_patches;
+    let strip_metadata_unchanged = old_entry.strip_metadata == entry.strip_metadata;
 
-    let all_inputs_unchanged =
-        resolved_commit_unchanged && variant_inputs_unchanged && patches_unchanged;
+    let all_inputs_unchanged = resolved_commit_unchanged
+        && variant_inputs_unchanged
+        && patches_unchanged
+        && strip_metadata_unchanged;
 
     if all_inputs_unchanged && dest_path.exists() {
         // File exists and all inputs match - verify checksum matches
@@ -522,7 +738,8 @@ pub fn should_skip_installation(
 /// Write resource content to disk with atomic operations.
 ///
 /// This function handles the final installation step, writing the content to disk
-/// atomically and updating the .gitignore file if needed.
+/// atomically and updating the .gitignore file if needed. When the context has an
+/// I/O limit, the write waits for one of its permits.
 ///
 /// # Arguments
 ///
@@ -549,10 +766,19 @@ pub async fn write_resource_to_disk(
     }
 
     if !content_changed {
-        // install=true but content unchanged
+        // install=true but content unchanged; later identical files can still link to it
+        if let Some(dedup) = context.dedup {
+            dedup.remember(dest_path, content);
+        }
         return Ok(false);
     }
 
+    // Wait for a disk I/O slot; released when the write completes
+    let _io_permit = match context.io_limit {
+        Some(io_limit) => Some(io_limit.acquire().await.context("File I/O limiter closed")?),
+        None => None,
+    };
+
     // Create parent directory if needed
     if let Some(parent) = dest_path.parent() {
         ensure_dir(parent)?;
@@ -576,9 +802,12 @@ pub async fn write_resource_to_disk(
         .with_context(|| format!("Failed to add {} to .gitignore", relative_path))?;
     }
 
-    // Write file atomically
-    atomic_write(dest_path, content.as_bytes())
-        .with_context(|| format!("Failed to install resource to {}", dest_path.display()))?;
+    // Write file atomically, or hardlink it to an identical installed file
+    match context.dedup {
+        Some(dedup) => dedup.write(dest_path, content)?,
+        None => atomic_write(dest_path, content.as_bytes())
+            .with_context(|| format!("Failed to install resource to {}", dest_path.display()))?,
+    }
 
     Ok(true)
 }
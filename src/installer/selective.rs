@@ -244,6 +244,7 @@ pub async fn install_updated_resources(
                 if let Some(size) = install_ctx.max_content_file_size {
                     builder = builder.max_content_file_size(size);
                 }
+                builder = builder.dedup(install_ctx.dedup).io_limit(install_ctx.io_limit);
 
                 let context = builder.build();
                 install_resource_for_parallel(&entry, &resource_dir, &context).await?;
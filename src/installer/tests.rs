@@ -2,8 +2,9 @@
 mod installer_tests {
     use crate::cache::Cache;
     use crate::installer::{
-        InstallContext, ResourceFilter, install_resource, install_resource_with_progress,
-        install_resources, install_updated_resources, update_gitignore,
+        CommittedPaths, InstallContext, ResourceFilter, add_path_to_gitignore, install_resource,
+        install_resource_with_progress, install_resources, install_updated_resources,
+        update_gitignore,
     };
     use crate::lockfile::{LockFile, LockedResource};
     use crate::manifest::Manifest;
@@ -33,6 +34,8 @@ mod installer_tests {
                 applied_patches: std::collections::BTreeMap::new(),
                 install: None,
                 variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+                strip_metadata: false,
+                inline: None,
             }
         } else {
             LockedResource {
@@ -52,6 +55,8 @@ mod installer_tests {
                 applied_patches: std::collections::BTreeMap::new(),
                 install: None,
                 variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+                strip_metadata: false,
+                inline: None,
             }
         }
     }
@@ -1226,6 +1231,8 @@ target/
                     applied_patches: std::collections::BTreeMap::new(),
                     install: None,
                     variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+                    strip_metadata: false,
+                    inline: None,
                 };
                 lockfile.agents.push(resource);
             }
@@ -1311,6 +1318,98 @@ target/
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_install_resource_rejects_paths_outside_project() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let project_dir = temp_dir.path().join("project");
+        std::fs::create_dir_all(&project_dir)?;
+        let cache = Cache::with_dir(temp_dir.path().join("cache"))?;
+
+        let local_file = project_dir.join("test.md");
+        std::fs::write(&local_file, "# Malicious Target")?;
+        let context = InstallContext::builder(&project_dir, &cache).build();
+
+        // target = "../../etc" and filename = "/tmp/evil" as they would appear in the lockfile
+        for installed_at in [".claude/../../etc/agent.md", "/tmp/evil.md", "../escaped.md"] {
+            let mut entry = create_test_locked_resource("evil", true);
+            entry.path = local_file.to_string_lossy().to_string();
+            entry.installed_at = installed_at.to_string();
+
+            let err = install_resource(&entry, "agents", &context).await.unwrap_err();
+            let message = err.to_string();
+            assert!(message.contains("evil"), "{message}");
+            assert!(message.contains("inside the project directory"), "{message}");
+        }
+        assert!(!temp_dir.path().join("escaped.md").exists());
+        assert!(!temp_dir.path().join("etc").exists());
+
+        // `..` that stays inside the project is fine
+        let mut entry = create_test_locked_resource("inside", true);
+        entry.path = local_file.to_string_lossy().to_string();
+        entry.installed_at = ".claude/agents/../snippets/inside.md".to_string();
+        install_resource(&entry, "agents", &context).await?;
+        assert!(project_dir.join(".claude/snippets/inside.md").exists());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_removed_artifacts_stays_inside_project() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let project_dir = temp_dir.path().join("project");
+        std::fs::create_dir_all(project_dir.join(".claude/agents"))?;
+        std::fs::write(project_dir.join("agpm.toml"), "")?;
+        std::fs::write(project_dir.join(".claude/agents/stale.md"), "# Stale")?;
+        std::fs::write(temp_dir.path().join("outside.md"), "# Outside")?;
+
+        // A hand-edited lockfile: an empty path would resolve to the project root
+        let mut old_lockfile = LockFile::new();
+        for (name, installed_at) in [
+            ("empty", ""),
+            ("dot", "."),
+            ("outside", "../outside.md"),
+            ("stale", ".claude/agents/stale.md"),
+        ] {
+            let mut entry = create_test_locked_resource(name, false);
+            entry.installed_at = installed_at.to_string();
+            old_lockfile.agents.push(entry);
+        }
+
+        let removed = crate::installer::cleanup_removed_artifacts(
+            &old_lockfile,
+            &LockFile::new(),
+            &project_dir,
+        )
+        .await?;
+
+        assert_eq!(removed, vec![".claude/agents/stale.md".to_string()]);
+        assert!(project_dir.join("agpm.toml").exists());
+        assert!(temp_dir.path().join("outside.md").exists());
+        assert!(!project_dir.join(".claude/agents/stale.md").exists());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_empty_dirs_stops_at_project_root() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let project_dir = temp_dir.path().join("project");
+        // A custom target outside .claude, in an otherwise empty project
+        std::fs::create_dir_all(project_dir.join("custom/agents"))?;
+        std::fs::write(project_dir.join("custom/agents/stale.md"), "# Stale")?;
+
+        let mut old_lockfile = LockFile::new();
+        let mut entry = create_test_locked_resource("stale", false);
+        entry.installed_at = "custom/agents/stale.md".to_string();
+        old_lockfile.agents.push(entry);
+
+        crate::installer::cleanup_removed_artifacts(&old_lockfile, &LockFile::new(), &project_dir)
+            .await?;
+
+        assert!(!project_dir.join("custom").exists());
+        assert!(project_dir.exists(), "the empty project directory must survive cleanup");
+        assert!(temp_dir.path().exists());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_install_resource_local_missing_file() -> Result<()> {
         let temp_dir = TempDir::new()?;
@@ -1435,6 +1534,7 @@ target/
             false,
             None,
             None,
+            None,
             false, // verbose
             None,  // old_lockfile
         )
@@ -1488,6 +1588,7 @@ target/
             cache,
             false,
             None,
+            Some(1), // one write at a time must still install everything
             None,
             false, // verbose
             None,  // old_lockfile
@@ -1775,6 +1876,69 @@ target/
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_update_gitignore_skips_agpmignore_paths() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let project_dir = temp_dir.path();
+
+        std::fs::write(
+            project_dir.join(".agpmignore"),
+            "# Reviewed resources are committed\n.claude/agents/reviewed/\n./.claude/commands/deploy.md\n",
+        )?;
+        let gitignore_path = project_dir.join(".gitignore");
+        std::fs::write(
+            &gitignore_path,
+            "user-file.txt\n\
+             # AGPM managed entries - do not edit below this line\n\
+             .claude/agents/reviewed/lint.md\n\
+             # End of AGPM managed entries\n",
+        )?;
+
+        let mut lockfile = LockFile::new();
+        for (name, path) in [
+            ("lint", ".claude/agents/reviewed/lint.md"),
+            ("scratch", ".claude/agents/scratch.md"),
+            ("reviewed-twin", ".claude/agents/reviewed-twin.md"),
+        ] {
+            let mut agent = create_test_locked_resource(name, true);
+            agent.installed_at = path.to_string();
+            lockfile.agents.push(agent);
+        }
+        let mut command = create_test_locked_resource("deploy", true);
+        command.installed_at = ".claude/commands/deploy.md".to_string();
+        lockfile.commands.push(command);
+
+        update_gitignore(&lockfile, project_dir, true, None)?;
+
+        // Committed paths are dropped from the recomputed section
+        let content = std::fs::read_to_string(&gitignore_path)?;
+        assert!(content.starts_with("user-file.txt\n"));
+        assert!(content.contains(".claude/agents/scratch.md"));
+        assert!(content.contains(".claude/agents/reviewed-twin.md"));
+        assert!(!content.contains(".claude/agents/reviewed/lint.md"));
+        assert!(!content.contains(".claude/commands/deploy.md"));
+
+        // Incremental additions honor .agpmignore too
+        let lock = Arc::new(tokio::sync::Mutex::new(()));
+        add_path_to_gitignore(project_dir, ".claude/agents/reviewed/new.md", &lock).await?;
+        add_path_to_gitignore(project_dir, ".claude/agents/other.md", &lock).await?;
+        let content = std::fs::read_to_string(&gitignore_path)?;
+        assert!(!content.contains(".claude/agents/reviewed/new.md"));
+        assert!(content.contains(".claude/agents/other.md"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_committed_paths_patterns() -> Result<()> {
+        let committed = CommittedPaths::parse("*.local.md\n.claude/snippets/**/shared.md\n")?;
+        assert!(committed.contains("notes.local.md"));
+        assert!(!committed.contains(".claude/agents/notes.local.md"));
+        assert!(committed.contains(".claude/snippets/a/b/shared.md"));
+        assert!(!committed.contains(".claude/snippets/other.md"));
+        assert!(CommittedPaths::parse("[").is_err());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_update_gitignore_migrates_ccpm_entries() -> Result<()> {
         let temp_dir = TempDir::new()?;
@@ -8,6 +8,21 @@ use std::fs;
 use std::path::Path;
 
 use super::{LockFile, ResourceId};
+use crate::core::ResourceType;
+
+/// Outcome of [`LockFile::refresh_checksums`].
+///
+/// Each list holds the `installed_at` paths of the affected resources.
+#[derive(Debug, Default)]
+pub struct ChecksumRefresh {
+    /// Local resources whose checksum was updated.
+    pub updated: Vec<String>,
+    /// Resources from a source whose installed file no longer matches the
+    /// lockfile. Their checksums are left unchanged.
+    pub remote_mismatches: Vec<String>,
+    /// Resources whose installed file does not exist.
+    pub missing: Vec<String>,
+}
 
 impl LockFile {
     /// Compute SHA-256 checksum for file integrity verification.
@@ -125,9 +140,8 @@ impl LockFile {
     ///
     /// # Performance
     ///
-    /// This method internally calls [`compute_checksum`](Self::compute_checksum),
-    /// so it has the same performance characteristics. For bulk verification
-    /// operations, consider caching computed checksums.
+    /// This method internally calls [`compute_checksum_cached`](Self::compute_checksum_cached),
+    /// so unchanged files are not re-read when the checksum cache is enabled.
     ///
     /// # Security
     ///
@@ -135,10 +149,57 @@ impl LockFile {
     /// not timing-attack resistant. Since checksums are not secrets, this
     /// is acceptable for integrity verification purposes.
     pub fn verify_checksum(path: &Path, expected: &str) -> Result<bool> {
-        let actual = Self::compute_checksum(path)?;
+        let actual = Self::compute_checksum_cached(path)?;
         Ok(actual == expected)
     }
 
+    /// Recompute checksums from the installed files without re-resolving.
+    ///
+    /// Updates only the `checksum` field of local (source-less) resources
+    /// whose installed file changed, for example after intentionally editing
+    /// a local resource. A changed file from a source repository is reported
+    /// in [`ChecksumRefresh::remote_mismatches`] instead of being accepted,
+    /// since it may have been tampered with. Hooks and MCP servers are merged
+    /// into shared configuration files and are skipped, as are resources that
+    /// are not installed to disk.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an installed file exists but cannot be read.
+    pub fn refresh_checksums(&mut self, project_dir: &Path) -> Result<ChecksumRefresh> {
+        let mut refresh = ChecksumRefresh::default();
+
+        for resource_type in ResourceType::all() {
+            if matches!(resource_type, ResourceType::Hook | ResourceType::McpServer) {
+                continue;
+            }
+            for resource in self.get_resources_mut(resource_type) {
+                if resource.install == Some(false) || resource.installed_at.is_empty() {
+                    continue;
+                }
+
+                let path = project_dir.join(&resource.installed_at);
+                if !path.is_file() {
+                    refresh.missing.push(resource.installed_at.clone());
+                    continue;
+                }
+
+                let checksum = Self::compute_checksum_cached(&path)?;
+                if checksum == resource.checksum {
+                    continue;
+                }
+                if resource.source.is_some() {
+                    refresh.remote_mismatches.push(resource.installed_at.clone());
+                } else {
+                    resource.checksum = checksum;
+                    refresh.updated.push(resource.installed_at.clone());
+                }
+            }
+        }
+
+        Ok(refresh)
+    }
+
     /// Update checksum for resource identified by ResourceId.
     ///
     /// Used after installation to record actual file checksum. ResourceId ensures unique
@@ -179,42 +240,42 @@ impl LockFile {
     pub fn update_resource_checksum(&mut self, id: &ResourceId, checksum: &str) -> bool {
         // Try each resource type until we find a match by comparing ResourceIds
         for resource in &mut self.agents {
-            if resource.id() == *id {
+            if resource.matches_id(id) {
                 resource.checksum = checksum.to_string();
                 return true;
             }
         }
 
         for resource in &mut self.snippets {
-            if resource.id() == *id {
+            if resource.matches_id(id) {
                 resource.checksum = checksum.to_string();
                 return true;
             }
         }
 
         for resource in &mut self.commands {
-            if resource.id() == *id {
+            if resource.matches_id(id) {
                 resource.checksum = checksum.to_string();
                 return true;
             }
         }
 
         for resource in &mut self.scripts {
-            if resource.id() == *id {
+            if resource.matches_id(id) {
                 resource.checksum = checksum.to_string();
                 return true;
             }
         }
 
         for resource in &mut self.hooks {
-            if resource.id() == *id {
+            if resource.matches_id(id) {
                 resource.checksum = checksum.to_string();
                 return true;
             }
         }
 
         for resource in &mut self.mcp_servers {
-            if resource.id() == *id {
+            if resource.matches_id(id) {
                 resource.checksum = checksum.to_string();
                 return true;
             }
@@ -252,42 +313,42 @@ impl LockFile {
     ) -> bool {
         // Try each resource type until we find a match by comparing ResourceIds
         for resource in &mut self.agents {
-            if resource.id() == *id {
+            if resource.matches_id(id) {
                 resource.context_checksum = Some(context_checksum.to_string());
                 return true;
             }
         }
 
         for resource in &mut self.snippets {
-            if resource.id() == *id {
+            if resource.matches_id(id) {
                 resource.context_checksum = Some(context_checksum.to_string());
                 return true;
             }
         }
 
         for resource in &mut self.commands {
-            if resource.id() == *id {
+            if resource.matches_id(id) {
                 resource.context_checksum = Some(context_checksum.to_string());
                 return true;
             }
         }
 
         for resource in &mut self.scripts {
-            if resource.id() == *id {
+            if resource.matches_id(id) {
                 resource.context_checksum = Some(context_checksum.to_string());
                 return true;
             }
         }
 
         for resource in &mut self.hooks {
-            if resource.id() == *id {
+            if resource.matches_id(id) {
                 resource.context_checksum = Some(context_checksum.to_string());
                 return true;
             }
         }
 
         for resource in &mut self.mcp_servers {
-            if resource.id() == *id {
+            if resource.matches_id(id) {
                 resource.context_checksum = Some(context_checksum.to_string());
                 return true;
             }
@@ -452,4 +513,119 @@ impl LockFile {
             self.update_resource_applied_patches(id.name(), &applied_patches);
         }
     }
+
+    /// Copy install-time checksums from `old` for resources whose inputs are unchanged.
+    ///
+    /// Used when the lockfile is written without installing (`agpm install
+    /// --resolve-only`). A Git resource at the same commit and path, with the same
+    /// variant inputs, patches and `strip_metadata` setting, installs the same
+    /// content, so the checksum recorded for it stays valid. Other resources keep
+    /// an empty checksum until they are installed.
+    pub fn carry_over_checksums(&mut self, old: &LockFile) {
+        for resources in [
+            &mut self.agents,
+            &mut self.snippets,
+            &mut self.commands,
+            &mut self.scripts,
+            &mut self.hooks,
+            &mut self.mcp_servers,
+        ] {
+            for resource in resources.iter_mut() {
+                if resource.resolved_commit.as_deref().is_none_or(str::is_empty) {
+                    continue;
+                }
+                let Some(previous) = old.find_resource_by_id(&resource.id()) else {
+                    continue;
+                };
+                if previous.resolved_commit == resource.resolved_commit
+                    && previous.installed_at == resource.installed_at
+                    && previous.variant_inputs == resource.variant_inputs
+                    && previous.applied_patches == resource.applied_patches
+                    && previous.strip_metadata == resource.strip_metadata
+                {
+                    resource.checksum.clone_from(&previous.checksum);
+                    resource.context_checksum.clone_from(&previous.context_checksum);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lockfile::{LockedResource, LockedResourceBuilder};
+    use tempfile::TempDir;
+
+    fn locked(name: &str, checksum: &str, source: Option<&str>) -> LockedResource {
+        LockedResourceBuilder::new(
+            name.to_string(),
+            format!("agents/{name}.md"),
+            checksum.to_string(),
+            format!(".claude/agents/{name}.md"),
+            ResourceType::Agent,
+        )
+        .source(source.map(str::to_string))
+        .build()
+    }
+
+    #[test]
+    fn test_refresh_checksums_updates_local_and_reports_remote() -> Result<()> {
+        let temp = TempDir::new()?;
+        let agents = temp.path().join(".claude/agents");
+        fs::create_dir_all(&agents)?;
+        fs::write(agents.join("local.md"), "edited locally")?;
+        fs::write(agents.join("remote.md"), "tampered")?;
+        fs::write(agents.join("same.md"), "unchanged")?;
+        let same = LockFile::compute_checksum(&agents.join("same.md"))?;
+
+        let mut lockfile = LockFile::new();
+        lockfile.agents.push(locked("local", "sha256:old", None));
+        lockfile.agents.push(locked("remote", "sha256:old", Some("community")));
+        lockfile.agents.push(locked("same", &same, Some("community")));
+        lockfile.agents.push(locked("gone", "sha256:old", None));
+
+        let refresh = lockfile.refresh_checksums(temp.path())?;
+        assert_eq!(refresh.updated, vec![".claude/agents/local.md"]);
+        assert_eq!(refresh.remote_mismatches, vec![".claude/agents/remote.md"]);
+        assert_eq!(refresh.missing, vec![".claude/agents/gone.md"]);
+
+        assert_eq!(
+            lockfile.agents[0].checksum,
+            LockFile::compute_checksum(&agents.join("local.md"))?
+        );
+        assert_eq!(lockfile.agents[1].checksum, "sha256:old");
+        assert_eq!(lockfile.agents[2].checksum, same);
+        Ok(())
+    }
+
+    #[test]
+    fn test_carry_over_checksums() {
+        let locked = |name: &str, commit: &str, checksum: &str| {
+            let mut resource = LockedResourceBuilder::new(
+                name.to_string(),
+                format!("agents/{name}.md"),
+                checksum.to_string(),
+                format!(".claude/agents/{name}.md"),
+                ResourceType::Agent,
+            )
+            .build();
+            resource.resolved_commit = Some(commit.repeat(40));
+            resource
+        };
+
+        let mut old = LockFile::new();
+        old.agents.push(locked("same", "a", "sha256:same"));
+        old.agents.push(locked("moved", "a", "sha256:moved"));
+
+        let mut new = LockFile::new();
+        new.agents.push(locked("same", "a", ""));
+        new.agents.push(locked("moved", "b", ""));
+        new.agents.push(locked("fresh", "a", ""));
+        new.carry_over_checksums(&old);
+
+        assert_eq!(new.agents[0].checksum, "sha256:same");
+        assert_eq!(new.agents[1].checksum, "");
+        assert_eq!(new.agents[2].checksum, "");
+    }
 }
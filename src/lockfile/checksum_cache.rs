@@ -0,0 +1,238 @@
+//! Cache of file checksums keyed by path, size and modification time.
+//!
+//! Installing and refreshing checksums hash every installed file on each run.
+//! On projects with many large resources that adds up, so the CLI enables a
+//! process-wide cache with [`enable`] that remembers the last SHA-256 of each
+//! file together with its size and mtime. A file whose size and mtime still
+//! match is not read again; any other file is hashed as before.
+//!
+//! The index lives in the cache directory as `checksums.json` and is written
+//! back by [`persist`]. Files modified within the last couple of seconds are
+//! never cached, because a second write in the same mtime tick would not be
+//! noticed. `--no-mtime-cache` leaves the cache disabled so every file is
+//! hashed in full.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::LockFile;
+
+/// Name of the index file inside the cache directory.
+const INDEX_FILE: &str = "checksums.json";
+
+/// Files modified more recently than this are hashed but not cached.
+const RACY_WINDOW: Duration = Duration::from_secs(2);
+
+/// The process-wide cache, `None` until [`enable`] is called.
+static CHECKSUM_CACHE: Mutex<Option<ChecksumCache>> = Mutex::new(None);
+
+/// Size and modification time of a file when its checksum was computed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct CachedChecksum {
+    size: u64,
+    mtime_secs: u64,
+    mtime_nanos: u32,
+    checksum: String,
+}
+
+/// Checksums of previously hashed files, stored at `index_path`.
+#[derive(Debug, Default)]
+struct ChecksumCache {
+    index_path: PathBuf,
+    entries: HashMap<String, CachedChecksum>,
+    dirty: bool,
+}
+
+impl ChecksumCache {
+    /// Load the index from `cache_dir`, starting empty if it is missing or unreadable.
+    fn load(cache_dir: &Path) -> Self {
+        let index_path = cache_dir.join(INDEX_FILE);
+        let entries = std::fs::read(&index_path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default();
+        Self {
+            index_path,
+            entries,
+            dirty: false,
+        }
+    }
+
+    fn save(&mut self) -> Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        // Forget files that no longer exist so the index does not grow forever
+        self.entries.retain(|path, _| Path::new(path).is_file());
+        if let Some(parent) = self.index_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        crate::utils::atomic_write(&self.index_path, &serde_json::to_vec(&self.entries)?)?;
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+/// Enable the checksum cache for this process, loading the index from `cache_dir`.
+pub fn enable(cache_dir: &Path) {
+    let cache = ChecksumCache::load(cache_dir);
+    *CHECKSUM_CACHE.lock().unwrap_or_else(std::sync::PoisonError::into_inner) = Some(cache);
+}
+
+/// Write newly computed checksums back to the index.
+///
+/// Does nothing if the cache is disabled or nothing changed.
+///
+/// # Errors
+///
+/// Returns an error if the index cannot be written.
+pub fn persist() -> Result<()> {
+    match CHECKSUM_CACHE.lock().unwrap_or_else(std::sync::PoisonError::into_inner).as_mut() {
+        Some(cache) => cache.save(),
+        None => Ok(()),
+    }
+}
+
+/// Split a modification time into whole seconds and nanoseconds since the epoch.
+fn mtime_parts(modified: SystemTime) -> Option<(u64, u32)> {
+    let since_epoch = modified.duration_since(UNIX_EPOCH).ok()?;
+    Some((since_epoch.as_secs(), since_epoch.subsec_nanos()))
+}
+
+impl LockFile {
+    /// Compute a file's checksum, reusing the cached value if the file is unchanged.
+    ///
+    /// Behaves exactly like [`compute_checksum`](Self::compute_checksum) when
+    /// the checksum cache is not enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read.
+    pub fn compute_checksum_cached(path: &Path) -> Result<String> {
+        checksum_with_cache(&CHECKSUM_CACHE, path)
+    }
+}
+
+/// Compute `path`'s checksum through `cache`, hashing in full if it is disabled.
+fn checksum_with_cache(cache: &Mutex<Option<ChecksumCache>>, path: &Path) -> Result<String> {
+    if cache.lock().unwrap_or_else(std::sync::PoisonError::into_inner).is_none() {
+        return LockFile::compute_checksum(path);
+    }
+    // Anything unusual is left to compute_checksum, which reports errors properly
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return LockFile::compute_checksum(path);
+    };
+    let (Ok(modified), Ok(absolute)) = (metadata.modified(), std::path::absolute(path)) else {
+        return LockFile::compute_checksum(path);
+    };
+    let Some((mtime_secs, mtime_nanos)) = mtime_parts(modified) else {
+        return LockFile::compute_checksum(path);
+    };
+    let key = absolute.to_string_lossy().into_owned();
+    let size = metadata.len();
+
+    {
+        let guard = cache.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(cached) = guard.as_ref().and_then(|index| index.entries.get(&key))
+            && cached.size == size
+            && cached.mtime_secs == mtime_secs
+            && cached.mtime_nanos == mtime_nanos
+        {
+            return Ok(cached.checksum.clone());
+        }
+    }
+
+    let checksum = LockFile::compute_checksum(path)?;
+
+    let settled = SystemTime::now().duration_since(modified).is_ok_and(|age| age >= RACY_WINDOW);
+    if settled {
+        let mut guard = cache.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(index) = guard.as_mut() {
+            index.entries.insert(
+                key,
+                CachedChecksum {
+                    size,
+                    mtime_secs,
+                    mtime_nanos,
+                    checksum: checksum.clone(),
+                },
+            );
+            index.dirty = true;
+        }
+    }
+    Ok(checksum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn set_mtime(path: &Path, modified: SystemTime) {
+        std::fs::File::options().write(true).open(path).unwrap().set_modified(modified).unwrap();
+    }
+
+    #[test]
+    fn test_cache_reuses_checksum_until_size_or_mtime_changes() {
+        let temp = TempDir::new().unwrap();
+        let file = temp.path().join("agent.md");
+        std::fs::write(&file, "original").unwrap();
+        let old = SystemTime::now() - Duration::from_secs(60);
+        set_mtime(&file, old);
+
+        // Seed the index with a checksum that only the cache could return
+        let mut index = ChecksumCache::load(temp.path());
+        let key = std::path::absolute(&file).unwrap().to_string_lossy().into_owned();
+        let modified = std::fs::metadata(&file).unwrap().modified().unwrap();
+        let (mtime_secs, mtime_nanos) = mtime_parts(modified).unwrap();
+        index.entries.insert(
+            key,
+            CachedChecksum {
+                size: 8,
+                mtime_secs,
+                mtime_nanos,
+                checksum: "sha256:cached".to_string(),
+            },
+        );
+        index.dirty = true;
+        index.save().unwrap();
+
+        let cache = Mutex::new(Some(ChecksumCache::load(temp.path())));
+        assert_eq!(checksum_with_cache(&cache, &file).unwrap(), "sha256:cached");
+
+        // Same size but a new mtime: hashed again
+        std::fs::write(&file, "modified").unwrap();
+        set_mtime(&file, old + Duration::from_secs(1));
+        let fresh = checksum_with_cache(&cache, &file).unwrap();
+        assert_eq!(fresh, LockFile::compute_checksum(&file).unwrap());
+
+        // Just-written files are hashed but not cached
+        let recent = temp.path().join("recent.md");
+        std::fs::write(&recent, "recent").unwrap();
+        checksum_with_cache(&cache, &recent).unwrap();
+
+        cache.lock().unwrap().as_mut().unwrap().save().unwrap();
+        let reloaded = ChecksumCache::load(temp.path());
+        assert_eq!(reloaded.entries.len(), 1);
+        assert_eq!(reloaded.entries.values().next().unwrap().checksum, fresh);
+
+        // A disabled cache always hashes in full
+        assert_eq!(checksum_with_cache(&Mutex::new(None), &file).unwrap(), fresh);
+    }
+
+    #[test]
+    fn test_load_tolerates_missing_and_corrupt_index() {
+        let temp = TempDir::new().unwrap();
+        let mut cache = ChecksumCache::load(temp.path());
+        assert!(cache.entries.is_empty());
+        assert!(cache.save().is_ok(), "nothing to write");
+        assert!(!temp.path().join(INDEX_FILE).exists());
+
+        std::fs::write(temp.path().join(INDEX_FILE), "not json").unwrap();
+        assert!(ChecksumCache::load(temp.path()).entries.is_empty());
+    }
+}
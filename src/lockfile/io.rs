@@ -56,17 +56,44 @@ impl LockFile {
     ///
     /// # Version Compatibility
     ///
-    /// The method checks the lockfile format version and will refuse to load
-    /// lockfiles created by newer versions of AGPM:
+    /// Lockfiles written in an older format are migrated to the current format
+    /// in memory only; `path` is never modified. Use
+    /// [`LockFile::load_and_persist_migration`] to write the upgraded format
+    /// back. The method refuses to load lockfiles created by newer versions of
+    /// AGPM:
     ///
     /// ```text
-    /// Error: Lockfile version 2 is newer than supported version 1.
+    /// Error: Lockfile version 3 is newer than supported version 2.
     /// This lockfile was created by a newer version of agpm.
     /// Please update agpm to the latest version to use this lockfile.
     /// ```
     pub fn load(path: &Path) -> Result<Self> {
+        Ok(Self::read_and_migrate(path)?.0)
+    }
+
+    /// Load lockfile from disk and save a migrated format back to `path`.
+    ///
+    /// Behaves like [`LockFile::load`], but an older lockfile is also written
+    /// back in the current format so the migration only runs once. Only
+    /// commands that write `agpm.lock` anyway, such as `agpm update`, use this.
+    /// A read-only lockfile is still usable, so failing to write it back is not
+    /// an error.
+    pub fn load_and_persist_migration(path: &Path) -> Result<Self> {
+        let (lockfile, migrated) = Self::read_and_migrate(path)?;
+
+        if migrated && let Err(e) = lockfile.save(path) {
+            tracing::warn!("Failed to save migrated lockfile {}: {e:#}", path.display());
+        }
+
+        Ok(lockfile)
+    }
+
+    /// Parse a lockfile and migrate it to the current format in memory.
+    ///
+    /// Returns the lockfile and whether a migration was applied.
+    fn read_and_migrate(path: &Path) -> Result<(Self, bool)> {
         if !path.exists() {
-            return Ok(Self::new());
+            return Ok((Self::new(), false));
         }
 
         let content = fs::read_to_string(path).with_context(|| {
@@ -82,7 +109,7 @@ impl LockFile {
 
         // Handle empty file
         if content.trim().is_empty() {
-            return Ok(Self::new());
+            return Ok((Self::new(), false));
         }
 
         let mut lockfile: Self = toml::from_str(&content)
@@ -101,45 +128,30 @@ impl LockFile {
                 )
             })?;
 
-        // Set resource_type and apply tool defaults based on which section it's in
-        for resource in &mut lockfile.agents {
-            resource.resource_type = crate::core::ResourceType::Agent;
-            if resource.tool.is_none() {
-                resource.tool = Some(crate::core::ResourceType::Agent.default_tool().to_string());
-            }
-        }
-        for resource in &mut lockfile.snippets {
-            resource.resource_type = crate::core::ResourceType::Snippet;
-            if resource.tool.is_none() {
-                resource.tool = Some(crate::core::ResourceType::Snippet.default_tool().to_string());
-            }
-        }
-        for resource in &mut lockfile.commands {
-            resource.resource_type = crate::core::ResourceType::Command;
-            if resource.tool.is_none() {
-                resource.tool = Some(crate::core::ResourceType::Command.default_tool().to_string());
-            }
-        }
-        for resource in &mut lockfile.scripts {
-            resource.resource_type = crate::core::ResourceType::Script;
-            if resource.tool.is_none() {
-                resource.tool = Some(crate::core::ResourceType::Script.default_tool().to_string());
-            }
-        }
-        for resource in &mut lockfile.hooks {
-            resource.resource_type = crate::core::ResourceType::Hook;
-            if resource.tool.is_none() {
-                resource.tool = Some(crate::core::ResourceType::Hook.default_tool().to_string());
+        // Reject formats written by a newer AGPM before touching any entries
+        if lockfile.version > Self::CURRENT_VERSION {
+            return Err(crate::core::AgpmError::Other {
+                message: format!(
+                    "Lockfile version {} is newer than supported version {}.\n\n\
+                    This lockfile was created by a newer version of agpm.\n\
+                    Please update agpm to the latest version to use this lockfile.",
+                    lockfile.version,
+                    Self::CURRENT_VERSION
+                ),
             }
+            .into());
         }
-        for resource in &mut lockfile.mcp_servers {
-            resource.resource_type = crate::core::ResourceType::McpServer;
-            if resource.tool.is_none() {
-                resource.tool =
-                    Some(crate::core::ResourceType::McpServer.default_tool().to_string());
+
+        // Set resource_type based on which section each entry is in
+        for resource_type in crate::core::ResourceType::all() {
+            for resource in lockfile.get_resources_mut(resource_type) {
+                resource.resource_type = *resource_type;
             }
         }
 
+        // Upgrade older formats (e.g. infer `tool` for v1 entries)
+        let migrated = lockfile.migrate();
+
         // Recompute hash for all VariantInputs
         // The hash is not stored in the lockfile (serde(skip)) but needs to be computed
         // from the variant_inputs Value for resource identity comparison
@@ -149,21 +161,7 @@ impl LockFile {
             }
         }
 
-        // Check version compatibility
-        if lockfile.version > Self::CURRENT_VERSION {
-            return Err(crate::core::AgpmError::Other {
-                message: format!(
-                    "Lockfile version {} is newer than supported version {}.\n\n\
-                    This lockfile was created by a newer version of agpm.\n\
-                    Please update agpm to the latest version to use this lockfile.",
-                    lockfile.version,
-                    Self::CURRENT_VERSION
-                ),
-            }
-            .into());
-        }
-
-        Ok(lockfile)
+        Ok((lockfile, migrated))
     }
 
     /// Save lockfile to disk with atomic writes and custom formatting.
@@ -231,7 +229,7 @@ impl LockFile {
     ///
     /// ```toml
     /// # Auto-generated lockfile - DO NOT EDIT
-    /// version = 1
+    /// version = 2
     ///
     /// [[sources]]
     /// name = "community"
@@ -240,8 +238,12 @@ impl LockFile {
     /// fetched_at = "2024-01-15T10:30:00Z"
     /// ```
     pub fn save(&self, path: &Path) -> Result<()> {
+        // Lockfiles are always written in the current format
+        let mut current = self.clone();
+        current.migrate();
+
         // Normalize lockfile for backward compatibility before saving
-        let normalized = self.normalize();
+        let normalized = current.normalize();
 
         // Use toml_edit to ensure applied_patches are formatted as inline tables
         let mut content = String::from("# Auto-generated lockfile - DO NOT EDIT\n");
@@ -0,0 +1,153 @@
+//! Lockfile format migrations.
+//!
+//! Lockfiles record the format version they were written with. When an older
+//! lockfile is loaded, [`LockFile::migrate`] upgrades it in memory one version at
+//! a time so the rest of AGPM only ever deals with the current format.
+//!
+//! # Version history
+//!
+//! - **v1**: Original format. `tool` could be omitted (implying the resource
+//!   type's default tool) and entries could be keyed by their manifest alias
+//!   instead of their canonical name.
+//! - **v2**: `tool`, `manifest_alias`, `context_checksum`, and `variant_inputs`
+//!   are first-class. Every entry records its tool explicitly and is named
+//!   canonically, with the manifest key kept in `manifest_alias`.
+
+use super::LockFile;
+
+impl LockFile {
+    /// Upgrade a lockfile loaded from an older format version in place.
+    ///
+    /// Migrations are deterministic: migrating the same file twice produces the
+    /// same result, and a migrated lockfile needs no further changes when it is
+    /// saved and loaded again.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the lockfile was migrated, `false` if it was already current.
+    pub(crate) fn migrate(&mut self) -> bool {
+        if self.version >= Self::CURRENT_VERSION {
+            return false;
+        }
+
+        let from = self.version;
+        if self.version < 2 {
+            self.migrate_v1_to_v2();
+        }
+
+        tracing::debug!("Migrated lockfile from format v{from} to v{}", self.version);
+        true
+    }
+
+    /// v1 → v2: make `tool` explicit and move aliases into `manifest_alias`.
+    fn migrate_v1_to_v2(&mut self) {
+        for resource_type in crate::core::ResourceType::all() {
+            for resource in self.get_resources_mut(resource_type) {
+                if resource.tool.is_none() {
+                    resource.tool = Some(resource_type.default_tool().to_string());
+                }
+            }
+        }
+
+        *self = self.normalize();
+        self.version = 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const V1_LOCKFILE: &str = r#"# Auto-generated lockfile - DO NOT EDIT
+version = 1
+
+[[sources]]
+name = "community"
+url = "https://github.com/example/agents.git"
+fetched_at = "2024-01-01T00:00:00Z"
+
+[[agents]]
+name = "my-reviewer"
+source = "community"
+url = "https://github.com/example/agents.git"
+path = "agents/reviewer.md"
+version = "v1.0.0"
+resolved_commit = "a1b2c3d4e5f6a1b2c3d4e5f6a1b2c3d4e5f6a1b2"
+checksum = "sha256:abc"
+installed_at = ".claude/agents/my-reviewer.md"
+dependencies = ["snippet:utils", "agent:helper"]
+
+[[snippets]]
+name = "utils"
+path = "snippets/utils.md"
+checksum = "sha256:def"
+installed_at = ".agpm/snippets/utils.md"
+"#;
+
+    #[test]
+    fn test_migrate_v1_lockfile() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("agpm.lock");
+        std::fs::write(&path, V1_LOCKFILE).unwrap();
+
+        let lockfile = LockFile::load_and_persist_migration(&path).unwrap();
+        assert_eq!(lockfile.version, 2);
+
+        // Tool is inferred from the resource type's default
+        assert_eq!(lockfile.agents[0].tool.as_deref(), Some("claude-code"));
+        assert_eq!(lockfile.snippets[0].tool.as_deref(), Some("agpm"));
+
+        // The manifest key moves to manifest_alias
+        let agent = &lockfile.agents[0];
+        assert_eq!(agent.manifest_alias.as_deref(), Some("my-reviewer"));
+        assert_ne!(agent.name, "my-reviewer");
+        assert_eq!(agent.dependencies, vec!["agent:helper", "snippet:utils"]);
+
+        // The migrated lockfile is written back as v2
+        let saved = std::fs::read_to_string(&path).unwrap();
+        assert!(saved.contains("version = 2"));
+    }
+
+    #[test]
+    fn test_migration_is_deterministic() {
+        let temp = TempDir::new().unwrap();
+        let first = temp.path().join("first.lock");
+        let second = temp.path().join("second.lock");
+        std::fs::write(&first, V1_LOCKFILE).unwrap();
+        std::fs::write(&second, V1_LOCKFILE).unwrap();
+
+        LockFile::load_and_persist_migration(&first).unwrap();
+        LockFile::load_and_persist_migration(&second).unwrap();
+        let migrated = std::fs::read_to_string(&first).unwrap();
+        assert_eq!(migrated, std::fs::read_to_string(&second).unwrap());
+
+        // Re-loading a migrated lockfile makes no further changes
+        let reloaded = LockFile::load_and_persist_migration(&first).unwrap();
+        assert_eq!(std::fs::read_to_string(&first).unwrap(), migrated);
+        reloaded.save(&first).unwrap();
+        assert_eq!(std::fs::read_to_string(&first).unwrap(), migrated);
+    }
+
+    #[test]
+    fn test_load_does_not_persist_migration() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("agpm.lock");
+        std::fs::write(&path, V1_LOCKFILE).unwrap();
+
+        let lockfile = LockFile::load(&path).unwrap();
+        assert_eq!(lockfile.version, 2);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), V1_LOCKFILE);
+    }
+
+    #[test]
+    fn test_newer_version_is_rejected() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("agpm.lock");
+        std::fs::write(&path, "version = 99\n").unwrap();
+
+        let err = LockFile::load(&path).unwrap_err();
+        let message = format!("{err:#}");
+        assert!(message.contains("Lockfile version 99 is newer than supported version 2"));
+    }
+}
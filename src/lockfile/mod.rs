@@ -26,7 +26,7 @@
 //!
 //! ```toml
 //! # Auto-generated lockfile - DO NOT EDIT
-//! version = 1
+//! version = 2
 //!
 //! # Source repositories with resolved commits
 //! [[sources]]
@@ -108,7 +108,7 @@
 //!
 //! ```toml
 //! # agpm.lock (lockfile)
-//! version = 1
+//! version = 2
 //!
 //! [[sources]]
 //! name = "community"
@@ -231,18 +231,16 @@
 //! AGPM checks lockfile format version and provides clear error messages:
 //!
 //! ```text
-//! Error: Lockfile version 2 is newer than supported version 1.
+//! Error: Lockfile version 3 is newer than supported version 2.
 //! This lockfile was created by a newer version of agpm.
 //! Please update agpm to the latest version to use this lockfile.
 //! ```
 //!
 //! ## Upgrading Lockfiles
-//! Future format versions will include automatic migration:
-//!
-//! ```bash
-//! # Future: Migrate lockfile to newer format
-//! agpm install --migrate-lockfile
-//! ```
+//! Older lockfiles are migrated automatically when loaded. A version 1 lockfile
+//! has `tool` inferred from each resource type's default and its entries renamed
+//! canonically (keeping the manifest key in `manifest_alias`), and is then saved
+//! back as version 2. No command-line flag is needed.
 //!
 //! # Comparison with Cargo.lock
 //!
@@ -385,6 +383,64 @@ pub enum StalenessReason {
         /// New tool from manifest (with defaults applied)
         new_tool: String,
     },
+
+    /// A dependency resolves to a different commit than the one locked.
+    ResolvedCommitChanged {
+        /// Name of the dependency
+        name: String,
+        /// Type of resource (agent, snippet, etc.)
+        resource_type: crate::core::ResourceType,
+        /// Commit recorded in the lockfile
+        old_commit: String,
+        /// Commit produced by resolution
+        new_commit: String,
+    },
+
+    /// A dependency would be installed to a different location.
+    InstalledAtChanged {
+        /// Name of the dependency
+        name: String,
+        /// Type of resource (agent, snippet, etc.)
+        resource_type: crate::core::ResourceType,
+        /// Installation path recorded in the lockfile
+        old_path: String,
+        /// Installation path produced by resolution
+        new_path: String,
+    },
+
+    /// The content of an inline resource differs from the one locked.
+    InlineContentChanged {
+        /// Name of the dependency
+        name: String,
+        /// Type of resource (agent, snippet, etc.)
+        resource_type: crate::core::ResourceType,
+    },
+
+    /// Resolution produced a transitive dependency that is not in the lockfile.
+    UnlockedDependency {
+        /// Name of the dependency
+        name: String,
+        /// Type of resource (agent, snippet, etc.)
+        resource_type: crate::core::ResourceType,
+    },
+
+    /// The lockfile contains a dependency that resolution no longer produces.
+    ExtraDependency {
+        /// Name of the dependency
+        name: String,
+        /// Type of resource (agent, snippet, etc.)
+        resource_type: crate::core::ResourceType,
+    },
+
+    /// A dependency was enabled or disabled in the manifest.
+    EnabledChanged {
+        /// Name of the dependency
+        name: String,
+        /// Type of resource (agent, snippet, etc.)
+        resource_type: crate::core::ResourceType,
+        /// Whether the manifest now enables the dependency
+        enabled: bool,
+    },
 }
 
 impl std::fmt::Display for StalenessReason {
@@ -449,6 +505,66 @@ impl std::fmt::Display for StalenessReason {
                     "Dependency '{name}' ({resource_type}) tool changed from '{old_tool}' to '{new_tool}'"
                 )
             }
+            Self::ResolvedCommitChanged {
+                name,
+                resource_type,
+                old_commit,
+                new_commit,
+            } => {
+                write!(
+                    f,
+                    "Dependency '{name}' ({resource_type}) resolves to commit {} but lockfile has {}",
+                    &new_commit[..8.min(new_commit.len())],
+                    &old_commit[..8.min(old_commit.len())]
+                )
+            }
+            Self::InstalledAtChanged {
+                name,
+                resource_type,
+                old_path,
+                new_path,
+            } => {
+                write!(
+                    f,
+                    "Dependency '{name}' ({resource_type}) install path changed from '{old_path}' to '{new_path}'"
+                )
+            }
+            Self::InlineContentChanged {
+                name,
+                resource_type,
+            } => {
+                write!(f, "Dependency '{name}' ({resource_type}) inline content changed")
+            }
+            Self::UnlockedDependency {
+                name,
+                resource_type,
+            } => {
+                write!(
+                    f,
+                    "Transitive dependency '{name}' ({resource_type}) is required but missing from lockfile"
+                )
+            }
+            Self::ExtraDependency {
+                name,
+                resource_type,
+            } => {
+                write!(
+                    f,
+                    "Dependency '{name}' ({resource_type}) is in lockfile but no longer required"
+                )
+            }
+            Self::EnabledChanged {
+                name,
+                resource_type,
+                enabled,
+            } => {
+                let state = if *enabled {
+                    "enabled"
+                } else {
+                    "disabled"
+                };
+                write!(f, "Dependency '{name}' ({resource_type}) is now {state} in the manifest")
+            }
         }
     }
 }
@@ -464,6 +580,7 @@ impl std::error::Error for StalenessReason {}
 /// - Same name, different sources: Different repositories providing same-named resources
 /// - Same name, different tools: Resources used by different tools (e.g., Claude Code vs OpenCode)
 /// - Same name and source, different tools: Transitive dependencies inherited from different parent tools
+/// - Same name, source and tool, different install paths: Variants of one dependency
 ///
 /// # Examples
 ///
@@ -494,6 +611,12 @@ pub struct ResourceId {
     /// they have the same name, source, and tool. Only the hash is needed for identity
     /// comparison; the full JSON value is stored in LockedResource for serialization.
     variant_inputs_hash: String,
+    /// Where the resource is installed, relative to the project directory.
+    ///
+    /// Variants of one dependency may differ only in their install path. Ids taken
+    /// from a [`LockedResource`] record it; ids built with [`ResourceId::new`]
+    /// leave it unset and match the resource at any location.
+    installed_at: Option<String>,
 }
 
 impl ResourceId {
@@ -511,6 +634,7 @@ impl ResourceId {
             tool: tool.map(|t| t.into()),
             resource_type,
             variant_inputs_hash,
+            installed_at: None,
         }
     }
 
@@ -522,6 +646,19 @@ impl ResourceId {
             tool: resource.tool.clone(),
             resource_type: resource.resource_type,
             variant_inputs_hash: resource.variant_inputs.hash().to_string(),
+            installed_at: Some(resource.installed_at.clone()),
+        }
+    }
+
+    /// This id without its install path, matching the resource at any location.
+    ///
+    /// Used to pair a resource with its entry in another lockfile when its
+    /// `target` or `filename` may have changed.
+    #[must_use]
+    pub fn ignoring_install_path(&self) -> Self {
+        Self {
+            installed_at: None,
+            ..self.clone()
         }
     }
 
@@ -600,7 +737,7 @@ impl std::fmt::Display for ResourceId {
 /// use agpm_cli::lockfile::LockFile;
 ///
 /// let lockfile = LockFile::new();
-/// assert_eq!(lockfile.version, 1);
+/// assert_eq!(lockfile.version, 2);
 /// assert!(lockfile.sources.is_empty());
 /// ```
 ///
@@ -621,8 +758,8 @@ pub struct LockFile {
     /// Version of the lockfile format.
     ///
     /// This field enables forward and backward compatibility checking. AGPM will
-    /// refuse to load lockfiles with versions newer than it supports, and may
-    /// provide migration paths for older versions in the future.
+    /// refuse to load lockfiles with versions newer than it supports, and migrates
+    /// older versions to the current format when loading them.
     pub version: u32,
 
     /// Locked source repositories with their resolved commit hashes.
@@ -694,6 +831,25 @@ pub struct LockFile {
     /// This field is omitted from TOML serialization if empty.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub hooks: Vec<LockedResource>,
+
+    /// Dependencies the manifest marks with `enabled = false`.
+    ///
+    /// Disabled dependencies are not resolved or installed, so they have no
+    /// resource entry; they are recorded here so the lockfile reflects the
+    /// manifest and `agpm list --show-disabled` can show them.
+    ///
+    /// This field is omitted from TOML serialization if empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled: Vec<DisabledResource>,
+}
+
+/// A manifest dependency that is disabled with `enabled = false`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct DisabledResource {
+    /// Dependency name from the manifest
+    pub name: String,
+    /// Type of resource (agent, snippet, etc.)
+    pub resource_type: crate::core::ResourceType,
 }
 
 /// A locked source repository with resolved commit information.
@@ -717,8 +873,17 @@ pub struct LockFile {
 /// [[sources]]
 /// name = "community"
 /// url = "https://github.com/example/agpm-community.git"
-/// commit = "a1b2c3d4e5f6789abcdef0123456789abcdef012"
 /// fetched_at = "2024-01-15T10:30:00Z"
+/// resolved_commit = "a1b2c3d4e5f6789abcdef0123456789abcdef012"
+/// ```
+///
+/// Sources resolved with signature verification also list the tags that were
+/// verified and who signed them:
+///
+/// ```toml
+/// [[sources.verified_tags]]
+/// tag = "v1.2.0"
+/// signer = "Alice <alice@example.com>"
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LockedSource {
@@ -737,8 +902,37 @@ pub struct LockedSource {
     /// Timestamp of last successful fetch in RFC 3339 format.
     ///
     /// Records when the repository was last fetched from the remote.
-    /// This helps track staleness and debugging fetch issues.
+    /// This helps track staleness and debugging fetch issues. Re-resolving
+    /// keeps the previous timestamp until one of the source's locked commits
+    /// changes, so an unchanged manifest produces an identical lockfile.
     pub fetched_at: String,
+
+    /// The commit every resource from this source is locked at.
+    ///
+    /// Only set when all of the source's resources resolved to the same commit,
+    /// which shows at a glance where the source is pinned. `None` when the
+    /// resources use different commits or the source is unused.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_commit: Option<String>,
+
+    /// Tags of this source whose signatures were verified during resolution.
+    ///
+    /// Only filled in when the source has `verify_signatures = true` or the
+    /// install ran with `--verify-signatures`. Resolution fails on a tag that
+    /// does not verify, so every tag listed here passed verification.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub verified_tags: Vec<VerifiedTag>,
+}
+
+/// A source tag whose GPG or SSH signature was verified, recorded for audit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifiedTag {
+    /// The tag name, as resolved from the dependency's version
+    pub tag: String,
+    /// The signer Git reported, such as `Alice <alice@example.com>` for GPG or
+    /// the principal from the allowed signers file for SSH
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signer: Option<String>,
 }
 
 /// A locked resource (agent or snippet) with resolved version and integrity information.
@@ -962,6 +1156,25 @@ pub struct LockedResource {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub install: Option<bool>,
 
+    /// Whether AGPM-managed frontmatter keys are stripped from the installed file.
+    ///
+    /// Set from the dependency's `strip_metadata` option or the manifest-wide
+    /// default. Recorded so that changing the option reinstalls the file even
+    /// when its commit is unchanged.
+    ///
+    /// Omitted from TOML serialization when `false`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub strip_metadata: bool,
+
+    /// Content of a resource defined inline in the manifest.
+    ///
+    /// Recorded so the resource can be installed from the lockfile alone.
+    /// Inline resources have no `source`, and their `path` is empty.
+    ///
+    /// Omitted from TOML serialization when `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inline: Option<String>,
+
     /// Variant inputs for template rendering.
     ///
     /// Stores the template variable overrides that were specified in the manifest
@@ -997,6 +1210,8 @@ pub struct LockedResourceBuilder {
     manifest_alias: Option<String>,
     applied_patches: BTreeMap<String, toml::Value>,
     install: Option<bool>,
+    strip_metadata: bool,
+    inline: Option<String>,
     context_checksum: Option<String>,
     variant_inputs: crate::resolver::lockfile_builder::VariantInputs,
 }
@@ -1025,6 +1240,8 @@ impl LockedResourceBuilder {
             manifest_alias: None,
             applied_patches: BTreeMap::new(),
             install: None,
+            strip_metadata: false,
+            inline: None,
             context_checksum: None,
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
         }
@@ -1084,6 +1301,18 @@ impl LockedResourceBuilder {
         self
     }
 
+    /// Set whether AGPM-managed frontmatter is stripped on install.
+    pub fn strip_metadata(mut self, strip_metadata: bool) -> Self {
+        self.strip_metadata = strip_metadata;
+        self
+    }
+
+    /// Set the inline content.
+    pub fn inline(mut self, inline: Option<String>) -> Self {
+        self.inline = inline;
+        self
+    }
+
     /// Set the context checksum.
     pub fn context_checksum(mut self, context_checksum: Option<String>) -> Self {
         self.context_checksum = context_checksum;
@@ -1117,6 +1346,8 @@ impl LockedResourceBuilder {
             manifest_alias: self.manifest_alias,
             applied_patches: self.applied_patches,
             install: self.install,
+            strip_metadata: self.strip_metadata,
+            inline: self.inline,
             variant_inputs: self.variant_inputs,
         }
     }
@@ -1134,13 +1365,19 @@ impl LockedResource {
     /// Check if resource matches ResourceId by comparing name, source, tool, and variant_inputs hash.
     ///
     /// Variant_inputs hash is part of identity - same resource with different variant_inputs
-    /// produces different artifacts and must be tracked separately.
+    /// produces different artifacts and must be tracked separately. The install path is
+    /// compared too when the id records one, which tells apart variants of one
+    /// dependency that differ only in their filename.
     #[must_use]
     pub fn matches_id(&self, id: &ResourceId) -> bool {
         self.name == id.name
             && self.source == id.source
             && self.tool == id.tool
             && self.variant_inputs.hash() == id.variant_inputs_hash
+            && id
+                .installed_at
+                .as_ref()
+                .is_none_or(|installed_at| *installed_at == self.installed_at)
     }
 
     /// Parse the dependencies field into structured lockfile dependency references.
@@ -1314,12 +1551,16 @@ impl LockedResource {
 
 // Submodules for organized implementation
 mod checksum;
+pub mod checksum_cache;
 mod helpers;
 mod io;
 pub mod lockfile_dependency_ref;
+mod migration;
+mod orphans;
 pub mod private_lock;
 mod resource_ops;
 mod validation;
+pub use checksum::ChecksumRefresh;
 pub use private_lock::PrivateLockFile;
 
 // Patch display utilities
@@ -1331,7 +1572,7 @@ impl LockFile {
     /// This constant defines the lockfile format version that this version of AGPM
     /// generates. It's used for compatibility checking when loading lockfiles that
     /// may have been created by different versions of AGPM.
-    const CURRENT_VERSION: u32 = 1;
+    const CURRENT_VERSION: u32 = 2;
 
     /// Create a new empty lockfile with the current format version.
     ///
@@ -1344,7 +1585,7 @@ impl LockFile {
     /// use agpm_cli::lockfile::LockFile;
     ///
     /// let lockfile = LockFile::new();
-    /// assert_eq!(lockfile.version, 1);
+    /// assert_eq!(lockfile.version, 2);
     /// assert!(lockfile.sources.is_empty());
     /// assert!(lockfile.agents.is_empty());
     /// assert!(lockfile.snippets.is_empty());
@@ -1360,6 +1601,7 @@ impl LockFile {
             mcp_servers: Vec::new(),
             scripts: Vec::new(),
             hooks: Vec::new(),
+            disabled: Vec::new(),
         }
     }
 }
@@ -1403,6 +1645,7 @@ impl LockFile {
         normalized.scripts.sort_by(Self::compare_resources);
         normalized.hooks.sort_by(Self::compare_resources);
         normalized.mcp_servers.sort_by(Self::compare_resources);
+        normalized.sources.sort_by(|a, b| a.name.cmp(&b.name));
 
         normalized
     }
@@ -1410,8 +1653,8 @@ impl LockFile {
     /// Compare two resources for deterministic sorting.
     ///
     /// Sort order:
-    /// 1. By name (lexicographic)
-    /// 2. By source (None first, then lexicographic)
+    /// 1. By source (None first, then lexicographic)
+    /// 2. By name (lexicographic)
     /// 3. By tool (None first, then lexicographic)
     /// 4. By template_vars (lexicographic comparison of JSON strings)
     ///
@@ -1419,9 +1662,9 @@ impl LockFile {
     /// exists with different template_vars (e.g., backend-engineer with language=typescript
     /// vs language=javascript).
     fn compare_resources(a: &LockedResource, b: &LockedResource) -> std::cmp::Ordering {
-        a.name
-            .cmp(&b.name)
-            .then_with(|| a.source.cmp(&b.source))
+        a.source
+            .cmp(&b.source)
+            .then_with(|| a.name.cmp(&b.name))
             .then_with(|| a.tool.cmp(&b.tool))
             .then_with(|| a.variant_inputs.hash().cmp(b.variant_inputs.hash()))
     }
@@ -0,0 +1,246 @@
+//! Detection of orphaned lockfile entries, and of the entries that depend on
+//! a given one.
+//!
+//! An entry is orphaned when its manifest dependency has been removed and no
+//! remaining resource depends on it transitively. `agpm install` drops such
+//! entries when it re-resolves, but until then they linger in `agpm.lock` and
+//! their files stay installed. `agpm validate --check-lock` reports them and
+//! `--prune` removes them.
+
+use std::collections::{HashSet, VecDeque};
+
+use crate::core::ResourceType;
+use crate::manifest::Manifest;
+
+use super::{LockFile, LockedResource};
+
+impl LockFile {
+    /// Find lockfile entries that are no longer reachable from the manifest.
+    ///
+    /// Entries whose manifest key (see [`LockedResource::lookup_name`]) is still
+    /// declared in `manifest` are roots; everything reachable from a root through
+    /// the recorded `dependencies` is kept. The remaining entries are returned
+    /// as `(resource type, entry)` pairs in lockfile order.
+    ///
+    /// Dependency references are matched by type and canonical name only, so an
+    /// entry is kept whenever any remaining resource might still require it.
+    #[must_use]
+    pub fn find_orphans(&self, manifest: &Manifest) -> Vec<(ResourceType, &LockedResource)> {
+        let mut reachable: HashSet<(ResourceType, usize)> = HashSet::new();
+        let mut queue = VecDeque::new();
+
+        for resource_type in ResourceType::all() {
+            let declared = manifest.get_resources(resource_type);
+            for (index, entry) in self.get_resources(resource_type).iter().enumerate() {
+                if declared.contains_key(entry.lookup_name())
+                    && reachable.insert((*resource_type, index))
+                {
+                    queue.push_back((*resource_type, index));
+                }
+            }
+        }
+
+        while let Some((resource_type, index)) = queue.pop_front() {
+            let entry = &self.get_resources(&resource_type)[index];
+            for dep in entry.parsed_dependencies() {
+                for (dep_index, candidate) in
+                    self.get_resources(&dep.resource_type).iter().enumerate()
+                {
+                    if matches_dependency_path(candidate, &dep.path)
+                        && reachable.insert((dep.resource_type, dep_index))
+                    {
+                        queue.push_back((dep.resource_type, dep_index));
+                    }
+                }
+            }
+        }
+
+        ResourceType::all()
+            .iter()
+            .flat_map(|resource_type| {
+                self.get_resources(resource_type)
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| !reachable.contains(&(*resource_type, *index)))
+                    .map(|(_, entry)| (*resource_type, entry))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Return a copy of this lockfile with all orphaned entries removed.
+    ///
+    /// Sources that are no longer referenced by any remaining entry are dropped
+    /// as well.
+    #[must_use]
+    pub fn without_orphans(&self, manifest: &Manifest) -> LockFile {
+        let orphans: HashSet<(ResourceType, &str, Option<&str>)> = self
+            .find_orphans(manifest)
+            .into_iter()
+            .map(|(resource_type, entry)| {
+                (resource_type, entry.name.as_str(), entry.source.as_deref())
+            })
+            .collect();
+
+        let mut pruned = self.clone();
+        for resource_type in ResourceType::all() {
+            pruned.get_resources_mut(resource_type).retain(|entry| {
+                !orphans.contains(&(*resource_type, entry.name.as_str(), entry.source.as_deref()))
+            });
+        }
+
+        let used_sources: HashSet<String> =
+            pruned.all_resources().into_iter().filter_map(|entry| entry.source.clone()).collect();
+        pruned.sources.retain(|source| used_sources.contains(&source.name));
+
+        pruned
+    }
+
+    /// Find the entries that record `entry` among their `dependencies`.
+    ///
+    /// References are matched by type and canonical name only, like
+    /// [`Self::find_orphans`], so every entry that might require `entry` is
+    /// returned. `entry` itself is never included.
+    #[must_use]
+    pub fn find_dependents(
+        &self,
+        resource_type: ResourceType,
+        entry: &LockedResource,
+    ) -> Vec<(ResourceType, &LockedResource)> {
+        ResourceType::all()
+            .iter()
+            .flat_map(|dependent_type| {
+                self.get_resources(dependent_type)
+                    .iter()
+                    .filter(|dependent| !std::ptr::eq(*dependent, entry))
+                    .filter(|dependent| {
+                        dependent.parsed_dependencies().any(|dep| {
+                            dep.resource_type == resource_type
+                                && matches_dependency_path(entry, &dep.path)
+                        })
+                    })
+                    .map(|dependent| (*dependent_type, dependent))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+/// Check whether a dependency reference path names the given entry.
+///
+/// References use the canonical name, which is the resource path without its
+/// extension; older lockfiles may record the full path.
+fn matches_dependency_path(entry: &LockedResource, path: &str) -> bool {
+    if entry.name == path || entry.path == path {
+        return true;
+    }
+    let without_extension =
+        entry.path.rsplit_once('.').map_or(entry.path.as_str(), |(stem, _)| stem);
+    without_extension == path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::ResourceDependency;
+
+    fn locked(name: &str, resource_type: ResourceType, alias: Option<&str>) -> LockedResource {
+        LockedResource {
+            name: name.to_string(),
+            source: None,
+            url: None,
+            path: format!("{name}.md"),
+            version: None,
+            resolved_commit: None,
+            checksum: "sha256:test".to_string(),
+            installed_at: format!(".claude/{name}.md"),
+            dependencies: vec![],
+            resource_type,
+            tool: Some("claude-code".to_string()),
+            manifest_alias: alias.map(str::to_string),
+            context_checksum: None,
+            applied_patches: std::collections::BTreeMap::new(),
+            install: None,
+            variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
+            inline: None,
+        }
+    }
+
+    fn manifest_with_agent(name: &str) -> Manifest {
+        let mut manifest = Manifest::new();
+        manifest
+            .agents
+            .insert(name.to_string(), ResourceDependency::Simple(format!("agents/{name}.md")));
+        manifest
+    }
+
+    #[test]
+    fn test_find_orphans_keeps_transitive_dependencies() {
+        let mut lockfile = LockFile::new();
+        let mut agent = locked("agents/helper", ResourceType::Agent, Some("helper"));
+        agent.dependencies = vec!["snippet:snippets/utils".to_string()];
+        lockfile.agents.push(agent);
+        lockfile.snippets.push(locked("snippets/utils", ResourceType::Snippet, None));
+
+        assert!(lockfile.find_orphans(&manifest_with_agent("helper")).is_empty());
+    }
+
+    #[test]
+    fn test_find_orphans_reports_removed_dependency_and_its_subtree() {
+        let mut lockfile = LockFile::new();
+        let mut removed = locked("agents/old", ResourceType::Agent, Some("old"));
+        removed.dependencies = vec!["snippet:snippets/old-utils".to_string()];
+        lockfile.agents.push(removed);
+        lockfile.agents.push(locked("agents/helper", ResourceType::Agent, Some("helper")));
+        lockfile.snippets.push(locked("snippets/old-utils", ResourceType::Snippet, None));
+
+        let orphans: Vec<_> = lockfile
+            .find_orphans(&manifest_with_agent("helper"))
+            .into_iter()
+            .map(|(resource_type, entry)| (resource_type, entry.name.clone()))
+            .collect();
+        assert_eq!(
+            orphans,
+            vec![
+                (ResourceType::Agent, "agents/old".to_string()),
+                (ResourceType::Snippet, "snippets/old-utils".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_dependents() {
+        let mut lockfile = LockFile::new();
+        let mut agent = locked("agents/helper", ResourceType::Agent, Some("helper"));
+        agent.dependencies = vec!["snippet:snippets/utils".to_string()];
+        lockfile.agents.push(agent);
+        lockfile.agents.push(locked("agents/other", ResourceType::Agent, Some("other")));
+        lockfile.snippets.push(locked("snippets/utils", ResourceType::Snippet, None));
+
+        let dependents = lockfile.find_dependents(ResourceType::Snippet, &lockfile.snippets[0]);
+        assert_eq!(dependents.len(), 1);
+        assert_eq!(dependents[0].1.name, "agents/helper");
+        assert!(lockfile.find_dependents(ResourceType::Agent, &lockfile.agents[0]).is_empty());
+    }
+
+    #[test]
+    fn test_without_orphans_drops_entries_and_unused_sources() {
+        let mut lockfile = LockFile::new();
+        lockfile.add_source(
+            "community".to_string(),
+            "https://example.com/community.git".to_string(),
+            "abc123".to_string(),
+        );
+        let mut removed = locked("agents/old", ResourceType::Agent, Some("old"));
+        removed.source = Some("community".to_string());
+        lockfile.agents.push(removed);
+        lockfile.agents.push(locked("agents/helper", ResourceType::Agent, Some("helper")));
+
+        let pruned = lockfile.without_orphans(&manifest_with_agent("helper"));
+        assert_eq!(pruned.agents.len(), 1);
+        assert_eq!(pruned.agents[0].name, "agents/helper");
+        assert!(pruned.sources.is_empty());
+        assert!(pruned.find_orphans(&manifest_with_agent("helper")).is_empty());
+    }
+}
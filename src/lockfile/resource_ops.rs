@@ -4,7 +4,11 @@
 //! resources (agents, snippets, commands, scripts, hooks, MCP servers) within
 //! the lockfile.
 
+use std::collections::{HashMap, HashSet};
+
+use super::lockfile_dependency_ref::LockfileDependencyRef;
 use super::{LockFile, LockedResource, LockedSource, ResourceId};
+use crate::core::ResourceType;
 
 impl LockFile {
     /// Add or update source repository, setting fetched_at to current UTC time.
@@ -15,7 +19,7 @@ impl LockFile {
     ///
     /// * `name` - Unique source identifier (matches manifest `[sources]` keys)
     /// * `url` - Full Git repository URL
-    /// * `commit` - Resolved 40-character commit hash
+    /// * `commit` - Commit the source is locked at, or empty if not (yet) known
     ///
     /// # Behavior
     ///
@@ -43,7 +47,7 @@ impl LockFile {
     ///
     /// The `fetched_at` timestamp is always recorded in UTC to ensure consistency
     /// across different time zones and systems.
-    pub fn add_source(&mut self, name: String, url: String, _commit: String) {
+    pub fn add_source(&mut self, name: String, url: String, commit: String) {
         // Remove existing entry if present
         self.sources.retain(|s| s.name != name);
 
@@ -51,9 +55,96 @@ impl LockFile {
             name,
             url,
             fetched_at: chrono::Utc::now().to_rfc3339(),
+            resolved_commit: (!commit.is_empty()).then_some(commit),
+            verified_tags: Vec::new(),
         });
     }
 
+    /// Set each source's `resolved_commit` from the resources locked from it.
+    ///
+    /// A source gets the commit shared by all of its resources, or `None` when
+    /// they resolved to different commits or it has no resources.
+    pub fn record_source_commits(&mut self) {
+        let mut commits: HashMap<&str, HashSet<&str>> = HashMap::new();
+        for resource in self.all_resources() {
+            if let Some(source) = resource.source.as_deref() {
+                commits
+                    .entry(source)
+                    .or_default()
+                    .insert(resource.resolved_commit.as_deref().unwrap_or_default());
+            }
+        }
+
+        let shared: HashMap<String, String> = commits
+            .into_iter()
+            .filter_map(|(source, commits)| {
+                let mut commits = commits.into_iter();
+                match (commits.next(), commits.next()) {
+                    (Some(commit), None) if !commit.is_empty() => {
+                        Some((source.to_string(), commit.to_string()))
+                    }
+                    _ => None,
+                }
+            })
+            .collect();
+        for source in &mut self.sources {
+            source.resolved_commit = shared.get(&source.name).cloned();
+        }
+    }
+
+    /// Keep the `fetched_at` of sources whose locked commits did not change.
+    ///
+    /// A source takes its timestamp from `old` when it has the same URL there
+    /// and its resources are locked at the same commits, so re-resolving an
+    /// unchanged manifest writes an identical lockfile. Sources that moved to
+    /// another commit keep the time of this resolution.
+    pub fn carry_over_fetched_at(&mut self, old: &LockFile) {
+        fn source_commits(lockfile: &LockFile) -> HashMap<String, HashSet<String>> {
+            let mut commits: HashMap<String, HashSet<String>> = HashMap::new();
+            for resource in lockfile.all_resources() {
+                if let Some(source) = &resource.source {
+                    commits
+                        .entry(source.clone())
+                        .or_default()
+                        .insert(resource.resolved_commit.clone().unwrap_or_default());
+                }
+            }
+            commits
+        }
+
+        let new_commits = source_commits(self);
+        let old_commits = source_commits(old);
+        for source in &mut self.sources {
+            if let Some(previous) = old.get_source(&source.name)
+                && previous.url == source.url
+                && new_commits.get(&source.name) == old_commits.get(&source.name)
+            {
+                source.fetched_at.clone_from(&previous.fetched_at);
+            }
+        }
+    }
+
+    /// Record replacement URLs for sources fetched through `[replace]`.
+    ///
+    /// Rewrites the URL of each replaced source and of every resource locked
+    /// from it, so the lockfile names the repository its commits came from.
+    /// Used by `--commit-replace`; without it the declared URLs are kept.
+    pub fn commit_source_replacements(&mut self, replacements: &HashMap<String, String>) {
+        for source in &mut self.sources {
+            if let Some(url) = replacements.get(&source.name) {
+                source.url.clone_from(url);
+            }
+        }
+        for resource_type in ResourceType::all() {
+            for resource in self.get_resources_mut(resource_type) {
+                if let Some(url) = resource.source.as_ref().and_then(|name| replacements.get(name))
+                {
+                    resource.url = Some(url.clone());
+                }
+            }
+        }
+    }
+
     /// Find source repository by name.
     ///
     /// # Arguments
@@ -249,6 +340,12 @@ impl LockFile {
         self.get_resource(name).is_some()
     }
 
+    /// Check if the manifest dependency `name` is recorded as disabled.
+    #[must_use]
+    pub fn is_disabled(&self, name: &str, resource_type: ResourceType) -> bool {
+        self.disabled.iter().any(|entry| entry.name == name && entry.resource_type == resource_type)
+    }
+
     /// Internal name-based lookup across all types.
     ///
     /// Returns first match. External callers should use `find_resource_by_id` for proper lookup.
@@ -524,4 +621,118 @@ impl LockFile {
     ) -> &[LockedResource] {
         self.get_resources(resource_type)
     }
+
+    /// Dependencies declared by locked resources that are not locked themselves.
+    ///
+    /// Returns `(dependent, dependency)` pairs. After `agpm install
+    /// --no-transitive` these are the transitive dependencies that were
+    /// discovered but not installed.
+    #[must_use]
+    pub fn missing_dependencies(&self) -> Vec<(&LockedResource, LockfileDependencyRef)> {
+        let locked: HashSet<(ResourceType, &str)> = self
+            .all_resources()
+            .into_iter()
+            .map(|resource| (resource.resource_type, resource.name.as_str()))
+            .collect();
+
+        let mut missing = Vec::new();
+        for resource in self.all_resources() {
+            for dependency in resource.parsed_dependencies() {
+                if !locked.contains(&(dependency.resource_type, dependency.path.as_str())) {
+                    missing.push((resource, dependency));
+                }
+            }
+        }
+        missing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lockfile::LockedResourceBuilder;
+
+    fn agent(name: &str, source: &str, commit: &str) -> LockedResource {
+        let mut resource = LockedResourceBuilder::new(
+            name.to_string(),
+            format!("agents/{name}.md"),
+            String::new(),
+            format!(".claude/agents/{name}.md"),
+            ResourceType::Agent,
+        )
+        .build();
+        resource.source = Some(source.to_string());
+        resource.resolved_commit = Some(commit.to_string());
+        resource
+    }
+
+    #[test]
+    fn test_record_source_commits_only_for_shared_commit() {
+        let mut lockfile = LockFile::new();
+        for source in ["pinned", "mixed", "unused"] {
+            lockfile.add_source(
+                source.to_string(),
+                format!("https://github.com/org/{source}.git"),
+                String::new(),
+            );
+        }
+        lockfile.agents.push(agent("a", "pinned", "abc123"));
+        lockfile.agents.push(agent("b", "pinned", "abc123"));
+        lockfile.agents.push(agent("c", "mixed", "abc123"));
+        lockfile.agents.push(agent("d", "mixed", "def456"));
+
+        lockfile.record_source_commits();
+
+        let commit = |name: &str| lockfile.get_source(name).unwrap().resolved_commit.as_deref();
+        assert_eq!(commit("pinned"), Some("abc123"));
+        assert_eq!(commit("mixed"), None);
+        assert_eq!(commit("unused"), None);
+    }
+
+    #[test]
+    fn test_carry_over_fetched_at_only_for_unchanged_commits() {
+        let mut old = LockFile::new();
+        for source in ["same", "moved"] {
+            old.add_source(
+                source.to_string(),
+                format!("https://github.com/org/{source}.git"),
+                String::new(),
+            );
+        }
+        for source in &mut old.sources {
+            source.fetched_at = "2024-01-01T00:00:00Z".to_string();
+        }
+        old.agents.push(agent("a", "same", "abc123"));
+        old.agents.push(agent("b", "moved", "abc123"));
+
+        let mut new = old.clone();
+        for source in &mut new.sources {
+            source.fetched_at = "2024-06-01T00:00:00Z".to_string();
+        }
+        new.agents[1].resolved_commit = Some("def456".to_string());
+
+        new.carry_over_fetched_at(&old);
+
+        let fetched_at = |name: &str| new.get_source(name).unwrap().fetched_at.as_str();
+        assert_eq!(fetched_at("same"), "2024-01-01T00:00:00Z");
+        assert_eq!(fetched_at("moved"), "2024-06-01T00:00:00Z");
+    }
+
+    #[test]
+    fn test_missing_dependencies_lists_unlocked_references() {
+        let mut lockfile = LockFile::new();
+        let mut reviewer = agent("agents/reviewer", "community", "abc123");
+        reviewer.dependencies = vec![
+            "agent:agents/helper".to_string(),
+            "community/snippet:snippets/utils@v1.0.0".to_string(),
+        ];
+        lockfile.agents.push(reviewer);
+        lockfile.agents.push(agent("agents/helper", "community", "abc123"));
+
+        let missing = lockfile.missing_dependencies();
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].0.name, "agents/reviewer");
+        assert_eq!(missing[0].1.resource_type, ResourceType::Snippet);
+        assert_eq!(missing[0].1.path, "snippets/utils");
+    }
 }
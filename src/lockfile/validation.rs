@@ -7,7 +7,7 @@ use anyhow::Result;
 use std::collections::HashMap;
 use std::path::Path;
 
-use super::{LockFile, StalenessReason};
+use super::{LockFile, LockedResource, StalenessReason};
 
 impl LockFile {
     /// Validate lockfile against manifest for staleness detection.
@@ -80,8 +80,12 @@ impl LockFile {
 
         // Check source URL changes (security concern - different repository)
         for (source_name, manifest_url) in &manifest.sources {
+            // A lockfile written with --commit-replace records the [replace] URL
             if let Some(locked_source) = self.get_source(source_name)
                 && &locked_source.url != manifest_url
+                && !manifest
+                    .replacement_urls()
+                    .is_ok_and(|urls| urls.get(source_name) == Some(&locked_source.url))
             {
                 return Ok(Some(StalenessReason::SourceUrlChanged {
                     name: source_name.clone(),
@@ -96,6 +100,19 @@ impl LockFile {
             for resource_type in crate::core::ResourceType::all() {
                 if let Some(manifest_deps) = manifest.get_dependencies(*resource_type) {
                     for (name, dep) in manifest_deps {
+                        let locked_disabled = self.is_disabled(name, *resource_type);
+                        if dep.is_enabled() == locked_disabled {
+                            return Ok(Some(StalenessReason::EnabledChanged {
+                                name: name.clone(),
+                                resource_type: *resource_type,
+                                enabled: dep.is_enabled(),
+                            }));
+                        }
+                        if !dep.is_enabled() {
+                            // Disabled dependencies have no lockfile entry
+                            continue;
+                        }
+
                         // Find matching resource in lockfile
                         let locked_resource = self.get_resource(name);
 
@@ -131,6 +148,13 @@ impl LockFile {
                                 }));
                             }
 
+                            if dep.get_inline() != locked.inline.as_deref() {
+                                return Ok(Some(StalenessReason::InlineContentChanged {
+                                    name: name.clone(),
+                                    resource_type: *resource_type,
+                                }));
+                            }
+
                             // Check for tool changes (apply defaults if not specified)
                             let manifest_tool_string = dep
                                 .get_tool()
@@ -156,6 +180,132 @@ impl LockFile {
         Ok(None)
     }
 
+    /// Compare this lockfile against a freshly resolved one.
+    ///
+    /// Used by `agpm install --frozen`, which must never change `agpm.lock`: any
+    /// difference between the committed lockfile and what resolution produces is
+    /// reported as a [`StalenessReason`]. Entries are matched by resource type,
+    /// display name, and source. Checksums and applied patches are ignored since
+    /// they are only computed during installation.
+    ///
+    /// # Returns
+    ///
+    /// * `None` - Resolution reproduces this lockfile exactly
+    /// * `Some(StalenessReason)` - The first difference found
+    pub fn diff_against_resolved(&self, resolved: &LockFile) -> Option<StalenessReason> {
+        fn find<'a>(
+            entries: &'a [LockedResource],
+            entry: &LockedResource,
+        ) -> Option<&'a LockedResource> {
+            entries
+                .iter()
+                .find(|e| e.display_name() == entry.display_name() && e.source == entry.source)
+        }
+
+        if let Some(entry) = resolved.disabled.iter().find(|entry| !self.disabled.contains(entry)) {
+            return Some(StalenessReason::EnabledChanged {
+                name: entry.name.clone(),
+                resource_type: entry.resource_type,
+                enabled: false,
+            });
+        }
+        if let Some(entry) = self.disabled.iter().find(|entry| !resolved.disabled.contains(entry)) {
+            return Some(StalenessReason::EnabledChanged {
+                name: entry.name.clone(),
+                resource_type: entry.resource_type,
+                enabled: true,
+            });
+        }
+
+        for resource_type in crate::core::ResourceType::all() {
+            let locked_entries = self.get_resources(resource_type);
+            let resolved_entries = resolved.get_resources(resource_type);
+
+            for new in resolved_entries {
+                let name = new.display_name().to_string();
+                let resource_type = *resource_type;
+
+                let Some(old) = find(locked_entries, new) else {
+                    return Some(if new.is_direct_manifest() {
+                        StalenessReason::MissingDependency {
+                            name,
+                            resource_type,
+                        }
+                    } else {
+                        StalenessReason::UnlockedDependency {
+                            name,
+                            resource_type,
+                        }
+                    });
+                };
+
+                if old.version != new.version {
+                    return Some(StalenessReason::VersionChanged {
+                        name,
+                        resource_type,
+                        old_version: old.version.clone().unwrap_or_else(|| "none".to_string()),
+                        new_version: new.version.clone().unwrap_or_else(|| "none".to_string()),
+                    });
+                }
+                if old.path != new.path {
+                    return Some(StalenessReason::PathChanged {
+                        name,
+                        resource_type,
+                        old_path: old.path.clone(),
+                        new_path: new.path.clone(),
+                    });
+                }
+                if old.tool != new.tool {
+                    return Some(StalenessReason::ToolChanged {
+                        name,
+                        resource_type,
+                        old_tool: old.tool.clone().unwrap_or_else(|| "none".to_string()),
+                        new_tool: new.tool.clone().unwrap_or_else(|| "none".to_string()),
+                    });
+                }
+                if old.resolved_commit != new.resolved_commit {
+                    return Some(StalenessReason::ResolvedCommitChanged {
+                        name,
+                        resource_type,
+                        old_commit: old
+                            .resolved_commit
+                            .clone()
+                            .unwrap_or_else(|| "none".to_string()),
+                        new_commit: new
+                            .resolved_commit
+                            .clone()
+                            .unwrap_or_else(|| "none".to_string()),
+                    });
+                }
+                if old.installed_at != new.installed_at {
+                    return Some(StalenessReason::InstalledAtChanged {
+                        name,
+                        resource_type,
+                        old_path: old.installed_at.clone(),
+                        new_path: new.installed_at.clone(),
+                    });
+                }
+                if old.inline != new.inline {
+                    return Some(StalenessReason::InlineContentChanged {
+                        name,
+                        resource_type,
+                    });
+                }
+            }
+
+            for old in locked_entries {
+                if find(resolved_entries, old).is_none() {
+                    return Some(StalenessReason::ExtraDependency {
+                        name: old.display_name().to_string(),
+                        resource_type: *resource_type,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
     /// Check if lockfile is stale (boolean convenience method).
     ///
     /// Returns simple bool instead of detailed `StalenessReason`.
@@ -193,7 +343,9 @@ impl LockFile {
 
     /// Detect duplicate entries indicating corruption.
     ///
-    /// Scans all resource types for duplicate names.
+    /// Scans all resource types for entries with the same name from the same
+    /// source. The same name from different sources is legitimate, e.g. two
+    /// namespaced dependencies.
     pub(crate) fn detect_duplicate_entries(&self) -> Result<Option<StalenessReason>> {
         // Check each resource type for duplicates
         for resource_type in crate::core::ResourceType::all() {
@@ -201,20 +353,75 @@ impl LockFile {
             let mut seen_names = HashMap::new();
 
             for resource in resources {
-                if seen_names.contains_key(&resource.name) {
+                // Variants of one dependency share a name but differ in variant inputs
+                // or install path
+                let key = resource.id();
+                if seen_names.contains_key(&key) {
                     return Ok(Some(StalenessReason::DuplicateEntries {
                         name: resource.name.clone(),
                         resource_type: *resource_type,
-                        count: resources.iter().filter(|r| r.name == resource.name).count(),
+                        count: resources.iter().filter(|r| r.matches_id(&key)).count(),
                     }));
                 }
-                seen_names.insert(&resource.name, 0);
+                seen_names.insert(key, 0);
             }
         }
 
         Ok(None)
     }
 
+    /// Drop duplicate entries, keeping the one that matches `resolved`.
+    ///
+    /// Entries are duplicates when they share a [`ResourceId`](super::ResourceId),
+    /// which a bad merge of `agpm.lock` can produce. For each group, the entry
+    /// whose path, version and commit agree with the entry of the same id in
+    /// `resolved` (the lockfile resolved from the current manifest) is kept; if
+    /// none agrees, the first one is. Used by `agpm install --force`.
+    ///
+    /// # Returns
+    ///
+    /// The dropped entries.
+    pub fn remove_duplicate_entries(&mut self, resolved: &LockFile) -> Vec<LockedResource> {
+        let mut dropped = Vec::new();
+
+        for resource_type in crate::core::ResourceType::all() {
+            let resolved_entries = resolved.get_resources(resource_type);
+            let entries = std::mem::take(self.get_resources_mut(resource_type));
+
+            let mut groups: Vec<Vec<LockedResource>> = Vec::new();
+            let mut index_by_id = HashMap::new();
+            for entry in entries {
+                match index_by_id.get(&entry.id()) {
+                    Some(&index) => groups[index].push(entry),
+                    None => {
+                        index_by_id.insert(entry.id(), groups.len());
+                        groups.push(vec![entry]);
+                    }
+                }
+            }
+
+            let kept = self.get_resources_mut(resource_type);
+            for mut group in groups {
+                let id = group[0].id();
+                let keep = resolved_entries
+                    .iter()
+                    .find(|r| r.matches_id(&id))
+                    .and_then(|r| {
+                        group.iter().position(|entry| {
+                            entry.path == r.path
+                                && entry.version == r.version
+                                && entry.resolved_commit == r.resolved_commit
+                        })
+                    })
+                    .unwrap_or(0);
+                kept.push(group.remove(keep));
+                dropped.extend(group);
+            }
+        }
+
+        dropped
+    }
+
     /// Validate no duplicate names within each resource type.
     ///
     /// Stricter than `detect_duplicate_entries`. Used during loading to catch
@@ -277,3 +484,183 @@ impl LockFile {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ResourceType;
+
+    fn agent(name: &str, commit: &str) -> LockedResource {
+        LockedResource {
+            name: name.to_string(),
+            source: Some("community".to_string()),
+            url: Some("https://github.com/example/agents.git".to_string()),
+            path: format!("agents/{name}.md"),
+            version: Some("v1.0.0".to_string()),
+            resolved_commit: Some(commit.to_string()),
+            checksum: "sha256:abc".to_string(),
+            installed_at: format!(".claude/agents/{name}.md"),
+            dependencies: vec![],
+            resource_type: ResourceType::Agent,
+            tool: Some("claude-code".to_string()),
+            manifest_alias: Some(name.to_string()),
+            context_checksum: None,
+            applied_patches: std::collections::BTreeMap::new(),
+            install: None,
+            variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
+            inline: None,
+        }
+    }
+
+    fn lockfile(agents: Vec<LockedResource>) -> LockFile {
+        let mut lockfile = LockFile::new();
+        lockfile.agents = agents;
+        lockfile
+    }
+
+    #[test]
+    fn test_remove_duplicate_entries_keeps_resolved_entry() {
+        let mut locked =
+            lockfile(vec![agent("reviewer", "aaaaaaaaaaaa"), agent("reviewer", "bbbbbbbbbbbb")]);
+        locked.agents.push(agent("helper", "cccccccccccc"));
+        assert!(matches!(
+            locked.detect_duplicate_entries().unwrap(),
+            Some(StalenessReason::DuplicateEntries {
+                count: 2,
+                ..
+            })
+        ));
+
+        let resolved =
+            lockfile(vec![agent("reviewer", "bbbbbbbbbbbb"), agent("helper", "cccccccccccc")]);
+        let dropped = locked.remove_duplicate_entries(&resolved);
+
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(dropped[0].resolved_commit.as_deref(), Some("aaaaaaaaaaaa"));
+        assert_eq!(locked.agents.len(), 2);
+        assert_eq!(locked.agents[0].resolved_commit.as_deref(), Some("bbbbbbbbbbbb"));
+        assert_eq!(locked.detect_duplicate_entries().unwrap(), None);
+    }
+
+    #[test]
+    fn test_remove_duplicate_entries_without_match_keeps_first() {
+        let mut locked =
+            lockfile(vec![agent("reviewer", "aaaaaaaaaaaa"), agent("reviewer", "bbbbbbbbbbbb")]);
+        // A duplicate of a different source is a different resource
+        let mut other = agent("reviewer", "dddddddddddd");
+        other.source = Some("other".to_string());
+        locked.agents.push(other);
+
+        let dropped = locked.remove_duplicate_entries(&LockFile::new());
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(locked.agents.len(), 2);
+        assert_eq!(locked.agents[0].resolved_commit.as_deref(), Some("aaaaaaaaaaaa"));
+    }
+
+    #[test]
+    fn test_diff_against_resolved_identical() {
+        let locked = lockfile(vec![agent("reviewer", "aaaaaaaaaaaa")]);
+        let mut resolved = locked.clone();
+        // Checksums are only known after installation
+        resolved.agents[0].checksum = String::new();
+
+        assert_eq!(locked.diff_against_resolved(&resolved), None);
+    }
+
+    #[test]
+    fn test_diff_against_resolved_reports_changes() {
+        let locked = lockfile(vec![agent("reviewer", "aaaaaaaaaaaa")]);
+
+        let resolved = lockfile(vec![agent("reviewer", "bbbbbbbbbbbb")]);
+        let reason = locked.diff_against_resolved(&resolved).unwrap();
+        assert!(matches!(reason, StalenessReason::ResolvedCommitChanged { .. }));
+        assert!(
+            reason.to_string().contains("resolves to commit bbbbbbbb but lockfile has aaaaaaaa")
+        );
+
+        let mut resolved = locked.clone();
+        resolved.agents[0].version = Some("v2.0.0".to_string());
+        assert!(matches!(
+            locked.diff_against_resolved(&resolved),
+            Some(StalenessReason::VersionChanged { .. })
+        ));
+    }
+
+    #[test]
+    fn test_diff_against_resolved_added_and_removed_entries() {
+        let locked = lockfile(vec![agent("reviewer", "aaaaaaaaaaaa")]);
+
+        let mut helper = agent("helper", "cccccccccccc");
+        helper.manifest_alias = None;
+        let resolved = lockfile(vec![agent("reviewer", "aaaaaaaaaaaa"), helper]);
+        assert_eq!(
+            locked.diff_against_resolved(&resolved),
+            Some(StalenessReason::UnlockedDependency {
+                name: "helper".to_string(),
+                resource_type: ResourceType::Agent,
+            })
+        );
+
+        let resolved = lockfile(vec![agent("reviewer", "aaaaaaaaaaaa"), agent("linter", "dddd")]);
+        assert!(matches!(
+            locked.diff_against_resolved(&resolved),
+            Some(StalenessReason::MissingDependency { .. })
+        ));
+
+        assert_eq!(
+            resolved.diff_against_resolved(&locked),
+            Some(StalenessReason::ExtraDependency {
+                name: "linter".to_string(),
+                resource_type: ResourceType::Agent,
+            })
+        );
+    }
+
+    #[test]
+    fn test_disabled_dependency_staleness() {
+        let manifest: crate::manifest::Manifest = toml::from_str(
+            r#"
+[sources]
+community = "https://github.com/example/agents.git"
+
+[agents]
+reviewer = { source = "community", path = "agents/reviewer.md", version = "v1.0.0", enabled = false }
+"#,
+        )
+        .unwrap();
+        let disabled = crate::lockfile::DisabledResource {
+            name: "reviewer".to_string(),
+            resource_type: ResourceType::Agent,
+        };
+
+        // Disabling an installed dependency makes the lockfile stale
+        let locked = lockfile(vec![agent("reviewer", "aaaaaaaaaaaa")]);
+        assert_eq!(
+            locked.validate_against_manifest(&manifest, true).unwrap(),
+            Some(StalenessReason::EnabledChanged {
+                name: "reviewer".to_string(),
+                resource_type: ResourceType::Agent,
+                enabled: false,
+            })
+        );
+
+        let mut resolved = LockFile::new();
+        resolved.disabled.push(disabled);
+        assert_eq!(resolved.validate_against_manifest(&manifest, true).unwrap(), None);
+        assert!(matches!(
+            locked.diff_against_resolved(&resolved),
+            Some(StalenessReason::EnabledChanged {
+                enabled: false,
+                ..
+            })
+        ));
+        assert!(matches!(
+            resolved.diff_against_resolved(&locked),
+            Some(StalenessReason::EnabledChanged {
+                enabled: true,
+                ..
+            })
+        ));
+    }
+}
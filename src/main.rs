@@ -14,7 +14,7 @@
 //! - `add` - Add sources or dependencies to manifest
 //! - `remove` - Remove sources or dependencies from manifest
 
-use agpm_cli::cli;
+use agpm_cli::cli::{self, LogFormat};
 use agpm_cli::core::error_formatting::user_friendly_error;
 use anyhow::Result;
 use clap::Parser;
@@ -49,23 +49,39 @@ async fn main() -> Result<()> {
         EnvFilter::new("off")
     };
 
-    tracing_subscriber::fmt()
-        .with_env_filter(filter)
-        .with_target(false) // Don't show the module path in logs
-        .with_thread_ids(false) // Don't show thread IDs
-        .init();
+    match config.log_format {
+        LogFormat::Text => tracing_subscriber::fmt()
+            .with_env_filter(filter)
+            .with_target(false) // Don't show the module path in logs
+            .with_thread_ids(false) // Don't show thread IDs
+            .init(),
+        // Structured logs for machine consumption: one JSON object per line on
+        // stderr, keeping the target and span context that text mode hides
+        LogFormat::Json => tracing_subscriber::fmt()
+            .json()
+            .with_env_filter(filter)
+            .with_writer(std::io::stderr)
+            .with_current_span(true)
+            .with_span_list(true)
+            .init(),
+    }
 
     // Set up colored output for Windows
     #[cfg(windows)]
     colored::control::set_virtual_terminal(true).ok();
 
     // Execute the command (execute_with_config will apply the rest of the config)
+    let json_errors = config.json_errors;
     match cli.execute_with_config(config).await {
         Ok(()) => Ok(()),
         Err(e) => {
             // Convert to user-friendly error with context and suggestions
             let error_ctx = user_friendly_error(e);
-            error_ctx.display();
+            if json_errors {
+                eprintln!("{}", error_ctx.to_json());
+            } else {
+                error_ctx.display();
+            }
             std::process::exit(1);
         }
     }
@@ -10,13 +10,17 @@ use std::path::PathBuf;
 /// Expand local paths to file:// URLs, preserving standard Git URLs.
 ///
 /// Converts local paths to file:// URLs while leaving http://, https://,
-/// git@, and file:// URLs unchanged. Returns original string on expansion failure.
+/// git@, file:// and dir:// URLs unchanged. Returns original string on expansion failure.
 pub fn expand_url(url: &str) -> Result<String> {
-    // If it looks like a standard protocol URL (http, https, git@, file://), don't expand
+    // If it looks like a standard protocol URL (http, https, git://, ssh://, git@, file://), don't expand
     if url.starts_with("http://")
         || url.starts_with("https://")
+        || url.starts_with("git://")
+        || url.starts_with("ssh://")
         || url.starts_with("git@")
         || url.starts_with("file://")
+        || url.starts_with(super::DIRECTORY_SOURCE_SCHEME)
+        || crate::source::provider::for_url(url).is_some()
     {
         return Ok(url.to_string());
     }
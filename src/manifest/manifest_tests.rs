@@ -47,6 +47,17 @@ mod tests {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             })),
             true,
         );
@@ -60,6 +71,134 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_min_agpm_version_checked_before_parsing() -> Result<()> {
+        let temp = tempdir()?;
+        let manifest_path = temp.path().join("agpm.toml");
+
+        // Content this version cannot deserialize fails on its own...
+        std::fs::write(&manifest_path, "agents = 5\n")?;
+        let err = format!("{:#}", Manifest::load(&manifest_path).unwrap_err());
+        assert!(!err.contains("requires agpm"), "unexpected error: {err}");
+
+        // ...but a newer manifest reports the required version instead
+        std::fs::write(&manifest_path, "min_agpm_version = \"999.0.0\"\nagents = 5\n")?;
+        let err = format!("{:#}", Manifest::load(&manifest_path).unwrap_err());
+        assert!(err.contains("requires agpm >= 999.0.0"), "unexpected error: {err}");
+        assert!(err.contains(env!("CARGO_PKG_VERSION")));
+
+        std::fs::write(&manifest_path, "min_agpm_version = \"v0.1.0\"\n")?;
+        let manifest = Manifest::load(&manifest_path)?;
+        assert_eq!(manifest.min_agpm_version.as_deref(), Some("v0.1.0"));
+
+        std::fs::write(&manifest_path, "min_agpm_version = \"soon\"\n")?;
+        assert!(Manifest::load(&manifest_path).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_strip_metadata_option() -> Result<()> {
+        let temp = tempdir()?;
+        let manifest_path = temp.path().join("agpm.toml");
+
+        std::fs::write(
+            &manifest_path,
+            r#"strip_metadata = true
+
+[agents]
+plain = "agents/plain.md"
+keep = { path = "agents/keep.md", strip_metadata = false }
+"#,
+        )?;
+        let manifest = Manifest::load(&manifest_path)?;
+        assert!(manifest.strip_metadata);
+        assert_eq!(manifest.agents["plain"].get_strip_metadata(), None);
+        assert_eq!(manifest.agents["keep"].get_strip_metadata(), Some(false));
+
+        // The default is not written back out
+        assert!(!toml::to_string(&Manifest::new())?.contains("strip_metadata"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_inline_dependency() -> Result<()> {
+        let temp = tempdir()?;
+        let manifest_path = temp.path().join("agpm.toml");
+
+        std::fs::write(
+            &manifest_path,
+            r#"[snippets]
+greeting = { inline = "Hello from {{ agpm.project.name }}" }
+plain = "snippets/plain.md"
+"#,
+        )?;
+        let manifest = Manifest::load(&manifest_path)?;
+        let greeting = &manifest.snippets["greeting"];
+        assert_eq!(greeting.get_inline(), Some("Hello from {{ agpm.project.name }}"));
+        assert_eq!(greeting.get_path(), "");
+        assert!(greeting.is_local());
+        assert_eq!(manifest.snippets["plain"].get_inline(), None);
+
+        // The empty path is not written back out
+        let serialized = toml::to_string(&manifest)?;
+        assert!(serialized.contains("inline = "));
+        assert!(!serialized.contains("path = \"\""));
+
+        for (section, entry) in [
+            ("snippets", r#"{ inline = "x", path = "snippets/x.md" }"#),
+            ("snippets", r#"{ inline = "x", source = "community" }"#),
+            ("hooks", r#"{ inline = "{}" }"#),
+        ] {
+            std::fs::write(
+                &manifest_path,
+                format!(
+                    "[sources]\ncommunity = \"https://example.com/repo.git\"\n\n[{section}]\nbad = {entry}\n"
+                ),
+            )?;
+            let err = format!("{:#}", Manifest::load(&manifest_path).unwrap_err());
+            assert!(err.contains("'inline'"), "{section} {entry}: {err}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_select_profile() -> Result<()> {
+        let temp = tempdir()?;
+        let manifest_path = temp.path().join("agpm.toml");
+        std::fs::write(
+            &manifest_path,
+            r#"
+[profiles]
+prod = ["reviewer", "deploy"]
+dev = ["reviewer", "debugger"]
+
+[agents]
+reviewer = "agents/reviewer.md"
+debugger = "agents/debugger.md"
+
+[commands]
+deploy = "commands/deploy.md"
+"#,
+        )?;
+
+        let mut manifest = Manifest::load(&manifest_path)?;
+        manifest.select_profile("prod")?;
+        assert!(manifest.agents.contains_key("reviewer"));
+        assert!(!manifest.agents.contains_key("debugger"));
+        assert!(manifest.commands.contains_key("deploy"));
+
+        let err = Manifest::load(&manifest_path)?.select_profile("staging").unwrap_err();
+        assert!(err.to_string().contains("available profiles: dev, prod"), "{err}");
+
+        std::fs::write(
+            &manifest_path,
+            "[profiles]\nprod = [\"missing\"]\n\n[agents]\nreviewer = \"agents/reviewer.md\"\n",
+        )?;
+        let err = Manifest::load(&manifest_path).unwrap_err();
+        assert!(format!("{err:#}").contains("unknown dependency 'missing'"), "{err:#}");
+        Ok(())
+    }
+
     #[test]
     fn test_manifest_validation() -> Result<()> {
         let mut manifest = Manifest::new();
@@ -91,6 +230,17 @@ mod tests {
                 install: None,
 
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             })),
             true,
         );
@@ -126,6 +276,17 @@ mod tests {
             install: None,
 
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         }));
         assert!(!detailed_dep.is_local());
         assert_eq!(detailed_dep.get_path(), "agents/test.md");
@@ -529,6 +690,17 @@ agents = "opencode"
             flatten: None,
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         }));
         assert!(detailed_dep.is_pattern());
     }
@@ -550,6 +722,17 @@ agents = "opencode"
             flatten: None, // Not specified
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         }));
         // When not specified, get_flatten returns None
         assert_eq!(dep_with_default.get_flatten(), None);
@@ -572,6 +755,17 @@ agents = "opencode"
             flatten: Some(true),
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         }));
         assert_eq!(dep_flatten_true.get_flatten(), Some(true));
 
@@ -590,6 +784,17 @@ agents = "opencode"
             flatten: Some(false),
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         }));
         assert_eq!(dep_flatten_false.get_flatten(), Some(false));
     }
@@ -611,6 +816,17 @@ agents = "opencode"
             flatten: None,
             install: None, // Not specified - defaults to true
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         }));
         assert_eq!(dep.get_install(), None); // Returns None when not specified
     }
@@ -632,6 +848,17 @@ agents = "opencode"
             flatten: None,
             install: Some(false), // Explicitly disabled
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         }));
         assert_eq!(dep_install_false.get_install(), Some(false));
 
@@ -650,6 +877,17 @@ agents = "opencode"
             flatten: None,
             install: Some(true), // Explicitly enabled
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         }));
         assert_eq!(dep_install_true.get_install(), Some(true));
     }
@@ -671,6 +909,17 @@ agents = "opencode"
             flatten: None,
             install: None,
             template_vars: None,
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         }));
         assert_eq!(dep_no_vars.get_template_vars(), None);
 
@@ -695,6 +944,17 @@ agents = "opencode"
             flatten: None,
             install: None,
             template_vars: Some(vars.clone()),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         }));
         assert_eq!(dep_with_vars.get_template_vars(), Some(&vars));
     }
@@ -866,6 +1126,17 @@ mod flatten_tests {
             flatten: Some(false), // Override default
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         }));
 
         assert_eq!(dep.get_flatten(), Some(false));
@@ -889,6 +1160,17 @@ mod flatten_tests {
             flatten: Some(true), // Override default
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         }));
 
         assert_eq!(dep.get_flatten(), Some(true));
@@ -982,6 +1264,17 @@ model = "claude-3-haiku"
                 flatten: None,
                 install: None,
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             })),
             true,
         );
@@ -994,6 +1287,154 @@ model = "claude-3-haiku"
         Ok(())
     }
 
+    #[test]
+    fn test_validate_mcp_transport_overrides() -> Result<()> {
+        let temp = tempdir()?;
+        let manifest_path = temp.path().join("agpm.toml");
+
+        let toml_content = r#"
+[sources]
+official = "https://github.com/example/mcp.git"
+
+[mcp-servers]
+fs = { source = "official", path = "mcp/fs.json", version = "v1", transport = "http", url = "http://localhost:3000" }
+"#;
+        std::fs::write(&manifest_path, toml_content)?;
+        let manifest = Manifest::load(&manifest_path)?;
+        let dep = manifest.mcp_servers.get("fs").unwrap();
+        assert_eq!(dep.get_transport(), Some("http"));
+        assert_eq!(dep.get_url(), Some("http://localhost:3000"));
+
+        // stdio with a url is inconsistent
+        std::fs::write(
+            &manifest_path,
+            toml_content.replace("transport = \"http\"", "transport = \"stdio\""),
+        )?;
+        let err = Manifest::load(&manifest_path).unwrap_err();
+        assert!(format!("{err:#}").contains("cannot be used with transport \"stdio\""));
+
+        // Unknown transports are rejected
+        std::fs::write(
+            &manifest_path,
+            toml_content.replace("transport = \"http\"", "transport = \"grpc\""),
+        )?;
+        let err = Manifest::load(&manifest_path).unwrap_err();
+        assert!(format!("{err:#}").contains("unknown transport 'grpc'"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_transport_only_for_mcp_servers() -> Result<()> {
+        let temp = tempdir()?;
+        let manifest_path = temp.path().join("agpm.toml");
+
+        std::fs::write(
+            &manifest_path,
+            r#"
+[agents]
+helper = { path = "./agents/helper.md", transport = "http" }
+"#,
+        )?;
+        let err = Manifest::load(&manifest_path).unwrap_err();
+        assert!(format!("{err:#}").contains("only supported for MCP server dependencies"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_as_of() -> Result<()> {
+        let temp = tempdir()?;
+        let manifest_path = temp.path().join("agpm.toml");
+
+        let toml_content = r#"
+[sources]
+community = "https://github.com/example/community.git"
+
+[agents]
+snapshot = { source = "community", path = "agents/x.md", branch = "main", as_of = "2024-06-01" }
+"#;
+        std::fs::write(&manifest_path, toml_content)?;
+        let manifest = Manifest::load(&manifest_path)?;
+        assert_eq!(manifest.agents.get("snapshot").unwrap().get_as_of(), Some("2024-06-01"));
+
+        for (from, to, expected) in [
+            (r#"branch = "main""#, r#"rev = "abc1234""#, "not 'version' or 'rev'"),
+            (r#"as_of = "2024-06-01""#, r#"as_of = "last week""#, "must be a date"),
+        ] {
+            std::fs::write(&manifest_path, toml_content.replace(from, to))?;
+            let err = Manifest::load(&manifest_path).unwrap_err();
+            assert!(format!("{err:#}").contains(expected), "{err:#}");
+        }
+
+        // Dependencies on the same branch must agree on the date
+        std::fs::write(
+            &manifest_path,
+            format!(
+                "{toml_content}other = {{ source = \"community\", path = \"agents/y.md\", branch = \"main\" }}\n"
+            ),
+        )?;
+        let err = Manifest::load(&manifest_path).unwrap_err();
+        assert!(format!("{err:#}").contains("different 'as_of' dates"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_expect_commit() -> Result<()> {
+        let temp = tempdir()?;
+        let manifest_path = temp.path().join("agpm.toml");
+
+        let toml_content = r#"
+[sources]
+community = "https://github.com/example/community.git"
+
+[agents]
+pinned = { source = "community", path = "agents/x.md", version = "v1.0.0", expect_commit = "abc1234" }
+"#;
+        std::fs::write(&manifest_path, toml_content)?;
+        let manifest = Manifest::load(&manifest_path)?;
+        assert_eq!(manifest.agents.get("pinned").unwrap().get_expect_commit(), Some("abc1234"));
+
+        for (from, to, expected) in [
+            (r#"expect_commit = "abc1234""#, r#"expect_commit = "abc""#, "7 to 40 hex"),
+            (r#"expect_commit = "abc1234""#, r#"expect_commit = "v1.0.0x""#, "7 to 40 hex"),
+            (r#"source = "community", "#, "", "requires a Git source"),
+        ] {
+            std::fs::write(&manifest_path, toml_content.replace(from, to))?;
+            let err = Manifest::load(&manifest_path).unwrap_err();
+            assert!(format!("{err:#}").contains(expected), "{err:#}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_namespaces() -> Result<()> {
+        let temp = tempdir()?;
+        let manifest_path = temp.path().join("agpm.toml");
+
+        let toml_content = r#"
+[sources]
+community = "https://github.com/example/community.git"
+vendor = { url = "https://github.com/vendor/agents.git", namespace = "acme" }
+
+[agents]
+"community/helper" = { source = "community", path = "agents/helper.md", version = "v1.0.0" }
+"acme/helper" = { source = "vendor", path = "agents/helper.md", version = "v1.0.0" }
+"#;
+        std::fs::write(&manifest_path, toml_content)?;
+        let manifest = Manifest::load(&manifest_path)?;
+        assert_eq!(manifest.sources.options("vendor").namespace.as_deref(), Some("acme"));
+
+        for (from, to, expected) in [
+            (r#""acme/helper""#, r#""other/helper""#, "not a valid namespaced name"),
+            (r#""acme/helper""#, r#""acme/nested/helper""#, "not a valid namespaced name"),
+            (r#"namespace = "acme""#, r#"namespace = "a/b""#, "invalid namespace"),
+        ] {
+            std::fs::write(&manifest_path, toml_content.replace(from, to))?;
+            let err = Manifest::load(&manifest_path).unwrap_err();
+            assert!(format!("{err:#}").contains(expected), "{err:#}");
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_validate_version_constraints() -> Result<()> {
         let mut manifest = Manifest::new();
@@ -1017,6 +1458,17 @@ model = "claude-3-haiku"
                 flatten: None,
                 install: None,
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             })),
             true,
         );
@@ -1041,6 +1493,17 @@ model = "claude-3-haiku"
                 flatten: None,
                 install: None,
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             })),
             true,
         );
@@ -1080,6 +1543,17 @@ mod template_vars_tests {
             flatten: None,
             install: None,
             template_vars: Some(vars.clone()),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         }));
 
         assert_eq!(dep.get_template_vars(), Some(&vars));
@@ -1137,6 +1611,17 @@ project = { language = "python", framework = "fastapi" }
             flatten: None,
             install: None,
             template_vars: None,
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         }));
 
         assert_eq!(dep.get_template_vars(), None);
@@ -1184,4 +1669,103 @@ templated = { source = "test-repo", path = "agents/templated-agent.md", version
         );
         Ok(())
     }
+
+    #[test]
+    fn test_variants_expand_to_one_dependency_each() -> Result<()> {
+        let temp = tempdir()?;
+        let manifest_path = temp.path().join("agpm.toml");
+        std::fs::write(
+            &manifest_path,
+            r#"
+[sources]
+official = "https://example.com/repo.git"
+
+[agents]
+code-reviewer = { source = "official", path = "agents/reviewer.md", version = "v1", template_vars = { style = "strict" }, variants = [
+    { filename = "reviewer-py.md", template_vars = { lang = "python" } },
+    { filename = "reviewer-rs.md", template_vars = { lang = "rust" } },
+] }
+"#,
+        )?;
+
+        let manifest = Manifest::load(&manifest_path)?;
+        let deps = manifest.all_dependencies_with_types();
+        assert_eq!(deps.len(), 2);
+        assert!(deps.iter().all(|(name, _, _)| *name == "code-reviewer"));
+        assert_eq!(deps[0].1.get_filename(), Some("reviewer-py.md"));
+        assert_eq!(
+            deps[1].1.get_template_vars(),
+            Some(&json!({ "style": "strict", "lang": "rust" }))
+        );
+
+        // Variants installing to the same file are rejected
+        std::fs::write(
+            &manifest_path,
+            r#"
+[sources]
+official = "https://example.com/repo.git"
+
+[agents]
+code-reviewer = { source = "official", path = "agents/reviewer.md", version = "v1", variants = [
+    { template_vars = { lang = "python" } },
+    { template_vars = { lang = "rust" } },
+] }
+"#,
+        )?;
+        let err = Manifest::load(&manifest_path).unwrap_err();
+        assert!(format!("{err:#}").contains("distinct 'filename'"), "{err:#}");
+        Ok(())
+    }
+
+    #[test]
+    fn test_inline_git_url_registers_source() -> Result<()> {
+        let temp = tempdir()?;
+        let manifest_path = temp.path().join("agpm.toml");
+        std::fs::write(
+            &manifest_path,
+            r#"
+[sources]
+official = "https://github.com/org/official.git"
+
+[agents]
+helper = { git = "https://github.com/org/repo.git", path = "agents/helper.md", version = "v1.0.0" }
+reviewer = { git = "https://github.com/org/official.git", path = "agents/reviewer.md" }
+"#,
+        )?;
+
+        let manifest = Manifest::load(&manifest_path)?;
+        assert_eq!(manifest.agents["helper"].get_source(), Some("org-repo"));
+        assert_eq!(manifest.sources["org-repo"], "https://github.com/org/repo.git");
+        // A [sources] entry with the same URL is reused
+        assert_eq!(manifest.agents["reviewer"].get_source(), Some("official"));
+        assert_eq!(manifest.sources.len(), 2);
+
+        // Saving writes the dependencies back as given
+        manifest.save(&manifest_path)?;
+        let saved = std::fs::read_to_string(&manifest_path)?;
+        assert!(!saved.contains("org-repo"), "{saved}");
+        assert_eq!(Manifest::load(&manifest_path)?.agents["helper"].get_source(), Some("org-repo"));
+
+        for (dep, expected) in [
+            (
+                r#"{ git = "https://github.com/org/repo.git", source = "official", path = "a.md" }"#,
+                "cannot combine 'git' with 'source'",
+            ),
+            (
+                r#"{ git = "https://ghp_token@github.com/org/repo.git", path = "a.md" }"#,
+                "contains credentials",
+            ),
+            (r#"{ git = "../repo", path = "a.md" }"#, "Invalid git URL"),
+        ] {
+            std::fs::write(
+                &manifest_path,
+                format!(
+                    "[sources]\nofficial = \"https://github.com/org/official.git\"\n\n[agents]\nhelper = {dep}\n"
+                ),
+            )?;
+            let err = Manifest::load(&manifest_path).unwrap_err();
+            assert!(format!("{err:#}").contains(expected), "{err:#}");
+        }
+        Ok(())
+    }
 }
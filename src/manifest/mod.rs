@@ -87,8 +87,14 @@
 //! # Environment variable expansion (useful for CI/CD)
 //! dynamic = "https://github.com/${GITHUB_ORG}/resources.git"
 //! home-repo = "file://${HOME}/git/resources"
+//!
+//! # Detailed form with per-source settings
+//! shared = { url = "https://github.com/org/shared.git", submodules = true }
 //! ```
 //!
+//! Setting `submodules = true` initializes the source's Git submodules in
+//! every checkout, for repositories that pull in shared content that way.
+//!
 //! ## Target Section
 //!
 //! The `[target]` section configures where resources are installed:
@@ -248,7 +254,7 @@
 //!
 //! - `"v1.0.0"` - Exact semantic version
 //! - `"1.0.0"` - Exact version (v prefix optional)
-//! - `"latest"` - Always use the latest available version
+//! - `"latest"` - The highest stable semver tag (`"latest-prerelease"` includes prereleases)
 //! - `"main"` - Use the main/master branch HEAD
 //! - `"develop"` - Use a specific branch
 //! - `"a1b2c3d4..."` - Use a specific commit SHA
@@ -478,8 +484,11 @@
 
 pub mod dependency_spec;
 pub mod helpers;
+pub mod overrides;
 pub mod patches;
+mod project_schema;
 pub mod resource_dependency;
+pub mod sources;
 pub mod tool_config;
 
 #[cfg(test)]
@@ -490,13 +499,18 @@ mod tool_config_tests;
 use crate::core::file_error::{FileOperation, FileResultExt};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 pub use dependency_spec::{DependencyMetadata, DependencySpec};
 pub use helpers::{expand_url, find_manifest, find_manifest_from, find_manifest_with_optional};
+pub use overrides::VersionOverrides;
 pub use patches::{ManifestPatches, PatchConflict, PatchData, PatchOrigin};
-pub use resource_dependency::{DetailedDependency, ResourceDependency};
+pub use resource_dependency::{DependencyVariant, DetailedDependency, ResourceDependency};
+pub use sources::{
+    DIRECTORY_SOURCE_SCHEME, SourceMap, SourceOptions, directory_source_path, replacement_url,
+    url_has_credentials, validate_source_url,
+};
 pub use tool_config::{ArtifactTypeConfig, ResourceConfig, ToolsConfig, WellKnownTool};
 
 /// The main manifest file structure representing a complete `agpm.toml` file.
@@ -695,6 +709,19 @@ pub(crate) fn json_value_to_toml(value: &serde_json::Value) -> toml::Value {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Manifest {
+    /// Oldest AGPM version able to install this manifest.
+    ///
+    /// Checked by [`Manifest::load`] against the running binary before the rest
+    /// of the manifest is parsed, so teammates on older versions are told to
+    /// upgrade instead of seeing errors about features their version lacks.
+    /// Versions of AGPM that predate this key may reject it as unknown.
+    ///
+    /// ```toml
+    /// min_agpm_version = "0.4.0"
+    /// ```
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_agpm_version: Option<String>,
+
     /// Named source repositories mapped to their Git URLs.
     ///
     /// Keys are short, convenient names used in dependency specifications.
@@ -710,9 +737,12 @@ pub struct Manifest {
     /// official = "https://github.com/claude-org/official.git"
     /// private = "git@github.com:company/private.git"
     /// local = "file:///home/user/local-repo"
+    /// shared = { url = "https://github.com/org/shared.git", submodules = true }
     /// ```
-    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
-    pub sources: HashMap<String, String>,
+    ///
+    /// See [`SourceMap`] for the detailed form and per-source settings.
+    #[serde(default, skip_serializing_if = "SourceMap::is_empty")]
+    pub sources: SourceMap,
 
     /// Tool type configurations for multi-tool support.
     ///
@@ -817,6 +847,35 @@ pub struct Manifest {
     #[serde(skip)]
     pub private_patches: ManifestPatches,
 
+    /// Version pins for transitive dependencies.
+    ///
+    /// Keyed by resource type and dependency path. See [`VersionOverrides`].
+    ///
+    /// # Examples
+    ///
+    /// ```toml
+    /// [override.snippets]
+    /// utils = "v1.1.0"
+    /// ```
+    #[serde(default, skip_serializing_if = "VersionOverrides::is_empty", rename = "override")]
+    pub version_overrides: VersionOverrides,
+
+    /// Alternate repositories for sources, keyed by source name.
+    ///
+    /// Each value is a Git URL or a path to a local Git repository, such as a
+    /// fork being developed. Dependencies on the source are fetched from the
+    /// replacement, but the lockfile keeps recording the source's declared URL
+    /// unless the replacement is committed with `--commit-replace`.
+    ///
+    /// # Examples
+    ///
+    /// ```toml
+    /// [replace]
+    /// community = "../agpm-community"
+    /// ```
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty", rename = "replace")]
+    pub replacements: BTreeMap<String, String>,
+
     /// Default tool overrides for resource types.
     ///
     /// Allows users to override which tool is used by default when a dependency
@@ -840,6 +899,23 @@ pub struct Manifest {
     #[serde(default, skip_serializing_if = "HashMap::is_empty", rename = "default-tools")]
     pub default_tools: HashMap<String, String>,
 
+    /// Named subsets of the dependencies, selected with `agpm install --profile`.
+    ///
+    /// Each profile lists the manifest names of the dependencies it installs,
+    /// from any resource section. A profile is resolved into its own lockfile,
+    /// `agpm.<profile>.lock` by default, so each environment is reproducible
+    /// on its own.
+    ///
+    /// # Examples
+    ///
+    /// ```toml
+    /// [profiles]
+    /// prod = ["reviewer", "deploy-checklist"]
+    /// dev = ["reviewer", "debugger", "test-runner"]
+    /// ```
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Vec<String>>,
+
     /// Project-specific template variables.
     ///
     /// Custom project configuration that can be referenced in resource templates
@@ -892,6 +968,37 @@ pub struct Manifest {
     #[serde(default = "Manifest::default_gitignore")]
     pub gitignore: bool,
 
+    /// Strip AGPM-managed frontmatter keys from every installed Markdown file.
+    ///
+    /// When `true`, the `dependencies` and `agpm` keys are removed from the YAML
+    /// frontmatter of installed files after templating; all other keys are kept.
+    /// Individual dependencies can override this with their own `strip_metadata`
+    /// option.
+    ///
+    /// # Examples
+    ///
+    /// ```toml
+    /// strip_metadata = true
+    /// ```
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub strip_metadata: bool,
+
+    /// Frontmatter keys read for transitive dependencies.
+    ///
+    /// `dependencies` is always read first. Additional keys let AGPM consume
+    /// files written for other tools, such as a `requires: [snippets/utils.md]`
+    /// list, without editing them. They are read in the order listed, and a
+    /// path already declared under an earlier key is ignored. Entries that
+    /// cannot be understood are skipped with a warning.
+    ///
+    /// # Examples
+    ///
+    /// ```toml
+    /// metadata_keys = ["dependencies", "requires"]
+    /// ```
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub metadata_keys: Vec<String>,
+
     /// Directory containing the manifest file (for resolving relative paths).
     ///
     /// This field is populated when loading the manifest and is used to resolve
@@ -901,6 +1008,13 @@ pub struct Manifest {
     /// This field is not serialized and only exists at runtime.
     #[serde(skip)]
     pub manifest_dir: Option<std::path::PathBuf>,
+
+    /// Sources registered for dependencies given with an inline `git` URL.
+    ///
+    /// These are added to [`Self::sources`] when loading and left out again
+    /// by [`Self::save`]. This field is not serialized.
+    #[serde(skip)]
+    pub git_sources: BTreeSet<String>,
 }
 
 /// A resource dependency specification supporting multiple formats.
@@ -998,7 +1112,8 @@ impl Manifest {
     #[allow(deprecated)]
     pub fn new() -> Self {
         Self {
-            sources: HashMap::new(),
+            min_agpm_version: None,
+            sources: SourceMap::new(),
             tools: None,
             agents: HashMap::new(),
             snippets: HashMap::new(),
@@ -1009,10 +1124,16 @@ impl Manifest {
             patches: ManifestPatches::new(),
             project_patches: ManifestPatches::new(),
             private_patches: ManifestPatches::new(),
+            version_overrides: VersionOverrides::default(),
+            replacements: BTreeMap::new(),
             default_tools: HashMap::new(),
+            profiles: BTreeMap::new(),
             project: None,
             gitignore: Self::default_gitignore(),
+            strip_metadata: false,
+            metadata_keys: Vec::new(),
             manifest_dir: None,
+            git_sources: BTreeSet::new(),
         }
     }
 
@@ -1070,6 +1191,8 @@ impl Manifest {
             "manifest_module",
         )?;
 
+        Self::check_min_agpm_version(&content, path)?;
+
         let mut manifest: Self = toml::from_str(&content)
             .map_err(|e| crate::core::AgpmError::ManifestParseError {
                 file: path.display().to_string(),
@@ -1091,6 +1214,9 @@ impl Manifest {
         // Snippets default to "agpm" (shared infrastructure) instead of "claude-code"
         manifest.apply_tool_defaults();
 
+        // Give dependencies with an inline `git` URL a source to resolve from
+        manifest.apply_git_sources()?;
+
         // Store the manifest directory for resolving relative paths
         manifest.manifest_dir = Some(
             path.parent()
@@ -1103,6 +1229,39 @@ impl Manifest {
         Ok(manifest)
     }
 
+    /// Fail if the manifest's `min_agpm_version` is newer than this binary.
+    ///
+    /// Reads the key from the raw TOML so the check runs before fields this
+    /// version may not understand are deserialized. Syntax errors are left to
+    /// the full parse in [`Self::load`].
+    fn check_min_agpm_version(content: &str, path: &Path) -> Result<()> {
+        let Ok(table) = toml::from_str::<toml::Table>(content) else {
+            return Ok(());
+        };
+        let Some(required) = table.get("min_agpm_version") else {
+            return Ok(());
+        };
+
+        let invalid = || {
+            anyhow::anyhow!(
+                "Invalid min_agpm_version {required} in {}: expected a version such as \"0.4.0\"",
+                path.display()
+            )
+        };
+        let required = required.as_str().ok_or_else(invalid)?;
+        let required_version =
+            semver::Version::parse(required.trim_start_matches('v')).map_err(|_| invalid())?;
+        let current = semver::Version::parse(env!("CARGO_PKG_VERSION"))?;
+
+        if current < required_version {
+            return Err(anyhow::anyhow!(
+                "This manifest requires agpm >= {required_version}, you have {current}\n\n\
+                 Hint: Run 'agpm upgrade' to install a newer version."
+            ));
+        }
+        Ok(())
+    }
+
     /// Load manifest with private config merged.
     ///
     /// Loads the project manifest from `agpm.toml` and then attempts to load
@@ -1300,6 +1459,85 @@ impl Manifest {
         }
     }
 
+    /// Register a source for each dependency given with an inline `git` URL.
+    ///
+    /// A `[sources]` entry with the same URL is reused. Otherwise a source is
+    /// added under a name derived from the repository, such as `org-repo`,
+    /// with a numeric suffix if that name is taken. The dependency's `source`
+    /// then names it, so resolution treats it like any other remote dependency.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a dependency sets both `git` and `source`, or if the
+    /// URL is not a supported Git URL or embeds credentials.
+    fn apply_git_sources(&mut self) -> Result<()> {
+        let mut inline = Vec::new();
+        for resource_type in crate::core::ResourceType::all() {
+            let Some(deps) = self.get_dependencies(*resource_type) else {
+                continue;
+            };
+            for (name, dep) in deps {
+                let Some(url) = dep.get_git() else {
+                    continue;
+                };
+                if dep.get_source().is_some() {
+                    return Err(crate::core::AgpmError::ManifestValidationError {
+                        reason: format!("Dependency '{name}' cannot combine 'git' with 'source'"),
+                    }
+                    .into());
+                }
+                validate_source_url(name, url)
+                    .with_context(|| format!("Invalid git URL for dependency '{name}'"))?;
+                if url_has_credentials(url) {
+                    return Err(crate::core::AgpmError::ManifestValidationError {
+                        reason: format!(
+                            "Git URL for dependency '{name}' contains credentials. \n\
+                            Configure authentication in ~/.agpm/config.toml instead."
+                        ),
+                    }
+                    .into());
+                }
+                inline.push((*resource_type, name.clone(), url.to_string()));
+            }
+        }
+
+        for (resource_type, name, url) in inline {
+            let existing = self
+                .sources
+                .iter()
+                .filter(|(_, source_url)| **source_url == url)
+                .map(|(source, _)| source.clone())
+                .min();
+            let source = match existing {
+                Some(source) => source,
+                None => {
+                    let (owner, repo) = crate::git::parse_git_url(&url)
+                        .unwrap_or(("direct".to_string(), "repo".to_string()));
+                    let base = format!("{owner}-{repo}");
+                    let source = (1..)
+                        .map(|n| {
+                            if n == 1 {
+                                base.clone()
+                            } else {
+                                format!("{base}-{n}")
+                            }
+                        })
+                        .find(|candidate| !self.sources.contains_key(candidate))
+                        .expect("an unused source name");
+                    self.sources.insert(source.clone(), url);
+                    self.git_sources.insert(source.clone());
+                    source
+                }
+            };
+            if let Some(ResourceDependency::Detailed(details)) =
+                self.get_dependencies_mut(resource_type).and_then(|deps| deps.get_mut(&name))
+            {
+                details.source = Some(source);
+            }
+        }
+        Ok(())
+    }
+
     /// Save the manifest to a TOML file with pretty formatting.
     ///
     /// This method serializes the manifest to TOML format and writes it to the
@@ -1367,8 +1605,11 @@ impl Manifest {
     /// utils = { source = "official", path = "snippets/utils.md", version = "v1.0.0" }
     /// ```
     pub fn save(&self, path: &Path) -> Result<()> {
+        // Write inline `git` URLs back as given, without the sources registered for them
+        let manifest = self.without_git_sources();
+
         // Serialize to a document first so we can control formatting
-        let mut doc = toml_edit::ser::to_document(self)
+        let mut doc = toml_edit::ser::to_document(&manifest)
             .with_context(|| "Failed to serialize manifest data to TOML format")?;
 
         // Convert top-level inline tables to regular tables (section headers)
@@ -1393,6 +1634,28 @@ impl Manifest {
         Ok(())
     }
 
+    /// A copy of the manifest as written, without the sources registered by
+    /// [`Self::apply_git_sources`].
+    fn without_git_sources(&self) -> Self {
+        let mut manifest = self.clone();
+        for source in &self.git_sources {
+            manifest.sources.remove(source);
+        }
+        for resource_type in crate::core::ResourceType::all() {
+            if let Some(deps) = manifest.get_dependencies_mut(*resource_type) {
+                for dep in deps.values_mut() {
+                    if let ResourceDependency::Detailed(details) = dep
+                        && details.git.is_some()
+                    {
+                        details.source = None;
+                    }
+                }
+            }
+        }
+        manifest.git_sources.clear();
+        manifest
+    }
+
     /// Validate the manifest structure and enforce business rules.
     ///
     /// This method performs comprehensive validation of the manifest to ensure
@@ -1460,6 +1723,17 @@ impl Manifest {
     ///         flatten: None,
     ///         install: None,
     ///         template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+    ///         env: None,
+    ///         transport: None,
+    ///         url: None,
+    ///         as_of: None,
+    ///         post_install: None,
+    ///         variants: None,
+    ///         strip_metadata: None,
+    ///         inline: None,
+    ///         expect_commit: None,
+    ///         enabled: None,
+    ///         git: None,
     ///     })),
     ///     true
     /// );
@@ -1504,8 +1778,29 @@ impl Manifest {
             }
         }
 
+        // `agpm` holds templating settings, not a dependency list
+        if let Some(key) = self.metadata_keys.iter().find(|key| key.is_empty() || *key == "agpm") {
+            return Err(crate::core::AgpmError::ManifestValidationError {
+                reason: format!("Invalid metadata key '{key}' in metadata_keys"),
+            }
+            .into());
+        }
+
         // Check that all referenced sources exist and dependencies have required fields
         for (name, dep) in self.all_dependencies() {
+            // Inline resources carry their content instead of a source and path
+            if dep.get_inline().is_some() {
+                if dep.get_source().is_some() || !dep.get_path().is_empty() {
+                    return Err(crate::core::AgpmError::ManifestValidationError {
+                        reason: format!(
+                            "Dependency '{name}' cannot combine 'inline' with 'source' or 'path'"
+                        ),
+                    }
+                    .into());
+                }
+                continue;
+            }
+
             // Check for empty path
             if dep.get_path().is_empty() {
                 return Err(crate::core::AgpmError::ManifestValidationError {
@@ -1563,6 +1858,142 @@ impl Manifest {
             }
         }
 
+        // MCP transport overrides are only meaningful for MCP servers, and must be
+        // self-consistent (e.g. no `url` with transport = "stdio")
+        for resource_type in crate::core::ResourceType::all() {
+            let Some(deps) = self.get_dependencies(*resource_type) else {
+                continue;
+            };
+            for (name, dep) in deps {
+                // Hooks and MCP servers are merged into configuration files, not installed
+                if dep.get_inline().is_some()
+                    && matches!(
+                        resource_type,
+                        crate::core::ResourceType::Hook | crate::core::ResourceType::McpServer
+                    )
+                {
+                    return Err(crate::core::AgpmError::ManifestValidationError {
+                        reason: format!(
+                            "Invalid dependency '{name}': 'inline' is not supported for {}",
+                            resource_type.to_plural()
+                        ),
+                    }
+                    .into());
+                }
+
+                let (transport, url) = (dep.get_transport(), dep.get_url());
+                if transport.is_none() && url.is_none() {
+                    continue;
+                }
+
+                let result = if *resource_type == crate::core::ResourceType::McpServer {
+                    crate::mcp::validation::validate_transport_override(transport, url)
+                } else {
+                    Err(anyhow::anyhow!(
+                        "'transport' and 'url' are only supported for MCP server dependencies"
+                    ))
+                };
+                result.map_err(|e| crate::core::AgpmError::ManifestValidationError {
+                    reason: format!("Invalid dependency '{name}': {e}"),
+                })?;
+            }
+        }
+
+        // `as_of` pins a branch by date. Dependencies on the same branch of a source
+        // share one resolved commit, so they must agree on the date.
+        let mut branch_dates: HashMap<(&str, &str), Option<&str>> = HashMap::new();
+        for (name, dep) in self.all_dependencies() {
+            let ResourceDependency::Detailed(detailed) = dep else {
+                continue;
+            };
+            if let Some(as_of) = detailed.as_of.as_deref() {
+                let problem = if detailed.source.is_none() {
+                    Some("'as_of' requires a Git source".to_string())
+                } else if detailed.rev.is_some() || detailed.version.is_some() {
+                    Some(
+                        "'as_of' can only be used with 'branch', not 'version' or 'rev'"
+                            .to_string(),
+                    )
+                } else if detailed.branch.is_none() {
+                    Some("'as_of' requires 'branch'".to_string())
+                } else if chrono::NaiveDate::parse_from_str(as_of, "%Y-%m-%d").is_err()
+                    && chrono::DateTime::parse_from_rfc3339(as_of).is_err()
+                {
+                    Some(format!(
+                        "'as_of' must be a date (YYYY-MM-DD) or RFC 3339 timestamp, got '{as_of}'"
+                    ))
+                } else {
+                    None
+                };
+                if let Some(problem) = problem {
+                    return Err(crate::core::AgpmError::ManifestValidationError {
+                        reason: format!("Invalid dependency '{name}': {problem}"),
+                    }
+                    .into());
+                }
+            }
+
+            if let Some(expected) = detailed.expect_commit.as_deref() {
+                let problem = if detailed.source.is_none() {
+                    Some("'expect_commit' requires a Git source".to_string())
+                } else if !(7..=40).contains(&expected.len())
+                    || !expected.chars().all(|c| c.is_ascii_hexdigit())
+                {
+                    Some(format!(
+                        "'expect_commit' must be a commit hash of 7 to 40 hex characters, got '{expected}'"
+                    ))
+                } else {
+                    None
+                };
+                if let Some(problem) = problem {
+                    return Err(crate::core::AgpmError::ManifestValidationError {
+                        reason: format!("Invalid dependency '{name}': {problem}"),
+                    }
+                    .into());
+                }
+            }
+
+            if let Some(variants) = &detailed.variants {
+                let mut filenames = std::collections::HashSet::new();
+                let problem = if variants.is_empty() {
+                    Some("'variants' must not be empty")
+                } else if dep.is_pattern() {
+                    Some("'variants' cannot be used with pattern dependencies")
+                } else if variants.len() > 1
+                    && !variants.iter().all(|variant| {
+                        filenames.insert(variant.filename.as_ref().or(detailed.filename.as_ref()))
+                    })
+                {
+                    Some("each variant needs a distinct 'filename'")
+                } else {
+                    None
+                };
+                if let Some(problem) = problem {
+                    return Err(crate::core::AgpmError::ManifestValidationError {
+                        reason: format!("Invalid dependency '{name}': {problem}"),
+                    }
+                    .into());
+                }
+            }
+
+            if let (Some(source), Some(branch), None) =
+                (detailed.source.as_deref(), detailed.branch.as_deref(), detailed.rev.as_deref())
+            {
+                let as_of = detailed.as_of.as_deref();
+                if let Some(existing) = branch_dates.insert((source, branch), as_of)
+                    && existing != as_of
+                {
+                    return Err(crate::core::AgpmError::ManifestValidationError {
+                        reason: format!(
+                            "Dependencies on branch '{branch}' of source '{source}' use different \
+                             'as_of' dates; all of them must use the same 'as_of' (or none)"
+                        ),
+                    }
+                    .into());
+                }
+            }
+        }
+
         // Check for version conflicts (same dependency name with different versions)
         let mut seen_deps: std::collections::HashMap<String, String> =
             std::collections::HashMap::new();
@@ -1583,38 +2014,56 @@ impl Manifest {
             }
         }
 
+        self.replacement_urls()?;
+
         // Validate URLs in sources
         for (name, url) in &self.sources {
-            // Expand environment variables and home directory in URL
-            let expanded_url = expand_url(url)?;
-
-            if !expanded_url.starts_with("http://")
-                && !expanded_url.starts_with("https://")
-                && !expanded_url.starts_with("git@")
-                && !expanded_url.starts_with("file://")
-            // Plain directory paths not allowed as sources
-            && !expanded_url.starts_with('/')
-            && !expanded_url.starts_with("./")
-            && !expanded_url.starts_with("../")
-            {
+            validate_source_url(name, url)?;
+
+            if let Err(e) = self.sources.options(name).tag_filter() {
                 return Err(crate::core::AgpmError::ManifestValidationError {
-                    reason: format!("Source '{name}' has invalid URL: '{url}'. Must be HTTP(S), SSH (git@...), or file:// URL"),
+                    reason: format!("Source '{name}' has invalid tag settings: {e:#}"),
                 }
                 .into());
             }
 
-            // Check if plain directory path is used as a source
-            if expanded_url.starts_with('/')
-                || expanded_url.starts_with("./")
-                || expanded_url.starts_with("../")
+            if self.sources.options(name).clone_depth == Some(0) {
+                return Err(crate::core::AgpmError::ManifestValidationError {
+                    reason: format!("Source '{name}' has clone_depth = 0; it must be at least 1"),
+                }
+                .into());
+            }
+
+            // A namespace becomes a single directory level under each resource directory
+            if let Some(namespace) = self.sources.options(name).namespace
+                && (namespace.is_empty()
+                    || namespace == "."
+                    || namespace == ".."
+                    || namespace.contains(['/', '\\']))
             {
                 return Err(crate::core::AgpmError::ManifestValidationError {
                     reason: format!(
-                        "Plain directory path '{url}' cannot be used as source '{name}'. \n\
-                        Sources must be git repositories. Use one of:\n\
-                        - Remote URL: https://github.com/owner/repo.git\n\
-                        - Local git repo: file:///absolute/path/to/repo\n\
-                        - Or use direct path dependencies without a source"
+                        "Source '{name}' has invalid namespace '{namespace}'. A namespace must be a single directory name"
+                    ),
+                }
+                .into());
+            }
+        }
+
+        // Namespaced dependency keys (`community/helper`) must name the dependency's
+        // own source, or that source's namespace, before the slash
+        for (name, dep) in self.all_dependencies() {
+            let Some((prefix, rest)) = name.split_once('/') else {
+                continue;
+            };
+            let source = dep.get_source();
+            let namespace = source.and_then(|source| self.sources.options(source).namespace);
+            let matches_source = source == Some(prefix) || namespace.as_deref() == Some(prefix);
+            if rest.is_empty() || rest.contains('/') || !matches_source {
+                return Err(crate::core::AgpmError::ManifestValidationError {
+                    reason: format!(
+                        "Dependency key '{name}' is not a valid namespaced name. Use '<source>/<name>' \
+                         where <source> is the dependency's source or that source's namespace"
                     ),
                 }
                 .into());
@@ -1806,9 +2255,88 @@ impl Manifest {
         // Validate patches reference valid aliases
         self.validate_patches()?;
 
+        for (profile, names) in &self.profiles {
+            if let Some(name) = names.iter().find(|name| !self.has_profile_dependency(name)) {
+                return Err(crate::core::AgpmError::ManifestValidationError {
+                    reason: format!(
+                        "Profile '{profile}' references unknown dependency '{name}'.\n\
+                         Profiles list the names of dependencies defined in agpm.toml."
+                    ),
+                }
+                .into());
+            }
+        }
+
+        // Recognized [project] keys only produce warnings
+        for warning in self.project_warnings() {
+            tracing::warn!("{warning}");
+        }
+
+        Ok(())
+    }
+
+    /// The URL each `[replace]` entry fetches its source from.
+    ///
+    /// Relative paths are resolved against the manifest directory. See
+    /// [`replacement_url`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a replacement is not a supported Git URL.
+    pub fn replacement_urls(&self) -> Result<HashMap<String, String>> {
+        self.replacements
+            .iter()
+            .map(|(name, target)| {
+                Ok((name.clone(), replacement_url(name, target, self.manifest_dir.as_deref())?))
+            })
+            .collect()
+    }
+
+    /// Keep only the dependencies listed in `profile`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest has no profile named `profile`.
+    pub fn select_profile(&mut self, profile: &str) -> Result<()> {
+        let Some(names) = self.profiles.get(profile) else {
+            let available = if self.profiles.is_empty() {
+                "none are defined in [profiles]".to_string()
+            } else {
+                self.profiles.keys().cloned().collect::<Vec<_>>().join(", ")
+            };
+            return Err(anyhow::anyhow!(
+                "Unknown profile '{profile}' (available profiles: {available})"
+            ));
+        };
+        let names: HashSet<String> = names.iter().cloned().collect();
+        for resource_type in crate::core::ResourceType::all() {
+            if let Some(deps) = self.get_dependencies_mut(*resource_type) {
+                deps.retain(|name, _| names.contains(name));
+            }
+        }
         Ok(())
     }
 
+    /// Whether any resource section defines a dependency named `name`.
+    fn has_profile_dependency(&self, name: &str) -> bool {
+        crate::core::ResourceType::all().iter().any(|resource_type| {
+            self.get_dependencies(*resource_type).is_some_and(|deps| deps.contains_key(name))
+        })
+    }
+
+    /// Check the recognized keys of the `[project]` section.
+    ///
+    /// Returns one warning per wrongly typed well-known key, missing
+    /// `project.paths` entry, or likely typo of a well-known key. Other keys
+    /// are free-form and never produce warnings.
+    #[must_use]
+    pub fn project_warnings(&self) -> Vec<String> {
+        self.project
+            .as_ref()
+            .map(|project| project.schema_warnings(self.manifest_dir.as_deref()))
+            .unwrap_or_default()
+    }
+
     /// Validate that patches reference valid manifest aliases.
     ///
     /// This method checks that all patch aliases correspond to actual dependencies
@@ -1946,6 +2474,14 @@ impl Manifest {
         })
     }
 
+    /// Get a mutable reference to the tools configuration.
+    ///
+    /// Materializes the default configuration if the manifest has no `[tools]`
+    /// section, so tools can be enabled or disabled for a single run.
+    pub fn tools_config_mut(&mut self) -> &mut ToolsConfig {
+        self.tools.get_or_insert_with(ToolsConfig::default)
+    }
+
     /// Get configuration for a specific tool type.
     ///
     /// Returns None if the tool is not configured.
@@ -1953,6 +2489,14 @@ impl Manifest {
         self.get_tools_config().types.get(tool)
     }
 
+    /// Whether resources for `tool` are installed.
+    ///
+    /// Tools that are not configured count as enabled, matching
+    /// [`Manifest::all_dependencies_with_types`].
+    pub fn is_tool_enabled(&self, tool: &str) -> bool {
+        self.get_tool_config(tool).is_none_or(|config| config.enabled)
+    }
+
     /// Get the installation path for a resource within a tool.
     ///
     /// Returns the full installation directory path by combining:
@@ -1974,6 +2518,26 @@ impl Manifest {
         resource_config.path.as_ref().map(|subdir| artifact_config.path.join(subdir))
     }
 
+    /// Get the target directory for a dependency, relative to its resource directory.
+    ///
+    /// Returns the dependency's own `target` if set, otherwise the
+    /// `default-target` configured for the resource type in `[tools]`.
+    #[must_use]
+    pub fn get_dependency_target<'a>(
+        &'a self,
+        dep: &'a ResourceDependency,
+        tool: &str,
+        resource_type: crate::core::ResourceType,
+    ) -> Option<&'a str> {
+        dep.get_target().or_else(|| {
+            self.get_tool_config(tool)?
+                .resources
+                .get(resource_type.to_plural())?
+                .default_target
+                .as_deref()
+        })
+    }
+
     /// Get the merge target configuration file path for a resource type.
     ///
     /// Returns the path to the configuration file where resources of this type
@@ -2122,7 +2686,8 @@ impl Manifest {
     /// This is used by the resolver to correctly type transitive dependencies without
     /// falling back to manifest section order lookups.
     ///
-    /// Dependencies for disabled tools are automatically filtered out.
+    /// Dependencies for disabled tools, and dependencies with `enabled = false`,
+    /// are automatically filtered out.
     pub fn all_dependencies_with_types(
         &self,
     ) -> Vec<(&str, std::borrow::Cow<'_, ResourceDependency>, crate::core::ResourceType)> {
@@ -2138,6 +2703,11 @@ impl Manifest {
                 sorted_deps.sort_by_key(|(name, _)| name.as_str());
 
                 for (name, dep) in sorted_deps {
+                    if !dep.is_enabled() {
+                        tracing::debug!("Skipping disabled dependency '{}'", name);
+                        continue;
+                    }
+
                     // Determine the tool for this dependency
                     let tool_string = dep
                         .get_tool()
@@ -2174,6 +2744,14 @@ impl Manifest {
                         std::borrow::Cow::Borrowed(dep)
                     };
 
+                    // A dependency with variants is resolved once per variant
+                    if let Some(variants) = dep_with_tool.expand_variants() {
+                        deps.extend(variants.into_iter().map(|variant| {
+                            (name.as_str(), std::borrow::Cow::Owned(variant), *resource_type)
+                        }));
+                        continue;
+                    }
+
                     deps.push((name.as_str(), dep_with_tool, *resource_type));
                 }
             }
@@ -2386,6 +2964,17 @@ impl Manifest {
     ///         flatten: None,
     ///         install: None,
     ///         template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+    ///         env: None,
+    ///         transport: None,
+    ///         url: None,
+    ///         as_of: None,
+    ///         post_install: None,
+    ///         variants: None,
+    ///         strip_metadata: None,
+    ///         inline: None,
+    ///         expect_commit: None,
+    ///         enabled: None,
+    ///         git: None,
     ///     })),
     ///     false  // is_agent = false (snippet)
     /// );
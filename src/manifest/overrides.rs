@@ -0,0 +1,212 @@
+//! Version overrides for transitive dependencies.
+//!
+//! When two resources depend on the same transitive resource at different
+//! versions, the `[override]` section pins it to one version without making it
+//! a direct dependency. Overrides are keyed by resource type and by the
+//! dependency's path in its source, with or without the resource directory
+//! and extension:
+//!
+//! ```toml
+//! [override.snippets]
+//! utils = "v1.1.0"                 # matches snippets/utils.md
+//! "python/helpers" = "abc1234"     # matches snippets/python/helpers.md
+//! ```
+//!
+//! Overrides apply to every transitive dependency from a Git source that they
+//! match, before versions are resolved to commits, and replace any `branch` or
+//! `rev` the dependency would otherwise track. Direct dependencies are
+//! already pinned by the manifest and are not affected. An override that
+//! matches no transitive dependency is an error, so stale entries do not
+//! linger after upstream changes.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::core::ResourceType;
+use crate::manifest::ResourceDependency;
+
+/// Version overrides for all resource types, keyed by dependency name.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct VersionOverrides {
+    /// Overrides for agent resources.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub agents: BTreeMap<String, String>,
+
+    /// Overrides for snippet resources.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub snippets: BTreeMap<String, String>,
+
+    /// Overrides for command resources.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub commands: BTreeMap<String, String>,
+
+    /// Overrides for script resources.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub scripts: BTreeMap<String, String>,
+
+    /// Overrides for MCP server resources.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty", rename = "mcp-servers")]
+    pub mcp_servers: BTreeMap<String, String>,
+
+    /// Overrides for hook resources.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub hooks: BTreeMap<String, String>,
+}
+
+impl VersionOverrides {
+    /// Whether no overrides are declared.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        ResourceType::all().iter().all(|rt| self.section(*rt).is_empty())
+    }
+
+    /// The overrides declared for one resource type.
+    #[must_use]
+    pub const fn section(&self, resource_type: ResourceType) -> &BTreeMap<String, String> {
+        match resource_type {
+            ResourceType::Agent => &self.agents,
+            ResourceType::Snippet => &self.snippets,
+            ResourceType::Command => &self.commands,
+            ResourceType::Script => &self.scripts,
+            ResourceType::McpServer => &self.mcp_servers,
+            ResourceType::Hook => &self.hooks,
+        }
+    }
+
+    /// The overrides declared for one resource type, for editing.
+    pub const fn section_mut(
+        &mut self,
+        resource_type: ResourceType,
+    ) -> &mut BTreeMap<String, String> {
+        match resource_type {
+            ResourceType::Agent => &mut self.agents,
+            ResourceType::Snippet => &mut self.snippets,
+            ResourceType::Command => &mut self.commands,
+            ResourceType::Script => &mut self.scripts,
+            ResourceType::McpServer => &mut self.mcp_servers,
+            ResourceType::Hook => &mut self.hooks,
+        }
+    }
+
+    /// The version pinned for the dependency with canonical `name`, if any.
+    ///
+    /// `name` is the dependency's path without extension, such as
+    /// `snippets/utils`.
+    #[must_use]
+    pub fn version_for(&self, resource_type: ResourceType, name: &str) -> Option<&str> {
+        self.section(resource_type)
+            .iter()
+            .find(|(key, _)| Self::matches(key, resource_type, name))
+            .map(|(_, version)| version.as_str())
+    }
+
+    /// Pin `dependency`, whose canonical name is `name`, to its override.
+    ///
+    /// Only dependencies from a Git source are pinned. Their `branch` and `rev`
+    /// are cleared, since either would take precedence over the pinned version.
+    ///
+    /// Returns the pinned version, if an override matched.
+    pub fn apply(
+        &self,
+        resource_type: ResourceType,
+        name: &str,
+        dependency: &mut ResourceDependency,
+    ) -> Option<&str> {
+        let version = self.version_for(resource_type, name)?;
+        let ResourceDependency::Detailed(detailed) = dependency else {
+            return None;
+        };
+        detailed.source.as_ref()?;
+
+        detailed.version = Some(version.to_string());
+        detailed.branch = None;
+        detailed.rev = None;
+        Some(version)
+    }
+
+    /// Whether the override `key` targets the dependency with canonical `name`.
+    ///
+    /// The key may be the full name or the name without its leading resource
+    /// directory (`utils` for `snippets/utils`).
+    #[must_use]
+    pub fn matches(key: &str, resource_type: ResourceType, name: &str) -> bool {
+        let key = key.trim_end_matches(".md").trim_end_matches(".json");
+        key == name
+            || name
+                .strip_prefix(resource_type.to_plural())
+                .and_then(|rest| rest.strip_prefix('/'))
+                .is_some_and(|rest| rest == key)
+    }
+
+    /// Return every override as `(resource type, key, version)`.
+    pub fn iter(&self) -> impl Iterator<Item = (ResourceType, &str, &str)> {
+        ResourceType::all().iter().flat_map(move |rt| {
+            self.section(*rt)
+                .iter()
+                .map(move |(key, version)| (*rt, key.as_str(), version.as_str()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_match_overrides() {
+        let overrides: VersionOverrides = toml::from_str(
+            r#"
+            [snippets]
+            utils = "v1.1.0"
+            "python/helpers.md" = "abc1234"
+
+            [mcp-servers]
+            "mcp-servers/postgres" = "v2.0.0"
+            "#,
+        )
+        .unwrap();
+
+        assert!(!overrides.is_empty());
+        assert_eq!(overrides.version_for(ResourceType::Snippet, "snippets/utils"), Some("v1.1.0"));
+        assert_eq!(
+            overrides.version_for(ResourceType::Snippet, "snippets/python/helpers"),
+            Some("abc1234")
+        );
+        assert_eq!(
+            overrides.version_for(ResourceType::McpServer, "mcp-servers/postgres"),
+            Some("v2.0.0")
+        );
+        assert_eq!(overrides.version_for(ResourceType::Snippet, "snippets/other/utils"), None);
+        assert_eq!(overrides.version_for(ResourceType::Agent, "agents/utils"), None);
+        assert_eq!(overrides.iter().count(), 3);
+        assert!(VersionOverrides::default().is_empty());
+    }
+
+    #[test]
+    fn test_apply_replaces_branch_and_rev() {
+        use crate::manifest::DetailedDependency;
+        use crate::resolver::types::ResolutionMode;
+
+        let overrides: VersionOverrides =
+            toml::from_str("[snippets]\nutils = \"v1.1.0\"\n").unwrap();
+
+        for (branch, rev) in [(Some("main"), None), (None, Some("abc1234")), (None, None)] {
+            let mut detailed: DetailedDependency = toml::from_str(
+                "source = \"community\"\npath = \"snippets/utils.md\"\nversion = \"v1.0.0\"\n",
+            )
+            .unwrap();
+            detailed.branch = branch.map(String::from);
+            detailed.rev = rev.map(String::from);
+            let mut dependency = ResourceDependency::Detailed(Box::new(detailed));
+
+            let pinned = overrides.apply(ResourceType::Snippet, "snippets/utils", &mut dependency);
+            assert_eq!(pinned, Some("v1.1.0"));
+            assert_eq!(dependency.get_version(), Some("v1.1.0"));
+            assert_eq!(ResolutionMode::from_dependency(&dependency), ResolutionMode::Version);
+        }
+
+        // Local dependencies are not pinned
+        let mut local = ResourceDependency::Simple("snippets/utils.md".to_string());
+        assert_eq!(overrides.apply(ResourceType::Snippet, "snippets/utils", &mut local), None);
+    }
+}
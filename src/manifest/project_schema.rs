@@ -0,0 +1,176 @@
+//! Recognized keys of the `[project]` manifest section.
+//!
+//! `[project]` is free-form: any key is accepted and exposed to templates as
+//! `agpm.project.*`. A few keys have a well-known meaning, and those are
+//! type-checked so mistakes surface as warnings instead of silently rendering
+//! as empty strings:
+//!
+//! ```toml
+//! [project]
+//! name = "my-service"          # must be a string
+//! version = "1.4.0"            # must be a semver version (a leading `v` is allowed)
+//! description = "Payments API" # must be a string
+//!
+//! [project.paths]              # must be a table of strings
+//! conventions = "docs/CONVENTIONS.md"  # warns if the path does not exist
+//! ```
+//!
+//! Unknown top-level keys that are a small edit away from a recognized key
+//! (such as `verion`) are reported as likely typos. All other keys are left
+//! alone, so existing free-form configurations keep working unchanged.
+
+use std::path::Path;
+
+use super::ProjectConfig;
+
+/// Top-level `[project]` keys with a well-known meaning.
+const RECOGNIZED_KEYS: &[&str] = &["name", "version", "description", "paths"];
+
+/// Maximum edit distance for an unknown key to be reported as a typo.
+///
+/// One edit per four characters, and at least one, so short free-form keys
+/// such as `type` are not mistaken for `name`.
+fn typo_distance(key: &str) -> usize {
+    (key.chars().count() / 4).max(1)
+}
+
+impl ProjectConfig {
+    /// Check the recognized `[project]` keys and return a warning for each problem.
+    ///
+    /// Relative `paths.*` entries are checked for existence against
+    /// `manifest_dir`; the existence check is skipped when it is `None`.
+    #[must_use]
+    pub fn schema_warnings(&self, manifest_dir: Option<&Path>) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        for (key, value) in &self.0 {
+            match key.as_str() {
+                "name" | "description" => {
+                    if !value.is_str() {
+                        warnings.push(format!(
+                            "project.{key} should be a string, found {}",
+                            value.type_str()
+                        ));
+                    }
+                }
+                "version" => match value.as_str() {
+                    Some(version) => {
+                        let bare = version.strip_prefix('v').unwrap_or(version);
+                        if semver::Version::parse(bare).is_err() {
+                            warnings.push(format!(
+                                "project.version '{version}' is not a valid semver version (e.g. \"1.2.3\")"
+                            ));
+                        }
+                    }
+                    None => warnings.push(format!(
+                        "project.version should be a string, found {}",
+                        value.type_str()
+                    )),
+                },
+                "paths" => match value.as_table() {
+                    Some(paths) => {
+                        for (name, path) in paths {
+                            match path.as_str() {
+                                Some(path) => {
+                                    if let Some(dir) = manifest_dir
+                                        && !dir.join(path).exists()
+                                    {
+                                        warnings.push(format!(
+                                            "project.paths.{name} points to '{path}', which does not exist"
+                                        ));
+                                    }
+                                }
+                                None => warnings.push(format!(
+                                    "project.paths.{name} should be a string, found {}",
+                                    path.type_str()
+                                )),
+                            }
+                        }
+                    }
+                    None => warnings.push(format!(
+                        "project.paths should be a table, found {}",
+                        value.type_str()
+                    )),
+                },
+                _ => {
+                    if let Some(recognized) = RECOGNIZED_KEYS.iter().find(|recognized| {
+                        strsim::levenshtein(key, recognized) <= typo_distance(key)
+                    }) {
+                        warnings.push(format!(
+                            "project.{key} is not a recognized key; did you mean project.{recognized}?"
+                        ));
+                    }
+                }
+            }
+        }
+
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn project(toml_str: &str) -> ProjectConfig {
+        toml::from_str(toml_str).unwrap()
+    }
+
+    #[test]
+    fn test_schema_warnings_accept_valid_and_free_form_keys() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("CONVENTIONS.md"), "# Conventions").unwrap();
+
+        let config = project(
+            r#"
+            name = "my-service"
+            version = "v1.4.0"
+            style_guide = "docs/STYLE.md"
+            max_line_length = 100
+            type = "service"
+            url = "https://example.com"
+
+            [paths]
+            conventions = "CONVENTIONS.md"
+            "#,
+        );
+        assert!(config.schema_warnings(Some(temp.path())).is_empty());
+    }
+
+    #[test]
+    fn test_schema_warnings_report_wrong_types_and_typos() {
+        let temp = TempDir::new().unwrap();
+        let config = project(
+            r#"
+            name = 42
+            verion = "1.0.0"
+            description = "ok"
+
+            [paths]
+            missing = "docs/MISSING.md"
+            count = 3
+            "#,
+        );
+
+        let mut warnings = config.schema_warnings(Some(temp.path()));
+        warnings.sort();
+        assert_eq!(warnings.len(), 4, "{warnings:?}");
+        assert!(warnings[0].contains("project.name should be a string, found integer"));
+        assert!(warnings[1].contains("project.paths.count should be a string"));
+        assert!(warnings[2].contains("project.paths.missing points to 'docs/MISSING.md'"));
+        assert!(warnings[3].contains("did you mean project.version?"));
+
+        assert!(
+            project(r#"version = "latest""#).schema_warnings(None)[0]
+                .contains("not a valid semver version")
+        );
+        assert!(config.schema_warnings(None).iter().all(|w| !w.contains("does not exist")));
+
+        // Longer keys tolerate more edits
+        assert!(
+            project(r#"descripton = "typo""#).schema_warnings(None)[0]
+                .contains("did you mean project.description?")
+        );
+    }
+}
@@ -5,7 +5,7 @@
 //! - `DetailedDependency`: Full dependency specification with all configuration options
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use crate::manifest::dependency_spec::DependencySpec;
 
@@ -142,6 +142,22 @@ pub struct DetailedDependency {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source: Option<String>,
 
+    /// Git repository URL to fetch this dependency from without a `[sources]` entry.
+    ///
+    /// The URL is registered as an anonymous source when the manifest is
+    /// loaded, named after the repository (reusing a `[sources]` entry with
+    /// the same URL), and then resolved like any other source. Cannot be
+    /// combined with [`source`](Self::source).
+    ///
+    /// # Examples
+    ///
+    /// ```toml
+    /// [agents]
+    /// helper = { git = "https://github.com/org/repo.git", path = "agents/helper.md", version = "v1.0.0" }
+    /// ```
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git: Option<String>,
+
     /// Path to the resource file or glob pattern for multiple resources.
     ///
     /// For **remote dependencies**: Path within the Git repository\
@@ -171,8 +187,27 @@ pub struct DetailedDependency {
     /// # Pattern: all agents recursively
     /// all_agents = { source = "repo", path = "agents/**/*.md", version = "v1.0.0" }
     /// ```
+    ///
+    /// Empty for [inline](Self::inline) dependencies.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub path: String,
 
+    /// Content of a resource defined directly in the manifest.
+    ///
+    /// Inline resources have no `source` or `path`. The content is always
+    /// rendered as a template and installed as `<name>.md` (or `filename`)
+    /// in the resource type's directory. Inline resources cannot declare
+    /// dependencies of their own.
+    ///
+    /// # Examples
+    ///
+    /// ```toml
+    /// [snippets]
+    /// greeting = { inline = "Hello from {{ agpm.project.name }}" }
+    /// ```
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inline: Option<String>,
+
     /// Version constraint for Git tag resolution.
     ///
     /// Specifies which version of the resource to use when resolving from
@@ -185,7 +220,7 @@ pub struct DetailedDependency {
     /// - `"v1.0.0"` - Exact semantic version tag
     /// - `"1.0.0"` - Exact version (v prefix optional)
     /// - `"^1.0.0"` - Semantic version constraint (highest compatible 1.x.x)
-    /// - `"latest"` - Git tag or branch named "latest" (not special - just a name)
+    /// - `"latest"` - Highest stable semver tag (`"latest-prerelease"` includes prereleases)
     /// - `"main"` - Use main/master branch HEAD
     ///
     /// # Examples
@@ -194,7 +229,7 @@ pub struct DetailedDependency {
     /// [agents]
     /// stable = { source = "repo", path = "agent.md", version = "v1.0.0" }
     /// flexible = { source = "repo", path = "agent.md", version = "^1.0.0" }
-    /// latest-tag = { source = "repo", path = "agent.md", version = "latest" }  # If repo has a "latest" tag
+    /// newest = { source = "repo", path = "agent.md", version = "latest" }  # Highest stable tag
     /// main = { source = "repo", path = "agent.md", version = "main" }
     /// ```
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -238,6 +273,54 @@ pub struct DetailedDependency {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rev: Option<String>,
 
+    /// Pin a branch dependency to the last commit on or before this date.
+    ///
+    /// Accepts a date (`2024-06-01`, meaning the end of that day) or an RFC 3339
+    /// timestamp. Only valid together with [`branch`](Self::branch). Other
+    /// dependencies on the same branch of the source resolve to the same commit.
+    ///
+    /// # Examples
+    ///
+    /// ```toml
+    /// [agents]
+    /// snapshot = { source = "repo", path = "agent.md", branch = "main", as_of = "2024-06-01" }
+    /// ```
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub as_of: Option<String>,
+
+    /// Commit the resolved version is expected to point at.
+    ///
+    /// Resolution fails if the dependency's version resolves to a commit that
+    /// does not start with this hash, which catches a tag that was moved or
+    /// force-pushed upstream. Accepts a full or abbreviated (at least 7
+    /// characters) hex SHA. Only valid for dependencies from a Git source.
+    ///
+    /// # Examples
+    ///
+    /// ```toml
+    /// [agents]
+    /// reviewer = { source = "repo", path = "agent.md", version = "v1.2.0", expect_commit = "a1b2c3d4e5f6" }
+    /// ```
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expect_commit: Option<String>,
+
+    /// Whether the dependency is installed (default: `true`).
+    ///
+    /// A dependency with `enabled = false` stays in the manifest but is left
+    /// out of resolution, together with any transitive dependencies only it
+    /// pulled in. Files and merged configuration entries from a previous
+    /// install are removed on the next install, and the lockfile records the
+    /// dependency as disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```toml
+    /// [agents]
+    /// reviewer = { source = "repo", path = "agents/reviewer.md", version = "v1.0.0", enabled = false }
+    /// ```
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
     /// Command to execute for MCP servers.
     ///
     /// This field is specific to MCP server dependencies and specifies
@@ -436,6 +519,24 @@ pub struct DetailedDependency {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub install: Option<bool>,
 
+    /// Whether to strip AGPM-managed frontmatter keys from the installed file.
+    ///
+    /// When `true`, the `dependencies` and `agpm` keys are removed from the YAML
+    /// frontmatter after templating, so the installed file only carries the
+    /// metadata the target tool reads. Other frontmatter keys are kept. Only
+    /// applies to Markdown resources.
+    ///
+    /// Defaults to the manifest-wide `strip_metadata` setting.
+    ///
+    /// # Examples
+    ///
+    /// ```toml
+    /// [agents]
+    /// reviewer = { source = "repo", path = "agents/reviewer.md", strip_metadata = true }
+    /// ```
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strip_metadata: Option<bool>,
+
     /// Template variable overrides for this specific resource.
     ///
     /// Allows specializing generic resources for different use cases by overriding
@@ -487,6 +588,99 @@ pub struct DetailedDependency {
     /// `python-best-practices.md`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub template_vars: Option<serde_json::Value>,
+
+    /// Environment variables injected into an MCP server configuration at install time.
+    ///
+    /// Only used for entries in the `[mcp-servers]` section. Values may reference
+    /// environment variables with `${VAR}` (or `${VAR:-default}` to fall back to a
+    /// default when unset); references are expanded when merging the server into
+    /// `.mcp.json` and the expanded values are never written to `agpm.lock`.
+    /// Entries override keys of the same name in the server's own `env` block.
+    ///
+    /// # Examples
+    ///
+    /// ```toml
+    /// [mcp-servers]
+    /// openai = {
+    ///     source = "community",
+    ///     path = "mcp-servers/openai.json",
+    ///     version = "v1.0.0",
+    ///     env = { OPENAI_API_KEY = "${OPENAI_API_KEY}", LOG_LEVEL = "${LOG_LEVEL:-info}" }
+    /// }
+    /// ```
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env: Option<BTreeMap<String, String>>,
+
+    /// Transport override for an MCP server dependency: `stdio`, `http`, or `sse`.
+    ///
+    /// Only used for entries in the `[mcp-servers]` section. Replaces the `type`
+    /// declared in the server's JSON file, and the merged configuration is reshaped
+    /// to match: HTTP-based transports drop `command`/`args`, stdio drops `url`/`headers`.
+    ///
+    /// # Examples
+    ///
+    /// ```toml
+    /// [mcp-servers]
+    /// fs = { source = "official", path = "mcp/fs.json", version = "v1", transport = "http", url = "http://localhost:3000" }
+    /// ```
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transport: Option<String>,
+
+    /// Server URL override for an HTTP-based MCP server dependency.
+    ///
+    /// Only valid with the `http` and `sse` transports, either set through
+    /// [`transport`](Self::transport) or declared by the server's JSON file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// Command to run after this dependency's file is installed.
+    ///
+    /// Runs from the project root with the installed path appended as the last
+    /// argument, whenever the resource is first installed or its content
+    /// changes. A non-zero exit status fails the install. Skipped entirely with
+    /// `--no-post-install`. Only honored on dependencies declared in the
+    /// manifest, never on transitive dependencies.
+    ///
+    /// # Examples
+    ///
+    /// ```toml
+    /// [scripts]
+    /// register = { source = "repo", path = "scripts/register.sh", version = "v1.0.0", post_install = "scripts/setup.sh" }
+    /// ```
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_install: Option<String>,
+
+    /// Install this dependency several times with different template variables.
+    ///
+    /// Each variant is installed as its own file and locked as its own entry,
+    /// distinguished by its template variables. A variant's `template_vars` are
+    /// merged over the dependency's own, and its `filename` replaces the
+    /// dependency's. Variants should use distinct filenames so their installed
+    /// paths do not conflict.
+    ///
+    /// # Examples
+    ///
+    /// ```toml
+    /// [agents]
+    /// code-reviewer = { source = "official", path = "agents/reviewer.md", version = "v1", variants = [
+    ///     { filename = "reviewer-py.md", template_vars = { lang = "python" } },
+    ///     { filename = "reviewer-rs.md", template_vars = { lang = "rust" } },
+    /// ] }
+    /// ```
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variants: Option<Vec<DependencyVariant>>,
+}
+
+/// One installed variant of a dependency, from its `variants` list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyVariant {
+    /// Installed filename of this variant.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filename: Option<String>,
+
+    /// Template variables of this variant, merged over the dependency's own.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template_vars: Option<serde_json::Value>,
 }
 
 impl ResourceDependency {
@@ -521,6 +715,17 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+    ///     env: None,
+    ///     transport: None,
+    ///     url: None,
+    ///     as_of: None,
+    ///     post_install: None,
+    ///     variants: None,
+    ///     strip_metadata: None,
+    ///     inline: None,
+    ///     expect_commit: None,
+    ///     enabled: None,
+    ///     git: None,
     /// }));
     /// assert_eq!(remote.get_source(), Some("official"));
     /// assert_eq!(remote.get_source(), Some("official"));
@@ -541,6 +746,15 @@ impl ResourceDependency {
         }
     }
 
+    /// Get the Git URL given inline with `git = "..."`, if any.
+    #[must_use]
+    pub fn get_git(&self) -> Option<&str> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Detailed(d) => d.git.as_deref(),
+        }
+    }
+
     /// Get the custom target directory for this dependency.
     ///
     /// Returns the custom target directory if specified, or `None` if the
@@ -567,6 +781,17 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+    ///     env: None,
+    ///     transport: None,
+    ///     url: None,
+    ///     as_of: None,
+    ///     post_install: None,
+    ///     variants: None,
+    ///     strip_metadata: None,
+    ///     inline: None,
+    ///     expect_commit: None,
+    ///     enabled: None,
+    ///     git: None,
     /// }));
     /// assert_eq!(custom.get_target(), Some("custom/tools"));
     ///
@@ -608,6 +833,38 @@ impl ResourceDependency {
         }
     }
 
+    /// Expand a dependency with `variants` into one dependency per variant.
+    ///
+    /// Each expanded dependency has the variant's filename and its template
+    /// variables merged over the dependency's own, and no `variants`. Returns
+    /// `None` for dependencies without variants.
+    #[must_use]
+    pub fn expand_variants(&self) -> Option<Vec<Self>> {
+        let Self::Detailed(detailed) = self else {
+            return None;
+        };
+        let variants = detailed.variants.as_ref()?;
+        Some(
+            variants
+                .iter()
+                .map(|variant| {
+                    let mut expanded = detailed.as_ref().clone();
+                    expanded.variants = None;
+                    if variant.filename.is_some() {
+                        expanded.filename.clone_from(&variant.filename);
+                    }
+                    if let Some(vars) = &variant.template_vars {
+                        expanded.template_vars = Some(match expanded.template_vars.take() {
+                            Some(base) => crate::templating::deep_merge_json(base, vars),
+                            None => vars.clone(),
+                        });
+                    }
+                    Self::Detailed(Box::new(expanded))
+                })
+                .collect(),
+        )
+    }
+
     /// Get the custom filename for this dependency.
     ///
     /// Returns the custom filename if specified, or `None` if the
@@ -634,6 +891,17 @@ impl ResourceDependency {
     ///     install: None,
     ///     flatten: None,
     ///     template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+    ///     env: None,
+    ///     transport: None,
+    ///     url: None,
+    ///     as_of: None,
+    ///     post_install: None,
+    ///     variants: None,
+    ///     strip_metadata: None,
+    ///     inline: None,
+    ///     expect_commit: None,
+    ///     enabled: None,
+    ///     git: None,
     /// }));
     /// assert_eq!(custom.get_filename(), Some("ai-assistant.md"));
     ///
@@ -693,6 +961,29 @@ impl ResourceDependency {
         }
     }
 
+    /// Get the `strip_metadata` setting for this dependency.
+    ///
+    /// Returns `None` when the dependency does not set it, in which case the
+    /// manifest-wide `strip_metadata` default applies.
+    #[must_use]
+    pub fn get_strip_metadata(&self) -> Option<bool> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Detailed(d) => d.strip_metadata,
+        }
+    }
+
+    /// Get the inline content of this dependency.
+    ///
+    /// Returns `Some` for resources defined with `inline` in the manifest.
+    #[must_use]
+    pub fn get_inline(&self) -> Option<&str> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Detailed(d) => d.inline.as_deref(),
+        }
+    }
+
     /// Get the template variable overrides for this resource.
     ///
     /// Returns the resource-specific template variables that override the global
@@ -724,6 +1015,17 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: Some(json!({ "project": { "language": "golang" } })),
+    ///     env: None,
+    ///     transport: None,
+    ///     url: None,
+    ///     as_of: None,
+    ///     post_install: None,
+    ///     variants: None,
+    ///     strip_metadata: None,
+    ///     inline: None,
+    ///     expect_commit: None,
+    ///     enabled: None,
+    ///     git: None,
     /// }));
     ///
     /// assert!(resource.get_template_vars().is_some());
@@ -735,6 +1037,45 @@ impl ResourceDependency {
         }
     }
 
+    /// Get the environment variable overrides for an MCP server dependency.
+    ///
+    /// Returns the unexpanded `env` mapping from the manifest. Values may still
+    /// contain `${VAR}` references, which are expanded at install time.
+    #[must_use]
+    pub fn get_env(&self) -> Option<&BTreeMap<String, String>> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Detailed(d) => d.env.as_ref(),
+        }
+    }
+
+    /// Get the transport override for an MCP server dependency.
+    #[must_use]
+    pub fn get_transport(&self) -> Option<&str> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Detailed(d) => d.transport.as_deref(),
+        }
+    }
+
+    /// Get the server URL override for an MCP server dependency.
+    #[must_use]
+    pub fn get_url(&self) -> Option<&str> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Detailed(d) => d.url.as_deref(),
+        }
+    }
+
+    /// Get the command to run after this dependency is installed.
+    #[must_use]
+    pub fn get_post_install(&self) -> Option<&str> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Detailed(d) => d.post_install.as_deref(),
+        }
+    }
+
     /// Get the path to the resource file.
     ///
     /// Returns the path component of the dependency, which is interpreted
@@ -768,6 +1109,17 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+    ///     env: None,
+    ///     transport: None,
+    ///     url: None,
+    ///     as_of: None,
+    ///     post_install: None,
+    ///     variants: None,
+    ///     strip_metadata: None,
+    ///     inline: None,
+    ///     expect_commit: None,
+    ///     enabled: None,
+    ///     git: None,
     /// }));
     /// assert_eq!(remote.get_path(), "agents/code-reviewer.md");
     /// ```
@@ -827,6 +1179,17 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+    ///     env: None,
+    ///     transport: None,
+    ///     url: None,
+    ///     as_of: None,
+    ///     post_install: None,
+    ///     variants: None,
+    ///     strip_metadata: None,
+    ///     inline: None,
+    ///     expect_commit: None,
+    ///     enabled: None,
+    ///     git: None,
     /// }));
     ///
     /// assert_eq!(dep.get_version(), Some("develop"));
@@ -857,6 +1220,17 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+    ///     env: None,
+    ///     transport: None,
+    ///     url: None,
+    ///     as_of: None,
+    ///     post_install: None,
+    ///     variants: None,
+    ///     strip_metadata: None,
+    ///     inline: None,
+    ///     expect_commit: None,
+    ///     enabled: None,
+    ///     git: None,
     /// }));
     /// assert_eq!(versioned.get_version(), Some("v1.0.0"));
     ///
@@ -876,6 +1250,17 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+    ///     env: None,
+    ///     transport: None,
+    ///     url: None,
+    ///     as_of: None,
+    ///     post_install: None,
+    ///     variants: None,
+    ///     strip_metadata: None,
+    ///     inline: None,
+    ///     expect_commit: None,
+    ///     enabled: None,
+    ///     git: None,
     /// }));
     /// assert_eq!(branch_ref.get_version(), Some("main"));
     /// ```
@@ -935,6 +1320,17 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+    ///     env: None,
+    ///     transport: None,
+    ///     url: None,
+    ///     as_of: None,
+    ///     post_install: None,
+    ///     variants: None,
+    ///     strip_metadata: None,
+    ///     inline: None,
+    ///     expect_commit: None,
+    ///     enabled: None,
+    ///     git: None,
     /// }));
     /// assert!(!remote.is_local());
     ///
@@ -954,6 +1350,17 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+    ///     env: None,
+    ///     transport: None,
+    ///     url: None,
+    ///     as_of: None,
+    ///     post_install: None,
+    ///     variants: None,
+    ///     strip_metadata: None,
+    ///     inline: None,
+    ///     expect_commit: None,
+    ///     enabled: None,
+    ///     git: None,
     /// }));
     /// assert!(local_detailed.is_local());
     /// ```
@@ -1002,6 +1409,17 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: None,
+    ///     env: None,
+    ///     transport: None,
+    ///     url: None,
+    ///     as_of: None,
+    ///     post_install: None,
+    ///     variants: None,
+    ///     strip_metadata: None,
+    ///     inline: None,
+    ///     expect_commit: None,
+    ///     enabled: None,
+    ///     git: None,
     /// }));
     /// assert_eq!(versioned.resolution_mode(), ResolutionMode::Version);
     ///
@@ -1021,6 +1439,17 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: None,
+    ///     env: None,
+    ///     transport: None,
+    ///     url: None,
+    ///     as_of: None,
+    ///     post_install: None,
+    ///     variants: None,
+    ///     strip_metadata: None,
+    ///     inline: None,
+    ///     expect_commit: None,
+    ///     enabled: None,
+    ///     git: None,
     /// }));
     /// assert_eq!(git_ref.resolution_mode(), ResolutionMode::GitRef);
     /// ```
@@ -1060,6 +1489,17 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: None,
+    ///     env: None,
+    ///     transport: None,
+    ///     url: None,
+    ///     as_of: None,
+    ///     post_install: None,
+    ///     variants: None,
+    ///     strip_metadata: None,
+    ///     inline: None,
+    ///     expect_commit: None,
+    ///     enabled: None,
+    ///     git: None,
     /// }));
     /// assert_eq!(versioned.get_version_constraint(), Some("^1.0.0"));
     ///
@@ -1079,6 +1519,17 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: None,
+    ///     env: None,
+    ///     transport: None,
+    ///     url: None,
+    ///     as_of: None,
+    ///     post_install: None,
+    ///     variants: None,
+    ///     strip_metadata: None,
+    ///     inline: None,
+    ///     expect_commit: None,
+    ///     enabled: None,
+    ///     git: None,
     /// }));
     /// assert_eq!(git_ref.get_version_constraint(), None);
     /// ```
@@ -1125,6 +1576,17 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: None,
+    ///     env: None,
+    ///     transport: None,
+    ///     url: None,
+    ///     as_of: None,
+    ///     post_install: None,
+    ///     variants: None,
+    ///     strip_metadata: None,
+    ///     inline: None,
+    ///     expect_commit: None,
+    ///     enabled: None,
+    ///     git: None,
     /// }));
     /// assert_eq!(branch_ref.get_git_ref(), Some("main"));
     ///
@@ -1144,6 +1606,17 @@ impl ResourceDependency {
     ///     flatten: None,
     ///     install: None,
     ///     template_vars: None,
+    ///     env: None,
+    ///     transport: None,
+    ///     url: None,
+    ///     as_of: None,
+    ///     post_install: None,
+    ///     variants: None,
+    ///     strip_metadata: None,
+    ///     inline: None,
+    ///     expect_commit: None,
+    ///     enabled: None,
+    ///     git: None,
     /// }));
     /// assert_eq!(versioned.get_git_ref(), None);
     /// ```
@@ -1157,4 +1630,31 @@ impl ResourceDependency {
             _ => None,
         }
     }
+
+    /// Get the `as_of` date a branch dependency is pinned to, if any.
+    #[must_use]
+    pub fn get_as_of(&self) -> Option<&str> {
+        match self {
+            Self::Detailed(d) => d.as_of.as_deref(),
+            Self::Simple(_) => None,
+        }
+    }
+
+    /// Get the commit this dependency's version is expected to resolve to, if any.
+    #[must_use]
+    pub fn get_expect_commit(&self) -> Option<&str> {
+        match self {
+            Self::Detailed(d) => d.expect_commit.as_deref(),
+            Self::Simple(_) => None,
+        }
+    }
+
+    /// Whether this dependency is enabled. Defaults to `true`.
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        match self {
+            Self::Detailed(d) => d.enabled.unwrap_or(true),
+            Self::Simple(_) => true,
+        }
+    }
 }
@@ -0,0 +1,482 @@
+//! Source repository declarations for the `[sources]` manifest section.
+//!
+//! A source is usually declared with just its URL. Sources that need extra
+//! Git behavior use the detailed form, an inline table with a `url` key:
+//!
+//! ```toml
+//! [sources]
+//! community = "https://github.com/aig787/agpm-community.git"
+//! shared = { url = "https://github.com/org/shared.git", submodules = true }
+//! agents = { url = "https://github.com/org/agents.git", tag_prefix = "agents-" }
+//! vendor = { url = "https://github.com/vendor/agents.git", namespace = "vendor" }
+//! monorepo = { url = "https://github.com/org/monorepo.git", resumable = true }
+//! signed = { url = "https://github.com/org/signed.git", verify_signatures = true, allowed_signers = ".agpm/allowed_signers" }
+//! checkout = "dir://../shared-resources"
+//! ```
+//!
+//! A `dir://` source is a plain directory rather than a Git repository. It is
+//! read in place, with no clone or worktree, and dependency versions are
+//! ignored. Relative paths are resolved against the manifest directory.
+//!
+//! [`SourceMap`] dereferences to the `name -> URL` map, so code that only
+//! needs URLs is unaffected by the detailed form. Per-source settings are
+//! available through [`SourceMap::options`].
+
+use serde::de::Deserializer;
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+
+use crate::version::tag_filter::TagFilter;
+
+/// Optional per-source settings from the detailed `[sources]` form.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceOptions {
+    /// Require Git submodules (recursively) in every worktree checked out
+    /// from this source. Every worktree gets a best-effort submodule
+    /// initialization; with this set, failures are reported instead of ignored.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub submodules: bool,
+
+    /// Only tags starting with this prefix are versions of this source; the
+    /// prefix is stripped before comparing against version constraints.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag_prefix: Option<String>,
+
+    /// Regex selecting the tags that are versions of this source. The capture
+    /// group named `version`, or else the first group, is compared against
+    /// version constraints. Cannot be combined with `tag_prefix`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag_pattern: Option<String>,
+
+    /// Install this source's file-based resources into a `<namespace>/`
+    /// subdirectory of each resource directory, so resources with the same
+    /// filename from different sources do not collide.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+
+    /// Only fetch this many commits of history when cloning. Versions whose
+    /// commits fall outside the fetched history cannot be resolved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clone_depth: Option<u32>,
+
+    /// Clone in stages so that an interrupted clone is resumed with
+    /// `git fetch` on the next run instead of starting over. Meant for very
+    /// large repositories on unreliable connections.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub resumable: bool,
+
+    /// Require the tags that versions resolve to to carry a valid GPG or SSH
+    /// signature. Branch and commit dependencies are not checked.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub verify_signatures: bool,
+
+    /// SSH allowed signers file used to verify this source's tags, relative to
+    /// the manifest directory. GPG signatures are checked against the user's
+    /// keyring instead.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_signers: Option<String>,
+}
+
+impl SourceOptions {
+    /// Whether all settings have their default values.
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Build the tag filter for `tag_prefix` or `tag_pattern`, if either is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if both are set or the pattern is not a valid regex.
+    pub fn tag_filter(&self) -> anyhow::Result<Option<TagFilter>> {
+        TagFilter::new(self.tag_prefix.as_deref(), self.tag_pattern.as_deref())
+    }
+
+    /// The allowed signers file, resolved against `base_dir` (the manifest directory).
+    #[must_use]
+    pub fn allowed_signers_path(&self, base_dir: Option<&Path>) -> Option<PathBuf> {
+        let path = Path::new(self.allowed_signers.as_deref()?);
+        Some(match base_dir {
+            Some(dir) if path.is_relative() => dir.join(path),
+            _ => path.to_path_buf(),
+        })
+    }
+
+    /// How the source's repository is cloned into the cache.
+    #[must_use]
+    pub const fn clone_strategy(&self) -> crate::git::CloneStrategy {
+        crate::git::CloneStrategy {
+            depth: self.clone_depth,
+            resumable: self.resumable,
+        }
+    }
+}
+
+/// Named source repositories declared in the manifest.
+///
+/// Dereferences to the `name -> URL` map. Settings from the detailed form are
+/// kept alongside and written back out by [`Manifest::save`](super::Manifest::save).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceMap {
+    urls: HashMap<String, String>,
+    options: HashMap<String, SourceOptions>,
+}
+
+impl SourceMap {
+    /// Create an empty source map.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the settings for a source, or the defaults if none were declared.
+    #[must_use]
+    pub fn options(&self, name: &str) -> SourceOptions {
+        self.options.get(name).cloned().unwrap_or_default()
+    }
+
+    /// Set the settings for a source.
+    ///
+    /// Default settings are not stored, so the source is written back in the
+    /// plain `name = "url"` form.
+    pub fn set_options(&mut self, name: &str, options: SourceOptions) {
+        if options.is_default() {
+            self.options.remove(name);
+        } else {
+            self.options.insert(name.to_string(), options);
+        }
+    }
+
+    /// Remove a source together with its settings, returning its URL.
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        self.options.remove(name);
+        self.urls.remove(name)
+    }
+
+    /// Whether no sources are declared.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.urls.is_empty()
+    }
+}
+
+impl Deref for SourceMap {
+    type Target = HashMap<String, String>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.urls
+    }
+}
+
+impl DerefMut for SourceMap {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.urls
+    }
+}
+
+impl From<HashMap<String, String>> for SourceMap {
+    fn from(urls: HashMap<String, String>) -> Self {
+        Self {
+            urls,
+            options: HashMap::new(),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a SourceMap {
+    type Item = (&'a String, &'a String);
+    type IntoIter = std::collections::hash_map::Iter<'a, String, String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.urls.iter()
+    }
+}
+
+/// URL prefix of sources that are plain directories rather than Git repositories.
+pub const DIRECTORY_SOURCE_SCHEME: &str = "dir://";
+
+/// The directory a `dir://` source URL points to, or `None` for other URLs.
+///
+/// Relative paths are resolved against `base_dir` (the manifest directory),
+/// or kept relative to the current directory when there is none.
+#[must_use]
+pub fn directory_source_path(url: &str, base_dir: Option<&Path>) -> Option<PathBuf> {
+    let path = Path::new(url.strip_prefix(DIRECTORY_SOURCE_SCHEME)?);
+    Some(if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        base_dir.unwrap_or_else(|| Path::new(".")).join(path)
+    })
+}
+
+/// Check that a source URL uses a supported scheme.
+///
+/// Environment variables and `~` are expanded first. HTTP(S), Git protocol
+/// (`git://`), SSH (`ssh://` or `git@...`), `file://` and `dir://` URLs are
+/// accepted; plain directory paths are rejected in favor of `dir://`, which
+/// makes the intent explicit.
+///
+/// # Errors
+///
+/// Returns [`AgpmError::ManifestValidationError`](crate::core::AgpmError::ManifestValidationError)
+/// naming the source if the URL is not supported.
+pub fn validate_source_url(name: &str, url: &str) -> anyhow::Result<()> {
+    // Expand environment variables and home directory in URL
+    let expanded_url = super::expand_url(url)?;
+
+    // Check if plain directory path is used as a source
+    if expanded_url.starts_with('/')
+        || expanded_url.starts_with("./")
+        || expanded_url.starts_with("../")
+    {
+        return Err(crate::core::AgpmError::ManifestValidationError {
+            reason: format!(
+                "Plain directory path '{url}' cannot be used as source '{name}'. \n\
+                Use one of:\n\
+                - Remote URL: https://github.com/owner/repo.git\n\
+                - Local git repo: file:///absolute/path/to/repo\n\
+                - Directory that is not a git repo: dir://{url}\n\
+                - Or use direct path dependencies without a source"
+            ),
+        }
+        .into());
+    }
+
+    if !expanded_url.starts_with("http://")
+        && !expanded_url.starts_with("https://")
+        && !expanded_url.starts_with("git://")
+        && !expanded_url.starts_with("ssh://")
+        && !expanded_url.starts_with("git@")
+        && !expanded_url.starts_with("file://")
+        && !expanded_url.starts_with(DIRECTORY_SOURCE_SCHEME)
+        && crate::source::provider::for_url(&expanded_url).is_none()
+    {
+        return Err(crate::core::AgpmError::ManifestValidationError {
+            reason: format!(
+                "Source '{name}' has invalid URL: '{url}'. Must be HTTP(S), git://, SSH (ssh:// or git@...), file:// or dir:// URL"
+            ),
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Whether a URL embeds credentials that would be committed with the manifest.
+///
+/// HTTP(S) URLs may not carry any user information, since access tokens are
+/// commonly passed as the user name. Other URLs may name a user (as in
+/// `git@github.com:org/repo.git`) but not a password.
+#[must_use]
+pub fn url_has_credentials(url: &str) -> bool {
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return false;
+    };
+    let authority = rest.split('/').next().unwrap_or(rest);
+    let Some((userinfo, _host)) = authority.rsplit_once('@') else {
+        return false;
+    };
+    matches!(scheme, "http" | "https") || userinfo.contains(':')
+}
+
+/// Resolve a `[replace]` entry to the URL its source is fetched from.
+///
+/// `target` is a Git URL or a path to a local Git repository; relative paths
+/// are resolved against `base_dir` (the manifest directory) and become
+/// `file://` URLs. The result is validated like any source URL.
+///
+/// # Errors
+///
+/// Returns an error naming the source if the replacement is not a supported URL.
+pub fn replacement_url(
+    name: &str,
+    target: &str,
+    base_dir: Option<&Path>,
+) -> anyhow::Result<String> {
+    let target = match base_dir {
+        Some(dir) if crate::utils::is_local_path(target) && Path::new(target).is_relative() => {
+            dir.join(target).to_string_lossy().into_owned()
+        }
+        _ => target.to_string(),
+    };
+    let url = super::expand_url(&target)?;
+    validate_source_url(name, &url)?;
+    Ok(url)
+}
+
+/// A single `[sources]` entry as written in the manifest.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum SourceEntry {
+    Url(String),
+    Detailed {
+        url: String,
+        #[serde(flatten)]
+        options: SourceOptions,
+    },
+}
+
+impl Serialize for SourceMap {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let sorted: BTreeMap<_, _> = self.urls.iter().collect();
+        let mut map = serializer.serialize_map(Some(sorted.len()))?;
+        for (name, url) in sorted {
+            match self.options.get(name) {
+                Some(options) if !options.is_default() => map.serialize_entry(
+                    name,
+                    &SourceEntry::Detailed {
+                        url: url.clone(),
+                        options: options.clone(),
+                    },
+                )?,
+                _ => map.serialize_entry(name, url)?,
+            }
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for SourceMap {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let entries = HashMap::<String, SourceEntry>::deserialize(deserializer)?;
+        let mut sources = Self::new();
+        for (name, entry) in entries {
+            match entry {
+                SourceEntry::Url(url) => {
+                    sources.urls.insert(name, url);
+                }
+                SourceEntry::Detailed {
+                    url,
+                    options,
+                } => {
+                    sources.set_options(&name, options);
+                    sources.urls.insert(name, url);
+                }
+            }
+        }
+        Ok(sources)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Wrapper {
+        sources: SourceMap,
+    }
+
+    #[test]
+    fn test_parse_simple_and_detailed_sources() {
+        let parsed: Wrapper = toml::from_str(
+            r#"
+            [sources]
+            plain = "https://github.com/org/plain.git"
+            shared = { url = "https://github.com/org/shared.git", submodules = true }
+            agents = { url = "https://github.com/org/agents.git", tag_prefix = "agents-" }
+            vendor = { url = "https://github.com/vendor/agents.git", namespace = "vendor" }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            parsed.sources.get("shared").map(String::as_str),
+            Some("https://github.com/org/shared.git")
+        );
+        assert!(parsed.sources.options("shared").submodules);
+        assert_eq!(parsed.sources.options("agents").tag_prefix.as_deref(), Some("agents-"));
+        assert!(!parsed.sources.options("plain").submodules);
+        assert!(!parsed.sources.options("missing").submodules);
+        assert!(parsed.sources.options("plain").tag_filter().unwrap().is_none());
+        assert_eq!(parsed.sources.options("vendor").namespace.as_deref(), Some("vendor"));
+    }
+
+    #[test]
+    fn test_validate_source_url_schemes() {
+        for url in [
+            "https://github.com/org/agents.git",
+            "git@github.com:org/agents.git",
+            "git://gitserver/repo.git",
+            "git://host/path/repo.git",
+            "ssh://git@host:2222/repo.git",
+            "file:///srv/git/agents.git",
+            "dir://../shared-resources",
+        ] {
+            assert!(validate_source_url("src", url).is_ok(), "{url} should be accepted");
+        }
+
+        let err = validate_source_url("src", "gitserver:repo.git").unwrap_err();
+        assert!(err.to_string().contains("git://"));
+        assert_eq!(
+            crate::manifest::expand_url("git://gitserver/repo.git").unwrap(),
+            "git://gitserver/repo.git"
+        );
+        assert_eq!(
+            crate::manifest::expand_url("ssh://git@host:2222/repo.git").unwrap(),
+            "ssh://git@host:2222/repo.git"
+        );
+    }
+
+    #[test]
+    fn test_directory_source_path() {
+        let base = Path::new("/project");
+        assert_eq!(
+            directory_source_path("dir:///srv/resources", Some(base)),
+            Some(PathBuf::from("/srv/resources"))
+        );
+        assert_eq!(
+            directory_source_path("dir://../shared", Some(base)),
+            Some(PathBuf::from("/project/../shared"))
+        );
+        assert_eq!(directory_source_path("file:///srv/repo.git", Some(base)), None);
+        assert_eq!(crate::manifest::expand_url("dir://../shared").unwrap(), "dir://../shared");
+    }
+
+    #[test]
+    fn test_replacement_url() {
+        let base = Path::new("/work/project");
+        assert_eq!(
+            replacement_url("community", "https://github.com/me/fork.git", Some(base)).unwrap(),
+            "https://github.com/me/fork.git"
+        );
+
+        let local = replacement_url("community", "../agpm-community", Some(base)).unwrap();
+        assert!(local.starts_with("file:///work/project"), "{local}");
+        assert!(local.ends_with("agpm-community"), "{local}");
+
+        let err = replacement_url("community", "not-a-url", Some(base)).unwrap_err();
+        assert!(err.to_string().contains("'community'"));
+    }
+
+    #[test]
+    fn test_serialize_keeps_plain_form_for_default_options() {
+        let mut sources = SourceMap::new();
+        sources.insert("plain".to_string(), "https://github.com/org/plain.git".to_string());
+        sources.insert("shared".to_string(), "https://github.com/org/shared.git".to_string());
+        sources.set_options(
+            "shared",
+            SourceOptions {
+                submodules: true,
+                ..SourceOptions::default()
+            },
+        );
+
+        let toml = toml::to_string(&Wrapper {
+            sources: sources.clone(),
+        })
+        .unwrap();
+        assert!(toml.contains(r#"plain = "https://github.com/org/plain.git""#));
+        assert!(toml.contains("submodules = true"));
+
+        let reparsed: Wrapper = toml::from_str(&toml).unwrap();
+        assert_eq!(reparsed.sources, sources);
+
+        sources.remove("shared");
+        assert!(!sources.options("shared").submodules);
+    }
+}
@@ -48,8 +48,8 @@
 //! ```
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
 // Cached default configuration to avoid repeated allocations
@@ -94,6 +94,14 @@ pub struct ResourceConfig {
     /// If not specified, defaults to `false` (preserve directory structure).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub flatten: Option<bool>,
+
+    /// Default subdirectory of `path` that this resource type installs into.
+    ///
+    /// Applies to dependencies without their own `target`, which still takes
+    /// precedence. For example, `default-target = "community"` on agents
+    /// installs them under `.claude/agents/community/`.
+    #[serde(default, skip_serializing_if = "Option::is_none", rename = "default-target")]
+    pub default_target: Option<String>,
 }
 
 /// Well-known tool types with specific default behaviors.
@@ -154,6 +162,18 @@ impl WellKnownTool {
             WellKnownTool::Generic => true,
         }
     }
+
+    /// Returns the executable whose presence on `PATH` indicates the tool is installed.
+    ///
+    /// `None` for AGPM itself, which is always available, and for custom tools,
+    /// which can only be detected by their directory or a configured command.
+    pub const fn detection_command(self) -> Option<&'static str> {
+        match self {
+            WellKnownTool::ClaudeCode => Some("claude"),
+            WellKnownTool::OpenCode => Some("opencode"),
+            WellKnownTool::Agpm | WellKnownTool::Generic => None,
+        }
+    }
 }
 
 /// Tool configuration (internal deserialization structure).
@@ -181,7 +201,7 @@ struct ArtifactTypeConfigRaw {
 /// Defines how a specific tool (e.g., claude-code, opencode, agpm)
 /// organizes its resources. Each tool has a base directory and
 /// a map of resource types to their subdirectory configurations.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
 pub struct ArtifactTypeConfig {
     /// Base directory for this tool (e.g., ".claude", ".opencode", ".agpm")
     pub path: PathBuf,
@@ -207,13 +227,28 @@ pub struct ArtifactTypeConfig {
 ///
 /// Maps tool type names to their configurations. This replaces the old
 /// `[target]` section and enables multi-tool support.
-#[derive(Debug, Clone, Serialize)]
+///
+/// Built-in tools are always present, so a manifest that only declares a
+/// custom tool can still install resources for `claude-code`, `opencode` and
+/// `agpm`. Built-in tools left at their defaults are not serialized.
+#[derive(Debug, Clone)]
 pub struct ToolsConfig {
     /// Map of tool type name -> configuration
-    #[serde(flatten)]
     pub types: HashMap<String, ArtifactTypeConfig>,
 }
 
+impl Serialize for ToolsConfig {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let defaults = DEFAULT_TOOLS_CONFIG.get_or_init(ToolsConfig::default);
+        let customized: BTreeMap<_, _> = self
+            .types
+            .iter()
+            .filter(|(name, config)| defaults.types.get(*name) != Some(*config))
+            .collect();
+        customized.serialize(serializer)
+    }
+}
+
 /// Custom deserializer that merges user configuration with built-in defaults.
 ///
 /// # Merging Behavior
@@ -227,6 +262,9 @@ pub struct ToolsConfig {
 /// - No default merging occurs (user config used as-is)
 /// - User must provide complete configuration
 ///
+/// Well-known tools that are not mentioned at all keep their built-in
+/// configuration, so declaring a custom tool does not remove them.
+///
 /// # Example
 ///
 /// ```toml
@@ -251,7 +289,7 @@ impl<'de> serde::Deserialize<'de> for ToolsConfig {
         let defaults = DEFAULT_TOOLS_CONFIG.get_or_init(ToolsConfig::default);
 
         // Convert to the final structure, applying tool-specific defaults
-        let types = raw_types
+        let mut types: HashMap<String, ArtifactTypeConfig> = raw_types
             .into_iter()
             .map(|(tool_name, raw_config)| {
                 // Determine the enabled value:
@@ -283,12 +321,73 @@ impl<'de> serde::Deserialize<'de> for ToolsConfig {
             })
             .collect();
 
+        for (tool_name, default_config) in &defaults.types {
+            types.entry(tool_name.clone()).or_insert_with(|| default_config.clone());
+        }
+
         Ok(ToolsConfig {
             types,
         })
     }
 }
 
+impl ToolsConfig {
+    /// Disable every tool except the given ones.
+    ///
+    /// The listed tools keep their own `enabled` setting. The `agpm` tool hosts
+    /// shared snippets and always stays enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming any tool that is not configured.
+    pub fn restrict_to(&mut self, tools: &[String]) -> anyhow::Result<()> {
+        if let Some(unknown) = tools.iter().find(|tool| !self.types.contains_key(tool.as_str())) {
+            let mut known: Vec<_> = self.types.keys().map(String::as_str).collect();
+            known.sort_unstable();
+            anyhow::bail!("Unknown tool '{unknown}'. Configured tools: {}", known.join(", "));
+        }
+
+        for (name, config) in &mut self.types {
+            config.enabled &= name == "agpm" || tools.contains(name);
+        }
+        Ok(())
+    }
+
+    /// Disable enabled tools that do not appear to be in use on this machine.
+    ///
+    /// A tool counts as detected if its base directory exists in `project_dir`
+    /// or its detection command is found on `PATH`. `commands` overrides the
+    /// built-in command of a tool (see [`WellKnownTool::detection_command`]) and
+    /// provides one for custom tools. The `agpm` tool is always detected.
+    ///
+    /// Returns the names of the tools that were disabled, sorted.
+    pub fn disable_undetected(
+        &mut self,
+        project_dir: &Path,
+        commands: &BTreeMap<String, String>,
+    ) -> Vec<String> {
+        let mut disabled = Vec::new();
+        for (name, config) in &mut self.types {
+            if !config.enabled || name == "agpm" || project_dir.join(&config.path).exists() {
+                continue;
+            }
+
+            let command = commands
+                .get(name)
+                .map(String::as_str)
+                .or_else(|| WellKnownTool::from_name(name).detection_command());
+            if command.is_some_and(|command| which::which(command).is_ok()) {
+                continue;
+            }
+
+            config.enabled = false;
+            disabled.push(name.clone());
+        }
+        disabled.sort();
+        disabled
+    }
+}
+
 impl Default for ToolsConfig {
     fn default() -> Self {
         use crate::core::ResourceType;
@@ -302,6 +401,7 @@ impl Default for ToolsConfig {
                 path: Some("agents".to_string()),
                 merge_target: None,
                 flatten: Some(true), // Agents flatten by default
+                default_target: None,
             },
         );
         claude_resources.insert(
@@ -310,6 +410,7 @@ impl Default for ToolsConfig {
                 path: Some("snippets".to_string()),
                 merge_target: None,
                 flatten: Some(false), // Snippets preserve directory structure
+                default_target: None,
             },
         );
         claude_resources.insert(
@@ -318,6 +419,7 @@ impl Default for ToolsConfig {
                 path: Some("commands".to_string()),
                 merge_target: None,
                 flatten: Some(true), // Commands flatten by default
+                default_target: None,
             },
         );
         claude_resources.insert(
@@ -326,6 +428,7 @@ impl Default for ToolsConfig {
                 path: Some("scripts".to_string()),
                 merge_target: None,
                 flatten: Some(false), // Scripts preserve directory structure
+                default_target: None,
             },
         );
         claude_resources.insert(
@@ -334,6 +437,7 @@ impl Default for ToolsConfig {
                 path: None, // Hooks are merged into configuration file
                 merge_target: Some(".claude/settings.local.json".to_string()),
                 flatten: None, // N/A for merge targets
+                default_target: None,
             },
         );
         claude_resources.insert(
@@ -342,6 +446,7 @@ impl Default for ToolsConfig {
                 path: None, // MCP servers are merged into configuration file
                 merge_target: Some(".mcp.json".to_string()),
                 flatten: None, // N/A for merge targets
+                default_target: None,
             },
         );
 
@@ -362,6 +467,7 @@ impl Default for ToolsConfig {
                 path: Some("agent".to_string()), // Singular
                 merge_target: None,
                 flatten: Some(true), // Agents flatten by default
+                default_target: None,
             },
         );
         opencode_resources.insert(
@@ -370,6 +476,7 @@ impl Default for ToolsConfig {
                 path: Some("snippet".to_string()), // Singular
                 merge_target: None,
                 flatten: Some(false), // Snippets preserve directory structure
+                default_target: None,
             },
         );
         opencode_resources.insert(
@@ -378,6 +485,7 @@ impl Default for ToolsConfig {
                 path: Some("command".to_string()), // Singular
                 merge_target: None,
                 flatten: Some(true), // Commands flatten by default
+                default_target: None,
             },
         );
         opencode_resources.insert(
@@ -386,6 +494,7 @@ impl Default for ToolsConfig {
                 path: None, // MCP servers are merged into configuration file
                 merge_target: Some(".opencode/opencode.json".to_string()),
                 flatten: None, // N/A for merge targets
+                default_target: None,
             },
         );
 
@@ -406,6 +515,7 @@ impl Default for ToolsConfig {
                 path: Some("snippets".to_string()),
                 merge_target: None,
                 flatten: Some(false), // Snippets preserve directory structure
+                default_target: None,
             },
         );
 
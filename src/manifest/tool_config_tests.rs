@@ -62,6 +62,7 @@ mod resource_config {
             path: Some("agents".to_string()),
             merge_target: None,
             flatten: Some(true),
+            default_target: None,
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -76,6 +77,7 @@ mod resource_config {
             path: None,
             merge_target: Some(".claude/settings.local.json".to_string()),
             flatten: None,
+            default_target: None,
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -90,6 +92,7 @@ mod resource_config {
             path: None,
             merge_target: None,
             flatten: None,
+            default_target: None,
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -104,6 +107,7 @@ mod resource_config {
             path: Some("agents".to_string()),
             merge_target: None,
             flatten: None,
+            default_target: None,
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -190,11 +194,45 @@ my-custom-tool = { path = ".my-tool", enabled = true, resources = { agents = { p
         assert!(custom_config.enabled);
     }
 
+    #[test]
+    fn test_custom_tool_keeps_built_in_tools() {
+        let toml = r#"
+my-ide = { path = ".my-ide", resources = { agents = { path = "personas" }, hooks = { merge-target = ".my-ide/hooks.json" } } }
+"#;
+
+        let config: ToolsConfig = toml::from_str(toml).unwrap();
+        let defaults = ToolsConfig::default();
+        for tool_name in ["claude-code", "opencode", "agpm"] {
+            assert_eq!(config.types.get(tool_name), defaults.types.get(tool_name));
+        }
+
+        // Only the custom tool is written back
+        let serialized = toml::to_string(&config).unwrap();
+        assert!(serialized.contains("my-ide"));
+        assert!(!serialized.contains("claude-code"));
+        let reparsed: ToolsConfig = toml::from_str(&serialized).unwrap();
+        assert_eq!(reparsed.types, config.types);
+
+        let manifest: crate::manifest::Manifest =
+            toml::from_str(&format!("[tools]\n{toml}")).unwrap();
+        let agent = crate::core::ResourceType::Agent;
+        let hook = crate::core::ResourceType::Hook;
+        assert_eq!(
+            manifest.get_artifact_resource_path("my-ide", agent),
+            Some(PathBuf::from(".my-ide/personas"))
+        );
+        assert_eq!(manifest.get_merge_target("my-ide", hook), Some(".my-ide/hooks.json".into()));
+        assert!(manifest.is_resource_supported("my-ide", agent));
+        assert!(!manifest.is_resource_supported("my-ide", crate::core::ResourceType::Script));
+        assert!(manifest.is_resource_supported("claude-code", agent));
+    }
+
     #[test]
     fn test_deserialize_empty_tools_config() {
         let toml = "";
         let config: ToolsConfig = toml::from_str(toml).unwrap();
-        assert!(config.types.is_empty());
+        // An empty section leaves only the built-in tools
+        assert_eq!(config.types, ToolsConfig::default().types);
     }
 
     #[test]
@@ -250,6 +288,7 @@ mod artifact_type_config {
                 path: Some("agents".to_string()),
                 merge_target: None,
                 flatten: Some(true),
+                default_target: None,
             },
         );
 
@@ -273,6 +312,7 @@ mod artifact_type_config {
                 path: Some("agents".to_string()),
                 merge_target: None,
                 flatten: Some(true),
+                default_target: None,
             },
         );
 
@@ -448,3 +488,46 @@ test-tool = { path = ".test", enabled = true, resources = { agents = { path = "a
         }
     }
 }
+
+mod tool_selection {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_restrict_to_keeps_agpm_and_rejects_unknown_tools() {
+        let mut tools = ToolsConfig::default();
+        tools.restrict_to(&["opencode".to_string()]).unwrap();
+
+        assert!(!tools.types["claude-code"].enabled);
+        assert!(tools.types["opencode"].enabled);
+        assert!(tools.types["agpm"].enabled);
+
+        // A listed tool that is disabled stays disabled
+        tools.types.get_mut("opencode").unwrap().enabled = false;
+        tools.restrict_to(&["opencode".to_string()]).unwrap();
+        assert!(!tools.types["opencode"].enabled);
+
+        let err = tools.restrict_to(&["cursor".to_string()]).unwrap_err();
+        assert!(err.to_string().contains("Unknown tool 'cursor'"));
+    }
+
+    #[test]
+    fn test_disable_undetected_uses_directories_and_commands() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join(".claude")).unwrap();
+
+        // Point detection commands at executables that cannot exist
+        let commands: BTreeMap<String, String> = ["claude-code", "opencode"]
+            .into_iter()
+            .map(|tool| (tool.to_string(), "agpm-missing-detection-command".to_string()))
+            .collect();
+
+        let mut tools = ToolsConfig::default();
+        let disabled = tools.disable_undetected(temp.path(), &commands);
+
+        assert_eq!(disabled, vec!["opencode".to_string()]);
+        assert!(tools.types["claude-code"].enabled, "detected by its .claude directory");
+        assert!(!tools.types["opencode"].enabled);
+        assert!(tools.types["agpm"].enabled);
+    }
+}
@@ -1198,6 +1198,71 @@ pub fn list_markdown_files(dir: &Path) -> Result<Vec<std::path::PathBuf>> {
     Ok(files)
 }
 
+/// Top-level frontmatter keys that only AGPM reads.
+const AGPM_FRONTMATTER_KEYS: &[&str] = &["dependencies", "agpm"];
+
+/// Remove AGPM-managed keys from a Markdown file's YAML frontmatter.
+///
+/// Drops the top-level `dependencies` and `agpm` keys, including their nested
+/// values, and leaves every other line of the document untouched. The rest of
+/// the frontmatter is kept as written rather than re-serialized, so comments,
+/// key order and quoting survive. When no other keys remain, the frontmatter
+/// block is removed entirely.
+///
+/// Content without frontmatter, or whose frontmatter has none of these keys,
+/// is returned unchanged.
+///
+/// # Examples
+///
+/// ```rust
+/// # use agpm_cli::markdown::strip_agpm_metadata;
+/// let content = "---\nname: reviewer\ndependencies:\n  snippets:\n    - path: style.md\n---\n# Reviewer\n";
+/// assert_eq!(strip_agpm_metadata(content), "---\nname: reviewer\n---\n# Reviewer\n");
+/// ```
+#[must_use]
+pub fn strip_agpm_metadata(content: &str) -> String {
+    let parser = frontmatter::FrontmatterParser::new();
+    let Some(boundaries) = parser.get_frontmatter_boundaries(content) else {
+        return content.to_string();
+    };
+    let lines: Vec<&str> =
+        content[boundaries.start..boundaries.end].split_inclusive('\n').collect();
+    let [opening, yaml @ .., closing] = lines.as_slice() else {
+        return content.to_string();
+    };
+
+    let mut kept = String::new();
+    let mut stripping = false;
+    let mut changed = false;
+    for line in yaml {
+        // A new top-level entry starts at column 0; indented lines, sequence
+        // items and blank lines continue the previous one
+        let top_level = !line.trim().is_empty()
+            && !line.starts_with(char::is_whitespace)
+            && !line.starts_with('-');
+        if top_level {
+            let key = line.split(':').next().unwrap_or_default().trim().trim_matches(['"', '\'']);
+            stripping = AGPM_FRONTMATTER_KEYS.contains(&key);
+        }
+        if stripping {
+            changed = true;
+        } else {
+            kept.push_str(line);
+        }
+    }
+    if !changed {
+        return content.to_string();
+    }
+
+    let before = &content[..boundaries.start];
+    let after = &content[boundaries.end..];
+    if kept.trim().is_empty() {
+        format!("{before}{}", after.trim_start_matches(['\r', '\n']))
+    } else {
+        format!("{before}{opening}{kept}{closing}{after}")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1414,4 +1479,22 @@ Content here"#;
             }
         }
     }
+
+    #[test]
+    fn test_strip_agpm_metadata() {
+        let input = "---\nname: reviewer\ndependencies:\n  snippets:\n    - path: style.md\n\n# Keep this comment\nagpm:\n  templating: true\ntools: [Read]\n---\n\n# Reviewer\n";
+        assert_eq!(
+            strip_agpm_metadata(input),
+            "---\nname: reviewer\n# Keep this comment\ntools: [Read]\n---\n\n# Reviewer\n"
+        );
+
+        // Only AGPM keys: the whole frontmatter block goes
+        let input = "---\ndependencies:\n  agents:\n  - path: helper.md\n---\n\nBody\n";
+        assert_eq!(strip_agpm_metadata(input), "Body\n");
+
+        // Nothing to strip
+        let input = "---\nname: plain\n---\nBody\n";
+        assert_eq!(strip_agpm_metadata(input), input);
+        assert_eq!(strip_agpm_metadata("No frontmatter\n"), "No frontmatter\n");
+    }
 }
@@ -1,6 +1,6 @@
 use crate::mcp::models::{McpConfig, McpServerConfig};
 use anyhow::{Context, Result};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 impl McpConfig {
@@ -118,6 +118,19 @@ impl McpConfig {
             .retain(|_, config| config.agpm_metadata.as_ref().is_none_or(|meta| !meta.managed));
     }
 
+    /// Remove the named AGPM-managed servers.
+    ///
+    /// User-managed servers are never removed, even if their name matches.
+    /// Returns the number of servers removed.
+    pub fn remove_managed_servers(&mut self, names: &HashSet<String>) -> usize {
+        let before = self.mcp_servers.len();
+        self.mcp_servers.retain(|name, config| {
+            !(names.contains(name)
+                && config.agpm_metadata.as_ref().is_some_and(|meta| meta.managed))
+        });
+        before - self.mcp_servers.len()
+    }
+
     /// Get all AGPM-managed servers.
     #[must_use]
     pub fn get_managed_servers(&self) -> HashMap<String, &McpServerConfig> {
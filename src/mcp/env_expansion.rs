@@ -0,0 +1,163 @@
+//! Environment variable injection for MCP server configurations.
+//!
+//! MCP server dependencies may declare an `env` mapping in `agpm.toml` whose values
+//! reference environment variables (`${OPENAI_API_KEY}`, `${LOG_LEVEL:-info}`). The
+//! references are expanded here, at install time, right before the server is merged
+//! into the tool's configuration file. Expanded values are never stored in the
+//! lockfile, so secrets stay out of version control.
+
+use crate::mcp::models::McpServerConfig;
+use anyhow::{Result, anyhow};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::LazyLock;
+
+/// Matches `${VAR}` and `${VAR:-default}` references.
+static ENV_REFERENCE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)(?::-([^}]*))?\}")
+        .expect("environment reference regex is valid")
+});
+
+/// Expand `${VAR}` and `${VAR:-default}` references in `value`.
+///
+/// Variables are resolved through `lookup`, which allows tests to supply values
+/// without touching the process environment. Unset variables without a default
+/// produce an error naming the variable and the `env` key that referenced it.
+pub fn expand_env_references<F>(value: &str, key: &str, lookup: F) -> Result<String>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut expanded = String::with_capacity(value.len());
+    let mut last = 0;
+
+    for captures in ENV_REFERENCE.captures_iter(value) {
+        let whole = captures.get(0).expect("capture group 0 always exists");
+        let var_name = &captures[1];
+
+        let resolved = match (lookup(var_name), captures.get(2)) {
+            (Some(resolved), _) => resolved,
+            (None, Some(default)) => default.as_str().to_string(),
+            (None, None) => {
+                return Err(anyhow!(
+                    "Environment variable '{var_name}' referenced by env.{key} is not set\n\
+                     Set it before installing, or provide a default with ${{{var_name}:-default}}"
+                ));
+            }
+        };
+
+        expanded.push_str(&value[last..whole.start()]);
+        expanded.push_str(&resolved);
+        last = whole.end();
+    }
+
+    expanded.push_str(&value[last..]);
+    Ok(expanded)
+}
+
+/// Apply manifest `env` overrides to an MCP server configuration.
+///
+/// Each value is expanded with [`expand_env_references`] and inserted into the
+/// server's `env` block, replacing any entry with the same key.
+///
+/// # Errors
+///
+/// Returns an error naming the server if a referenced variable is unset and has
+/// no default.
+pub fn apply_env_overrides<F>(
+    config: &mut McpServerConfig,
+    env: &BTreeMap<String, String>,
+    server_name: &str,
+    lookup: F,
+) -> Result<()>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    if env.is_empty() {
+        return Ok(());
+    }
+
+    let server_env = config.env.get_or_insert_with(HashMap::new);
+    for (key, value) in env {
+        let expanded = expand_env_references(value, key, &lookup)
+            .map_err(|e| anyhow!("Failed to configure MCP server '{server_name}': {e}"))?;
+        server_env.insert(key.clone(), Value::String(expanded));
+    }
+
+    Ok(())
+}
+
+/// Look up a variable in the process environment.
+pub fn process_env_lookup(var_name: &str) -> Option<String> {
+    std::env::var(var_name).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(var_name: &str) -> Option<String> {
+        match var_name {
+            "API_KEY" => Some("secret".to_string()),
+            "REGION" => Some("eu".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_expand_env_references() {
+        assert_eq!(expand_env_references("${API_KEY}", "KEY", lookup).unwrap(), "secret");
+        assert_eq!(
+            expand_env_references("https://${REGION}.example.com/${API_KEY}", "URL", lookup)
+                .unwrap(),
+            "https://eu.example.com/secret"
+        );
+        assert_eq!(expand_env_references("plain", "KEY", lookup).unwrap(), "plain");
+    }
+
+    #[test]
+    fn test_expand_env_references_default() {
+        assert_eq!(expand_env_references("${MISSING:-info}", "LEVEL", lookup).unwrap(), "info");
+        assert_eq!(expand_env_references("${MISSING:-}", "LEVEL", lookup).unwrap(), "");
+        // Set variables win over defaults
+        assert_eq!(expand_env_references("${REGION:-us}", "REGION", lookup).unwrap(), "eu");
+    }
+
+    #[test]
+    fn test_expand_env_references_unset() {
+        let err = expand_env_references("${MISSING}", "TOKEN", lookup).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("'MISSING'"));
+        assert!(message.contains("env.TOKEN"));
+    }
+
+    #[test]
+    fn test_apply_env_overrides() {
+        let mut config: McpServerConfig = serde_json::from_value(serde_json::json!({
+            "command": "npx",
+            "env": { "API_KEY": "placeholder", "NODE_ENV": "production" }
+        }))
+        .unwrap();
+
+        let env = BTreeMap::from([
+            ("API_KEY".to_string(), "${API_KEY}".to_string()),
+            ("LOG_LEVEL".to_string(), "${LOG_LEVEL:-debug}".to_string()),
+        ]);
+        apply_env_overrides(&mut config, &env, "openai", lookup).unwrap();
+
+        let server_env = config.env.unwrap();
+        assert_eq!(server_env["API_KEY"], "secret");
+        assert_eq!(server_env["LOG_LEVEL"], "debug");
+        assert_eq!(server_env["NODE_ENV"], "production");
+    }
+
+    #[test]
+    fn test_apply_env_overrides_unset_names_server() {
+        let mut config: McpServerConfig =
+            serde_json::from_value(serde_json::json!({ "command": "npx" })).unwrap();
+        let env = BTreeMap::from([("TOKEN".to_string(), "${MISSING}".to_string())]);
+
+        let err = apply_env_overrides(&mut config, &env, "github", lookup).unwrap_err();
+        assert!(err.to_string().contains("MCP server 'github'"));
+    }
+}
@@ -5,6 +5,7 @@
 
 use crate::core::file_error::{FileOperation, FileResultExt};
 use anyhow::{Context, Result};
+use std::collections::HashSet;
 use std::future::Future;
 use std::path::Path;
 use std::pin::Pin;
@@ -69,6 +70,32 @@ pub trait McpHandler: Send + Sync {
     ///
     /// `Ok(())` on success, or an error if the cleanup failed.
     fn clean_mcp_servers(&self, project_root: &Path, artifact_base: &Path) -> Result<()>;
+
+    /// Remove specific managed MCP servers for this handler.
+    ///
+    /// Called when MCP server dependencies are dropped from the lockfile. Only
+    /// AGPM-managed entries whose names appear in `server_names` are removed;
+    /// user-managed servers are always preserved.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_root` - The project root directory
+    /// * `artifact_base` - The base directory for this tool
+    /// * `server_names` - Names of the servers to remove (manifest alias or canonical name)
+    ///
+    /// # Returns
+    ///
+    /// The number of servers removed. The default implementation removes
+    /// nothing, so handlers without targeted removal keep stale entries until
+    /// [`clean_mcp_servers`](Self::clean_mcp_servers) runs.
+    fn remove_mcp_servers(
+        &self,
+        _project_root: &Path,
+        _artifact_base: &Path,
+        _server_names: &HashSet<String>,
+    ) -> Result<usize> {
+        Ok(0)
+    }
 }
 
 /// MCP handler for Claude Code.
@@ -147,12 +174,12 @@ impl McpHandler for ClaudeCodeMcpHandler {
                         worktree.join(&entry.path)
                     }
                 } else {
-                    // Local file - resolve relative to project root
+                    // Local file - resolve relative to the manifest directory
                     let candidate = Path::new(&entry.path);
                     if candidate.is_absolute() {
                         candidate.to_path_buf()
                     } else {
-                        project_root.join(candidate)
+                        manifest.manifest_dir.as_deref().unwrap_or(&project_root).join(candidate)
                     }
                 };
 
@@ -188,11 +215,26 @@ impl McpHandler for ClaudeCodeMcpHandler {
                 // Collect applied patches for this server
                 all_applied_patches.push((entry.name.clone(), applied_patches));
 
-                // Parse the patched JSON
-                let mut config: super::McpServerConfig = serde_json::from_str(&patched_content)
-                    .with_context(|| {
-                        format!("Failed to parse MCP server JSON from {}", source_path.display())
-                    })?;
+                // Parse the patched JSON, apply manifest transport overrides, and check
+                // it has the fields its transport needs
+                let manifest_dep = manifest.mcp_servers.get(entry.lookup_name());
+                let mut config = super::validation::parse_mcp_server_config(
+                    &patched_content,
+                    &source_path,
+                    entry.lookup_name(),
+                    manifest_dep,
+                )?;
+
+                // Inject manifest-provided environment variables (expanded at install time,
+                // never recorded in the lockfile)
+                if let Some(env) = manifest_dep.and_then(|dep| dep.get_env()) {
+                    super::env_expansion::apply_env_overrides(
+                        &mut config,
+                        env,
+                        entry.lookup_name(),
+                        super::env_expansion::process_env_lookup,
+                    )?;
+                }
 
                 // Add AGPM metadata
                 config.agpm_metadata = Some(super::AgpmMetadata {
@@ -219,6 +261,15 @@ impl McpHandler for ClaudeCodeMcpHandler {
         // Use existing clean_mcp_servers function
         super::clean_mcp_servers(project_root)
     }
+
+    fn remove_mcp_servers(
+        &self,
+        project_root: &Path,
+        _artifact_base: &Path,
+        server_names: &HashSet<String>,
+    ) -> Result<usize> {
+        super::remove_mcp_servers(&project_root.join(".mcp.json"), server_names)
+    }
 }
 
 /// MCP handler for OpenCode.
@@ -299,12 +350,12 @@ impl McpHandler for OpenCodeMcpHandler {
                         worktree.join(&entry.path)
                     }
                 } else {
-                    // Local file - resolve relative to project root
+                    // Local file - resolve relative to the manifest directory
                     let candidate = Path::new(&entry.path);
                     if candidate.is_absolute() {
                         candidate.to_path_buf()
                     } else {
-                        project_root.join(candidate)
+                        manifest.manifest_dir.as_deref().unwrap_or(&project_root).join(candidate)
                     }
                 };
 
@@ -340,11 +391,26 @@ impl McpHandler for OpenCodeMcpHandler {
                 // Collect applied patches for this server
                 all_applied_patches.push((entry.name.clone(), applied_patches));
 
-                // Parse the patched JSON
-                let mut config: super::McpServerConfig = serde_json::from_str(&patched_content)
-                    .with_context(|| {
-                        format!("Failed to parse MCP server JSON from {}", source_path.display())
-                    })?;
+                // Parse the patched JSON, apply manifest transport overrides, and check
+                // it has the fields its transport needs
+                let manifest_dep = manifest.mcp_servers.get(entry.lookup_name());
+                let mut config = super::validation::parse_mcp_server_config(
+                    &patched_content,
+                    &source_path,
+                    entry.lookup_name(),
+                    manifest_dep,
+                )?;
+
+                // Inject manifest-provided environment variables (expanded at install time,
+                // never recorded in the lockfile)
+                if let Some(env) = manifest_dep.and_then(|dep| dep.get_env()) {
+                    super::env_expansion::apply_env_overrides(
+                        &mut config,
+                        env,
+                        entry.lookup_name(),
+                        super::env_expansion::process_env_lookup,
+                    )?;
+                }
 
                 // Add AGPM metadata
                 config.agpm_metadata = Some(super::AgpmMetadata {
@@ -431,11 +497,17 @@ impl McpHandler for OpenCodeMcpHandler {
                 }
             }
 
-            // Save the updated configuration
-            crate::utils::write_json_file(&opencode_config_path, &opencode_config, true)
-                .with_context(|| {
-                    format!("Failed to write OpenCode config: {}", opencode_config_path.display())
-                })?;
+            // Save the updated configuration, unless the rendered servers match what is
+            // already there
+            if changed_count > 0 || !opencode_config_path.exists() {
+                crate::utils::write_json_file(&opencode_config_path, &opencode_config, true)
+                    .with_context(|| {
+                        format!(
+                            "Failed to write OpenCode config: {}",
+                            opencode_config_path.display()
+                        )
+                    })?;
+            }
 
             Ok((all_applied_patches, changed_count))
         })
@@ -511,6 +583,48 @@ impl McpHandler for OpenCodeMcpHandler {
 
         Ok(())
     }
+
+    fn remove_mcp_servers(
+        &self,
+        _project_root: &Path,
+        artifact_base: &Path,
+        server_names: &HashSet<String>,
+    ) -> Result<usize> {
+        let opencode_config_path = artifact_base.join("opencode.json");
+        if server_names.is_empty() || !opencode_config_path.exists() {
+            return Ok(0);
+        }
+
+        let mut opencode_config: serde_json::Value =
+            crate::utils::read_json_file(&opencode_config_path).with_context(|| {
+                format!("Failed to read OpenCode config: {}", opencode_config_path.display())
+            })?;
+
+        let mut removed = 0;
+        if let Some(mcp_obj) = opencode_config.get_mut("mcp").and_then(|m| m.as_object_mut()) {
+            let before = mcp_obj.len();
+            mcp_obj.retain(|name, server| {
+                if !server_names.contains(name) {
+                    return true;
+                }
+                // Keep if we can't parse it or it isn't managed (preserve user data)
+                match serde_json::from_value::<super::McpServerConfig>(server.clone()) {
+                    Ok(config) => config.agpm_metadata.as_ref().is_none_or(|meta| !meta.managed),
+                    Err(_) => true,
+                }
+            });
+            removed = before - mcp_obj.len();
+        }
+
+        if removed > 0 {
+            crate::utils::write_json_file(&opencode_config_path, &opencode_config, true)
+                .with_context(|| {
+                    format!("Failed to write OpenCode config: {}", opencode_config_path.display())
+                })?;
+        }
+
+        Ok(removed)
+    }
 }
 
 /// Concrete MCP handler enum for different tools.
@@ -573,6 +687,18 @@ impl McpHandler for ConcreteMcpHandler {
             Self::OpenCode(h) => h.clean_mcp_servers(project_root, artifact_base),
         }
     }
+
+    fn remove_mcp_servers(
+        &self,
+        project_root: &Path,
+        artifact_base: &Path,
+        server_names: &HashSet<String>,
+    ) -> Result<usize> {
+        match self {
+            Self::ClaudeCode(h) => h.remove_mcp_servers(project_root, artifact_base, server_names),
+            Self::OpenCode(h) => h.remove_mcp_servers(project_root, artifact_base, server_names),
+        }
+    }
 }
 
 /// Get the appropriate MCP handler for a tool.
@@ -12,10 +12,12 @@
 
 // Module declarations
 mod config;
+pub mod env_expansion;
 pub mod handlers;
 mod models;
 mod operations;
 mod settings;
+pub mod validation;
 
 #[cfg(test)]
 mod tests;
@@ -24,4 +26,6 @@ mod tests;
 pub use models::{AgpmMetadata, ClaudeSettings, McpConfig, McpServerConfig};
 pub use operations::{
     clean_mcp_servers, configure_mcp_servers, list_mcp_servers, merge_mcp_servers,
+    remove_mcp_servers,
 };
+pub use validation::validate_mcp_server_config;
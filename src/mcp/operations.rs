@@ -1,7 +1,7 @@
 use crate::mcp::models::{AgpmMetadata, McpConfig, McpServerConfig};
 use anyhow::{Context, Result};
 use chrono::Utc;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 /// Merge MCP server configurations into the config file.
@@ -9,6 +9,10 @@ use std::path::Path;
 /// This is a helper function used by MCP handlers to merge server configurations
 /// that have already been read from source files.
 ///
+/// Servers are rendered on every install, since manifest overrides and `${VAR}`
+/// expansion can change them without the lockfile changing. The config file is
+/// only written when a rendered server differs from the one it contains.
+///
 /// Returns the number of servers that actually changed (ignoring timestamps).
 pub async fn merge_mcp_servers(
     mcp_config_path: &Path,
@@ -61,8 +65,17 @@ pub async fn merge_mcp_servers(
     }
 
     // Update MCP configuration with AGPM-managed servers
+    let server_count = mcp_config.mcp_servers.len();
     mcp_config.update_managed_servers(agpm_servers)?;
 
+    // Leave the file untouched if the rendered servers match what is already there
+    if changed_count == 0
+        && mcp_config.mcp_servers.len() == server_count
+        && mcp_config_path.exists()
+    {
+        return Ok(0);
+    }
+
     // Save the updated MCP configuration
     mcp_config.save(mcp_config_path)?;
 
@@ -88,6 +101,8 @@ pub async fn configure_mcp_servers(project_root: &Path, mcp_servers_dir: &Path)
             // Read and parse the MCP server configuration
             let config: McpServerConfig = crate::utils::read_json_file(&path)
                 .with_context(|| format!("Failed to parse MCP server file: {}", path.display()))?;
+            super::validate_mcp_server_config(&config)
+                .with_context(|| format!("Invalid MCP server '{name}' in {}", path.display()))?;
 
             // Add AGPM metadata
             let mut config_with_metadata = config;
@@ -148,6 +163,27 @@ pub fn clean_mcp_servers(project_root: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Remove the named AGPM-managed servers from an MCP configuration file.
+///
+/// Used when dependencies are dropped from the lockfile so stale entries don't
+/// linger. User-managed servers are preserved, and the file is only rewritten
+/// when something was removed.
+///
+/// Returns the number of servers removed.
+pub fn remove_mcp_servers(mcp_config_path: &Path, server_names: &HashSet<String>) -> Result<usize> {
+    if server_names.is_empty() || !mcp_config_path.exists() {
+        return Ok(0);
+    }
+
+    let mut mcp_config = McpConfig::load_or_default(mcp_config_path)?;
+    let removed = mcp_config.remove_managed_servers(server_names);
+    if removed > 0 {
+        mcp_config.save(mcp_config_path)?;
+    }
+
+    Ok(removed)
+}
+
 /// List all MCP servers, indicating which are AGPM-managed.
 pub fn list_mcp_servers(project_root: &Path) -> Result<()> {
     let mcp_config_path = project_root.join(".mcp.json");
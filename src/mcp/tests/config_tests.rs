@@ -422,6 +422,41 @@ fn test_mcp_config_remove_all_managed() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_mcp_config_remove_managed_servers() -> Result<()> {
+    let server = |managed: Option<bool>| McpServerConfig {
+        command: Some("node".to_string()),
+        args: vec![],
+        env: None,
+        r#type: None,
+        url: None,
+        headers: None,
+        agpm_metadata: managed.map(|managed| AgpmMetadata {
+            managed,
+            source: None,
+            version: None,
+            installed_at: "time".to_string(),
+            dependency_name: None,
+        }),
+    };
+
+    let mut config = McpConfig::default();
+    config.mcp_servers.insert("dropped".to_string(), server(Some(true)));
+    config.mcp_servers.insert("kept".to_string(), server(Some(true)));
+    config.mcp_servers.insert("user-same-name".to_string(), server(None));
+
+    let names: std::collections::HashSet<String> =
+        ["dropped".to_string(), "user-same-name".to_string()].into_iter().collect();
+    let removed = config.remove_managed_servers(&names);
+
+    // Only the managed server in the set is removed; user servers are never touched
+    assert_eq!(removed, 1);
+    assert!(!config.mcp_servers.contains_key("dropped"));
+    assert!(config.mcp_servers.contains_key("kept"));
+    assert!(config.mcp_servers.contains_key("user-same-name"));
+    Ok(())
+}
+
 #[test]
 fn test_mcp_config_get_managed_servers() -> Result<()> {
     let mut config = McpConfig::default();
@@ -250,7 +250,8 @@ async fn test_merge_mcp_servers_unchanged_detection() -> Result<()> {
         }
     });
 
-    tokio::fs::write(&config_path, serde_json::to_string_pretty(&initial_config)?).await?;
+    let initial_content = serde_json::to_string_pretty(&initial_config)?;
+    tokio::fs::write(&config_path, &initial_content).await?;
 
     // Create "same" server configuration (only timestamp differs)
     let mut agpm_servers = HashMap::new();
@@ -276,6 +277,8 @@ async fn test_merge_mcp_servers_unchanged_detection() -> Result<()> {
     // Merge should detect no changes (ignoring timestamps)
     let changed_count = merge_mcp_servers(&config_path, agpm_servers).await?;
     assert_eq!(changed_count, 0, "Should detect no changes when only timestamp differs");
+    // The config file is left untouched
+    assert_eq!(tokio::fs::read_to_string(&config_path).await?, initial_content);
     Ok(())
 }
 
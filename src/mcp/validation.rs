@@ -0,0 +1,320 @@
+//! Structural validation for MCP server configurations.
+//!
+//! A malformed `mcp-servers/*.json` file would otherwise only fail when the tool
+//! (Claude Code, OpenCode) tries to start the server. These checks run before a
+//! server is merged into the tool's configuration file, and from
+//! `agpm validate --mcp`, so bad configurations surface at resolve time.
+//!
+//! The expected structure depends on the transport:
+//! - `stdio` (the default when `type` is absent) requires a non-empty `command`;
+//!   a `url` is ignored by the tool and only produces a warning
+//! - `http` and `sse` require a `url`
+//!
+//! A manifest entry may override the transport (`transport = "http"`) and the
+//! server URL (`url = "..."`); [`apply_transport_override`] reshapes the server
+//! configuration before it is validated.
+
+use crate::manifest::ResourceDependency;
+use crate::mcp::models::McpServerConfig;
+use anyhow::{Result, anyhow, bail};
+use std::path::Path;
+
+/// Transports accepted in the `type` field of an MCP server configuration.
+pub const SUPPORTED_TRANSPORTS: &[&str] = &["stdio", "http", "sse"];
+
+/// Check that an MCP server configuration has the fields its transport needs.
+///
+/// # Errors
+///
+/// Returns an error describing the first problem found: a missing `command` for
+/// stdio servers, a missing `url` for HTTP-based servers, or an unknown transport.
+/// A `url` on a stdio server is only logged as a warning, since existing
+/// configurations may carry one that the tool ignores.
+pub fn validate_mcp_server_config(config: &McpServerConfig) -> Result<()> {
+    match config.r#type.as_deref().unwrap_or("stdio") {
+        "stdio" => {
+            if config.command.as_deref().is_none_or(|command| command.trim().is_empty()) {
+                bail!("missing `command` (required for stdio servers)");
+            }
+            if config.url.is_some() {
+                tracing::warn!(
+                    "`url` is ignored for stdio servers \
+                     (use transport \"http\" or \"sse\", or remove `url`)"
+                );
+            }
+        }
+        transport @ ("http" | "sse") => {
+            if config.url.as_deref().is_none_or(|url| url.trim().is_empty()) {
+                bail!("missing `url` (required for {transport} servers)");
+            }
+        }
+        other => {
+            bail!(
+                "unknown transport '{other}' (expected one of: {})",
+                SUPPORTED_TRANSPORTS.join(", ")
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that a manifest `transport`/`url` override is self-consistent.
+///
+/// This only looks at the manifest fields; whether a `url`-only override fits the
+/// server's own transport is checked by [`apply_transport_override`].
+///
+/// # Errors
+///
+/// Returns an error for an unknown transport or a `url` combined with `stdio`.
+pub fn validate_transport_override(transport: Option<&str>, url: Option<&str>) -> Result<()> {
+    match transport {
+        Some(transport) if !SUPPORTED_TRANSPORTS.contains(&transport) => {
+            bail!(
+                "unknown transport '{transport}' (expected one of: {})",
+                SUPPORTED_TRANSPORTS.join(", ")
+            );
+        }
+        Some("stdio") if url.is_some() => {
+            bail!("`url` cannot be used with transport \"stdio\" (use \"http\" or \"sse\")");
+        }
+        _ => {}
+    }
+
+    if url.is_some_and(|url| url.trim().is_empty()) {
+        bail!("`url` must not be empty");
+    }
+
+    Ok(())
+}
+
+/// Apply a manifest `transport`/`url` override to an MCP server configuration.
+///
+/// Switching to `http` or `sse` drops `command` and `args`; switching to `stdio`
+/// drops `url` and `headers`, so the merged entry has the shape the tool expects.
+/// A `url` override without a `transport` is only accepted when the server is
+/// already HTTP-based.
+///
+/// # Errors
+///
+/// Returns an error if the override is inconsistent (see
+/// [`validate_transport_override`]) or sets a `url` on a stdio server.
+pub fn apply_transport_override(
+    config: &mut McpServerConfig,
+    transport: Option<&str>,
+    url: Option<&str>,
+) -> Result<()> {
+    validate_transport_override(transport, url)?;
+
+    match transport {
+        Some("stdio") => {
+            config.r#type = Some("stdio".to_string());
+            config.url = None;
+            config.headers = None;
+        }
+        Some(transport) => {
+            config.r#type = Some(transport.to_string());
+            config.command = None;
+            config.args.clear();
+        }
+        None => {
+            if url.is_some() && matches!(config.r#type.as_deref(), None | Some("stdio")) {
+                bail!(
+                    "`url` override requires an HTTP-based server, but this server uses stdio \
+                     (add transport = \"http\" or transport = \"sse\")"
+                );
+            }
+        }
+    }
+
+    if let Some(url) = url {
+        config.url = Some(url.to_string());
+    }
+
+    Ok(())
+}
+
+/// Parse and validate the JSON content of an MCP server file.
+///
+/// When `dependency` is the server's manifest entry, its `transport`/`url`
+/// overrides are applied before validation. Errors name both the resource and
+/// the source file so the offending configuration can be located without
+/// installing.
+pub fn parse_mcp_server_config(
+    content: &str,
+    source_path: &Path,
+    server_name: &str,
+    dependency: Option<&ResourceDependency>,
+) -> Result<McpServerConfig> {
+    let mut config: McpServerConfig = serde_json::from_str(content).map_err(|e| {
+        anyhow!(
+            "Invalid MCP server '{server_name}' in {}: failed to parse JSON: {e}",
+            source_path.display()
+        )
+    })?;
+
+    if let Some(dep) = dependency {
+        apply_transport_override(&mut config, dep.get_transport(), dep.get_url()).map_err(|e| {
+            anyhow!("Invalid transport override for MCP server '{server_name}': {e}")
+        })?;
+    }
+
+    validate_mcp_server_config(&config).map_err(|e| {
+        anyhow!("Invalid MCP server '{server_name}' in {}: {e}", source_path.display())
+    })?;
+
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config(value: serde_json::Value) -> McpServerConfig {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_validate_stdio_server() {
+        assert!(validate_mcp_server_config(&config(json!({"command": "npx"}))).is_ok());
+        assert!(
+            validate_mcp_server_config(&config(json!({"type": "stdio", "command": "uvx"}))).is_ok()
+        );
+
+        let err = validate_mcp_server_config(&config(json!({"args": ["server"]}))).unwrap_err();
+        assert!(err.to_string().contains("missing `command`"));
+
+        let err = validate_mcp_server_config(&config(json!({"command": "  "}))).unwrap_err();
+        assert!(err.to_string().contains("missing `command`"));
+    }
+
+    #[test]
+    fn test_validate_http_server() {
+        assert!(
+            validate_mcp_server_config(&config(
+                json!({"type": "http", "url": "https://example.com/mcp"})
+            ))
+            .is_ok()
+        );
+
+        let err = validate_mcp_server_config(&config(json!({"type": "sse"}))).unwrap_err();
+        assert!(err.to_string().contains("missing `url` (required for sse servers)"));
+    }
+
+    #[test]
+    fn test_validate_unknown_transport() {
+        let err = validate_mcp_server_config(&config(json!({"type": "websocket", "url": "x"})))
+            .unwrap_err();
+        assert!(err.to_string().contains("unknown transport 'websocket'"));
+    }
+
+    #[test]
+    fn test_parse_mcp_server_config_names_source() {
+        let path = Path::new("mcp-servers/broken.json");
+
+        let err = parse_mcp_server_config(r#"{"args": []}"#, path, "broken", None).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("'broken'"));
+        assert!(message.contains("mcp-servers/broken.json"));
+        assert!(message.contains("missing `command`"));
+
+        let err = parse_mcp_server_config("{not json", path, "broken", None).unwrap_err();
+        assert!(err.to_string().contains("failed to parse JSON"));
+
+        let parsed = parse_mcp_server_config(r#"{"command": "npx"}"#, path, "ok", None).unwrap();
+        assert_eq!(parsed.command.as_deref(), Some("npx"));
+    }
+
+    #[test]
+    fn test_validate_stdio_server_with_url() {
+        assert!(
+            validate_mcp_server_config(&config(
+                json!({"command": "npx", "url": "http://localhost:3000"}),
+            ))
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_parse_existing_stdio_config_with_url() {
+        // An existing configuration written before transports were validated
+        let content = r#"{
+            "command": "npx",
+            "args": ["-y", "@modelcontextprotocol/server-filesystem", "/tmp"],
+            "env": {"NODE_ENV": "production"},
+            "url": "http://localhost:3000"
+        }"#;
+        let server =
+            parse_mcp_server_config(content, Path::new("mcp/fs.json"), "fs", None).unwrap();
+        assert_eq!(server.command.as_deref(), Some("npx"));
+        assert_eq!(server.url.as_deref(), Some("http://localhost:3000"));
+    }
+
+    #[test]
+    fn test_validate_transport_override() {
+        assert!(validate_transport_override(None, None).is_ok());
+        assert!(validate_transport_override(Some("http"), Some("http://localhost")).is_ok());
+        assert!(validate_transport_override(Some("stdio"), None).is_ok());
+
+        let err = validate_transport_override(Some("grpc"), None).unwrap_err();
+        assert!(err.to_string().contains("unknown transport 'grpc'"));
+
+        let err = validate_transport_override(Some("stdio"), Some("http://x")).unwrap_err();
+        assert!(err.to_string().contains("cannot be used with transport \"stdio\""));
+    }
+
+    #[test]
+    fn test_apply_transport_override_to_http() {
+        let mut server = config(json!({"command": "npx", "args": ["-y", "fs-server"]}));
+        apply_transport_override(&mut server, Some("http"), Some("http://localhost:3000")).unwrap();
+
+        assert_eq!(server.r#type.as_deref(), Some("http"));
+        assert_eq!(server.url.as_deref(), Some("http://localhost:3000"));
+        assert!(server.command.is_none());
+        assert!(server.args.is_empty());
+        assert!(validate_mcp_server_config(&server).is_ok());
+    }
+
+    #[test]
+    fn test_apply_transport_override_to_stdio() {
+        let mut server = config(json!({
+            "command": "npx",
+            "type": "sse",
+            "url": "http://localhost:3000/sse",
+            "headers": {"Authorization": "token"}
+        }));
+        apply_transport_override(&mut server, Some("stdio"), None).unwrap();
+
+        assert_eq!(server.r#type.as_deref(), Some("stdio"));
+        assert!(server.url.is_none());
+        assert!(server.headers.is_none());
+        assert!(validate_mcp_server_config(&server).is_ok());
+    }
+
+    #[test]
+    fn test_apply_url_override() {
+        let mut server = config(json!({"type": "http", "url": "http://localhost:3000"}));
+        apply_transport_override(&mut server, None, Some("https://mcp.example.com")).unwrap();
+        assert_eq!(server.url.as_deref(), Some("https://mcp.example.com"));
+
+        // A url on a stdio server is a mismatch
+        let mut server = config(json!({"command": "npx"}));
+        let err = apply_transport_override(&mut server, None, Some("http://x")).unwrap_err();
+        assert!(err.to_string().contains("add transport = \"http\""));
+    }
+
+    #[test]
+    fn test_parse_mcp_server_config_http_override_without_url() {
+        let dep: ResourceDependency =
+            toml::from_str("path = \"mcp/fs.json\"\ntransport = \"http\"").unwrap();
+        let err = parse_mcp_server_config(
+            r#"{"command": "npx"}"#,
+            Path::new("mcp/fs.json"),
+            "fs",
+            Some(&dep),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("missing `url` (required for http servers)"));
+    }
+}
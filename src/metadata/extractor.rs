@@ -20,9 +20,13 @@ use serde_json::Value as JsonValue;
 use std::path::Path;
 
 use crate::core::OperationContext;
-use crate::manifest::{DependencyMetadata, dependency_spec::AgpmMetadata};
+use crate::manifest::{DependencyMetadata, DependencySpec, dependency_spec::AgpmMetadata};
 use crate::markdown::frontmatter::FrontmatterParser;
 
+/// Resource type names accepted as dependency section headers.
+const VALID_RESOURCE_TYPES: &[&str] =
+    &["agents", "commands", "snippets", "hooks", "mcp-servers", "scripts"];
+
 /// Metadata extractor for resource files.
 ///
 /// Extracts dependency information embedded in resource files:
@@ -74,12 +78,57 @@ impl MetadataExtractor {
         content: &str,
         variant_inputs: Option<&serde_json::Value>,
         context: Option<&OperationContext>,
+    ) -> Result<DependencyMetadata> {
+        Self::extract_with_keys(path, content, variant_inputs, &[], context)
+    }
+
+    /// Extract dependency metadata, also reading additional frontmatter keys.
+    ///
+    /// Behaves like [`extract`](Self::extract), then reads each key in
+    /// `metadata_keys` (the manifest's `metadata_keys`) as a further source of
+    /// dependencies. `dependencies` itself is always read first; additional
+    /// keys are read in order and cannot redeclare a path seen earlier.
+    ///
+    /// Additional keys accept a list of paths or dependency objects, with the
+    /// resource type taken from the path (`snippets/utils.md`), or a mapping
+    /// by resource type like `dependencies`. Entries that cannot be understood
+    /// are skipped with a warning instead of failing, since these keys usually
+    /// come from files written for other tools.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use agpm_cli::metadata::MetadataExtractor;
+    /// use std::path::Path;
+    ///
+    /// let content = "---\nrequires:\n  - snippets/utils.md\n---\n# Agent";
+    /// let keys = vec!["requires".to_string()];
+    ///
+    /// let metadata =
+    ///     MetadataExtractor::extract_with_keys(Path::new("agent.md"), content, None, &keys, None)
+    ///         .unwrap();
+    /// assert!(metadata.has_dependencies());
+    /// ```
+    pub fn extract_with_keys(
+        path: &Path,
+        content: &str,
+        variant_inputs: Option<&serde_json::Value>,
+        metadata_keys: &[String],
+        context: Option<&OperationContext>,
     ) -> Result<DependencyMetadata> {
         let extension = path.extension().and_then(|s| s.to_str()).unwrap_or("");
 
         match extension {
-            "md" => Self::extract_markdown_frontmatter(content, variant_inputs, path, context),
-            "json" => Self::extract_json_field(content, variant_inputs, path, context),
+            "md" => Self::extract_markdown_frontmatter(
+                content,
+                variant_inputs,
+                metadata_keys,
+                path,
+                context,
+            ),
+            "json" => {
+                Self::extract_json_field(content, variant_inputs, metadata_keys, path, context)
+            }
             _ => {
                 // Scripts and other files don't support embedded dependencies
                 Ok(DependencyMetadata::default())
@@ -94,6 +143,7 @@ impl MetadataExtractor {
     fn extract_markdown_frontmatter(
         content: &str,
         variant_inputs: Option<&serde_json::Value>,
+        metadata_keys: &[String],
         path: &Path,
         context: Option<&OperationContext>,
     ) -> Result<DependencyMetadata> {
@@ -124,7 +174,13 @@ impl MetadataExtractor {
 
             // Validate resource types if we successfully parsed metadata
             Self::validate_resource_types(&dependency_metadata, path)?;
-            Ok(dependency_metadata)
+            Ok(Self::read_metadata_keys(
+                dependency_metadata,
+                |key| markdown_metadata.extra.get(key),
+                metadata_keys,
+                path,
+                context,
+            ))
         } else {
             Ok(DependencyMetadata::default())
         }
@@ -137,6 +193,7 @@ impl MetadataExtractor {
     fn extract_json_field(
         content: &str,
         variant_inputs: Option<&serde_json::Value>,
+        metadata_keys: &[String],
         path: &Path,
         context: Option<&OperationContext>,
     ) -> Result<DependencyMetadata> {
@@ -147,7 +204,7 @@ impl MetadataExtractor {
         let json: JsonValue = serde_json::from_str(&templated_content)
             .with_context(|| "Failed to parse JSON content")?;
 
-        if let Some(deps) = json.get("dependencies") {
+        let metadata = if let Some(deps) = json.get("dependencies") {
             // The dependencies field should match our expected structure
             match serde_json::from_value::<
                 std::collections::BTreeMap<String, Vec<crate::manifest::DependencySpec>>,
@@ -157,7 +214,7 @@ impl MetadataExtractor {
                     let metadata = DependencyMetadata::new(Some(dependencies), None);
                     // Validate resource types (catch tool names used as types)
                     Self::validate_resource_types(&metadata, path)?;
-                    Ok(metadata)
+                    metadata
                 }
                 Err(e) => {
                     // Only warn once per file to avoid spam during transitive dependency resolution
@@ -178,14 +235,134 @@ https://github.com/aig787/agpm#transitive-dependencies",
                             );
                         }
                     }
-                    Ok(DependencyMetadata::default())
+                    DependencyMetadata::default()
                 }
             }
         } else {
-            Ok(DependencyMetadata::default())
+            DependencyMetadata::default()
+        };
+
+        Ok(Self::read_metadata_keys(metadata, |key| json.get(key), metadata_keys, path, context))
+    }
+
+    /// Add the dependencies declared under additional metadata keys.
+    ///
+    /// `lookup` returns the value of a top-level key in the frontmatter or JSON
+    /// document. Paths already declared, under `dependencies` or an earlier
+    /// key, are skipped. Entries that cannot be understood are reported in one
+    /// warning per file and otherwise ignored.
+    fn read_metadata_keys<'a>(
+        metadata: DependencyMetadata,
+        lookup: impl Fn(&str) -> Option<&'a JsonValue>,
+        metadata_keys: &[String],
+        path: &Path,
+        context: Option<&OperationContext>,
+    ) -> DependencyMetadata {
+        let mut declared: std::collections::HashSet<String> = metadata
+            .get_dependencies()
+            .into_iter()
+            .flat_map(|deps| deps.values().flatten().map(|spec| spec.path.clone()))
+            .collect();
+        let mut dependencies = metadata.dependencies.clone().unwrap_or_default();
+        let mut problems = Vec::new();
+        let mut added = false;
+
+        for key in metadata_keys.iter().filter(|key| *key != "dependencies") {
+            let Some(value) = lookup(key) else {
+                continue;
+            };
+
+            let entries: Vec<(Option<&str>, &JsonValue)> = match value {
+                JsonValue::Array(entries) => entries.iter().map(|entry| (None, entry)).collect(),
+                JsonValue::Object(by_type) => {
+                    let mut entries = Vec::new();
+                    for (resource_type, list) in by_type {
+                        match list {
+                            JsonValue::Array(list) => entries.extend(
+                                list.iter().map(|entry| (Some(resource_type.as_str()), entry)),
+                            ),
+                            _ => problems.push(format!("'{key}.{resource_type}' is not a list")),
+                        }
+                    }
+                    entries
+                }
+                _ => {
+                    problems.push(format!("'{key}' is neither a list nor a mapping"));
+                    continue;
+                }
+            };
+
+            for (resource_type, entry) in entries {
+                match Self::parse_metadata_key_entry(resource_type, entry) {
+                    Ok((resource_type, spec)) => {
+                        if declared.insert(spec.path.clone()) {
+                            dependencies.entry(resource_type).or_default().push(spec);
+                            added = true;
+                        }
+                    }
+                    Err(reason) => problems.push(format!("'{key}' entry {entry}: {reason}")),
+                }
+            }
+        }
+
+        if !problems.is_empty() {
+            if context.is_some_and(|ctx| ctx.should_warn_file(path)) {
+                eprintln!(
+                    "Warning: Skipped {} dependency entries in '{}':\n  - {}",
+                    problems.len(),
+                    path.display(),
+                    problems.join("\n  - ")
+                );
+            } else {
+                tracing::debug!(
+                    "Skipped metadata key entries in '{}': {:?}",
+                    path.display(),
+                    problems
+                );
+            }
+        }
+
+        if added {
+            DependencyMetadata::new(Some(dependencies), metadata.agpm.clone())
+        } else {
+            metadata
         }
     }
 
+    /// Parse one entry under an additional metadata key.
+    ///
+    /// An entry is a path string or a dependency object. Without an explicit
+    /// resource type, the type is the first path component naming one, so
+    /// `../snippets/utils.md` is a snippet.
+    fn parse_metadata_key_entry(
+        resource_type: Option<&str>,
+        entry: &JsonValue,
+    ) -> std::result::Result<(String, DependencySpec), String> {
+        let spec: DependencySpec = match entry {
+            JsonValue::String(path) => serde_json::from_value(serde_json::json!({ "path": path }))
+                .map_err(|e| e.to_string())?,
+            JsonValue::Object(_) => {
+                serde_json::from_value(entry.clone()).map_err(|e| e.to_string())?
+            }
+            _ => return Err("expected a path or a dependency object".to_string()),
+        };
+
+        let resource_type = match resource_type {
+            Some(resource_type) => resource_type.to_string(),
+            None => Path::new(&spec.path)
+                .components()
+                .filter_map(|component| component.as_os_str().to_str())
+                .find(|component| VALID_RESOURCE_TYPES.contains(component))
+                .ok_or("cannot tell the resource type from the path")?
+                .to_string(),
+        };
+        if !VALID_RESOURCE_TYPES.contains(&resource_type.as_str()) {
+            return Err(format!("unknown resource type '{resource_type}'"));
+        }
+
+        Ok((resource_type, spec))
+    }
+
     /// Validate that resource type names are correct (not tool names).
     ///
     /// Common mistake: using tool names (claude-code, opencode) as section headers
@@ -199,8 +376,6 @@ https://github.com/aig787/agpm#transitive-dependencies",
     /// * `Ok(())` if validation passes
     /// * `Err` with helpful error message if tool names detected
     fn validate_resource_types(metadata: &DependencyMetadata, file_path: &Path) -> Result<()> {
-        const VALID_RESOURCE_TYPES: &[&str] =
-            &["agents", "commands", "snippets", "hooks", "mcp-servers", "scripts"];
         const TOOL_NAMES: &[&str] = &["claude-code", "opencode", "agpm"];
 
         // Check both root-level and nested dependencies
@@ -248,6 +423,7 @@ https://github.com/aig787/agpm#transitive-dependencies",
             && let Ok(metadata) = Self::extract_markdown_frontmatter(
                 content,
                 None,
+                &[],
                 &PathBuf::from("unknown.md"),
                 None,
             )
@@ -259,7 +435,7 @@ https://github.com/aig787/agpm#transitive-dependencies",
         // Try JSON format
         if content.trim_start().starts_with('{')
             && let Ok(metadata) =
-                Self::extract_json_field(content, None, &PathBuf::from("unknown.json"), None)
+                Self::extract_json_field(content, None, &[], &PathBuf::from("unknown.json"), None)
             && metadata.has_dependencies()
         {
             return Ok(metadata);
@@ -852,4 +1028,85 @@ dependencies:
         assert!(!ctx1.should_warn_file(&path));
         assert!(!ctx2.should_warn_file(&path));
     }
+
+    #[test]
+    fn test_extract_with_requires_key() {
+        let content = r#"---
+title: Third-party agent
+requires:
+  - snippets/utils.md
+  - ../commands/deploy.md
+  - path: agents/helper.md
+    version: v2.0.0
+---
+# Agent"#;
+        let keys = vec!["dependencies".to_string(), "requires".to_string()];
+
+        let metadata =
+            MetadataExtractor::extract_with_keys(Path::new("agent.md"), content, None, &keys, None)
+                .unwrap();
+        let deps = metadata.get_dependencies().unwrap();
+
+        assert_eq!(deps["snippets"][0].path, "snippets/utils.md");
+        assert_eq!(deps["commands"][0].path, "../commands/deploy.md");
+        assert_eq!(deps["agents"][0].path, "agents/helper.md");
+        assert_eq!(deps["agents"][0].version.as_deref(), Some("v2.0.0"));
+
+        // Without the key, `requires` is ordinary frontmatter
+        let metadata =
+            MetadataExtractor::extract(Path::new("agent.md"), content, None, None).unwrap();
+        assert!(!metadata.has_dependencies());
+    }
+
+    #[test]
+    fn test_extract_with_keys_precedence() {
+        let content = r#"---
+dependencies:
+  snippets:
+    - path: snippets/utils.md
+      version: v1.0.0
+requires:
+  snippets:
+    - path: snippets/utils.md
+      version: v2.0.0
+    - snippets/extra.md
+---
+# Agent"#;
+        let keys = vec!["requires".to_string()];
+
+        let metadata =
+            MetadataExtractor::extract_with_keys(Path::new("agent.md"), content, None, &keys, None)
+                .unwrap();
+        let snippets = &metadata.get_dependencies().unwrap()["snippets"];
+
+        // `dependencies` wins for a path declared under both keys
+        assert_eq!(snippets.len(), 2);
+        assert_eq!(snippets[0].path, "snippets/utils.md");
+        assert_eq!(snippets[0].version.as_deref(), Some("v1.0.0"));
+        assert_eq!(snippets[1].path, "snippets/extra.md");
+    }
+
+    #[test]
+    fn test_extract_with_keys_skips_malformed_entries() {
+        let content = r#"{
+  "requires": [
+    "snippets/utils.md",
+    "helper.md",
+    42,
+    {"path": "agents/a.md", "unknown": true}
+  ]
+}"#;
+        let keys = vec!["requires".to_string()];
+        let ctx = OperationContext::new();
+        let path = Path::new("hook.json");
+
+        let metadata =
+            MetadataExtractor::extract_with_keys(path, content, None, &keys, Some(&ctx)).unwrap();
+        let deps = metadata.get_dependencies().unwrap();
+
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps["snippets"][0].path, "snippets/utils.md");
+        // The skipped entries were reported for this file
+        assert!(!ctx.should_warn_file(path));
+    }
 }
@@ -116,6 +116,30 @@ pub struct DependencySpec {
     ///   --no-install    - Add to manifest only, skip installation
     #[arg(long)]
     pub no_install: bool,
+
+    /// Also list the dependency in this profile of `[profiles]`
+    ///
+    /// The dependency is added to its resource section as usual, and its name
+    /// is appended to the profile. Profiles install only the names they list,
+    /// so a profile that doesn't exist yet is created with the manifest's
+    /// existing dependencies.
+    ///
+    /// Examples:
+    ///   --group dev     - Installed by `agpm install --profile dev`
+    #[arg(long, value_name = "PROFILE")]
+    pub group: Option<String>,
+
+    /// Shorthand for `--group dev`
+    #[arg(long, conflicts_with = "group")]
+    pub dev: bool,
+}
+
+impl DependencySpec {
+    /// The profile selected with `--group` or `--dev`, if any.
+    #[must_use]
+    pub fn group(&self) -> Option<&str> {
+        self.group.as_deref().or(self.dev.then_some("dev"))
+    }
 }
 
 /// Arguments for adding an agent dependency
@@ -252,6 +276,8 @@ mod tests {
                 filename: None,
                 force: false,
                 no_install: false,
+                group: None,
+                dev: false,
             },
         });
 
@@ -270,6 +296,8 @@ mod tests {
                 filename: None,
                 force: true,
                 no_install: false,
+                group: None,
+                dev: false,
             },
         });
 
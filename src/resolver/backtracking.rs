@@ -749,7 +749,7 @@ impl<'a> BacktrackingResolver<'a> {
 
         // Filter parent versions matching the parent's constraint
         let matching_versions =
-            self.filter_by_constraint(&available_versions, parent_version_constraint)?;
+            self.filter_for_source(source_name, &available_versions, parent_version_constraint)?;
 
         tracing::debug!(
             "Found {} parent versions matching constraint {}",
@@ -826,6 +826,7 @@ impl<'a> BacktrackingResolver<'a> {
                     &worktree_path,
                     &parent_resource_path,
                     parent_variant_inputs_cloned.as_ref(),
+                    &self.core.manifest.metadata_keys,
                 )
                 .await
                 {
@@ -925,7 +926,7 @@ impl<'a> BacktrackingResolver<'a> {
         );
 
         let matching_versions =
-            self.filter_by_constraint(&available_versions, &requirement.requirement)?;
+            self.filter_for_source(source_name, &available_versions, &requirement.requirement)?;
 
         tracing::debug!(
             "Found {} versions matching constraint {}",
@@ -992,6 +993,13 @@ impl<'a> BacktrackingResolver<'a> {
     ///
     /// List of available version strings (tag names)
     async fn get_available_versions(&self, source_name: &str) -> Result<Vec<String>> {
+        if let Some((provider, url)) = self.registered_provider(source_name) {
+            return provider
+                .list_versions(source_name, &url)
+                .await
+                .with_context(|| format!("Failed to list versions for source '{}'", source_name));
+        }
+
         // Get bare repository path from version service
         let bare_repo_path =
             self.version_service.get_bare_repo_path(source_name).ok_or_else(|| {
@@ -1011,6 +1019,31 @@ impl<'a> BacktrackingResolver<'a> {
         Ok(tags)
     }
 
+    /// Filter a source's tags by constraint, honoring its `tag_prefix`/`tag_pattern`.
+    ///
+    /// For sources with a tag filter, the constraint is matched against the version
+    /// part of each selected tag and the original tag names are returned.
+    fn filter_for_source(
+        &self,
+        source_name: &str,
+        versions: &[String],
+        constraint: &str,
+    ) -> Result<Vec<String>> {
+        let Some(filter) = self.version_service.tag_filter(source_name) else {
+            return self.filter_by_constraint(versions, constraint);
+        };
+
+        let tags = filter.apply(versions.to_vec());
+        let version_parts: Vec<String> = tags.iter().map(|(_, part)| part.clone()).collect();
+        let matching = self.filter_by_constraint(&version_parts, constraint)?;
+        Ok(matching
+            .into_iter()
+            .map(|part| {
+                tags.iter().find(|(_, p)| *p == part).map_or(part.clone(), |(tag, _)| tag.clone())
+            })
+            .collect())
+    }
+
     /// Filter versions by constraint, returning matching versions in preference order.
     ///
     /// This function implements prefix-aware version filtering, ensuring that prefixed
@@ -1138,6 +1171,13 @@ impl<'a> BacktrackingResolver<'a> {
     ///
     /// Full commit SHA
     async fn resolve_version_to_sha(&self, source_name: &str, version: &str) -> Result<String> {
+        if let Some((provider, url)) = self.registered_provider(source_name) {
+            return provider
+                .resolve_version_to_commit(source_name, &url, Some(version))
+                .await
+                .context("Failed to resolve version to SHA");
+        }
+
         // Get bare repository path from version service
         let bare_repo_path = self
             .version_service
@@ -1150,6 +1190,18 @@ impl<'a> BacktrackingResolver<'a> {
         git_repo.resolve_to_sha(Some(version)).await.context("Failed to resolve version to SHA")
     }
 
+    /// The provider registered for a source's URL scheme, with the source URL.
+    ///
+    /// Returns `None` for Git sources, which are read from the bare repositories
+    /// synced by the version service.
+    fn registered_provider(
+        &self,
+        source_name: &str,
+    ) -> Option<(std::sync::Arc<dyn crate::source::provider::SourceProvider>, String)> {
+        let url = self.core.source_manager().get_source_url(source_name)?;
+        crate::source::provider::for_url(&url).map(|provider| (provider, url))
+    }
+
     /// Build a BacktrackingResult with all required fields.
     fn build_result(
         &self,
@@ -1248,9 +1300,13 @@ impl<'a> BacktrackingResolver<'a> {
             let variant_inputs = self.get_variant_inputs_for_resource(&resource_id)?;
 
             // Extract transitive dependencies from resource file at new version
-            let transitive_deps =
-                extract_transitive_deps(&worktree_path, resource_path, variant_inputs.as_ref())
-                    .await?;
+            let transitive_deps = extract_transitive_deps(
+                &worktree_path,
+                resource_path,
+                variant_inputs.as_ref(),
+                &self.core.manifest.metadata_keys,
+            )
+            .await?;
 
             // Resolve each transitive dependency (version → SHA)
             for (_resource_type, specs) in transitive_deps {
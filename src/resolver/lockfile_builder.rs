@@ -66,6 +66,21 @@ pub fn is_duplicate_entry(existing: &LockedResource, new_entry: &LockedResource)
         return false; // Different direct dependencies = NOT duplicates
     }
 
+    // Variants of one manifest entry share its alias and may differ only in the
+    // file they install to
+    if existing.manifest_alias.is_some()
+        && existing.manifest_alias == new_entry.manifest_alias
+        && existing.installed_at != new_entry.installed_at
+    {
+        tracing::debug!(
+            "NOT duplicates - variants of '{}' install to {} and {}",
+            existing.manifest_alias.as_deref().unwrap_or_default(),
+            existing.installed_at,
+            new_entry.installed_at
+        );
+        return false;
+    }
+
     // Determine if one is direct and one is transitive
     let existing_is_direct = existing.manifest_alias.is_some();
     let new_is_direct = new_entry.manifest_alias.is_some();
@@ -1056,6 +1071,7 @@ mod tests {
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
         });
 
         lockfile.snippets.push(LockedResource {
@@ -1075,6 +1091,7 @@ mod tests {
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
         });
 
         lockfile
@@ -1103,6 +1120,7 @@ mod tests {
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
         };
 
         builder.add_or_update_lockfile_entry(&mut lockfile, entry);
@@ -1134,6 +1152,7 @@ mod tests {
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
         };
 
         builder.add_or_update_lockfile_entry(&mut lockfile, updated_entry);
@@ -1200,6 +1219,7 @@ mod tests {
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
         };
 
         LockfileBuilder::collect_transitive_children(&lockfile, &parent, &mut entries_to_remove);
@@ -1242,6 +1262,17 @@ test-repo = "https://example.com/repo.git"
                 "project": { "name": "Production" },
                 "config": { "model": "claude-3-opus", "temperature": 0.5 }
             })),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         }));
 
         // Call build_merged_variant_inputs
@@ -1284,6 +1315,7 @@ test-repo = "https://example.com/repo.git"
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: VariantInputs::new(json!({"lang": "rust"})),
+            strip_metadata: false,
         };
 
         // Create transitive dependency with template_vars = {lang: "python"}
@@ -1304,6 +1336,7 @@ test-repo = "https://example.com/repo.git"
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: VariantInputs::new(json!({"lang": "python"})),
+            strip_metadata: false,
         };
 
         // According to the CRITICAL note in the code:
@@ -1319,5 +1352,13 @@ test-repo = "https://example.com/repo.git"
             "Direct and transitive dependencies with different template_vars should NOT be duplicates. \
              They represent distinct resources that both need to exist in the lockfile."
         );
+
+        // Variants of one manifest entry that differ only in their filename
+        let python = LockedResource {
+            installed_at: ".claude/agents/generic-py.md".to_string(),
+            ..direct.clone()
+        };
+        assert!(!is_duplicate_entry(&direct, &python));
+        assert!(is_duplicate_entry(&direct, &direct.clone()));
     }
 }
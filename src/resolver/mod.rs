@@ -45,9 +45,9 @@ use anyhow::Result;
 use dashmap::DashMap;
 
 use crate::cache::Cache;
-use crate::core::{OperationContext, ResourceType};
+use crate::core::{AgpmError, ConflictParty, DependencyConflict, OperationContext, ResourceType};
 use crate::lockfile::lockfile_dependency_ref::LockfileDependencyRef;
-use crate::lockfile::{LockFile, LockedResource};
+use crate::lockfile::{DisabledResource, LockFile, LockedResource, VerifiedTag};
 use crate::manifest::{Manifest, ResourceDependency};
 use crate::source::SourceManager;
 
@@ -55,6 +55,7 @@ use crate::source::SourceManager;
 pub use conflict_service::ConflictService;
 pub use pattern_expander::PatternExpansionService;
 pub use resource_service::ResourceFetchingService;
+pub use transitive_resolver::DEFAULT_MAX_DEPTH;
 pub use types::ResolutionCore;
 pub use version_resolver::{
     VersionResolutionService, VersionResolver as VersionResolverExport, find_best_matching_tag,
@@ -176,6 +177,15 @@ pub struct DependencyResolver {
     /// without searching through all resolved dependencies.
     /// Uses DashMap for concurrent access during parallel dependency resolution
     reverse_dependency_map: std::sync::Arc<dashmap::DashMap<String, Vec<String>>>,
+
+    /// Whether an `[override]` entry matching no transitive dependency is an error.
+    ///
+    /// Disabled for the partial manifests of incremental updates, which only
+    /// contain some of the dependencies the overrides were written for.
+    check_unused_overrides: bool,
+
+    /// Longest transitive dependency chain allowed below a manifest dependency.
+    max_depth: usize,
 }
 
 impl DependencyResolver {
@@ -207,6 +217,8 @@ impl DependencyResolver {
             sources_pre_synced: std::sync::atomic::AtomicBool::new(false),
             resolved_deps_for_conflict_check: Arc::new(DashMap::new()),
             reverse_dependency_map: std::sync::Arc::new(dashmap::DashMap::new()),
+            check_unused_overrides: true,
+            max_depth: DEFAULT_MAX_DEPTH,
         })
     }
 
@@ -242,12 +254,16 @@ impl DependencyResolver {
     ) -> Result<Self> {
         // Create source manager from manifest
         let source_manager = SourceManager::from_manifest(&manifest)?;
+        cache.configure_sources(&manifest.sources).await;
+        cache.replace_sources(manifest.replacement_urls()?).await;
 
         // Create resolution core with shared state
         let core = ResolutionCore::new(manifest, cache, source_manager, operation_context);
 
         // Initialize all services
         let version_service = VersionResolutionService::new(core.cache().clone());
+        version_service
+            .configure_sources(&core.manifest().sources, core.manifest().manifest_dir.as_deref())?;
         let pattern_service = PatternExpansionService::new();
 
         Self::init_dependencies(core, version_service, pattern_service)
@@ -288,6 +304,8 @@ impl DependencyResolver {
         operation_context: Option<Arc<OperationContext>>,
     ) -> Result<Self> {
         let source_manager = SourceManager::from_manifest_with_global(&manifest).await?;
+        cache.configure_sources(&manifest.sources).await;
+        cache.replace_sources(manifest.replacement_urls()?).await;
 
         let core = ResolutionCore::new(manifest, cache, source_manager, operation_context);
 
@@ -296,6 +314,8 @@ impl DependencyResolver {
         } else {
             VersionResolutionService::new(core.cache().clone())
         };
+        version_service
+            .configure_sources(&core.manifest().sources, core.manifest().manifest_dir.as_deref())?;
         let pattern_service = PatternExpansionService::new();
 
         Self::init_dependencies(core, version_service, pattern_service)
@@ -405,6 +425,7 @@ impl DependencyResolver {
         for (name, url) in &self.core.manifest().sources {
             lockfile.add_source(name.clone(), url.clone(), String::new());
         }
+        lockfile.disabled = disabled_resources(self.core.manifest());
 
         // Extract dependencies from manifest with types
         let base_deps: Vec<(String, ResourceDependency, ResourceType)> = self
@@ -467,15 +488,23 @@ impl DependencyResolver {
                 "Phase 3: Calling resolve_transitive_dependencies with {} base deps",
                 base_deps.len()
             );
-            let result = self.resolve_transitive_dependencies(base_deps, progress).await?;
+            let result = self.resolve_transitive_dependencies(base_deps, true, progress).await?;
             tracing::info!("Phase 3: Resolved {} total deps (including transitive)", result.len());
+            if self.check_unused_overrides {
+                check_version_overrides_applied(
+                    &self.core.manifest().version_overrides,
+                    base_deps,
+                    &result,
+                )?;
+            }
             Ok(result)
         } else {
             tracing::info!(
                 "Phase 3: Transitive resolution disabled, using {} base deps",
                 base_deps.len()
             );
-            Ok(base_deps.to_vec())
+            // Scan the declared resources to record what they depend on
+            self.resolve_transitive_dependencies(base_deps, false, progress).await
         }
     }
 
@@ -654,6 +683,7 @@ impl DependencyResolver {
         let conflict_start = std::time::Instant::now();
 
         // Populate SHA conflict detector with Git path dependencies only
+        let mut sha_resource_types = HashMap::new();
         for entry in self.resolved_deps_for_conflict_check.iter() {
             let ((resource_id, required_by, _name), dependency_info) = (entry.key(), entry.value());
             let ResolvedDependencyInfo {
@@ -679,6 +709,9 @@ impl DependencyResolver {
                     &resolved_sha[..8.min(resolved_sha.len())]
                 );
 
+                sha_resource_types
+                    .insert((source.to_string(), path.to_string()), resource_id.resource_type());
+
                 // Add to SHA conflict detector
                 self.sha_conflict_detector.add_requirement(
                     crate::resolver::sha_conflict_detector::ResolvedRequirement {
@@ -705,13 +738,34 @@ impl DependencyResolver {
         if !sha_conflicts.is_empty() {
             // SHA conflicts are true conflicts that cannot be resolved by backtracking
             // Report them as errors
-            let error_messages: Vec<String> =
-                sha_conflicts.iter().map(|conflict| conflict.format_error()).collect();
-
-            return Err(anyhow::anyhow!(
-                "Unresolvable SHA conflicts detected:\n{}",
-                error_messages.join("\n")
-            ));
+            let conflicts = sha_conflicts
+                .iter()
+                .map(|conflict| {
+                    let parties = conflict
+                        .sha_groups
+                        .values()
+                        .flatten()
+                        .map(|req| {
+                            self.conflict_party(
+                                &req.requested_version,
+                                &req.resolved_sha,
+                                &req.required_by,
+                            )
+                        })
+                        .collect();
+                    let resource_type = sha_resource_types
+                        .get(&(conflict.source.clone(), conflict.path.clone()))
+                        .copied()
+                        .unwrap_or_default();
+                    dependency_conflict(
+                        resource_type,
+                        &conflict.path,
+                        Some(&conflict.source),
+                        parties,
+                    )
+                })
+                .collect();
+            return Err(resolution_conflict("Unresolvable SHA conflicts detected", conflicts));
         }
 
         // Phase 6: Version-based conflict detection (only for Version path dependencies)
@@ -789,30 +843,20 @@ impl DependencyResolver {
 
                     tracing::warn!("Backtracking failed: {}", reason_msg);
 
-                    // Use original error with detailed conflict information
-                    let mut error_msg = format!(
-                        "Version conflicts detected (automatic resolution failed: {}):\n\n",
-                        reason_msg
-                    );
-                    for conflict in &conflicts {
-                        error_msg.push_str(&format!("{conflict}\n"));
-                    }
-                    error_msg.push_str(
-                        "\nSuggestion: Manually specify compatible versions in agpm.toml",
-                    );
-                    return Err(anyhow::anyhow!("{}", error_msg));
+                    return Err(resolution_conflict(
+                        &format!(
+                            "Version conflicts detected (automatic resolution failed: {reason_msg})"
+                        ),
+                        self.version_conflicts(&conflicts),
+                    ));
                 }
                 Err(e) => {
                     // Backtracking encountered an error
                     tracing::error!("Backtracking error: {}", e);
-                    let mut error_msg = format!(
-                        "Version conflicts detected (automatic resolution error: {}):\n\n",
-                        e
-                    );
-                    for conflict in &conflicts {
-                        error_msg.push_str(&format!("{conflict}\n"));
-                    }
-                    return Err(anyhow::anyhow!("{}", error_msg));
+                    return Err(resolution_conflict(
+                        &format!("Version conflicts detected (automatic resolution error: {e})"),
+                        self.version_conflicts(&conflicts),
+                    ));
                 }
             }
         }
@@ -820,6 +864,94 @@ impl DependencyResolver {
         Ok(())
     }
 
+    /// Describe version conflicts with the parent chain of every requirement.
+    fn version_conflicts(
+        &self,
+        conflicts: &[crate::version::conflict::VersionConflict],
+    ) -> Vec<DependencyConflict> {
+        conflicts
+            .iter()
+            .map(|conflict| {
+                let parties = conflict
+                    .conflicting_requirements
+                    .iter()
+                    .map(|req| {
+                        self.conflict_party(&req.requirement, &req.resolved_sha, &req.required_by)
+                    })
+                    .collect();
+                dependency_conflict(
+                    conflict.resource.resource_type(),
+                    conflict.resource.name(),
+                    conflict.resource.source(),
+                    parties,
+                )
+            })
+            .collect()
+    }
+
+    /// Build one party of a conflict, tracing `required_by` back to the manifest.
+    fn conflict_party(
+        &self,
+        version: &str,
+        resolved_sha: &str,
+        required_by: &str,
+    ) -> ConflictParty {
+        ConflictParty {
+            version: version.to_string(),
+            resolved_commit: resolved_sha.to_string(),
+            chain: self.dependency_chain(required_by),
+        }
+    }
+
+    /// Chain of dependencies that introduced a requirement, outermost first.
+    ///
+    /// Walks the reverse dependency map up from `required_by` until reaching a
+    /// dependency that no other resource requires. Empty for direct
+    /// dependencies, whose `required_by` is `"manifest"`.
+    fn dependency_chain(&self, required_by: &str) -> Vec<String> {
+        let mut chain = Vec::new();
+        if required_by == "manifest" {
+            return chain;
+        }
+        let mut current = required_by.to_string();
+        loop {
+            chain.push(current.clone());
+            let parent = self
+                .reverse_dependency_map
+                .get(&current)
+                .and_then(|parents| parents.iter().find(|parent| !chain.contains(parent)).cloned());
+            match parent {
+                Some(parent) => current = parent,
+                None => break,
+            }
+        }
+        chain.reverse();
+        chain
+    }
+
+    /// Record on each locked source the verified tags its resources are locked at.
+    fn record_verified_tags(&self, lockfile: &mut LockFile) {
+        let locked: std::collections::HashSet<(String, String)> = lockfile
+            .all_resources()
+            .into_iter()
+            .filter_map(|resource| {
+                Some((resource.source.clone()?, resource.resolved_commit.clone()?))
+            })
+            .collect();
+        let resolver = self.version_service.version_resolver();
+        for source in &mut lockfile.sources {
+            source.verified_tags = resolver
+                .verified_tags(&source.name)
+                .into_iter()
+                .filter(|(_, commit, _)| locked.contains(&(source.name.clone(), commit.clone())))
+                .map(|(tag, _, signer)| VerifiedTag {
+                    tag,
+                    signer,
+                })
+                .collect();
+        }
+    }
+
     /// Phase 6: Final post-processing and cleanup
     fn finalize_resolution(
         &mut self,
@@ -829,6 +961,8 @@ impl DependencyResolver {
         // Post-process dependencies and detect target conflicts
         self.add_version_to_dependencies(lockfile)?;
         self.detect_target_conflicts(lockfile)?;
+        lockfile.record_source_commits();
+        self.record_verified_tags(lockfile);
 
         // Complete the resolution phase (includes all phases: version resolution,
         // transitive deps, conflict detection)
@@ -911,7 +1045,9 @@ impl DependencyResolver {
             None => {
                 // Update all dependencies (full resolution)
                 tracing::debug!("Performing full resolution for all dependencies");
-                self.resolve_with_options(true, progress).await
+                let mut lockfile = self.resolve_with_options(true, progress).await?;
+                lockfile.carry_over_fetched_at(existing);
+                Ok(lockfile)
             }
             Some(names) => {
                 // Incremental update requested
@@ -947,12 +1083,16 @@ impl DependencyResolver {
                     self.core.operation_context().cloned(),
                 )
                 .await?;
+                temp_resolver.check_unused_overrides = false;
+                temp_resolver.max_depth = self.max_depth;
 
                 // Phase 4: Resolve filtered dependencies with updates allowed
                 let updated = temp_resolver.resolve_with_options(true, progress).await?;
 
                 // Phase 5: Merge unchanged and updated lockfiles
-                let merged = Self::merge_lockfiles(unchanged, updated);
+                let mut merged = Self::merge_lockfiles(unchanged, updated);
+                merged.disabled = disabled_resources(self.core.manifest());
+                merged.carry_over_fetched_at(existing);
 
                 tracing::debug!(
                     "Incremental update complete: merged lockfile has {} total entries",
@@ -969,6 +1109,50 @@ impl DependencyResolver {
         }
     }
 
+    /// Update only the named dependencies and the resources they depend on.
+    ///
+    /// Unlike [`update`](Self::update) with names, every resource outside the
+    /// named dependencies' subgraph keeps its locked entry, and transitive
+    /// dependencies that the subgraph shares with other resources are pinned to
+    /// their locked commits through version overrides, so untargeted resources
+    /// never see a version change. Transitive dependencies that only the named
+    /// dependencies used are re-resolved, and dropped if no longer required.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if resolving the named dependencies fails
+    pub async fn update_conservative(
+        &mut self,
+        existing: &LockFile,
+        names: &[String],
+        progress: Option<Arc<crate::utils::MultiPhaseProgress>>,
+    ) -> Result<LockFile> {
+        let (unchanged, to_resolve, shared) =
+            Self::partition_for_conservative_update(existing, names);
+        if to_resolve.is_empty() {
+            tracing::warn!("No matching dependencies found in lockfile: {:?}", names);
+            return Ok(existing.clone());
+        }
+
+        let mut filtered_manifest = self.create_filtered_manifest(&to_resolve);
+        Self::pin_shared_dependencies(&mut filtered_manifest.version_overrides, &shared);
+
+        let mut temp_resolver = DependencyResolver::new_with_context(
+            filtered_manifest,
+            self.core.cache().clone(),
+            self.core.operation_context().cloned(),
+        )
+        .await?;
+        temp_resolver.check_unused_overrides = false;
+        temp_resolver.max_depth = self.max_depth;
+        let updated = temp_resolver.resolve_with_options(true, progress).await?;
+
+        let mut merged = Self::merge_lockfiles(unchanged, updated);
+        merged.disabled = disabled_resources(self.core.manifest());
+        merged.carry_over_fetched_at(existing);
+        Ok(merged)
+    }
+
     /// Get available versions for a repository.
     ///
     /// # Arguments
@@ -982,6 +1166,26 @@ impl DependencyResolver {
         VersionResolutionService::get_available_versions(&self.core, repo_path).await
     }
 
+    /// List the version tags of a synced source.
+    ///
+    /// Each tag is paired with its version part, which differs from the tag
+    /// only when the source has a `tag_prefix` or `tag_pattern`; other tags
+    /// are left out.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the source has not been synced by this resolver.
+    pub async fn source_tags(&self, source: &str) -> Result<Option<Vec<(String, String)>>> {
+        let Some(repo_path) = self.version_service.get_bare_repo_path(source) else {
+            return Ok(None);
+        };
+        let tags = self.get_available_versions(&repo_path).await?;
+        Ok(Some(match self.version_service.tag_filter(source) {
+            Some(filter) => filter.apply(tags),
+            None => tags.into_iter().map(|tag| (tag.clone(), tag)).collect(),
+        }))
+    }
+
     /// Verify that existing lockfile is still valid.
     ///
     /// # Arguments
@@ -1009,6 +1213,35 @@ impl DependencyResolver {
     pub fn set_operation_context(&mut self, context: Arc<OperationContext>) {
         self.core.operation_context = Some(context);
     }
+
+    /// Set the longest transitive dependency chain allowed below a manifest dependency.
+    ///
+    /// Resolution fails, naming the chain, when a dependency is discovered
+    /// deeper than this. Defaults to [`DEFAULT_MAX_DEPTH`].
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
+    /// Require the tags of every Git source to be signed (`--verify-signatures`).
+    ///
+    /// Covers sources from the global config as well as the manifest. Manifest
+    /// sources use their `allowed_signers` file if they declare one.
+    pub fn require_tag_signatures(&mut self) {
+        let manifest = self.core.manifest();
+        let base_dir = manifest.manifest_dir.as_deref();
+        let sources = self.core.source_manager.list();
+        self.version_service.require_tag_signatures(sources.iter().map(|source| {
+            let allowed_signers =
+                manifest.sources.options(&source.name).allowed_signers_path(base_dir);
+            (source.name.as_str(), allowed_signers)
+        }));
+    }
+
+    /// Fetch every branch and tag of each source during pre-sync, even for
+    /// dependencies pinned to exact versions.
+    pub fn set_fetch_all_refs(&mut self, fetch_all_refs: bool) {
+        self.version_service.set_fetch_all_refs(fetch_all_refs);
+    }
 }
 
 // Private helper methods
@@ -1028,8 +1261,9 @@ impl DependencyResolver {
         let mut index = HashMap::new();
 
         for (name, dep, resource_type) in base_deps {
-            // Skip pattern dependencies (they expand later)
-            if dep.is_pattern() {
+            // Skip pattern dependencies (they expand later) and inline resources
+            // (transitive dependencies cannot refer to them by path)
+            if dep.is_pattern() || dep.get_inline().is_some() {
                 continue;
             }
 
@@ -1063,6 +1297,7 @@ impl DependencyResolver {
                 filename: dep.get_filename().map(std::string::ToString::to_string),
                 target: dep.get_target().map(std::string::ToString::to_string),
                 install: dep.get_install(),
+                strip_metadata: dep.get_strip_metadata(),
                 manifest_alias: Some(name.clone()),
                 template_vars: dep.get_template_vars().cloned(),
             };
@@ -1086,10 +1321,12 @@ impl DependencyResolver {
     ///
     /// Discovers dependencies declared in resource files, expands patterns,
     /// builds dependency graph with cycle detection, and returns all dependencies
-    /// in topological order.
+    /// in topological order. With `enable_transitive` false, only `base_deps`
+    /// and their pattern matches are scanned and returned.
     async fn resolve_transitive_dependencies(
         &mut self,
         base_deps: &[(String, ResourceDependency, ResourceType)],
+        enable_transitive: bool,
         progress: Option<std::sync::Arc<crate::utils::MultiPhaseProgress>>,
     ) -> Result<Vec<(String, ResourceDependency, ResourceType)>> {
         use crate::resolver::transitive_resolver;
@@ -1129,7 +1366,8 @@ impl DependencyResolver {
                 ctx: &mut ctx,
                 core: &self.core,
                 base_deps,
-                enable_transitive: true,
+                enable_transitive,
+                max_depth: self.max_depth,
                 prepared_versions: &prepared_versions,
                 pattern_alias_map: &self.pattern_alias_map,
                 services: &services,
@@ -1260,7 +1498,12 @@ impl DependencyResolver {
         use crate::resolver::path_resolver as install_path_resolver;
         use crate::utils::normalize_path_for_storage;
 
-        let filename = Self::resolve_filename(dep);
+        // Inline resources have no path to take a filename from
+        let filename = if dep.get_inline().is_some() {
+            dep.get_filename().map_or_else(|| format!("{name}.md"), str::to_string)
+        } else {
+            Self::resolve_filename(dep)
+        };
         let artifact_type_string = self.resolve_tool(dep, resource_type);
         let artifact_type = artifact_type_string.as_str();
 
@@ -1292,7 +1535,11 @@ impl DependencyResolver {
         // For transitive dependencies (manifest_alias=None), use the name as-is since it's
         // already the correct relative path computed by the transitive resolver
         // For direct dependencies (manifest_alias=Some), normalize the path
-        let canonical_name = self.compute_local_canonical_name(name, dep, &manifest_alias)?;
+        let canonical_name = if dep.get_inline().is_some() {
+            name.to_string()
+        } else {
+            self.compute_local_canonical_name(name, dep, &manifest_alias)?
+        };
 
         let variant_inputs = lockfile_builder::VariantInputs::new(
             lockfile_builder::build_merged_variant_inputs(self.core.manifest(), dep),
@@ -1321,6 +1568,8 @@ impl DependencyResolver {
             install: dep.get_install(),
             variant_inputs,
             context_checksum: None,
+            strip_metadata: dep.get_strip_metadata().unwrap_or(self.core.manifest().strip_metadata),
+            inline: dep.get_inline().map(str::to_string),
         })
     }
 
@@ -1421,6 +1670,7 @@ impl DependencyResolver {
         // Extract data from prepared before storing variant_inputs
         let resolved_version = prepared.resolved_version.clone();
         let resolved_commit = prepared.resolved_commit.clone();
+        check_expected_commit(name, dep, &resolved_commit)?;
 
         // Store variant_inputs in PreparedSourceVersion for backtracking
         // DashMap allows concurrent inserts, so we don't need mutable access
@@ -1450,6 +1700,8 @@ impl DependencyResolver {
             install: dep.get_install(),
             variant_inputs,
             context_checksum: None,
+            strip_metadata: dep.get_strip_metadata().unwrap_or(self.core.manifest().strip_metadata),
+            inline: None,
         })
     }
 
@@ -1535,6 +1787,10 @@ impl DependencyResolver {
                 install: dep.get_install(),
                 variant_inputs: variant_inputs.clone(),
                 context_checksum: None,
+                strip_metadata: dep
+                    .get_strip_metadata()
+                    .unwrap_or(self.core.manifest().strip_metadata),
+                inline: None,
             });
         }
 
@@ -1550,9 +1806,7 @@ impl DependencyResolver {
     ) -> Result<Vec<LockedResource>> {
         use crate::pattern::PatternResolver;
         use crate::resolver::{lockfile_builder, path_resolver};
-        use crate::utils::{
-            compute_relative_install_path, normalize_path, normalize_path_for_storage,
-        };
+        use crate::utils::normalize_path_for_storage;
 
         let pattern = dep.get_path();
         let pattern_name = name;
@@ -1582,6 +1836,7 @@ impl DependencyResolver {
         let worktree_path = prepared.worktree_path.clone();
         let resolved_version = prepared.resolved_version.clone();
         let resolved_commit = prepared.resolved_commit.clone();
+        check_expected_commit(name, dep, &resolved_commit)?;
 
         let repo_path = Path::new(&worktree_path);
         let pattern_resolver = PatternResolver::new();
@@ -1600,50 +1855,14 @@ impl DependencyResolver {
             let resource_name = crate::pattern::extract_resource_name(&matched_path);
 
             // Compute installation path
-            let installed_at = match resource_type {
-                ResourceType::Hook | ResourceType::McpServer => {
-                    path_resolver::resolve_merge_target_path(
-                        self.core.manifest(),
-                        artifact_type,
-                        resource_type,
-                    )
-                }
-                _ => {
-                    let artifact_path = self
-                        .core
-                        .manifest()
-                        .get_artifact_resource_path(artifact_type, resource_type)
-                        .ok_or_else(|| {
-                            anyhow::anyhow!(
-                                "Resource type '{}' is not supported by tool '{}'",
-                                resource_type,
-                                artifact_type
-                            )
-                        })?;
-
-                    let dep_flatten = dep.get_flatten();
-                    let tool_flatten = self
-                        .core
-                        .manifest()
-                        .get_tool_config(artifact_type)
-                        .and_then(|config| config.resources.get(resource_type.to_plural()))
-                        .and_then(|resource_config| resource_config.flatten);
-
-                    let flatten = dep_flatten.or(tool_flatten).unwrap_or(false);
-
-                    let base_target = if let Some(custom_target) = dep.get_target() {
-                        PathBuf::from(artifact_path.display().to_string())
-                            .join(custom_target.trim_start_matches('/'))
-                    } else {
-                        artifact_path.to_path_buf()
-                    };
-
-                    let filename = repo_path.join(&matched_path).to_string_lossy().to_string();
-                    let relative_path =
-                        compute_relative_install_path(&base_target, Path::new(&filename), flatten);
-                    normalize_path_for_storage(normalize_path(&base_target.join(relative_path)))
-                }
-            };
+            let installed_at = path_resolver::resolve_git_pattern_install_path(
+                self.core.manifest(),
+                dep,
+                artifact_type,
+                resource_type,
+                repo_path,
+                &matched_path,
+            )?;
 
             // Store variant_inputs in PreparedSourceVersion for backtracking
             // DashMap allows concurrent inserts, so we access through regular get()
@@ -1676,6 +1895,10 @@ impl DependencyResolver {
                 install: dep.get_install(),
                 variant_inputs: variant_inputs.clone(),
                 context_checksum: None,
+                strip_metadata: dep
+                    .get_strip_metadata()
+                    .unwrap_or(self.core.manifest().strip_metadata),
+                inline: None,
             });
         }
 
@@ -2101,6 +2324,8 @@ impl DependencyResolver {
             patches: self.core.manifest.patches.clone(),
             project_patches: self.core.manifest.project_patches.clone(),
             private_patches: self.core.manifest.private_patches.clone(),
+            version_overrides: self.core.manifest.version_overrides.clone(),
+            replacements: self.core.manifest.replacements.clone(),
             manifest_dir: self.core.manifest.manifest_dir.clone(),
             ..Default::default()
         };
@@ -2214,6 +2439,107 @@ impl DependencyResolver {
         (unchanged, deps_requiring_resolution)
     }
 
+    /// Pin shared dependencies to their locked commits through version overrides.
+    ///
+    /// Overrides replace any `branch`/`rev` on the dependency spec, so the
+    /// re-resolved resources keep the exact commit the untouched ones were
+    /// installed with. An explicit `[override]` entry takes precedence.
+    fn pin_shared_dependencies(
+        overrides: &mut crate::manifest::VersionOverrides,
+        shared: &[LockedResource],
+    ) {
+        for resource in shared {
+            let (Some(_), Some(commit)) = (&resource.source, &resource.resolved_commit) else {
+                continue;
+            };
+            if overrides.version_for(resource.resource_type, &resource.name).is_none() {
+                tracing::debug!("Pinning shared dependency '{}' to {}", resource.name, commit);
+                overrides
+                    .section_mut(resource.resource_type)
+                    .insert(resource.name.clone(), commit.clone());
+            }
+        }
+    }
+
+    /// Split a lockfile for [`update_conservative`](Self::update_conservative).
+    ///
+    /// The named entries and everything they transitively depend on form the
+    /// subgraph to re-resolve. Every other entry is kept, together with the
+    /// entries it depends on.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of (unchanged_lockfile, deps_requiring_resolution, shared):
+    /// - `unchanged_lockfile`: Entries outside the subgraph or shared with it
+    /// - `deps_requiring_resolution`: The named (name, resource_type) pairs
+    /// - `shared`: Entries in the subgraph that other entries also depend on
+    fn partition_for_conservative_update(
+        existing: &LockFile,
+        names: &[String],
+    ) -> (LockFile, Vec<(String, ResourceType)>, Vec<LockedResource>) {
+        use std::collections::HashSet;
+
+        let entries: Vec<&LockedResource> = ResourceType::all()
+            .iter()
+            .flat_map(|resource_type| existing.get_resources(resource_type))
+            .collect();
+
+        // Indices of the entries a resource depends on, preferring its own source
+        let edges = |parent: &LockedResource| -> Vec<usize> {
+            parent
+                .parsed_dependencies()
+                .filter_map(|dep| {
+                    let source = dep.source.as_deref().or(parent.source.as_deref());
+                    let candidates = || {
+                        entries.iter().enumerate().filter(|(_, entry)| {
+                            entry.resource_type == dep.resource_type && entry.name == dep.path
+                        })
+                    };
+                    candidates()
+                        .find(|(_, entry)| entry.source.as_deref() == source)
+                        .or_else(|| candidates().next())
+                        .map(|(index, _)| index)
+                })
+                .collect()
+        };
+        let closure = |roots: Vec<usize>| -> HashSet<usize> {
+            let mut seen = HashSet::new();
+            let mut stack = roots;
+            while let Some(index) = stack.pop() {
+                if seen.insert(index) {
+                    stack.extend(edges(entries[index]));
+                }
+            }
+            seen
+        };
+
+        let named = |entry: &LockedResource| {
+            entry.manifest_alias.as_ref().is_some_and(|alias| names.contains(alias))
+                || names.contains(&entry.name)
+        };
+        let targeted = closure((0..entries.len()).filter(|index| named(entries[*index])).collect());
+        let pinned =
+            closure((0..entries.len()).filter(|index| !targeted.contains(index)).collect());
+
+        let mut unchanged = LockFile::new();
+        unchanged.sources = existing.sources.clone();
+        let mut to_resolve = Vec::new();
+        let mut shared = Vec::new();
+        for (index, entry) in entries.iter().enumerate() {
+            if named(entry) {
+                let name = entry.manifest_alias.as_ref().unwrap_or(&entry.name);
+                to_resolve.push((name.clone(), entry.resource_type));
+            } else if pinned.contains(&index) {
+                unchanged.get_resources_mut(&entry.resource_type).push((*entry).clone());
+                if targeted.contains(&index) {
+                    shared.push((*entry).clone());
+                }
+            }
+        }
+
+        (unchanged, to_resolve, shared)
+    }
+
     /// Merge unchanged and updated lockfiles.
     ///
     /// This method combines entries from two lockfiles, with updated entries
@@ -2285,11 +2611,122 @@ impl DependencyResolver {
 
         // Add all updated sources
         unchanged.sources.extend(updated.sources);
+        unchanged.record_source_commits();
 
         unchanged
     }
 }
 
+/// Check that every `[override]` entry pinned at least one transitive dependency.
+///
+/// Direct dependencies are skipped: their version comes from the manifest.
+///
+/// # Errors
+///
+/// Returns an error listing the overrides that matched nothing.
+fn check_version_overrides_applied(
+    overrides: &crate::manifest::VersionOverrides,
+    base_deps: &[(String, ResourceDependency, ResourceType)],
+    all_deps: &[(String, ResourceDependency, ResourceType)],
+) -> Result<()> {
+    let unused: Vec<String> = overrides
+        .iter()
+        .filter(|(resource_type, key, _)| {
+            !all_deps.iter().any(|(name, dep, dep_type)| {
+                dep_type == resource_type
+                    && dep.get_source().is_some()
+                    && !base_deps
+                        .iter()
+                        .any(|(base, _, base_type)| base == name && base_type == dep_type)
+                    && crate::manifest::VersionOverrides::matches(key, *resource_type, name)
+            })
+        })
+        .map(|(resource_type, key, _)| format!("override.{}.{key}", resource_type.to_plural()))
+        .collect();
+
+    if unused.is_empty() {
+        return Ok(());
+    }
+    Err(anyhow::anyhow!(
+        "[override] entries do not match any transitive dependency from a Git source: {}",
+        unused.join(", ")
+    ))
+}
+
+/// Build a conflict report with its parties in a stable order.
+fn dependency_conflict(
+    resource_type: ResourceType,
+    dependency: &str,
+    source: Option<&str>,
+    mut parties: Vec<ConflictParty>,
+) -> DependencyConflict {
+    parties.sort_by(|a, b| (&a.version, &a.chain).cmp(&(&b.version, &b.chain)));
+    DependencyConflict {
+        resource_type,
+        dependency: dependency.to_string(),
+        source: source.map(str::to_string),
+        parties,
+    }
+}
+
+/// The error for conflicts that resolution could not reconcile.
+fn resolution_conflict(summary: &str, mut conflicts: Vec<DependencyConflict>) -> anyhow::Error {
+    conflicts.sort_by(|a, b| a.dependency.cmp(&b.dependency));
+    AgpmError::ResolutionConflict {
+        summary: summary.to_string(),
+        conflicts,
+    }
+    .into()
+}
+
+/// Collect the manifest dependencies disabled with `enabled = false`, sorted.
+fn disabled_resources(manifest: &Manifest) -> Vec<DisabledResource> {
+    let mut disabled: Vec<DisabledResource> = ResourceType::all()
+        .iter()
+        .filter_map(|resource_type| {
+            manifest.get_dependencies(*resource_type).map(|deps| (*resource_type, deps))
+        })
+        .flat_map(|(resource_type, deps)| {
+            deps.iter().filter(|(_, dep)| !dep.is_enabled()).map(move |(name, _)| {
+                DisabledResource {
+                    name: name.clone(),
+                    resource_type,
+                }
+            })
+        })
+        .collect();
+    disabled.sort();
+    disabled
+}
+
+/// Check that a dependency resolved to the commit its `expect_commit` names.
+///
+/// # Errors
+///
+/// Returns an error if `resolved_commit` does not start with `expect_commit`.
+fn check_expected_commit(
+    name: &str,
+    dep: &ResourceDependency,
+    resolved_commit: &str,
+) -> Result<()> {
+    let Some(expected) = dep.get_expect_commit() else {
+        return Ok(());
+    };
+    if resolved_commit.to_ascii_lowercase().starts_with(&expected.to_ascii_lowercase()) {
+        return Ok(());
+    }
+    Err(anyhow::anyhow!(
+        "Dependency '{}' ({} @ {}) resolved to commit {}, but expect_commit is {}. \
+         The version may have been moved upstream; review the new commit before updating \
+         'expect_commit'",
+        name,
+        dep.get_source().unwrap_or_default(),
+        dep.get_version().unwrap_or("HEAD"),
+        resolved_commit,
+        expected
+    ))
+}
+
 #[cfg(test)]
 mod resolver_tests {
     use super::*;
@@ -2309,4 +2746,55 @@ mod resolver_tests {
         DependencyResolver::new_with_global(manifest, cache).await?;
         Ok(())
     }
+
+    #[test]
+    fn test_check_version_overrides_applied() {
+        let remote = |path: &str| {
+            ResourceDependency::Detailed(Box::new(crate::manifest::DetailedDependency {
+                source: Some("community".to_string()),
+                path: path.to_string(),
+                version: Some("v1.1.0".to_string()),
+                branch: None,
+                rev: None,
+                command: None,
+                args: None,
+                target: None,
+                filename: None,
+                dependencies: None,
+                tool: None,
+                flatten: None,
+                install: None,
+                template_vars: None,
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
+            }))
+        };
+        let base =
+            vec![("reviewer".to_string(), remote("agents/reviewer.md"), ResourceType::Agent)];
+        let mut all = base.clone();
+        all.push((
+            "snippets/utils".to_string(),
+            remote("snippets/utils.md"),
+            ResourceType::Snippet,
+        ));
+
+        let mut overrides = crate::manifest::VersionOverrides::default();
+        overrides.snippets.insert("utils".to_string(), "v1.1.0".to_string());
+        assert!(check_version_overrides_applied(&overrides, &base, &all).is_ok());
+
+        // Direct dependencies and unknown names do not count
+        overrides.agents.insert("reviewer".to_string(), "v2.0.0".to_string());
+        overrides.snippets.insert("missing".to_string(), "v1.0.0".to_string());
+        let err = check_version_overrides_applied(&overrides, &base, &all).unwrap_err();
+        assert!(err.to_string().contains("override.agents.reviewer, override.snippets.missing"));
+    }
 }
@@ -193,7 +193,9 @@ pub fn compute_regular_resource_install_path(
     let flatten = get_flatten_behavior(manifest, dep, artifact_type, resource_type);
 
     // Determine the base target directory
-    let base_target = if let Some(custom_target) = dep.get_target() {
+    let base_target = if let Some(custom_target) =
+        manifest.get_dependency_target(dep, artifact_type, resource_type)
+    {
         // Custom target is relative to the artifact's resource directory
         PathBuf::from(artifact_path.display().to_string())
             .join(custom_target.trim_start_matches('/'))
@@ -475,6 +477,67 @@ pub fn resolve_install_path(
     }
 }
 
+/// Resolves the installation path of a file matched by a Git pattern dependency.
+///
+/// Merge-target resources (Hook, McpServer) go to their configuration file.
+/// Other resources are placed under the artifact directory (or the
+/// dependency's custom target), keeping the matched file's meaningful
+/// directory structure unless flattened.
+///
+/// # Arguments
+///
+/// * `manifest` - The project manifest containing tool configurations
+/// * `dep` - The pattern dependency specification
+/// * `artifact_type` - The tool name (e.g., "claude-code", "opencode")
+/// * `resource_type` - The resource type of the pattern dependency
+/// * `repo_path` - The worktree the pattern was resolved in
+/// * `matched_path` - The matched file, relative to `repo_path`
+///
+/// # Errors
+///
+/// Returns an error if the resource type is not supported by the specified tool.
+pub fn resolve_git_pattern_install_path(
+    manifest: &Manifest,
+    dep: &ResourceDependency,
+    artifact_type: &str,
+    resource_type: ResourceType,
+    repo_path: &Path,
+    matched_path: &Path,
+) -> Result<String> {
+    if matches!(resource_type, ResourceType::Hook | ResourceType::McpServer) {
+        return Ok(resolve_merge_target_path(manifest, artifact_type, resource_type));
+    }
+
+    let artifact_path =
+        manifest.get_artifact_resource_path(artifact_type, resource_type).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Resource type '{}' is not supported by tool '{}'",
+                resource_type,
+                artifact_type
+            )
+        })?;
+
+    let dep_flatten = dep.get_flatten();
+    let tool_flatten = manifest
+        .get_tool_config(artifact_type)
+        .and_then(|config| config.resources.get(resource_type.to_plural()))
+        .and_then(|resource_config| resource_config.flatten);
+    let flatten = dep_flatten.or(tool_flatten).unwrap_or(false);
+
+    let base_target = if let Some(custom_target) =
+        manifest.get_dependency_target(dep, artifact_type, resource_type)
+    {
+        PathBuf::from(artifact_path.display().to_string())
+            .join(custom_target.trim_start_matches('/'))
+    } else {
+        artifact_path.to_path_buf()
+    };
+
+    let filename = repo_path.join(matched_path).to_string_lossy().to_string();
+    let relative_path = compute_relative_install_path(&base_target, Path::new(&filename), flatten);
+    Ok(normalize_path_for_storage(normalize_path(&base_target.join(relative_path))))
+}
+
 /// Resolves the installation path for merge-target resources (Hook, McpServer).
 ///
 /// These resources are not installed as files but are merged into configuration files.
@@ -519,8 +582,9 @@ pub fn resolve_merge_target_path(
 /// Handles agents, commands, snippets, and scripts by:
 /// 1. Getting the base artifact path from tool configuration
 /// 2. Applying custom target overrides if specified
-/// 3. Computing the relative path based on flatten behavior
-/// 4. Avoiding redundant directory prefixes
+/// 3. Adding the source's `namespace` directory, if it has one
+/// 4. Computing the relative path based on flatten behavior
+/// 5. Avoiding redundant directory prefixes
 ///
 /// # Arguments
 ///
@@ -551,7 +615,9 @@ pub fn resolve_regular_resource_path(
         })?;
 
     // Compute the final path
-    let path = if let Some(custom_target) = dep.get_target() {
+    let path = if let Some(custom_target) =
+        manifest.get_dependency_target(dep, artifact_type, resource_type)
+    {
         compute_custom_target_path(
             &artifact_path,
             custom_target,
@@ -595,7 +661,7 @@ fn compute_custom_target_path(
     // For custom targets, still strip prefix based on the original artifact path
     let relative_path =
         compute_relative_install_path(artifact_path, Path::new(source_filename), flatten);
-    base_target.join(relative_path)
+    base_target.join(source_namespace(manifest, dep)).join(relative_path)
 }
 
 /// Computes the installation path using the default artifact path.
@@ -610,7 +676,18 @@ fn compute_default_path(
     let flatten = get_flatten_behavior(manifest, dep, artifact_type, resource_type);
     let relative_path =
         compute_relative_install_path(artifact_path, Path::new(source_filename), flatten);
-    artifact_path.join(relative_path)
+    artifact_path.join(source_namespace(manifest, dep)).join(relative_path)
+}
+
+/// Returns the `namespace` directory of the dependency's source.
+///
+/// Empty for local dependencies and sources without a namespace, so joining
+/// it leaves the path unchanged.
+fn source_namespace(manifest: &Manifest, dep: &ResourceDependency) -> PathBuf {
+    dep.get_source()
+        .and_then(|source| manifest.sources.options(source).namespace)
+        .map(PathBuf::from)
+        .unwrap_or_default()
 }
 
 /// Creates a detailed error message when a resource type is not supported by a tool.
@@ -698,4 +775,78 @@ mod tests {
         let filename = extract_pattern_filename(&base, matched);
         assert_eq!(filename, "agents/helper.md");
     }
+
+    #[test]
+    fn test_resolve_install_path_with_source_namespace() {
+        let mut manifest = Manifest::new();
+        for source in ["official", "vendor"] {
+            manifest
+                .sources
+                .insert(source.to_string(), format!("https://example.com/{source}.git"));
+        }
+        manifest.sources.set_options(
+            "vendor",
+            crate::manifest::SourceOptions {
+                namespace: Some("vendor".to_string()),
+                ..crate::manifest::SourceOptions::default()
+            },
+        );
+
+        let install_path = |source: &str| {
+            let dep: ResourceDependency = toml::from_str(&format!(
+                "source = \"{source}\"\npath = \"agents/helper.md\"\nversion = \"v1.0.0\""
+            ))
+            .unwrap();
+            resolve_install_path(
+                &manifest,
+                &dep,
+                "claude-code",
+                ResourceType::Agent,
+                "agents/helper.md",
+            )
+            .unwrap()
+        };
+
+        assert_eq!(install_path("official"), ".claude/agents/helper.md");
+        assert_eq!(install_path("vendor"), ".claude/agents/vendor/helper.md");
+    }
+
+    #[test]
+    fn test_resolve_install_path_with_default_target() {
+        let manifest: Manifest = toml::from_str(
+            r#"
+            [tools.claude-code]
+            path = ".claude"
+            resources = { agents = { path = "agents", flatten = true, default-target = "community" } }
+            "#,
+        )
+        .unwrap();
+
+        let install_path = |extra: &str| {
+            let dep: ResourceDependency =
+                toml::from_str(&format!("path = \"../shared/helper.md\"\n{extra}")).unwrap();
+            resolve_install_path(
+                &manifest,
+                &dep,
+                "claude-code",
+                ResourceType::Agent,
+                "agents/helper.md",
+            )
+            .unwrap()
+        };
+
+        assert_eq!(install_path(""), ".claude/agents/community/helper.md");
+        assert_eq!(install_path("target = \"mine\""), ".claude/agents/mine/helper.md");
+        assert_eq!(
+            resolve_install_path(
+                &manifest,
+                &toml::from_str("path = \"commands/deploy.md\"").unwrap(),
+                "claude-code",
+                ResourceType::Command,
+                "commands/deploy.md",
+            )
+            .unwrap(),
+            ".claude/commands/deploy.md"
+        );
+    }
 }
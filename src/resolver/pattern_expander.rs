@@ -5,7 +5,6 @@
 //! dependencies. It supports both local and remote pattern resolution with
 //! proper path handling, dependency naming, and locked resource generation.
 
-use crate::git::GitRepo;
 use crate::manifest::{DetailedDependency, ResourceDependency};
 use crate::pattern::PatternResolver;
 use anyhow::{Context, Result};
@@ -134,6 +133,17 @@ async fn expand_local_pattern(
             flatten,
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         }));
 
         concrete_deps.push((dep_name, concrete_dep));
@@ -158,23 +168,25 @@ async fn expand_remote_pattern(
         .get_source_url(source_name)
         .with_context(|| format!("Source not found: {}", source_name))?;
 
-    // Get or clone the source repository
-    let repo_path = cache
-        .get_or_clone_source(source_name, &source_url, dep.get_version())
+    // Git repositories, or the provider registered for the source's URL scheme
+    let provider = crate::source::provider::provider_for(cache, &source_url);
+    provider
+        .fetch(source_name, &source_url)
         .await
         .with_context(|| format!("Failed to access source repository: {}", source_name))?;
 
-    let repo = GitRepo::new(&repo_path);
-
     // Resolve the version to a commit SHA
     let version = dep.get_version().unwrap_or("HEAD");
-    let commit_sha = repo.resolve_to_sha(Some(version)).await.with_context(|| {
-        format!("Failed to resolve version '{}' for source {}", version, source_name)
-    })?;
+    let commit_sha = provider
+        .resolve_version_to_commit(source_name, &source_url, Some(version))
+        .await
+        .with_context(|| {
+            format!("Failed to resolve version '{}' for source {}", version, source_name)
+        })?;
 
-    // Create a worktree for the specific commit
-    let worktree_path = cache
-        .get_or_create_worktree_for_sha(source_name, &source_url, &commit_sha, Some(version))
+    // Check out the specific commit
+    let worktree_path = provider
+        .checkout(source_name, &source_url, &commit_sha)
         .await
         .with_context(|| format!("Failed to create worktree for {}@{}", source_name, version))?;
 
@@ -215,6 +227,17 @@ async fn expand_remote_pattern(
             flatten,
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         }));
 
         concrete_deps.push((dep_name, concrete_dep));
@@ -421,6 +444,17 @@ mod tests {
             flatten: None,
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         }));
 
         // Test pattern expansion with local source context
@@ -97,7 +97,10 @@ impl ResourceFetchingService {
                 Self::read_with_cache_retry(&canonical_path).await
             }
             ResourceDependency::Detailed(detailed) => {
-                if let Some(source) = &detailed.source {
+                if let Some(inline) = &detailed.inline {
+                    // Inline resource - content lives in the manifest
+                    Ok(inline.clone())
+                } else if let Some(source) = &detailed.source {
                     // Git-backed dependency
                     // Use dep.get_version() to handle branch/rev/version precedence
                     let version_key = dep.get_version().unwrap_or("HEAD");
@@ -229,6 +229,17 @@ mod tests {
             flatten: None,
             install: None,
             template_vars: None,
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         }));
 
         let manifest_dir = Path::new("/project");
@@ -251,6 +262,17 @@ mod tests {
             flatten: None,
             install: None,
             template_vars: None,
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         }));
 
         let repo_root = Path::new("/repo");
@@ -285,6 +307,8 @@ mod tests {
             applied_patches: std::collections::BTreeMap::new(),
             install: None,
             variant_inputs: crate::resolver::lockfile_builder::VariantInputs::default(),
+            strip_metadata: false,
+            inline: None,
         };
 
         let manifest_dir = Path::new("/project");
@@ -93,6 +93,17 @@ async fn pre_sync_sources() -> Result<(), Box<dyn std::error::Error>> {
             flatten: None,
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         })),
         ResourceType::Agent,
     );
@@ -301,6 +312,8 @@ async fn update_specific_dependency() -> Result<(), Box<dyn std::error::Error>>
         applied_patches: std::collections::BTreeMap::new(),
         install: None,
         variant_inputs: VariantInputs::default(),
+        strip_metadata: false,
+        inline: None,
     });
 
     // Create the agent file
@@ -378,6 +391,17 @@ async fn version_constraint_resolution() -> Result<(), Box<dyn std::error::Error
             flatten: None,
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         })),
         ResourceType::Agent,
     );
@@ -431,6 +455,17 @@ async fn multi_tool_support() -> Result<(), Box<dyn std::error::Error>> {
             flatten: None,
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         })),
         ResourceType::Agent,
     );
@@ -452,6 +487,17 @@ async fn multi_tool_support() -> Result<(), Box<dyn std::error::Error>> {
             flatten: None,
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         })),
         ResourceType::Agent,
     );
@@ -571,6 +617,17 @@ async fn template_variable_inheritance() -> Result<(), Box<dyn std::error::Error
             flatten: None,
             install: None,
             template_vars: Some(json!({"local_var": "local_value"})),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         })),
         ResourceType::Agent,
     );
@@ -626,3 +683,91 @@ async fn patch_application() -> Result<(), Box<dyn std::error::Error>> {
     assert!(!entry.applied_patches.is_empty());
     Ok(())
 }
+
+#[test]
+fn conservative_update_pins_shared_transitive_dependencies() {
+    use crate::lockfile::LockedResourceBuilder;
+
+    let entry = |resource_type: ResourceType, name: &str, alias: Option<&str>, deps: &[&str]| {
+        LockedResourceBuilder::new(
+            name.to_string(),
+            format!("{name}.md"),
+            String::new(),
+            format!(".claude/{name}.md"),
+            resource_type,
+        )
+        .source(Some("community".to_string()))
+        .resolved_commit(Some(format!("{name}-commit")))
+        .manifest_alias(alias.map(ToString::to_string))
+        .dependencies(deps.iter().map(ToString::to_string).collect())
+        .build()
+    };
+
+    let mut lockfile = LockFile::new();
+    lockfile.agents.push(entry(
+        ResourceType::Agent,
+        "agents/a",
+        Some("a"),
+        &["snippet:snippets/shared", "snippet:snippets/only-a"],
+    ));
+    lockfile.agents.push(entry(
+        ResourceType::Agent,
+        "agents/b",
+        Some("b"),
+        &["community/snippet:snippets/shared@v1.0.0"],
+    ));
+    lockfile.snippets.push(entry(ResourceType::Snippet, "snippets/shared", None, &[]));
+    lockfile.snippets.push(entry(ResourceType::Snippet, "snippets/only-a", None, &[]));
+
+    let (unchanged, to_resolve, shared) =
+        DependencyResolver::partition_for_conservative_update(&lockfile, &["a".to_string()]);
+
+    assert_eq!(to_resolve, vec![("a".to_string(), ResourceType::Agent)]);
+    let kept: Vec<&str> = unchanged.all_resources().iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(kept, ["agents/b", "snippets/shared"]);
+    assert_eq!(shared.len(), 1);
+    assert_eq!(shared[0].name, "snippets/shared");
+}
+
+#[test]
+fn conservative_update_pins_shared_branch_and_rev_dependencies() {
+    use crate::lockfile::LockedResourceBuilder;
+    use crate::manifest::VersionOverrides;
+    use crate::resolver::types::ResolutionMode;
+
+    let shared = |name: &str| {
+        LockedResourceBuilder::new(
+            name.to_string(),
+            format!("{name}.md"),
+            String::new(),
+            format!(".claude/{name}.md"),
+            ResourceType::Snippet,
+        )
+        .source(Some("community".to_string()))
+        .resolved_commit(Some(format!("{}-commit", name.trim_start_matches("snippets/"))))
+        .build()
+    };
+
+    let mut overrides: VersionOverrides =
+        toml::from_str("[snippets]\nexplicit = \"v2.0.0\"\n").unwrap();
+    DependencyResolver::pin_shared_dependencies(
+        &mut overrides,
+        &[shared("snippets/tracked"), shared("snippets/explicit")],
+    );
+
+    // A shared dependency that tracks a branch or rev resolves to its locked commit
+    for (branch, rev) in [(Some("main"), None), (None, Some("abc1234"))] {
+        let mut detailed: DetailedDependency =
+            toml::from_str("source = \"community\"\npath = \"snippets/tracked.md\"\n").unwrap();
+        detailed.branch = branch.map(String::from);
+        detailed.rev = rev.map(String::from);
+        let mut dependency = ResourceDependency::Detailed(Box::new(detailed));
+
+        overrides.apply(ResourceType::Snippet, "snippets/tracked", &mut dependency);
+        assert_eq!(dependency.get_version(), Some("tracked-commit"));
+        assert_eq!(ResolutionMode::from_dependency(&dependency), ResolutionMode::Version);
+    }
+
+    // An explicit override is not replaced by the locked pin
+    assert_eq!(overrides.version_for(ResourceType::Snippet, "snippets/explicit"), Some("v2.0.0"));
+}
@@ -23,6 +23,7 @@ use crate::metadata::MetadataExtractor;
 /// * `worktree_path` - Path to the worktree containing the resource
 /// * `resource_path` - Relative path to the resource file within worktree
 /// * `variant_inputs` - Optional template variables for frontmatter rendering
+/// * `metadata_keys` - Additional frontmatter keys to read dependencies from
 ///
 /// # Returns
 ///
@@ -38,7 +39,7 @@ use crate::metadata::MetadataExtractor;
 /// let worktree = Path::new("/path/to/worktree");
 /// let resource = "agents/helper.md";
 ///
-/// let deps = extract_transitive_deps(worktree, resource, None).await?;
+/// let deps = extract_transitive_deps(worktree, resource, None, &[]).await?;
 /// for (resource_type, specs) in deps {
 ///     println!("{:?}: {} dependencies", resource_type, specs.len());
 /// }
@@ -49,6 +50,7 @@ pub async fn extract_transitive_deps(
     worktree_path: &Path,
     resource_path: &str,
     variant_inputs: Option<&serde_json::Value>,
+    metadata_keys: &[String],
 ) -> Result<HashMap<ResourceType, Vec<DependencySpec>>> {
     // Build full path to the resource file
     let file_path = worktree_path.join(resource_path);
@@ -59,8 +61,14 @@ pub async fn extract_transitive_deps(
         .with_context(|| format!("Failed to read resource file: {}", file_path.display()))?;
 
     // Extract metadata (no operation context needed for backtracking)
-    let metadata = MetadataExtractor::extract(&file_path, &content, variant_inputs, None)
-        .with_context(|| format!("Failed to extract metadata from: {}", file_path.display()))?;
+    let metadata = MetadataExtractor::extract_with_keys(
+        &file_path,
+        &content,
+        variant_inputs,
+        metadata_keys,
+        None,
+    )
+    .with_context(|| format!("Failed to extract metadata from: {}", file_path.display()))?;
 
     // Get typed dependencies (with ResourceType keys)
     let deps = metadata.get_dependencies_typed().unwrap_or_default();
@@ -105,7 +113,7 @@ dependencies:
 
         tokio::fs::write(&file_path, content).await.unwrap();
 
-        let deps = extract_transitive_deps(temp_dir.path(), "test.md", None).await.unwrap();
+        let deps = extract_transitive_deps(temp_dir.path(), "test.md", None, &[]).await.unwrap();
 
         assert_eq!(deps.len(), 2);
         assert!(deps.contains_key(&ResourceType::Agent));
@@ -125,7 +133,7 @@ dependencies:
         let content = "# Simple Agent\n\nNo dependencies here.";
         tokio::fs::write(&file_path, content).await.unwrap();
 
-        let deps = extract_transitive_deps(temp_dir.path(), "test.md", None).await.unwrap();
+        let deps = extract_transitive_deps(temp_dir.path(), "test.md", None, &[]).await.unwrap();
 
         assert_eq!(deps.len(), 0);
     }
@@ -149,7 +157,7 @@ dependencies:
 
         tokio::fs::write(&file_path, content).await.unwrap();
 
-        let deps = extract_transitive_deps(temp_dir.path(), "test.json", None).await.unwrap();
+        let deps = extract_transitive_deps(temp_dir.path(), "test.json", None, &[]).await.unwrap();
 
         assert_eq!(deps.len(), 1);
         assert!(deps.contains_key(&ResourceType::Agent));
@@ -163,7 +171,7 @@ dependencies:
     async fn test_extract_nonexistent_file() {
         let temp_dir = TempDir::new().unwrap();
 
-        let result = extract_transitive_deps(temp_dir.path(), "nonexistent.md", None).await;
+        let result = extract_transitive_deps(temp_dir.path(), "nonexistent.md", None, &[]).await;
 
         assert!(result.is_err());
     }
@@ -40,6 +40,12 @@ use super::types::{
 use super::version_resolver::{PreparedSourceVersion, VersionResolutionService};
 use super::{PatternExpansionService, ResourceFetchingService, is_file_relative_path};
 
+/// Default limit on the length of a transitive dependency chain.
+///
+/// Counts the dependencies below a manifest dependency, so a manifest
+/// dependency's own dependencies are at depth 1.
+pub const DEFAULT_MAX_DEPTH: usize = 32;
+
 /// Container for resolution services to reduce parameter count.
 pub struct ResolutionServices<'a> {
     /// Service for version resolution and commit SHA lookup
@@ -58,6 +64,8 @@ pub struct TransitiveResolutionParams<'a> {
     pub base_deps: &'a [(String, ResourceDependency, ResourceType)],
     /// Whether transitive resolution is enabled
     pub enable_transitive: bool,
+    /// Longest dependency chain below a manifest dependency before resolution fails
+    pub max_depth: usize,
     /// Pre-prepared source versions for resolution (concurrent)
     pub prepared_versions: &'a Arc<DashMap<String, PreparedSourceVersion>>,
     /// Map for pattern aliases (concurrent)
@@ -145,6 +153,13 @@ struct TransitiveSharedState<'a> {
     dependency_map: &'a Arc<DashMap<DependencyKey, Vec<String>>>,
     custom_names: &'a Arc<DashMap<DependencyKey, String>>,
     prepared_versions: &'a Arc<DashMap<String, PreparedSourceVersion>>,
+    /// Whether discovered dependencies are queued for resolution; when `false`
+    /// they are only recorded as dependencies of the resource that declares them
+    follow_discovered: bool,
+    /// Chain of resources from a manifest dependency to each queued transitive
+    /// dependency, used to enforce `max_depth`
+    chains: Arc<DashMap<DependencyKey, Vec<String>>>,
+    max_depth: usize,
 }
 
 /// Resolution context and services.
@@ -402,8 +417,8 @@ fn create_path_only_transitive_dep(
             utils::compute_relative_path(&canonical_manifest, trans_canonical)
         }
         Err(e) => {
-            eprintln!(
-                "Warning: Could not canonicalize manifest directory {}: {}. Using non-canonical path.",
+            tracing::warn!(
+                "Could not canonicalize manifest directory {}: {}. Using non-canonical path.",
                 manifest_dir.display(),
                 e
             );
@@ -438,6 +453,17 @@ fn create_path_only_transitive_dep(
             ctx.base.manifest,
             parent_dep,
         )),
+        env: None,
+        transport: None,
+        url: None,
+        as_of: None,
+        post_install: None,
+        variants: None,
+        strip_metadata: None,
+        inline: None,
+        expect_commit: None,
+        enabled: None,
+        git: None,
     })))
 }
 
@@ -500,6 +526,17 @@ async fn create_git_backed_transitive_dep(
             ctx.base.manifest,
             parent_dep,
         )),
+        env: None,
+        transport: None,
+        url: None,
+        as_of: None,
+        post_install: None,
+        variants: None,
+        strip_metadata: None,
+        inline: None,
+        expect_commit: None,
+        enabled: None,
+        git: None,
     })))
 }
 
@@ -827,6 +864,9 @@ async fn process_single_transitive_dependency<'a>(
                         concrete_variant_hash.clone(),
                     );
 
+                    if let Some(chain) = ctx.shared.chains.get(&key).map(|c| c.clone()) {
+                        ctx.shared.chains.insert(concrete_key.clone(), chain);
+                    }
                     if let dashmap::mapref::entry::Entry::Vacant(e) =
                         ctx.shared.all_deps.entry(concrete_key)
                     {
@@ -895,10 +935,11 @@ async fn process_single_transitive_dependency<'a>(
 
     // Extract metadata from the resource with complete variant_inputs
     let path = PathBuf::from(ctx.input.dep.get_path());
-    let metadata = MetadataExtractor::extract(
+    let metadata = MetadataExtractor::extract_with_keys(
         &path,
         &content,
         variant_inputs,
+        &ctx.resolution.ctx_base.manifest.metadata_keys,
         ctx.resolution.ctx_base.operation_context.map(|arc| arc.as_ref()),
     )?;
 
@@ -934,7 +975,7 @@ async fn process_single_transitive_dependency<'a>(
                 };
 
                 // Process each transitive dependency spec
-                let (trans_dep, trans_name) =
+                let (mut trans_dep, trans_name) =
                     process_transitive_dependency_spec(TransitiveDepProcessingParams {
                         ctx: &temp_ctx,
                         core: ctx.resolution.core,
@@ -948,6 +989,19 @@ async fn process_single_transitive_dependency<'a>(
                     })
                     .await?;
 
+                // Pin the version from [override] before it is resolved to a commit
+                if let Some(version) = ctx.resolution.ctx_base.manifest.version_overrides.apply(
+                    dep_resource_type,
+                    &trans_name,
+                    &mut trans_dep,
+                ) {
+                    tracing::debug!(
+                        "[TRANSITIVE] Overriding version of '{}' to '{}'",
+                        trans_name,
+                        version
+                    );
+                }
+
                 let trans_source = trans_dep.get_source().map(std::string::ToString::to_string);
                 let trans_tool = trans_dep.get_tool().map(std::string::ToString::to_string);
                 let trans_variant_hash = compute_dependency_variant_hash(&trans_dep);
@@ -980,6 +1034,8 @@ async fn process_single_transitive_dependency<'a>(
                     &trans_name,
                     trans_source.clone(),
                 );
+                let to_display = to_node.display_name();
+                let from_display = from_node.display_name();
                 ctx.shared.graph.lock().unwrap().add_dependency(from_node, to_node);
 
                 // Track in dependency map
@@ -1025,10 +1081,32 @@ async fn process_single_transitive_dependency<'a>(
                     ctx.input.name
                 );
 
+                // With transitive resolution disabled, only declared resources are scanned
+                if !ctx.shared.follow_discovered {
+                    continue;
+                }
+
                 // Check if we already have this dependency
                 if let dashmap::mapref::entry::Entry::Vacant(e) =
-                    ctx.shared.all_deps.entry(trans_key)
+                    ctx.shared.all_deps.entry(trans_key.clone())
                 {
+                    // Bound the chain from the manifest dependency to this one
+                    let mut chain = ctx
+                        .shared
+                        .chains
+                        .get(&key)
+                        .map_or_else(|| vec![from_display], |chain| chain.clone());
+                    chain.push(to_display);
+                    if chain.len() - 1 > ctx.shared.max_depth {
+                        anyhow::bail!(
+                            "Transitive dependency chain exceeds the maximum depth of {}: {}\n\
+                             Use --max-depth to raise the limit if this chain is intended",
+                            ctx.shared.max_depth,
+                            chain.join(" → ")
+                        );
+                    }
+                    ctx.shared.chains.insert(trans_key, chain);
+
                     // No conflict, add the dependency
                     tracing::debug!(
                         "Adding transitive dep '{}' (parent: {})",
@@ -1082,6 +1160,7 @@ pub async fn resolve_with_services(
         core,
         base_deps,
         enable_transitive,
+        max_depth,
         prepared_versions,
         pattern_alias_map,
         services,
@@ -1090,13 +1169,14 @@ pub async fn resolve_with_services(
     // Clear state from any previous resolution
     ctx.dependency_map.clear();
 
-    if !enable_transitive {
-        return Ok(base_deps.to_vec());
-    }
+    // Without transitive resolution the declared resources (and pattern matches)
+    // are still scanned, so their lockfile entries list the dependencies they
+    // declare, but nothing they declare is queued
 
     let graph = Arc::new(Mutex::new(DependencyGraph::new()));
     let all_deps: Arc<DashMap<DependencyKey, ResourceDependency>> = Arc::new(DashMap::new());
     let processed: Arc<DashMap<DependencyKey, ()>> = Arc::new(DashMap::new()); // Simulates HashSet
+    let chains: Arc<DashMap<DependencyKey, Vec<String>>> = Arc::new(DashMap::new());
 
     // Type alias to reduce complexity
     type QueueItem = (String, ResourceDependency, Option<ResourceType>, String);
@@ -1171,6 +1251,7 @@ pub async fn resolve_with_services(
                 let all_deps_clone = Arc::clone(&all_deps);
                 let processed_clone = Arc::clone(&processed);
                 let queue_clone = Arc::clone(&queue);
+                let chains_clone = Arc::clone(&chains);
                 let pattern_alias_map_clone = Arc::clone(pattern_alias_map);
                 let progress_clone = progress.clone();
                 let counter_clone = Arc::clone(&completed_counter);
@@ -1201,6 +1282,9 @@ pub async fn resolve_with_services(
                             dependency_map: dependency_map_clone,
                             custom_names: custom_names_clone,
                             prepared_versions: &prepared_versions_clone,
+                            follow_discovered: enable_transitive,
+                            chains: chains_clone,
+                            max_depth,
                         },
                         resolution: TransitiveResolutionContext {
                             ctx_base,
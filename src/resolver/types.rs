@@ -197,6 +197,9 @@ pub struct ManifestOverride {
     /// Install flag override
     pub install: Option<bool>,
 
+    /// Frontmatter stripping override
+    pub strip_metadata: Option<bool>,
+
     /// Manifest alias (for reference)
     pub manifest_alias: Option<String>,
 
@@ -323,6 +326,10 @@ pub fn apply_manifest_override(
             detailed.install = Some(install);
         }
 
+        if let Some(strip_metadata) = override_info.strip_metadata {
+            detailed.strip_metadata = Some(strip_metadata);
+        }
+
         // Replace template vars with manifest version for consistent rendering
         if let Some(template_vars) = &override_info.template_vars {
             detailed.template_vars = Some(template_vars.clone());
@@ -626,6 +633,17 @@ pub fn format_dependency_without_version(resource_type: ResourceType, name: &str
 ///     flatten: None,
 ///     install: None,
 ///     template_vars: None,
+///     env: None,
+///     transport: None,
+///     url: None,
+///     as_of: None,
+///     post_install: None,
+///     variants: None,
+///     strip_metadata: None,
+///     inline: None,
+///     expect_commit: None,
+///     enabled: None,
+///     git: None,
 /// }));
 ///
 /// let hash = compute_dependency_variant_hash(&dep);
@@ -743,6 +761,17 @@ mod tests {
             flatten: None,
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         }));
         let resource_id = build_resource_id(&dep);
         assert!(resource_id.contains("agents/helper.md"));
@@ -16,6 +16,14 @@
 //! 2. **Resolution Phase**: Batch resolve all versions to SHAs
 //!
 //! This design minimizes Git operations and enables parallel resolution.
+//!
+//! # Signed Tags
+//!
+//! Sources with `verify_signatures = true`, or every source when
+//! [`require_tag_signatures`] was called for `--verify-signatures`, have the
+//! tags their versions resolve to checked with `git verify-tag`. Resolution
+//! fails on an unsigned tag or a signature that does not verify; branch and
+//! commit versions are not checked.
 
 use anyhow::{Context, Result};
 use dashmap::DashMap;
@@ -29,6 +37,7 @@ use crate::cache::Cache;
 use crate::git::GitRepo;
 use crate::manifest::ResourceDependency;
 use crate::source::SourceManager;
+use crate::version::tag_filter::TagFilter;
 
 /// Version resolution entry tracking source and version to SHA mapping
 #[derive(Debug, Clone)]
@@ -45,6 +54,8 @@ pub struct VersionEntry {
     pub resolved_version: Option<String>,
     /// Resolution mode used for this entry
     pub resolution_mode: ResolutionMode,
+    /// Date the branch is pinned to (`as_of`); resolves to the last commit on or before it
+    pub as_of: Option<String>,
 }
 
 impl VersionEntry {
@@ -64,6 +75,7 @@ impl VersionEntry {
     ///     resolved_sha: None,
     ///     resolved_version: None,
     ///     resolution_mode: ResolutionMode::Version,
+    ///     as_of: None,
     /// };
     /// assert_eq!(entry.format_display(), "community@v1.0.0");
     /// ```
@@ -133,6 +145,14 @@ pub struct VersionResolver {
     resolved: Arc<DashMap<(String, String), ResolvedVersion>>,
     /// Bare repository paths, keyed by source name
     bare_repos: Arc<DashMap<String, PathBuf>>,
+    /// Tag filters for sources that declare `tag_prefix` or `tag_pattern`
+    tag_filters: Arc<DashMap<String, TagFilter>>,
+    /// Sources whose tags must be signed, with their allowed signers file
+    signature_checks: Arc<DashMap<String, Option<PathBuf>>>,
+    /// Commits and signers of verified tags, keyed by (source, tag)
+    verified_tags: Arc<DashMap<(String, String), (String, Option<String>)>>,
+    /// Whether pre-sync fetches every ref, even for sources pinned to exact versions
+    fetch_all_refs: bool,
     /// Maximum concurrency for parallel version resolution
     max_concurrency: usize,
 }
@@ -150,6 +170,10 @@ impl VersionResolver {
             entries: Arc::new(DashMap::new()),
             resolved: Arc::new(DashMap::new()),
             bare_repos: Arc::new(DashMap::new()),
+            tag_filters: Arc::new(DashMap::new()),
+            signature_checks: Arc::new(DashMap::new()),
+            verified_tags: Arc::new(DashMap::new()),
+            fetch_all_refs: false,
             max_concurrency: default_concurrency,
         }
     }
@@ -161,10 +185,62 @@ impl VersionResolver {
             entries: Arc::new(DashMap::new()),
             resolved: Arc::new(DashMap::new()),
             bare_repos: Arc::new(DashMap::new()),
+            tag_filters: Arc::new(DashMap::new()),
+            signature_checks: Arc::new(DashMap::new()),
+            verified_tags: Arc::new(DashMap::new()),
+            fetch_all_refs: false,
             max_concurrency,
         }
     }
 
+    /// Restricts version resolution for `source` to the tags selected by `filter`.
+    ///
+    /// Constraints are matched against the version part the filter extracts,
+    /// and the original tag name is what the version resolves to.
+    pub fn set_tag_filter(&self, source: &str, filter: TagFilter) {
+        self.tag_filters.insert(source.to_string(), filter);
+    }
+
+    /// Returns the tag filter configured for `source`, if any.
+    pub fn tag_filter(&self, source: &str) -> Option<TagFilter> {
+        self.tag_filters.get(source).map(|filter| filter.clone())
+    }
+
+    /// Requires the tags `source` versions resolve to to carry a valid signature.
+    ///
+    /// SSH signatures are checked against `allowed_signers` when given.
+    pub fn set_signature_check(&self, source: &str, allowed_signers: Option<PathBuf>) {
+        self.signature_checks.insert(source.to_string(), allowed_signers);
+    }
+
+    /// Returns whether tags of `source` must carry a valid signature.
+    pub fn has_signature_check(&self, source: &str) -> bool {
+        self.signature_checks.contains_key(source)
+    }
+
+    /// Fetches every branch and tag of each source during pre-sync.
+    ///
+    /// Commands that look beyond the versions in the manifest, such as
+    /// `agpm outdated` listing the newest tags, need this.
+    pub fn set_fetch_all_refs(&mut self, fetch_all_refs: bool) {
+        self.fetch_all_refs = fetch_all_refs;
+    }
+
+    /// Returns the tags verified for `source` so far as `(tag, commit, signer)`.
+    pub fn verified_tags(&self, source: &str) -> Vec<(String, String, Option<String>)> {
+        let mut tags: Vec<_> = self
+            .verified_tags
+            .iter()
+            .filter(|entry| entry.key().0 == source)
+            .map(|entry| {
+                let (commit, signer) = entry.value().clone();
+                (entry.key().1.clone(), commit, signer)
+            })
+            .collect();
+        tags.sort();
+        tags
+    }
+
     /// Adds a version to be resolved
     ///
     /// Multiple calls with the same (source, version) pair will be deduplicated.
@@ -193,9 +269,21 @@ impl VersionResolver {
             resolved_sha: None,
             resolved_version: None,
             resolution_mode,
+            as_of: None,
         });
     }
 
+    /// Pins a collected branch version to its last commit on or before `as_of`.
+    ///
+    /// Must be called after [`add_version`](Self::add_version) for the same
+    /// source and version.
+    pub fn set_as_of(&self, source: &str, version: Option<&str>, as_of: &str) {
+        let key = (source.to_string(), version.unwrap_or("HEAD").to_string());
+        if let Some(mut entry) = self.entries.get_mut(&key) {
+            entry.as_of = Some(as_of.to_string());
+        }
+    }
+
     /// Resolves all collected versions to their commit SHAs using cached repositories.
     ///
     /// This is the second phase of AGPM's two-phase resolution architecture. Call after `pre_sync_sources()`.
@@ -250,52 +338,52 @@ impl VersionResolver {
         // Thread-safe counter for completed versions
         let completed_counter = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
 
-        // Process each source
-        for (source, versions) in by_source {
-            // Repository must have been pre-synced
-            let repo_path = self
-                .bare_repos
-                .get(&source)
-                .ok_or_else(|| {
-                    anyhow::anyhow!("Repository for source '{source}' was not pre-synced. Call pre_sync_sources() first.")
-                })?
-                .clone();
-
-            let repo = GitRepo::new(&repo_path);
-
-            // Pre-fetch tags once per source if any version uses constraints
-            // This optimization avoids repeated git tag -l calls for the same repository
-            let needs_tags = versions.iter().any(|(_, entry)| {
-                !crate::utils::is_local_path(&entry.url)
-                    && entry.version.as_ref().is_some_and(|v| is_version_constraint(v))
-            });
-
-            let tags_cache = if needs_tags {
-                let tags = repo.list_tags().await.unwrap_or_default();
-                if tags.is_empty() {
-                    return Err(anyhow::anyhow!(
-                        "No tags found in repository '{source}' but version constraints require tags"
-                    ));
+        // Prepare all sources concurrently: each needs its pre-synced repository
+        // and, for constraints or tag filters, its tag list.
+        let concurrency = self.max_concurrency.max(1);
+        let prepared = stream::iter(by_source)
+            .map(|(source, versions)| async move {
+                let prepared = self.prepare_source(&source, &versions).await;
+                (source, versions, prepared)
+            })
+            .buffer_unordered(concurrency)
+            .collect::<Vec<_>>()
+            .await;
+
+        // A failing source must not hide failures in other sources
+        let mut failures = Vec::new();
+        let mut work = Vec::new();
+        for (source, versions, prepared) in prepared {
+            match prepared {
+                Ok((repo_path, tags_cache)) => {
+                    let tags_cache = tags_cache.map(Arc::new);
+                    let has_tag_filter = self.tag_filter(&source).is_some();
+                    for (version_str, entry) in versions {
+                        work.push((
+                            source.clone(),
+                            repo_path.clone(),
+                            tags_cache.clone(),
+                            has_tag_filter,
+                            version_str,
+                            entry,
+                        ));
+                    }
                 }
-                Some(tags)
-            } else {
-                None
-            };
-
-            // Resolve each version for this source in parallel
-            // Use configured concurrency limit to avoid overwhelming git processes
-            let concurrency = std::cmp::min(self.max_concurrency, versions.len());
-
-            let resolved_versions = stream::iter(versions)
-                .map(|(version_str, entry)| {
-                    let repo_path = repo_path.clone();
-                    let source = source.clone();
-                    let tags_cache = tags_cache.clone();
-                    let progress = progress.clone();
-                    let completed_counter = completed_counter.clone();
-                    let total = total_versions;
+                Err(e) => failures.push((source, e)),
+            }
+        }
 
-                    async move {
+        // Resolve versions of all sources through one bounded queue, so a source
+        // with many versions does not hold up the others.
+        // Use configured concurrency limit to avoid overwhelming git processes
+        let resolved_versions = stream::iter(work)
+            .map(|(source, repo_path, tags_cache, has_tag_filter, version_str, entry)| {
+                let progress = progress.clone();
+                let completed_counter = completed_counter.clone();
+                let total = total_versions;
+
+                async move {
+                    let result = async {
                         // Mark this version as active in the progress window
                         if let Some(ref pm) = progress {
                             let display = entry.format_display();
@@ -307,25 +395,51 @@ impl VersionResolver {
                         let repo = GitRepo::new(&repo_path);
                         // Check if this is a local directory source (not a Git repository)
                         let is_local = crate::utils::is_local_path(&entry.url);
+                        // Sources with a registered provider resolve through it instead of Git
+                        let provider = crate::source::provider::for_url(&entry.url);
 
                         // For local directory sources, we don't resolve versions - just use "local"
                         let resolved_ref = if is_local {
                             "local".to_string()
                         } else if let Some(ref version) = entry.version {
-                            // First check if this is a version constraint
-                            if is_version_constraint(version) {
+                            if crate::version::is_latest_keyword(version) {
+                                // Highest semver tag, or the default branch if there is none
+                                let best = tags_cache.as_deref().and_then(|tags| {
+                                    let version_parts = tags.iter().map(|(_, part)| part.clone()).collect();
+                                    find_best_matching_tag(version, version_parts)
+                                        .ok()
+                                        .map(|best| original_tag(tags, &best))
+                                });
+                                match best {
+                                    Some(tag) => tag,
+                                    None => {
+                                        let branch = repo.get_default_branch().await.unwrap_or_else(|_| "main".to_string());
+                                        tracing::warn!(
+                                            "Source '{source}' has no semver tags for version '{version}', using HEAD of branch '{branch}'"
+                                        );
+                                        branch
+                                    }
+                                }
+                            } else if is_version_constraint(version) {
                                 // Use pre-fetched tags from cache
                                 let tags = tags_cache.as_ref().ok_or_else(|| {
                                     anyhow::anyhow!("Tags should have been pre-fetched for constraint '{version}'")
                                 })?;
 
-                                // Find best matching tag
-                                find_best_matching_tag(version, tags.clone())
-                                    .with_context(|| format!("Failed to resolve version constraint '{version}' for source '{source}'"))?
+                                // Find best matching tag by version part, then map back to the tag
+                                let version_parts = tags.iter().map(|(_, part)| part.clone()).collect();
+                                let best = find_best_matching_tag(version, version_parts)
+                                    .with_context(|| format!("Failed to resolve version constraint '{version}' for source '{source}'"))?;
+                                original_tag(tags, &best)
+                            } else if has_tag_filter {
+                                // Exact version part of a filtered tag, or a branch/commit as-is
+                                tags_cache.as_deref().map_or_else(|| version.clone(), |tags| original_tag(tags, version))
                             } else {
                                 // Not a constraint, use as-is
                                 version.clone()
                             }
+                        } else if provider.is_some() {
+                            "HEAD".to_string()
                         } else {
                             // No version specified for Git source, resolve HEAD to actual branch name
                             repo.get_default_branch().await.unwrap_or_else(|_| "main".to_string())
@@ -343,12 +457,19 @@ impl VersionResolver {
                                 version_str,
                                 resolved_ref
                             );
-                            let resolved_sha =
-                                repo.resolve_to_sha(Some(&resolved_ref)).await.with_context(|| {
-                                    format!(
-                                        "Failed to resolve version '{version_str}' for source '{source}'"
-                                    )
-                                })?;
+                            let resolved_sha = match (&provider, &entry.as_of) {
+                                (Some(provider), _) => {
+                                    let version = entry.version.as_ref().map(|_| resolved_ref.as_str());
+                                    provider.resolve_version_to_commit(&source, &entry.url, version).await
+                                }
+                                (None, Some(as_of)) => repo.commit_before(&resolved_ref, as_of).await,
+                                (None, None) => repo.resolve_to_sha(Some(&resolved_ref)).await,
+                            }
+                            .with_context(|| {
+                                format!(
+                                    "Failed to resolve version '{version_str}' for source '{source}'"
+                                )
+                            })?;
                             tracing::debug!(
                                 "RESOLVE: source='{}' version='{}' resolved_ref='{}' -> SHA={}",
                                 source,
@@ -359,6 +480,22 @@ impl VersionResolver {
                             Some(resolved_sha)
                         };
 
+                        // Signed tags: only tags are checked, never branches or commits
+                        let signature_check = self.signature_checks.get(&source).map(|check| check.clone());
+                        let check_signature = !is_local && provider.is_none() && signature_check.is_some();
+                        let signer = if check_signature && repo.is_tag(&resolved_ref).await {
+                            let allowed_signers = signature_check.flatten();
+                            let signer = repo
+                                .verify_tag(&resolved_ref, allowed_signers.as_deref())
+                                .await
+                                .with_context(|| {
+                                    format!("Signature verification failed for source '{source}'")
+                                })?;
+                            Some(signer)
+                        } else {
+                            None
+                        };
+
                         // Mark this version as complete in the progress window
                         if let Some(ref pm) = progress {
                             let completed = completed_counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
@@ -367,37 +504,114 @@ impl VersionResolver {
                             pm.mark_item_complete(&key, Some(&display), completed, total, "Resolving dependencies");
                         }
 
-                        Ok::<_, anyhow::Error>((version_str, resolved_ref, sha))
+                        Ok::<_, anyhow::Error>((version_str, resolved_ref, sha, signer))
                     }
-                })
-                .buffer_unordered(concurrency)
-                .collect::<Vec<_>>()
-                .await;
-
-            // Store all resolved versions
-            for result in resolved_versions {
-                let (version_str, resolved_ref, sha) = result?;
-                let key = (source.clone(), version_str);
-
-                // Only insert into resolved map if we have a SHA (Git sources only)
-                if let Some(sha_value) = sha {
-                    self.resolved.insert(
-                        key,
-                        ResolvedVersion {
-                            sha: sha_value,
-                            resolved_ref,
-                        },
-                    );
+                    .await;
+                    (source, result)
+                }
+            })
+            .buffer_unordered(concurrency)
+            .collect::<Vec<_>>()
+            .await;
+
+        // Store all resolved versions
+        for (source, result) in resolved_versions {
+            let (version_str, resolved_ref, sha, signer) = match result {
+                Ok(resolved) => resolved,
+                Err(e) => {
+                    failures.push((source, e));
+                    continue;
                 }
+            };
+            if let (Some(signer), Some(sha_value)) = (signer, &sha) {
+                self.verified_tags
+                    .insert((source.clone(), resolved_ref.clone()), (sha_value.clone(), signer));
+            }
+            let key = (source, version_str);
+
+            // Only insert into resolved map if we have a SHA (Git sources only)
+            if let Some(sha_value) = sha {
+                self.resolved.insert(
+                    key,
+                    ResolvedVersion {
+                        sha: sha_value,
+                        resolved_ref,
+                    },
+                );
             }
         }
 
+        collect_source_failures(failures)?;
+
         // Note: Progress phase is NOT completed here - it continues through
         // conflict detection and will be completed at the end of resolve_with_options()
 
         Ok(())
     }
 
+    /// Looks up a source's pre-synced repository and lists its tags if needed.
+    ///
+    /// Returns the repository path and, when any version is a constraint or the
+    /// source has a tag filter, its `(original tag, version part)` pairs.
+    async fn prepare_source(
+        &self,
+        source: &str,
+        versions: &[(String, VersionEntry)],
+    ) -> Result<(PathBuf, Option<Vec<(String, String)>>)> {
+        // Repository must have been pre-synced
+        let repo_path = self
+            .bare_repos
+            .get(source)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Repository for source '{source}' was not pre-synced. Call pre_sync_sources() first."
+                )
+            })?
+            .clone();
+
+        let repo = GitRepo::new(&repo_path);
+
+        // Pre-fetch tags once per source if any version uses constraints
+        // This optimization avoids repeated git tag -l calls for the same repository
+        let constraints: Vec<&str> = versions
+            .iter()
+            .filter(|(_, entry)| !crate::utils::is_local_path(&entry.url))
+            .filter_map(|(_, entry)| entry.version.as_deref())
+            .filter(|v| is_version_constraint(v))
+            .collect();
+        let needs_tags = !constraints.is_empty();
+        // `latest` falls back to the default branch, so only real constraints require tags
+        let requires_tags = constraints.iter().any(|v| !crate::version::is_latest_keyword(v));
+
+        // With a tag filter, exact versions name the version part of a tag too,
+        // so tags are needed to map them back to the original tag name.
+        let tag_filter = self.tag_filter(source);
+
+        // (original tag, version part) pairs; identical unless a tag filter applies
+        let tags_cache = if needs_tags || tag_filter.is_some() {
+            let provider = versions
+                .first()
+                .and_then(|(_, entry)| crate::source::provider::for_url(&entry.url));
+            let tags = match provider {
+                Some(provider) => provider.list_versions(source, &versions[0].1.url).await?,
+                None => repo.list_tags().await.unwrap_or_default(),
+            };
+            if requires_tags && tags.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "No tags found in repository '{source}' but version constraints require tags"
+                ));
+            }
+            Some(match &tag_filter {
+                Some(filter) => filter.apply(tags),
+                None => tags.into_iter().map(|tag| (tag.clone(), tag)).collect(),
+            })
+        } else {
+            None
+        };
+
+        Ok((repo_path, tags_cache))
+    }
+
     /// Resolves a single version to SHA without affecting the batch
     ///
     /// This is useful for incremental resolution or testing.
@@ -517,6 +731,9 @@ impl VersionResolver {
     /// - Repository cloning or fetching fails (network, auth, invalid URL)
     /// - Authentication fails for private repositories
     /// - Insufficient disk space or repository corruption
+    ///
+    /// All sources are attempted before returning; the error lists every source
+    /// that failed.
     pub async fn pre_sync_sources(&self) -> Result<()> {
         // Group entries by source to get unique sources
         let mut unique_sources: HashMap<String, String> = HashMap::new();
@@ -526,20 +743,68 @@ impl VersionResolver {
             unique_sources.insert(entry.source.clone(), entry.url.clone());
         }
 
-        // Pre-sync each unique source
-        for (source, url) in unique_sources {
-            // Clone or update the repository (this does the actual Git operations)
-            let repo_path = self
-                .cache
-                .get_or_clone_source(&source, &url, None)
-                .await
-                .with_context(|| format!("Failed to sync repository for source '{source}'"))?;
-
-            // Store bare repo path for later use in resolve_all
-            self.bare_repos.insert(source.clone(), repo_path);
+        // Clone or fetch distinct sources concurrently. Sources sharing a repository
+        // are serialized by the cache's per-repository fetch locks.
+        let concurrency = self.max_concurrency.clamp(1, unique_sources.len().max(1));
+        let results: Vec<(String, Result<PathBuf>)> = stream::iter(unique_sources)
+            .map(|(source, url)| async move {
+                let result = match self.refs_to_fetch(&source) {
+                    Some(refs) => {
+                        self.cache.get_or_clone_source_for_refs(&source, &url, &refs).await
+                    }
+                    None => self.cache.get_or_clone_source(&source, &url, None).await,
+                }
+                .with_context(|| format!("Failed to sync repository for source '{source}'"));
+                (source, result)
+            })
+            .buffer_unordered(concurrency)
+            .collect()
+            .await;
+
+        // Let every source finish before failing, so all broken sources are reported
+        let mut failures = Vec::new();
+        for (source, result) in results {
+            match result {
+                // Store bare repo path for later use in resolve_all
+                Ok(repo_path) => {
+                    self.bare_repos.insert(source, repo_path);
+                }
+                Err(e) => failures.push((source, e)),
+            }
         }
 
-        Ok(())
+        collect_source_failures(failures)
+    }
+
+    /// The refs `source` needs fetched, or `None` if resolving it needs every ref.
+    ///
+    /// Exact tags, branches and full commit SHAs can be fetched on their own.
+    /// Constraints and `latest` select from all tags, a missing version resolves
+    /// the default branch, abbreviated SHAs cannot be fetched by name, and tag
+    /// filters map versions back to tags, so any of those requires a full fetch.
+    pub(crate) fn refs_to_fetch(&self, source: &str) -> Option<Vec<String>> {
+        if self.fetch_all_refs || self.tag_filters.contains_key(source) {
+            return None;
+        }
+
+        let mut refs = Vec::new();
+        for entry_ref in self.entries.iter() {
+            let entry = entry_ref.value();
+            if entry.source != source {
+                continue;
+            }
+            let version = entry.version.as_deref().filter(|v| *v != "HEAD")?;
+            let short_sha = version.len() < 40
+                && version.len() >= 7
+                && version.chars().all(|c| c.is_ascii_hexdigit());
+            if is_version_constraint(version) || short_sha {
+                return None;
+            }
+            refs.push(version.to_string());
+        }
+        refs.sort();
+        refs.dedup();
+        Some(refs)
     }
 
     /// Gets the bare repository path for a source
@@ -563,6 +828,7 @@ impl VersionResolver {
         self.entries.clear();
         self.resolved.clear();
         self.bare_repos.clear();
+        self.verified_tags.clear();
     }
 
     /// Returns the number of unique versions to resolve
@@ -640,7 +906,7 @@ impl VersionResolutionService {
                     || v.starts_with('<')
                     || v.starts_with('=')
                     || v.starts_with('v')
-                    || v == "latest"
+                    || crate::version::is_latest_keyword(v)
                 {
                     ResolutionMode::Version
                 } else {
@@ -668,6 +934,57 @@ impl VersionResolutionService {
         }
     }
 
+    /// Apply per-source settings that affect version resolution.
+    ///
+    /// Sources declaring `tag_prefix` or `tag_pattern` only resolve versions
+    /// from the tags their filter selects. Sources with `verify_signatures`
+    /// have their tags verified, using an allowed signers file resolved against
+    /// `base_dir` (the manifest directory).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a source's tag settings are invalid.
+    pub fn configure_sources(
+        &self,
+        sources: &crate::manifest::SourceMap,
+        base_dir: Option<&std::path::Path>,
+    ) -> Result<()> {
+        for name in sources.keys() {
+            let options = sources.options(name);
+            let filter = options
+                .tag_filter()
+                .with_context(|| format!("Invalid tag settings for source '{name}'"))?;
+            if let Some(filter) = filter {
+                self.version_resolver.set_tag_filter(name, filter);
+            }
+            if options.verify_signatures {
+                self.version_resolver
+                    .set_signature_check(name, options.allowed_signers_path(base_dir));
+            }
+        }
+        Ok(())
+    }
+
+    /// Require signed tags for every named source, as `--verify-signatures` does.
+    ///
+    /// Takes `(source, allowed_signers)` pairs. Sources that already verify
+    /// signatures keep their settings.
+    pub fn require_tag_signatures<'a>(
+        &self,
+        sources: impl IntoIterator<Item = (&'a str, Option<PathBuf>)>,
+    ) {
+        for (name, allowed_signers) in sources {
+            if !self.version_resolver.has_signature_check(name) {
+                self.version_resolver.set_signature_check(name, allowed_signers);
+            }
+        }
+    }
+
+    /// Fetch every branch and tag of each source during pre-sync.
+    pub fn set_fetch_all_refs(&mut self, fetch_all_refs: bool) {
+        self.version_resolver.set_fetch_all_refs(fetch_all_refs);
+    }
+
     /// Pre-sync all source repositories needed for dependencies.
     ///
     /// This performs all Git network operations upfront:
@@ -706,6 +1023,9 @@ impl VersionResolutionService {
                     version,
                     dep.resolution_mode(),
                 );
+                if let Some(as_of) = dep.get_as_of() {
+                    self.version_resolver.set_as_of(source, version, as_of);
+                }
             }
         }
 
@@ -832,12 +1152,19 @@ impl VersionResolutionService {
         let resolution_mode = Self::resolution_mode_from_version(version);
         self.version_resolver.add_version(source_name, &source_url, version, resolution_mode);
 
-        // Ensure the bare repository exists
-        if self.version_resolver.get_bare_repo_path(source_name).is_none() {
-            let repo_path =
-                core.cache.get_or_clone_source(source_name, &source_url, None).await.with_context(
-                    || format!("Failed to sync repository for source '{}'", source_name),
-                )?;
+        // Ensure the bare repository exists and has the ref. Pre-sync may only
+        // have fetched the versions the manifest names, so transitive versions
+        // are fetched here: selectively if exact, otherwise in full (at most once).
+        let synced = self.version_resolver.get_bare_repo_path(source_name).is_some();
+        if !synced || crate::source::provider::for_url(&source_url).is_none() {
+            let repo_path = match (self.version_resolver.refs_to_fetch(source_name), version) {
+                (Some(_), Some(version)) => {
+                    let refs = [version.to_string()];
+                    core.cache.get_or_clone_source_for_refs(source_name, &source_url, &refs).await
+                }
+                _ => core.cache.get_or_clone_source(source_name, &source_url, None).await,
+            }
+            .with_context(|| format!("Failed to sync repository for source '{}'", source_name))?;
             self.version_resolver.register_bare_repo(source_name.to_string(), repo_path);
         }
 
@@ -903,6 +1230,11 @@ impl VersionResolutionService {
         Ok(versions)
     }
 
+    /// Get the tag filter configured for a source, if any.
+    pub fn tag_filter(&self, source: &str) -> Option<TagFilter> {
+        self.version_resolver.tag_filter(source)
+    }
+
     /// Get the bare repository path for a source.
     ///
     /// Returns None if the source hasn't been synced yet.
@@ -931,7 +1263,8 @@ use semver::Version;
 /// Checks if a string represents a version constraint rather than a direct reference.
 ///
 /// Version constraints contain operators like `^`, `~`, `>`, `<`, `=`, or special
-/// keywords. Direct references are branch names, tag names, or commit hashes.
+/// keywords (`*`, `latest`, `latest-prerelease`). Direct references are branch
+/// names, tag names, or commit hashes.
 /// This function now supports prefixed constraints like `agents-^v1.0.0`.
 ///
 /// # Arguments
@@ -948,7 +1281,7 @@ pub fn is_version_constraint(version: &str) -> bool {
     let (_prefix, version_str) = crate::version::split_prefix_and_version(version);
 
     // Check for wildcard (works with or without prefix)
-    if version_str == "*" {
+    if version_str == "*" || crate::version::is_latest_keyword(version) {
         return true;
     }
 
@@ -1024,6 +1357,36 @@ pub fn parse_tags_to_versions(tags: Vec<String>) -> Vec<(String, Version)> {
     versions
 }
 
+/// Turns per-source failures into a single error, ordered by source name.
+///
+/// A single failure is returned unchanged so its context chain is kept.
+fn collect_source_failures(mut failures: Vec<(String, anyhow::Error)>) -> Result<()> {
+    if failures.len() <= 1 {
+        return failures.pop().map_or(Ok(()), |(_, e)| Err(e));
+    }
+
+    // Sort so the report does not depend on which task finished first
+    failures.sort_by_cached_key(|(source, e)| (source.clone(), format!("{e:#}")));
+    let mut sources: Vec<&str> = failures.iter().map(|(source, _)| source.as_str()).collect();
+    sources.dedup();
+    let details = failures.iter().map(|(_, e)| format!("  - {e:#}")).collect::<Vec<_>>().join("\n");
+    Err(anyhow::anyhow!(
+        "Failed to resolve {} source(s) ({}):\n{details}",
+        sources.len(),
+        sources.join(", ")
+    ))
+}
+
+/// Maps the version part of a tag back to the original tag name.
+///
+/// Returns `version_part` itself if no tag has it, so branches and commits
+/// pass through unchanged.
+fn original_tag(tags: &[(String, String)], version_part: &str) -> String {
+    tags.iter()
+        .find(|(_, part)| part == version_part)
+        .map_or_else(|| version_part.to_string(), |(tag, _)| tag.clone())
+}
+
 /// Finds the best matching tag for a version constraint.
 ///
 /// This function resolves version constraints to actual Git tags by:
@@ -1031,6 +1394,9 @@ pub fn parse_tags_to_versions(tags: Vec<String>) -> Vec<(String, Version)> {
 /// 2. Filtering tags to only those with matching prefix
 /// 3. Parsing the constraint and matching tags
 /// 4. Selecting the best match (usually the highest compatible version)
+///
+/// `latest` selects the highest stable version and `latest-prerelease` the
+/// highest version including prereleases.
 pub fn find_best_matching_tag(constraint_str: &str, tags: Vec<String>) -> Result<String> {
     // Extract prefix from constraint
     let (constraint_prefix, version_str) = crate::version::split_prefix_and_version(constraint_str);
@@ -1060,10 +1426,17 @@ pub fn find_best_matching_tag(constraint_str: &str, tags: Vec<String>) -> Result
     }
 
     // Special case: wildcard (*) matches the highest available version
-    if version_str == "*" {
+    if version_str == "*" || version_str == crate::version::LATEST_PRERELEASE {
         // tag_versions is already sorted highest first
         return Ok(tag_versions[0].0.clone());
     }
+    if version_str == crate::version::LATEST {
+        return tag_versions
+            .into_iter()
+            .find(|(_, version)| version.pre.is_empty())
+            .map(|(tag, _)| tag)
+            .ok_or_else(|| anyhow::anyhow!("No stable version tags found for '{constraint_str}'"));
+    }
 
     // Parse constraint using ONLY the version part (prefix already filtered)
     // This ensures semver matching works correctly after prefix filtering
@@ -1260,6 +1633,42 @@ mod tests {
         assert_eq!(resolver.pending_count(), 1);
     }
 
+    #[tokio::test]
+    async fn test_require_tag_signatures_keeps_configured_sources() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = Cache::with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let service = VersionResolutionService::new(cache);
+        let signers = PathBuf::from("/project/.agpm/allowed_signers");
+        service.version_resolver.set_signature_check("signed", Some(signers.clone()));
+
+        // "global" stands for a source defined only in the global config
+        service.require_tag_signatures([("signed", None), ("global", None)]);
+
+        assert!(service.version_resolver.has_signature_check("global"));
+        assert_eq!(
+            service.version_resolver.signature_checks.get("signed").map(|e| e.value().clone()),
+            Some(Some(signers))
+        );
+        assert!(!service.version_resolver.has_signature_check("other"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_all_refs_disables_selective_fetch() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = Cache::with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let mut resolver = VersionResolver::new(cache);
+        resolver.add_version(
+            "source1",
+            "https://example.com/repo.git",
+            Some("v1.0.0"),
+            ResolutionMode::Version,
+        );
+        assert_eq!(resolver.refs_to_fetch("source1"), Some(vec!["v1.0.0".to_string()]));
+
+        resolver.set_fetch_all_refs(true);
+        assert_eq!(resolver.refs_to_fetch("source1"), None);
+    }
+
     #[tokio::test]
     async fn test_sha_optimization() {
         let temp_dir = TempDir::new().unwrap();
@@ -1300,4 +1709,42 @@ mod tests {
         assert_eq!(WorktreeManager::group_key("source", "version"), "source::version");
         assert_eq!(WorktreeManager::group_key("community", "v1.0.0"), "community::v1.0.0");
     }
+
+    #[tokio::test]
+    async fn test_resolve_all_reports_every_failing_source() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = Cache::with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let resolver = VersionResolver::new(cache);
+        for source in ["beta", "alpha"] {
+            resolver.add_version(
+                source,
+                &format!("https://example.com/{source}.git"),
+                Some("v1.0.0"),
+                ResolutionMode::Version,
+            );
+        }
+
+        // Neither source was pre-synced, so both fail; the error lists them in name order
+        let message = format!("{:#}", resolver.resolve_all(None).await.unwrap_err());
+        assert!(message.contains("Failed to resolve 2 source(s) (alpha, beta)"), "{message}");
+        assert!(message.find("'alpha'").unwrap() < message.find("'beta'").unwrap());
+    }
+
+    #[test]
+    fn test_latest_keywords_select_highest_semver_tag() {
+        let tags: Vec<String> =
+            ["v1.2.0", "v1.10.0", "v2.0.0-beta.1", "main-snapshot", "tools-v9.0.0"]
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+
+        assert!(is_version_constraint("latest"));
+        assert!(is_version_constraint("latest-prerelease"));
+        assert_eq!(find_best_matching_tag("latest", tags.clone()).unwrap(), "v1.10.0");
+        assert_eq!(find_best_matching_tag("latest-prerelease", tags).unwrap(), "v2.0.0-beta.1");
+
+        // No stable tags: `latest` fails so the resolver can fall back to the default branch
+        assert!(find_best_matching_tag("latest", vec!["v1.0.0-rc.1".to_string()]).is_err());
+        assert!(find_best_matching_tag("latest", vec!["nightly".to_string()]).is_err());
+    }
 }
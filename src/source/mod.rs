@@ -12,6 +12,9 @@
 //! - [`Source`] - Represents an individual repository with metadata and caching information
 //! - [`SourceManager`] - Manages multiple sources with operations for syncing, verification, and caching
 //!
+//! Sources that are not Git repositories can be added by host applications
+//! through a [`provider::SourceProvider`] registered for their URL scheme.
+//!
 //! # Source Configuration
 //!
 //! Sources can be defined in two locations with different purposes:
@@ -198,6 +201,8 @@
 //! # }
 //! ```
 
+pub mod provider;
+
 use crate::cache::lock::CacheLock;
 use crate::config::GlobalConfig;
 use crate::core::AgpmError;
@@ -497,6 +502,15 @@ fn is_local_filesystem_path(url: &str) -> bool {
     false
 }
 
+/// The URL a manifest source is accessed at.
+///
+/// `dir://` sources become plain directory paths, resolved against the
+/// manifest directory, which the cache reads in place instead of cloning.
+fn manifest_source_url(manifest: &Manifest, url: &str) -> String {
+    crate::manifest::directory_source_path(url, manifest.manifest_dir.as_deref())
+        .map_or_else(|| url.to_string(), |path| path.to_string_lossy().into_owned())
+}
+
 impl SourceManager {
     /// Creates a new source manager with the default cache directory.
     ///
@@ -591,7 +605,7 @@ impl SourceManager {
 
         // Load all sources from the manifest
         for (name, url) in &manifest.sources {
-            let source = Source::new(name.clone(), url.clone());
+            let source = Source::new(name.clone(), manifest_source_url(manifest, url));
             manager.sources.insert(name.clone(), source);
         }
 
@@ -622,6 +636,7 @@ impl SourceManager {
     /// Returns an error if:
     /// - Cache directory cannot be determined
     /// - Global configuration cannot be loaded (though this is non-fatal)
+    /// - A global source's token references an unset environment variable
     ///
     /// # Examples
     ///
@@ -647,11 +662,11 @@ impl SourceManager {
 
         // Load global config and merge sources
         let global_config = GlobalConfig::load().await.unwrap_or_default();
-        let merged_sources = global_config.merge_sources(&manifest.sources);
+        let merged_sources = global_config.merge_sources(&manifest.sources)?;
 
         // Load all merged sources
         for (name, url) in &merged_sources {
-            let source = Source::new(name.clone(), url.clone());
+            let source = Source::new(name.clone(), manifest_source_url(manifest, url));
             manager.sources.insert(name.clone(), source);
         }
 
@@ -688,7 +703,7 @@ impl SourceManager {
 
         // Load all sources from the manifest
         for (name, url) in &manifest.sources {
-            let source = Source::new(name.clone(), url.clone());
+            let source = Source::new(name.clone(), manifest_source_url(manifest, url));
             manager.sources.insert(name.clone(), source);
         }
 
@@ -0,0 +1,269 @@
+//! Pluggable providers for dependency sources that are not Git repositories.
+//!
+//! Resolution and installation reach sources through the [`SourceProvider`]
+//! trait: make the source available locally, list its versions, resolve a
+//! version to a commit, and check a commit out as a directory of files. Git
+//! repositories are served by [`GitSourceProvider`], built on the [`Cache`].
+//!
+//! Applications embedding AGPM can support other kinds of sources, such as an
+//! internal artifact registry, by implementing the trait and calling
+//! [`register`] with the URL scheme it handles. Sources whose URL uses that
+//! scheme (`artifact://registry/agents`) are then resolved and installed
+//! through the provider:
+//!
+//! ```rust,no_run
+//! use agpm_cli::source::provider::{self, ProviderFuture, SourceProvider};
+//! use std::path::PathBuf;
+//! use std::sync::Arc;
+//!
+//! struct ArtifactProvider;
+//!
+//! impl SourceProvider for ArtifactProvider {
+//!     fn scheme(&self) -> &str {
+//!         "artifact"
+//!     }
+//!     fn fetch<'a>(&'a self, _name: &'a str, _url: &'a str) -> ProviderFuture<'a, PathBuf> {
+//!         Box::pin(async { Ok(PathBuf::from("/var/cache/artifacts")) })
+//!     }
+//!     fn list_versions<'a>(&'a self, _name: &'a str, _url: &'a str) -> ProviderFuture<'a, Vec<String>> {
+//!         Box::pin(async { Ok(vec!["v1.0.0".to_string()]) })
+//!     }
+//!     fn resolve_version_to_commit<'a>(
+//!         &'a self,
+//!         _name: &'a str,
+//!         _url: &'a str,
+//!         _version: Option<&'a str>,
+//!     ) -> ProviderFuture<'a, String> {
+//!         Box::pin(async { Ok("0123456789abcdef0123456789abcdef01234567".to_string()) })
+//!     }
+//!     fn checkout<'a>(
+//!         &'a self,
+//!         _name: &'a str,
+//!         _url: &'a str,
+//!         commit: &'a str,
+//!     ) -> ProviderFuture<'a, PathBuf> {
+//!         Box::pin(async move { Ok(PathBuf::from("/var/cache/artifacts").join(commit)) })
+//!     }
+//! }
+//!
+//! provider::register(Arc::new(ArtifactProvider));
+//! ```
+//!
+//! Commits are recorded in `agpm.lock` and used to name checkouts, so a
+//! provider must identify each version by a stable 40-character hex string,
+//! such as the SHA-1 of the artifact. Providers are registered for the whole
+//! process, before the manifest is loaded, because source URL validation
+//! accepts registered schemes.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::{Arc, LazyLock, RwLock};
+
+use crate::cache::Cache;
+use crate::git::GitRepo;
+
+/// Future returned by [`SourceProvider`] methods.
+pub type ProviderFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
+
+/// Access to the versions and files of one kind of dependency source.
+///
+/// `name` is the source's name in the manifest and `url` its URL.
+pub trait SourceProvider: Send + Sync {
+    /// The URL scheme this provider handles, without `://` (e.g. `artifact`).
+    fn scheme(&self) -> &str;
+
+    /// Make the source available locally and return its directory.
+    fn fetch<'a>(&'a self, name: &'a str, url: &'a str) -> ProviderFuture<'a, PathBuf>;
+
+    /// List the versions of the source, such as `v1.2.0`.
+    ///
+    /// Version constraints (`^1.0`, `latest`) are matched against this list.
+    fn list_versions<'a>(&'a self, name: &'a str, url: &'a str) -> ProviderFuture<'a, Vec<String>>;
+
+    /// Resolve a version, or the newest one for `None`, to a 40-character hex commit.
+    fn resolve_version_to_commit<'a>(
+        &'a self,
+        name: &'a str,
+        url: &'a str,
+        version: Option<&'a str>,
+    ) -> ProviderFuture<'a, String>;
+
+    /// Return a directory holding the source's files at `commit`.
+    fn checkout<'a>(
+        &'a self,
+        name: &'a str,
+        url: &'a str,
+        commit: &'a str,
+    ) -> ProviderFuture<'a, PathBuf>;
+
+    /// Read the file at `path` (relative to the source root) at `commit`.
+    fn read_file<'a>(
+        &'a self,
+        name: &'a str,
+        url: &'a str,
+        commit: &'a str,
+        path: &'a str,
+    ) -> ProviderFuture<'a, String> {
+        Box::pin(async move {
+            let file = self.checkout(name, url, commit).await?.join(path);
+            tokio::fs::read_to_string(&file)
+                .await
+                .with_context(|| format!("Failed to read {} from source '{name}'", file.display()))
+        })
+    }
+}
+
+/// The default provider: Git repositories in the AGPM cache.
+pub struct GitSourceProvider {
+    cache: Cache,
+}
+
+impl GitSourceProvider {
+    /// Create a provider serving Git sources from `cache`.
+    #[must_use]
+    pub const fn new(cache: Cache) -> Self {
+        Self {
+            cache,
+        }
+    }
+}
+
+impl SourceProvider for GitSourceProvider {
+    fn scheme(&self) -> &str {
+        "git"
+    }
+
+    fn fetch<'a>(&'a self, name: &'a str, url: &'a str) -> ProviderFuture<'a, PathBuf> {
+        Box::pin(self.cache.get_or_clone_source(name, url, None))
+    }
+
+    fn list_versions<'a>(&'a self, name: &'a str, url: &'a str) -> ProviderFuture<'a, Vec<String>> {
+        Box::pin(async move {
+            let repo_path = self.fetch(name, url).await?;
+            GitRepo::new(&repo_path).list_tags().await
+        })
+    }
+
+    fn resolve_version_to_commit<'a>(
+        &'a self,
+        name: &'a str,
+        url: &'a str,
+        version: Option<&'a str>,
+    ) -> ProviderFuture<'a, String> {
+        Box::pin(async move {
+            let repo_path = self.fetch(name, url).await?;
+            GitRepo::new(&repo_path).resolve_to_sha(version).await
+        })
+    }
+
+    fn checkout<'a>(
+        &'a self,
+        name: &'a str,
+        url: &'a str,
+        commit: &'a str,
+    ) -> ProviderFuture<'a, PathBuf> {
+        Box::pin(self.cache.get_or_create_worktree_for_sha(name, url, commit, None))
+    }
+}
+
+/// Providers registered by the host application, keyed by URL scheme.
+static PROVIDERS: LazyLock<RwLock<HashMap<String, Arc<dyn SourceProvider>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Register `provider` for sources whose URL uses its scheme.
+///
+/// A provider registered later for the same scheme replaces the earlier one.
+pub fn register(provider: Arc<dyn SourceProvider>) {
+    let scheme = provider.scheme().to_string();
+    PROVIDERS.write().unwrap().insert(scheme, provider);
+}
+
+/// The registered provider for `url`'s scheme, if any.
+#[must_use]
+pub fn for_url(url: &str) -> Option<Arc<dyn SourceProvider>> {
+    let (scheme, _) = url.split_once("://")?;
+    PROVIDERS.read().unwrap().get(scheme).cloned()
+}
+
+/// The provider for `url`: a registered one, or Git through `cache`.
+#[must_use]
+pub fn provider_for(cache: &Cache, url: &str) -> Arc<dyn SourceProvider> {
+    for_url(url).unwrap_or_else(|| Arc::new(GitSourceProvider::new(cache.clone())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    struct StaticProvider {
+        root: PathBuf,
+    }
+
+    const COMMIT: &str = "0123456789abcdef0123456789abcdef01234567";
+
+    impl SourceProvider for StaticProvider {
+        fn scheme(&self) -> &str {
+            "static-test"
+        }
+        fn fetch<'a>(&'a self, _name: &'a str, _url: &'a str) -> ProviderFuture<'a, PathBuf> {
+            Box::pin(async move { Ok(self.root.clone()) })
+        }
+        fn list_versions<'a>(
+            &'a self,
+            _name: &'a str,
+            _url: &'a str,
+        ) -> ProviderFuture<'a, Vec<String>> {
+            Box::pin(async { Ok(vec!["v1.0.0".to_string()]) })
+        }
+        fn resolve_version_to_commit<'a>(
+            &'a self,
+            _name: &'a str,
+            _url: &'a str,
+            _version: Option<&'a str>,
+        ) -> ProviderFuture<'a, String> {
+            Box::pin(async { Ok(COMMIT.to_string()) })
+        }
+        fn checkout<'a>(
+            &'a self,
+            _name: &'a str,
+            _url: &'a str,
+            commit: &'a str,
+        ) -> ProviderFuture<'a, PathBuf> {
+            Box::pin(async move { Ok(self.root.join(commit)) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_registered_provider_serves_its_scheme() {
+        let temp = TempDir::new().unwrap();
+        let checkout = temp.path().join(COMMIT).join("agents");
+        std::fs::create_dir_all(&checkout).unwrap();
+        std::fs::write(checkout.join("reviewer.md"), "# Reviewer\n").unwrap();
+
+        assert!(for_url("static-test://registry/agents").is_none());
+        register(Arc::new(StaticProvider {
+            root: temp.path().to_path_buf(),
+        }));
+
+        let provider = for_url("static-test://registry/agents").unwrap();
+        let commit = provider.resolve_version_to_commit("reg", "static-test://x", None).await;
+        let content = provider
+            .read_file("reg", "static-test://x", &commit.unwrap(), "agents/reviewer.md")
+            .await
+            .unwrap();
+        assert_eq!(content, "# Reviewer\n");
+        assert!(for_url("https://github.com/org/repo.git").is_none());
+
+        // Sources through the cache reach the provider too
+        let cache = Cache::with_dir(temp.path().join("cache")).unwrap();
+        let path = cache
+            .get_or_create_worktree_for_sha("reg", "static-test://x", COMMIT, None)
+            .await
+            .unwrap();
+        assert_eq!(path, temp.path().join(COMMIT));
+    }
+}
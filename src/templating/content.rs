@@ -44,6 +44,11 @@ pub(crate) trait ContentExtractor {
             resource.resource_type
         );
 
+        // Inline content has no file and is always templated
+        if let Some(inline) = &resource.inline {
+            return Some((inline.clone(), true));
+        }
+
         // Determine source path
         let source_path = if let Some(source_name) = &resource.source {
             let url = resource.url.as_ref()?;
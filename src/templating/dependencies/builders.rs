@@ -320,8 +320,14 @@ pub(crate) async fn build_dependencies_data<T: DependencyExtractor>(
             // CRITICAL: Include tool and resolved_commit in cache key to prevent cache pollution!
             // Same path renders differently for different tools (claude-code vs opencode)
             // and different commits must have different cache entries.
+            // Inline resources have no path, so they are keyed by name instead
+            let cache_path = if resource.inline.is_some() {
+                format!("inline:{}", resource.name)
+            } else {
+                resource.path.clone()
+            };
             let cache_key = RenderCacheKey::new(
-                resource.path.clone(),
+                cache_path,
                 *dep_type,
                 resource.tool.clone(),
                 resource.variant_inputs.hash().to_string(),
@@ -78,10 +78,26 @@
 //! ```
 //!
 //! The template system will render up to 10 levels of nested references.
+//!
+//! ## Reading Files with `read_file`
+//!
+//! The `read_file` function returns a project file's contents unchanged, with
+//! the same path restrictions as the `content` filter:
+//!
+//! ```markdown
+//! ## Conventions
+//! {{ read_file(path="docs/CONVENTIONS.md") }}
+//! ```
+//!
+//! Every file read this way is recorded with its checksum so the installer can
+//! fold it into the resource's context checksum; editing the file causes the
+//! resource to be re-rendered on the next install.
 
 use anyhow::{Result, bail};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
 use crate::core::file_error::{FileOperation, FileResultExt, LARGE_FILE_SIZE};
 
@@ -453,6 +469,66 @@ pub fn create_content_filter(
     }
 }
 
+/// Project files read by `read_file` during rendering.
+///
+/// Maps each path as written in the template to the `sha256:` checksum of the
+/// contents that were returned.
+pub type ReadFiles = Arc<Mutex<BTreeMap<String, String>>>;
+
+/// Creates the `read_file` Tera function for embedding project files.
+///
+/// The function takes a single `path` argument, relative to the project root,
+/// and returns the file's contents unchanged. Paths are validated exactly as
+/// for the `content` filter (see [`validate_content_path`]), so reads are
+/// restricted to text files inside the project.
+///
+/// Each successful read is recorded in `read_files` so callers can include the
+/// files in the resource's context checksum.
+///
+/// # Function Usage
+///
+/// ```markdown
+/// {{ read_file(path="docs/CONVENTIONS.md") }}
+/// ```
+///
+/// # Errors
+///
+/// The returned function produces template rendering errors if:
+/// - The `path` argument is missing or not a string
+/// - Path validation fails (absolute path, traversal, invalid extension, etc.)
+/// - The file cannot be read
+pub fn create_read_file_function(
+    project_dir: PathBuf,
+    max_size: Option<u64>,
+    read_files: ReadFiles,
+) -> impl tera::Function + 'static {
+    move |args: &HashMap<String, tera::Value>| -> tera::Result<tera::Value> {
+        let path_str = args
+            .get("path")
+            .and_then(tera::Value::as_str)
+            .ok_or_else(|| tera::Error::msg("read_file requires a string `path` argument"))?;
+
+        let file_path = validate_content_path(path_str, &project_dir, max_size)
+            .map_err(|e| tera::Error::msg(format!("read_file error: {}", e)))?;
+
+        let content = std::fs::read_to_string(&file_path)
+            .with_file_context(
+                FileOperation::Read,
+                &file_path,
+                format!("reading '{}' for template embedding", path_str),
+                "read_file",
+            )
+            .map_err(|e| tera::Error::msg(format!("read_file error: {}", e)))?;
+
+        let checksum = format!("sha256:{}", hex::encode(Sha256::digest(content.as_bytes())));
+        if let Ok(mut read_files) = read_files.lock() {
+            read_files.insert(path_str.to_string(), checksum);
+        }
+
+        Ok(tera::Value::String(content))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -645,6 +721,57 @@ mod tests {
         assert!(result.is_err(), "Filter should reject non-string values");
     }
 
+    #[test]
+    fn test_read_file_function_records_checksums() {
+        use tera::Tera;
+
+        let temp = create_test_project();
+        let read_files = ReadFiles::default();
+
+        let mut tera = Tera::default();
+        tera.register_function(
+            "read_file",
+            create_read_file_function(temp.path().to_path_buf(), None, read_files.clone()),
+        );
+
+        let context = tera::Context::new();
+        let rendered =
+            tera.render_str(r#"{{ read_file(path="docs/with-frontmatter.md") }}"#, &context);
+        assert_eq!(rendered.unwrap(), "---\ntitle: Test\n---\n\n# Content");
+
+        let first = read_files.lock().unwrap().get("docs/with-frontmatter.md").cloned().unwrap();
+        assert!(first.starts_with("sha256:"));
+
+        fs::write(temp.path().join("docs/with-frontmatter.md"), "# Changed").unwrap();
+        tera.render_str(r#"{{ read_file(path="docs/with-frontmatter.md") }}"#, &context).unwrap();
+        let second = read_files.lock().unwrap().get("docs/with-frontmatter.md").cloned().unwrap();
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_read_file_function_rejects_paths_outside_project() {
+        use tera::Tera;
+
+        let temp = create_test_project();
+        let read_files = ReadFiles::default();
+
+        let mut tera = Tera::default();
+        tera.register_function(
+            "read_file",
+            create_read_file_function(temp.path().to_path_buf(), None, read_files.clone()),
+        );
+
+        let context = tera::Context::new();
+        for template in [
+            r#"{{ read_file(path="../outside.md") }}"#,
+            r#"{{ read_file(path="/etc/hosts.txt") }}"#,
+            r#"{{ read_file() }}"#,
+        ] {
+            assert!(tera.render_str(template, &context).is_err(), "{template} should fail");
+        }
+        assert!(read_files.lock().unwrap().is_empty());
+    }
+
     #[test]
     fn test_recursive_template_rendering() {
         // This test is in the templating module tests
@@ -5,7 +5,7 @@
 
 use anyhow::Result;
 use regex::Regex;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 use strsim::levenshtein;
 use tera::{Context as TeraContext, Tera};
@@ -61,6 +61,8 @@ pub struct TemplateRenderer {
     project_dir: PathBuf,
     /// Maximum file size for content filter
     max_content_file_size: Option<u64>,
+    /// Project files read through `read_file`, with their checksums
+    read_files: filters::ReadFiles,
 }
 
 impl TemplateRenderer {
@@ -80,6 +82,8 @@ impl TemplateRenderer {
     ///
     /// The following custom filters are registered:
     /// - `content`: Read project-specific files with path validation and size limits
+    ///
+    /// The `read_file` function is registered as well; see [`Self::read_files`].
     pub fn new(
         enabled: bool,
         project_dir: PathBuf,
@@ -89,9 +93,20 @@ impl TemplateRenderer {
             enabled,
             project_dir,
             max_content_file_size,
+            read_files: filters::ReadFiles::default(),
         })
     }
 
+    /// Project files read through the `read_file` function so far.
+    ///
+    /// Maps each path as written in the template to the checksum of its
+    /// contents. Reads from every `render_template` call on this renderer are
+    /// accumulated.
+    #[must_use]
+    pub fn read_files(&self) -> BTreeMap<String, String> {
+        self.read_files.lock().map(|files| files.clone()).unwrap_or_default()
+    }
+
     /// Protect literal blocks from template rendering by replacing them with placeholders.
     ///
     /// This method scans for ```literal fenced code blocks and replaces them with
@@ -329,6 +344,14 @@ impl TemplateRenderer {
             "content",
             filters::create_content_filter(self.project_dir.clone(), self.max_content_file_size),
         );
+        tera.register_function(
+            "read_file",
+            filters::create_read_file_function(
+                self.project_dir.clone(),
+                self.max_content_file_size,
+                self.read_files.clone(),
+            ),
+        );
 
         let rendered = tera.render_str(&protected_content, context).map_err(|e| {
             // Parse into structured error
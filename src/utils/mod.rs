@@ -291,6 +291,44 @@ pub fn is_local_path(url: &str) -> bool {
     false
 }
 
+/// Parses a duration such as `60s`, `5m`, `1h` or `90` (seconds).
+///
+/// Used for command-line options that take a timeout.
+///
+/// # Examples
+///
+/// ```
+/// use agpm_cli::utils::parse_duration;
+/// use std::time::Duration;
+///
+/// assert_eq!(parse_duration("90").unwrap(), Duration::from_secs(90));
+/// assert_eq!(parse_duration("2m").unwrap(), Duration::from_secs(120));
+/// assert!(parse_duration("soon").is_err());
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the value is not a positive whole number, optionally
+/// followed by `s`, `m` or `h`, or if it is too large to represent.
+pub fn parse_duration(value: &str) -> anyhow::Result<std::time::Duration> {
+    let value = value.trim();
+    let (number, multiplier) = match value.char_indices().last() {
+        Some((i, 's')) => (&value[..i], 1),
+        Some((i, 'm')) => (&value[..i], 60),
+        Some((i, 'h')) => (&value[..i], 3600),
+        _ => (value, 1),
+    };
+    let seconds = number
+        .parse::<u64>()
+        .ok()
+        .filter(|amount| *amount > 0)
+        .and_then(|amount| amount.checked_mul(multiplier))
+        .with_context(|| {
+            format!("Invalid duration '{value}'; expected e.g. 60s, 5m, 1h or 90 (seconds)")
+        })?;
+    Ok(std::time::Duration::from_secs(seconds))
+}
+
 /// Determines if a given URL is a Git repository URL (including file:// URLs).
 ///
 /// Git repository URLs need to be cloned/fetched, unlike local filesystem paths.
@@ -559,6 +597,19 @@ mod tests {
         assert_eq!(result, "");
     }
 
+    #[test]
+    fn test_parse_duration() {
+        use std::time::Duration;
+
+        assert_eq!(parse_duration("60s").unwrap(), Duration::from_secs(60));
+        assert_eq!(parse_duration("5m").unwrap(), Duration::from_secs(300));
+        assert_eq!(parse_duration("1h").unwrap(), Duration::from_secs(3600));
+        assert_eq!(parse_duration("45").unwrap(), Duration::from_secs(45));
+        for invalid in ["", "0s", "-5", "1.5m", "10d", "m", "18446744073709551615h"] {
+            assert!(parse_duration(invalid).is_err(), "{invalid} should be rejected");
+        }
+    }
+
     #[test]
     fn test_compute_relative_path_nested() {
         // Complex nesting
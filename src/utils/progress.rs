@@ -611,6 +611,8 @@ mod tests {
             dependencies: vec![],
             applied_patches: std::collections::BTreeMap::new(),
             install: Some(true),
+            strip_metadata: false,
+            inline: None,
         }
     }
 
@@ -86,7 +86,7 @@
 //! | `<2.0.0` | Less than | `"<2.0.0"` |
 //! | `>=1.0.0, <2.0.0` | Range constraint | Multiple constraints |
 //! | `main` | Git branch reference | Branch name |
-//! | `latest` | Git tag or branch name | Just a regular ref |
+//! | `latest` | Highest stable semver tag | Selected by the version resolver |
 //! | `v1.0.0` | Git tag reference | Tag name |
 //! | `abc123` | Git commit reference | Commit hash (full or abbreviated) |
 //!
@@ -23,6 +23,10 @@
 //!
 //! ## Special Keywords
 //! - **Wildcard**: `"*"` - Matches any version
+//! - **Latest**: `"latest"` - The highest stable semver tag, or the default
+//!   branch HEAD if the source has no semver tags
+//! - **Latest prerelease**: `"latest-prerelease"` - Like `latest`, but
+//!   prereleases are candidates too
 //!
 //! ## Git References
 //! - **Branches**: `"main"`, `"develop"`, `"feature/auth"`
@@ -157,6 +161,28 @@ use semver::{Version, VersionReq};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+/// Version keyword selecting the highest stable semver tag of a source.
+pub const LATEST: &str = "latest";
+
+/// Version keyword selecting the highest semver tag of a source, prereleases included.
+pub const LATEST_PRERELEASE: &str = "latest-prerelease";
+
+/// Whether `version` is the [`LATEST`] or [`LATEST_PRERELEASE`] keyword.
+///
+/// # Examples
+///
+/// ```
+/// use agpm_cli::version::is_latest_keyword;
+///
+/// assert!(is_latest_keyword("latest"));
+/// assert!(is_latest_keyword("latest-prerelease"));
+/// assert!(!is_latest_keyword("main"));
+/// ```
+#[must_use]
+pub fn is_latest_keyword(version: &str) -> bool {
+    version == LATEST || version == LATEST_PRERELEASE
+}
+
 /// Parse a version requirement string, normalizing 'v' prefixes.
 ///
 /// This helper function provides centralized semver parsing that handles both
@@ -1313,6 +1339,12 @@ pub mod conflict;
 /// resolution. See the module documentation for comprehensive examples.
 pub mod constraints;
 
+/// Per-source tag prefixes and patterns.
+///
+/// The [`tag_filter`] module selects the tags of a source that declares
+/// `tag_prefix` or `tag_pattern` and extracts the version part of each.
+pub mod tag_filter;
+
 /// Represents different types of version constraints in AGPM.
 ///
 /// `VersionConstraint` is a simple enum that categorizes version references into
@@ -0,0 +1,115 @@
+//! Per-source selection of version tags.
+//!
+//! Some repositories tag releases with a fixed prefix (`agents-v1.2.0`) or a
+//! scheme that AGPM's built-in prefix detection does not understand. A source
+//! can declare `tag_prefix` or `tag_pattern` in the detailed `[sources]` form;
+//! the resulting [`TagFilter`] keeps only matching tags and extracts the
+//! version part of each, which is what version constraints are compared
+//! against. The original tag name is still what gets checked out and
+//! recorded in the lockfile.
+//!
+//! ```toml
+//! [sources]
+//! agents = { url = "https://github.com/org/agents.git", tag_prefix = "agents-" }
+//! tools = { url = "https://github.com/org/tools.git", tag_pattern = "^release/(.+)$" }
+//! ```
+
+use anyhow::{Context, Result, bail};
+use regex::Regex;
+
+/// Selects a source's version tags and extracts their version part.
+#[derive(Debug, Clone)]
+pub enum TagFilter {
+    /// Tags must start with this prefix, which is stripped.
+    Prefix(String),
+    /// Tags must match this regex. The capture group named `version`, or
+    /// else the first capture group, or else the whole match is the version.
+    Pattern(Regex),
+}
+
+impl TagFilter {
+    /// Build a filter from a source's `tag_prefix` and `tag_pattern` settings.
+    ///
+    /// Returns `None` if neither is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if both are set or the pattern is not a valid regex.
+    pub fn new(prefix: Option<&str>, pattern: Option<&str>) -> Result<Option<Self>> {
+        match (prefix, pattern) {
+            (Some(_), Some(_)) => bail!("tag_prefix and tag_pattern cannot both be set"),
+            (Some(prefix), None) => Ok(Some(Self::Prefix(prefix.to_string()))),
+            (None, Some(pattern)) => {
+                let regex = Regex::new(pattern)
+                    .with_context(|| format!("Invalid tag_pattern '{pattern}'"))?;
+                Ok(Some(Self::Pattern(regex)))
+            }
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// Return the version part of `tag`, or `None` if the tag is not selected.
+    #[must_use]
+    pub fn version_part<'a>(&self, tag: &'a str) -> Option<&'a str> {
+        match self {
+            Self::Prefix(prefix) => tag.strip_prefix(prefix.as_str()),
+            Self::Pattern(regex) => {
+                let captures = regex.captures(tag)?;
+                let version = captures
+                    .name("version")
+                    .or_else(|| captures.get(1))
+                    .or_else(|| captures.get(0))?;
+                Some(version.as_str())
+            }
+        }
+        .filter(|version| !version.is_empty())
+    }
+
+    /// Keep the selected tags, pairing each original tag with its version part.
+    #[must_use]
+    pub fn apply(&self, tags: Vec<String>) -> Vec<(String, String)> {
+        tags.into_iter()
+            .filter_map(|tag| {
+                let version = self.version_part(&tag)?.to_string();
+                Some((tag, version))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| (*s).to_string()).collect()
+    }
+
+    #[test]
+    fn test_prefix_filter() {
+        let filter = TagFilter::new(Some("agents-"), None).unwrap().unwrap();
+        let selected =
+            filter.apply(tags(&["agents-v1.2.0", "1.2.0", "snippets-v2.0.0", "agents-"]));
+        assert_eq!(selected, vec![("agents-v1.2.0".to_string(), "v1.2.0".to_string())]);
+    }
+
+    #[test]
+    fn test_pattern_filter_uses_capture_groups() {
+        let filter = TagFilter::new(None, Some(r"^release/(.+)$")).unwrap().unwrap();
+        assert_eq!(filter.version_part("release/1.2.0"), Some("1.2.0"));
+        assert_eq!(filter.version_part("v1.2.0"), None);
+
+        let named = TagFilter::new(None, Some(r"^(pkg)-(?P<version>\d+\.\d+\.\d+)$")).unwrap();
+        assert_eq!(named.unwrap().version_part("pkg-3.1.4"), Some("3.1.4"));
+
+        let whole = TagFilter::new(None, Some(r"^\d+\.\d+\.\d+$")).unwrap().unwrap();
+        assert_eq!(whole.version_part("1.0.0"), Some("1.0.0"));
+    }
+
+    #[test]
+    fn test_new_rejects_invalid_settings() {
+        assert!(TagFilter::new(None, None).unwrap().is_none());
+        assert!(TagFilter::new(Some("a-"), Some("^a-(.*)$")).is_err());
+        assert!(TagFilter::new(None, Some("(unclosed")).is_err());
+    }
+}
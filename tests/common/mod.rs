@@ -186,6 +186,15 @@ impl TestGit {
         Ok(())
     }
 
+    /// Force an existing tag to point at another commit, as a retagging upstream would
+    pub fn move_tag(&self, tag_name: &str, target: &str) -> Result<()> {
+        self.run_git_command(
+            &["tag", "-f", tag_name, target],
+            &format!("Failed to move tag: {}", tag_name),
+        )?;
+        Ok(())
+    }
+
     /// Create and checkout a branch
     pub fn create_branch(&self, branch_name: &str) -> Result<()> {
         self.run_git_command(
@@ -2316,3 +2316,61 @@ Depends on same helpers with different versions
 
     Ok(())
 }
+
+/// Test that a diamond conflict is reported with both versions, the chains that
+/// introduced them and an `[override]` suggestion, including with `--json-errors`.
+#[tokio::test]
+async fn test_diamond_conflict_reports_parties() -> Result<()> {
+    let project = TestProject::new().await?;
+    let source_repo = project.create_source_repo("source").await?;
+
+    source_repo.add_resource("snippets", "shared-snippet", "# Shared Snippet v1.0.0").await?;
+    source_repo.commit_all("Add v1.0.0")?;
+    source_repo.tag_version("v1.0.0")?;
+    source_repo.add_resource("snippets", "shared-snippet", "# Shared Snippet v2.0.0").await?;
+    source_repo.commit_all("Add v2.0.0")?;
+    source_repo.tag_version("v2.0.0")?;
+
+    for (agent, version) in [("agent-a", "v1.0.0"), ("agent-b", "v2.0.0")] {
+        let content = format!(
+            "---\ndependencies:\n  snippets:\n    - path: snippets/shared-snippet.md\n      version: {version}\n---\n# {agent}"
+        );
+        source_repo.add_resource("agents", agent, &content).await?;
+    }
+    source_repo.commit_all("Add agents")?;
+    source_repo.tag_version("agents-v1.0.0")?;
+
+    let manifest = ManifestBuilder::new()
+        .add_source("source", &source_repo.bare_file_url(project.sources_path())?)
+        .add_agent("agent-a", |d| {
+            d.source("source").path("agents/agent-a.md").version("agents-v1.0.0")
+        })
+        .add_agent("agent-b", |d| {
+            d.source("source").path("agents/agent-b.md").version("agents-v1.0.0")
+        })
+        .build();
+    project.write_manifest(&manifest).await?;
+
+    let output = project.run_agpm(&["install", "--no-progress"])?;
+    assert!(!output.success, "Install should fail. Stderr: {}", output.stderr);
+    assert!(output.stderr.contains("Version conflicts detected"), "Stderr: {}", output.stderr);
+    assert!(output.stderr.contains("[override.snippets]"), "Stderr: {}", output.stderr);
+
+    let output = project.run_agpm(&["--json-errors", "install", "--no-progress"])?;
+    assert!(!output.success, "Install should fail. Stderr: {}", output.stderr);
+    let line = output.stderr.lines().rev().find(|line| line.starts_with('{')).unwrap_or_default();
+    let error: serde_json::Value = serde_json::from_str(line)
+        .map_err(|e| anyhow::anyhow!("Stderr is not JSON ({e}): {}", output.stderr))?;
+
+    let conflicts = error["conflicts"].as_array().expect("conflicts should be listed");
+    assert_eq!(conflicts.len(), 1, "Error: {error}");
+    assert!(conflicts[0]["dependency"].as_str().unwrap().contains("shared-snippet"));
+    assert_eq!(conflicts[0]["resource_type"], "snippet");
+    let parties = conflicts[0]["parties"].as_array().unwrap();
+    let versions: Vec<&str> = parties.iter().map(|p| p["version"].as_str().unwrap()).collect();
+    assert_eq!(versions, ["v1.0.0", "v2.0.0"], "Error: {error}");
+    assert!(parties[0]["chain"].to_string().contains("agent-a"), "Error: {error}");
+    assert!(parties[1]["chain"].to_string().contains("agent-b"), "Error: {error}");
+    assert!(error["suggestion"].as_str().unwrap().contains("[override.snippets]"));
+    Ok(())
+}
@@ -612,3 +612,60 @@ my-command = {{ source = "test", path = "command/commands/my-command.md", versio
 
     Ok(())
 }
+
+/// Test that a tool defined only in the manifest installs resources to its own paths
+#[tokio::test]
+async fn test_install_custom_tool_defined_in_manifest() -> Result<()> {
+    let project = TestProject::new().await?;
+
+    let source_repo = project.create_source_repo("test").await?;
+    fs::create_dir_all(source_repo.path.join("agents/review")).await?;
+    fs::write(
+        source_repo.path.join("agents/review/ide-agent.md"),
+        "# IDE Agent\nThis is an agent for my-ide.",
+    )
+    .await?;
+    fs::write(
+        source_repo.path.join("agents/claude-agent.md"),
+        "# Claude Code Agent\nThis is a claude-code agent.",
+    )
+    .await?;
+    source_repo.git.add_all()?;
+    source_repo.git.commit("Initial commit")?;
+    source_repo.git.tag("v1.0.0")?;
+
+    // Only the custom tool is declared; built-in tools keep their defaults
+    let manifest_content = format!(
+        r#"
+[sources]
+test = "{}"
+
+[tools.my-ide]
+path = ".my-ide"
+resources = {{ agents = {{ path = "personas", flatten = true }} }}
+
+[agents]
+ide-agent = {{ source = "test", path = "agents/review/ide-agent.md", version = "v1.0.0", tool = "my-ide" }}
+claude-agent = {{ source = "test", path = "agents/claude-agent.md", version = "v1.0.0" }}
+"#,
+        normalize_path_for_storage(&source_repo.path)
+    );
+    project.write_manifest(&manifest_content).await?;
+
+    let output = project.run_agpm(&["install"])?;
+    assert!(output.success, "Install should succeed. stderr: {}", output.stderr);
+
+    let ide_agent_path = project.project_path().join(".my-ide/personas/ide-agent.md");
+    assert!(ide_agent_path.exists(), "Custom tool agent should be installed to its own path");
+    assert!(fs::read_to_string(&ide_agent_path).await?.contains("IDE Agent"));
+    assert!(
+        project.project_path().join(".claude/agents/claude-agent.md").exists(),
+        "Default claude-code agent should still be installed"
+    );
+
+    let lockfile = project.read_lockfile().await?;
+    assert!(lockfile.contains(r#"installed_at = ".my-ide/personas/ide-agent.md""#));
+    assert!(lockfile.contains(r#"tool = "my-ide""#));
+
+    Ok(())
+}
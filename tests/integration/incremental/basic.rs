@@ -227,3 +227,64 @@ async fn test_incremental_nonexistent_dependency() -> Result<()> {
 
     Ok(())
 }
+
+/// Test that editing an MCP server's `env` in agpm.toml updates .mcp.json on the next install,
+/// even though the locked commit is unchanged
+#[tokio::test]
+async fn test_incremental_install_applies_mcp_env_changes() -> Result<()> {
+    agpm_cli::test_utils::init_test_logging(None);
+
+    let project = TestProject::new().await?;
+
+    let remote = project.create_source_repo("remote").await?;
+    let mcp_dir = remote.path.join("mcp-servers");
+    tokio::fs::create_dir_all(&mcp_dir).await?;
+    tokio::fs::write(
+        mcp_dir.join("filesystem.json"),
+        r#"{
+  "command": "npx",
+  "args": ["-y", "@modelcontextprotocol/server-filesystem"]
+}"#,
+    )
+    .await?;
+    remote.commit_all("Add MCP server")?;
+    remote.tag_version("v1.0.0")?;
+
+    let remote_url = remote.bare_file_url(project.sources_path())?;
+    let manifest = |log_level: &str| {
+        format!(
+            r#"[sources]
+remote = "{remote_url}"
+
+[mcp-servers]
+filesystem = {{ source = "remote", path = "mcp-servers/filesystem.json", version = "v1.0.0", env = {{ LOG_LEVEL = "{log_level}" }} }}
+"#
+        )
+    };
+
+    project.write_manifest(&manifest("info")).await?;
+    let output = project.run_agpm(&["install"])?;
+    assert!(output.success, "Install should succeed. Stderr: {}", output.stderr);
+
+    let mcp_json_path = project.project_path().join(".mcp.json");
+    let read_log_level = || -> Result<serde_json::Value> {
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&mcp_json_path)?)?;
+        Ok(json["mcpServers"]["filesystem"]["env"]["LOG_LEVEL"].clone())
+    };
+    assert_eq!(read_log_level()?, "info");
+
+    // Only the manifest env changes; the lockfile resolves to the same commit
+    project.write_manifest(&manifest("debug")).await?;
+    let output = project.run_agpm(&["install"])?;
+    assert!(output.success, "Install should succeed. Stderr: {}", output.stderr);
+    assert_eq!(read_log_level()?, "debug", ".mcp.json should pick up the new env value");
+
+    // A managed server deleted by hand is merged back in
+    std::fs::write(&mcp_json_path, r#"{"mcpServers": {}}"#)?;
+    let output = project.run_agpm(&["install"])?;
+    assert!(output.success, "Install should succeed. Stderr: {}", output.stderr);
+    assert_eq!(read_log_level()?, "debug", "the deleted server should be restored");
+
+    Ok(())
+}
@@ -47,7 +47,7 @@ async fn test_install_creates_lockfile() {
 
     // Verify lockfile content structure
     let lockfile_content = fs::read_to_string(&lockfile_path).await.unwrap();
-    assert!(lockfile_content.contains("version = 1"));
+    assert!(lockfile_content.contains("version = 2"));
     assert!(lockfile_content.contains("[[sources]]"));
     assert!(lockfile_content.contains("[[agents]]"));
     assert!(lockfile_content.contains("my-agent"));
@@ -319,6 +319,36 @@ async fn test_install_local_dependencies() {
     assert!(local_utils_path.exists(), "Local utils should be at {:?}", local_utils_path);
 }
 
+/// Test that --target-dir installs elsewhere while reading the project's manifest
+#[tokio::test]
+async fn test_install_target_dir() {
+    let project = TestProject::new().await.unwrap();
+
+    project
+        .create_local_resource("snippets/local-utils.md", "# Local Utils\n\nA local snippet.")
+        .await
+        .unwrap();
+    let (_official_repo, official_url) = project.create_standard_v1_repo("official").await.unwrap();
+
+    let manifest = ManifestBuilder::new()
+        .add_source("official", &official_url)
+        .add_standard_agent("my-agent", "official", "agents/test-agent.md")
+        .add_local_snippet("local-utils", "./snippets/local-utils.md")
+        .build();
+    project.write_manifest(&manifest).await.unwrap();
+
+    let output = project.run_agpm(&["install", "--target-dir", "build/output"]).unwrap();
+    output.assert_success();
+
+    // The lockfile stays in the project, the resources go to the target
+    let target = project.project_path().join("build/output");
+    assert!(project.project_path().join("agpm.lock").exists());
+    assert!(!target.join("agpm.lock").exists());
+    assert!(target.join(".claude/agents/test-agent.md").exists());
+    assert!(target.join(".agpm/snippets/local-utils.md").exists());
+    assert!(!project.project_path().join(".claude/agents/test-agent.md").exists());
+}
+
 /// Test install with verbose output
 #[tokio::test]
 async fn test_install_verbose() {
@@ -240,3 +240,135 @@ test-repo = "{}"
 
     Ok(())
 }
+
+/// Test that changing a dependency's target or filename removes the file at its old location
+#[tokio::test]
+async fn test_cleanup_after_target_and_filename_change() -> Result<()> {
+    agpm_cli::test_utils::init_test_logging(None);
+
+    let project = TestProject::new().await?;
+    let test_repo = project.create_source_repo("test-repo").await?;
+    test_repo.add_resource("agents", "helper", "# Helper Agent\n").await?;
+    test_repo.commit_all("Initial version")?;
+    test_repo.tag_version("v1.0.0")?;
+    let repo_url = test_repo.bare_file_url(project.sources_path())?;
+
+    let install_with = |fields: &str| {
+        format!(
+            r#"[sources]
+test-repo = "{repo_url}"
+
+[agents]
+helper = {{ source = "test-repo", path = "agents/helper.md", version = "v1.0.0"{fields} }}
+"#
+        )
+    };
+
+    // First install: default location
+    project.write_manifest(&install_with("")).await?;
+    let output = project.run_agpm(&["install"])?;
+    assert!(output.success, "Initial install should succeed. Stderr: {}", output.stderr);
+    let default_path = project.project_path().join(".claude/agents/helper.md");
+    assert!(fs::metadata(&default_path).await.is_ok(), "Helper should be at {default_path:?}");
+
+    // Second install: custom target
+    project.write_manifest(&install_with(r#", target = "tools""#)).await?;
+    let output = project.run_agpm(&["install"])?;
+    assert!(output.success, "Target change install should succeed. Stderr: {}", output.stderr);
+    let target_path = project.project_path().join(".claude/agents/tools/helper.md");
+    assert!(fs::metadata(&target_path).await.is_ok(), "Helper should be at {target_path:?}");
+    assert!(
+        fs::metadata(&default_path).await.is_err(),
+        "Old location should be removed after target change"
+    );
+    assert!(output.stdout.contains("Moved"), "Should report the move. Output: {}", output.stdout);
+
+    // Third install: custom filename back in the default directory
+    project.write_manifest(&install_with(r#", filename = "assistant.md""#)).await?;
+    let output = project.run_agpm(&["install"])?;
+    assert!(output.success, "Filename change install should succeed. Stderr: {}", output.stderr);
+    let renamed_path = project.project_path().join(".claude/agents/assistant.md");
+    assert!(fs::metadata(&renamed_path).await.is_ok(), "Helper should be at {renamed_path:?}");
+    assert!(
+        fs::metadata(&target_path).await.is_err(),
+        "Old location should be removed after filename change"
+    );
+    assert!(
+        fs::metadata(project.project_path().join(".claude/agents/tools")).await.is_err(),
+        "Empty target directory should be removed"
+    );
+
+    Ok(())
+}
+
+/// Test that disabling a dependency removes it and its transitive-only
+/// dependencies, and that enabling it again reinstalls them
+#[tokio::test]
+async fn test_cleanup_after_dependency_disabled() -> Result<()> {
+    agpm_cli::test_utils::init_test_logging(None);
+
+    let project = TestProject::new().await?;
+    let test_repo = project.create_source_repo("test-repo").await?;
+    test_repo
+        .add_resource(
+            "agents",
+            "reviewer",
+            r#"---
+dependencies:
+  agents:
+    - path: agents/helper.md
+      version: "v1.0.0"
+---
+# Reviewer
+"#,
+        )
+        .await?;
+    test_repo.add_resource("agents", "helper", "# Helper\n").await?;
+    test_repo.commit_all("Initial version")?;
+    test_repo.tag_version("v1.0.0")?;
+    let repo_url = test_repo.bare_file_url(project.sources_path())?;
+
+    let manifest = |enabled: bool| {
+        format!(
+            r#"[sources]
+test-repo = "{repo_url}"
+
+[agents]
+reviewer = {{ source = "test-repo", path = "agents/reviewer.md", version = "v1.0.0", enabled = {enabled} }}
+"#
+        )
+    };
+    let reviewer_path = project.project_path().join(".claude/agents/reviewer.md");
+    let helper_path = project.project_path().join(".claude/agents/helper.md");
+
+    project.write_manifest(&manifest(true)).await?;
+    let output = project.run_agpm(&["install"])?;
+    assert!(output.success, "Install should succeed. Stderr: {}", output.stderr);
+    assert!(reviewer_path.exists());
+    assert!(helper_path.exists());
+
+    project.write_manifest(&manifest(false)).await?;
+    let output = project.run_agpm(&["install"])?;
+    assert!(output.success, "Install should succeed. Stderr: {}", output.stderr);
+    assert!(!reviewer_path.exists(), "Disabled agent should be removed");
+    assert!(!helper_path.exists(), "Transitive-only dependency should be removed");
+
+    let lockfile = project.read_lockfile().await?;
+    assert!(lockfile.contains("[[disabled]]"), "Lockfile:\n{}", lockfile);
+    assert!(!lockfile.contains("[[agents]]"), "Lockfile:\n{}", lockfile);
+
+    let output = project.run_agpm(&["list"])?;
+    assert!(output.success, "List should succeed. Stderr: {}", output.stderr);
+    assert!(!output.stdout.contains("reviewer"), "Stdout: {}", output.stdout);
+    let output = project.run_agpm(&["list", "--show-disabled"])?;
+    assert!(output.success, "List should succeed. Stderr: {}", output.stderr);
+    assert!(output.stdout.contains("reviewer (disabled)"), "Stdout: {}", output.stdout);
+
+    project.write_manifest(&manifest(true)).await?;
+    let output = project.run_agpm(&["install"])?;
+    assert!(output.success, "Install should succeed. Stderr: {}", output.stderr);
+    assert!(reviewer_path.exists(), "Re-enabled agent should be reinstalled");
+    assert!(helper_path.exists());
+    assert!(!project.read_lockfile().await?.contains("[[disabled]]"));
+    Ok(())
+}
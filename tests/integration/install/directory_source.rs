@@ -0,0 +1,40 @@
+use tokio::fs;
+
+use crate::common::TestProject;
+
+/// A `dir://` source is read in place, without Git, relative to the manifest
+#[tokio::test]
+async fn test_install_from_directory_source() {
+    let project = TestProject::new().await.unwrap();
+
+    // A plain directory, not a Git repository
+    let shared = project.sources_path().join("shared");
+    fs::create_dir_all(shared.join("agents")).await.unwrap();
+    fs::write(shared.join("agents/helper.md"), "# Helper\n\nFrom a directory").await.unwrap();
+
+    project
+        .write_manifest(
+            r#"
+[sources]
+shared = "dir://../sources/shared"
+
+[agents]
+helper = { source = "shared", path = "agents/helper.md" }
+"#,
+        )
+        .await
+        .unwrap();
+
+    project.run_agpm(&["install"]).unwrap().assert_success();
+
+    let installed = project.project_path().join(".claude/agents/helper.md");
+    assert_eq!(fs::read_to_string(&installed).await.unwrap(), "# Helper\n\nFrom a directory");
+    assert!(!shared.join(".git").exists(), "the source must not be turned into a repository");
+
+    let lockfile = project.read_lockfile().await.unwrap();
+    assert!(lockfile.contains("name = \"helper\""), "{lockfile}");
+
+    // Reinstalling with the lockfile present reads the same directory
+    project.run_agpm(&["install"]).unwrap().assert_success();
+    assert!(installed.exists());
+}
@@ -0,0 +1,74 @@
+//! Integration tests for resources defined inline in the manifest.
+
+use anyhow::Result;
+use tokio::fs;
+
+use crate::common::TestProject;
+
+/// Test that an inline snippet is rendered, installed and locked with its content
+#[tokio::test]
+async fn test_install_inline_snippet() -> Result<()> {
+    agpm_cli::test_utils::init_test_logging(None);
+
+    let project = TestProject::new().await?;
+    project
+        .write_manifest(
+            r#"[project]
+name = "demo"
+
+[snippets]
+greeting = { inline = "Hello from {{ agpm.project.name }}", tool = "agpm" }
+"#,
+        )
+        .await?;
+
+    let output = project.run_agpm(&["install"])?;
+    assert!(output.success, "Install should succeed. Stderr: {}", output.stderr);
+
+    let installed = project.project_path().join(".agpm/snippets/greeting.md");
+    assert_eq!(fs::read_to_string(&installed).await?, "Hello from demo");
+
+    let lockfile = project.read_lockfile().await?;
+    assert!(
+        lockfile.contains(r#"inline = "Hello from {{ agpm.project.name }}""#),
+        "Lockfile should record the inline content. Lockfile:\n{}",
+        lockfile
+    );
+    assert!(lockfile.contains("checksum = \"sha256:"), "Lockfile:\n{}", lockfile);
+
+    // Editing the inline content reinstalls the file
+    project
+        .write_manifest(
+            r#"[project]
+name = "demo"
+
+[snippets]
+greeting = { inline = "Goodbye from {{ agpm.project.name }}", tool = "agpm" }
+"#,
+        )
+        .await?;
+    let output = project.run_agpm(&["install"])?;
+    assert!(output.success, "Install should succeed. Stderr: {}", output.stderr);
+    assert_eq!(fs::read_to_string(&installed).await?, "Goodbye from demo");
+    Ok(())
+}
+
+/// Test that `inline` cannot be combined with `path`
+#[tokio::test]
+async fn test_inline_with_path_is_rejected() -> Result<()> {
+    agpm_cli::test_utils::init_test_logging(None);
+
+    let project = TestProject::new().await?;
+    project
+        .write_manifest(
+            r#"[snippets]
+greeting = { inline = "Hello", path = "snippets/greeting.md" }
+"#,
+        )
+        .await?;
+
+    let output = project.run_agpm(&["install"])?;
+    assert!(!output.success, "Install should fail");
+    assert!(output.stderr.contains("cannot combine 'inline'"), "Stderr: {}", output.stderr);
+    Ok(())
+}
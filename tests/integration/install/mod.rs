@@ -2,8 +2,10 @@
 //!
 //! Tests for resource installation and deployment:
 //! - Basic installation workflows (formerly deploy.rs)
+//! - Plain-directory (`dir://`) sources
 //! - Install field and content embedding
 //! - Incremental dependency addition
+//! - Inline resources defined in the manifest
 //! - Multi-artifact installation
 //! - Multi-resource management
 //! - Artifact cleanup and removal
@@ -11,7 +13,9 @@
 
 mod basic;
 mod cleanup;
+mod directory_source;
 mod incremental_add;
+mod inline;
 mod install_field;
 mod multi_artifact;
 mod multi_resource;
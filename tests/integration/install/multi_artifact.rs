@@ -561,3 +561,55 @@ async fn test_agpm_artifact_type() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_install_tools_filters_installation_only() -> Result<()> {
+    agpm_cli::test_utils::init_test_logging(None);
+
+    let project = TestProject::new().await?;
+    let source_repo = project.create_source_repo("test_repo").await?;
+    source_repo.add_resource("agents", "helper", "# Helper\n\nA helper agent.").await?;
+    source_repo.add_resource("agents", "reviewer", "# Reviewer\n\nA reviewer agent.").await?;
+    source_repo.commit_all("Add agents")?;
+    source_repo.tag_version("v1.0.0")?;
+
+    let repo_url = source_repo.bare_file_url(project.sources_path())?;
+    let manifest = ManifestBuilder::new()
+        .add_source("test_repo", &repo_url)
+        .with_tools_config(|t| {
+            t.tool("opencode", |tc| {
+                tc.path(".opencode")
+                    .enabled(true)
+                    .agents(ResourceConfigBuilder::default().path("agent"))
+            })
+        })
+        .add_agent("claude-helper", |d| {
+            d.source("test_repo").path("agents/helper.md").version("v1.0.0")
+        })
+        .add_agent("opencode-reviewer", |d| {
+            d.source("test_repo").path("agents/reviewer.md").version("v1.0.0").tool("opencode")
+        })
+        .build();
+    project.write_manifest(&manifest).await?;
+
+    project.run_agpm(&["install"])?.assert_success();
+    let full_lockfile = project.read_lockfile().await?;
+    let opencode_agent = project.project_path().join(".opencode/agent/reviewer.md");
+    assert!(opencode_agent.exists());
+
+    // Deselecting a tool neither changes the lockfile nor removes its files
+    project.run_agpm(&["install", "--tools", "claude-code"])?.assert_success();
+    assert_eq!(project.read_lockfile().await?, full_lockfile);
+    assert!(opencode_agent.exists());
+
+    // A fresh install for one tool still locks every tool's dependencies
+    fs::remove_dir_all(project.project_path().join(".opencode")).await?;
+    fs::remove_file(project.project_path().join("agpm.lock")).await?;
+    project.run_agpm(&["install", "--tools", "claude-code"])?.assert_success();
+    assert!(project.project_path().join(".claude/agents/helper.md").exists());
+    assert!(!opencode_agent.exists());
+    let lockfile = project.read_lockfile().await?;
+    assert!(lockfile.contains(r#"installed_at = ".opencode/agent/reviewer.md""#), "{lockfile}");
+
+    Ok(())
+}
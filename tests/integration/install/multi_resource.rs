@@ -454,15 +454,15 @@ fn update_mcp_v2_2(repo_dir: &Path) -> Result<()> {
     sync_fs::create_dir_all(repo_dir.join("mcp-servers"))?;
     sync_fs::write(
         repo_dir.join("mcp-servers/filesystem.json"),
-        r#"{"name": "filesystem", "version": "v2.2.0", "type": "filesystem"}"#,
+        r#"{"name": "filesystem", "version": "v2.2.0", "command": "npx", "args": ["filesystem"]}"#,
     )?;
     sync_fs::write(
         repo_dir.join("mcp-servers/postgres.json"),
-        r#"{"name": "postgres", "version": "v2.2.0", "type": "database"}"#,
+        r#"{"name": "postgres", "version": "v2.2.0", "command": "npx", "args": ["postgres"]}"#,
     )?;
     sync_fs::write(
         repo_dir.join("mcp-servers/redis.json"),
-        r#"{"name": "redis", "version": "v2.2.0", "type": "cache"}"#,
+        r#"{"name": "redis", "version": "v2.2.0", "command": "npx", "args": ["redis"]}"#,
     )?;
     Ok(())
 }
@@ -479,7 +479,7 @@ fn update_major_v3(repo_dir: &Path) -> Result<()> {
     )?;
     sync_fs::write(
         repo_dir.join("mcp-servers/postgres.json"),
-        r#"{"name": "postgres", "version": "v3.0.0", "type": "database", "features": ["ssl"]}"#,
+        r#"{"name": "postgres", "version": "v3.0.0", "command": "npx", "args": ["postgres"], "features": ["ssl"]}"#,
     )?;
     Ok(())
 }
@@ -516,7 +516,7 @@ fn update_breaking_v4(repo_dir: &Path) -> Result<()> {
     sync_fs::write(repo_dir.join("commands/lint.md"), "# Lint Command v4.0.0\n\nLinter v4.0")?;
     sync_fs::write(
         repo_dir.join("mcp-servers/redis.json"),
-        r#"{"name": "redis", "version": "v4.0.0", "type": "cache", "breaking": true}"#,
+        r#"{"name": "redis", "version": "v4.0.0", "command": "npx", "args": ["redis"], "breaking": true}"#,
     )?;
     sync_fs::write(
         repo_dir.join("agents/gamma.md"),
@@ -34,6 +34,17 @@ async fn test_small_installation_display() -> Result<()> {
                 flatten: None,
                 install: None,
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             })),
         );
     }
@@ -81,6 +92,17 @@ async fn test_large_installation_display() -> Result<()> {
                 flatten: None,
                 install: None,
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             })),
         );
     }
@@ -126,6 +148,17 @@ async fn test_quiet_mode_no_progress() -> Result<()> {
             flatten: None,
             install: None,
             template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         })),
     );
     manifest.save(&manifest_path)?;
@@ -688,3 +688,30 @@ fn unified_diff(a: &str, b: &str) -> String {
 
     diff
 }
+
+/// Test that re-resolving an unchanged manifest writes a byte-identical lockfile,
+/// including the sources' `fetched_at` timestamps
+#[tokio::test]
+async fn test_update_keeps_fetched_at_when_commits_unchanged() -> Result<()> {
+    let project = TestProject::new().await?;
+    let test_repo = project.create_source_repo("test-repo").await?;
+    test_repo.add_resource("agents", "stable-agent", "# Stable Agent\n").await?;
+    test_repo.commit_all("Add stable agent")?;
+    test_repo.tag_version("v1.0.0")?;
+    let source_url = test_repo.bare_file_url(project.sources_path())?;
+
+    let manifest = crate::common::ManifestBuilder::new()
+        .add_source("test-repo", &source_url)
+        .add_standard_agent("stable-agent", "test-repo", "agents/stable-agent.md")
+        .build();
+    project.write_manifest(&manifest).await?;
+
+    project.run_agpm(&["install"])?.assert_success();
+    let first = fs::read_to_string(project.project_path().join("agpm.lock")).await?;
+
+    project.run_agpm(&["update"])?.assert_success();
+    let second = fs::read_to_string(project.project_path().join("agpm.lock")).await?;
+
+    assert_eq!(first, second, "Re-resolving an unchanged manifest changed agpm.lock");
+    Ok(())
+}
@@ -102,14 +102,16 @@ async fn test_install_frozen_detects_version_mismatch() -> Result<()> {
     // Change manifest back to v2.0.0
     project.write_manifest(&manifest_v2).await?;
 
-    // --frozen mode should succeed (only checks corruption/security, not version changes)
-    // It will use the lockfile as-is with v1.0.0 even though manifest has v2.0.0
+    // --frozen mode must fail: resolution would move the lockfile from v1.0.0 to v2.0.0
+    let lockfile_before = project.read_lockfile().await?;
     let output = project.run_agpm(&["install", "--frozen"])?;
+    assert!(!output.success, "Frozen install should fail on version change");
     assert!(
-        output.success,
-        "Frozen install should succeed (ignores version changes): {}",
+        output.stderr.contains("version changed from 'v1.0.0' to 'v2.0.0'"),
+        "Should report the version change, got: {}",
         output.stderr
     );
+    assert_eq!(project.read_lockfile().await?, lockfile_before, "Lockfile must not change");
 
     Ok(())
 }
@@ -201,7 +203,12 @@ async fn test_install_detects_path_change() -> Result<()> {
 
     // --frozen mode should fail
     let output = project.run_agpm(&["install", "--frozen"])?;
-    assert!(output.success, "Frozen mode should succeed (ignores path changes): {}", output.stderr);
+    assert!(!output.success, "Frozen mode should fail on path change");
+    assert!(
+        output.stderr.contains("path changed from 'agents/old-path.md' to 'agents/new-path.md'"),
+        "Should report the path change, got: {}",
+        output.stderr
+    );
 
     Ok(())
 }
@@ -433,3 +440,64 @@ async fn test_install_detects_tool_field_change() -> Result<()> {
 
     Ok(())
 }
+
+/// Test that --frozen fails with the specific reason when the lockfile is missing an entry
+#[tokio::test]
+async fn test_install_frozen_rejects_missing_dependency() -> Result<()> {
+    let project = TestProject::new().await?;
+
+    let source_repo = project.create_source_repo("test-source").await?;
+    source_repo.add_resource("agents", "agent-one", "# Agent One").await?;
+    source_repo.add_resource("agents", "agent-two", "# Agent Two").await?;
+    source_repo.commit_all("Add agents")?;
+    source_repo.tag_version("v1.0.0")?;
+
+    let manifest = ManifestBuilder::new()
+        .add_source("test-source", &source_repo.file_url())
+        .add_standard_agent("agent-one", "test-source", "agents/agent-one.md")
+        .add_standard_agent("agent-two", "test-source", "agents/agent-two.md")
+        .build();
+    project.write_manifest(&manifest).await?;
+
+    let output = project.run_agpm(&["install", "--quiet"])?;
+    assert!(output.success, "Initial install failed: {}", output.stderr);
+
+    // An unchanged lockfile is accepted
+    let output = project.run_agpm(&["install", "--frozen", "--quiet"])?;
+    assert!(output.success, "Frozen install of current lockfile failed: {}", output.stderr);
+
+    // Drop agent-two from the lockfile
+    let mut lockfile = project.load_lockfile()?;
+    lockfile.agents.retain(|agent| agent.manifest_alias.as_deref() != Some("agent-two"));
+    lockfile.save(&project.project_path().join("agpm.lock"))?;
+    let lockfile_before = project.read_lockfile().await?;
+
+    let output = project.run_agpm(&["install", "--frozen"])?;
+    assert!(!output.success, "Frozen install should fail on missing dependency");
+    assert!(
+        output.stderr.contains("Dependency 'agent-two' (agent) is in manifest but missing"),
+        "Should report the missing dependency, got: {}",
+        output.stderr
+    );
+    assert_eq!(project.read_lockfile().await?, lockfile_before, "Lockfile must not change");
+
+    Ok(())
+}
+
+/// Test that --frozen refuses to create a lockfile
+#[tokio::test]
+async fn test_install_frozen_requires_lockfile() -> Result<()> {
+    let project = TestProject::new().await?;
+    project.write_manifest(&ManifestBuilder::new().build()).await?;
+
+    let output = project.run_agpm(&["install", "--frozen"])?;
+    assert!(!output.success, "Frozen install without a lockfile should fail");
+    assert!(
+        output.stderr.contains("No lockfile found"),
+        "Should report the missing lockfile, got: {}",
+        output.stderr
+    );
+    assert!(!project.project_path().join("agpm.lock").exists());
+
+    Ok(())
+}
@@ -105,7 +105,7 @@ installed_at = "snippets/utils.md"
     assert!(lockfile_path.exists());
 
     let lockfile_content = fs::read_to_string(&lockfile_path).await.unwrap();
-    assert!(lockfile_content.contains("version = 1"));
+    assert!(lockfile_content.contains("version = 2"));
 }
 
 /// Test updating specific dependency
@@ -216,3 +216,49 @@ async fn test_cache_persistence() -> Result<()> {
 
     Ok(())
 }
+
+/// Test that `cache warm` refills an empty cache with sources and locked commits
+#[tokio::test]
+async fn test_cache_warm_after_clean() -> Result<()> {
+    let project = TestProject::new().await?;
+
+    let source_repo = project.create_source_repo("official").await?;
+    source_repo.add_resource("agents", "warm-agent", "# Warm Agent\n\nTest warm agent").await?;
+    source_repo.commit_all("Add warm agent")?;
+    source_repo.tag_version("v1.0.0")?;
+    let source_url = source_repo.bare_file_url(project.sources_path())?;
+
+    let manifest_content = ManifestBuilder::new()
+        .add_source("official", &source_url)
+        .add_standard_agent("agent", "official", "agents/warm-agent.md")
+        .build();
+    project.write_manifest(&manifest_content).await?;
+
+    project.run_agpm(&["install"])?.assert_success();
+    project.run_agpm(&["cache", "clean", "--all"])?.assert_success();
+
+    // Sources only: no worktrees are created
+    let output = project.run_agpm(&["cache", "warm", "--manifest-only"])?;
+    output.assert_success();
+    output.assert_stdout_contains("official fetched");
+    assert!(!output.stdout.contains("locked commit"));
+
+    let output = project.run_agpm(&["cache", "warm"])?;
+    output.assert_success();
+    output.assert_stdout_contains("Checking out 1 locked commit(s)");
+    output.assert_stdout_contains("official @");
+    output.assert_stdout_contains("Cache size:");
+
+    let output = project.run_agpm(&["cache", "stats", "--format", "json"])?;
+    output.assert_success();
+    let stats: serde_json::Value = serde_json::from_str(&output.stdout)?;
+    let worktrees: u64 = stats["sources"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|source| source["worktree_count"].as_u64().unwrap_or(0))
+        .sum();
+    assert_eq!(worktrees, 1, "Expected one worktree after warming: {stats}");
+
+    Ok(())
+}
@@ -422,3 +422,57 @@ Should handle gracefully.
 
     Ok(())
 }
+
+/// Test that editing a file embedded with `read_file` re-renders the resource
+#[tokio::test]
+async fn test_read_file_change_rerenders_resource() -> Result<()> {
+    let project = TestProject::new().await?;
+    let source_repo = project.create_source_repo("test").await?;
+
+    source_repo
+        .add_resource(
+            "agents",
+            "conventions",
+            r#"---
+agpm:
+  templating: true
+---
+# Conventions
+
+{{ read_file(path="docs/CONVENTIONS.md") }}
+"#,
+        )
+        .await?;
+    source_repo.commit_all("Add conventions agent")?;
+    source_repo.tag_version("v1.0.0")?;
+    let source_url = source_repo.bare_file_url(project.sources_path())?;
+
+    let docs = project.project_path().join("docs");
+    fs::create_dir_all(&docs).await?;
+    fs::write(docs.join("CONVENTIONS.md"), "Use tabs.").await?;
+
+    let manifest = ManifestBuilder::new()
+        .add_source("test", &source_url)
+        .add_standard_agent("conventions", "test", "agents/conventions.md")
+        .build();
+    project.write_manifest(&manifest).await?;
+
+    project.run_agpm(&["install"])?.assert_success();
+    let installed = project.project_path().join(".claude/agents/conventions.md");
+    assert!(fs::read_to_string(&installed).await?.contains("Use tabs."));
+
+    // The inputs behind the context checksum are recorded so unchanged
+    // resources can still be skipped
+    let mut records = fs::read_dir(project.cache_path().join("render-inputs")).await?;
+    assert!(records.next_entry().await?.is_some(), "render inputs should be recorded");
+
+    project.run_agpm(&["install"])?.assert_success();
+    assert!(fs::read_to_string(&installed).await?.contains("Use tabs."));
+
+    fs::write(docs.join("CONVENTIONS.md"), "Use spaces.").await?;
+    project.run_agpm(&["install"])?.assert_success();
+    let content = fs::read_to_string(&installed).await?;
+    assert!(content.contains("Use spaces."), "{content}");
+
+    Ok(())
+}
@@ -20,4 +20,5 @@ mod test_template_validation;
 mod test_transitive_errors;
 mod tool_inheritance;
 mod transitive_conditional_deps;
+mod variants;
 mod windows_security;
@@ -0,0 +1,114 @@
+//! Tests for dependency variants: one manifest entry installed several times.
+
+use anyhow::Result;
+use tokio::fs;
+
+use crate::common::TestProject;
+
+const REVIEWER: &str = r#"---
+agpm:
+  templating: true
+---
+# Reviewer for {{ lang }}
+
+Installed at {{ agpm.resource.install_path }}
+"#;
+
+/// Set up a source with a templated reviewer agent and return its URL.
+async fn reviewer_source(project: &TestProject) -> Result<String> {
+    let repo = project.create_source_repo("official").await?;
+    repo.add_resource("agents", "reviewer", REVIEWER).await?;
+    repo.commit_all("Add reviewer")?;
+    repo.tag_version("v1.0.0")?;
+    repo.bare_file_url(project.sources_path())
+}
+
+/// Each variant is rendered with its own template variables.
+#[tokio::test]
+async fn test_variants_install_with_their_own_template_vars() -> Result<()> {
+    agpm_cli::test_utils::init_test_logging(None);
+
+    let project = TestProject::new().await?;
+    let url = reviewer_source(&project).await?;
+    project
+        .write_manifest(&format!(
+            r#"[sources]
+official = "{url}"
+
+[agents.code-reviewer]
+source = "official"
+path = "agents/reviewer.md"
+version = "v1.0.0"
+template_vars = {{ lang = "any" }}
+variants = [
+    {{ filename = "reviewer-py.md", template_vars = {{ lang = "python" }} }},
+    {{ filename = "reviewer-rs.md", template_vars = {{ lang = "rust" }} }},
+]
+"#
+        ))
+        .await?;
+
+    project.run_agpm(&["install"])?.assert_success();
+
+    let agents = project.project_path().join(".claude/agents");
+    let python = fs::read_to_string(agents.join("reviewer-py.md")).await?;
+    let rust = fs::read_to_string(agents.join("reviewer-rs.md")).await?;
+    assert!(python.contains("# Reviewer for python"), "{python}");
+    assert!(rust.contains("# Reviewer for rust"), "{rust}");
+
+    let lockfile = project.load_lockfile()?;
+    assert_eq!(lockfile.agents.len(), 2);
+
+    // A second install finds both variants up to date
+    let output = project.run_agpm(&["install"])?;
+    output.assert_success();
+    assert!(output.stdout.contains("0 installed, 2 up-to-date"), "{}", output.stdout);
+    Ok(())
+}
+
+/// Variants that differ only in their filename are kept apart by install path.
+#[tokio::test]
+async fn test_variants_differing_only_in_filename() -> Result<()> {
+    agpm_cli::test_utils::init_test_logging(None);
+
+    let project = TestProject::new().await?;
+    let url = reviewer_source(&project).await?;
+    project
+        .write_manifest(&format!(
+            r#"[sources]
+official = "{url}"
+
+[agents.code-reviewer]
+source = "official"
+path = "agents/reviewer.md"
+version = "v1.0.0"
+template_vars = {{ lang = "python" }}
+variants = [
+    {{ filename = "reviewer-a.md" }},
+    {{ filename = "reviewer-b.md" }},
+]
+"#
+        ))
+        .await?;
+
+    project.run_agpm(&["install"])?.assert_success();
+
+    let agents = project.project_path().join(".claude/agents");
+    let first = fs::read_to_string(agents.join("reviewer-a.md")).await?;
+    let second = fs::read_to_string(agents.join("reviewer-b.md")).await?;
+    assert!(first.contains("reviewer-a.md"), "{first}");
+    assert!(second.contains("reviewer-b.md"), "{second}");
+
+    let lockfile = project.load_lockfile()?;
+    let mut installed: Vec<&str> =
+        lockfile.agents.iter().map(|agent| agent.installed_at.as_str()).collect();
+    installed.sort_unstable();
+    assert_eq!(installed, [".claude/agents/reviewer-a.md", ".claude/agents/reviewer-b.md"]);
+    assert!(lockfile.agents.iter().all(|agent| agent.checksum.starts_with("sha256:")));
+    assert_ne!(lockfile.agents[0].checksum, lockfile.agents[1].checksum);
+
+    // The lockfile is not mistaken for a corrupted one on the next install
+    project.run_agpm(&["install"])?.assert_success();
+    project.run_agpm(&["install", "--frozen"])?.assert_success();
+    Ok(())
+}
@@ -269,6 +269,50 @@ Depends on Agent A (creates cycle)
     Ok(())
 }
 
+/// Test that `--max-depth` bounds transitive dependency chains
+#[tokio::test]
+async fn test_transitive_max_depth() -> Result<()> {
+    agpm_cli::test_utils::init_test_logging(None);
+
+    let project = TestProject::new().await?;
+
+    // Create source repo with a chain: A → B → C
+    let repo = project.create_source_repo("community").await?;
+    for (name, next) in
+        [("agent-a", Some("agent-b")), ("agent-b", Some("agent-c")), ("agent-c", None)]
+    {
+        let frontmatter = next
+            .map(|next| {
+                format!("---\ndependencies:\n  agents:\n    - path: ./{next}.md\n      version: v1.0.0\n---\n\n")
+            })
+            .unwrap_or_default();
+        repo.add_resource("agents", name, &format!("{frontmatter}# {name}\n")).await?;
+    }
+    repo.commit_all("Add chained agents")?;
+    repo.tag_version("v1.0.0")?;
+
+    let source_url = repo.bare_file_url(project.sources_path())?;
+    let manifest = ManifestBuilder::new()
+        .add_source("community", &source_url)
+        .add_standard_agent("agent-a", "community", "agents/agent-a.md")
+        .build();
+    project.write_manifest(&manifest).await?;
+
+    // C is two levels below the manifest dependency
+    let output = project.run_agpm(&["install", "--max-depth", "1"])?;
+    assert!(!output.success, "Install should fail when the chain is too deep");
+    assert!(
+        output.stderr.contains("maximum depth of 1") && output.stderr.contains("agent-c"),
+        "Error should name the limit and the chain, got: {}",
+        output.stderr
+    );
+
+    let output = project.run_agpm(&["install", "--max-depth", "2"])?;
+    assert!(output.success, "Install should succeed within the limit: {}", output.stderr);
+
+    Ok(())
+}
+
 /// Test diamond dependencies (same resource via multiple paths)
 #[tokio::test]
 async fn test_transitive_diamond_dependencies() -> Result<()> {
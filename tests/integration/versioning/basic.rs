@@ -264,6 +264,40 @@ async fn test_install_with_commit_hash() {
     assert!(installed.contains("Initial version"));
 }
 
+#[tokio::test]
+async fn test_expect_commit_detects_moved_tag() {
+    test_config::init_test_env();
+    let project = TestProject::new().await.unwrap();
+    let source_repo = project.create_source_repo("versioned").await.unwrap();
+
+    let v1_commit = setup_git_repo_with_versions(&source_repo).await.unwrap();
+    let source_url = format!("file://{}", normalize_path_for_storage(&source_repo.path));
+
+    // The tag points at the expected commit
+    let manifest = format!(
+        r#"[sources]
+versioned = "{source_url}"
+
+[agents]
+pinned = {{ source = "versioned", path = "agents/example.md", version = "v1.0.0", expect_commit = "{}" }}
+"#,
+        &v1_commit[..12]
+    );
+    project.write_manifest(&manifest).await.unwrap();
+    let output = project.run_agpm(&["install"]).unwrap();
+    output.assert_success();
+
+    // Move the tag to another commit upstream
+    source_repo.git.move_tag("v1.0.0", "v2.0.0").unwrap();
+    let output = project.run_agpm(&["update"]).unwrap();
+    assert!(!output.success, "Expected update to fail after the tag moved");
+    assert!(
+        output.stderr.contains("but expect_commit is"),
+        "Expected expect_commit mismatch, got: {}",
+        output.stderr
+    );
+}
+
 #[tokio::test]
 async fn test_install_with_wildcard_version() {
     test_config::init_test_env();
@@ -73,6 +73,17 @@ async fn test_heavy_stress_500_dependencies() -> Result<()> {
                     flatten: None,
                     install: None,
                     template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                    env: None,
+                    transport: None,
+                    url: None,
+                    as_of: None,
+                    post_install: None,
+                    variants: None,
+                    strip_metadata: None,
+                    inline: None,
+                    expect_commit: None,
+                    enabled: None,
+                    git: None,
                 })),
             );
             total_agents += 1;
@@ -96,6 +107,7 @@ async fn test_heavy_stress_500_dependencies() -> Result<()> {
         cache.clone(),
         false,
         None,
+        None,
         Some(progress),
         false, // verbose
         None,  // old_lockfile
@@ -315,6 +327,17 @@ async fn test_heavy_stress_500_updates() -> Result<()> {
                     flatten: None,
                     install: None,
                     template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                    env: None,
+                    transport: None,
+                    url: None,
+                    as_of: None,
+                    post_install: None,
+                    variants: None,
+                    strip_metadata: None,
+                    inline: None,
+                    expect_commit: None,
+                    enabled: None,
+                    git: None,
                 })),
             );
             total_agents += 1;
@@ -338,6 +361,7 @@ async fn test_heavy_stress_500_updates() -> Result<()> {
         cache.clone(),
         false,
         None,
+        None,
         Some(progress),
         false, // verbose
         None,  // old_lockfile
@@ -379,6 +403,17 @@ async fn test_heavy_stress_500_updates() -> Result<()> {
                     flatten: None,
                     install: None,
                     template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                    env: None,
+                    transport: None,
+                    url: None,
+                    as_of: None,
+                    post_install: None,
+                    variants: None,
+                    strip_metadata: None,
+                    inline: None,
+                    expect_commit: None,
+                    enabled: None,
+                    git: None,
                 })),
             );
         }
@@ -402,6 +437,7 @@ async fn test_heavy_stress_500_updates() -> Result<()> {
         cache.clone(),
         false,
         None,
+        None,
         Some(progress2),
         false, // verbose
         None,  // old_lockfile
@@ -518,6 +554,17 @@ async fn test_mixed_repos_file_and_https() -> Result<()> {
                     flatten: None,
                     install: None,
                     template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                    env: None,
+                    transport: None,
+                    url: None,
+                    as_of: None,
+                    post_install: None,
+                    variants: None,
+                    strip_metadata: None,
+                    inline: None,
+                    expect_commit: None,
+                    enabled: None,
+                    git: None,
                 })),
             );
             total_resources += 1;
@@ -556,6 +603,17 @@ async fn test_mixed_repos_file_and_https() -> Result<()> {
                 flatten: None,
                 install: None,
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             })),
         );
         total_resources += 1;
@@ -581,6 +639,7 @@ async fn test_mixed_repos_file_and_https() -> Result<()> {
         cache.clone(),
         false,
         None,
+        None,
         Some(progress),
         false, // verbose
         None,  // old_lockfile
@@ -715,6 +774,17 @@ async fn test_community_repo_parallel_checkout_performance() -> Result<()> {
                 flatten: None,
                 install: None,
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             })),
         );
     }
@@ -740,6 +810,7 @@ async fn test_community_repo_parallel_checkout_performance() -> Result<()> {
         cache.clone(),
         false,
         None,
+        None,
         Some(progress),
         false, // verbose
         None,  // old_lockfile
@@ -891,6 +962,17 @@ async fn test_community_repo_500_dependencies() -> Result<()> {
                 flatten: None,
                 install: None,
                 template_vars: Some(serde_json::Value::Object(serde_json::Map::new())),
+                env: None,
+                transport: None,
+                url: None,
+                as_of: None,
+                post_install: None,
+                variants: None,
+                strip_metadata: None,
+                inline: None,
+                expect_commit: None,
+                enabled: None,
+                git: None,
             })),
         );
     }
@@ -911,6 +993,7 @@ async fn test_community_repo_500_dependencies() -> Result<()> {
         cache,
         false,
         None,
+        None,
         Some(progress),
         false, // verbose
         None,  // old_lockfile
@@ -41,6 +41,17 @@ fn create_test_manifest() -> agpm_cli::manifest::Manifest {
             command: None,
             args: None,
             dependencies: None,
+            env: None,
+            transport: None,
+            url: None,
+            as_of: None,
+            post_install: None,
+            variants: None,
+            strip_metadata: None,
+            inline: None,
+            expect_commit: None,
+            enabled: None,
+            git: None,
         })),
     );
 